@@ -8,33 +8,17 @@ SET search_path TO nebula_id, public;
 -- Create extensions
 CREATE EXTENSION IF NOT EXISTS "uuid-ossp" SCHEMA public;
 
--- Create enums
-DO $$ BEGIN
-    CREATE TYPE algorithm_type AS ENUM ('segment', 'snowflake', 'uuid_v7', 'uuid_v4');
-EXCEPTION
-    WHEN duplicate_object THEN null;
-END $$;
-
-DO $$ BEGIN
-    CREATE TYPE id_format AS ENUM ('numeric', 'prefixed', 'uuid');
-EXCEPTION
-    WHEN duplicate_object THEN null;
-END $$;
-
-DO $$ BEGIN
-    CREATE TYPE workspace_status AS ENUM ('active', 'inactive', 'suspended');
-EXCEPTION
-    WHEN duplicate_object THEN null;
-END $$;
+-- algorithm / format / status 列使用 VARCHAR 而非枚举类型，与实体的字符串绑定一致
 
 -- Workspaces table
 CREATE TABLE IF NOT EXISTS workspaces (
     id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
     name VARCHAR(255) NOT NULL UNIQUE,
     description TEXT,
-    status workspace_status DEFAULT 'active',
+    status VARCHAR(20) DEFAULT 'active',
     max_groups INT DEFAULT 100,
     max_biz_tags INT DEFAULT 1000,
+    strict_generation BOOLEAN NOT NULL DEFAULT true,
     created_at TIMESTAMP WITHOUT TIME ZONE DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMP WITHOUT TIME ZONE DEFAULT CURRENT_TIMESTAMP
 );
@@ -58,8 +42,8 @@ CREATE TABLE IF NOT EXISTS biz_tags (
     group_id UUID NOT NULL REFERENCES groups(id) ON DELETE CASCADE,
     name VARCHAR(255) NOT NULL,
     description TEXT,
-    algorithm algorithm_type DEFAULT 'segment',
-    format id_format DEFAULT 'numeric',
+    algorithm VARCHAR(20) DEFAULT 'segment',
+    format VARCHAR(20) DEFAULT 'numeric',
     prefix VARCHAR(50) DEFAULT '',
     base_step INT DEFAULT 1000,
     max_step INT DEFAULT 100000,
@@ -93,7 +77,12 @@ CREATE TABLE IF NOT EXISTS api_keys (
     expires_at TIMESTAMP WITHOUT TIME ZONE DEFAULT (CURRENT_TIMESTAMP + INTERVAL '30 days'),
     last_used_at TIMESTAMP WITHOUT TIME ZONE,
     created_at TIMESTAMP WITHOUT TIME ZONE DEFAULT CURRENT_TIMESTAMP,
-    updated_at TIMESTAMP WITHOUT TIME ZONE DEFAULT CURRENT_TIMESTAMP
+    updated_at TIMESTAMP WITHOUT TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    scopes TEXT,  -- Space-separated scopes (e.g. "generate:orders/* parse"); NULL = unrestricted
+    signing_key VARCHAR(64),  -- HMAC request-signing key derived from the secret (hex); NULL = signing unavailable
+    previous_secret_hash VARCHAR(255),  -- Hash of the secret replaced by the last rotation
+    previous_signing_key VARCHAR(64),  -- Signing key replaced by the last rotation (hex)
+    previous_secret_expires_at TIMESTAMP WITHOUT TIME ZONE  -- End of the rotation grace period; NULL = no grace
 );
 
 CREATE INDEX IF NOT EXISTS idx_api_keys_workspace ON api_keys(workspace_id);
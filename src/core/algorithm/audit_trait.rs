@@ -12,8 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Audit event model shared by every producer.
+//!
+//! 核心层（`DegradationManager` 等）与服务层（HTTP handler、审计中间件、配置热更新）
+//! 使用同一个 [`AuditEvent`] 和同一个 [`AuditLogger`] trait；`server::audit::AuditLogger`
+//! 只是其中一个实现。事件通过 [`AuditEvent::builder`] 构建，构建时从
+//! [`AuditContext`] 继承当前请求的调用方与请求 ID，使核心层事件也能关联到触发它的请求。
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -22,11 +32,15 @@ pub enum AuditEventType {
     IdGeneration,
     BatchGeneration,
     Authentication,
+    /// 已认证的 key 缺少操作所需的作用域
+    AuthorizationDenied,
     ConfigChange,
     DegradationEvent,
     RateLimitExceeded,
     HealthCheck,
     MetricsAccess,
+    /// 只读或未归类的 API 请求（审计中间件按路由归类后的兜底类型）
+    ApiAccess,
     // 业务管理事件
     WorkspaceCreated,
     WorkspaceUpdated,
@@ -41,6 +55,10 @@ pub enum AuditEventType {
     ApiKeyUpdated,
     ApiKeyDeleted,
     ApiKeyRegenerated,
+    /// 认证失败达到阈值后封禁 IP 或 key_id
+    AuthBanIssued,
+    /// 管理员解除封禁
+    AuthBanLifted,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -51,18 +69,92 @@ pub enum AuditResult {
     Unknown,
 }
 
+/// 生成审计事件 ID（M1 修复）。
+///
+/// 格式：`(unix_millis << 20) | (counter & 0xFFFFF)`
+/// - 高 44 位：Unix 毫秒时间戳，保证进程重启后 ID 单调递增、不冲突
+/// - 低 20 位：进程内计数器（最多 1M/ms，远超任何审计吞吐）
+///
+/// 这避免了原 `static COUNTER: AtomicU64` 在进程重启后从 0 开始导致 ID 冲突的问题。
+fn next_audit_event_id() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let unix_ms = Utc::now().timestamp_millis() as u64;
+    let c = COUNTER.fetch_add(1, Ordering::SeqCst) & 0xFFFFF;
+    (unix_ms << 20) | c
+}
+
+/// 当前请求的审计上下文（调用方与请求 ID）。
+///
+/// 服务层在认证通过后用 [`AuditContext::scope`] 包裹请求处理，作用域内构建的
+/// 事件自动带上 `user_id` 与 `request_id`，包括由请求间接触发的核心层事件
+/// （如管理 API 手动降级）。后台任务中没有上下文，事件不带调用方。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditContext {
+    /// 调用方标识（API key ID）
+    pub actor: Option<String>,
+    pub request_id: Option<String>,
+}
+
+tokio::task_local! {
+    static AUDIT_CONTEXT: AuditContext;
+}
+
+impl AuditContext {
+    /// 当前作用域的上下文；不在任何作用域内时返回 `None`
+    pub fn current() -> Option<AuditContext> {
+        AUDIT_CONTEXT.try_with(|ctx| ctx.clone()).ok()
+    }
+
+    /// 在该上下文中运行 `future`
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        AUDIT_CONTEXT.scope(self, future).await
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEvent {
+    pub id: u64,
+    pub timestamp: DateTime<Utc>,
     pub event_type: AuditEventType,
     pub workspace_id: Option<String>,
+    /// 调用方标识（API key ID）
+    pub user_id: Option<String>,
     pub action: String,
     pub resource: String,
     pub result: AuditResult,
     pub details: Option<serde_json::Value>,
-    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub client_ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub duration_ms: u64,
+    pub error_message: Option<String>,
+    /// 触发该事件的请求 ID（`x-request-id`），用于关联同一请求产生的多条事件
+    #[serde(default)]
+    pub request_id: Option<String>,
 }
 
 impl AuditEvent {
+    /// 开始构建事件；结果默认为 `Success`
+    pub fn builder(
+        event_type: AuditEventType,
+        action: impl Into<String>,
+        resource: impl Into<String>,
+    ) -> AuditEventBuilder {
+        AuditEventBuilder {
+            event_type,
+            action: action.into(),
+            resource: resource.into(),
+            result: AuditResult::Success,
+            workspace_id: None,
+            user_id: None,
+            details: None,
+            client_ip: None,
+            user_agent: None,
+            duration_ms: 0,
+            error_message: None,
+            request_id: None,
+        }
+    }
+
     pub fn new(
         event_type: AuditEventType,
         workspace_id: Option<String>,
@@ -70,70 +162,267 @@ impl AuditEvent {
         resource: String,
         result: AuditResult,
     ) -> Self {
-        Self {
-            event_type,
-            workspace_id,
-            action,
-            resource,
-            result,
-            details: None,
-            timestamp: chrono::Utc::now(),
-        }
+        let mut builder = Self::builder(event_type, action, resource).result(result);
+        builder.workspace_id = workspace_id;
+        builder.build()
     }
 
     pub fn with_details(mut self, details: serde_json::Value) -> Self {
         self.details = Some(details);
         self
     }
+
+    pub fn with_client_ip(mut self, ip: String) -> Self {
+        self.client_ip = Some(ip);
+        self
+    }
+
+    pub fn with_user_agent(mut self, ua: String) -> Self {
+        self.user_agent = Some(ua);
+        self
+    }
+
+    pub fn with_user_id(mut self, user_id: String) -> Self {
+        self.user_id = Some(user_id);
+        self
+    }
+
+    pub fn with_duration(mut self, ms: u64) -> Self {
+        self.duration_ms = ms;
+        self
+    }
+
+    pub fn with_error(mut self, error: String) -> Self {
+        self.error_message = Some(error);
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: String) -> Self {
+        self.request_id = Some(request_id);
+        self
+    }
+}
+
+/// [`AuditEvent`] 构建器。`build` 时分配事件 ID 与时间戳，未显式设置的
+/// `user_id` / `request_id` 取自当前 [`AuditContext`]。
+#[derive(Debug, Clone)]
+pub struct AuditEventBuilder {
+    event_type: AuditEventType,
+    action: String,
+    resource: String,
+    result: AuditResult,
+    workspace_id: Option<String>,
+    user_id: Option<String>,
+    details: Option<serde_json::Value>,
+    client_ip: Option<String>,
+    user_agent: Option<String>,
+    duration_ms: u64,
+    error_message: Option<String>,
+    request_id: Option<String>,
 }
 
+impl AuditEventBuilder {
+    pub fn result(mut self, result: AuditResult) -> Self {
+        self.result = result;
+        self
+    }
+
+    /// 按成功与否设置 `Success` / `Failure`
+    pub fn succeeded(self, success: bool) -> Self {
+        self.result(if success {
+            AuditResult::Success
+        } else {
+            AuditResult::Failure
+        })
+    }
+
+    pub fn workspace(mut self, workspace_id: impl Into<String>) -> Self {
+        self.workspace_id = Some(workspace_id.into());
+        self
+    }
+
+    pub fn actor(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    pub fn details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn client_ip(mut self, ip: impl Into<String>) -> Self {
+        self.client_ip = Some(ip.into());
+        self
+    }
+
+    pub fn user_agent(mut self, ua: impl Into<String>) -> Self {
+        self.user_agent = Some(ua.into());
+        self
+    }
+
+    pub fn duration_ms(mut self, ms: u64) -> Self {
+        self.duration_ms = ms;
+        self
+    }
+
+    /// 记录错误信息；不改变 `result`
+    pub fn error(mut self, error: impl Into<String>) -> Self {
+        self.error_message = Some(error.into());
+        self
+    }
+
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    pub fn build(self) -> AuditEvent {
+        let context = AuditContext::current().unwrap_or_default();
+        AuditEvent {
+            id: next_audit_event_id(),
+            timestamp: Utc::now(),
+            event_type: self.event_type,
+            workspace_id: self.workspace_id,
+            user_id: self.user_id.or(context.actor),
+            action: self.action,
+            resource: self.resource,
+            result: self.result,
+            details: self.details,
+            client_ip: self.client_ip,
+            user_agent: self.user_agent,
+            duration_ms: self.duration_ms,
+            error_message: self.error_message,
+            request_id: self.request_id.or(context.request_id),
+        }
+    }
+}
+
+/// 降级事件的结果：进入 Critical 为失败，回到 Normal 为成功，其余为部分可用
+fn degradation_result(current_state: &str) -> AuditResult {
+    match current_state {
+        "Critical" => AuditResult::Failure,
+        "Normal" => AuditResult::Success,
+        _ => AuditResult::Partial,
+    }
+}
+
+/// 管理类（CRUD）事件的公共形态；`user_id` 为 `None` 时取自 [`AuditContext`]
+fn management_event(
+    event_type: AuditEventType,
+    workspace_id: Option<String>,
+    action: &str,
+    resource: String,
+    user_id: Option<String>,
+    client_ip: Option<String>,
+) -> AuditEvent {
+    let mut builder = AuditEvent::builder(event_type, action, resource);
+    builder.workspace_id = workspace_id;
+    builder.user_id = user_id;
+    builder.client_ip = client_ip;
+    builder.build()
+}
+
+/// 审计事件的唯一入口：所有产生审计事件的组件都通过该 trait 记录。
+///
+/// 实现者只需提供 [`AuditLogger::log`]；各类事件的便捷方法以默认实现提供，
+/// 保证同一类事件无论由哪一层产生，`action` / `resource` / `details` 形态一致。
 #[async_trait]
 pub trait AuditLogger: Send + Sync {
     async fn log(&self, event: AuditEvent);
 
+    #[allow(clippy::too_many_arguments)]
     async fn log_id_generation(
         &self,
-        workspace_id: Option<String>,
-        action: String,
-        algorithm_type: String,
+        workspace_id: String,
+        biz_tag: String,
         id: String,
+        algorithm: String,
+        client_ip: Option<String>,
+        duration_ms: u64,
         success: bool,
+        error_message: Option<String>,
     ) {
-        let event = AuditEvent::new(
+        let mut builder = AuditEvent::builder(
             AuditEventType::IdGeneration,
-            workspace_id,
-            action,
-            format!("id:{}", id),
-            if success {
-                AuditResult::Success
-            } else {
-                AuditResult::Failure
-            },
+            "generate_id",
+            format!("biz_tag:{}", biz_tag),
         )
-        .with_details(serde_json::json!({
-            "algorithm_type": algorithm_type
-        }));
-        self.log(event).await;
+        .workspace(workspace_id)
+        .succeeded(success)
+        .details(serde_json::json!({
+            "generated_id": id,
+            "algorithm": algorithm
+        }))
+        .duration_ms(duration_ms);
+        builder.client_ip = client_ip;
+        builder.error_message = error_message;
+        self.log(builder.build()).await;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn log_batch_generation(
+        &self,
+        workspace_id: String,
+        biz_tag: String,
+        size: usize,
+        client_ip: Option<String>,
+        duration_ms: u64,
+        success: bool,
+        error_message: Option<String>,
+    ) {
+        let mut builder = AuditEvent::builder(
+            AuditEventType::BatchGeneration,
+            "batch_generate_ids",
+            format!("biz_tag:{} size:{}", biz_tag, size),
+        )
+        .workspace(workspace_id)
+        .succeeded(success)
+        .details(serde_json::json!({
+            "batch_size": size,
+            "biz_tag": biz_tag
+        }))
+        .duration_ms(duration_ms);
+        builder.client_ip = client_ip;
+        builder.error_message = error_message;
+        self.log(builder.build()).await;
+    }
+
+    async fn log_auth_event(
+        &self,
+        workspace_id: Option<String>,
+        action: String,
+        success: bool,
+        client_ip: Option<String>,
+        error_message: Option<String>,
+    ) {
+        let mut builder =
+            AuditEvent::builder(AuditEventType::Authentication, action, "authentication")
+                .succeeded(success);
+        builder.workspace_id = workspace_id;
+        builder.client_ip = client_ip;
+        builder.error_message = error_message;
+        self.log(builder.build()).await;
     }
 
     async fn log_config_change(
         &self,
         workspace_id: Option<String>,
         action: String,
-        resource: String,
+        config_type: String,
         details: serde_json::Value,
     ) {
-        let event = AuditEvent::new(
+        let mut builder = AuditEvent::builder(
             AuditEventType::ConfigChange,
-            workspace_id,
             action,
-            resource,
-            AuditResult::Success,
+            format!("config:{}", config_type),
         )
-        .with_details(details);
-        self.log(event).await;
+        .details(details);
+        builder.workspace_id = workspace_id;
+        self.log(builder.build()).await;
     }
 
+    /// `current_state` 为 `DegradationState` 的 Debug 形式（如 `Normal`、`Degraded(Segment)`）
     async fn log_degradation_event(
         &self,
         workspace_id: Option<String>,
@@ -143,26 +432,461 @@ pub trait AuditLogger: Send + Sync {
         current_state: String,
         details: serde_json::Value,
     ) {
-        let result = match current_state.as_str() {
-            "Critical" => AuditResult::Failure,
-            "Normal" => AuditResult::Success,
-            _ => AuditResult::Partial,
-        };
-        let event = AuditEvent::new(
+        let mut builder = AuditEvent::builder(
             AuditEventType::DegradationEvent,
-            workspace_id,
             action,
             format!("algorithm:{}", algorithm_type),
-            result,
         )
-        .with_details(serde_json::json!({
+        .result(degradation_result(&current_state))
+        .details(serde_json::json!({
             "previous_state": previous_state,
             "current_state": current_state,
             "algorithm_type": algorithm_type,
             "details": details
         }));
-        self.log(event).await;
+        builder.workspace_id = workspace_id;
+        self.log(builder.build()).await;
+    }
+
+    /// 作用域拒绝：`resource` 为被拒绝的端点或 RPC，`required_scope` 写入 details
+    async fn log_scope_denied(
+        &self,
+        workspace_id: Option<String>,
+        resource: String,
+        required_scope: String,
+        client_ip: Option<String>,
+    ) {
+        let mut builder = AuditEvent::builder(
+            AuditEventType::AuthorizationDenied,
+            "scope_denied",
+            resource,
+        )
+        .result(AuditResult::Failure)
+        .details(serde_json::json!({ "required_scope": required_scope }))
+        .error(format!("missing scope '{}'", required_scope));
+        builder.workspace_id = workspace_id;
+        builder.client_ip = client_ip;
+        self.log(builder.build()).await;
+    }
+
+    async fn log_rate_limit_exceeded(
+        &self,
+        workspace_id: Option<String>,
+        client_ip: String,
+        endpoint: String,
+    ) {
+        let mut builder = AuditEvent::builder(
+            AuditEventType::RateLimitExceeded,
+            "rate_limit_exceeded",
+            endpoint,
+        )
+        .result(AuditResult::Failure)
+        .client_ip(client_ip)
+        .error("Rate limit exceeded");
+        builder.workspace_id = workspace_id;
+        self.log(builder.build()).await;
+    }
+
+    async fn log_workspace_created(
+        &self,
+        workspace_id: String,
+        workspace_name: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::WorkspaceCreated,
+            Some(workspace_id),
+            "create_workspace",
+            format!("workspace:{}", workspace_name),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_workspace_updated(
+        &self,
+        workspace_id: String,
+        workspace_name: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::WorkspaceUpdated,
+            Some(workspace_id),
+            "update_workspace",
+            format!("workspace:{}", workspace_name),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_workspace_deleted(
+        &self,
+        workspace_id: String,
+        workspace_name: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::WorkspaceDeleted,
+            Some(workspace_id),
+            "delete_workspace",
+            format!("workspace:{}", workspace_name),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_group_created(
+        &self,
+        workspace_id: String,
+        group_id: String,
+        group_name: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::GroupCreated,
+            Some(workspace_id),
+            "create_group",
+            format!("group:{}:{}", group_id, group_name),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_group_updated(
+        &self,
+        workspace_id: String,
+        group_id: String,
+        group_name: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::GroupUpdated,
+            Some(workspace_id),
+            "update_group",
+            format!("group:{}:{}", group_id, group_name),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_group_deleted(
+        &self,
+        workspace_id: String,
+        group_id: String,
+        group_name: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::GroupDeleted,
+            Some(workspace_id),
+            "delete_group",
+            format!("group:{}:{}", group_id, group_name),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_biz_tag_created(
+        &self,
+        workspace_id: String,
+        biz_tag_id: String,
+        biz_tag_name: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::BizTagCreated,
+            Some(workspace_id),
+            "create_biz_tag",
+            format!("biz_tag:{}:{}", biz_tag_id, biz_tag_name),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_biz_tag_updated(
+        &self,
+        workspace_id: String,
+        biz_tag_id: String,
+        biz_tag_name: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::BizTagUpdated,
+            Some(workspace_id),
+            "update_biz_tag",
+            format!("biz_tag:{}:{}", biz_tag_id, biz_tag_name),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_biz_tag_deleted(
+        &self,
+        workspace_id: String,
+        biz_tag_id: String,
+        biz_tag_name: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::BizTagDeleted,
+            Some(workspace_id),
+            "delete_biz_tag",
+            format!("biz_tag:{}:{}", biz_tag_id, biz_tag_name),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_api_key_created(
+        &self,
+        workspace_id: Option<String>,
+        key_id: String,
+        key_role: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::ApiKeyCreated,
+            workspace_id,
+            "create_api_key",
+            format!("api_key:{}:{}", key_id, key_role),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_api_key_updated(
+        &self,
+        workspace_id: Option<String>,
+        key_id: String,
+        key_role: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::ApiKeyUpdated,
+            workspace_id,
+            "update_api_key",
+            format!("api_key:{}:{}", key_id, key_role),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_api_key_deleted(
+        &self,
+        workspace_id: Option<String>,
+        key_id: String,
+        key_role: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::ApiKeyDeleted,
+            workspace_id,
+            "delete_api_key",
+            format!("api_key:{}:{}", key_id, key_role),
+            user_id,
+            client_ip,
+        ))
+        .await;
+    }
+
+    async fn log_api_key_regenerated(
+        &self,
+        workspace_id: String,
+        key_id: String,
+        key_role: String,
+        user_id: Option<String>,
+        client_ip: Option<String>,
+    ) {
+        self.log(management_event(
+            AuditEventType::ApiKeyRegenerated,
+            Some(workspace_id),
+            "regenerate_api_key",
+            format!("api_key:{}:{}", key_id, key_role),
+            user_id,
+            client_ip,
+        ))
+        .await;
     }
 }
 
 pub type DynAuditLogger = Arc<dyn AuditLogger>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<AuditEvent>>);
+
+    #[async_trait]
+    impl AuditLogger for Recorder {
+        async fn log(&self, event: AuditEvent) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    impl Recorder {
+        fn last(&self) -> AuditEvent {
+            self.0
+                .lock()
+                .unwrap()
+                .last()
+                .cloned()
+                .expect("event recorded")
+        }
+    }
+
+    fn context() -> AuditContext {
+        AuditContext {
+            actor: Some("key-ctx".to_string()),
+            request_id: Some("req-ctx".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_next_audit_event_id_generates_unique_ids() {
+        let id1 = next_audit_event_id();
+        let id2 = next_audit_event_id();
+        let id3 = next_audit_event_id();
+        assert_ne!(id1, id2);
+        assert_ne!(id2, id3);
+        assert_ne!(id1, id3);
+    }
+
+    #[test]
+    fn test_next_audit_event_id_uses_high_44_bits_for_timestamp() {
+        // Low 20 bits are counter; high 44 bits should be >= some recent unix_ms
+        let id = next_audit_event_id();
+        let high_44 = id >> 20;
+        // Unix millis for 2024-01-01 is around 1704067200000
+        assert!(high_44 >= 1704067200000);
+    }
+
+    #[test]
+    fn test_builder_defaults_outside_context() {
+        let event = AuditEvent::builder(AuditEventType::HealthCheck, "check", "health").build();
+        assert_eq!(event.result, AuditResult::Success);
+        assert!(event.workspace_id.is_none());
+        assert!(event.user_id.is_none());
+        assert!(event.request_id.is_none());
+        assert!(event.client_ip.is_none());
+        assert_eq!(event.duration_ms, 0);
+    }
+
+    #[tokio::test]
+    async fn test_builder_inherits_context_unless_set_explicitly() {
+        let (inherited, explicit) = context()
+            .scope(async {
+                (
+                    AuditEvent::builder(AuditEventType::ConfigChange, "update", "config:x").build(),
+                    AuditEvent::builder(AuditEventType::ConfigChange, "update", "config:x")
+                        .actor("key-explicit")
+                        .request_id("req-explicit")
+                        .build(),
+                )
+            })
+            .await;
+
+        assert_eq!(inherited.user_id.as_deref(), Some("key-ctx"));
+        assert_eq!(inherited.request_id.as_deref(), Some("req-ctx"));
+        assert_eq!(explicit.user_id.as_deref(), Some("key-explicit"));
+        assert_eq!(explicit.request_id.as_deref(), Some("req-explicit"));
+    }
+
+    #[test]
+    fn test_builder_succeeded_and_error() {
+        let event = AuditEvent::builder(AuditEventType::Authentication, "login", "authentication")
+            .succeeded(false)
+            .error("bad key")
+            .build();
+        assert_eq!(event.result, AuditResult::Failure);
+        assert_eq!(event.error_message.as_deref(), Some("bad key"));
+    }
+
+    #[test]
+    fn test_degradation_result_mapping() {
+        assert_eq!(degradation_result("Critical"), AuditResult::Failure);
+        assert_eq!(degradation_result("Normal"), AuditResult::Success);
+        assert_eq!(degradation_result("Degraded"), AuditResult::Partial);
+    }
+
+    #[tokio::test]
+    async fn test_helpers_share_event_shape_and_read_context() {
+        let recorder = Recorder::default();
+
+        recorder
+            .log_biz_tag_deleted(
+                "ws-1".to_string(),
+                "bt-1".to_string(),
+                "order".to_string(),
+                None,
+                Some("10.0.0.1".to_string()),
+            )
+            .await;
+        let outside = recorder.last();
+        assert_eq!(outside.event_type, AuditEventType::BizTagDeleted);
+        assert_eq!(outside.resource, "biz_tag:bt-1:order");
+        assert_eq!(outside.workspace_id.as_deref(), Some("ws-1"));
+        assert!(outside.user_id.is_none());
+
+        context()
+            .scope(recorder.log_config_change(
+                None,
+                "update".to_string(),
+                "rate_limit".to_string(),
+                serde_json::json!({"rps": 10}),
+            ))
+            .await;
+        let inside = recorder.last();
+        assert_eq!(inside.resource, "config:rate_limit");
+        assert_eq!(inside.user_id.as_deref(), Some("key-ctx"));
+        assert_eq!(inside.request_id.as_deref(), Some("req-ctx"));
+    }
+
+    #[tokio::test]
+    async fn test_scope_denied_event_records_required_scope() {
+        let recorder = Recorder::default();
+
+        context()
+            .scope(recorder.log_scope_denied(
+                Some("ws-1".to_string()),
+                "POST /biz-tags".to_string(),
+                "biz_tags:write".to_string(),
+                Some("10.0.0.1".to_string()),
+            ))
+            .await;
+        let event = recorder.last();
+        assert_eq!(event.event_type, AuditEventType::AuthorizationDenied);
+        assert_eq!(event.result, AuditResult::Failure);
+        assert_eq!(event.resource, "POST /biz-tags");
+        assert_eq!(event.user_id.as_deref(), Some("key-ctx"));
+        assert_eq!(
+            event.details,
+            Some(serde_json::json!({"required_scope": "biz_tags:write"}))
+        );
+    }
+}
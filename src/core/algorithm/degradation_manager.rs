@@ -35,11 +35,11 @@
 #![allow(dead_code)]
 
 use crate::core::algorithm::{audit_trait::DynAuditLogger, HealthStatus, IdAlgorithm};
-use crate::core::AlgorithmType;
+use crate::core::{AlgorithmType, CoreError, Result};
 use arc_swap::ArcSwap;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -301,6 +301,9 @@ pub struct DegradationManager {
     current_state: RwLock<DegradationState>,
     primary_algorithm: RwLock<AlgorithmType>,
     fallback_chain: RwLock<Vec<AlgorithmType>>,
+    /// 运维手动降级的算法（粘性）：路由层据此把流量切离这些算法，
+    /// 只有 `manual_recover` 能解除，自动恢复不会清除该标记。
+    manual_overrides: ArcSwap<HashSet<AlgorithmType>>,
     running: AtomicBool,
     last_check: RwLock<Instant>,
     audit_logger: Option<DynAuditLogger>,
@@ -321,6 +324,7 @@ impl DegradationManager {
             current_state: RwLock::new(DegradationState::Normal),
             primary_algorithm: RwLock::new(AlgorithmType::Segment),
             fallback_chain: RwLock::new(vec![]),
+            manual_overrides: ArcSwap::from_pointee(HashSet::new()),
             running: AtomicBool::new(false),
             last_check: RwLock::new(Instant::now()),
             audit_logger,
@@ -601,28 +605,43 @@ impl DegradationManager {
     }
 
     pub async fn determine_effective_algorithm(&self) -> DegradationState {
+        self.evaluate_state()
+    }
+
+    /// `determine_effective_algorithm` 的同步实现，供手动降级/恢复等同步入口复用。
+    /// 手动降级的算法即使已自动恢复，也视为不可用。
+    fn evaluate_state(&self) -> DegradationState {
         let primary = *self.primary_algorithm.read();
         let chain = self.fallback_chain.read().clone();
 
         let health_states = self.health_states.load_full();
+        let overrides = self.manual_overrides.load();
+        let available = |alg: &AlgorithmType| {
+            !overrides.contains(alg)
+                && health_states
+                    .get(alg)
+                    .is_some_and(|state| !state.is_degraded.load(Ordering::SeqCst))
+        };
 
-        if let Some(state) = health_states.get(&primary) {
-            if !state.is_degraded.load(Ordering::SeqCst) {
-                return DegradationState::Normal;
-            }
+        if available(&primary) {
+            return DegradationState::Normal;
         }
 
         for fallback in chain {
-            if let Some(state) = health_states.get(&fallback) {
-                if !state.is_degraded.load(Ordering::SeqCst) {
-                    return DegradationState::Degraded(fallback);
-                }
+            if available(&fallback) {
+                return DegradationState::Degraded(fallback);
             }
         }
 
         DegradationState::Critical
     }
 
+    fn refresh_current_state(&self) -> DegradationState {
+        let new_state = self.evaluate_state();
+        *self.current_state.write() = new_state.clone();
+        new_state
+    }
+
     pub async fn get_effective_algorithm(&self) -> AlgorithmType {
         let state = self.current_state.read();
         match &*state {
@@ -653,6 +672,7 @@ impl DegradationManager {
                 consecutive_successes: state.consecutive_successes.load(Ordering::SeqCst),
                 is_degraded: state.is_degraded.load(Ordering::SeqCst),
                 is_healthy: state.current_state.load(Ordering::SeqCst),
+                circuit_breaker_state: state.get_circuit_breaker_state(),
             })
     }
 
@@ -666,6 +686,7 @@ impl DegradationManager {
                 consecutive_successes: state.consecutive_successes.load(Ordering::SeqCst),
                 is_degraded: state.is_degraded.load(Ordering::SeqCst),
                 is_healthy: state.current_state.load(Ordering::SeqCst),
+                circuit_breaker_state: state.get_circuit_breaker_state(),
             })
             .collect()
     }
@@ -674,6 +695,15 @@ impl DegradationManager {
         self.current_state.read().clone()
     }
 
+    pub fn get_primary_algorithm(&self) -> AlgorithmType {
+        *self.primary_algorithm.read()
+    }
+
+    /// 距上次 `check_all_health` 完成的时间（用于健康报告的 last check）
+    pub fn last_check_elapsed(&self) -> Duration {
+        self.last_check.read().elapsed()
+    }
+
     pub fn manual_degrade(&self, alg_type: AlgorithmType) {
         // 先尝试在已有 state 上标记（无锁读路径）
         let existing = self.health_states.load().get(&alg_type).cloned();
@@ -689,6 +719,12 @@ impl DegradationManager {
                 Arc::new(new)
             });
         }
+        self.manual_overrides.rcu(|old| {
+            let mut new: HashSet<_> = (**old).clone();
+            new.insert(alg_type);
+            Arc::new(new)
+        });
+        self.refresh_current_state();
         info!(
             alg_type = ?alg_type,
             "{}",
@@ -697,8 +733,14 @@ impl DegradationManager {
     }
 
     pub fn manual_recover(&self, alg_type: AlgorithmType) {
+        self.manual_overrides.rcu(|old| {
+            let mut new: HashSet<_> = (**old).clone();
+            new.remove(&alg_type);
+            Arc::new(new)
+        });
         if let Some(state) = self.health_states.load().get(&alg_type) {
             state.reset();
+            self.refresh_current_state();
             info!(
                 alg_type = ?alg_type,
                 "{}",
@@ -707,6 +749,167 @@ impl DegradationManager {
         }
     }
 
+    pub fn is_manually_degraded(&self, alg_type: AlgorithmType) -> bool {
+        self.manual_overrides.load().contains(&alg_type)
+    }
+
+    pub fn get_manual_overrides(&self) -> Vec<AlgorithmType> {
+        self.manual_overrides.load().iter().copied().collect()
+    }
+
+    pub fn get_fallback_chain(&self) -> Vec<AlgorithmType> {
+        self.fallback_chain.read().clone()
+    }
+
+    /// 路由实际使用的降级链：配置链中剔除被手动降级的算法。
+    pub fn routable_fallback_chain(&self) -> Vec<AlgorithmType> {
+        let overrides = self.manual_overrides.load();
+        self.fallback_chain
+            .read()
+            .iter()
+            .filter(|alg| !overrides.contains(alg))
+            .copied()
+            .collect()
+    }
+
+    /// 校验运行时提交的降级链：算法必须已注册、不能重复、不能包含主算法。
+    pub fn validate_fallback_chain(&self, chain: &[AlgorithmType]) -> Result<()> {
+        let primary = *self.primary_algorithm.read();
+        let health_states = self.health_states.load();
+        let mut seen = HashSet::new();
+        for alg in chain {
+            if *alg == primary {
+                return Err(CoreError::InvalidInput(format!(
+                    "Fallback chain must not contain the primary algorithm {}",
+                    alg
+                )));
+            }
+            if !health_states.contains_key(alg) {
+                return Err(CoreError::InvalidInput(format!(
+                    "Algorithm {} is not registered",
+                    alg
+                )));
+            }
+            if !seen.insert(*alg) {
+                return Err(CoreError::InvalidInput(format!(
+                    "Algorithm {} appears more than once in the fallback chain",
+                    alg
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// 管理 API 入口：手动降级已注册的算法，并记录 `DegradationEvent` 审计。
+    pub async fn admin_degrade(
+        &self,
+        alg_type: AlgorithmType,
+        reason: Option<String>,
+    ) -> Result<DegradationState> {
+        self.ensure_registered(alg_type)?;
+        let previous_state = self.get_current_state();
+        self.manual_degrade(alg_type);
+        let current_state = self.get_current_state();
+        self.audit_manual_action(
+            "manual_degrade",
+            alg_type,
+            &previous_state,
+            &current_state,
+            serde_json::json!({ "manual": true, "reason": reason }),
+        )
+        .await;
+        Ok(current_state)
+    }
+
+    /// 管理 API 入口：解除手动降级并重置熔断器，记录 `DegradationEvent` 审计。
+    pub async fn admin_recover(
+        &self,
+        alg_type: AlgorithmType,
+        reason: Option<String>,
+    ) -> Result<DegradationState> {
+        self.ensure_registered(alg_type)?;
+        let previous_state = self.get_current_state();
+        self.manual_recover(alg_type);
+        let current_state = self.get_current_state();
+        self.audit_manual_action(
+            "manual_recover",
+            alg_type,
+            &previous_state,
+            &current_state,
+            serde_json::json!({ "manual": true, "reason": reason }),
+        )
+        .await;
+        Ok(current_state)
+    }
+
+    /// 管理 API 入口：运行时替换降级链，记录 `DegradationEvent` 审计。
+    pub async fn admin_set_fallback_chain(
+        &self,
+        chain: Vec<AlgorithmType>,
+        reason: Option<String>,
+    ) -> Result<DegradationState> {
+        self.validate_fallback_chain(&chain)?;
+        let previous_state = self.get_current_state();
+        let previous_chain = self.get_fallback_chain();
+        self.set_fallback_chain(chain.clone());
+        let current_state = self.refresh_current_state();
+        let primary = self.get_primary_algorithm();
+        self.audit_manual_action(
+            "fallback_chain_update",
+            primary,
+            &previous_state,
+            &current_state,
+            serde_json::json!({
+                "manual": true,
+                "reason": reason,
+                "previous_chain": previous_chain.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+                "fallback_chain": chain.iter().map(|a| a.to_string()).collect::<Vec<_>>(),
+            }),
+        )
+        .await;
+        Ok(current_state)
+    }
+
+    fn ensure_registered(&self, alg_type: AlgorithmType) -> Result<()> {
+        if self.health_states.load().contains_key(&alg_type) {
+            Ok(())
+        } else {
+            Err(CoreError::NotFound(format!(
+                "Algorithm {} is not registered",
+                alg_type
+            )))
+        }
+    }
+
+    async fn audit_manual_action(
+        &self,
+        action: &str,
+        alg_type: AlgorithmType,
+        previous_state: &DegradationState,
+        current_state: &DegradationState,
+        details: serde_json::Value,
+    ) {
+        info!(
+            action = action,
+            alg_type = ?alg_type,
+            previous_state = ?previous_state,
+            current_state = ?current_state,
+            "Manual degradation control applied"
+        );
+        if let Some(ref logger) = self.audit_logger {
+            logger
+                .log_degradation_event(
+                    None,
+                    action.to_string(),
+                    format!("{:?}", alg_type),
+                    format!("{:?}", previous_state),
+                    format!("{:?}", current_state),
+                    details,
+                )
+                .await;
+        }
+    }
+
     pub fn update_config(&mut self, config: DegradationConfig) {
         self.config = config.clone();
         info!(
@@ -812,6 +1015,7 @@ pub struct AlgorithmHealthStateInfo {
     pub consecutive_successes: u8,
     pub is_degraded: bool,
     pub is_healthy: bool,
+    pub circuit_breaker_state: CircuitBreakerState,
 }
 
 pub fn default_degradation_config() -> DegradationConfig {
@@ -1415,6 +1619,144 @@ mod tests {
         assert!(manager.get_all_states().is_empty());
     }
 
+    fn build_registered_manager(logger: Option<DynAuditLogger>) -> DegradationManager {
+        let manager = DegradationManager::new(None, logger);
+        manager.set_primary_algorithm(AlgorithmType::Segment);
+        manager.set_fallback_chain(vec![AlgorithmType::Snowflake, AlgorithmType::UuidV7]);
+        for alg_type in [
+            AlgorithmType::Segment,
+            AlgorithmType::Snowflake,
+            AlgorithmType::UuidV7,
+        ] {
+            manager.register_algorithm(
+                alg_type,
+                Arc::new(MockIdAlgorithm::new(alg_type)) as Arc<dyn IdAlgorithm>,
+            );
+        }
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_manual_override_survives_auto_recovery_until_manual_recover() {
+        let manager = build_registered_manager(None);
+
+        manager.manual_degrade(AlgorithmType::Snowflake);
+        assert!(manager.is_manually_degraded(AlgorithmType::Snowflake));
+        assert_eq!(
+            manager.routable_fallback_chain(),
+            vec![AlgorithmType::UuidV7]
+        );
+
+        // 自动恢复只清除健康标记，不解除手动降级
+        for _ in 0..DEFAULT_RECOVERY_THRESHOLD {
+            manager
+                .record_generation_result(AlgorithmType::Snowflake, true)
+                .await;
+        }
+        assert!(manager.is_manually_degraded(AlgorithmType::Snowflake));
+        assert_eq!(
+            manager.routable_fallback_chain(),
+            vec![AlgorithmType::UuidV7]
+        );
+
+        manager.manual_recover(AlgorithmType::Snowflake);
+        assert!(!manager.is_manually_degraded(AlgorithmType::Snowflake));
+        assert_eq!(
+            manager.routable_fallback_chain(),
+            vec![AlgorithmType::Snowflake, AlgorithmType::UuidV7]
+        );
+    }
+
+    #[test]
+    fn test_manual_degrade_and_recover_update_current_state() {
+        let manager = build_registered_manager(None);
+
+        manager.manual_degrade(AlgorithmType::Segment);
+        assert_eq!(
+            manager.get_current_state(),
+            DegradationState::Degraded(AlgorithmType::Snowflake)
+        );
+
+        manager.manual_recover(AlgorithmType::Segment);
+        assert_eq!(manager.get_current_state(), DegradationState::Normal);
+    }
+
+    #[tokio::test]
+    async fn test_admin_degrade_and_recover_record_audit_events() {
+        let logger = Arc::new(CountingAuditLogger::default());
+        let manager = build_registered_manager(Some(logger.clone() as DynAuditLogger));
+
+        let state = manager
+            .admin_degrade(AlgorithmType::Segment, Some("incident".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(state, DegradationState::Degraded(AlgorithmType::Snowflake));
+        let state = manager
+            .admin_recover(AlgorithmType::Segment, None)
+            .await
+            .unwrap();
+        assert_eq!(state, DegradationState::Normal);
+
+        let events = logger.events.lock().unwrap();
+        let actions: Vec<_> = events
+            .iter()
+            .filter(|e| e.event_type == AuditEventType::DegradationEvent)
+            .map(|e| e.action.clone())
+            .collect();
+        assert_eq!(actions, vec!["manual_degrade", "manual_recover"]);
+    }
+
+    #[tokio::test]
+    async fn test_admin_degrade_unregistered_algorithm_is_rejected() {
+        let manager = build_registered_manager(None);
+        let result = manager.admin_degrade(AlgorithmType::UuidV4, None).await;
+        assert!(matches!(result, Err(CoreError::NotFound(_))));
+        assert!(!manager.is_manually_degraded(AlgorithmType::UuidV4));
+    }
+
+    #[tokio::test]
+    async fn test_admin_set_fallback_chain_replaces_chain_and_audits() {
+        let logger = Arc::new(CountingAuditLogger::default());
+        let manager = build_registered_manager(Some(logger.clone() as DynAuditLogger));
+        manager.manual_degrade(AlgorithmType::Segment);
+
+        let state = manager
+            .admin_set_fallback_chain(vec![AlgorithmType::UuidV7, AlgorithmType::Snowflake], None)
+            .await
+            .unwrap();
+        assert_eq!(state, DegradationState::Degraded(AlgorithmType::UuidV7));
+        assert_eq!(
+            manager.get_fallback_chain(),
+            vec![AlgorithmType::UuidV7, AlgorithmType::Snowflake]
+        );
+        assert!(logger
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|e| e.action == "fallback_chain_update"));
+    }
+
+    #[test]
+    fn test_validate_fallback_chain_rejects_invalid_chains() {
+        let manager = build_registered_manager(None);
+        // 包含主算法
+        assert!(manager
+            .validate_fallback_chain(&[AlgorithmType::Segment])
+            .is_err());
+        // 未注册
+        assert!(manager
+            .validate_fallback_chain(&[AlgorithmType::UuidV4])
+            .is_err());
+        // 重复
+        assert!(manager
+            .validate_fallback_chain(&[AlgorithmType::Snowflake, AlgorithmType::Snowflake])
+            .is_err());
+        assert!(manager
+            .validate_fallback_chain(&[AlgorithmType::UuidV7])
+            .is_ok());
+    }
+
     #[tokio::test]
     async fn test_record_generation_result_unknown_alg_is_no_op() {
         let manager = DegradationManager::new(None, None);
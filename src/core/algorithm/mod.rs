@@ -25,7 +25,10 @@ pub use traits::*;
 
 pub use router::AlgorithmRouter;
 
-pub use audit_trait::{AuditEvent, AuditEventType, AuditLogger, AuditResult, DynAuditLogger};
+pub use audit_trait::{
+    AuditContext, AuditEvent, AuditEventBuilder, AuditEventType, AuditLogger, AuditResult,
+    DynAuditLogger,
+};
 
 pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitBreakerState};
 
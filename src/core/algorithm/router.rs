@@ -91,6 +91,10 @@ impl IdGenerator for AlgorithmRouter {
         }
     }
 
+    async fn algorithm_health(&self) -> Vec<(AlgorithmType, HealthStatus)> {
+        AlgorithmRouter::health_check(self).await
+    }
+
     async fn get_primary_algorithm(&self) -> String {
         format!("{:?}", self.config.algorithm.get_default_algorithm())
     }
@@ -125,13 +129,28 @@ impl IdGenerator for AlgorithmRouter {
     }
 }
 
+/// 降级原因：主算法生成失败后切换到降级链
+const FALLBACK_PRIMARY_FAILED: &str = "primary_failed";
+/// 降级原因：主算法未注册/不可用，直接走降级链
+const FALLBACK_PRIMARY_UNAVAILABLE: &str = "primary_unavailable";
+/// 降级原因：主算法被运维手动降级（管理 API），直接走降级链
+const FALLBACK_MANUALLY_DEGRADED: &str = "manually_degraded";
+
+/// 在当前 `id.generate` / `id.batch_generate` span 上标注降级决策，
+/// 导出到 OTLP 后可按 `fallback` 属性检索发生降级的请求。
+fn record_fallback_decision(fallback: AlgorithmType, reason: &'static str) {
+    let span = tracing::Span::current();
+    span.record("fallback", tracing::field::display(fallback));
+    span.record("fallback_reason", reason);
+}
+
 pub struct AlgorithmRouter {
     config: Config,
     algorithms: Arc<ArcSwap<HashMap<AlgorithmType, Arc<dyn IdAlgorithm>>>>,
-    fallback_chain: SmallVec<[AlgorithmType; 8]>,
     current_algorithm: Arc<ArcSwap<HashMap<String, AlgorithmType>>>,
     degradation_manager: Arc<DegradationManager>,
     cpu_monitor: Option<Arc<crate::core::algorithm::segment::CpuMonitor>>,
+    usage_tracker: Option<Arc<crate::core::monitoring::UsageTracker>>,
     #[cfg(feature = "etcd")]
     etcd_health_monitor: Option<Arc<EtcdClusterHealthMonitor>>,
     // L12 修复：非 etcd 版本不再持有 `etcd_health_monitor: Option<()>`
@@ -140,7 +159,7 @@ pub struct AlgorithmRouter {
 }
 
 // L11 修复：删除手动 `unsafe impl Send/Sync`。所有字段（Config /
-// Arc<ArcSwap<T>> / SmallVec / Arc<DegradationManager> /
+// Arc<ArcSwap<T>> / Arc<DegradationManager> /
 // Option<Arc<CpuMonitor>> / Option<Arc<EtcdClusterHealthMonitor>>）
 // 均为 Send + Sync，编译器会自动推导。原 `unsafe impl` 是历史遗留，
 // 掩盖了潜在的非线程安全字段，应删除让编译器做严格检查。
@@ -165,16 +184,18 @@ impl AlgorithmRouter {
         let primary_algorithm = config.algorithm.get_default_algorithm();
         let degradation_manager = Arc::new(DegradationManager::new(None, audit_logger));
 
+        // 降级链只保存在 DegradationManager 中：路由每次从这里读取，
+        // 管理 API 对降级链和手动降级的修改无需重启即可生效。
         degradation_manager.set_primary_algorithm(primary_algorithm);
         degradation_manager.set_fallback_chain(fallback_chain.to_vec());
 
         Self {
             config,
             algorithms: Arc::new(ArcSwap::from_pointee(HashMap::new())),
-            fallback_chain,
             current_algorithm: Arc::new(ArcSwap::from_pointee(HashMap::new())),
             degradation_manager,
             cpu_monitor: None,
+            usage_tracker: None,
             #[cfg(feature = "etcd")]
             etcd_health_monitor: None,
         }
@@ -188,6 +209,15 @@ impl AlgorithmRouter {
         self
     }
 
+    /// 注入租户用量统计器，初始化时透传给各算法构建器
+    pub fn with_usage_tracker(
+        mut self,
+        tracker: Arc<crate::core::monitoring::UsageTracker>,
+    ) -> Self {
+        self.usage_tracker = Some(tracker);
+        self
+    }
+
     #[cfg(feature = "etcd")]
     pub fn with_etcd_health_monitor(mut self, monitor: Arc<EtcdClusterHealthMonitor>) -> Self {
         self.etcd_health_monitor = Some(monitor);
@@ -215,6 +245,9 @@ impl AlgorithmRouter {
             if let Some(ref cpu_monitor) = self.cpu_monitor {
                 builder = builder.with_cpu_monitor(cpu_monitor.clone());
             }
+            if let Some(ref tracker) = self.usage_tracker {
+                builder = builder.with_usage_tracker(tracker.clone());
+            }
 
             match builder.build(&self.config).await {
                 Ok(algo) => {
@@ -332,6 +365,18 @@ impl AlgorithmRouter {
             .await
     }
 
+    #[tracing::instrument(
+        name = "id.generate",
+        skip_all,
+        fields(
+            workspace = %ctx.workspace_id,
+            group = %ctx.group_id,
+            biz_tag = %ctx.biz_tag,
+            algorithm = %algorithm,
+            fallback = tracing::field::Empty,
+            fallback_reason = tracing::field::Empty,
+        )
+    )]
     async fn generate_with_algorithm_internal(
         &self,
         algorithm: AlgorithmType,
@@ -350,7 +395,20 @@ impl AlgorithmRouter {
 
         // 一次性加载算法表（无锁），后续查找复用，避免循环中多次加锁
         let algorithms = self.algorithms.load_full();
-        let alg_opt = algorithms.get(&effective_algorithm).cloned();
+        let manually_degraded = self
+            .degradation_manager
+            .is_manually_degraded(effective_algorithm);
+        let alg_opt = if manually_degraded {
+            None
+        } else {
+            algorithms.get(&effective_algorithm).cloned()
+        };
+        let fallback_chain = self.degradation_manager.routable_fallback_chain();
+        let unavailable_reason = if manually_degraded {
+            FALLBACK_MANUALLY_DEGRADED
+        } else {
+            FALLBACK_PRIMARY_UNAVAILABLE
+        };
 
         if let Some(alg) = alg_opt {
             debug!(
@@ -384,17 +442,18 @@ impl AlgorithmRouter {
                         .await;
                     warn!(
                         algorithm = ?effective_algorithm,
-                        fallback_chain = ?self.fallback_chain,
+                        fallback_chain = ?fallback_chain,
                         "{}",
                         t!("log.core.algorithm.router.algorithm_failed_fallback")
                     );
-                    for fallback in &self.fallback_chain {
+                    for fallback in &fallback_chain {
                         if let Some(fallback_alg) = algorithms.get(fallback).cloned() {
                             match fallback_alg.generate(ctx).await {
                                 Ok(id) => {
                                     self.degradation_manager
                                         .record_generation_result(*fallback, true)
                                         .await;
+                                    record_fallback_decision(*fallback, FALLBACK_PRIMARY_FAILED);
                                     info!(
                                         fallback = ?fallback,
                                         "{}",
@@ -416,20 +475,29 @@ impl AlgorithmRouter {
             }
         }
 
-        warn!(
-            algorithm = ?effective_algorithm,
-            fallback_chain = ?self.fallback_chain,
-            "{}",
-            t!("log.core.algorithm.router.algorithm_not_found")
-        );
+        if manually_degraded {
+            debug!(
+                algorithm = ?effective_algorithm,
+                fallback_chain = ?fallback_chain,
+                "Algorithm manually degraded, routing to fallback chain"
+            );
+        } else {
+            warn!(
+                algorithm = ?effective_algorithm,
+                fallback_chain = ?fallback_chain,
+                "{}",
+                t!("log.core.algorithm.router.algorithm_not_found")
+            );
+        }
 
-        for fallback in &self.fallback_chain {
+        for fallback in &fallback_chain {
             if let Some(fallback_alg) = algorithms.get(fallback).cloned() {
                 match fallback_alg.generate(ctx).await {
                     Ok(id) => {
                         self.degradation_manager
                             .record_generation_result(*fallback, true)
                             .await;
+                        record_fallback_decision(*fallback, unavailable_reason);
                         return Ok(id);
                     }
                     Err(_) => {
@@ -447,6 +515,19 @@ impl AlgorithmRouter {
         ))
     }
 
+    #[tracing::instrument(
+        name = "id.batch_generate",
+        skip_all,
+        fields(
+            workspace = %ctx.workspace_id,
+            group = %ctx.group_id,
+            biz_tag = %ctx.biz_tag,
+            algorithm = %algorithm,
+            batch_size = size,
+            fallback = tracing::field::Empty,
+            fallback_reason = tracing::field::Empty,
+        )
+    )]
     async fn batch_generate_with_algorithm_internal(
         &self,
         algorithm: AlgorithmType,
@@ -457,7 +538,20 @@ impl AlgorithmRouter {
 
         // 一次性加载算法表（无锁），后续查找复用
         let algorithms = self.algorithms.load_full();
-        let alg_opt = algorithms.get(&effective_algorithm).cloned();
+        let manually_degraded = self
+            .degradation_manager
+            .is_manually_degraded(effective_algorithm);
+        let alg_opt = if manually_degraded {
+            None
+        } else {
+            algorithms.get(&effective_algorithm).cloned()
+        };
+        let fallback_chain = self.degradation_manager.routable_fallback_chain();
+        let unavailable_reason = if manually_degraded {
+            FALLBACK_MANUALLY_DEGRADED
+        } else {
+            FALLBACK_PRIMARY_UNAVAILABLE
+        };
 
         if let Some(alg) = alg_opt {
             match alg.batch_generate(ctx, size).await {
@@ -479,13 +573,14 @@ impl AlgorithmRouter {
                     self.degradation_manager
                         .record_generation_result(effective_algorithm, false)
                         .await;
-                    for fallback in &self.fallback_chain {
+                    for fallback in &fallback_chain {
                         if let Some(fallback_alg) = algorithms.get(fallback).cloned() {
                             match fallback_alg.batch_generate(ctx, size).await {
                                 Ok(batch) => {
                                     self.degradation_manager
                                         .record_generation_result(*fallback, true)
                                         .await;
+                                    record_fallback_decision(*fallback, FALLBACK_PRIMARY_FAILED);
                                     return Ok(batch);
                                 }
                                 Err(_) => {
@@ -502,13 +597,14 @@ impl AlgorithmRouter {
             }
         }
 
-        for fallback in &self.fallback_chain {
+        for fallback in &fallback_chain {
             if let Some(fallback_alg) = algorithms.get(fallback).cloned() {
                 match fallback_alg.batch_generate(ctx, size).await {
                     Ok(batch) => {
                         self.degradation_manager
                             .record_generation_result(*fallback, true)
                             .await;
+                        record_fallback_decision(*fallback, unavailable_reason);
                         return Ok(batch);
                     }
                     Err(_e) => {
@@ -814,6 +910,37 @@ mod tests {
         assert_eq!(id.as_u128(), 42);
     }
 
+    #[tokio::test]
+    async fn test_manually_degraded_algorithm_is_skipped_by_routing() {
+        let router = AlgorithmRouter::new(Config::default(), None);
+        for alg_type in [
+            AlgorithmType::Segment,
+            AlgorithmType::Snowflake,
+            AlgorithmType::UuidV7,
+        ] {
+            insert_mock(
+                &router,
+                alg_type,
+                Arc::new(MockHealthyAlgorithm { alg_type }),
+            );
+        }
+        let dm = router.get_degradation_manager();
+
+        // 主算法被手动降级 → 直接走降级链
+        dm.manual_degrade(AlgorithmType::Segment);
+        let batch = router.batch_generate(&make_ctx("bt"), 2).await.unwrap();
+        assert_eq!(batch.algorithm, AlgorithmType::Snowflake);
+
+        // 降级链中的算法被手动降级 → 跳过
+        dm.manual_degrade(AlgorithmType::Snowflake);
+        let batch = router.batch_generate(&make_ctx("bt"), 2).await.unwrap();
+        assert_eq!(batch.algorithm, AlgorithmType::UuidV7);
+
+        dm.manual_recover(AlgorithmType::Segment);
+        let batch = router.batch_generate(&make_ctx("bt"), 2).await.unwrap();
+        assert_eq!(batch.algorithm, AlgorithmType::Segment);
+    }
+
     #[tokio::test]
     async fn test_id_generator_batch_generate_trait_method_succeeds() {
         let router = AlgorithmRouter::new(Config::default(), None);
@@ -995,7 +1122,7 @@ mod tests {
         // 默认 Config: default = "segment"
         let router = AlgorithmRouter::new(Config::default(), None);
         assert_eq!(
-            router.fallback_chain.to_vec(),
+            router.degradation_manager.get_fallback_chain(),
             vec![
                 AlgorithmType::Snowflake,
                 AlgorithmType::UuidV7,
@@ -1010,7 +1137,7 @@ mod tests {
         config.algorithm.default = "snowflake".to_string();
         let router = AlgorithmRouter::new(config, None);
         assert_eq!(
-            router.fallback_chain.to_vec(),
+            router.degradation_manager.get_fallback_chain(),
             vec![AlgorithmType::UuidV7, AlgorithmType::UuidV4]
         );
     }
@@ -1020,7 +1147,7 @@ mod tests {
         let mut config = Config::default();
         config.algorithm.default = "uuid_v7".to_string();
         let router = AlgorithmRouter::new(config, None);
-        assert!(router.fallback_chain.is_empty());
+        assert!(router.degradation_manager.get_fallback_chain().is_empty());
     }
 
     #[tokio::test]
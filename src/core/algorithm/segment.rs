@@ -46,6 +46,7 @@ use crate::core::config::{Config, SegmentAlgorithmConfig};
 #[cfg(feature = "etcd")]
 use crate::core::coordinator::EtcdClusterHealthMonitor;
 use crate::core::database::SegmentRepository;
+use crate::core::monitoring::UsageTracker;
 use crate::core::types::{AlgorithmType, CoreError, Id, IdBatch, Result};
 use arc_swap::{ArcSwap, ArcSwapOption};
 use async_trait::async_trait;
@@ -642,6 +643,8 @@ pub struct SegmentAlgorithm {
     shutdown_tx: Arc<tokio::sync::watch::Sender<bool>>,
     /// Handle to the health check task
     health_check_task: Arc<tokio::sync::Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    /// Per-tenant usage tracker (segment burn rate)
+    usage_tracker: Option<Arc<UsageTracker>>,
 }
 
 struct AlgorithmMetricsInner {
@@ -701,6 +704,7 @@ impl SegmentAlgorithm {
             cpu_monitor_task: Arc::new(tokio::sync::Mutex::new(None)),
             shutdown_tx: Arc::new(shutdown_tx),
             health_check_task: Arc::new(tokio::sync::Mutex::new(None)),
+            usage_tracker: None,
         }
     }
 
@@ -719,6 +723,23 @@ impl SegmentAlgorithm {
         self
     }
 
+    pub fn with_usage_tracker(mut self, tracker: Arc<UsageTracker>) -> Self {
+        self.usage_tracker = Some(tracker);
+        self
+    }
+
+    /// 号段加载成功后记录号段消耗，供 `/api/v1/admin/usage` 计算 burn rate
+    fn record_segment_loaded(&self, ctx: &GenerateContext, segment: &SegmentData) {
+        if let Some(ref tracker) = self.usage_tracker {
+            tracker.record_segment_allocation(
+                &ctx.workspace_id,
+                &ctx.biz_tag,
+                segment.max_id,
+                segment.max_id.saturating_sub(segment.start_id),
+            );
+        }
+    }
+
     // L13 修复：`initialize` 从 `impl IdAlgorithm for SegmentAlgorithm`
     // 移到 inherent impl。原 trait method `initialize(&mut self, ...)` 让
     // trait 不那么对象安全（`Arc<dyn IdAlgorithm>` 共享后无法调用 `&mut self`）。
@@ -820,6 +841,7 @@ impl IdAlgorithm for SegmentAlgorithm {
                         let load_result = self.segment_loader.load_segment(ctx, 0).await;
                         buffer.finish_loading(); // 无论成功失败都重置 loading
                         let new_seg = load_result?;
+                        self.record_segment_loaded(ctx, &new_seg);
                         let atomic_seg = Arc::new(AtomicSegment::new(
                             new_seg.start_id,
                             new_seg.max_id,
@@ -873,6 +895,7 @@ impl IdAlgorithm for SegmentAlgorithm {
                 if next.is_none() {
                     self.metrics.cache_misses.fetch_add(1, Ordering::Relaxed);
                     let new_seg = self.segment_loader.load_segment(ctx, 0).await?;
+                    self.record_segment_loaded(ctx, &new_seg);
                     let atomic_seg = Arc::new(AtomicSegment::new(
                         new_seg.start_id,
                         new_seg.max_id,
@@ -1144,6 +1167,11 @@ impl DatabaseSegmentLoader {
 
 #[async_trait]
 impl SegmentLoader for DatabaseSegmentLoader {
+    #[tracing::instrument(
+        name = "segment.load",
+        skip_all,
+        fields(workspace = %ctx.workspace_id, biz_tag = %ctx.biz_tag)
+    )]
     async fn load_segment(&self, ctx: &GenerateContext, _worker_id: u8) -> Result<SegmentData> {
         // 计数器递增，用于 QPS 计算
         self.counter
@@ -1289,6 +1317,11 @@ impl RepositoryBackedLoader {
 
 #[async_trait]
 impl SegmentLoader for RepositoryBackedLoader {
+    #[tracing::instrument(
+        name = "segment.load",
+        skip_all,
+        fields(workspace = %ctx.workspace_id, biz_tag = %ctx.biz_tag)
+    )]
     async fn load_segment(&self, ctx: &GenerateContext, _worker_id: u8) -> Result<SegmentData> {
         let segment = self
             .repository
@@ -1415,6 +1448,7 @@ impl SegmentAlgorithmBuilder {
             cpu_monitor_task: Arc::new(tokio::sync::Mutex::new(None)),
             shutdown_tx: Arc::new(shutdown_tx),
             health_check_task: Arc::new(tokio::sync::Mutex::new(None)),
+            usage_tracker: None,
         }
     }
 }
@@ -1439,6 +1473,9 @@ impl crate::core::algorithm::AlgorithmFactory for crate::core::algorithm::Segmen
         if let Some(ref cpu_monitor) = builder.cpu_monitor() {
             algo = algo.with_cpu_monitor(cpu_monitor.clone());
         }
+        if let Some(ref tracker) = builder.usage_tracker() {
+            algo = algo.with_usage_tracker(tracker.clone());
+        }
         algo.initialize(config).await?;
         Ok(Box::new(algo))
     }
@@ -1695,6 +1732,10 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn list_all_segments(&self) -> Result<Vec<SegmentInfo>> {
+            Ok(vec![])
+        }
+
         async fn delete_segment(&self, _workspace_id: &str, _biz_tag: &str) -> Result<()> {
             Ok(())
         }
@@ -2272,6 +2313,38 @@ mod tests {
         assert_eq!(id.as_u128(), 1);
     }
 
+    #[tokio::test]
+    async fn test_segment_algorithm_records_segment_burn_in_usage_tracker() {
+        struct FixedLoader;
+        #[async_trait]
+        impl SegmentLoader for FixedLoader {
+            async fn load_segment(
+                &self,
+                _ctx: &GenerateContext,
+                _worker_id: u8,
+            ) -> Result<SegmentData> {
+                Ok(SegmentData {
+                    start_id: 1,
+                    max_id: 101,
+                    step: 100,
+                    version: 0,
+                })
+            }
+        }
+        let tracker = Arc::new(UsageTracker::default());
+        let algo = SegmentAlgorithm::new(0)
+            .with_loader(Arc::new(FixedLoader))
+            .with_usage_tracker(tracker.clone());
+        algo.generate(&sample_ctx()).await.unwrap();
+
+        let report = tracker.report(None, None, 10);
+        assert_eq!(report.biz_tags.len(), 1);
+        assert_eq!(report.biz_tags[0].workspace, "ws");
+        assert_eq!(report.biz_tags[0].biz_tag, "tag");
+        assert_eq!(report.biz_tags[0].window_segment_ids, 100);
+        assert_eq!(report.biz_tags[0].last_segment_end, 101);
+    }
+
     #[tokio::test]
     async fn test_segment_algorithm_with_loader_replaces_default_async() {
         // 寮傛鐗堟湰锛氫繚璇?with_loader 鍦?async 涓婁笅鏂囦腑涔熻兘宸ヤ綔
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use crate::core::config::Config;
+use crate::core::monitoring::UsageTracker;
 use crate::core::types::{AlgorithmType, CoreError, Id, IdBatch, Result};
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -93,6 +94,12 @@ pub trait IdGenerator: Send + Sync {
 
     async fn health_check(&self) -> HealthStatus;
 
+    /// 各已注册算法的健康状态（组件级健康报告使用）。
+    /// 默认返回空列表，表示实现方不暴露逐算法状态。
+    async fn algorithm_health(&self) -> Vec<(AlgorithmType, HealthStatus)> {
+        Vec::new()
+    }
+
     async fn get_primary_algorithm(&self) -> String;
 
     fn get_degradation_manager(&self) -> &Arc<DegradationManager>;
@@ -161,6 +168,7 @@ impl AlgorithmMetricsSnapshot {
 pub struct AlgorithmBuilder {
     algorithm_type: AlgorithmType,
     cpu_monitor: Option<Arc<CpuMonitor>>,
+    usage_tracker: Option<Arc<UsageTracker>>,
     #[cfg(feature = "etcd")]
     etcd_health_monitor: Option<Arc<EtcdClusterHealthMonitor>>,
     // L12 修复：非 etcd 版本不再持有 `etcd_health_monitor: Option<()>` 占位字段。
@@ -173,6 +181,7 @@ impl AlgorithmBuilder {
         Self {
             algorithm_type,
             cpu_monitor: None,
+            usage_tracker: None,
             #[cfg(feature = "etcd")]
             etcd_health_monitor: None,
         }
@@ -183,6 +192,12 @@ impl AlgorithmBuilder {
         self
     }
 
+    /// 注入租户用量统计器（号段算法据此记录号段消耗速率）
+    pub fn with_usage_tracker(mut self, tracker: Arc<UsageTracker>) -> Self {
+        self.usage_tracker = Some(tracker);
+        self
+    }
+
     #[cfg(feature = "etcd")]
     pub fn with_etcd_health_monitor(mut self, monitor: Arc<EtcdClusterHealthMonitor>) -> Self {
         self.etcd_health_monitor = Some(monitor);
@@ -199,6 +214,10 @@ impl AlgorithmBuilder {
         &self.cpu_monitor
     }
 
+    pub(crate) fn usage_tracker(&self) -> &Option<Arc<UsageTracker>> {
+        &self.usage_tracker
+    }
+
     /// ARCH-HIGH-001 修复：暴露 `etcd_health_monitor` 给工厂 impl。
     #[cfg(feature = "etcd")]
     pub(crate) fn etcd_health_monitor(&self) -> &Option<Arc<EtcdClusterHealthMonitor>> {
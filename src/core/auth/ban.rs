@@ -0,0 +1,939 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 认证失败封禁
+//!
+//! [`BanManager`] 按客户端 IP 与 key_id 统计认证失败，窗口内达到阈值即封禁：
+//! - 封禁由 limiteron 的封禁管理器签发，`auth_bans` 适配为它的封禁存储；升级等级取自目标在 `escalation_window_seconds`
+//!   内未被解封的上一次封禁，时长按 limiteron 的退避档位依次为 `base_ban_seconds`
+//!   的 1、2、4、8 倍，最长 `max_ban_seconds`
+//! - 配置了 [`BanRepository`] 时失败计数写入 `auth_failures`，各实例累加同一计数；
+//!   封禁写入 `auth_bans`，各实例每 `sync_interval` 从数据库重新加载生效的封禁，
+//!   新封禁与解封在一个同步周期内跨实例生效；未配置时只在进程内生效
+//! - 解封按封禁 id 走仓库：limiteron 按目标解封且不记录操作者，无法满足按 id
+//!   解封与审计
+//! - 每次封禁与解封都写审计事件
+
+use crate::core::algorithm::{AuditEvent, AuditEventType, DynAuditLogger};
+use crate::core::config::BanConfig;
+use crate::core::database::{Ban, BanRepository, BanTargetKind};
+use crate::core::types::{CoreError, Result};
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use limiteron::ban::{BackoffConfig, BanManagerConfig, BanSource};
+use limiteron::error::StorageError;
+use limiteron::storage::{BanHistory, BanRecord, BanStorage, BanTarget};
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+/// 进程内同时统计失败次数的目标数上限，写满时清理窗口外的记录，仍满则清空
+const MAX_TRACKED_FAILURE_TARGETS: usize = 10_000;
+
+/// 每次同步从数据库加载的生效封禁上限
+const BAN_SYNC_LIMIT: u64 = 10_000;
+
+/// 自动封禁的原因
+pub const BAN_REASON_AUTH_FAILURES: &str = "too_many_auth_failures";
+
+const AUDIT_ACTOR: &str = "system:ban_manager";
+
+type TargetKey = (BanTargetKind, String);
+
+fn now() -> NaiveDateTime {
+    chrono::Utc::now().naive_utc()
+}
+
+fn seconds(secs: u64) -> chrono::Duration {
+    chrono::Duration::seconds(secs.min(i32::MAX as u64) as i64)
+}
+
+pub struct BanManager {
+    config: BanConfig,
+    /// 未配置仓库时的封禁与失败计数；共享计数不可用时也退回这里计数
+    local: Arc<LocalBans>,
+    repo: Option<Arc<dyn BanRepository>>,
+    audit_logger: Option<DynAuditLogger>,
+    /// limiteron 封禁管理器，构建是异步的，首次封禁时创建
+    limiteron: OnceCell<(limiteron::ban::BanManager, Arc<RepositoryBanStorage>)>,
+    /// 串行化本实例的封禁签发，使存储适配器保存的记录与当前签发对应
+    issuing: tokio::sync::Mutex<()>,
+    /// 每个目标最近一次封禁；由 [`BanManager::sync`] 以仓库中生效的封禁覆盖
+    bans: RwLock<HashMap<TargetKey, Ban>>,
+}
+
+impl BanManager {
+    /// 只在进程内生效的封禁管理器
+    pub fn new(config: BanConfig) -> Self {
+        let local = Arc::new(LocalBans::new(seconds(config.escalation_window_seconds)));
+        Self {
+            config,
+            local,
+            repo: None,
+            audit_logger: None,
+            limiteron: OnceCell::new(),
+            issuing: tokio::sync::Mutex::new(()),
+            bans: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// 封禁与失败计数持久化到数据库并在实例间共享
+    pub fn with_repository(mut self, repo: Arc<dyn BanRepository>) -> Self {
+        self.repo = Some(repo);
+        self
+    }
+
+    pub fn with_audit_logger(mut self, logger: DynAuditLogger) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub fn sync_interval(&self) -> Duration {
+        Duration::from_secs(self.config.sync_interval_seconds.max(1))
+    }
+
+    fn repository(&self) -> Arc<dyn BanRepository> {
+        match &self.repo {
+            Some(repo) => Arc::clone(repo),
+            None => Arc::clone(&self.local) as Arc<dyn BanRepository>,
+        }
+    }
+
+    /// 目标当前生效的封禁
+    pub fn active_ban(&self, kind: BanTargetKind, target: &str) -> Option<Ban> {
+        if !self.config.enabled {
+            return None;
+        }
+        let now = now();
+        self.bans
+            .read()
+            .get(&(kind, target.to_string()))
+            .filter(|ban| ban.is_active(now))
+            .cloned()
+    }
+
+    /// 记录一次认证失败；窗口内失败次数达到阈值时封禁目标并返回新封禁
+    pub async fn record_failure(&self, kind: BanTargetKind, target: &str) -> Option<Ban> {
+        if !self.config.enabled {
+            return None;
+        }
+        let now = now();
+        let window_start = now - seconds(self.config.failure_window_seconds);
+        let failures = match &self.repo {
+            Some(repo) => match repo.record_failure(kind, target, window_start, now).await {
+                Ok(failures) => failures,
+                Err(e) => {
+                    tracing::warn!(
+                        event = "auth_failure_count_failed",
+                        target_type = %kind,
+                        error = %e,
+                        "failed to update the shared failure count, counting on this instance"
+                    );
+                    self.local.count_failure(kind, target, window_start, now)
+                }
+            },
+            None => self.local.count_failure(kind, target, window_start, now),
+        };
+        // 共享计数在行锁下递增，恰好一个实例看到阈值并签发封禁
+        if failures != self.config.failure_threshold.max(1) {
+            return None;
+        }
+        if let Err(e) = self.repository().clear_failures(kind, target).await {
+            tracing::warn!(
+                event = "auth_failure_reset_failed",
+                target_type = %kind,
+                error = %e,
+                "failed to reset the failure count"
+            );
+        }
+        self.local.clear(kind, target);
+
+        // 封禁生效前已在处理中的请求仍可能失败，不为此重复封禁
+        if self.active_ban(kind, target).is_some() {
+            return None;
+        }
+        self.issue(kind, target, BAN_REASON_AUTH_FAILURES).await
+    }
+
+    async fn limiteron(&self) -> Result<&(limiteron::ban::BanManager, Arc<RepositoryBanStorage>)> {
+        self.limiteron
+            .get_or_try_init(|| async {
+                let storage = Arc::new(RepositoryBanStorage {
+                    repo: self.repository(),
+                    escalation_window: seconds(self.config.escalation_window_seconds),
+                    saved: Mutex::new(None),
+                });
+                // 过期封禁保留在表中作为升级依据，不启用 limiteron 的自动解封清理
+                let manager = limiteron::ban::BanManager::builder()
+                    .with_storage(storage.clone())
+                    .with_config(BanManagerConfig {
+                        backoff: backoff(&self.config),
+                        enable_auto_unban: false,
+                        ..BanManagerConfig::default()
+                    })
+                    .build()
+                    .await
+                    .map_err(|e| {
+                        CoreError::InternalError(format!("Failed to build ban manager: {}", e))
+                    })?;
+                Ok((manager, storage))
+            })
+            .await
+    }
+
+    async fn issue(&self, kind: BanTargetKind, target: &str, reason: &str) -> Option<Ban> {
+        let (manager, storage) = match self.limiteron().await {
+            Ok(limiteron) => limiteron,
+            Err(e) => {
+                tracing::error!(
+                    event = "auth_ban_manager_unavailable",
+                    error = %e,
+                    "ban manager unavailable, target not banned"
+                );
+                return None;
+            }
+        };
+        let ban_target = match kind {
+            BanTargetKind::Ip => BanTarget::Ip(target.to_string()),
+            BanTargetKind::KeyId => BanTarget::UserId(target.to_string()),
+        };
+
+        let ban = {
+            let _issuing = self.issuing.lock().await;
+            let created = manager
+                .create_ban(
+                    ban_target,
+                    reason.to_string(),
+                    BanSource::Auto,
+                    serde_json::Value::Null,
+                    None,
+                )
+                .await;
+            let saved = storage.saved.lock().take();
+            match (created, saved) {
+                (Ok(_), Some(ban)) => ban,
+                (Ok(_), None) => return None,
+                (Err(e), _) => {
+                    // 格式非法的 key_id 会被 limiteron 拒绝，来源 IP 的封禁不受影响
+                    tracing::warn!(
+                        event = "auth_ban_rejected",
+                        target_type = %kind,
+                        error = %e,
+                        "ban manager rejected the ban"
+                    );
+                    return None;
+                }
+            }
+        };
+        self.bans
+            .write()
+            .insert((kind, target.to_string()), ban.clone());
+
+        tracing::warn!(
+            event = "auth_ban_issued",
+            ban_id = %ban.id,
+            target_type = %kind,
+            target = %masked_target(&ban),
+            level = ban.level,
+            expires_at = %ban.expires_at,
+            "authentication target banned"
+        );
+        self.audit(
+            AuditEventType::AuthBanIssued,
+            "issue_ban",
+            AUDIT_ACTOR,
+            &ban,
+        )
+        .await;
+        Some(ban)
+    }
+
+    /// 解封。本实例立即生效，其他实例在下次同步时生效
+    pub async fn lift(&self, id: Uuid, actor: &str) -> Result<Ban> {
+        let lifted_at = now();
+        let ban = self.repository().lift_ban(id, actor, lifted_at).await?;
+
+        let key = (ban.target_kind, ban.target.clone());
+        if let Some(current) = self.bans.write().get_mut(&key) {
+            if current.id == ban.id {
+                *current = ban.clone();
+            }
+        }
+        if let Err(e) = self
+            .repository()
+            .clear_failures(ban.target_kind, &ban.target)
+            .await
+        {
+            tracing::warn!(
+                event = "auth_failure_reset_failed",
+                target_type = %ban.target_kind,
+                error = %e,
+                "failed to reset the failure count"
+            );
+        }
+        self.local.clear(ban.target_kind, &ban.target);
+
+        tracing::info!(
+            event = "auth_ban_lifted",
+            ban_id = %ban.id,
+            target_type = %ban.target_kind,
+            target = %masked_target(&ban),
+            actor = %actor,
+            "authentication ban lifted"
+        );
+        self.audit(AuditEventType::AuthBanLifted, "lift_ban", actor, &ban)
+            .await;
+        Ok(ban)
+    }
+
+    /// 按封禁时间降序列出封禁；`active_only` 时只返回仍生效的
+    pub async fn list(&self, active_only: bool, limit: u64) -> Result<Vec<Ban>> {
+        self.repository()
+            .list_bans(active_only.then(now), limit)
+            .await
+    }
+
+    /// 从数据库重新加载生效的封禁并清理过期的失败计数；未配置仓库时不做任何事
+    pub async fn sync(&self) -> Result<()> {
+        let Some(repo) = &self.repo else {
+            return Ok(());
+        };
+        let started = now();
+        let active = repo.list_bans(Some(started), BAN_SYNC_LIMIT).await?;
+
+        let mut loaded: HashMap<TargetKey, Ban> = HashMap::with_capacity(active.len());
+        for ban in active {
+            // 按封禁时间降序返回，同一目标保留最新的一条
+            loaded
+                .entry((ban.target_kind, ban.target.clone()))
+                .or_insert(ban);
+        }
+        {
+            let mut bans = self.bans.write();
+            // 查询期间本实例新发出的封禁不在结果中，保留
+            for (key, ban) in bans.drain() {
+                if ban.banned_at >= started && ban.lifted_at.is_none() {
+                    loaded.insert(key, ban);
+                }
+            }
+            *bans = loaded;
+        }
+
+        if let Err(e) = repo
+            .purge_failures(started - seconds(self.config.failure_window_seconds))
+            .await
+        {
+            tracing::warn!(
+                event = "auth_failure_purge_failed",
+                error = %e,
+                "failed to purge expired failure counts"
+            );
+        }
+        Ok(())
+    }
+
+    /// 启动周期同步
+    pub fn spawn_sync(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(manager.sync_interval());
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                if let Err(e) = manager.sync().await {
+                    tracing::warn!(
+                        event = "auth_ban_sync_failed",
+                        error = %e,
+                        "failed to reload bans, keeping the current set"
+                    );
+                }
+            }
+        })
+    }
+
+    async fn audit(&self, event_type: AuditEventType, action: &str, actor: &str, ban: &Ban) {
+        let Some(logger) = &self.audit_logger else {
+            return;
+        };
+        let event = AuditEvent::builder(
+            event_type,
+            action,
+            format!("ban:{}:{}", ban.target_kind, ban.target),
+        )
+        .actor(actor)
+        .details(serde_json::json!({
+            "ban_id": ban.id,
+            "level": ban.level,
+            "reason": ban.reason,
+            "banned_at": ban.banned_at.and_utc().to_rfc3339(),
+            "expires_at": ban.expires_at.and_utc().to_rfc3339(),
+        }))
+        .build();
+        logger.log(event).await;
+    }
+}
+
+/// limiteron 的退避档位：第 n 档为 `base_ban_seconds` 的 2^(n-1) 倍，不超过上限
+fn backoff(config: &BanConfig) -> BackoffConfig {
+    let tier = |doublings: u32| {
+        config
+            .base_ban_seconds
+            .saturating_mul(1u64 << doublings)
+            .min(config.max_ban_seconds)
+            .min(i32::MAX as u64)
+    };
+    BackoffConfig {
+        first_duration: tier(0),
+        second_duration: tier(1),
+        third_duration: tier(2),
+        fourth_duration: tier(3),
+        max_duration: config.max_ban_seconds.min(i32::MAX as u64),
+    }
+}
+
+/// 日志中 key_id 只保留前 8 个字符，与认证失败日志一致
+fn masked_target(ban: &Ban) -> String {
+    match ban.target_kind {
+        BanTargetKind::Ip => ban.target.clone(),
+        BanTargetKind::KeyId => ban.target.chars().take(8).collect(),
+    }
+}
+
+fn target_kind(target: &BanTarget) -> std::result::Result<(BanTargetKind, &str), StorageError> {
+    match target {
+        BanTarget::Ip(ip) => Ok((BanTargetKind::Ip, ip)),
+        BanTarget::UserId(key_id) => Ok((BanTargetKind::KeyId, key_id)),
+        other => Err(StorageError::QueryError(format!(
+            "unsupported ban target: {:?}",
+            other
+        ))),
+    }
+}
+
+fn storage_error(e: CoreError) -> StorageError {
+    StorageError::QueryError(e.to_string())
+}
+
+fn to_record(ban: &Ban) -> BanRecord {
+    let target = match ban.target_kind {
+        BanTargetKind::Ip => BanTarget::Ip(ban.target.clone()),
+        BanTargetKind::KeyId => BanTarget::UserId(ban.target.clone()),
+    };
+    BanRecord {
+        target,
+        ban_times: ban.level.max(1) as u32,
+        duration: (ban.expires_at - ban.banned_at)
+            .to_std()
+            .unwrap_or_default(),
+        banned_at: Utc.from_utc_datetime(&ban.banned_at),
+        expires_at: Utc.from_utc_datetime(&ban.expires_at),
+        is_manual: false,
+        reason: ban.reason.clone(),
+    }
+}
+
+/// 以 `auth_bans` 为后端的 limiteron 封禁存储。
+///
+/// limiteron 的数据库适配器使用自己的表结构，这里改为读写 [`BanRepository`]，
+/// 封禁记录与管理端接口、跨实例同步共用同一张表。
+struct RepositoryBanStorage {
+    repo: Arc<dyn BanRepository>,
+    escalation_window: chrono::Duration,
+    /// 最近一次保存的封禁，由签发方在同一临界区内取走
+    saved: Mutex<Option<Ban>>,
+}
+
+impl RepositoryBanStorage {
+    async fn latest(&self, target: &BanTarget) -> std::result::Result<Option<Ban>, StorageError> {
+        let (kind, target) = target_kind(target)?;
+        self.repo
+            .latest_ban(kind, target)
+            .await
+            .map_err(storage_error)
+    }
+}
+
+#[async_trait]
+impl BanStorage for RepositoryBanStorage {
+    async fn is_banned(
+        &self,
+        target: &BanTarget,
+    ) -> std::result::Result<Option<BanRecord>, StorageError> {
+        let now = now();
+        Ok(self
+            .latest(target)
+            .await?
+            .filter(|ban| ban.is_active(now))
+            .map(|ban| to_record(&ban)))
+    }
+
+    async fn save(&self, record: &BanRecord) -> std::result::Result<(), StorageError> {
+        let (kind, target) = target_kind(&record.target)?;
+        let ban = Ban {
+            id: Uuid::new_v4(),
+            target_kind: kind,
+            target: target.to_string(),
+            level: record.ban_times.min(i32::MAX as u32) as i32,
+            reason: record.reason.clone(),
+            banned_at: record.banned_at.naive_utc(),
+            expires_at: record.expires_at.naive_utc(),
+            lifted_at: None,
+            lifted_by: None,
+        };
+        // 写库失败时封禁仍在本实例生效，只是不会同步到其他实例
+        if let Err(e) = self.repo.insert_ban(&ban).await {
+            tracing::warn!(
+                event = "auth_ban_persist_failed",
+                ban_id = %ban.id,
+                error = %e,
+                "failed to persist ban, it only applies to this instance"
+            );
+        }
+        *self.saved.lock() = Some(ban);
+        Ok(())
+    }
+
+    /// 升级依据：升级窗口内、未被管理员解封的上一次封禁
+    async fn get_history(
+        &self,
+        target: &BanTarget,
+    ) -> std::result::Result<Option<BanHistory>, StorageError> {
+        let previous = match self.latest(target).await {
+            Ok(previous) => previous,
+            Err(e) => {
+                // 查不到历史时仍按第一级封禁，不因数据库故障放过攻击者
+                tracing::warn!(
+                    event = "auth_ban_lookup_failed",
+                    error = %e,
+                    "failed to load previous ban, banning at the first level"
+                );
+                None
+            }
+        };
+        let now = now();
+        Ok(previous
+            .filter(|ban| ban.lifted_at.is_none() && now - ban.banned_at < self.escalation_window)
+            .map(|ban| BanHistory {
+                ban_times: ban.level.max(1) as u32,
+                last_banned_at: Utc.from_utc_datetime(&ban.banned_at),
+            }))
+    }
+
+    async fn increment_ban_times(
+        &self,
+        target: &BanTarget,
+    ) -> std::result::Result<u64, StorageError> {
+        Ok(self.get_ban_times(target).await? + 1)
+    }
+
+    async fn get_ban_times(&self, target: &BanTarget) -> std::result::Result<u64, StorageError> {
+        Ok(self
+            .get_history(target)
+            .await?
+            .map(|history| history.ban_times as u64)
+            .unwrap_or(0))
+    }
+
+    async fn remove_ban(&self, target: &BanTarget) -> std::result::Result<(), StorageError> {
+        let now = now();
+        if let Some(ban) = self.latest(target).await?.filter(|ban| ban.is_active(now)) {
+            self.repo
+                .lift_ban(ban.id, AUDIT_ACTOR, now)
+                .await
+                .map_err(storage_error)?;
+        }
+        Ok(())
+    }
+
+    /// 过期封禁保留为升级历史，不删除
+    async fn cleanup_expired_bans(&self) -> std::result::Result<u64, StorageError> {
+        Ok(0)
+    }
+
+    async fn list_bans(
+        &self,
+        active_only: bool,
+        offset: u64,
+        limit: u64,
+    ) -> std::result::Result<Vec<BanRecord>, StorageError> {
+        let bans = self
+            .repo
+            .list_bans(active_only.then(now), offset.saturating_add(limit))
+            .await
+            .map_err(storage_error)?;
+        Ok(bans.iter().skip(offset as usize).map(to_record).collect())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// 进程内的封禁记录与失败计数，未配置数据库时代替 [`BanRepository`]
+struct LocalBans {
+    /// 超出该时长且已失效的封禁不再参与升级，写入新封禁时清理
+    retention: chrono::Duration,
+    rows: Mutex<Vec<Ban>>,
+    /// 失败次数与当前窗口起点
+    failures: Mutex<HashMap<TargetKey, (u64, NaiveDateTime)>>,
+}
+
+impl LocalBans {
+    fn new(retention: chrono::Duration) -> Self {
+        Self {
+            retention,
+            rows: Mutex::new(Vec::new()),
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn count_failure(
+        &self,
+        kind: BanTargetKind,
+        target: &str,
+        window_start: NaiveDateTime,
+        now: NaiveDateTime,
+    ) -> u64 {
+        let key = (kind, target.to_string());
+        let mut failures = self.failures.lock();
+        if failures.len() >= MAX_TRACKED_FAILURE_TARGETS && !failures.contains_key(&key) {
+            failures.retain(|_, (_, started)| *started > window_start);
+            if failures.len() >= MAX_TRACKED_FAILURE_TARGETS {
+                failures.clear();
+            }
+        }
+        let entry = failures.entry(key).or_insert((0, now));
+        if entry.1 <= window_start {
+            *entry = (0, now);
+        }
+        entry.0 += 1;
+        entry.0
+    }
+
+    fn clear(&self, kind: BanTargetKind, target: &str) {
+        self.failures.lock().remove(&(kind, target.to_string()));
+    }
+}
+
+#[async_trait]
+impl BanRepository for LocalBans {
+    async fn insert_ban(&self, ban: &Ban) -> Result<()> {
+        let mut rows = self.rows.lock();
+        rows.retain(|b| b.is_active(ban.banned_at) || ban.banned_at - b.banned_at < self.retention);
+        rows.push(ban.clone());
+        Ok(())
+    }
+
+    async fn latest_ban(&self, kind: BanTargetKind, target: &str) -> Result<Option<Ban>> {
+        Ok(self
+            .rows
+            .lock()
+            .iter()
+            .filter(|b| b.target_kind == kind && b.target == target)
+            .max_by_key(|b| b.banned_at)
+            .cloned())
+    }
+
+    async fn list_bans(&self, active_at: Option<NaiveDateTime>, limit: u64) -> Result<Vec<Ban>> {
+        let mut bans: Vec<Ban> = self
+            .rows
+            .lock()
+            .iter()
+            .filter(|b| active_at.is_none_or(|now| b.is_active(now)))
+            .cloned()
+            .collect();
+        bans.sort_by(|a, b| b.banned_at.cmp(&a.banned_at));
+        bans.truncate(limit as usize);
+        Ok(bans)
+    }
+
+    async fn lift_ban(&self, id: Uuid, lifted_by: &str, now: NaiveDateTime) -> Result<Ban> {
+        let mut rows = self.rows.lock();
+        let ban = rows
+            .iter_mut()
+            .find(|b| b.id == id)
+            .ok_or_else(|| CoreError::NotFound(format!("Ban not found: {}", id)))?;
+        if ban.lifted_at.is_none() {
+            ban.lifted_at = Some(now);
+            ban.lifted_by = Some(lifted_by.to_string());
+        }
+        Ok(ban.clone())
+    }
+
+    async fn record_failure(
+        &self,
+        kind: BanTargetKind,
+        target: &str,
+        window_start: NaiveDateTime,
+        now: NaiveDateTime,
+    ) -> Result<u64> {
+        Ok(self.count_failure(kind, target, window_start, now))
+    }
+
+    async fn clear_failures(&self, kind: BanTargetKind, target: &str) -> Result<()> {
+        self.clear(kind, target);
+        Ok(())
+    }
+
+    async fn purge_failures(&self, before: NaiveDateTime) -> Result<u64> {
+        let mut failures = self.failures.lock();
+        let tracked = failures.len();
+        failures.retain(|_, (_, started)| *started >= before);
+        Ok((tracked - failures.len()) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::algorithm::AuditLogger;
+
+    /// 多个管理器共享的封禁表与失败计数，模拟同一数据库
+    fn shared_bans() -> Arc<LocalBans> {
+        Arc::new(LocalBans::new(seconds(86_400)))
+    }
+
+    #[derive(Default)]
+    struct RecordingAuditLogger {
+        events: Mutex<Vec<AuditEvent>>,
+    }
+
+    #[async_trait]
+    impl AuditLogger for RecordingAuditLogger {
+        async fn log(&self, event: AuditEvent) {
+            self.events.lock().push(event);
+        }
+    }
+
+    fn config() -> BanConfig {
+        BanConfig {
+            failure_threshold: 3,
+            base_ban_seconds: 60,
+            max_ban_seconds: 200,
+            ..BanConfig::default()
+        }
+    }
+
+    fn previous_ban(level: i32, banned_secs_ago: i64) -> Ban {
+        let banned_at = now() - chrono::Duration::seconds(banned_secs_ago);
+        Ban {
+            id: Uuid::new_v4(),
+            target_kind: BanTargetKind::Ip,
+            target: "10.0.0.1".to_string(),
+            level,
+            reason: BAN_REASON_AUTH_FAILURES.to_string(),
+            banned_at,
+            expires_at: banned_at,
+            lifted_at: None,
+            lifted_by: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_failure_bans_at_threshold_and_audits() {
+        let logger = Arc::new(RecordingAuditLogger::default());
+        let manager = BanManager::new(config()).with_audit_logger(logger.clone());
+
+        assert!(manager
+            .record_failure(BanTargetKind::Ip, "10.0.0.1")
+            .await
+            .is_none());
+        assert!(manager
+            .record_failure(BanTargetKind::Ip, "10.0.0.1")
+            .await
+            .is_none());
+        let ban = manager
+            .record_failure(BanTargetKind::Ip, "10.0.0.1")
+            .await
+            .expect("third failure reaches the threshold");
+
+        assert_eq!(ban.level, 1);
+        assert_eq!((ban.expires_at - ban.banned_at).num_seconds(), 60);
+        assert_eq!(
+            manager.active_ban(BanTargetKind::Ip, "10.0.0.1"),
+            Some(ban.clone())
+        );
+        assert!(manager
+            .active_ban(BanTargetKind::KeyId, "10.0.0.1")
+            .is_none());
+
+        let events = logger.events.lock();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, AuditEventType::AuthBanIssued);
+        assert_eq!(events[0].resource, "ban:ip:10.0.0.1");
+    }
+
+    #[test]
+    fn test_backoff_doubles_duration_up_to_max() {
+        let backoff = backoff(&config());
+        assert_eq!(backoff.first_duration, 60);
+        assert_eq!(backoff.second_duration, 120);
+        assert_eq!(backoff.third_duration, 200);
+        assert_eq!(backoff.fourth_duration, 200);
+        assert_eq!(backoff.max_duration, 200);
+    }
+
+    #[tokio::test]
+    async fn test_escalation_uses_unlifted_bans_within_window() {
+        async fn level_after(previous: Ban) -> i32 {
+            let repo = shared_bans();
+            repo.rows.lock().push(previous);
+            let manager = BanManager::new(config()).with_repository(repo);
+            manager
+                .issue(BanTargetKind::Ip, "10.0.0.1", BAN_REASON_AUTH_FAILURES)
+                .await
+                .unwrap()
+                .level
+        }
+
+        assert_eq!(level_after(previous_ban(1, 600)).await, 2);
+        assert_eq!(level_after(previous_ban(3, 600)).await, 4);
+        // 超出升级窗口后从第一级重新开始
+        assert_eq!(level_after(previous_ban(3, 2 * 86_400)).await, 1);
+        // 被解封的封禁不参与升级
+        let mut lifted = previous_ban(3, 600);
+        lifted.lifted_at = Some(now());
+        assert_eq!(level_after(lifted).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_repeat_ban_escalates_from_persisted_history() {
+        let repo = shared_bans();
+        repo.rows.lock().push(previous_ban(2, 600));
+        let manager = BanManager::new(config()).with_repository(repo.clone());
+
+        for _ in 0..2 {
+            manager.record_failure(BanTargetKind::Ip, "10.0.0.1").await;
+        }
+        let ban = manager
+            .record_failure(BanTargetKind::Ip, "10.0.0.1")
+            .await
+            .unwrap();
+
+        assert_eq!(ban.level, 3);
+        assert_eq!((ban.expires_at - ban.banned_at).num_seconds(), 200);
+        assert_eq!(repo.rows.lock().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_counted_across_instances() {
+        let repo = shared_bans();
+        let a = BanManager::new(config()).with_repository(repo.clone());
+        let b = BanManager::new(config()).with_repository(repo.clone());
+
+        assert!(a
+            .record_failure(BanTargetKind::Ip, "10.0.0.1")
+            .await
+            .is_none());
+        assert!(b
+            .record_failure(BanTargetKind::Ip, "10.0.0.1")
+            .await
+            .is_none());
+        let ban = a
+            .record_failure(BanTargetKind::Ip, "10.0.0.1")
+            .await
+            .expect("third failure across instances reaches the threshold");
+        assert_eq!(ban.level, 1);
+
+        // 封禁后计数清零，下一次失败重新计数
+        assert!(b
+            .record_failure(BanTargetKind::Ip, "10.0.0.1")
+            .await
+            .is_none());
+        assert_eq!(repo.rows.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_key_id_is_not_banned() {
+        let manager = BanManager::new(BanConfig {
+            failure_threshold: 1,
+            ..config()
+        });
+        assert!(manager
+            .record_failure(BanTargetKind::KeyId, "bad key\n")
+            .await
+            .is_none());
+        assert!(manager
+            .record_failure(BanTargetKind::Ip, "10.0.0.1")
+            .await
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_bans_and_lifts_propagate_through_sync() {
+        let repo = shared_bans();
+        let logger = Arc::new(RecordingAuditLogger::default());
+        let a = BanManager::new(config())
+            .with_repository(repo.clone())
+            .with_audit_logger(logger.clone());
+        let b = BanManager::new(config()).with_repository(repo.clone());
+
+        let mut ban = None;
+        for _ in 0..3 {
+            ban = a.record_failure(BanTargetKind::KeyId, "nino_key").await;
+        }
+        let ban = ban.unwrap();
+        assert!(b.active_ban(BanTargetKind::KeyId, "nino_key").is_none());
+
+        b.sync().await.unwrap();
+        assert_eq!(
+            b.active_ban(BanTargetKind::KeyId, "nino_key").map(|b| b.id),
+            Some(ban.id)
+        );
+        assert_eq!(b.list(true, 10).await.unwrap().len(), 1);
+
+        let lifted = a.lift(ban.id, "admin-key").await.unwrap();
+        assert_eq!(lifted.lifted_by.as_deref(), Some("admin-key"));
+        assert!(a.active_ban(BanTargetKind::KeyId, "nino_key").is_none());
+
+        b.sync().await.unwrap();
+        assert!(b.active_ban(BanTargetKind::KeyId, "nino_key").is_none());
+        assert!(b.list(true, 10).await.unwrap().is_empty());
+        assert_eq!(b.list(false, 10).await.unwrap().len(), 1);
+
+        let events = logger.events.lock();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].event_type, AuditEventType::AuthBanLifted);
+        assert_eq!(events[1].user_id.as_deref(), Some("admin-key"));
+    }
+
+    #[tokio::test]
+    async fn test_lift_unknown_ban_returns_not_found() {
+        let manager = BanManager::new(config());
+        let result = manager.lift(Uuid::new_v4(), "admin-key").await;
+        assert!(matches!(result, Err(CoreError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_manager_never_bans() {
+        let manager = BanManager::new(BanConfig {
+            enabled: false,
+            ..config()
+        });
+        for _ in 0..10 {
+            assert!(manager
+                .record_failure(BanTargetKind::Ip, "10.0.0.1")
+                .await
+                .is_none());
+        }
+        assert!(manager.active_ban(BanTargetKind::Ip, "10.0.0.1").is_none());
+    }
+}
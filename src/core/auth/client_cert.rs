@@ -0,0 +1,275 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Client certificate to caller mapping (mTLS).
+//!
+//! 证书链已由 TLS 层按 `tls.ca_path` / `tls.crl_paths` 校验，这里只把叶子证书的
+//! subject CN 与 SAN（DNS / URI / email）按 `tls.client_identities` 映射为
+//! workspace、角色与作用域，取第一条匹配的映射。
+
+use crate::core::auth::ApiKeyScopes;
+use crate::core::config::ClientIdentityMapping;
+use crate::core::database::ApiKeyRole;
+use crate::core::types::CoreError;
+use x509_parser::extensions::GeneralName;
+
+/// 由客户端证书认证的调用方
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCertIdentity {
+    /// 命中映射的证书名称（CN 或 SAN）
+    pub subject: String,
+    /// Admin 为 `None`
+    pub workspace_id: Option<uuid::Uuid>,
+    pub role: ApiKeyRole,
+    pub scopes: ApiKeyScopes,
+}
+
+#[derive(Debug, Clone)]
+struct CompiledMapping {
+    pattern: String,
+    workspace_id: Option<uuid::Uuid>,
+    role: ApiKeyRole,
+    scopes: ApiKeyScopes,
+}
+
+impl CompiledMapping {
+    fn matches(&self, name: &str) -> bool {
+        match self.pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == self.pattern,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClientCertMapper {
+    mappings: Vec<CompiledMapping>,
+}
+
+impl ClientCertMapper {
+    /// 校验并编译映射：角色只能是 `user` / `admin`，User 必须指定
+    /// `workspace_id`，作用域必须可解析；否则返回 `ConfigurationError`
+    pub fn new(mappings: &[ClientIdentityMapping]) -> Result<Self, CoreError> {
+        let invalid = |pattern: &str, reason: String| {
+            CoreError::ConfigurationError(format!(
+                "tls.client_identities '{}': {}",
+                pattern, reason
+            ))
+        };
+        let mappings = mappings
+            .iter()
+            .map(|mapping| {
+                if mapping.pattern.is_empty() {
+                    return Err(invalid("", "pattern must not be empty".to_string()));
+                }
+                let role = match mapping.role.as_str() {
+                    "admin" => ApiKeyRole::Admin,
+                    "user" => ApiKeyRole::User,
+                    "workspace_admin" => ApiKeyRole::WorkspaceAdmin,
+                    other => {
+                        return Err(invalid(
+                            &mapping.pattern,
+                            format!("unknown role '{}'", other),
+                        ))
+                    }
+                };
+                let workspace_id = match role {
+                    ApiKeyRole::Admin => None,
+                    _ => Some(mapping.workspace_id.ok_or_else(|| {
+                        invalid(
+                            &mapping.pattern,
+                            "user and workspace_admin mappings need a workspace_id".to_string(),
+                        )
+                    })?),
+                };
+                let scopes = if mapping.scopes.is_empty() {
+                    ApiKeyScopes::unrestricted()
+                } else {
+                    ApiKeyScopes::parse(&mapping.scopes)
+                        .map_err(|e| invalid(&mapping.pattern, e.to_string()))?
+                };
+                Ok(CompiledMapping {
+                    pattern: mapping.pattern.clone(),
+                    workspace_id,
+                    role,
+                    scopes,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { mappings })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// 映射 DER 编码的叶子证书；失败时返回用于日志的原因标识
+    pub fn resolve(&self, certificate_der: &[u8]) -> Result<ClientCertIdentity, &'static str> {
+        let names = certificate_names(certificate_der).ok_or("unparseable_client_certificate")?;
+        self.mappings
+            .iter()
+            .find_map(|mapping| {
+                names
+                    .iter()
+                    .find(|name| mapping.matches(name))
+                    .map(|name| ClientCertIdentity {
+                        subject: name.clone(),
+                        workspace_id: mapping.workspace_id,
+                        role: mapping.role.clone(),
+                        scopes: mapping.scopes.clone(),
+                    })
+            })
+            .ok_or("unmapped_client_certificate")
+    }
+}
+
+/// 证书的 subject CN 与 SAN 中的 DNS / URI / email 名称；无法解析时返回 `None`
+pub fn certificate_names(certificate_der: &[u8]) -> Option<Vec<String>> {
+    let (_, certificate) = x509_parser::parse_x509_certificate(certificate_der).ok()?;
+    let mut names: Vec<String> = certificate
+        .subject()
+        .iter_common_name()
+        .filter_map(|cn| cn.as_str().ok())
+        .map(str::to_string)
+        .collect();
+    if let Ok(Some(san)) = certificate.subject_alternative_name() {
+        for name in &san.value.general_names {
+            match name {
+                GeneralName::DNSName(value)
+                | GeneralName::URI(value)
+                | GeneralName::RFC822Name(value) => names.push(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+    Some(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::auth::Scope;
+    use rcgen::{CertificateParams, DnType, KeyPair, SanType};
+
+    const WORKSPACE: &str = "0192f0c4-8d3e-7c2a-9b1f-3e5d6a7b8c9d";
+
+    fn certificate(common_name: &str, uri: Option<&str>) -> Vec<u8> {
+        let mut params = CertificateParams::new(vec!["orders.internal".to_string()]).unwrap();
+        params
+            .distinguished_name
+            .push(DnType::CommonName, common_name);
+        if let Some(uri) = uri {
+            params
+                .subject_alt_names
+                .push(SanType::URI(uri.try_into().unwrap()));
+        }
+        let key_pair = KeyPair::generate().unwrap();
+        params.self_signed(&key_pair).unwrap().der().to_vec()
+    }
+
+    fn mapping(pattern: &str, role: &str, scopes: &[&str]) -> ClientIdentityMapping {
+        ClientIdentityMapping {
+            pattern: pattern.to_string(),
+            role: role.to_string(),
+            workspace_id: Some(WORKSPACE.parse().unwrap()),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_certificate_names_include_cn_and_sans() {
+        let names = certificate_names(&certificate(
+            "orders-svc",
+            Some("spiffe://cluster.local/ns/orders/sa/api"),
+        ))
+        .unwrap();
+        assert_eq!(
+            names,
+            vec![
+                "orders-svc",
+                "orders.internal",
+                "spiffe://cluster.local/ns/orders/sa/api"
+            ]
+        );
+        assert!(certificate_names(b"not a certificate").is_none());
+    }
+
+    #[test]
+    fn test_first_matching_mapping_wins() {
+        let mapper = ClientCertMapper::new(&[
+            mapping("ops-admin", "admin", &[]),
+            mapping(
+                "spiffe://cluster.local/ns/orders/*",
+                "user",
+                &["generate:orders"],
+            ),
+            mapping("*", "user", &["parse"]),
+        ])
+        .unwrap();
+
+        let spiffe = mapper
+            .resolve(&certificate(
+                "orders-svc",
+                Some("spiffe://cluster.local/ns/orders/sa/api"),
+            ))
+            .unwrap();
+        assert_eq!(spiffe.subject, "spiffe://cluster.local/ns/orders/sa/api");
+        assert_eq!(spiffe.role, ApiKeyRole::User);
+        assert_eq!(spiffe.workspace_id, Some(WORKSPACE.parse().unwrap()));
+        assert!(spiffe.scopes.allows(&Scope::generate("orders", "created")));
+
+        let admin = mapper.resolve(&certificate("ops-admin", None)).unwrap();
+        assert_eq!(admin.role, ApiKeyRole::Admin);
+        assert_eq!(admin.workspace_id, None);
+        assert!(admin.scopes.is_unrestricted());
+
+        let fallback = mapper.resolve(&certificate("billing", None)).unwrap();
+        assert_eq!(fallback.subject, "billing");
+        assert!(!fallback
+            .scopes
+            .allows(&Scope::generate("orders", "created")));
+    }
+
+    #[test]
+    fn test_unmapped_certificate_is_rejected() {
+        let mapper = ClientCertMapper::new(&[mapping("orders-svc", "user", &[])]).unwrap();
+        assert_eq!(
+            mapper.resolve(&certificate("billing", None)),
+            Err("unmapped_client_certificate")
+        );
+    }
+
+    #[test]
+    fn test_invalid_mappings_are_configuration_errors() {
+        let no_workspace = ClientIdentityMapping {
+            workspace_id: None,
+            ..mapping("orders-svc", "user", &[])
+        };
+        for invalid in [
+            no_workspace,
+            mapping("orders-svc", "anonymous", &[]),
+            mapping("orders-svc", "user", &["generate:bad/tag/x"]),
+            mapping("", "user", &[]),
+        ] {
+            assert!(
+                matches!(
+                    ClientCertMapper::new(&[invalid.clone()]),
+                    Err(CoreError::ConfigurationError(_))
+                ),
+                "should reject {:?}",
+                invalid
+            );
+        }
+    }
+}
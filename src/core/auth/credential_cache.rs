@@ -0,0 +1,999 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 已校验凭证缓存与跨实例吊销传播
+//!
+//! Argon2id 校验每次消耗数十毫秒 CPU，高频调用方的每个请求都要付出这笔开销。
+//! [`CachedApiKeyRepository`] 包装 [`ApiKeyRepository`]，校验成功的 `(key_id, secret)`
+//! 在 TTL 内直接命中缓存：
+//! - 缓存键是 HMAC-SHA256（进程随机密钥）摘要，内存中不保留明文 secret，摘要也无法离线穷举
+//! - 只缓存成功结果，错误密钥每次仍走 Argon2id
+//! - 经本实例的吊销、轮换、删除立即失效本地条目；仓库把变更写入 `api_key_changes`，
+//!   其他实例的 [`RevocationSync`] 每个 `poll_interval` 轮询一次，传播延迟不超过该间隔；
+//!   轮询失败时清空缓存，保证上限不因变更表不可用而失效
+//! - 配置了 [`RevocationNotifier`]（如 etcd watch）时变更另行推送，通常毫秒级生效
+//! - 命中缓存不刷新 `last_used_at`，该字段的精度降为一个 TTL
+
+use crate::core::auth::ApiKeyScopes;
+use crate::core::config::CredentialCacheConfig;
+use crate::core::database::{
+    ApiKeyChange, ApiKeyInfo, ApiKeyRepository, ApiKeyRole, ApiKeySigningKey, ApiKeyWithSecret,
+    CreateApiKeyRequest,
+};
+use crate::core::types::Result;
+use async_trait::async_trait;
+use hmac::{Hmac, KeyInit, Mac};
+use parking_lot::Mutex;
+use rand::RngExt;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 每次读取变更表的最大行数
+const CHANGE_BATCH_SIZE: u64 = 500;
+
+/// 校验成功后缓存的凭证信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedCredential {
+    pub workspace_id: Option<Uuid>,
+    pub role: ApiKeyRole,
+    pub scopes: ApiKeyScopes,
+}
+
+struct CacheEntry {
+    key_id: String,
+    credential: VerifiedCredential,
+    expires_at: Instant,
+}
+
+/// 进程内的已校验凭证缓存
+pub struct CredentialCache {
+    fingerprint_key: [u8; 32],
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<[u8; 32], CacheEntry>>,
+    /// 每次失效递增。校验开始后发生过失效时放弃写入，
+    /// 避免与吊销并发的校验把旧凭证写回缓存
+    generation: AtomicU64,
+}
+
+impl CredentialCache {
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            fingerprint_key: rand::rng().random(),
+            ttl,
+            max_entries: max_entries.max(1),
+            entries: Mutex::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    pub fn from_config(config: &CredentialCacheConfig) -> Self {
+        Self::new(
+            Duration::from_secs(config.ttl_seconds.max(1)),
+            config.max_entries,
+        )
+    }
+
+    fn fingerprint(&self, key_id: &str, key_secret: &str) -> [u8; 32] {
+        let mut mac = <HmacSha256 as KeyInit>::new_from_slice(&self.fingerprint_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(key_id.as_bytes());
+        mac.update(b":");
+        mac.update(key_secret.as_bytes());
+        let mut fingerprint = [0u8; 32];
+        fingerprint.copy_from_slice(&mac.finalize().into_bytes());
+        fingerprint
+    }
+
+    /// 当前失效代数，在调用底层校验之前读取，传给 [`insert`](Self::insert)
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    pub fn get(&self, key_id: &str, key_secret: &str) -> Option<VerifiedCredential> {
+        let fingerprint = self.fingerprint(key_id, key_secret);
+        let mut entries = self.entries.lock();
+        match entries.get(&fingerprint) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.credential.clone()),
+            Some(_) => {
+                entries.remove(&fingerprint);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// 写入校验成功的凭证；`generation` 之后发生过失效时放弃写入并返回 `false`。
+    /// 写满时先清理过期条目，仍然满则淘汰最早到期的条目。
+    pub fn insert(
+        &self,
+        generation: u64,
+        key_id: &str,
+        key_secret: &str,
+        credential: VerifiedCredential,
+    ) -> bool {
+        let fingerprint = self.fingerprint(key_id, key_secret);
+        let now = Instant::now();
+        let mut entries = self.entries.lock();
+        // 失效操作同样在持锁时递增代数，比较与写入不会与之交错
+        if self.generation.load(Ordering::Acquire) != generation {
+            return false;
+        }
+        if entries.len() >= self.max_entries && !entries.contains_key(&fingerprint) {
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= self.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.expires_at)
+                    .map(|(fingerprint, _)| *fingerprint);
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(
+            fingerprint,
+            CacheEntry {
+                key_id: key_id.to_string(),
+                credential,
+                expires_at: now + self.ttl,
+            },
+        );
+        true
+    }
+
+    /// 失效某个 key 的全部条目（轮换宽限期内新旧密钥可能同时在缓存中），返回移除的条目数
+    pub fn invalidate_key(&self, key_id: &str) -> usize {
+        let mut entries = self.entries.lock();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        let before = entries.len();
+        entries.retain(|_, entry| entry.key_id != key_id);
+        before - entries.len()
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock();
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+}
+
+/// 向其他实例推送本实例产生的 key 变更；变更表轮询仍作兜底
+#[async_trait]
+pub trait RevocationNotifier: Send + Sync {
+    async fn publish(&self, key_id: &str) -> Result<()>;
+}
+
+/// 轮询 `api_key_changes`，失效本实例缓存中被吊销、轮换或删除的 key
+pub struct RevocationSync {
+    repo: Arc<dyn ApiKeyRepository>,
+    cache: Arc<CredentialCache>,
+    poll_interval: Duration,
+    /// 已处理的最大 `seq`；异步锁保证同一时刻只有一次同步在推进游标
+    cursor: tokio::sync::Mutex<i64>,
+}
+
+impl RevocationSync {
+    pub fn new(
+        repo: Arc<dyn ApiKeyRepository>,
+        cache: Arc<CredentialCache>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            repo,
+            cache,
+            poll_interval: poll_interval.max(Duration::from_millis(10)),
+            cursor: tokio::sync::Mutex::new(0),
+        }
+    }
+
+    /// 跨实例传播延迟的上限
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// 从当前最新的变更开始跟踪：启动前的变更与刚创建的空缓存无关
+    pub async fn start_from_latest(&self) -> Result<()> {
+        let latest = self.repo.latest_api_key_change_seq().await?;
+        *self.cursor.lock().await = latest;
+        Ok(())
+    }
+
+    /// 读取游标之后的全部变更并失效对应条目，返回本次处理的变更
+    pub async fn sync_once(&self) -> Result<Vec<ApiKeyChange>> {
+        let mut cursor = self.cursor.lock().await;
+        let mut applied = Vec::new();
+        loop {
+            let batch = self
+                .repo
+                .list_api_key_changes(*cursor, CHANGE_BATCH_SIZE)
+                .await?;
+            let exhausted = (batch.len() as u64) < CHANGE_BATCH_SIZE;
+            for change in batch {
+                self.cache.invalidate_key(&change.key_id);
+                *cursor = (*cursor).max(change.seq);
+                applied.push(change);
+            }
+            if exhausted {
+                return Ok(applied);
+            }
+        }
+    }
+
+    /// 一次轮询；读取变更失败时清空缓存，无法确认吊销状态时宁可重新校验
+    pub async fn poll(&self) {
+        if let Err(e) = self.sync_once().await {
+            self.cache.clear();
+            tracing::warn!(
+                event = "credential_cache_sync_failed",
+                error = %e,
+                "failed to read api key changes, credential cache cleared"
+            );
+        }
+    }
+
+    /// 启动周期轮询
+    pub fn spawn(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let sync = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(sync.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                sync.poll().await;
+            }
+        })
+    }
+}
+
+/// 本实例发起的变更目标
+#[derive(Clone, Copy)]
+enum LocalChange<'a> {
+    Record(Uuid),
+    Key(&'a str),
+}
+
+impl LocalChange<'_> {
+    fn matches(&self, change: &ApiKeyChange) -> bool {
+        match self {
+            LocalChange::Record(id) => change.api_key_id == *id,
+            LocalChange::Key(key_id) => change.key_id == *key_id,
+        }
+    }
+}
+
+/// 带已校验凭证缓存的 [`ApiKeyRepository`] 装饰器。
+///
+/// 认证中间件、管理接口和生命周期检查共用同一个实例，
+/// 经任一入口的吊销都会立即失效本地缓存。
+pub struct CachedApiKeyRepository {
+    inner: Arc<dyn ApiKeyRepository>,
+    cache: Arc<CredentialCache>,
+    sync: Arc<RevocationSync>,
+    notifier: Option<Arc<dyn RevocationNotifier>>,
+}
+
+impl CachedApiKeyRepository {
+    pub fn new(
+        inner: Arc<dyn ApiKeyRepository>,
+        cache: Arc<CredentialCache>,
+        sync: Arc<RevocationSync>,
+    ) -> Self {
+        Self {
+            inner,
+            cache,
+            sync,
+            notifier: None,
+        }
+    }
+
+    pub fn with_notifier(mut self, notifier: Arc<dyn RevocationNotifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    pub fn cache(&self) -> &Arc<CredentialCache> {
+        &self.cache
+    }
+
+    /// 本地变更完成后：立即同步变更表失效本地条目，并推送给其他实例。
+    /// 变更表里找不到对应记录（仓库不记录变更或写入失败）时，
+    /// 已知 key_id 的直接失效，否则清空本地缓存。
+    async fn after_change(&self, local: LocalChange<'_>) {
+        let synced = self.sync.sync_once().await.unwrap_or_else(|e| {
+            tracing::warn!(
+                event = "credential_cache_sync_failed",
+                error = %e,
+                "failed to read api key changes after local change"
+            );
+            Vec::new()
+        });
+        let mut key_ids: Vec<String> = synced
+            .into_iter()
+            .filter(|change| local.matches(change))
+            .map(|change| change.key_id)
+            .collect();
+        if let LocalChange::Key(key_id) = local {
+            if key_ids.is_empty() {
+                key_ids.push(key_id.to_string());
+            }
+        }
+        if key_ids.is_empty() {
+            self.cache.clear();
+        }
+        key_ids.dedup();
+        for key_id in &key_ids {
+            self.cache.invalidate_key(key_id);
+            if let Some(notifier) = &self.notifier {
+                if let Err(e) = notifier.publish(key_id).await {
+                    tracing::warn!(
+                        event = "credential_revocation_publish_failed",
+                        key_id = %key_id,
+                        error = %e,
+                        "failed to publish api key change"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for CachedApiKeyRepository {
+    async fn create_api_key(&self, request: &CreateApiKeyRequest) -> Result<ApiKeyWithSecret> {
+        self.inner.create_api_key(request).await
+    }
+
+    async fn get_api_key_by_id(&self, key_id: &str) -> Result<Option<ApiKeyInfo>> {
+        self.inner.get_api_key_by_id(key_id).await
+    }
+
+    async fn get_api_key(&self, id: Uuid) -> Result<Option<ApiKeyInfo>> {
+        self.inner.get_api_key(id).await
+    }
+
+    async fn validate_api_key(
+        &self,
+        key_id: &str,
+        key_secret: &str,
+    ) -> Result<Option<(Option<Uuid>, ApiKeyRole)>> {
+        Ok(self
+            .validate_api_key_with_scopes(key_id, key_secret)
+            .await?
+            .map(|(workspace_id, role, _)| (workspace_id, role)))
+    }
+
+    async fn validate_api_key_with_scopes(
+        &self,
+        key_id: &str,
+        key_secret: &str,
+    ) -> Result<Option<(Option<Uuid>, ApiKeyRole, ApiKeyScopes)>> {
+        if let Some(credential) = self.cache.get(key_id, key_secret) {
+            return Ok(Some((
+                credential.workspace_id,
+                credential.role,
+                credential.scopes,
+            )));
+        }
+
+        let generation = self.cache.generation();
+        let result = self
+            .inner
+            .validate_api_key_with_scopes(key_id, key_secret)
+            .await?;
+        if let Some((workspace_id, role, scopes)) = &result {
+            self.cache.insert(
+                generation,
+                key_id,
+                key_secret,
+                VerifiedCredential {
+                    workspace_id: *workspace_id,
+                    role: role.clone(),
+                    scopes: scopes.clone(),
+                },
+            );
+        }
+        Ok(result)
+    }
+
+    async fn get_signing_key(&self, key_id: &str) -> Result<Option<ApiKeySigningKey>> {
+        self.inner.get_signing_key(key_id).await
+    }
+
+    async fn list_api_keys(
+        &self,
+        workspace_id: Uuid,
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<ApiKeyInfo>> {
+        self.inner.list_api_keys(workspace_id, limit, offset).await
+    }
+
+    async fn delete_api_key(&self, id: Uuid) -> Result<()> {
+        self.inner.delete_api_key(id).await?;
+        self.after_change(LocalChange::Record(id)).await;
+        Ok(())
+    }
+
+    async fn revoke_api_key(&self, id: Uuid) -> Result<()> {
+        self.inner.revoke_api_key(id).await?;
+        self.after_change(LocalChange::Record(id)).await;
+        Ok(())
+    }
+
+    async fn update_last_used(&self, id: Uuid) -> Result<()> {
+        self.inner.update_last_used(id).await
+    }
+
+    async fn get_admin_api_key(&self, workspace_id: Uuid) -> Result<Option<ApiKeyInfo>> {
+        self.inner.get_admin_api_key(workspace_id).await
+    }
+
+    async fn count_api_keys(&self, workspace_id: Uuid) -> Result<u64> {
+        self.inner.count_api_keys(workspace_id).await
+    }
+
+    async fn count_enabled_api_keys(&self, workspace_id: Uuid) -> Result<u64> {
+        self.inner.count_enabled_api_keys(workspace_id).await
+    }
+
+    async fn create_api_key_within_limit(
+        &self,
+        request: &CreateApiKeyRequest,
+        limit: u64,
+    ) -> Result<Option<ApiKeyWithSecret>> {
+        self.inner.create_api_key_within_limit(request, limit).await
+    }
+
+    async fn rotate_api_key(
+        &self,
+        key_id: &str,
+        grace_period_seconds: u64,
+    ) -> Result<ApiKeyWithSecret> {
+        let rotated = self
+            .inner
+            .rotate_api_key(key_id, grace_period_seconds)
+            .await?;
+        self.after_change(LocalChange::Key(key_id)).await;
+        Ok(rotated)
+    }
+
+    async fn rotate_api_key_with_signing(
+        &self,
+        key_id: &str,
+        grace_period_seconds: u64,
+        request_signing: Option<bool>,
+    ) -> Result<ApiKeyWithSecret> {
+        let rotated = self
+            .inner
+            .rotate_api_key_with_signing(key_id, grace_period_seconds, request_signing)
+            .await?;
+        self.after_change(LocalChange::Key(key_id)).await;
+        Ok(rotated)
+    }
+
+    async fn get_keys_older_than(&self, age_threshold_days: i64) -> Result<Vec<ApiKeyInfo>> {
+        self.inner.get_keys_older_than(age_threshold_days).await
+    }
+
+    async fn list_enabled_api_keys(&self) -> Result<Vec<ApiKeyInfo>> {
+        self.inner.list_enabled_api_keys().await
+    }
+
+    async fn list_api_key_changes(&self, after_seq: i64, limit: u64) -> Result<Vec<ApiKeyChange>> {
+        self.inner.list_api_key_changes(after_seq, limit).await
+    }
+
+    async fn latest_api_key_change_seq(&self) -> Result<i64> {
+        self.inner.latest_api_key_change_seq().await
+    }
+
+    async fn latest_api_key_rotations(&self) -> Result<HashMap<Uuid, chrono::NaiveDateTime>> {
+        self.inner.latest_api_key_rotations().await
+    }
+
+    async fn claim_api_key_notification(
+        &self,
+        key_id: &str,
+        reason: &str,
+        now: chrono::NaiveDateTime,
+    ) -> Result<bool> {
+        self.inner
+            .claim_api_key_notification(key_id, reason, now)
+            .await
+    }
+
+    async fn release_api_key_notifications(&self, active: &[(String, String)]) -> Result<u64> {
+        self.inner.release_api_key_notifications(active).await
+    }
+
+    async fn claim_request_nonce(
+        &self,
+        key_id: &str,
+        nonce: &str,
+        expires_at: chrono::NaiveDateTime,
+    ) -> Result<bool> {
+        self.inner
+            .claim_request_nonce(key_id, nonce, expires_at)
+            .await
+    }
+
+    async fn prune_request_nonces(&self, before: chrono::NaiveDateTime) -> Result<u64> {
+        self.inner.prune_request_nonces(before).await
+    }
+}
+
+/// 通过 etcd 推送 key 变更：写入 `{prefix}{key_id}`（关联 TTL 与缓存相同的 lease），
+/// 各实例 watch 该前缀并失效对应条目
+#[cfg(feature = "etcd")]
+pub struct EtcdRevocationNotifier {
+    client: Arc<dyn crate::core::coordinator::EtcdClientOps>,
+    prefix: String,
+    lease_ttl_seconds: i64,
+}
+
+#[cfg(feature = "etcd")]
+impl EtcdRevocationNotifier {
+    pub fn new(
+        client: Arc<dyn crate::core::coordinator::EtcdClientOps>,
+        config: &CredentialCacheConfig,
+    ) -> Self {
+        Self {
+            client,
+            prefix: config.etcd_prefix.clone(),
+            lease_ttl_seconds: config.ttl_seconds.clamp(1, i64::MAX as u64) as i64,
+        }
+    }
+
+    /// 启动 watch；断开后按 `retry_interval` 重连，断开期间的变更由变更表轮询兜底
+    pub fn spawn_watch(
+        &self,
+        cache: Arc<CredentialCache>,
+        retry_interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let client = Arc::clone(&self.client);
+        let prefix = self.prefix.clone();
+        tokio::spawn(async move {
+            loop {
+                match client.watch_prefix_puts(&prefix).await {
+                    Ok(mut events) => {
+                        while let Some(key) = events.recv().await {
+                            if let Some(key_id) = key.strip_prefix(&prefix) {
+                                cache.invalidate_key(key_id);
+                            }
+                        }
+                        tracing::warn!(
+                            event = "credential_revocation_watch_closed",
+                            prefix = %prefix,
+                            "api key change watch closed, reconnecting"
+                        );
+                    }
+                    Err(e) => tracing::warn!(
+                        event = "credential_revocation_watch_failed",
+                        prefix = %prefix,
+                        error = %e,
+                        "failed to watch api key changes"
+                    ),
+                }
+                tokio::time::sleep(retry_interval).await;
+            }
+        })
+    }
+}
+
+#[cfg(feature = "etcd")]
+#[async_trait]
+impl RevocationNotifier for EtcdRevocationNotifier {
+    async fn publish(&self, key_id: &str) -> Result<()> {
+        let to_core = |e: crate::core::coordinator::EtcdError| {
+            crate::core::CoreError::EtcdError(e.to_string())
+        };
+        let lease_id = self
+            .client
+            .lease_grant(self.lease_ttl_seconds)
+            .await
+            .map_err(to_core)?;
+        self.client
+            .kv_put_with_lease(
+                &format!("{}{}", self.prefix, key_id),
+                key_id.as_bytes().to_vec(),
+                lease_id,
+            )
+            .await
+            .map_err(to_core)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::{ApiKeyChangeKind, ApiKeyResponse};
+    use std::sync::atomic::{AtomicBool, AtomicUsize};
+
+    struct StoredKey {
+        id: Uuid,
+        secret: String,
+        enabled: bool,
+    }
+
+    /// 模拟多个实例共享的数据库：key 表 + 变更表
+    #[derive(Default)]
+    struct SharedStore {
+        keys: Mutex<HashMap<String, StoredKey>>,
+        changes: Mutex<Vec<ApiKeyChange>>,
+        validations: AtomicUsize,
+        fail_changes: AtomicBool,
+    }
+
+    impl SharedStore {
+        fn add_key(&self, key_id: &str, secret: &str) -> Uuid {
+            let id = Uuid::new_v4();
+            self.keys.lock().insert(
+                key_id.to_string(),
+                StoredKey {
+                    id,
+                    secret: secret.to_string(),
+                    enabled: true,
+                },
+            );
+            id
+        }
+
+        fn record(&self, api_key_id: Uuid, key_id: &str, kind: ApiKeyChangeKind) {
+            let mut changes = self.changes.lock();
+            let seq = changes.len() as i64 + 1;
+            changes.push(ApiKeyChange {
+                seq,
+                api_key_id,
+                key_id: key_id.to_string(),
+                kind,
+                changed_at: chrono::Utc::now().naive_utc(),
+            });
+        }
+
+        fn key_id_of(&self, id: Uuid) -> Option<String> {
+            self.keys
+                .lock()
+                .iter()
+                .find(|(_, key)| key.id == id)
+                .map(|(key_id, _)| key_id.clone())
+        }
+    }
+
+    #[async_trait]
+    impl ApiKeyRepository for SharedStore {
+        async fn create_api_key(&self, _: &CreateApiKeyRequest) -> Result<ApiKeyWithSecret> {
+            unimplemented!()
+        }
+        async fn get_api_key_by_id(&self, _: &str) -> Result<Option<ApiKeyInfo>> {
+            unimplemented!()
+        }
+        async fn validate_api_key(
+            &self,
+            key_id: &str,
+            key_secret: &str,
+        ) -> Result<Option<(Option<Uuid>, ApiKeyRole)>> {
+            self.validations.fetch_add(1, Ordering::SeqCst);
+            Ok(self
+                .keys
+                .lock()
+                .get(key_id)
+                .filter(|key| key.enabled && key.secret == key_secret)
+                .map(|_| (None, ApiKeyRole::User)))
+        }
+        async fn list_api_keys(
+            &self,
+            _: Uuid,
+            _: Option<u32>,
+            _: Option<u32>,
+        ) -> Result<Vec<ApiKeyInfo>> {
+            unimplemented!()
+        }
+        async fn delete_api_key(&self, id: Uuid) -> Result<()> {
+            let key_id = self.key_id_of(id).expect("key exists");
+            self.keys.lock().remove(&key_id);
+            self.record(id, &key_id, ApiKeyChangeKind::Deleted);
+            Ok(())
+        }
+        async fn revoke_api_key(&self, id: Uuid) -> Result<()> {
+            let key_id = self.key_id_of(id).expect("key exists");
+            if let Some(key) = self.keys.lock().get_mut(&key_id) {
+                key.enabled = false;
+            }
+            self.record(id, &key_id, ApiKeyChangeKind::Revoked);
+            Ok(())
+        }
+        async fn update_last_used(&self, _: Uuid) -> Result<()> {
+            unimplemented!()
+        }
+        async fn get_admin_api_key(&self, _: Uuid) -> Result<Option<ApiKeyInfo>> {
+            unimplemented!()
+        }
+        async fn count_api_keys(&self, _: Uuid) -> Result<u64> {
+            unimplemented!()
+        }
+        async fn rotate_api_key(&self, key_id: &str, _: u64) -> Result<ApiKeyWithSecret> {
+            let id = {
+                let mut keys = self.keys.lock();
+                let key = keys.get_mut(key_id).expect("key exists");
+                key.secret = "rotated-secret".to_string();
+                key.id
+            };
+            self.record(id, key_id, ApiKeyChangeKind::Rotated);
+            let now = chrono::Utc::now().naive_utc();
+            Ok(ApiKeyWithSecret {
+                key: ApiKeyResponse {
+                    id,
+                    key_id: key_id.to_string(),
+                    key_prefix: "nino_".to_string(),
+                    name: key_id.to_string(),
+                    description: None,
+                    role: ApiKeyRole::User,
+                    rate_limit: 100,
+                    enabled: true,
+                    expires_at: None,
+                    created_at: now,
+                    scopes: ApiKeyScopes::unrestricted(),
+                },
+                key_secret: "rotated-secret".to_string(),
+            })
+        }
+        async fn get_keys_older_than(&self, _: i64) -> Result<Vec<ApiKeyInfo>> {
+            unimplemented!()
+        }
+        async fn list_api_key_changes(
+            &self,
+            after_seq: i64,
+            limit: u64,
+        ) -> Result<Vec<ApiKeyChange>> {
+            if self.fail_changes.load(Ordering::SeqCst) {
+                return Err(crate::core::CoreError::DatabaseError(
+                    "changes unavailable".to_string(),
+                ));
+            }
+            Ok(self
+                .changes
+                .lock()
+                .iter()
+                .filter(|change| change.seq > after_seq)
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+        async fn latest_api_key_change_seq(&self) -> Result<i64> {
+            Ok(self.changes.lock().len() as i64)
+        }
+    }
+
+    struct Instance {
+        repo: CachedApiKeyRepository,
+        sync: Arc<RevocationSync>,
+    }
+
+    fn instance(store: &Arc<SharedStore>, poll_interval: Duration) -> Instance {
+        let inner: Arc<dyn ApiKeyRepository> = store.clone();
+        let cache = Arc::new(CredentialCache::new(Duration::from_secs(60), 100));
+        let sync = Arc::new(RevocationSync::new(
+            Arc::clone(&inner),
+            Arc::clone(&cache),
+            poll_interval,
+        ));
+        Instance {
+            repo: CachedApiKeyRepository::new(inner, cache, Arc::clone(&sync)),
+            sync,
+        }
+    }
+
+    fn credential() -> VerifiedCredential {
+        VerifiedCredential {
+            workspace_id: None,
+            role: ApiKeyRole::User,
+            scopes: ApiKeyScopes::unrestricted(),
+        }
+    }
+
+    #[test]
+    fn test_cache_entries_expire_after_ttl() {
+        let cache = CredentialCache::new(Duration::from_millis(20), 10);
+        cache.insert(cache.generation(), "nino_a", "secret", credential());
+        assert_eq!(cache.get("nino_a", "secret"), Some(credential()));
+        assert_eq!(cache.get("nino_a", "other"), None);
+
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(cache.get("nino_a", "secret"), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_rejects_insert_after_concurrent_invalidation() {
+        let cache = CredentialCache::new(Duration::from_secs(60), 10);
+        let generation = cache.generation();
+        cache.invalidate_key("nino_a");
+
+        assert!(!cache.insert(generation, "nino_a", "secret", credential()));
+        assert_eq!(cache.get("nino_a", "secret"), None);
+    }
+
+    #[test]
+    fn test_cache_evicts_when_full() {
+        let cache = CredentialCache::new(Duration::from_secs(60), 2);
+        for key_id in ["nino_a", "nino_b", "nino_c"] {
+            cache.insert(cache.generation(), key_id, "secret", credential());
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get("nino_c", "secret").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_skips_inner_validation() {
+        let store = Arc::new(SharedStore::default());
+        store.add_key("nino_a", "secret");
+        let node = instance(&store, Duration::from_secs(1));
+
+        for _ in 0..3 {
+            let result = node
+                .repo
+                .validate_api_key("nino_a", "secret")
+                .await
+                .unwrap();
+            assert_eq!(result, Some((None, ApiKeyRole::User)));
+        }
+        assert_eq!(store.validations.load(Ordering::SeqCst), 1);
+
+        // 失败结果不缓存
+        for _ in 0..2 {
+            assert!(node
+                .repo
+                .validate_api_key("nino_a", "wrong")
+                .await
+                .unwrap()
+                .is_none());
+        }
+        assert_eq!(store.validations.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_local_revoke_and_rotate_invalidate_immediately() {
+        let store = Arc::new(SharedStore::default());
+        let revoked = store.add_key("nino_a", "secret");
+        store.add_key("nino_b", "secret");
+        let node = instance(&store, Duration::from_secs(60));
+        node.sync.start_from_latest().await.unwrap();
+
+        for key_id in ["nino_a", "nino_b"] {
+            assert!(node
+                .repo
+                .validate_api_key(key_id, "secret")
+                .await
+                .unwrap()
+                .is_some());
+        }
+
+        node.repo.revoke_api_key(revoked).await.unwrap();
+        node.repo.rotate_api_key("nino_b", 0).await.unwrap();
+
+        assert!(node
+            .repo
+            .validate_api_key("nino_a", "secret")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(node
+            .repo
+            .validate_api_key("nino_b", "secret")
+            .await
+            .unwrap()
+            .is_none());
+        assert!(node
+            .repo
+            .validate_api_key("nino_b", "rotated-secret")
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_remote_change_applied_by_next_poll() {
+        let store = Arc::new(SharedStore::default());
+        let id = store.add_key("nino_a", "secret");
+        let a = instance(&store, Duration::from_secs(60));
+        let b = instance(&store, Duration::from_secs(60));
+        b.sync.start_from_latest().await.unwrap();
+
+        assert!(b
+            .repo
+            .validate_api_key("nino_a", "secret")
+            .await
+            .unwrap()
+            .is_some());
+        a.repo.delete_api_key(id).await.unwrap();
+
+        // 下一次轮询之前 B 仍命中缓存，轮询之后立即失效
+        assert!(b.repo.cache().get("nino_a", "secret").is_some());
+        let applied = b.sync.sync_once().await.unwrap();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].kind, ApiKeyChangeKind::Deleted);
+        assert!(b
+            .repo
+            .validate_api_key("nino_a", "secret")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_revocation_propagates_within_poll_interval() {
+        let poll_interval = Duration::from_millis(50);
+        let store = Arc::new(SharedStore::default());
+        let id = store.add_key("nino_a", "secret");
+        let a = instance(&store, poll_interval);
+        let b = instance(&store, poll_interval);
+        b.sync.start_from_latest().await.unwrap();
+        let poller = b.sync.spawn();
+
+        assert!(b
+            .repo
+            .validate_api_key("nino_a", "secret")
+            .await
+            .unwrap()
+            .is_some());
+        let revoked_at = tokio::time::Instant::now();
+        a.repo.revoke_api_key(id).await.unwrap();
+
+        while b.repo.cache().get("nino_a", "secret").is_some() {
+            assert!(
+                revoked_at.elapsed() <= poll_interval * 4,
+                "revocation not propagated within the poll interval"
+            );
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(b
+            .repo
+            .validate_api_key("nino_a", "secret")
+            .await
+            .unwrap()
+            .is_none());
+        poller.abort();
+    }
+
+    #[tokio::test]
+    async fn test_poll_failure_clears_cache() {
+        let store = Arc::new(SharedStore::default());
+        store.add_key("nino_a", "secret");
+        let node = instance(&store, Duration::from_secs(60));
+        node.repo
+            .validate_api_key("nino_a", "secret")
+            .await
+            .unwrap();
+        assert_eq!(node.repo.cache().len(), 1);
+
+        store.fail_changes.store(true, Ordering::SeqCst);
+        node.sync.poll().await;
+        assert!(node.repo.cache().is_empty());
+    }
+}
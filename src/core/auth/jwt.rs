@@ -0,0 +1,519 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JWT / OIDC bearer token validation.
+//!
+//! 校验 `Authorization: Bearer <jwt>`：只接受 RS256 / ES256，公钥来自 JWKS
+//! （本地文件或 URL）。JWKS 按 `jwks_refresh_seconds` 定期刷新；遇到未知
+//! `kid` 时提前刷新一次（至少间隔 [`MIN_REFRESH_INTERVAL`]），以跟上 IdP 的
+//! 密钥轮换。刷新失败时保留上一次加载的公钥。
+//!
+//! claim 映射（均可配置，支持 `a.b` 嵌套路径）：
+//! - `role_claim` 含 `admin_role` → Admin，否则 User
+//! - `workspace_claim` → workspace ID（UUID），User 必须携带
+//! - `scopes_claim` → 作用域；缺失时不授予任何作用域，
+//!   `missing_scopes_unrestricted` 开启时不受限
+
+use crate::core::auth::ApiKeyScopes;
+use crate::core::config::JwtConfig;
+use crate::core::database::ApiKeyRole;
+use crate::core::types::CoreError;
+use arc_swap::ArcSwap;
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::jwk::{AlgorithmParameters, EllipticCurve, JwkSet, KeyAlgorithm, PublicKeyUse};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use parking_lot::Mutex;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 因未知 `kid` 触发刷新的最短间隔，避免伪造 `kid` 放大对 IdP 的请求
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+const JWKS_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 通过校验的 token 持有者
+#[derive(Debug, Clone, PartialEq)]
+pub struct JwtPrincipal {
+    /// `sub` claim
+    pub subject: String,
+    /// Admin 为 `None`
+    pub workspace_id: Option<uuid::Uuid>,
+    pub role: ApiKeyRole,
+    pub scopes: ApiKeyScopes,
+}
+
+#[derive(Clone)]
+struct JwksKey {
+    algorithm: Algorithm,
+    key: DecodingKey,
+}
+
+/// `kid` → 公钥；没有 `kid` 的 JWK 以空串为键
+type KeyMap = HashMap<String, JwksKey>;
+
+pub struct JwtValidator {
+    config: JwtConfig,
+    keys: ArcSwap<KeyMap>,
+    last_refresh: Mutex<Instant>,
+    client: reqwest::Client,
+}
+
+impl JwtValidator {
+    /// 加载 JWKS 并构造校验器；JWKS 不可读或不含 RS256 / ES256 公钥时返回
+    /// `ConfigurationError`
+    pub async fn new(config: JwtConfig) -> Result<Self, CoreError> {
+        let client = reqwest::Client::builder()
+            .timeout(JWKS_FETCH_TIMEOUT)
+            .build()
+            .map_err(|e| CoreError::ConfigurationError(format!("JWKS client: {}", e)))?;
+        let keys = load_jwks(&config, &client).await?;
+        tracing::info!(event = "jwks_loaded", keys = keys.len(), "Loaded JWKS");
+        Ok(Self {
+            config,
+            keys: ArcSwap::from_pointee(keys),
+            last_refresh: Mutex::new(Instant::now()),
+            client,
+        })
+    }
+
+    /// 重新加载 JWKS，返回可用公钥数量；失败时保留原有公钥
+    pub async fn refresh(&self) -> Result<usize, CoreError> {
+        *self.last_refresh.lock() = Instant::now();
+        let keys = load_jwks(&self.config, &self.client).await?;
+        let count = keys.len();
+        self.keys.store(Arc::new(keys));
+        Ok(count)
+    }
+
+    /// 按 `jwks_refresh_seconds` 在后台定期刷新 JWKS
+    pub fn spawn_refresh(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let validator = Arc::clone(self);
+        let period =
+            Duration::from_secs(self.config.jwks_refresh_seconds).max(MIN_REFRESH_INTERVAL);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            // 第一次 tick 立即返回，启动时已经加载过
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = validator.refresh().await {
+                    tracing::warn!(
+                        event = "jwks_refresh_failed",
+                        error = %e,
+                        "Failed to refresh JWKS, keeping previous keys"
+                    );
+                }
+            }
+        })
+    }
+
+    /// 校验 token 并映射 claim；失败时返回用于日志的原因标识
+    pub async fn validate(&self, token: &str) -> Result<JwtPrincipal, &'static str> {
+        let header = decode_header(token).map_err(|_| "malformed_token")?;
+        if !matches!(header.alg, Algorithm::RS256 | Algorithm::ES256) {
+            return Err("unsupported_algorithm");
+        }
+        let kid = header.kid.unwrap_or_default();
+        let key = match self.key(&kid) {
+            Some(key) => key,
+            None => {
+                self.refresh_for_unknown_kid(&kid).await;
+                self.key(&kid).ok_or("unknown_key_id")?
+            }
+        };
+        // 以 JWKS 中登记的算法为准，防止 header 篡改算法
+        if key.algorithm != header.alg {
+            return Err("algorithm_mismatch");
+        }
+
+        let claims = decode::<Value>(token, &key.key, &self.validation(key.algorithm))
+            .map_err(|e| match e.kind() {
+                ErrorKind::ExpiredSignature => "token_expired",
+                ErrorKind::ImmatureSignature => "token_not_yet_valid",
+                ErrorKind::InvalidIssuer => "invalid_issuer",
+                ErrorKind::InvalidAudience => "invalid_audience",
+                ErrorKind::InvalidSignature => "invalid_signature",
+                ErrorKind::MissingRequiredClaim(_) => "missing_required_claim",
+                _ => "invalid_token",
+            })?
+            .claims;
+        self.principal(&claims)
+    }
+
+    fn key(&self, kid: &str) -> Option<JwksKey> {
+        self.keys.load().get(kid).cloned()
+    }
+
+    async fn refresh_for_unknown_kid(&self, kid: &str) {
+        {
+            // 占位后再刷新，并发的未知 kid 请求只触发一次
+            let mut last_refresh = self.last_refresh.lock();
+            if last_refresh.elapsed() < MIN_REFRESH_INTERVAL {
+                return;
+            }
+            *last_refresh = Instant::now();
+        }
+        tracing::info!(event = "jwks_unknown_kid", kid = kid, "Refreshing JWKS");
+        if let Err(e) = self.refresh().await {
+            tracing::warn!(
+                event = "jwks_refresh_failed",
+                error = %e,
+                "Failed to refresh JWKS, keeping previous keys"
+            );
+        }
+    }
+
+    fn validation(&self, algorithm: Algorithm) -> Validation {
+        let mut validation = Validation::new(algorithm);
+        validation.leeway = self.config.leeway_seconds;
+        let mut required = vec!["exp", "sub"];
+        if !self.config.issuer.is_empty() {
+            validation.set_issuer(&[&self.config.issuer]);
+            required.push("iss");
+        }
+        if self.config.audience.is_empty() {
+            validation.validate_aud = false;
+        } else {
+            validation.set_audience(&[&self.config.audience]);
+            required.push("aud");
+        }
+        validation.set_required_spec_claims(&required);
+        validation
+    }
+
+    fn principal(&self, claims: &Value) -> Result<JwtPrincipal, &'static str> {
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .filter(|sub| !sub.is_empty())
+            .ok_or("missing_subject")?
+            .to_string();
+
+        let is_admin = claim(claims, &self.config.role_claim)
+            .is_some_and(|roles| claim_strings(roles).contains(&self.config.admin_role.as_str()));
+        let (role, workspace_id) = if is_admin {
+            (ApiKeyRole::Admin, None)
+        } else {
+            let workspace_id = claim(claims, &self.config.workspace_claim)
+                .and_then(Value::as_str)
+                .and_then(|id| uuid::Uuid::parse_str(id).ok())
+                .ok_or("missing_workspace_claim")?;
+            (ApiKeyRole::User, Some(workspace_id))
+        };
+
+        let scopes = match claim(claims, &self.config.scopes_claim) {
+            Some(value) => ApiKeyScopes::from_claim(claim_strings(value)),
+            None if self.config.missing_scopes_unrestricted => ApiKeyScopes::unrestricted(),
+            None => ApiKeyScopes::from_claim(std::iter::empty()),
+        };
+
+        Ok(JwtPrincipal {
+            subject,
+            workspace_id,
+            role,
+            scopes,
+        })
+    }
+}
+
+/// 按 `a.b` 路径取 claim
+fn claim<'a>(claims: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .try_fold(claims, |value, segment| value.get(segment))
+}
+
+/// 空格分隔的字符串或字符串数组
+fn claim_strings(value: &Value) -> Vec<&str> {
+    match value {
+        Value::String(s) => s.split_whitespace().collect(),
+        Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+async fn load_jwks(config: &JwtConfig, client: &reqwest::Client) -> Result<KeyMap, CoreError> {
+    let error = |e: String| CoreError::ConfigurationError(format!("JWKS: {}", e));
+    let set: JwkSet = if !config.jwks_url.is_empty() {
+        client
+            .get(&config.jwks_url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|e| error(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| error(e.to_string()))?
+    } else if !config.jwks_path.is_empty() {
+        let content = tokio::fs::read_to_string(&config.jwks_path)
+            .await
+            .map_err(|e| error(format!("{}: {}", config.jwks_path, e)))?;
+        serde_json::from_str(&content).map_err(|e| error(e.to_string()))?
+    } else {
+        return Err(error(
+            "either jwks_url or jwks_path must be set".to_string(),
+        ));
+    };
+
+    let keys = parse_jwks(&set);
+    if keys.is_empty() {
+        return Err(error("no RS256 / ES256 signing keys".to_string()));
+    }
+    Ok(keys)
+}
+
+/// 只保留签名用途的 RS256 与 ES256（P-256）公钥
+fn parse_jwks(set: &JwkSet) -> KeyMap {
+    let mut keys = KeyMap::new();
+    for jwk in &set.keys {
+        if matches!(jwk.common.public_key_use, Some(PublicKeyUse::Encryption)) {
+            continue;
+        }
+        let algorithm = match (&jwk.common.key_algorithm, &jwk.algorithm) {
+            (Some(KeyAlgorithm::RS256) | None, AlgorithmParameters::RSA(_)) => Algorithm::RS256,
+            (Some(KeyAlgorithm::ES256) | None, AlgorithmParameters::EllipticCurve(params))
+                if matches!(params.curve, EllipticCurve::P256) =>
+            {
+                Algorithm::ES256
+            }
+            _ => continue,
+        };
+        match DecodingKey::from_jwk(jwk) {
+            Ok(key) => {
+                let kid = jwk.common.key_id.clone().unwrap_or_default();
+                keys.insert(kid, JwksKey { algorithm, key });
+            }
+            Err(e) => tracing::warn!(
+                event = "jwks_key_ignored",
+                kid = ?jwk.common.key_id,
+                error = %e,
+                "Ignoring unusable JWK"
+            ),
+        }
+    }
+    keys
+}
+
+/// 测试用的本地 ES256 密钥与 JWKS 文件
+#[cfg(test)]
+pub(crate) mod testing {
+    use super::*;
+    use base64::Engine;
+    use jsonwebtoken::{encode, EncodingKey, Header};
+    use serde_json::json;
+
+    pub(crate) struct TestKey {
+        kid: &'static str,
+        key_pair: rcgen::KeyPair,
+    }
+
+    impl TestKey {
+        pub(crate) fn generate(kid: &'static str) -> Self {
+            Self {
+                kid,
+                key_pair: rcgen::KeyPair::generate_for(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap(),
+            }
+        }
+
+        fn jwk(&self) -> Value {
+            // 未压缩点：0x04 || x || y
+            let raw = self.key_pair.public_key_raw();
+            let b64 = |bytes: &[u8]| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+            json!({
+                "kty": "EC",
+                "crv": "P-256",
+                "use": "sig",
+                "alg": "ES256",
+                "kid": self.kid,
+                "x": b64(&raw[1..33]),
+                "y": b64(&raw[33..65]),
+            })
+        }
+
+        pub(crate) fn sign(&self, claims: &Value) -> String {
+            let mut header = Header::new(Algorithm::ES256);
+            header.kid = Some(self.kid.to_string());
+            let key = EncodingKey::from_ec_pem(self.key_pair.serialize_pem().as_bytes()).unwrap();
+            encode(&header, claims, &key).unwrap()
+        }
+    }
+
+    pub(crate) fn write_jwks(file: &tempfile::NamedTempFile, keys: &[&TestKey]) {
+        let set = json!({ "keys": keys.iter().map(|k| k.jwk()).collect::<Vec<_>>() });
+        std::fs::write(file.path(), set.to_string()).unwrap();
+    }
+
+    /// 以 JWKS 文件构造校验器，其余配置取默认值
+    pub(crate) async fn validator(
+        file: &tempfile::NamedTempFile,
+        config: JwtConfig,
+    ) -> JwtValidator {
+        JwtValidator::new(JwtConfig {
+            enabled: true,
+            jwks_path: file.path().to_string_lossy().into_owned(),
+            ..config
+        })
+        .await
+        .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::{write_jwks, TestKey};
+    use super::*;
+    use serde_json::json;
+
+    const WORKSPACE: &str = "0192f0c4-8d3e-7c2a-9b1f-3e5d6a7b8c9d";
+
+    async fn validator(file: &tempfile::NamedTempFile) -> JwtValidator {
+        super::testing::validator(
+            file,
+            JwtConfig {
+                issuer: "https://idp.internal".to_string(),
+                role_claim: "realm_access.roles".to_string(),
+                ..JwtConfig::default()
+            },
+        )
+        .await
+    }
+
+    fn claims(extra: Value) -> Value {
+        let mut claims = json!({
+            "sub": "svc-orders",
+            "iss": "https://idp.internal",
+            "exp": chrono::Utc::now().timestamp() + 300,
+            "workspace_id": WORKSPACE,
+        });
+        claims
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        claims
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_maps_claims() {
+        let key = TestKey::generate("k1");
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write_jwks(&file, &[&key]);
+        let validator = validator(&file).await;
+
+        let token = key.sign(&claims(json!({ "scope": "openid generate:orders" })));
+        let principal = validator.validate(&token).await.unwrap();
+        assert_eq!(principal.subject, "svc-orders");
+        assert_eq!(principal.role, ApiKeyRole::User);
+        assert_eq!(principal.workspace_id, Some(WORKSPACE.parse().unwrap()));
+        assert!(principal
+            .scopes
+            .allows(&crate::core::auth::Scope::generate("orders", "created")));
+        assert!(!principal.scopes.allows(&crate::core::auth::Scope::Parse));
+
+        let admin = key.sign(&claims(json!({
+            "realm_access": { "roles": ["admin"] },
+            "scope": "*",
+        })));
+        let principal = validator.validate(&admin).await.unwrap();
+        assert_eq!(principal.role, ApiKeyRole::Admin);
+        assert_eq!(principal.workspace_id, None);
+        assert!(principal.scopes.is_unrestricted());
+    }
+
+    #[tokio::test]
+    async fn test_missing_scopes_claim_grants_no_scopes_unless_configured() {
+        let key = TestKey::generate("k1");
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write_jwks(&file, &[&key]);
+        let token = key.sign(&claims(json!({})));
+
+        let principal = validator(&file).await.validate(&token).await.unwrap();
+        assert!(!principal.scopes.is_unrestricted());
+        assert!(!principal.scopes.allows(&crate::core::auth::Scope::Parse));
+
+        let lenient = super::testing::validator(
+            &file,
+            JwtConfig {
+                issuer: "https://idp.internal".to_string(),
+                missing_scopes_unrestricted: true,
+                ..JwtConfig::default()
+            },
+        )
+        .await;
+        let principal = lenient.validate(&token).await.unwrap();
+        assert!(principal.scopes.is_unrestricted());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_tokens_are_rejected() {
+        let key = TestKey::generate("k1");
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write_jwks(&file, &[&key]);
+        let validator = validator(&file).await;
+
+        let expired = key.sign(&claims(
+            json!({ "exp": chrono::Utc::now().timestamp() - 600 }),
+        ));
+        assert_eq!(validator.validate(&expired).await, Err("token_expired"));
+
+        let other_issuer = key.sign(&claims(json!({ "iss": "https://evil.example" })));
+        assert_eq!(
+            validator.validate(&other_issuer).await,
+            Err("invalid_issuer")
+        );
+
+        let mut no_workspace = claims(json!({}));
+        no_workspace.as_object_mut().unwrap().remove("workspace_id");
+        assert_eq!(
+            validator.validate(&key.sign(&no_workspace)).await,
+            Err("missing_workspace_claim")
+        );
+
+        // 同一 kid，但由另一把私钥签名
+        let forged = TestKey::generate("k1").sign(&claims(json!({})));
+        assert_eq!(validator.validate(&forged).await, Err("invalid_signature"));
+
+        assert_eq!(
+            validator.validate("not-a-jwt").await,
+            Err("malformed_token")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_picks_up_rotated_keys() {
+        let old = TestKey::generate("k1");
+        let new = TestKey::generate("k2");
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write_jwks(&file, &[&old]);
+        let validator = validator(&file).await;
+
+        let token = new.sign(&claims(json!({})));
+        // 刚加载过，未知 kid 不会立即触发刷新
+        assert_eq!(validator.validate(&token).await, Err("unknown_key_id"));
+
+        write_jwks(&file, &[&old, &new]);
+        assert_eq!(validator.refresh().await.unwrap(), 2);
+        assert!(validator.validate(&token).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_missing_jwks_source_is_a_configuration_error() {
+        let result = JwtValidator::new(JwtConfig {
+            enabled: true,
+            ..JwtConfig::default()
+        })
+        .await;
+        assert!(matches!(result, Err(CoreError::ConfigurationError(_))));
+    }
+}
@@ -0,0 +1,766 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API key 生命周期检查
+//!
+//! 按 `auth.lifecycle` 策略周期扫描所有启用的 key：
+//! - 已过 `expires_at` → `expired`，`disable_expired` 时禁用
+//! - `expires_at` 前 `expiry_warning_days` 天内 → `expiring_soon`
+//! - 超过 `disable_unused_days` 天未使用 → `unused`，User key 禁用，Admin key 只告警
+//! - 创建或上次轮换超过 `max_key_age_days` 天 → `rotation_due`，`auto_rotate` 时轮换
+//!
+//! 自动轮换默认关闭：新密钥不会送达调用方，旧密钥只在轮换宽限期内可用，
+//! 调用方需在宽限期内通过管理接口再次轮换取得密钥。
+//!
+//! 每个 (key, 原因) 只在首次出现时写审计事件、发送告警并执行轮换，问题消失后重新计数。
+//! 去重状态登记在 `api_key_notifications` 表中，多实例和重启后共享：
+//! 只有登记成功的实例处理该发现。
+
+use crate::core::algorithm::{AuditEvent, AuditEventType, DynAuditLogger};
+use crate::core::config::ApiKeyLifecycleConfig;
+use crate::core::database::{ApiKeyInfo, ApiKeyRepository, ApiKeyRole};
+use crate::core::monitoring::{Alert, AlertNotificationSender, AlertSeverity};
+use crate::core::types::Result;
+use chrono::NaiveDateTime;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+const AUDIT_ACTOR: &str = "system:api_key_lifecycle";
+const ALERT_RULE_NAME: &str = "api_key_lifecycle";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyAttentionReason {
+    Expired,
+    ExpiringSoon,
+    Unused,
+    RotationDue,
+}
+
+impl fmt::Display for KeyAttentionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyAttentionReason::Expired => write!(f, "expired"),
+            KeyAttentionReason::ExpiringSoon => write!(f, "expiring_soon"),
+            KeyAttentionReason::Unused => write!(f, "unused"),
+            KeyAttentionReason::RotationDue => write!(f, "rotation_due"),
+        }
+    }
+}
+
+/// 一个需要处理的 key 及原因；同一个 key 可能有多条
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyAttention {
+    pub id: Uuid,
+    pub key_id: String,
+    pub name: String,
+    pub role: ApiKeyRole,
+    pub workspace_id: Option<Uuid>,
+    pub reason: KeyAttentionReason,
+    /// 策略要求禁用该 key（下一次检查时执行）
+    pub auto_disable: bool,
+    /// 策略要求轮换该 key（首次发现时执行）
+    pub auto_rotate: bool,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl KeyAttention {
+    fn new(key: &ApiKeyInfo, reason: KeyAttentionReason, auto_disable: bool) -> Self {
+        Self {
+            id: key.id,
+            key_id: key.key_id.clone(),
+            name: key.name.clone(),
+            role: key.role.clone(),
+            workspace_id: key.workspace_id,
+            reason,
+            auto_disable,
+            auto_rotate: false,
+            expires_at: key.expires_at,
+            last_used_at: key.last_used_at,
+            created_at: key.created_at,
+        }
+    }
+}
+
+pub struct ApiKeyLifecycle {
+    repo: Arc<dyn ApiKeyRepository>,
+    config: ApiKeyLifecycleConfig,
+    audit_logger: Option<DynAuditLogger>,
+    alert_sender: Option<Arc<AlertNotificationSender>>,
+    /// 自动轮换时旧密钥的宽限期（秒）
+    rotation_grace_period_seconds: u64,
+    /// 本实例已处理过的 (key_id, 原因)，避免每次检查都访问登记表
+    notified: Mutex<HashSet<(String, KeyAttentionReason)>>,
+}
+
+impl ApiKeyLifecycle {
+    pub fn new(repo: Arc<dyn ApiKeyRepository>, config: ApiKeyLifecycleConfig) -> Self {
+        Self {
+            repo,
+            config,
+            audit_logger: None,
+            alert_sender: None,
+            rotation_grace_period_seconds:
+                crate::core::config::auth::DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS,
+            notified: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn with_audit_logger(mut self, logger: DynAuditLogger) -> Self {
+        self.audit_logger = Some(logger);
+        self
+    }
+
+    pub fn with_alert_sender(mut self, sender: Arc<AlertNotificationSender>) -> Self {
+        self.alert_sender = Some(sender);
+        self
+    }
+
+    pub fn with_rotation_grace_period(mut self, seconds: u64) -> Self {
+        self.rotation_grace_period_seconds = seconds;
+        self
+    }
+
+    pub fn config(&self) -> &ApiKeyLifecycleConfig {
+        &self.config
+    }
+
+    /// 按策略评估一组 key；未启用的 key 跳过。`rotated_at` 为各 key 最近一次轮换的时间，
+    /// 密钥年龄从创建和最近一次轮换中较晚者算起
+    pub fn evaluate(
+        &self,
+        keys: &[ApiKeyInfo],
+        rotated_at: &HashMap<Uuid, NaiveDateTime>,
+        now: NaiveDateTime,
+    ) -> Vec<KeyAttention> {
+        // 上限约 2700 年，避免超大配置值溢出
+        let days = |n: u64| chrono::Duration::days(n.min(1_000_000) as i64);
+        let mut findings = Vec::new();
+
+        for key in keys.iter().filter(|k| k.enabled) {
+            if let Some(expires_at) = key.expires_at {
+                if expires_at <= now {
+                    // 已过期的 key 无法再通过认证，其他检查没有意义
+                    findings.push(KeyAttention::new(
+                        key,
+                        KeyAttentionReason::Expired,
+                        self.config.disable_expired,
+                    ));
+                    continue;
+                }
+                if self.config.expiry_warning_days > 0
+                    && expires_at - now <= days(self.config.expiry_warning_days)
+                {
+                    findings.push(KeyAttention::new(
+                        key,
+                        KeyAttentionReason::ExpiringSoon,
+                        false,
+                    ));
+                }
+            }
+
+            if self.config.disable_unused_days > 0 {
+                let last_activity = key.last_used_at.unwrap_or(key.created_at);
+                if now - last_activity >= days(self.config.disable_unused_days) {
+                    findings.push(KeyAttention::new(
+                        key,
+                        KeyAttentionReason::Unused,
+                        key.role != ApiKeyRole::Admin,
+                    ));
+                }
+            }
+
+            if self.config.max_key_age_days > 0 {
+                let secret_created_at = rotated_at
+                    .get(&key.id)
+                    .map_or(key.created_at, |at| (*at).max(key.created_at));
+                if now - secret_created_at >= days(self.config.max_key_age_days) {
+                    findings.push(KeyAttention {
+                        auto_rotate: self.config.auto_rotate,
+                        ..KeyAttention::new(key, KeyAttentionReason::RotationDue, false)
+                    });
+                }
+            }
+        }
+
+        findings
+    }
+
+    /// 当前需要处理的 key（不做任何修改）
+    pub async fn attention(&self) -> Result<Vec<KeyAttention>> {
+        let keys = self.repo.list_enabled_api_keys().await?;
+        let rotated_at = self.repo.latest_api_key_rotations().await?;
+        Ok(self.evaluate(&keys, &rotated_at, chrono::Utc::now().naive_utc()))
+    }
+
+    /// 执行一次检查：禁用策略要求禁用的 key，并对新出现的问题登记、轮换、写审计、发告警。
+    /// 返回本次的全部发现。
+    pub async fn run_once(&self) -> Result<Vec<KeyAttention>> {
+        let findings = self.attention().await?;
+
+        let mut disabled = HashSet::new();
+        for finding in findings.iter().filter(|f| f.auto_disable) {
+            if disabled.contains(&finding.id) {
+                continue;
+            }
+            match self.repo.revoke_api_key(finding.id).await {
+                Ok(()) => {
+                    disabled.insert(finding.id);
+                    tracing::info!(
+                        event = "api_key_auto_disabled",
+                        key_id = %finding.key_id,
+                        reason = %finding.reason,
+                        "Disabled API key by lifecycle policy"
+                    );
+                }
+                Err(e) => tracing::warn!(
+                    event = "api_key_auto_disable_failed",
+                    key_id = %finding.key_id,
+                    reason = %finding.reason,
+                    error = %e,
+                    "Failed to disable API key"
+                ),
+            }
+        }
+
+        let active: Vec<(String, String)> = findings
+            .iter()
+            .map(|f| (f.key_id.clone(), f.reason.to_string()))
+            .collect();
+        if let Err(e) = self.repo.release_api_key_notifications(&active).await {
+            tracing::warn!(
+                event = "api_key_notification_release_failed",
+                error = %e,
+                "Failed to release resolved API key notifications"
+            );
+        }
+
+        let pending: Vec<&KeyAttention> = {
+            let mut notified = self.notified.lock();
+            let current: HashSet<_> = findings
+                .iter()
+                .map(|f| (f.key_id.clone(), f.reason))
+                .collect();
+            notified.retain(|entry| current.contains(entry));
+            findings
+                .iter()
+                .filter(|f| !notified.contains(&(f.key_id.clone(), f.reason)))
+                .collect()
+        };
+
+        let now = chrono::Utc::now().naive_utc();
+        for finding in pending {
+            let claimed = match self
+                .repo
+                .claim_api_key_notification(&finding.key_id, &finding.reason.to_string(), now)
+                .await
+            {
+                Ok(claimed) => claimed,
+                Err(e) => {
+                    // 未登记成功，下次检查重试
+                    tracing::warn!(
+                        event = "api_key_notification_claim_failed",
+                        key_id = %finding.key_id,
+                        reason = %finding.reason,
+                        error = %e,
+                        "Failed to claim API key notification"
+                    );
+                    continue;
+                }
+            };
+            self.notified
+                .lock()
+                .insert((finding.key_id.clone(), finding.reason));
+            if !claimed {
+                // 其他实例或重启前已处理
+                continue;
+            }
+
+            let was_disabled = finding.auto_disable && disabled.contains(&finding.id);
+            let rotated = finding.auto_rotate
+                && !disabled.contains(&finding.id)
+                && self.rotate(finding).await;
+            self.notify(finding, was_disabled, rotated).await;
+        }
+
+        Ok(findings)
+    }
+
+    /// 轮换超龄 key；新密钥不保留，调用方在宽限期内通过管理接口轮换取得
+    async fn rotate(&self, finding: &KeyAttention) -> bool {
+        match self
+            .repo
+            .rotate_api_key(&finding.key_id, self.rotation_grace_period_seconds)
+            .await
+        {
+            Ok(_) => {
+                tracing::info!(
+                    event = "api_key_auto_rotated",
+                    key_id = %finding.key_id,
+                    grace_period_seconds = self.rotation_grace_period_seconds,
+                    "Rotated API key by lifecycle policy"
+                );
+                true
+            }
+            Err(e) => {
+                tracing::warn!(
+                    event = "api_key_auto_rotate_failed",
+                    key_id = %finding.key_id,
+                    error = %e,
+                    "Failed to rotate API key"
+                );
+                false
+            }
+        }
+    }
+
+    /// 启动周期检查；第一次检查立即执行
+    pub fn spawn(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let lifecycle = Arc::clone(self);
+        let period = Duration::from_secs(self.config.check_interval_seconds.max(1));
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                if let Err(e) = lifecycle.run_once().await {
+                    tracing::warn!(
+                        event = "api_key_lifecycle_check_failed",
+                        error = %e,
+                        "API key lifecycle check failed"
+                    );
+                }
+            }
+        })
+    }
+
+    async fn notify(&self, finding: &KeyAttention, disabled: bool, rotated: bool) {
+        let message = match (finding.reason, disabled) {
+            (KeyAttentionReason::Expired, true) => "API key expired and was disabled",
+            (KeyAttentionReason::Expired, false) => "API key expired",
+            (KeyAttentionReason::ExpiringSoon, _) => "API key expires soon",
+            (KeyAttentionReason::Unused, true) => "Unused API key was disabled",
+            (KeyAttentionReason::Unused, false) => "API key has not been used recently",
+            (KeyAttentionReason::RotationDue, _) if rotated => {
+                "API key exceeded maximum age and was rotated"
+            }
+            (KeyAttentionReason::RotationDue, _) => {
+                "API key exceeded maximum age and should be rotated"
+            }
+        };
+        let workspace = finding
+            .workspace_id
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+
+        if let Some(logger) = &self.audit_logger {
+            let action = if disabled {
+                "disable_api_key"
+            } else if rotated {
+                "rotate_api_key"
+            } else {
+                "api_key_lifecycle_warning"
+            };
+            let mut event = AuditEvent::builder(
+                AuditEventType::ApiKeyUpdated,
+                action,
+                format!("api_key:{}:{}", finding.key_id, finding.role),
+            )
+            .actor(AUDIT_ACTOR)
+            .details(serde_json::json!({
+                "reason": finding.reason.to_string(),
+                "disabled": disabled,
+                "rotated": rotated,
+                "expires_at": finding.expires_at.map(|t| t.and_utc().to_rfc3339()),
+                "last_used_at": finding.last_used_at.map(|t| t.and_utc().to_rfc3339()),
+                "created_at": finding.created_at.and_utc().to_rfc3339(),
+            }));
+            if !workspace.is_empty() {
+                event = event.workspace(workspace.clone());
+            }
+            logger.log(event.build()).await;
+        }
+
+        if let Some(sender) = &self.alert_sender {
+            let severity = match finding.reason {
+                KeyAttentionReason::Expired | KeyAttentionReason::ExpiringSoon => {
+                    AlertSeverity::Warning
+                }
+                KeyAttentionReason::Unused | KeyAttentionReason::RotationDue => AlertSeverity::Info,
+            };
+            let labels = HashMap::from([
+                ("key_id".to_string(), finding.key_id.clone()),
+                ("reason".to_string(), finding.reason.to_string()),
+                ("workspace_id".to_string(), workspace),
+            ]);
+            let mut alert = Alert::new(
+                ALERT_RULE_NAME.to_string(),
+                severity,
+                format!("{}: {}", message, finding.key_id),
+                labels,
+                Some(finding.reason.to_string()),
+            );
+            alert.fire();
+            sender.send(&alert).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::algorithm::AuditLogger;
+    use crate::core::auth::ApiKeyScopes;
+    use crate::core::database::{ApiKeyResponse, ApiKeyWithSecret, CreateApiKeyRequest};
+    use async_trait::async_trait;
+
+    #[derive(Default)]
+    struct LifecycleTestRepo {
+        keys: Mutex<Vec<ApiKeyInfo>>,
+        /// 模拟 `api_key_notifications` 登记表，多个实例共享
+        claims: Mutex<HashSet<(String, String)>>,
+        rotations: Mutex<HashMap<Uuid, NaiveDateTime>>,
+    }
+
+    #[async_trait]
+    impl ApiKeyRepository for LifecycleTestRepo {
+        async fn create_api_key(&self, _: &CreateApiKeyRequest) -> Result<ApiKeyWithSecret> {
+            unimplemented!()
+        }
+        async fn get_api_key_by_id(&self, _: &str) -> Result<Option<ApiKeyInfo>> {
+            unimplemented!()
+        }
+        async fn validate_api_key(
+            &self,
+            _: &str,
+            _: &str,
+        ) -> Result<Option<(Option<Uuid>, ApiKeyRole)>> {
+            unimplemented!()
+        }
+        async fn list_api_keys(
+            &self,
+            _: Uuid,
+            _: Option<u32>,
+            _: Option<u32>,
+        ) -> Result<Vec<ApiKeyInfo>> {
+            unimplemented!()
+        }
+        async fn delete_api_key(&self, _: Uuid) -> Result<()> {
+            unimplemented!()
+        }
+        async fn revoke_api_key(&self, id: Uuid) -> Result<()> {
+            for key in self.keys.lock().iter_mut().filter(|k| k.id == id) {
+                key.enabled = false;
+            }
+            Ok(())
+        }
+        async fn update_last_used(&self, _: Uuid) -> Result<()> {
+            unimplemented!()
+        }
+        async fn get_admin_api_key(&self, _: Uuid) -> Result<Option<ApiKeyInfo>> {
+            unimplemented!()
+        }
+        async fn count_api_keys(&self, _: Uuid) -> Result<u64> {
+            unimplemented!()
+        }
+        async fn rotate_api_key(&self, key_id: &str, _: u64) -> Result<ApiKeyWithSecret> {
+            let key = self
+                .keys
+                .lock()
+                .iter()
+                .find(|k| k.key_id == key_id)
+                .cloned()
+                .unwrap();
+            self.rotations.lock().insert(key.id, now());
+            Ok(ApiKeyWithSecret {
+                key: ApiKeyResponse {
+                    id: key.id,
+                    key_id: key.key_id,
+                    key_prefix: key.key_prefix,
+                    name: key.name,
+                    description: key.description,
+                    role: key.role,
+                    rate_limit: key.rate_limit,
+                    enabled: key.enabled,
+                    expires_at: key.expires_at,
+                    created_at: key.created_at,
+                    scopes: key.scopes,
+                },
+                key_secret: "rotated-secret".to_string(),
+            })
+        }
+        async fn get_keys_older_than(&self, _: i64) -> Result<Vec<ApiKeyInfo>> {
+            unimplemented!()
+        }
+        async fn list_enabled_api_keys(&self) -> Result<Vec<ApiKeyInfo>> {
+            Ok(self
+                .keys
+                .lock()
+                .iter()
+                .filter(|k| k.enabled)
+                .cloned()
+                .collect())
+        }
+        async fn latest_api_key_rotations(&self) -> Result<HashMap<Uuid, NaiveDateTime>> {
+            Ok(self.rotations.lock().clone())
+        }
+        async fn claim_api_key_notification(
+            &self,
+            key_id: &str,
+            reason: &str,
+            _: NaiveDateTime,
+        ) -> Result<bool> {
+            Ok(self
+                .claims
+                .lock()
+                .insert((key_id.to_string(), reason.to_string())))
+        }
+        async fn release_api_key_notifications(&self, active: &[(String, String)]) -> Result<u64> {
+            let mut claims = self.claims.lock();
+            let before = claims.len();
+            claims.retain(|claim| active.contains(claim));
+            Ok((before - claims.len()) as u64)
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingAuditLogger {
+        events: Mutex<Vec<AuditEvent>>,
+    }
+
+    #[async_trait]
+    impl AuditLogger for RecordingAuditLogger {
+        async fn log(&self, event: AuditEvent) {
+            self.events.lock().push(event);
+        }
+    }
+
+    fn now() -> NaiveDateTime {
+        chrono::Utc::now().naive_utc()
+    }
+
+    fn key(key_id: &str, role: ApiKeyRole, age_days: i64) -> ApiKeyInfo {
+        ApiKeyInfo {
+            id: Uuid::new_v4(),
+            key_id: key_id.to_string(),
+            key_prefix: "nino_".to_string(),
+            role,
+            workspace_id: Some(Uuid::new_v4()),
+            name: key_id.to_string(),
+            description: None,
+            rate_limit: 100,
+            enabled: true,
+            expires_at: None,
+            last_used_at: Some(now()),
+            created_at: now() - chrono::Duration::days(age_days),
+            scopes: ApiKeyScopes::unrestricted(),
+        }
+    }
+
+    fn policy() -> ApiKeyLifecycleConfig {
+        ApiKeyLifecycleConfig {
+            enabled: true,
+            max_key_age_days: 90,
+            expiry_warning_days: 7,
+            disable_expired: true,
+            disable_unused_days: 30,
+            ..Default::default()
+        }
+    }
+
+    fn lifecycle(keys: Vec<ApiKeyInfo>) -> (ApiKeyLifecycle, Arc<LifecycleTestRepo>) {
+        let repo = Arc::new(LifecycleTestRepo {
+            keys: Mutex::new(keys),
+            ..Default::default()
+        });
+        (ApiKeyLifecycle::new(repo.clone(), policy()), repo)
+    }
+
+    fn reasons(findings: &[KeyAttention], key_id: &str) -> Vec<KeyAttentionReason> {
+        findings
+            .iter()
+            .filter(|f| f.key_id == key_id)
+            .map(|f| f.reason)
+            .collect()
+    }
+
+    #[test]
+    fn test_evaluate_applies_each_policy() {
+        let mut expired = key("expired", ApiKeyRole::User, 1);
+        expired.expires_at = Some(now() - chrono::Duration::hours(1));
+        let mut expiring = key("expiring", ApiKeyRole::User, 1);
+        expiring.expires_at = Some(now() + chrono::Duration::days(3));
+        let mut unused = key("unused", ApiKeyRole::User, 40);
+        unused.last_used_at = None;
+        let mut unused_admin = key("unused_admin", ApiKeyRole::Admin, 1);
+        unused_admin.last_used_at = Some(now() - chrono::Duration::days(31));
+        let old = key("old", ApiKeyRole::User, 120);
+        let fresh = key("fresh", ApiKeyRole::User, 1);
+
+        let (lifecycle, _) = lifecycle(vec![]);
+        let findings = lifecycle.evaluate(
+            &[expired, expiring, unused, unused_admin, old, fresh],
+            &HashMap::new(),
+            now(),
+        );
+
+        assert_eq!(
+            reasons(&findings, "expired"),
+            vec![KeyAttentionReason::Expired]
+        );
+        assert_eq!(
+            reasons(&findings, "expiring"),
+            vec![KeyAttentionReason::ExpiringSoon]
+        );
+        assert_eq!(
+            reasons(&findings, "unused"),
+            vec![KeyAttentionReason::Unused]
+        );
+        assert_eq!(
+            reasons(&findings, "old"),
+            vec![KeyAttentionReason::RotationDue]
+        );
+        assert!(reasons(&findings, "fresh").is_empty());
+
+        let auto_disabled: Vec<_> = findings
+            .iter()
+            .filter(|f| f.auto_disable)
+            .map(|f| f.key_id.as_str())
+            .collect();
+        assert_eq!(auto_disabled, vec!["expired", "unused"]);
+    }
+
+    #[test]
+    fn test_evaluate_skips_disabled_policies() {
+        let mut expired = key("expired", ApiKeyRole::User, 200);
+        expired.expires_at = Some(now() - chrono::Duration::hours(1));
+        let (mut lifecycle, _) = lifecycle(vec![]);
+        lifecycle.config = ApiKeyLifecycleConfig {
+            disable_expired: false,
+            max_key_age_days: 0,
+            disable_unused_days: 0,
+            ..policy()
+        };
+
+        let findings = lifecycle.evaluate(
+            &[expired, key("old", ApiKeyRole::User, 200)],
+            &HashMap::new(),
+            now(),
+        );
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].reason, KeyAttentionReason::Expired);
+        assert!(!findings[0].auto_disable);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_disables_keys_and_audits_new_findings_once() {
+        let mut expired = key("expired", ApiKeyRole::User, 1);
+        expired.expires_at = Some(now() - chrono::Duration::hours(1));
+        let old = key("old", ApiKeyRole::User, 120);
+        let (lifecycle, repo) = lifecycle(vec![expired, old]);
+        let logger = Arc::new(RecordingAuditLogger::default());
+        let lifecycle = lifecycle.with_audit_logger(logger.clone());
+
+        let findings = lifecycle.run_once().await.unwrap();
+        assert_eq!(findings.len(), 2);
+        assert!(
+            !repo
+                .keys
+                .lock()
+                .iter()
+                .find(|k| k.key_id == "expired")
+                .unwrap()
+                .enabled
+        );
+        {
+            let events = logger.events.lock();
+            assert_eq!(events.len(), 2);
+            let disable = events
+                .iter()
+                .find(|e| e.action == "disable_api_key")
+                .unwrap();
+            assert_eq!(disable.resource, "api_key:expired:user");
+            assert_eq!(disable.user_id.as_deref(), Some(AUDIT_ACTOR));
+            assert!(events
+                .iter()
+                .any(|e| e.action == "api_key_lifecycle_warning"));
+        }
+
+        // 第二次只剩超龄 key，且已通知过
+        let findings = lifecycle.run_once().await.unwrap();
+        assert_eq!(
+            reasons(&findings, "old"),
+            vec![KeyAttentionReason::RotationDue]
+        );
+        assert_eq!(logger.events.lock().len(), 2);
+    }
+
+    #[test]
+    fn test_evaluate_counts_key_age_from_last_rotation() {
+        let old = key("old", ApiKeyRole::User, 120);
+        let rotated_at = HashMap::from([(old.id, now() - chrono::Duration::days(10))]);
+        let (lifecycle, _) = lifecycle(vec![]);
+
+        assert!(lifecycle.evaluate(&[old], &rotated_at, now()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_once_notifies_once_across_instances() {
+        let old = key("old", ApiKeyRole::User, 120);
+        let (first, repo) = lifecycle(vec![old]);
+        let second = ApiKeyLifecycle::new(repo.clone(), policy());
+        let logger = Arc::new(RecordingAuditLogger::default());
+        let first = first.with_audit_logger(logger.clone());
+        let second = second.with_audit_logger(logger.clone());
+
+        first.run_once().await.unwrap();
+        second.run_once().await.unwrap();
+        // 重启后的实例同样读取登记表
+        let restarted =
+            ApiKeyLifecycle::new(repo.clone(), policy()).with_audit_logger(logger.clone());
+        restarted.run_once().await.unwrap();
+
+        assert_eq!(logger.events.lock().len(), 1);
+        assert_eq!(repo.claims.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_once_auto_rotates_due_keys_once() {
+        let old = key("old", ApiKeyRole::User, 120);
+        let old_id = old.id;
+        let (lifecycle, repo) = lifecycle(vec![old]);
+        let logger = Arc::new(RecordingAuditLogger::default());
+        let mut lifecycle = lifecycle.with_audit_logger(logger.clone());
+        lifecycle.config.auto_rotate = true;
+
+        let findings = lifecycle.run_once().await.unwrap();
+        assert!(findings[0].auto_rotate);
+        assert!(repo.rotations.lock().contains_key(&old_id));
+        {
+            let events = logger.events.lock();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].action, "rotate_api_key");
+        }
+
+        // 轮换后密钥年龄重新计算，登记随之释放
+        assert!(lifecycle.run_once().await.unwrap().is_empty());
+        assert!(repo.claims.lock().is_empty());
+        assert_eq!(logger.events.lock().len(), 1);
+    }
+}
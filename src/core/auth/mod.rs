@@ -14,6 +14,24 @@
 
 //! Authentication module for Nebula ID.
 
+pub mod ban;
+pub mod client_cert;
+pub mod credential_cache;
+pub mod jwt;
+pub mod lifecycle;
 pub mod manager;
+pub mod scope;
+pub mod signing;
 
+pub use ban::BanManager;
+pub use client_cert::{ClientCertIdentity, ClientCertMapper};
+#[cfg(feature = "etcd")]
+pub use credential_cache::EtcdRevocationNotifier;
+pub use credential_cache::{
+    CachedApiKeyRepository, CredentialCache, RevocationNotifier, RevocationSync, VerifiedCredential,
+};
+pub use jwt::{JwtPrincipal, JwtValidator};
+pub use lifecycle::{ApiKeyLifecycle, KeyAttention, KeyAttentionReason};
 pub use manager::{AuthConfig, AuthManager, Authenticator};
+pub use scope::{ApiKeyScopes, Scope, ScopeAccess, ScopeResource};
+pub use signing::{RequestSigner, SignedAuthorization};
@@ -0,0 +1,429 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API key scopes.
+//!
+//! 作用域串形如 `generate:orders/*`、`parse`、`biz_tags:write`、`config:read`。
+//! key 的作用域以空格分隔存储在 `api_keys.scopes`；`NULL` 表示不受限，
+//! 旧 key 和未指定作用域创建的 key 仍只按角色授权。作用域只会收窄角色
+//! 已有的权限，不会放宽（例如 User key 带 `api_keys:write` 仍无法访问 admin 端点）。
+
+use crate::core::types::error::CoreError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// 单个 key 最多携带的作用域数量
+pub const MAX_SCOPES: usize = 32;
+
+/// 受作用域保护的资源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScopeResource {
+    Workspaces,
+    Groups,
+    BizTags,
+    Config,
+    Audit,
+    ApiKeys,
+    /// 用量、容量与降级控制（`/api/v1/admin/*`）
+    Admin,
+}
+
+impl ScopeResource {
+    const ALL: [ScopeResource; 7] = [
+        ScopeResource::Workspaces,
+        ScopeResource::Groups,
+        ScopeResource::BizTags,
+        ScopeResource::Config,
+        ScopeResource::Audit,
+        ScopeResource::ApiKeys,
+        ScopeResource::Admin,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScopeResource::Workspaces => "workspaces",
+            ScopeResource::Groups => "groups",
+            ScopeResource::BizTags => "biz_tags",
+            ScopeResource::Config => "config",
+            ScopeResource::Audit => "audit",
+            ScopeResource::ApiKeys => "api_keys",
+            ScopeResource::Admin => "admin",
+        }
+    }
+}
+
+/// 访问级别；`Write` 隐含 `Read`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScopeAccess {
+    Read,
+    Write,
+}
+
+/// 一个作用域，或一次操作所需的作用域
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// `*`：不受限
+    All,
+    /// `generate`（等价于 `generate:*/*`）或 `generate:<group>/<biz_tag>`，
+    /// 任一段可为 `*`；`generate:<group>` 等价于 `generate:<group>/*`
+    Generate {
+        group: String,
+        biz_tag: String,
+    },
+    Parse,
+    Resource(ScopeResource, ScopeAccess),
+}
+
+impl Scope {
+    /// 生成 `group/biz_tag` 所需的作用域
+    pub fn generate(group: &str, biz_tag: &str) -> Self {
+        Scope::Generate {
+            group: group.to_string(),
+            biz_tag: biz_tag.to_string(),
+        }
+    }
+
+    pub fn read(resource: ScopeResource) -> Self {
+        Scope::Resource(resource, ScopeAccess::Read)
+    }
+
+    pub fn write(resource: ScopeResource) -> Self {
+        Scope::Resource(resource, ScopeAccess::Write)
+    }
+
+    /// 本作用域（授予的）是否覆盖 `required`（操作所需的）
+    pub fn covers(&self, required: &Scope) -> bool {
+        match (self, required) {
+            (Scope::All, _) => true,
+            (
+                Scope::Generate { group, biz_tag },
+                Scope::Generate {
+                    group: required_group,
+                    biz_tag: required_biz_tag,
+                },
+            ) => {
+                segment_matches(group, required_group) && segment_matches(biz_tag, required_biz_tag)
+            }
+            (Scope::Parse, Scope::Parse) => true,
+            (
+                Scope::Resource(resource, access),
+                Scope::Resource(required_resource, required_access),
+            ) => {
+                resource == required_resource
+                    && (access == required_access || *access == ScopeAccess::Write)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn segment_matches(granted: &str, required: &str) -> bool {
+    granted == "*" || granted == required
+}
+
+/// group / biz_tag 段只允许与资源名称相同的字符集，或单独的 `*`
+fn valid_segment(segment: &str) -> bool {
+    segment == "*"
+        || (!segment.is_empty()
+            && segment.len() <= 64
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+}
+
+impl FromStr for Scope {
+    type Err = CoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CoreError::InvalidInput(format!("invalid API key scope '{}'", s));
+        match s {
+            "*" => return Ok(Scope::All),
+            "generate" => return Ok(Scope::generate("*", "*")),
+            "parse" => return Ok(Scope::Parse),
+            _ => {}
+        }
+
+        let (head, tail) = s.split_once(':').ok_or_else(invalid)?;
+        if head == "generate" {
+            let (group, biz_tag) = tail.split_once('/').unwrap_or((tail, "*"));
+            if !valid_segment(group) || !valid_segment(biz_tag) {
+                return Err(invalid());
+            }
+            return Ok(Scope::generate(group, biz_tag));
+        }
+
+        let resource = ScopeResource::ALL
+            .into_iter()
+            .find(|r| r.as_str() == head)
+            .ok_or_else(invalid)?;
+        let access = match tail {
+            "read" => ScopeAccess::Read,
+            "write" => ScopeAccess::Write,
+            _ => return Err(invalid()),
+        };
+        Ok(Scope::Resource(resource, access))
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::All => write!(f, "*"),
+            Scope::Generate { group, biz_tag } => write!(f, "generate:{}/{}", group, biz_tag),
+            Scope::Parse => write!(f, "parse"),
+            Scope::Resource(resource, ScopeAccess::Read) => write!(f, "{}:read", resource.as_str()),
+            Scope::Resource(resource, ScopeAccess::Write) => {
+                write!(f, "{}:write", resource.as_str())
+            }
+        }
+    }
+}
+
+impl Serialize for Scope {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scope {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// 一个 key 被授予的作用域集合
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeyScopes(Vec<Scope>);
+
+impl Default for ApiKeyScopes {
+    fn default() -> Self {
+        Self::unrestricted()
+    }
+}
+
+impl ApiKeyScopes {
+    /// 不受限（`*`）
+    pub fn unrestricted() -> Self {
+        Self(vec![Scope::All])
+    }
+
+    /// 由显式作用域列表构造；列表为空或超过 [`MAX_SCOPES`] 时返回 `InvalidInput`
+    pub fn new(scopes: Vec<Scope>) -> Result<Self, CoreError> {
+        if scopes.is_empty() || scopes.len() > MAX_SCOPES {
+            return Err(CoreError::InvalidInput(format!(
+                "an API key must have between 1 and {} scopes",
+                MAX_SCOPES
+            )));
+        }
+        let mut deduped: Vec<Scope> = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            if !deduped.contains(&scope) {
+                deduped.push(scope);
+            }
+        }
+        Ok(Self(deduped))
+    }
+
+    /// 解析作用域字符串列表（管理 API 的请求体）
+    pub fn parse<S: AsRef<str>>(scopes: &[S]) -> Result<Self, CoreError> {
+        let parsed = scopes
+            .iter()
+            .map(|s| s.as_ref().trim().parse())
+            .collect::<Result<Vec<Scope>, _>>()?;
+        Self::new(parsed)
+    }
+
+    /// 从 `api_keys.scopes` 列读取；`NULL` 或空串为不受限。
+    /// 无法解析的作用域被丢弃并记录警告（收窄而不是放宽权限）。
+    pub fn from_column(column: Option<&str>) -> Self {
+        let Some(column) = column.filter(|c| !c.trim().is_empty()) else {
+            return Self::unrestricted();
+        };
+        let scopes: Vec<Scope> = column
+            .split_whitespace()
+            .filter_map(|s| match s.parse() {
+                Ok(scope) => Some(scope),
+                Err(_) => {
+                    tracing::warn!(
+                        event = "api_key_scope_ignored",
+                        scope = s,
+                        "ignoring unparseable API key scope stored in database"
+                    );
+                    None
+                }
+            })
+            .collect();
+        Self(scopes)
+    }
+
+    /// 外部凭证（JWT 的 scope claim）授予的作用域。OIDC 的 `openid`、`profile`
+    /// 等无法识别的值被忽略；一个都不识别时不授予任何作用域。
+    pub fn from_claim<'a>(values: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut scopes: Vec<Scope> = Vec::new();
+        for scope in values.into_iter().filter_map(|s| s.parse::<Scope>().ok()) {
+            if !scopes.contains(&scope) {
+                scopes.push(scope);
+            }
+        }
+        Self(scopes)
+    }
+
+    /// 写入 `api_keys.scopes` 的值；不受限时为 `None`
+    pub fn to_column(&self) -> Option<String> {
+        if self.is_unrestricted() {
+            return None;
+        }
+        Some(
+            self.0
+                .iter()
+                .map(Scope::to_string)
+                .collect::<Vec<_>>()
+                .join(" "),
+        )
+    }
+
+    pub fn is_unrestricted(&self) -> bool {
+        self.0.contains(&Scope::All)
+    }
+
+    pub fn allows(&self, required: &Scope) -> bool {
+        self.0.iter().any(|granted| granted.covers(required))
+    }
+
+    pub fn scopes(&self) -> &[Scope] {
+        &self.0
+    }
+
+    pub fn to_strings(&self) -> Vec<String> {
+        self.0.iter().map(Scope::to_string).collect()
+    }
+}
+
+impl fmt::Display for ApiKeyScopes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_strings().join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display_round_trip() {
+        for s in [
+            "*",
+            "generate:orders/*",
+            "generate:*/invoice",
+            "parse",
+            "biz_tags:write",
+            "config:read",
+            "api_keys:read",
+        ] {
+            let scope: Scope = s.parse().unwrap();
+            assert_eq!(scope.to_string(), s);
+        }
+        assert_eq!(
+            "generate".parse::<Scope>().unwrap(),
+            Scope::generate("*", "*")
+        );
+        assert_eq!(
+            "generate:orders".parse::<Scope>().unwrap(),
+            Scope::generate("orders", "*")
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_scopes() {
+        for s in [
+            "",
+            "generate:",
+            "generate:orders/",
+            "generate:or ders/x",
+            "biz_tags",
+            "biz_tags:delete",
+            "widgets:read",
+            "parse:all",
+        ] {
+            assert!(s.parse::<Scope>().is_err(), "{:?} should be rejected", s);
+        }
+    }
+
+    #[test]
+    fn test_generate_pattern_matching() {
+        let scopes = ApiKeyScopes::parse(&["generate:orders/*", "generate:*/audit"]).unwrap();
+        assert!(scopes.allows(&Scope::generate("orders", "invoice")));
+        assert!(scopes.allows(&Scope::generate("billing", "audit")));
+        assert!(!scopes.allows(&Scope::generate("billing", "invoice")));
+        assert!(!scopes.allows(&Scope::Parse));
+    }
+
+    #[test]
+    fn test_write_implies_read() {
+        let scopes = ApiKeyScopes::parse(&["biz_tags:write", "config:read"]).unwrap();
+        assert!(scopes.allows(&Scope::read(ScopeResource::BizTags)));
+        assert!(scopes.allows(&Scope::write(ScopeResource::BizTags)));
+        assert!(scopes.allows(&Scope::read(ScopeResource::Config)));
+        assert!(!scopes.allows(&Scope::write(ScopeResource::Config)));
+        assert!(!scopes.allows(&Scope::read(ScopeResource::Groups)));
+    }
+
+    #[test]
+    fn test_unrestricted_allows_everything() {
+        let scopes = ApiKeyScopes::unrestricted();
+        assert!(scopes.allows(&Scope::generate("any", "tag")));
+        assert!(scopes.allows(&Scope::write(ScopeResource::ApiKeys)));
+        assert_eq!(scopes.to_column(), None);
+    }
+
+    #[test]
+    fn test_column_round_trip() {
+        assert!(ApiKeyScopes::from_column(None).is_unrestricted());
+        assert!(ApiKeyScopes::from_column(Some("  ")).is_unrestricted());
+
+        let scopes = ApiKeyScopes::parse(&["generate:orders/*", "parse", "parse"]).unwrap();
+        assert_eq!(scopes.scopes().len(), 2);
+        let column = scopes.to_column().unwrap();
+        assert_eq!(column, "generate:orders/* parse");
+        assert_eq!(ApiKeyScopes::from_column(Some(&column)), scopes);
+    }
+
+    #[test]
+    fn test_unparseable_column_entries_narrow_access() {
+        let scopes = ApiKeyScopes::from_column(Some("parse bogus:thing"));
+        assert!(scopes.allows(&Scope::Parse));
+        assert!(!scopes.is_unrestricted());
+        assert!(!scopes.allows(&Scope::generate("a", "b")));
+    }
+
+    #[test]
+    fn test_new_rejects_empty_and_oversized_lists() {
+        assert!(ApiKeyScopes::new(Vec::new()).is_err());
+        assert!(ApiKeyScopes::new(vec![Scope::Parse; MAX_SCOPES + 1]).is_err());
+    }
+
+    #[test]
+    fn test_claim_ignores_oidc_scopes() {
+        let scopes = ApiKeyScopes::from_claim("openid generate:orders parse".split_whitespace());
+        assert!(scopes.allows(&Scope::generate("orders", "created")));
+        assert!(scopes.allows(&Scope::Parse));
+        assert!(!scopes.is_unrestricted());
+
+        let none = ApiKeyScopes::from_claim(["openid", "profile"]);
+        assert!(none.scopes().is_empty());
+        assert!(!none.allows(&Scope::Parse));
+    }
+}
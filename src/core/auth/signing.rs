@@ -0,0 +1,312 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! HMAC request signing (`NEBULA-HMAC-SHA256`).
+//!
+//! 客户端用 key_secret 派生出签名密钥，对方法、路径、时间戳、nonce 与请求体
+//! SHA-256 做 HMAC-SHA256，`key_secret` 本身不出现在请求中：
+//!
+//! ```text
+//! Authorization: NEBULA-HMAC-SHA256 KeyId=<key_id>,Timestamp=<unix 秒>,Nonce=<nonce>,Signature=<hex>
+//! ```
+//!
+//! 服务端只保存派生后的签名密钥（`api_keys.signing_key`），在
+//! `ApiKeyAuth` 中校验签名、时间窗口与 nonce 重放。派生密钥足以伪造签名，
+//! 因此只有创建或轮换时开启 `request_signing` 的 key 才保存它。
+//!
+//! ```
+//! use nebulaid::core::auth::RequestSigner;
+//!
+//! let signer = RequestSigner::new("nino_key", "secret");
+//! let authorization = signer.sign("POST", "/api/v1/generate", br#"{"workspace":"acme"}"#);
+//! assert!(authorization.starts_with("NEBULA-HMAC-SHA256 KeyId=nino_key,"));
+//! ```
+
+use crate::core::types::CoreError;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// `Authorization` 头中的签名方案名
+pub const SIGNATURE_SCHEME: &str = "NEBULA-HMAC-SHA256";
+
+/// 默认允许的时间戳偏差（秒），同时是 nonce 的保留时长
+pub const DEFAULT_MAX_CLOCK_SKEW_SECS: u64 = 300;
+
+/// 派生签名密钥的上下文；更换算法时递增版本
+const SIGNING_KEY_CONTEXT: &[u8] = b"nebula-id/request-signing/v1";
+
+const MIN_NONCE_LEN: usize = 8;
+const MAX_NONCE_LEN: usize = 64;
+
+/// 由 key_secret 派生签名密钥：`HMAC-SHA256(key_secret, context)`
+pub fn derive_signing_key(key_secret: &str) -> [u8; 32] {
+    let mut mac = <HmacSha256 as KeyInit>::new_from_slice(key_secret.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(SIGNING_KEY_CONTEXT);
+    let mut signing_key = [0u8; 32];
+    signing_key.copy_from_slice(&mac.finalize().into_bytes());
+    signing_key
+}
+
+/// 待签名串：方法、路径（含 query）、时间戳、nonce、请求体 SHA-256，以 `\n` 分隔
+pub fn canonical_request(
+    method: &str,
+    path_and_query: &str,
+    timestamp: i64,
+    nonce: &str,
+    body: &[u8],
+) -> String {
+    format!(
+        "{}\n{}\n{}\n{}\n{}",
+        method.to_ascii_uppercase(),
+        path_and_query,
+        timestamp,
+        nonce,
+        hex::encode(Sha256::digest(body))
+    )
+}
+
+fn mac(signing_key: &[u8], canonical: &str) -> HmacSha256 {
+    let mut mac = <HmacSha256 as KeyInit>::new_from_slice(signing_key)
+        .expect("HMAC accepts keys of any length");
+    mac.update(canonical.as_bytes());
+    mac
+}
+
+fn valid_nonce(nonce: &str) -> bool {
+    (MIN_NONCE_LEN..=MAX_NONCE_LEN).contains(&nonce.len())
+        && nonce
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_'))
+}
+
+/// 解析后的签名 `Authorization` 头
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedAuthorization {
+    pub key_id: String,
+    pub timestamp: i64,
+    pub nonce: String,
+    /// 小写十六进制 HMAC
+    pub signature: String,
+}
+
+impl SignedAuthorization {
+    /// 是否为签名方案；用于在 `Basic` / `ApiKey` 之外分流
+    pub fn is_signed(value: &str) -> bool {
+        value
+            .strip_prefix(SIGNATURE_SCHEME)
+            .is_some_and(|rest| rest.starts_with(' '))
+    }
+
+    /// 解析 `NEBULA-HMAC-SHA256 KeyId=..,Timestamp=..,Nonce=..,Signature=..`，
+    /// 参数顺序不限；缺项、重复或格式非法时返回 `InvalidApiKeySignature`
+    pub fn parse(value: &str) -> Result<Self, CoreError> {
+        let params = value
+            .strip_prefix(SIGNATURE_SCHEME)
+            .and_then(|rest| rest.strip_prefix(' '))
+            .ok_or(CoreError::InvalidApiKeySignature)?;
+
+        let (mut key_id, mut timestamp, mut nonce, mut signature) = (None, None, None, None);
+        for param in params.split(',') {
+            let (name, value) = param
+                .trim()
+                .split_once('=')
+                .ok_or(CoreError::InvalidApiKeySignature)?;
+            let slot = match name {
+                "KeyId" => &mut key_id,
+                "Timestamp" => &mut timestamp,
+                "Nonce" => &mut nonce,
+                "Signature" => &mut signature,
+                _ => return Err(CoreError::InvalidApiKeySignature),
+            };
+            if slot.replace(value.to_string()).is_some() {
+                return Err(CoreError::InvalidApiKeySignature);
+            }
+        }
+
+        let (Some(key_id), Some(timestamp), Some(nonce), Some(signature)) =
+            (key_id, timestamp, nonce, signature)
+        else {
+            return Err(CoreError::InvalidApiKeySignature);
+        };
+        let timestamp = timestamp
+            .parse()
+            .map_err(|_| CoreError::InvalidApiKeySignature)?;
+        if key_id.is_empty() || !valid_nonce(&nonce) || signature.len() != 64 {
+            return Err(CoreError::InvalidApiKeySignature);
+        }
+
+        Ok(Self {
+            key_id,
+            timestamp,
+            nonce,
+            signature: signature.to_ascii_lowercase(),
+        })
+    }
+
+    /// 常量时间比较签名
+    pub fn verify(
+        &self,
+        signing_key: &[u8],
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+    ) -> bool {
+        let Ok(signature) = hex::decode(&self.signature) else {
+            return false;
+        };
+        let canonical =
+            canonical_request(method, path_and_query, self.timestamp, &self.nonce, body);
+        mac(signing_key, &canonical)
+            .verify_slice(&signature)
+            .is_ok()
+    }
+}
+
+impl fmt::Display for SignedAuthorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} KeyId={},Timestamp={},Nonce={},Signature={}",
+            SIGNATURE_SCHEME, self.key_id, self.timestamp, self.nonce, self.signature
+        )
+    }
+}
+
+/// 客户端签名器；只在本地持有派生密钥
+#[derive(Clone)]
+pub struct RequestSigner {
+    key_id: String,
+    signing_key: [u8; 32],
+}
+
+impl fmt::Debug for RequestSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestSigner")
+            .field("key_id", &self.key_id)
+            .field("signing_key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl RequestSigner {
+    pub fn new(key_id: impl Into<String>, key_secret: &str) -> Self {
+        Self {
+            key_id: key_id.into(),
+            signing_key: derive_signing_key(key_secret),
+        }
+    }
+
+    /// 以当前时间与随机 nonce 签名，返回 `Authorization` 头的值
+    pub fn sign(&self, method: &str, path_and_query: &str, body: &[u8]) -> String {
+        let nonce = uuid::Uuid::new_v4().simple().to_string();
+        self.sign_at(
+            method,
+            path_and_query,
+            body,
+            chrono::Utc::now().timestamp(),
+            &nonce,
+        )
+        .to_string()
+    }
+
+    /// 以指定时间戳与 nonce 签名
+    pub fn sign_at(
+        &self,
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+        timestamp: i64,
+        nonce: &str,
+    ) -> SignedAuthorization {
+        let canonical = canonical_request(method, path_and_query, timestamp, nonce, body);
+        SignedAuthorization {
+            key_id: self.key_id.clone(),
+            timestamp,
+            nonce: nonce.to_string(),
+            signature: hex::encode(mac(&self.signing_key, &canonical).finalize().into_bytes()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONCE: &str = "0123456789abcdef";
+
+    #[test]
+    fn test_signature_round_trips_through_header() {
+        let signer = RequestSigner::new("key-1", "secret");
+        let signed = signer.sign_at("post", "/api/v1/generate", b"{}", 1_700_000_000, NONCE);
+        let header = signed.to_string();
+        assert!(SignedAuthorization::is_signed(&header));
+
+        let parsed = SignedAuthorization::parse(&header).unwrap();
+        assert_eq!(parsed, signed);
+        let signing_key = derive_signing_key("secret");
+        assert!(parsed.verify(&signing_key, "POST", "/api/v1/generate", b"{}"));
+    }
+
+    #[test]
+    fn test_signature_binds_method_path_and_body() {
+        let signing_key = derive_signing_key("secret");
+        let signed = RequestSigner::new("key-1", "secret").sign_at(
+            "POST",
+            "/api/v1/generate",
+            b"{}",
+            1_700_000_000,
+            NONCE,
+        );
+        assert!(!signed.verify(&signing_key, "PUT", "/api/v1/generate", b"{}"));
+        assert!(!signed.verify(&signing_key, "POST", "/api/v1/parse", b"{}"));
+        assert!(!signed.verify(&signing_key, "POST", "/api/v1/generate", b"{ }"));
+        assert!(!signed.verify(
+            &derive_signing_key("other"),
+            "POST",
+            "/api/v1/generate",
+            b"{}"
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_headers() {
+        let signature = "a".repeat(64);
+        for header in [
+            "ApiKey id:secret".to_string(),
+            format!("{} KeyId=k,Timestamp=1,Nonce={}", SIGNATURE_SCHEME, NONCE),
+            format!(
+                "{} KeyId=k,Timestamp=x,Nonce={},Signature={}",
+                SIGNATURE_SCHEME, NONCE, signature
+            ),
+            format!(
+                "{} KeyId=k,Timestamp=1,Nonce=short,Signature={}",
+                SIGNATURE_SCHEME, signature
+            ),
+            format!(
+                "{} KeyId=k,KeyId=j,Timestamp=1,Nonce={},Signature={}",
+                SIGNATURE_SCHEME, NONCE, signature
+            ),
+        ] {
+            assert!(
+                SignedAuthorization::parse(&header).is_err(),
+                "should reject: {}",
+                header
+            );
+        }
+    }
+}
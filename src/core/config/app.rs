@@ -58,6 +58,63 @@ impl From<String> for DatabaseEngine {
     }
 }
 
+/// 号段分配方式
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SegmentAllocationMode {
+    /// 数据库原子更新，无需外部锁：PostgreSQL 使用 `UPDATE ... RETURNING`，
+    /// MySQL / SQLite 在同一事务内 `UPDATE` 后读回
+    #[default]
+    Atomic,
+    /// 先获取分布式锁，再在事务中 `SELECT ... FOR UPDATE` 读改写号段
+    Locked,
+}
+
+impl std::fmt::Display for SegmentAllocationMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SegmentAllocationMode::Atomic => write!(f, "atomic"),
+            SegmentAllocationMode::Locked => write!(f, "locked"),
+        }
+    }
+}
+
+impl From<&str> for SegmentAllocationMode {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "locked" => SegmentAllocationMode::Locked,
+            _ => SegmentAllocationMode::Atomic,
+        }
+    }
+}
+
+/// 只读副本配置。配置了副本时列表、查询类读请求轮询分发到健康副本，
+/// 写入与号段分配始终走主库；复制延迟超过 `max_lag_seconds` 或健康检查失败的
+/// 副本暂停承接读请求，全部副本不可用时读请求回退主库。
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct ReplicaConfig {
+    /// 副本的完整连接 URL，为空时不启用读写分离
+    pub urls: Vec<String>,
+    /// 每个副本连接池的最大连接数
+    pub max_connections: u32,
+    /// 可容忍的最大复制延迟（秒）
+    pub max_lag_seconds: u64,
+    /// 健康与复制延迟检查间隔（秒）
+    pub health_check_interval_seconds: u64,
+}
+
+impl Default for ReplicaConfig {
+    fn default() -> Self {
+        Self {
+            urls: Vec::new(),
+            max_connections: 20,
+            max_lag_seconds: 5,
+            health_check_interval_seconds: 10,
+        }
+    }
+}
+
 /// Application configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AppConfig {
@@ -140,6 +197,12 @@ pub struct DatabaseConfig {
     pub acquire_timeout_seconds: u64,
     /// Idle connection timeout (seconds)
     pub idle_timeout_seconds: u64,
+    /// Segment allocation strategy
+    #[serde(default)]
+    pub segment_allocation: SegmentAllocationMode,
+    /// Read replicas
+    #[serde(default)]
+    pub replicas: ReplicaConfig,
 }
 
 impl Default for DatabaseConfig {
@@ -162,6 +225,8 @@ impl Default for DatabaseConfig {
                 min_connections: 10,
                 acquire_timeout_seconds: 30,
                 idle_timeout_seconds: 300,
+                segment_allocation: SegmentAllocationMode::Atomic,
+                replicas: ReplicaConfig::default(),
             };
         }
 
@@ -180,6 +245,8 @@ impl Default for DatabaseConfig {
                 min_connections: 1,
                 acquire_timeout_seconds: 30,
                 idle_timeout_seconds: 300,
+                segment_allocation: SegmentAllocationMode::Atomic,
+                replicas: ReplicaConfig::default(),
             };
         }
 
@@ -206,6 +273,8 @@ impl Default for DatabaseConfig {
             min_connections: 10,
             acquire_timeout_seconds: 30,
             idle_timeout_seconds: 300,
+            segment_allocation: SegmentAllocationMode::Atomic,
+            replicas: ReplicaConfig::default(),
         }
     }
 }
@@ -284,6 +353,25 @@ mod tests {
         assert_eq!(e, DatabaseEngine::Sqlite);
     }
 
+    // ----- SegmentAllocationMode -----
+
+    #[test]
+    fn test_segment_allocation_mode_from_str_defaults_to_atomic() {
+        assert_eq!(
+            SegmentAllocationMode::from("LOCKED"),
+            SegmentAllocationMode::Locked
+        );
+        assert_eq!(
+            SegmentAllocationMode::from("atomic"),
+            SegmentAllocationMode::Atomic
+        );
+        assert_eq!(
+            SegmentAllocationMode::from("unknown"),
+            SegmentAllocationMode::Atomic
+        );
+        assert_eq!(SegmentAllocationMode::Locked.to_string(), "locked");
+    }
+
     #[test]
     fn test_database_engine_from_str_unknown_falls_back_to_postgresql() {
         // 未知字符串应回退到 PostgreSQL（默认值，不报错）
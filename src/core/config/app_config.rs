@@ -15,10 +15,11 @@
 //! Top-level Config aggregation and loading.
 
 use super::{
-    AlgorithmConfig, AppConfig, AuthConfig, BatchGenerateConfig, ConfigError, ConfigResult,
-    DatabaseConfig, EtcdConfig, LogLevel, LoggingConfig, MonitoringConfig, RateLimitConfig,
-    RedisConfig, TlsConfig,
+    AlgorithmConfig, AppConfig, AuditConfig, AuthConfig, BatchGenerateConfig, ConfigError,
+    ConfigResult, DatabaseConfig, EtcdConfig, LogLevel, LoggingConfig, MonitoringConfig,
+    OtlpProtocol, RateLimitConfig, RedisConfig, SyslogProtocol, TlsConfig, HEALTH_COMPONENTS,
 };
+use crate::core::algorithm::{AuditEventType, AuditResult};
 use serde::{Deserialize, Serialize};
 
 /// Complete application configuration
@@ -47,6 +48,9 @@ pub struct Config {
     pub tls: TlsConfig,
     /// Batch generation settings
     pub batch_generate: BatchGenerateConfig,
+    /// Audit log settings
+    #[serde(default)]
+    pub audit: AuditConfig,
 }
 
 /// 使用 confers 从 TOML 字符串解析 Config。
@@ -212,6 +216,126 @@ impl Config {
             ));
         }
 
+        if !(0.0..=1.0).contains(&self.monitoring.trace_sample_ratio) {
+            return Err(ConfigError::InvalidValue(
+                "Monitoring trace_sample_ratio must be between 0.0 and 1.0".to_string(),
+            ));
+        }
+
+        let capacity = &self.monitoring.capacity;
+        if capacity.id_ceiling == 0 || capacity.id_ceiling > i64::MAX as u64 {
+            return Err(ConfigError::InvalidValue(
+                "Monitoring capacity id_ceiling must be between 1 and i64::MAX".to_string(),
+            ));
+        }
+        if capacity.critical_threshold_secs > capacity.warning_threshold_secs {
+            return Err(ConfigError::InvalidValue(
+                "Monitoring capacity critical_threshold_secs must not exceed warning_threshold_secs"
+                    .to_string(),
+            ));
+        }
+
+        let health = &self.monitoring.health;
+        if let Some(unknown) = health
+            .readiness_required
+            .iter()
+            .chain(&health.readiness_strict)
+            .find(|name| !HEALTH_COMPONENTS.contains(&name.as_str()))
+        {
+            return Err(ConfigError::InvalidValue(format!(
+                "Monitoring health readiness component '{}' is unknown",
+                unknown
+            )));
+        }
+
+        if self.audit.checkpoint_interval == 0 {
+            return Err(ConfigError::InvalidValue(
+                "Audit checkpoint_interval must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.audit.channel_capacity == 0 {
+            return Err(ConfigError::InvalidValue(
+                "Audit channel_capacity must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.audit.database_enabled
+            && (self.audit.database_batch_size == 0 || self.audit.database_flush_interval_ms == 0)
+        {
+            return Err(ConfigError::InvalidValue(
+                "Audit database_batch_size and database_flush_interval_ms must be greater than 0"
+                    .to_string(),
+            ));
+        }
+
+        self.validate_audit_sinks()?;
+
+        Ok(())
+    }
+
+    /// 只校验已启用的 sink：目标地址必填，过滤名必须是已知的事件类型 / 结果
+    fn validate_audit_sinks(&self) -> ConfigResult<()> {
+        let sinks = &self.audit.sinks;
+        let invalid = |msg: String| Err(ConfigError::InvalidValue(msg));
+
+        let mut enabled = Vec::new();
+        if sinks.syslog.enabled {
+            if sinks.syslog.address.trim().is_empty() {
+                return invalid("Audit syslog sink requires an address".to_string());
+            }
+            if sinks.syslog.protocol == SyslogProtocol::Tls
+                && sinks.syslog.ca_cert_path.trim().is_empty()
+            {
+                return invalid("Audit syslog sink over TLS requires ca_cert_path".to_string());
+            }
+            enabled.push(("syslog", &sinks.syslog.delivery));
+        }
+        if sinks.webhook.enabled {
+            if !(sinks.webhook.url.starts_with("http://")
+                || sinks.webhook.url.starts_with("https://"))
+            {
+                return invalid("Audit webhook sink url must be an http(s) URL".to_string());
+            }
+            if sinks.webhook.batch_size == 0 || sinks.webhook.flush_interval_ms == 0 {
+                return invalid(
+                    "Audit webhook sink batch_size and flush_interval_ms must be greater than 0"
+                        .to_string(),
+                );
+            }
+            enabled.push(("webhook", &sinks.webhook.delivery));
+        }
+        if sinks.file.enabled {
+            if sinks.file.path.trim().is_empty() || sinks.file.path == self.audit.log_path {
+                return invalid(
+                    "Audit file sink requires a path different from audit.log_path".to_string(),
+                );
+            }
+            enabled.push(("file", &sinks.file.delivery));
+        }
+
+        for (name, delivery) in enabled {
+            if delivery.channel_capacity == 0 {
+                return invalid(format!(
+                    "Audit {} sink channel_capacity must be greater than 0",
+                    name
+                ));
+            }
+            let unknown_type = delivery.event_types.iter().find(|t| {
+                serde_json::from_value::<AuditEventType>(serde_json::Value::String(t.to_string()))
+                    .is_err()
+            });
+            let unknown_result = delivery.results.iter().find(|r| {
+                serde_json::from_value::<AuditResult>(serde_json::Value::String(r.to_string()))
+                    .is_err()
+            });
+            if let Some(unknown) = unknown_type.or(unknown_result) {
+                return invalid(format!(
+                    "Audit {} sink filter value '{}' is unknown",
+                    name, unknown
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -261,6 +385,15 @@ impl Config {
             config.database.url = url;
         }
 
+        if let Ok(urls) = std::env::var("DATABASE_REPLICA_URLS") {
+            config.database.replicas.urls = urls
+                .split(',')
+                .map(str::trim)
+                .filter(|url| !url.is_empty())
+                .map(String::from)
+                .collect();
+        }
+
         if let Ok(endpoints) = std::env::var("ETCD_ENDPOINTS") {
             config.etcd.endpoints = endpoints.split(',').map(String::from).collect();
         }
@@ -269,6 +402,26 @@ impl Config {
             config.logging.level = LogLevel::from(level);
         }
 
+        if let Ok(key) = std::env::var("NEBULA_AUDIT_SIGNING_KEY") {
+            config.audit.signing_key = key;
+        }
+
+        // OpenTelemetry 标准环境变量（与 SDK 约定一致）
+        if let Ok(endpoint) = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            config.monitoring.otlp_endpoint = endpoint;
+        }
+        if let Ok(protocol) = std::env::var("OTEL_EXPORTER_OTLP_PROTOCOL") {
+            config.monitoring.otlp_protocol = match protocol.as_str() {
+                "grpc" => OtlpProtocol::Grpc,
+                "http/protobuf" | "http" => OtlpProtocol::Http,
+                _ => {
+                    return Err(ConfigError::InvalidValue(
+                        "OTEL_EXPORTER_OTLP_PROTOCOL".to_string(),
+                    ))
+                }
+            };
+        }
+
         Ok(config)
     }
 
@@ -295,6 +448,9 @@ impl Config {
         if other.database.max_connections != 100 {
             self.database.max_connections = other.database.max_connections;
         }
+        if !other.database.replicas.urls.is_empty() {
+            self.database.replicas.urls = other.database.replicas.urls;
+        }
 
         if !other.etcd.endpoints.is_empty() {
             self.etcd.endpoints = other.etcd.endpoints;
@@ -320,6 +476,11 @@ impl Config {
         if !other.monitoring.otlp_endpoint.is_empty() {
             self.monitoring.otlp_endpoint = other.monitoring.otlp_endpoint;
         }
+        self.monitoring.otlp_protocol = other.monitoring.otlp_protocol;
+
+        if !other.audit.signing_key.is_empty() {
+            self.audit.signing_key = other.audit.signing_key;
+        }
 
         if other.logging.level != LogLevel::Info {
             self.logging.level = other.logging.level;
@@ -705,6 +866,132 @@ mod tests {
         );
     }
 
+    /// trace_sample_ratio 超出 [0.0, 1.0] 时校验失败
+    #[test]
+    fn validate_trace_sample_ratio_out_of_range_fails() {
+        let mut config = Config::default();
+        config.monitoring.trace_sample_ratio = 1.5;
+        assert_invalid_value(
+            config.validate(),
+            "Monitoring trace_sample_ratio must be between 0.0 and 1.0",
+        );
+    }
+
+    /// 容量告警：critical 阈值大于 warning 阈值时校验失败
+    #[test]
+    fn validate_capacity_thresholds_out_of_order_fails() {
+        let mut config = Config::default();
+        config.monitoring.capacity.critical_threshold_secs = 90 * 24 * 60 * 60;
+        assert_invalid_value(
+            config.validate(),
+            "Monitoring capacity critical_threshold_secs must not exceed warning_threshold_secs",
+        );
+    }
+
+    /// 容量告警：id_ceiling 超出 i64 范围时校验失败
+    #[test]
+    fn validate_capacity_id_ceiling_above_i64_max_fails() {
+        let mut config = Config::default();
+        config.monitoring.capacity.id_ceiling = u64::MAX;
+        assert_invalid_value(
+            config.validate(),
+            "Monitoring capacity id_ceiling must be between 1 and i64::MAX",
+        );
+    }
+
+    /// 就绪策略引用未知组件时校验失败
+    #[test]
+    fn validate_health_readiness_unknown_component_fails() {
+        let mut config = Config::default();
+        config.monitoring.health.readiness_strict = vec!["primary".to_string()];
+        assert_invalid_value(
+            config.validate(),
+            "Monitoring health readiness component 'primary' is unknown",
+        );
+    }
+
+    /// 审计检查点间隔为 0 时校验失败
+    #[test]
+    fn validate_audit_checkpoint_interval_zero_fails() {
+        let mut config = Config::default();
+        config.audit.checkpoint_interval = 0;
+        assert_invalid_value(
+            config.validate(),
+            "Audit checkpoint_interval must be greater than 0",
+        );
+    }
+
+    /// 审计写入队列容量为 0 时校验失败
+    #[test]
+    fn validate_audit_channel_capacity_zero_fails() {
+        let mut config = Config::default();
+        config.audit.channel_capacity = 0;
+        assert_invalid_value(
+            config.validate(),
+            "Audit channel_capacity must be greater than 0",
+        );
+    }
+
+    /// 启用数据库审计时批大小为 0 校验失败；关闭后不再校验
+    #[test]
+    fn validate_audit_database_batch_size_zero_fails_only_when_enabled() {
+        let mut config = Config::default();
+        config.audit.database_batch_size = 0;
+        assert_invalid_value(
+            config.validate(),
+            "Audit database_batch_size and database_flush_interval_ms must be greater than 0",
+        );
+
+        config.audit.database_enabled = false;
+        assert!(config.validate().is_ok());
+    }
+
+    /// 已启用的外部 sink 缺少目标或过滤名未知时校验失败；未启用时不校验
+    #[test]
+    fn validate_audit_sinks_only_when_enabled() {
+        let mut config = Config::default();
+        config.audit.sinks.syslog.protocol = SyslogProtocol::Tls;
+        assert!(config.validate().is_ok());
+
+        config.audit.sinks.syslog.enabled = true;
+        assert_invalid_value(config.validate(), "Audit syslog sink requires an address");
+        config.audit.sinks.syslog.address = "siem:6514".to_string();
+        assert_invalid_value(
+            config.validate(),
+            "Audit syslog sink over TLS requires ca_cert_path",
+        );
+        config.audit.sinks.syslog.ca_cert_path = "/etc/nebula/siem-ca.pem".to_string();
+        config.audit.sinks.syslog.delivery.event_types = vec!["ConfigChanged".to_string()];
+        assert_invalid_value(
+            config.validate(),
+            "Audit syslog sink filter value 'ConfigChanged' is unknown",
+        );
+        config.audit.sinks.syslog.delivery.event_types = vec!["ConfigChange".to_string()];
+        assert!(config.validate().is_ok());
+
+        config.audit.sinks.file.enabled = true;
+        assert_invalid_value(
+            config.validate(),
+            "Audit file sink requires a path different from audit.log_path",
+        );
+    }
+
+    /// 旧配置缺少 otlp_protocol / service_name / trace_sample_ratio 时使用默认值
+    #[test]
+    fn monitoring_config_new_fields_default_when_absent() {
+        let legacy = serde_json::json!({
+            "metrics_enabled": true,
+            "metrics_path": "/metrics",
+            "tracing_enabled": true,
+            "otlp_endpoint": "http://otel:4318"
+        });
+        let monitoring: MonitoringConfig =
+            serde_json::from_value(legacy).expect("旧版 monitoring 配置应能解析");
+        assert_eq!(monitoring.otlp_protocol, OtlpProtocol::Grpc);
+        assert_eq!(monitoring.service_name, "nebula-id");
+        assert_eq!(monitoring.trace_sample_ratio, 1.0);
+    }
+
     // ==================== expand_env_vars 测试 ====================
 
     /// 存在的环境变量应被替换为对应值
@@ -765,6 +1052,22 @@ mod tests {
         assert_eq!(config.app.worker_id, 0);
     }
 
+    /// NEBULA_AUDIT_SIGNING_KEY 只在环境加载路径读取，默认配置不受其影响
+    #[test]
+    fn load_from_env_audit_signing_key() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let _g = VarGuard::set("NEBULA_AUDIT_SIGNING_KEY", "env-signing-key");
+
+        assert_eq!(Config::default().audit.signing_key, "");
+        let config = Config::load_from_env().unwrap();
+        assert_eq!(config.audit.signing_key, "env-signing-key");
+
+        let mut base = Config::default();
+        base.audit.signing_key = "file-signing-key".to_string();
+        base.merge(config);
+        assert_eq!(base.audit.signing_key, "env-signing-key");
+    }
+
     /// APP_HOST 环境变量应被加载到 app.host
     #[test]
     fn load_from_env_app_host() {
@@ -883,6 +1186,24 @@ mod tests {
         );
     }
 
+    /// DATABASE_REPLICA_URLS 环境变量应按逗号拆分加载，忽略空项
+    #[test]
+    fn load_from_env_database_replica_urls() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let _g = VarGuard::set(
+            "DATABASE_REPLICA_URLS",
+            "postgres://replica1/db, postgres://replica2/db,",
+        );
+        let config = Config::load_from_env().unwrap();
+        assert_eq!(
+            config.database.replicas.urls,
+            vec![
+                "postgres://replica1/db".to_string(),
+                "postgres://replica2/db".to_string()
+            ]
+        );
+    }
+
     /// RUST_LOG 环境变量应被加载为对应日志级别
     #[test]
     fn load_from_env_rust_log() {
@@ -954,6 +1275,20 @@ mod tests {
         assert_eq!(base.logging.level, LogLevel::Debug);
     }
 
+    /// otlp_protocol 与 algorithm 子配置一样整体取 other 的值，gRPC 也能覆盖 HTTP
+    #[test]
+    fn merge_takes_otlp_protocol_from_other() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let mut base = Config::default();
+        base.monitoring.otlp_protocol = OtlpProtocol::Http;
+        let mut other = Config::default();
+        other.monitoring.otlp_protocol = OtlpProtocol::Grpc;
+
+        base.merge(other);
+
+        assert_eq!(base.monitoring.otlp_protocol, OtlpProtocol::Grpc);
+    }
+
     /// 默认 other 不应覆盖 base 的自定义字段（覆盖各 if 的 false 分支）
     ///
     /// 加 ENV_LOCK：`Config::default()` 内部读取 `DATABASE_URL` 环境变量，
@@ -0,0 +1,263 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Audit log configuration.
+
+use serde::{Deserialize, Serialize};
+
+/// 写入队列满时的处理策略
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOverflowPolicy {
+    /// 调用方等待队列腾出空间（不丢事件，磁盘停顿时请求被拖慢）
+    Block,
+    /// 丢弃队列中最旧的事件（请求不受影响，丢弃计入指标）
+    DropOldest,
+    /// 溢出事件追加到 `<log_path>.spill`，writer 追上后按顺序补写；
+    /// 入队不等待，默认策略
+    #[default]
+    Spill,
+}
+
+impl std::fmt::Display for AuditOverflowPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditOverflowPolicy::Block => write!(f, "block"),
+            AuditOverflowPolicy::DropOldest => write!(f, "drop_oldest"),
+            AuditOverflowPolicy::Spill => write!(f, "spill"),
+        }
+    }
+}
+
+/// 外部 sink 队列满时的处理策略。sink 不允许阻塞请求路径，只能选择丢弃哪一端
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSinkOverflowPolicy {
+    /// 丢弃新到的事件，保留已排队的事件
+    #[default]
+    DropNewest,
+    /// 丢弃队列中最旧的事件，优先转发最新事件
+    DropOldest,
+}
+
+impl std::fmt::Display for AuditSinkOverflowPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditSinkOverflowPolicy::DropNewest => write!(f, "drop_newest"),
+            AuditSinkOverflowPolicy::DropOldest => write!(f, "drop_oldest"),
+        }
+    }
+}
+
+/// 外部 sink 共用的过滤与队列设置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct AuditSinkDelivery {
+    /// 只转发这些事件类型（如 `ConfigChange`）；为空时全部转发
+    pub event_types: Vec<String>,
+    /// 只转发这些结果（`Success` / `Failure` / `Partial` / `Unknown`）；为空时全部转发
+    pub results: Vec<String>,
+    /// 待发送队列容量（事件数）
+    pub channel_capacity: usize,
+    pub overflow_policy: AuditSinkOverflowPolicy,
+}
+
+impl Default for AuditSinkDelivery {
+    fn default() -> Self {
+        Self {
+            event_types: Vec::new(),
+            results: Vec::new(),
+            channel_capacity: 1000,
+            overflow_policy: AuditSinkOverflowPolicy::DropNewest,
+        }
+    }
+}
+
+/// syslog 传输协议
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogProtocol {
+    /// RFC 5426，每条消息一个数据报
+    #[default]
+    Udp,
+    /// RFC 6587 octet-counting 帧
+    Tcp,
+    /// RFC 5425
+    Tls,
+}
+
+/// RFC 5424 syslog sink
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct SyslogSinkConfig {
+    pub enabled: bool,
+    pub protocol: SyslogProtocol,
+    /// 收集端地址 `host:port`
+    pub address: String,
+    /// TLS 协议下校验收集端证书的 CA（PEM）
+    pub ca_cert_path: String,
+    /// syslog facility，默认 13（log audit）
+    pub facility: u8,
+    pub app_name: String,
+    #[serde(flatten)]
+    pub delivery: AuditSinkDelivery,
+}
+
+impl Default for SyslogSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            protocol: SyslogProtocol::Udp,
+            address: String::new(),
+            ca_cert_path: String::new(),
+            facility: 13,
+            app_name: "nebula-id".to_string(),
+            delivery: AuditSinkDelivery::default(),
+        }
+    }
+}
+
+/// HTTP 批量 webhook sink：POST JSON 数组，失败按指数退避重试
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct WebhookSinkConfig {
+    pub enabled: bool,
+    pub url: String,
+    /// 原样写入 `Authorization` 请求头（如 `Bearer <token>`）；为空时不发送
+    pub auth_header: String,
+    /// 每个请求最多携带的事件数
+    pub batch_size: usize,
+    /// 未攒满一批时的最长等待（毫秒）
+    pub flush_interval_ms: u64,
+    pub timeout_ms: u64,
+    /// 网络错误、429 与 5xx 的最大重试次数
+    pub max_retries: u32,
+    /// 首次重试前的等待（毫秒），之后每次翻倍
+    pub retry_backoff_ms: u64,
+    #[serde(flatten)]
+    pub delivery: AuditSinkDelivery,
+}
+
+impl Default for WebhookSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            auth_header: String::new(),
+            batch_size: 100,
+            flush_interval_ms: 1000,
+            timeout_ms: 5000,
+            max_retries: 3,
+            retry_backoff_ms: 500,
+            delivery: AuditSinkDelivery::default(),
+        }
+    }
+}
+
+/// 轮转文件 sink：脱敏后的 JSON lines（不带 hash 链），供日志采集器读取
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct FileSinkConfig {
+    pub enabled: bool,
+    pub path: String,
+    /// 0 表示不按大小轮转
+    pub max_file_size_mb: u64,
+    /// 0 表示不按时间轮转
+    pub rotation_interval_secs: u64,
+    /// 0 表示永久保留
+    pub retention_days: u64,
+    pub compress_rotated: bool,
+    #[serde(flatten)]
+    pub delivery: AuditSinkDelivery,
+}
+
+impl Default for FileSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: String::new(),
+            max_file_size_mb: 100,
+            rotation_interval_secs: 86_400,
+            retention_days: 30,
+            compress_rotated: true,
+            delivery: AuditSinkDelivery::default(),
+        }
+    }
+}
+
+/// 外部审计 sink（SIEM 转发）；每个 sink 独立过滤、独立队列
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct AuditSinksConfig {
+    pub syslog: SyslogSinkConfig,
+    pub webhook: WebhookSinkConfig,
+    pub file: FileSinkConfig,
+}
+
+/// Audit log configuration
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    /// 持久化审计日志路径（JSON lines，hash 链）；为空时只保留内存中的最近事件
+    pub log_path: String,
+    /// 每写入多少条记录生成一个签名检查点（写入 `<log_path>.checkpoints`）
+    pub checkpoint_interval: u64,
+    /// 检查点 HMAC-SHA256 签名密钥；设置了 `NEBULA_AUDIT_SIGNING_KEY` 时由环境变量覆盖，
+    /// 为空时不生成检查点（hash 链仍然生效）
+    pub signing_key: String,
+    /// 单个日志文件达到该大小（MB）后轮转；0 表示不按大小轮转
+    pub max_file_size_mb: u64,
+    /// 日志文件打开超过该时长（秒）后轮转；0 表示不按时间轮转
+    pub rotation_interval_secs: u64,
+    /// 轮转文件保留天数，超期删除；0 表示永久保留
+    pub retention_days: u64,
+    /// 轮转后的文件是否 gzip 压缩
+    pub compress_rotated: bool,
+    /// 写入队列容量（事件数）
+    pub channel_capacity: usize,
+    /// 写入队列满时的处理策略
+    pub overflow_policy: AuditOverflowPolicy,
+    /// Block 策略下 ID 生成请求的审计是否不等待队列：开启后队列满时丢弃该事件
+    /// （计入 `dropped_total`），换取生成延迟不受磁盘停顿影响；默认关闭，不丢事件
+    pub nonblocking_generation: bool,
+    /// 是否将审计事件批量写入数据库 `audit_events` 表（供 `GET /api/v1/audit` 查询）
+    pub database_enabled: bool,
+    /// 每批写入数据库的最大事件数
+    pub database_batch_size: usize,
+    /// 未攒满一批时的最长写入间隔（毫秒）
+    pub database_flush_interval_ms: u64,
+    /// 外部 sink（syslog / webhook / 文件）
+    pub sinks: AuditSinksConfig,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            log_path: String::new(),
+            checkpoint_interval: 1000,
+            signing_key: String::new(),
+            max_file_size_mb: 100,
+            rotation_interval_secs: 86_400,
+            retention_days: 90,
+            compress_rotated: true,
+            channel_capacity: 10_000,
+            overflow_policy: AuditOverflowPolicy::Spill,
+            nonblocking_generation: false,
+            database_enabled: true,
+            database_batch_size: 100,
+            database_flush_interval_ms: 1000,
+            sinks: AuditSinksConfig::default(),
+        }
+    }
+}
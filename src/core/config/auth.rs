@@ -25,7 +25,7 @@ pub struct ApiKeyEntry {
     pub key_secret: String,
     /// Associated workspace
     pub workspace: String,
-    /// Key role (admin/user)
+    /// Key role (admin/workspace_admin/user)
     pub role: String,
     /// Rate limit (requests per second)
     pub rate_limit: u32,
@@ -51,6 +51,179 @@ pub struct AuthConfig {
     /// 默认 7 天，与原 `const GRACE_PERIOD_SECONDS: u64 = 7 * 24 * 60 * 60` 保持一致。
     #[serde(default = "default_key_rotation_grace_period_seconds")]
     pub key_rotation_grace_period_seconds: u64,
+    /// HMAC 签名请求允许的时间戳偏差（秒）；nonce 在同一窗口内不可重复，
+    /// 已用 nonce 经数据库在各实例间共享
+    #[serde(default = "default_signature_max_skew_seconds")]
+    pub signature_max_skew_seconds: u64,
+    /// workspace 管理员自助创建 key 时，每个 workspace 启用的 key 数量上限
+    /// （吊销或禁用的 key 不计入）；0 表示不限制。全局 Admin 不受此限制。
+    #[serde(default = "default_max_api_keys_per_workspace")]
+    pub max_api_keys_per_workspace: u64,
+    /// JWT / OIDC bearer token 认证，与 API key 并存
+    #[serde(default)]
+    pub jwt: JwtConfig,
+    /// API key 生命周期检查（过期告警、自动禁用）
+    #[serde(default)]
+    pub lifecycle: ApiKeyLifecycleConfig,
+    /// 已校验凭证缓存及吊销传播
+    #[serde(default)]
+    pub credential_cache: CredentialCacheConfig,
+    /// 认证失败封禁（按 IP 与 key_id，逐级升级）
+    #[serde(default)]
+    pub bans: BanConfig,
+}
+
+/// JWT / OIDC bearer token 认证配置（RS256 / ES256，公钥来自 JWKS）
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct JwtConfig {
+    pub enabled: bool,
+    /// 期望的 `iss`；为空时不校验
+    pub issuer: String,
+    /// 期望的 `aud`；为空时不校验
+    pub audience: String,
+    /// JWKS 地址（如 OIDC provider 的 `jwks_uri`）；优先于 `jwks_path`
+    pub jwks_url: String,
+    /// 本地 JWKS 文件路径
+    pub jwks_path: String,
+    /// 定期重新加载 JWKS 的间隔（秒）；遇到未知 `kid` 时也会提前刷新
+    pub jwks_refresh_seconds: u64,
+    /// `exp` / `nbf` 允许的时钟偏差（秒）
+    pub leeway_seconds: u64,
+    /// workspace ID（UUID）所在的 claim，支持 `a.b` 形式的嵌套路径
+    pub workspace_claim: String,
+    /// 角色所在的 claim（字符串或字符串数组）
+    pub role_claim: String,
+    /// 角色 claim 含该值时映射为 Admin，否则为 User
+    pub admin_role: String,
+    /// 作用域所在的 claim（空格分隔字符串或字符串数组）；缺失时不授予任何作用域，
+    /// 无法识别的值（如 `openid`）被忽略
+    pub scopes_claim: String,
+    /// 缺少 `scopes_claim` 的 token 视为不受限（与未设置作用域的 API key 一致）。
+    /// 仅用于不签发作用域的 IdP，默认关闭
+    pub missing_scopes_unrestricted: bool,
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            issuer: String::new(),
+            audience: String::new(),
+            jwks_url: String::new(),
+            jwks_path: String::new(),
+            jwks_refresh_seconds: 300,
+            leeway_seconds: 60,
+            workspace_claim: "workspace_id".to_string(),
+            role_claim: "role".to_string(),
+            admin_role: "admin".to_string(),
+            scopes_claim: "scope".to_string(),
+            missing_scopes_unrestricted: false,
+        }
+    }
+}
+
+/// API key 生命周期策略；各阈值为 0 时关闭对应检查
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct ApiKeyLifecycleConfig {
+    pub enabled: bool,
+    /// 检查间隔（秒）
+    pub check_interval_seconds: u64,
+    /// 创建（或上次轮换）超过该天数的 key 标记为待轮换
+    pub max_key_age_days: u64,
+    /// 待轮换的 key 自动轮换，旧密钥在 `key_rotation_grace_period_seconds` 内仍可用。
+    /// 新密钥不会送达调用方，调用方需在宽限期内通过管理接口重新轮换取得密钥
+    pub auto_rotate: bool,
+    /// `expires_at` 前该天数内告警
+    pub expiry_warning_days: u64,
+    /// 禁用已过 `expires_at` 的 key
+    pub disable_expired: bool,
+    /// 禁用超过该天数未使用（按 `last_used_at`，从未使用按创建时间）的 User key；
+    /// Admin key 只告警，避免锁死管理入口
+    pub disable_unused_days: u64,
+    /// 告警除写日志外还 POST 到该地址；为空时只写日志
+    pub alert_webhook_url: String,
+}
+
+impl Default for ApiKeyLifecycleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            check_interval_seconds: 3600,
+            max_key_age_days: 90,
+            auto_rotate: false,
+            expiry_warning_days: 14,
+            disable_expired: true,
+            disable_unused_days: 0,
+            alert_webhook_url: String::new(),
+        }
+    }
+}
+
+/// 已校验凭证缓存：命中时跳过 Argon2id 校验。吊销、轮换和删除写入变更表，
+/// 各实例轮询变更表（配置了 etcd 时另有 watch 推送）失效对应条目，
+/// 因此跨实例传播延迟不超过 `poll_interval_ms`。
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct CredentialCacheConfig {
+    pub enabled: bool,
+    /// 条目存活时间（秒）；变更记录写入失败时的兜底失效时间
+    pub ttl_seconds: u64,
+    /// 缓存条目上限，写满时淘汰最早到期的条目
+    pub max_entries: usize,
+    /// 轮询变更表的间隔（毫秒），即跨实例传播延迟的上限
+    pub poll_interval_ms: u64,
+    /// 配置了 etcd 时通过 watch 推送变更（需 `etcd` feature），变更表轮询仍作兜底
+    pub etcd_watch: bool,
+    /// etcd 中变更通知的 key 前缀
+    pub etcd_prefix: String,
+}
+
+impl Default for CredentialCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_seconds: 60,
+            max_entries: 10_000,
+            poll_interval_ms: 1000,
+            etcd_watch: true,
+            etcd_prefix: "/nebula_id/api_key_changes/".to_string(),
+        }
+    }
+}
+
+/// 认证失败封禁策略。同一 IP 或 key_id 在 `failure_window_seconds` 内失败
+/// `failure_threshold` 次即被封禁；`escalation_window_seconds` 内再次被封禁时
+/// 等级加一，前四级时长依次为 `base_ban_seconds` 的 1、2、4、8 倍，不超过
+/// `max_ban_seconds`。失败计数与封禁都写入数据库并在实例间共享，各实例每
+/// `sync_interval_seconds` 同步一次封禁。
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct BanConfig {
+    pub enabled: bool,
+    pub failure_threshold: u64,
+    pub failure_window_seconds: u64,
+    /// 第一级封禁时长（秒）
+    pub base_ban_seconds: u64,
+    pub max_ban_seconds: u64,
+    /// 距上次封禁超过该时长后重新从第一级开始
+    pub escalation_window_seconds: u64,
+    pub sync_interval_seconds: u64,
+}
+
+impl Default for BanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: 10,
+            failure_window_seconds: 300,
+            base_ban_seconds: 300,
+            max_ban_seconds: 86_400,
+            escalation_window_seconds: 86_400,
+            sync_interval_seconds: 5,
+        }
+    }
 }
 
 fn default_api_key_salt() -> String {
@@ -78,6 +251,17 @@ fn default_key_rotation_grace_period_seconds() -> u64 {
     DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS
 }
 
+fn default_signature_max_skew_seconds() -> u64 {
+    crate::core::auth::signing::DEFAULT_MAX_CLOCK_SKEW_SECS
+}
+
+/// workspace 管理员自助创建 key 的默认数量上限
+pub const DEFAULT_MAX_API_KEYS_PER_WORKSPACE: u64 = 50;
+
+fn default_max_api_keys_per_workspace() -> u64 {
+    DEFAULT_MAX_API_KEYS_PER_WORKSPACE
+}
+
 impl Default for AuthConfig {
     fn default() -> Self {
         Self {
@@ -86,6 +270,12 @@ impl Default for AuthConfig {
             api_keys: vec![],
             api_key_salt: default_api_key_salt(),
             key_rotation_grace_period_seconds: default_key_rotation_grace_period_seconds(),
+            signature_max_skew_seconds: default_signature_max_skew_seconds(),
+            max_api_keys_per_workspace: default_max_api_keys_per_workspace(),
+            jwt: JwtConfig::default(),
+            lifecycle: ApiKeyLifecycleConfig::default(),
+            credential_cache: CredentialCacheConfig::default(),
+            bans: BanConfig::default(),
         }
     }
 }
@@ -460,6 +460,7 @@ mod tests {
             status: WorkspaceStatus::Active,
             max_groups: 10,
             max_biz_tags: 100,
+            strict_generation: true,
             created_at: now_naive(),
             updated_at: now_naive(),
         }
@@ -679,6 +680,7 @@ mod tests {
             status: None,
             max_groups: None,
             max_biz_tags: None,
+            strict_generation: None,
         };
 
         let result = manager.update_workspace(workspace_id, &request).await;
@@ -701,6 +703,7 @@ mod tests {
             status: None,
             max_groups: None,
             max_biz_tags: None,
+            strict_generation: None,
         };
 
         let result = manager.update_workspace(Uuid::new_v4(), &request).await;
@@ -724,6 +727,7 @@ mod tests {
             status: None,
             max_groups: None,
             max_biz_tags: None,
+            strict_generation: None,
         };
 
         let result = manager.update_workspace(Uuid::new_v4(), &request).await;
@@ -751,6 +755,7 @@ mod tests {
             status: Some(WorkspaceStatus::Suspended),
             max_groups: Some(20),
             max_biz_tags: Some(200),
+            strict_generation: None,
         };
 
         let result = manager.update_workspace(workspace_id, &request).await;
@@ -776,6 +781,7 @@ mod tests {
             status: None,
             max_groups: None,
             max_biz_tags: None,
+            strict_generation: None,
         };
 
         let result = manager.update_workspace(Uuid::new_v4(), &request).await;
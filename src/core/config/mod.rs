@@ -28,6 +28,7 @@ pub(crate) mod workspace;
 pub mod algorithm;
 pub mod app;
 pub mod app_config;
+pub mod audit;
 pub mod auth;
 pub mod batch;
 pub mod environment;
@@ -43,14 +44,24 @@ pub mod tls;
 pub use algorithm::{
     AlgorithmConfig, SegmentAlgorithmConfig, SnowflakeAlgorithmConfig, UuidV7Config,
 };
-pub use app::{AppConfig, DatabaseConfig, DatabaseEngine, EtcdConfig};
+pub use app::{
+    AppConfig, DatabaseConfig, DatabaseEngine, EtcdConfig, ReplicaConfig, SegmentAllocationMode,
+};
 pub use app_config::Config;
-pub use auth::{ApiKeyEntry, AuthConfig};
+pub use audit::{
+    AuditConfig, AuditOverflowPolicy, AuditSinkDelivery, AuditSinkOverflowPolicy, AuditSinksConfig,
+    FileSinkConfig, SyslogProtocol, SyslogSinkConfig, WebhookSinkConfig,
+};
+pub use auth::{
+    ApiKeyEntry, ApiKeyLifecycleConfig, AuthConfig, BanConfig, CredentialCacheConfig, JwtConfig,
+};
 pub use batch::BatchGenerateConfig;
 pub use environment::{is_production, Environment};
 pub use error::{ConfigError, ConfigResult};
 pub use logging::{LogFormat, LogLevel, LoggingConfig};
-pub use monitoring::MonitoringConfig;
+pub use monitoring::{
+    CapacityConfig, HealthConfig, MonitoringConfig, OtlpProtocol, UsageConfig, HEALTH_COMPONENTS,
+};
 pub use rate_limit::RateLimitConfig;
 pub use redis::RedisConfig;
-pub use tls::{TlsConfig, TlsVersion};
+pub use tls::{ClientAuthMode, ClientIdentityMapping, TlsConfig, TlsVersion};
@@ -16,6 +16,132 @@
 
 use serde::{Deserialize, Serialize};
 
+/// OTLP 导出协议
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OtlpProtocol {
+    /// OTLP/gRPC（默认端口 4317）
+    #[default]
+    Grpc,
+    /// OTLP/HTTP protobuf（默认端口 4318，路径 `/v1/traces`）
+    Http,
+}
+
+impl std::fmt::Display for OtlpProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OtlpProtocol::Grpc => write!(f, "grpc"),
+            OtlpProtocol::Http => write!(f, "http"),
+        }
+    }
+}
+
+fn default_service_name() -> String {
+    "nebula-id".to_string()
+}
+
+fn default_trace_sample_ratio() -> f64 {
+    1.0
+}
+
+/// 租户（workspace / biz_tag）用量统计配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct UsageConfig {
+    /// Enable per-workspace / per-biz_tag usage tracking
+    pub enabled: bool,
+    /// Maximum number of (workspace, biz_tag) keys tracked at once
+    pub max_tracked_keys: usize,
+    /// Width of one aggregation bucket in seconds
+    pub bucket_secs: u64,
+    /// How long per-bucket history is retained (upper bound of query windows)
+    pub retention_secs: u64,
+    /// Default query window for `/api/v1/admin/usage`
+    pub default_window_secs: u64,
+    /// Keys idle for longer than this are evicted
+    pub idle_eviction_secs: u64,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_tracked_keys: 1000,
+            bucket_secs: 60,
+            retention_secs: 3600,
+            default_window_secs: 300,
+            idle_eviction_secs: 3600,
+        }
+    }
+}
+
+/// 号段容量预测与耗尽告警配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct CapacityConfig {
+    /// Enable segment exhaustion forecasting and capacity alerts
+    pub enabled: bool,
+    /// Window over which the segment burn rate is measured from
+    /// `nebula_segments` samples (without a database the in-process usage
+    /// tracker is used, capped at `monitoring.usage.retention_secs`)
+    pub forecast_window_secs: u64,
+    /// Highest usable ID; defaults to `i64::MAX` (the `nebula_segments` column
+    /// type). Lower it, e.g. to 2^53 - 1, when IDs must survive JSON numbers.
+    pub id_ceiling: u64,
+    /// Projected time-to-exhaustion below which a warning alert fires
+    pub warning_threshold_secs: u64,
+    /// Projected time-to-exhaustion below which a critical alert fires
+    pub critical_threshold_secs: u64,
+    /// How often the built-in capacity alert rules are evaluated
+    pub evaluation_interval_secs: u64,
+}
+
+impl Default for CapacityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            forecast_window_secs: 3600,
+            id_ceiling: i64::MAX as u64,
+            warning_threshold_secs: 30 * 24 * 60 * 60,
+            critical_threshold_secs: 7 * 24 * 60 * 60,
+            evaluation_interval_secs: 60,
+        }
+    }
+}
+
+/// `/health` 与 `/ready` 报告中可能出现的组件名
+pub const HEALTH_COMPONENTS: &[&str] = &[
+    "database",
+    "cache",
+    "algorithms",
+    "degradation",
+    "circuit_breakers",
+    "segment_buffer",
+    "etcd",
+    "worker_lease",
+];
+
+/// 组件健康报告与就绪策略配置
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct HealthConfig {
+    /// Components that must not be `unhealthy` for `/ready` to pass
+    pub readiness_required: Vec<String>,
+    /// Components that must be fully `healthy` (not even `degraded`) for
+    /// `/ready` to pass, e.g. `["degradation"]` keeps the instance out of
+    /// rotation while it serves from the fallback chain
+    pub readiness_strict: Vec<String>,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            readiness_required: vec!["database".to_string(), "cache".to_string()],
+            readiness_strict: Vec::new(),
+        }
+    }
+}
+
 /// Monitoring configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct MonitoringConfig {
@@ -27,6 +153,24 @@ pub struct MonitoringConfig {
     pub tracing_enabled: bool,
     /// OpenTelemetry collector endpoint
     pub otlp_endpoint: String,
+    /// OTLP transport protocol (default: grpc)
+    #[serde(default)]
+    pub otlp_protocol: OtlpProtocol,
+    /// `service.name` resource attribute reported to the collector
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+    /// Parent-based trace sampling ratio in [0.0, 1.0] (default: 1.0)
+    #[serde(default = "default_trace_sample_ratio")]
+    pub trace_sample_ratio: f64,
+    /// Per-tenant usage tracking
+    #[serde(default)]
+    pub usage: UsageConfig,
+    /// Segment exhaustion forecasting
+    #[serde(default)]
+    pub capacity: CapacityConfig,
+    /// Component health report and readiness policy
+    #[serde(default)]
+    pub health: HealthConfig,
 }
 
 impl Default for MonitoringConfig {
@@ -36,6 +180,12 @@ impl Default for MonitoringConfig {
             metrics_path: "/metrics".to_string(),
             tracing_enabled: false,
             otlp_endpoint: "".to_string(),
+            otlp_protocol: OtlpProtocol::Grpc,
+            service_name: default_service_name(),
+            trace_sample_ratio: default_trace_sample_ratio(),
+            usage: UsageConfig::default(),
+            capacity: CapacityConfig::default(),
+            health: HealthConfig::default(),
         }
     }
 }
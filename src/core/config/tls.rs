@@ -37,6 +37,50 @@ impl std::fmt::Display for TlsVersion {
     }
 }
 
+/// 客户端证书校验模式（mTLS）
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientAuthMode {
+    /// 不请求客户端证书
+    #[default]
+    None,
+    /// 请求证书；出示时必须通过 `ca_path` 校验，未出示时退回 API key / JWT
+    Optional,
+    /// 握手阶段即要求有效的客户端证书
+    Required,
+}
+
+impl std::fmt::Display for ClientAuthMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientAuthMode::None => write!(f, "none"),
+            ClientAuthMode::Optional => write!(f, "optional"),
+            ClientAuthMode::Required => write!(f, "required"),
+        }
+    }
+}
+
+/// 客户端证书到调用方的映射
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct ClientIdentityMapping {
+    /// 与证书 subject CN 或 SAN（DNS / URI / email）比较；以 `*` 结尾时按前缀匹配，
+    /// 如 `spiffe://cluster.local/ns/orders/*`
+    pub pattern: String,
+    /// `user`（默认）、`workspace_admin` 或 `admin`
+    #[serde(default = "default_client_role")]
+    pub role: String,
+    /// User 必须指定
+    #[serde(default)]
+    pub workspace_id: Option<uuid::Uuid>,
+    /// 为空时不受限
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+fn default_client_role() -> String {
+    "user".to_string()
+}
+
 /// TLS configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TlsConfig {
@@ -46,7 +90,8 @@ pub struct TlsConfig {
     pub cert_path: String,
     /// Path to TLS private key file
     pub key_path: String,
-    /// Path to CA certificate file (optional)
+    /// Path to CA certificate file (optional)。客户端证书的信任根；
+    /// 未设置 `client_auth` 时仅 gRPC 要求客户端证书（兼容旧行为）
     pub ca_path: Option<String>,
     /// Enable TLS for HTTP
     pub http_enabled: bool,
@@ -58,6 +103,30 @@ pub struct TlsConfig {
     /// ALPN protocols for HTTP/2 support
     #[serde(default)]
     pub alpn_protocols: Vec<String>,
+    /// 客户端证书校验（HTTP 与 gRPC），需要 `ca_path`
+    #[serde(default)]
+    pub client_auth: ClientAuthMode,
+    /// 证书吊销列表（PEM），校验客户端证书时生效
+    #[serde(default)]
+    pub crl_paths: Vec<String>,
+    /// 客户端证书到 workspace / 角色 / 作用域的映射，按顺序取第一条匹配
+    #[serde(default)]
+    pub client_identities: Vec<ClientIdentityMapping>,
+    /// 轮询 `cert_path` / `key_path` 修改时间的间隔（秒），变化时为新握手替换证书；
+    /// 0 关闭热加载
+    #[serde(default = "default_reload_interval_seconds")]
+    pub reload_interval_seconds: u64,
+    /// 证书剩余有效期低于该天数时记录告警
+    #[serde(default = "default_expiry_warning_days")]
+    pub expiry_warning_days: u64,
+}
+
+fn default_reload_interval_seconds() -> u64 {
+    30
+}
+
+fn default_expiry_warning_days() -> u64 {
+    14
 }
 
 impl Default for TlsConfig {
@@ -71,6 +140,11 @@ impl Default for TlsConfig {
             grpc_enabled: false,
             min_tls_version: TlsVersion::Tls13,
             alpn_protocols: vec!["h2".to_string(), "http/1.1".to_string()],
+            client_auth: ClientAuthMode::None,
+            crl_paths: Vec::new(),
+            client_identities: Vec::new(),
+            reload_interval_seconds: default_reload_interval_seconds(),
+            expiry_warning_days: default_expiry_warning_days(),
         }
     }
 }
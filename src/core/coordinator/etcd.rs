@@ -68,6 +68,32 @@ pub trait EtcdClientOps: Send + Sync {
 
     /// 健康检查 ping：执行一个轻量级 etcd 操作验证连通性。
     async fn ping(&self) -> std::result::Result<(), EtcdError>;
+
+    /// 写入 value 并关联 lease_id（lease 到期后 key 自动删除）。
+    /// 默认实现不支持，供只用到锁与 worker 分配操作的实现沿用。
+    async fn kv_put_with_lease(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        lease_id: i64,
+    ) -> std::result::Result<(), EtcdError> {
+        let _ = (key, value, lease_id);
+        Err(EtcdError::Internal(
+            "kv_put_with_lease not supported".to_string(),
+        ))
+    }
+
+    /// watch `prefix` 下的写入事件，逐个返回被写入的完整 key；
+    /// watch 断开时发送端关闭，调用方需要重新 watch。默认实现不支持。
+    async fn watch_prefix_puts(
+        &self,
+        prefix: &str,
+    ) -> std::result::Result<tokio::sync::mpsc::Receiver<String>, EtcdError> {
+        let _ = prefix;
+        Err(EtcdError::Internal(
+            "watch_prefix_puts not supported".to_string(),
+        ))
+    }
 }
 
 /// etcd 操作错误类型。
@@ -182,6 +208,57 @@ impl EtcdClientOps for EtcdClientWrapper {
             .map_err(|e| EtcdError::Network(e.to_string()))?;
         Ok(())
     }
+
+    async fn kv_put_with_lease(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        lease_id: i64,
+    ) -> std::result::Result<(), EtcdError> {
+        use etcd_client::PutOptions;
+
+        let mut client = self.inner.lock().await;
+        client
+            .put(key, value, Some(PutOptions::new().with_lease(lease_id)))
+            .await
+            .map_err(|e| EtcdError::Network(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn watch_prefix_puts(
+        &self,
+        prefix: &str,
+    ) -> std::result::Result<tokio::sync::mpsc::Receiver<String>, EtcdError> {
+        use etcd_client::{EventType, WatchOptions};
+
+        // 只在建立 watch 时持锁，事件流由后台任务消费
+        let (watcher, mut stream) = {
+            let mut client = self.inner.lock().await;
+            client
+                .watch(prefix, Some(WatchOptions::new().with_prefix()))
+                .await
+                .map_err(|e| EtcdError::Network(e.to_string()))?
+        };
+        let (tx, rx) = tokio::sync::mpsc::channel(256);
+        tokio::spawn(async move {
+            // watcher 被 drop 会取消 watch，随任务一起存活
+            let _watcher = watcher;
+            while let Ok(Some(resp)) = stream.message().await {
+                for event in resp.events() {
+                    if event.event_type() != EventType::Put {
+                        continue;
+                    }
+                    let Some(key) = event.kv().and_then(|kv| kv.key_str().ok()) else {
+                        continue;
+                    };
+                    if tx.send(key.to_string()).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(rx)
+    }
 }
 
 /// etcd 集群健康监控器。
@@ -0,0 +1,87 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use dbnexus::sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// API key 变更表。每次吊销、轮换或删除写入一行，`seq` 单调递增，
+/// 各实例按 `seq` 轮询以失效本地的凭证校验缓存。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "api_key_changes", schema_name = "nebula_id")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub seq: i64,
+    pub api_key_id: Uuid,
+    pub key_id: String,
+    pub change_type: String,
+    pub changed_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 使已校验凭证失效的变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyChangeKind {
+    Revoked,
+    Rotated,
+    Deleted,
+}
+
+impl fmt::Display for ApiKeyChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiKeyChangeKind::Revoked => write!(f, "revoked"),
+            ApiKeyChangeKind::Rotated => write!(f, "rotated"),
+            ApiKeyChangeKind::Deleted => write!(f, "deleted"),
+        }
+    }
+}
+
+impl From<String> for ApiKeyChangeKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "rotated" => ApiKeyChangeKind::Rotated,
+            "deleted" => ApiKeyChangeKind::Deleted,
+            // 未知类型按吊销处理：对缓存而言同样意味着失效
+            _ => ApiKeyChangeKind::Revoked,
+        }
+    }
+}
+
+/// 一条 API key 变更记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiKeyChange {
+    pub seq: i64,
+    pub api_key_id: Uuid,
+    pub key_id: String,
+    pub kind: ApiKeyChangeKind,
+    pub changed_at: DateTime,
+}
+
+impl From<Model> for ApiKeyChange {
+    fn from(model: Model) -> Self {
+        ApiKeyChange {
+            seq: model.seq,
+            api_key_id: model.api_key_id,
+            key_id: model.key_id,
+            kind: model.change_type.into(),
+            changed_at: model.changed_at,
+        }
+    }
+}
@@ -17,6 +17,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use super::connection::NEBULA_SCHEMA;
+use crate::core::auth::ApiKeyScopes;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "api_keys", schema_name = "nebula_id")]
@@ -38,6 +39,17 @@ pub struct Model {
     pub last_used_at: Option<DateTime>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    /// 空格分隔的作用域（见 [`ApiKeyScopes`]）；`NULL` 为不受限
+    pub scopes: Option<String>,
+    /// 由 key_secret 派生的 HMAC 签名密钥（hex）；早于请求签名创建的 key 为 `NULL`，
+    /// 轮换后生成
+    pub signing_key: Option<String>,
+    /// 轮换前的密钥哈希；`previous_secret_expires_at` 之前旧密钥仍可认证
+    pub previous_secret_hash: Option<String>,
+    /// 轮换前的签名密钥（hex），与旧密钥同时失效
+    pub previous_signing_key: Option<String>,
+    /// 轮换宽限期的结束时间；宽限期为 0 的轮换不保留旧密钥
+    pub previous_secret_expires_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -72,6 +84,7 @@ pub struct ApiKey {
     pub expires_at: Option<DateTime>,
     pub last_used_at: Option<DateTime>,
     pub created_at: DateTime,
+    pub scopes: ApiKeyScopes,
 }
 
 pub type ApiKeyInfo = ApiKey;
@@ -80,6 +93,9 @@ pub type ApiKeyInfo = ApiKey;
 pub enum ApiKeyRole {
     Admin,
     User,
+    /// workspace 管理员：拥有 User 的全部权限，并可管理本 workspace 的
+    /// 非 Admin key（创建、列出、轮换、吊销）
+    WorkspaceAdmin,
     /// LOW-1 修复（CWE-1188）：禁用认证时使用的匿名角色。
     /// 该角色仅存在于内存中（请求 extensions），不会被持久化到数据库
     /// （`repository.rs` 的 `create_api_key` 会拒绝 Anonymous）。
@@ -93,6 +109,7 @@ impl fmt::Display for ApiKeyRole {
         match self {
             ApiKeyRole::Admin => write!(f, "admin"),
             ApiKeyRole::User => write!(f, "user"),
+            ApiKeyRole::WorkspaceAdmin => write!(f, "workspace_admin"),
             ApiKeyRole::Anonymous => write!(f, "anonymous"),
         }
     }
@@ -109,6 +126,7 @@ impl From<&str> for ApiKeyRole {
         match s {
             "admin" => ApiKeyRole::Admin,
             "user" => ApiKeyRole::User,
+            "workspace_admin" => ApiKeyRole::WorkspaceAdmin,
             // ARCH-LOW-002 修复：`"anonymous"` 不应从数据库反序列化。
             // Anonymous 是仅运行时存在的角色（禁用认证时注入 extensions），
             // 不应被持久化。若数据库出现 'anonymous'（运维误操作/迁移脚本
@@ -144,6 +162,10 @@ pub struct CreateApiKeyRequest {
     pub expires_at: Option<DateTime>,
     pub key_secret: Option<String>, // Optional: use provided secret instead of generating
     pub key_id: Option<String>,     // Optional: use provided key_id instead of generating
+    /// `None`：不受限（仅按角色授权）
+    pub scopes: Option<ApiKeyScopes>,
+    /// 是否支持请求签名；只有开启的 key 才保存派生签名密钥
+    pub request_signing: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -158,6 +180,7 @@ pub struct ApiKeyResponse {
     pub enabled: bool,
     pub expires_at: Option<DateTime>,
     pub created_at: DateTime,
+    pub scopes: ApiKeyScopes,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -166,6 +189,32 @@ pub struct ApiKeyWithSecret {
     pub key_secret: String,
 }
 
+/// 校验签名请求所需的 key 信息；仅对启用且未过期的 key 返回
+#[derive(Clone)]
+pub struct ApiKeySigningKey {
+    pub workspace_id: Option<Uuid>,
+    pub role: ApiKeyRole,
+    pub scopes: ApiKeyScopes,
+    pub signing_key: Vec<u8>,
+    /// 轮换宽限期内旧密钥对应的签名密钥
+    pub previous_signing_key: Option<Vec<u8>>,
+}
+
+impl fmt::Debug for ApiKeySigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiKeySigningKey")
+            .field("workspace_id", &self.workspace_id)
+            .field("role", &self.role)
+            .field("scopes", &self.scopes)
+            .field("signing_key", &"<redacted>")
+            .field(
+                "previous_signing_key",
+                &self.previous_signing_key.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
 impl From<Model> for ApiKey {
     fn from(model: Model) -> Self {
         ApiKey {
@@ -181,6 +230,7 @@ impl From<Model> for ApiKey {
             expires_at: model.expires_at,
             last_used_at: model.last_used_at,
             created_at: model.created_at,
+            scopes: ApiKeyScopes::from_column(model.scopes.as_deref()),
         }
     }
 }
@@ -198,6 +248,7 @@ impl From<Model> for ApiKeyResponse {
             enabled: model.enabled,
             expires_at: model.expires_at,
             created_at: model.created_at,
+            scopes: ApiKeyScopes::from_column(model.scopes.as_deref()),
         }
     }
 }
@@ -0,0 +1,125 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use dbnexus::sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 审计事件持久化表。`id` 即审计事件 ID（毫秒时间戳高位 + 计数器），
+/// 单调递增，同时用作游标分页的排序键。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "audit_events", schema_name = "nebula_id")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: i64,
+    pub occurred_at: DateTime,
+    pub event_type: String,
+    pub workspace_id: Option<String>,
+    /// 发起操作的 API key 标识（`AuditEvent::user_id`）
+    pub actor: Option<String>,
+    pub action: String,
+    pub resource: String,
+    pub result: String,
+    /// JSON 文本
+    pub details: Option<String>,
+    /// 已脱敏
+    pub client_ip: Option<String>,
+    /// 已脱敏
+    pub user_agent: Option<String>,
+    pub duration_ms: i64,
+    pub error_message: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 一条持久化的审计事件（PII 字段已脱敏）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEventRecord {
+    pub id: i64,
+    pub occurred_at: DateTime,
+    pub event_type: String,
+    pub workspace_id: Option<String>,
+    pub actor: Option<String>,
+    pub action: String,
+    pub resource: String,
+    pub result: String,
+    pub details: Option<serde_json::Value>,
+    pub client_ip: Option<String>,
+    pub user_agent: Option<String>,
+    pub duration_ms: i64,
+    pub error_message: Option<String>,
+}
+
+/// 审计事件查询条件；所有过滤项为 AND 关系，结果按 `id` 降序（新事件在前）
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditEventQuery {
+    /// 起始时间（含）
+    pub start: Option<DateTime>,
+    /// 结束时间（不含）
+    pub end: Option<DateTime>,
+    pub workspace_id: Option<String>,
+    pub event_type: Option<String>,
+    pub result: Option<String>,
+    pub actor: Option<String>,
+    /// 资源前缀匹配，如 `biz_tag:` 匹配所有 biz tag 资源
+    pub resource: Option<String>,
+    /// 游标：只返回 `id` 小于该值的事件
+    pub before_id: Option<i64>,
+    pub limit: u64,
+}
+
+impl From<Model> for AuditEventRecord {
+    fn from(model: Model) -> Self {
+        AuditEventRecord {
+            id: model.id,
+            occurred_at: model.occurred_at,
+            event_type: model.event_type,
+            workspace_id: model.workspace_id,
+            actor: model.actor,
+            action: model.action,
+            resource: model.resource,
+            result: model.result,
+            details: model
+                .details
+                .map(|d| serde_json::from_str(&d).unwrap_or(serde_json::Value::String(d))),
+            client_ip: model.client_ip,
+            user_agent: model.user_agent,
+            duration_ms: model.duration_ms,
+            error_message: model.error_message,
+        }
+    }
+}
+
+impl From<&AuditEventRecord> for ActiveModel {
+    fn from(record: &AuditEventRecord) -> Self {
+        use dbnexus::sea_orm::Set;
+        ActiveModel {
+            id: Set(record.id),
+            occurred_at: Set(record.occurred_at),
+            event_type: Set(record.event_type.clone()),
+            workspace_id: Set(record.workspace_id.clone()),
+            actor: Set(record.actor.clone()),
+            action: Set(record.action.clone()),
+            resource: Set(record.resource.clone()),
+            result: Set(record.result.clone()),
+            details: Set(record.details.as_ref().map(|d| d.to_string())),
+            client_ip: Set(record.client_ip.clone()),
+            user_agent: Set(record.user_agent.clone()),
+            duration_ms: Set(record.duration_ms),
+            error_message: Set(record.error_message.clone()),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use dbnexus::sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 认证失败封禁表。每次封禁写入一行，解封时填写 `lifted_at` / `lifted_by`；
+/// 同一目标的历史封禁用于计算升级后的封禁等级。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "auth_bans", schema_name = "nebula_id")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub target_type: String,
+    pub target: String,
+    pub level: i32,
+    pub reason: String,
+    pub banned_at: DateTime,
+    pub expires_at: DateTime,
+    pub lifted_at: Option<DateTime>,
+    pub lifted_by: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 封禁目标类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BanTargetKind {
+    /// 客户端 IP（经可信代理解析后的来源地址）
+    Ip,
+    /// API key 的 `key_id`
+    KeyId,
+}
+
+impl fmt::Display for BanTargetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BanTargetKind::Ip => write!(f, "ip"),
+            BanTargetKind::KeyId => write!(f, "key_id"),
+        }
+    }
+}
+
+impl From<String> for BanTargetKind {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "key_id" => BanTargetKind::KeyId,
+            _ => BanTargetKind::Ip,
+        }
+    }
+}
+
+/// 一条封禁记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ban {
+    pub id: Uuid,
+    pub target_kind: BanTargetKind,
+    pub target: String,
+    /// 封禁等级，从 1 开始；等级越高封禁时长越长
+    pub level: i32,
+    pub reason: String,
+    pub banned_at: DateTime,
+    pub expires_at: DateTime,
+    pub lifted_at: Option<DateTime>,
+    pub lifted_by: Option<String>,
+}
+
+impl Ban {
+    /// 未解封且尚未到期
+    pub fn is_active(&self, now: DateTime) -> bool {
+        self.lifted_at.is_none() && self.expires_at > now
+    }
+}
+
+impl From<Model> for Ban {
+    fn from(model: Model) -> Self {
+        Ban {
+            id: model.id,
+            target_kind: model.target_type.into(),
+            target: model.target,
+            level: model.level,
+            reason: model.reason,
+            banned_at: model.banned_at,
+            expires_at: model.expires_at,
+            lifted_at: model.lifted_at,
+            lifted_by: model.lifted_by,
+        }
+    }
+}
+
+impl From<&Ban> for ActiveModel {
+    fn from(ban: &Ban) -> Self {
+        use dbnexus::sea_orm::Set;
+        ActiveModel {
+            id: Set(ban.id),
+            target_type: Set(ban.target_kind.to_string()),
+            target: Set(ban.target.clone()),
+            level: Set(ban.level),
+            reason: Set(ban.reason.clone()),
+            banned_at: Set(ban.banned_at),
+            expires_at: Set(ban.expires_at),
+            lifted_at: Set(ban.lifted_at),
+            lifted_by: Set(ban.lifted_by.clone()),
+        }
+    }
+}
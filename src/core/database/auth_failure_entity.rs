@@ -0,0 +1,34 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use dbnexus::sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// 认证失败计数表。每个封禁目标一行，各实例共享同一计数；`window_started_at`
+/// 为当前统计窗口的起点。
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "auth_failures", schema_name = "nebula_id")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub target_type: String,
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub target: String,
+    pub failures: i64,
+    pub window_started_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -141,8 +141,9 @@ impl From<Model> for BizTag {
     }
 }
 
+/// 以 VARCHAR(20) 存储（不依赖 PostgreSQL 枚举类型，MySQL / SQLite 通用）
 #[derive(Debug, Clone, Copy, EnumIter, DeriveActiveEnum, PartialEq, Eq, Serialize, Deserialize)]
-#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "algorithm_type")]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
 pub enum AlgorithmTypeDb {
     #[sea_orm(string_value = "segment")]
     Segment,
@@ -155,7 +156,7 @@ pub enum AlgorithmTypeDb {
 }
 
 #[derive(Debug, Clone, Copy, EnumIter, DeriveActiveEnum, PartialEq, Eq, Serialize, Deserialize)]
-#[sea_orm(rs_type = "String", db_type = "Enum", enum_name = "id_format")]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(20))")]
 pub enum IdFormatDb {
     #[sea_orm(string_value = "numeric")]
     Numeric,
@@ -15,7 +15,7 @@
 use dbnexus::sea_orm::{
     ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, DbErr, Statement,
 };
-use tracing::{info, warn};
+use tracing::info;
 
 use crate::core::config::DatabaseConfig;
 use crate::core::types::CoreError;
@@ -32,7 +32,7 @@ pub const NEBULA_SCHEMA: &str = "nebula_id";
 /// is emitted; the password is replaced by `***`. If the URL cannot be
 /// parsed by `url::Url`, the entire string is replaced by `<redacted>`
 /// to guarantee no password can leak.
-fn redact_db_url(url: &str) -> String {
+pub(crate) fn redact_db_url(url: &str) -> String {
     match url::Url::parse(url) {
         Ok(mut parsed) => {
             let _ = parsed.set_password(Some("***"));
@@ -48,12 +48,17 @@ impl From<DbErr> for CoreError {
     }
 }
 
+/// URL 是否为完整连接串；否则由 host/port/username 等字段拼出连接串
+pub(crate) fn is_full_url(url: &str) -> bool {
+    url.starts_with("postgresql://")
+        || url.starts_with("mysql://")
+        || url.starts_with("sqlite://")
+        || url.starts_with("postgres://")
+}
+
 pub async fn create_connection(config: &DatabaseConfig) -> Result<DatabaseConnection, CoreError> {
     // Check if we are using a complete connection string URL
-    let use_full_url = config.url.starts_with("postgresql://")
-        || config.url.starts_with("mysql://")
-        || config.url.starts_with("sqlite://")
-        || config.url.starts_with("postgres://");
+    let use_full_url = is_full_url(&config.url);
 
     // Validate database password only if not using full URL and not Sqlite
     if !use_full_url && config.engine != crate::core::config::DatabaseEngine::Sqlite {
@@ -69,6 +74,16 @@ pub async fn create_connection(config: &DatabaseConfig) -> Result<DatabaseConnec
         }
     }
 
+    // MySQL 驱动需 `mysql` feature，未编译时给出明确提示而非 sqlx 的驱动缺失错误
+    #[cfg(not(feature = "mysql"))]
+    if config.engine == crate::core::config::DatabaseEngine::Mysql
+        || config.url.starts_with("mysql://")
+    {
+        return Err(CoreError::ConfigurationError(
+            "MySQL support is not compiled in; rebuild with --features mysql".to_string(),
+        ));
+    }
+
     // Use URL if it's a complete connection string, otherwise construct from parts
     let final_url = if use_full_url {
         config.url.clone()
@@ -124,180 +139,6 @@ pub async fn create_connection(config: &DatabaseConfig) -> Result<DatabaseConnec
     Ok(db)
 }
 
-/// Auto-create schema and tables for Nebula ID
-pub async fn run_migrations(db: &DatabaseConnection) -> Result<(), CoreError> {
-    info!("{}", t!("log.core.database.connection.running_migrations"));
-
-    // Create schema if not exists (only for PostgreSQL)
-    let create_schema_sql = format!(r#"CREATE SCHEMA IF NOT EXISTS {}"#, NEBULA_SCHEMA);
-    // sea-orm 2.0: execute() 要求 StatementBuilder trait，原始 SQL 用 execute_unprepared
-    match db.execute_unprepared(&create_schema_sql).await {
-        Ok(_) => info!(
-            "{}",
-            t!(
-                "log.core.database.connection.schema_created_verified",
-                schema = NEBULA_SCHEMA
-            )
-        ),
-        Err(e) => {
-            warn!(
-                "{}",
-                t!(
-                    "log.core.database.connection.schema_create_failed",
-                    error = e
-                )
-            );
-        }
-    }
-
-    // Define all tables with their CREATE statements
-    // All tables are created in the nebula_id schema
-    let tables = vec![
-        // API Keys table
-        format!(
-            r#"
-        CREATE TABLE IF NOT EXISTS {}.api_keys (
-            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-            key_id VARCHAR(64) NOT NULL UNIQUE,
-            key_secret_hash VARCHAR(128) NOT NULL,
-            key_prefix VARCHAR(16) NOT NULL,
-            role VARCHAR(20) NOT NULL DEFAULT 'user',
-            workspace_id UUID,  -- 允许 NULL，用于全局 admin key
-            name VARCHAR(255) NOT NULL,
-            description TEXT,
-            rate_limit INT DEFAULT 1000,
-            enabled BOOLEAN DEFAULT true,
-            expires_at TIMESTAMP,
-            last_used_at TIMESTAMP,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            CONSTRAINT check_admin_key CHECK (
-                (workspace_id IS NULL AND role = 'admin')
-                OR (workspace_id IS NOT NULL AND role != 'admin')
-            )
-        )
-        "#,
-            NEBULA_SCHEMA
-        ),
-        // Workspaces table
-        format!(
-            r#"
-        CREATE TABLE IF NOT EXISTS {}.workspaces (
-            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-            name VARCHAR(255) NOT NULL UNIQUE,
-            description TEXT,
-            status VARCHAR(20) DEFAULT 'active',
-            max_groups INT DEFAULT 100,
-            max_biz_tags INT DEFAULT 1000,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-            NEBULA_SCHEMA
-        ),
-        // Groups table
-        format!(
-            r#"
-        CREATE TABLE IF NOT EXISTS {}.groups (
-            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-            workspace_id UUID NOT NULL REFERENCES {}.workspaces(id) ON DELETE CASCADE,
-            name VARCHAR(255) NOT NULL,
-            description TEXT,
-            max_biz_tags INT DEFAULT 100,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            UNIQUE(workspace_id, name)
-        )
-        "#,
-            NEBULA_SCHEMA, NEBULA_SCHEMA
-        ),
-        // BizTags table
-        format!(
-            r#"
-        CREATE TABLE IF NOT EXISTS {}.biz_tags (
-            id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
-            workspace_id UUID NOT NULL REFERENCES {}.workspaces(id) ON DELETE CASCADE,
-            group_id UUID NOT NULL REFERENCES {}.groups(id) ON DELETE CASCADE,
-            name VARCHAR(255) NOT NULL,
-            description TEXT,
-            algorithm VARCHAR(20) DEFAULT 'segment',
-            format VARCHAR(20) DEFAULT 'numeric',
-            prefix VARCHAR(50) DEFAULT '',
-            base_step INT DEFAULT 1000,
-            max_step INT DEFAULT 100000,
-            datacenter_ids TEXT DEFAULT '[]',
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            UNIQUE(workspace_id, group_id, name)
-        )
-        "#,
-            NEBULA_SCHEMA, NEBULA_SCHEMA, NEBULA_SCHEMA
-        ),
-        // Nebula segments table
-        format!(
-            r#"
-        CREATE TABLE IF NOT EXISTS {}.nebula_segments (
-            id BIGSERIAL PRIMARY KEY,
-            workspace_id VARCHAR(255) NOT NULL,
-            biz_tag VARCHAR(255) NOT NULL,
-            current_id BIGINT NOT NULL,
-            max_id BIGINT NOT NULL,
-            step INT NOT NULL DEFAULT 1000,
-            delta INT NOT NULL DEFAULT 1,
-            dc_id INT NOT NULL DEFAULT 0,
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
-            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )
-        "#,
-            NEBULA_SCHEMA
-        ),
-    ];
-
-    for sql in tables {
-        // sea-orm 2.0: execute() 要求 StatementBuilder trait，原始 SQL 用 execute_unprepared
-        match db.execute_unprepared(&sql).await {
-            Ok(_) => {
-                let table_name = sql.split_whitespace().nth(4).unwrap_or("").replace('(', "");
-                info!(
-                    "{}",
-                    t!(
-                        "log.core.database.connection.table_created_verified",
-                        table_name = table_name
-                    )
-                );
-            }
-            Err(e) => {
-                let error_msg = e.to_string();
-                if error_msg.contains("already exists") || error_msg.contains("duplicate") {
-                    info!(
-                        "{}",
-                        t!("log.core.database.connection.table_already_exists")
-                    );
-                } else {
-                    // MEDIUM-2 修复（CWE-209）：不将 SeaORM 原始错误消息嵌入
-                    // CoreError::DatabaseError（可能含 schema/表名/字段名/SQL 片段）。
-                    // 完整错误通过 tracing::error! 记录到服务端日志，
-                    // 返回给上层的是通用消息（helpers.rs 仍会进一步净化）。
-                    tracing::error!(
-                        event = "db_create_table_failed",
-                        error = %error_msg,
-                        "database table creation failed"
-                    );
-                    return Err(CoreError::DatabaseError(
-                        "Failed to create table (see server logs for details)".to_string(),
-                    ));
-                }
-            }
-        }
-    }
-
-    info!(
-        "{}",
-        t!("log.core.database.connection.migrations_completed")
-    );
-    Ok(())
-}
-
 #[allow(dead_code)]
 pub struct DatabaseManager {
     db: DatabaseConnection,
@@ -331,7 +172,9 @@ impl DatabaseManager {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::config::{DatabaseConfig, DatabaseEngine};
+    use crate::core::config::{
+        DatabaseConfig, DatabaseEngine, ReplicaConfig, SegmentAllocationMode,
+    };
     use dbnexus::sea_orm::{DatabaseBackend, DbErr, MockDatabase, MockExecResult, RuntimeErr};
 
     #[cfg(feature = "sqlite")]
@@ -349,6 +192,8 @@ mod tests {
             min_connections: 1,
             acquire_timeout_seconds: 30,
             idle_timeout_seconds: 300,
+            segment_allocation: SegmentAllocationMode::Atomic,
+            replicas: ReplicaConfig::default(),
         };
 
         let conn = create_connection(&config).await;
@@ -430,6 +275,8 @@ mod tests {
             min_connections: 1,
             acquire_timeout_seconds: 5,
             idle_timeout_seconds: 300,
+            segment_allocation: SegmentAllocationMode::Atomic,
+            replicas: ReplicaConfig::default(),
         }
     }
 
@@ -501,6 +348,8 @@ mod tests {
             min_connections: 0,
             acquire_timeout_seconds: 1,
             idle_timeout_seconds: 1,
+            segment_allocation: SegmentAllocationMode::Atomic,
+            replicas: ReplicaConfig::default(),
         };
         let result = create_connection(&config).await;
         match result {
@@ -512,6 +361,22 @@ mod tests {
         }
     }
 
+    #[cfg(not(feature = "mysql"))]
+    #[tokio::test]
+    async fn test_create_connection_rejects_mysql_without_feature() {
+        let config = DatabaseConfig {
+            engine: DatabaseEngine::Mysql,
+            port: 3306,
+            ..make_pg_config("real_password", "user")
+        };
+        match create_connection(&config).await {
+            Err(CoreError::ConfigurationError(msg)) => {
+                assert!(msg.contains("--features mysql"), "got: {msg}")
+            }
+            other => panic!("expected ConfigurationError, got {other:?}"),
+        }
+    }
+
     // ===== DatabaseManager with MockDatabase =====
 
     #[tokio::test]
@@ -553,178 +418,4 @@ mod tests {
         let result = manager.close().await;
         assert!(result.is_ok(), "close should return Ok: {result:?}");
     }
-
-    // ===== run_migrations with MockDatabase =====
-
-    fn mock_db_with_n_ok(n: usize) -> dbnexus::sea_orm::DatabaseConnection {
-        let results: Vec<MockExecResult> = (0..n)
-            .map(|_| MockExecResult {
-                last_insert_id: 0,
-                rows_affected: 0,
-            })
-            .collect();
-        MockDatabase::new(DatabaseBackend::Postgres)
-            .append_exec_results(results)
-            .into_connection()
-    }
-
-    #[tokio::test]
-    async fn test_run_migrations_succeeds_when_all_executes_succeed() {
-        // 1 schema + 5 tables = 6 successful executes.
-        let db = mock_db_with_n_ok(6);
-        let result = run_migrations(&db).await;
-        assert!(
-            result.is_ok(),
-            "migrations should succeed when all execs succeed: {result:?}"
-        );
-    }
-
-    #[tokio::test]
-    async fn test_run_migrations_logs_warn_but_continues_when_schema_create_fails() {
-        // Schema create fails (logged as warn), tables still succeed.
-        let db = MockDatabase::new(DatabaseBackend::Postgres)
-            .append_exec_errors(vec![DbErr::Query(RuntimeErr::Internal(
-                "schema creation permission denied".to_string(),
-            ))])
-            .append_exec_results(vec![
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-            ])
-            .into_connection();
-        let result = run_migrations(&db).await;
-        assert!(
-            result.is_ok(),
-            "schema create failure should be logged but not propagate Err: {result:?}"
-        );
-    }
-
-    #[tokio::test]
-    async fn test_run_migrations_returns_database_error_when_table_create_fails() {
-        // Schema + first table succeed, second table fails with non-"already exists".
-        let db = MockDatabase::new(DatabaseBackend::Postgres)
-            .append_exec_results(vec![
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-            ])
-            .append_exec_errors(vec![DbErr::Query(RuntimeErr::Internal(
-                "syntax error near 'FOO'".to_string(),
-            ))])
-            .into_connection();
-        let result = run_migrations(&db).await;
-        match result {
-            Err(CoreError::DatabaseError(msg)) => {
-                assert!(
-                    msg.contains("Failed to create table"),
-                    "should return generic message, got: {msg}"
-                );
-                assert!(
-                    !msg.contains("syntax error"),
-                    "should NOT leak SQL error details (CWE-209), got: {msg}"
-                );
-            }
-            other => panic!("expected DatabaseError for table create failure, got {other:?}"),
-        }
-    }
-
-    #[tokio::test]
-    async fn test_run_migrations_treats_already_exists_as_info_not_error() {
-        // Schema + first table succeed, second table fails with "already exists".
-        let db = MockDatabase::new(DatabaseBackend::Postgres)
-            .append_exec_results(vec![
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-            ])
-            .append_exec_errors(vec![DbErr::Query(RuntimeErr::Internal(
-                "relation already exists".to_string(),
-            ))])
-            .append_exec_results(vec![
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-            ])
-            .into_connection();
-        let result = run_migrations(&db).await;
-        assert!(
-            result.is_ok(),
-            "'already exists' should be logged as info, not propagate Err: {result:?}"
-        );
-    }
-
-    #[tokio::test]
-    async fn test_run_migrations_treats_duplicate_as_info_not_error() {
-        // Schema + 2 tables succeed, third table fails with "duplicate".
-        let db = MockDatabase::new(DatabaseBackend::Postgres)
-            .append_exec_results(vec![
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-            ])
-            .append_exec_errors(vec![DbErr::Query(RuntimeErr::Internal(
-                "duplicate table name".to_string(),
-            ))])
-            .append_exec_results(vec![
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-                MockExecResult {
-                    last_insert_id: 0,
-                    rows_affected: 0,
-                },
-            ])
-            .into_connection();
-        let result = run_migrations(&db).await;
-        assert!(
-            result.is_ok(),
-            "'duplicate' should be logged as info, not propagate Err: {result:?}"
-        );
-    }
 }
@@ -0,0 +1,1392 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chrono::NaiveDateTime;
+use dbnexus::sea_orm::{
+    ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbBackend, Statement,
+    TransactionTrait,
+};
+use sha2::{Digest, Sha256};
+use tracing::{error, info, warn};
+
+use super::connection::NEBULA_SCHEMA;
+use crate::core::types::CoreError;
+
+/// 记录已应用迁移的版本表
+pub const MIGRATIONS_TABLE: &str = "schema_migrations";
+
+/// PostgreSQL 迁移 advisory lock 的 key（"nebula" 的 ASCII）
+const MIGRATION_LOCK_KEY: i64 = 0x6e65_6275_6c61;
+
+/// MySQL 迁移命名锁
+const MIGRATION_LOCK_NAME: &str = "nebula_id.schema_migrations";
+
+/// MySQL 等待其他实例释放迁移锁的最长时间（秒）
+const MIGRATION_LOCK_TIMEOUT_SECS: u64 = 300;
+
+/// 迁移 SQL 方言，与 `DatabaseEngine` 的三种引擎一一对应
+///
+/// SQLite 没有 schema 概念，表直接建在主库中；PostgreSQL / MySQL 建在
+/// `nebula_id` schema（MySQL 中即同名数据库）下。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+impl SqlDialect {
+    pub fn from_backend(backend: DbBackend) -> Self {
+        match backend {
+            DbBackend::MySql => SqlDialect::MySql,
+            DbBackend::Sqlite => SqlDialect::Sqlite,
+            _ => SqlDialect::Postgres,
+        }
+    }
+
+    fn table(self, name: &str) -> String {
+        match self {
+            SqlDialect::Sqlite => name.to_string(),
+            SqlDialect::Postgres | SqlDialect::MySql => format!("{}.{}", NEBULA_SCHEMA, name),
+        }
+    }
+
+    fn uuid_type(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "UUID",
+            SqlDialect::MySql => "BINARY(16)",
+            SqlDialect::Sqlite => "BLOB",
+        }
+    }
+
+    fn uuid_primary_key(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "UUID PRIMARY KEY DEFAULT gen_random_uuid()",
+            SqlDialect::MySql => "BINARY(16) PRIMARY KEY",
+            SqlDialect::Sqlite => "BLOB PRIMARY KEY",
+        }
+    }
+
+    fn timestamp_type(self) -> &'static str {
+        match self {
+            SqlDialect::MySql => "DATETIME",
+            SqlDialect::Postgres | SqlDialect::Sqlite => "TIMESTAMP",
+        }
+    }
+
+    fn serial_primary_key(self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "BIGSERIAL PRIMARY KEY",
+            SqlDialect::MySql => "BIGINT AUTO_INCREMENT PRIMARY KEY",
+            SqlDialect::Sqlite => "INTEGER PRIMARY KEY AUTOINCREMENT",
+        }
+    }
+
+    fn create_index(self) -> &'static str {
+        // MySQL 不支持 CREATE INDEX IF NOT EXISTS
+        match self {
+            SqlDialect::MySql => "CREATE INDEX",
+            SqlDialect::Postgres | SqlDialect::Sqlite => "CREATE INDEX IF NOT EXISTS",
+        }
+    }
+
+    fn create_unique_index(self) -> &'static str {
+        match self {
+            SqlDialect::MySql => "CREATE UNIQUE INDEX",
+            SqlDialect::Postgres | SqlDialect::Sqlite => "CREATE UNIQUE INDEX IF NOT EXISTS",
+        }
+    }
+
+    fn text_default(self, literal: &str) -> String {
+        // MySQL 8.0.13+ 的 TEXT 列只接受表达式默认值
+        match self {
+            SqlDialect::MySql => format!("TEXT DEFAULT ('{}')", literal),
+            SqlDialect::Postgres | SqlDialect::Sqlite => format!("TEXT DEFAULT '{}'", literal),
+        }
+    }
+
+    fn has_schema(self) -> bool {
+        self != SqlDialect::Sqlite
+    }
+}
+
+/// 一条版本化迁移：版本号单调递增，up / down 按方言生成 SQL
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    up: fn(SqlDialect) -> Vec<String>,
+    down: fn(SqlDialect) -> Vec<String>,
+}
+
+impl Migration {
+    pub fn up_sql(&self, dialect: SqlDialect) -> Vec<String> {
+        (self.up)(dialect)
+    }
+
+    pub fn down_sql(&self, dialect: SqlDialect) -> Vec<String> {
+        (self.down)(dialect)
+    }
+
+    /// up SQL 的 SHA-256（hex），写入版本表，用于发现已发布迁移被改动
+    pub fn checksum(&self, dialect: SqlDialect) -> String {
+        hex::encode(Sha256::digest(self.up_sql(dialect).join(";\n").as_bytes()))
+    }
+}
+
+/// 全部迁移，按版本升序。已发布的迁移不可修改，列变更需追加新版本。
+static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline",
+        up: baseline_up,
+        down: baseline_down,
+    },
+    Migration {
+        version: 2,
+        name: "segment_unique_key",
+        up: segment_unique_key_up,
+        down: segment_unique_key_down,
+    },
+    Migration {
+        version: 3,
+        name: "auth_failures",
+        up: auth_failures_up,
+        down: auth_failures_down,
+    },
+    Migration {
+        version: 4,
+        name: "enum_columns_to_varchar",
+        up: enum_columns_to_varchar_up,
+        down: enum_columns_to_varchar_down,
+    },
+    Migration {
+        version: 5,
+        name: "api_key_notifications",
+        up: api_key_notifications_up,
+        down: api_key_notifications_down,
+    },
+    Migration {
+        version: 6,
+        name: "api_key_rotation_grace",
+        up: api_key_rotation_grace_up,
+        down: api_key_rotation_grace_down,
+    },
+    Migration {
+        version: 7,
+        name: "request_nonces",
+        up: request_nonces_up,
+        down: request_nonces_down,
+    },
+    Migration {
+        version: 8,
+        name: "api_key_hash_width",
+        up: api_key_hash_width_up,
+        down: api_key_hash_width_down,
+    },
+];
+
+pub fn migrations() -> &'static [Migration] {
+    MIGRATIONS
+}
+
+/// 当前二进制支持的最高 schema 版本
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// 版本 1：引入版本表之前 `run_migrations` 创建的全部表。
+///
+/// PostgreSQL 旧部署已有这些表，因此全部语句保持幂等（`IF NOT EXISTS`），
+/// 并补齐旧版本缺少的列，首次升级时只会登记版本。
+fn baseline_up(dialect: SqlDialect) -> Vec<String> {
+    let uuid = dialect.uuid_type();
+    let uuid_pk = dialect.uuid_primary_key();
+    let ts = dialect.timestamp_type();
+    let api_keys = dialect.table("api_keys");
+    let workspaces = dialect.table("workspaces");
+    let groups = dialect.table("groups");
+    let biz_tags = dialect.table("biz_tags");
+    let nebula_segments = dialect.table("nebula_segments");
+    let audit_events = dialect.table("audit_events");
+    let api_key_changes = dialect.table("api_key_changes");
+    let auth_bans = dialect.table("auth_bans");
+
+    let mut statements = vec![
+        // API Keys table
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {api_keys} (
+            id {uuid_pk},
+            key_id VARCHAR(64) NOT NULL UNIQUE,
+            key_secret_hash VARCHAR(128) NOT NULL,
+            key_prefix VARCHAR(16) NOT NULL,
+            role VARCHAR(20) NOT NULL DEFAULT 'user',
+            workspace_id {uuid},  -- 允许 NULL，用于全局 admin key
+            name VARCHAR(255) NOT NULL,
+            description TEXT,
+            rate_limit INT DEFAULT 1000,
+            enabled BOOLEAN DEFAULT true,
+            expires_at {ts},
+            last_used_at {ts},
+            created_at {ts} DEFAULT CURRENT_TIMESTAMP,
+            updated_at {ts} DEFAULT CURRENT_TIMESTAMP,
+            scopes TEXT,  -- 空格分隔的作用域，NULL 为不受限
+            signing_key VARCHAR(64),  -- 由 key_secret 派生的 HMAC 签名密钥（hex）
+            CONSTRAINT check_admin_key CHECK (
+                (workspace_id IS NULL AND role = 'admin')
+                OR (workspace_id IS NOT NULL AND role != 'admin')
+            )
+        )
+        "#
+        ),
+        // Workspaces table
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {workspaces} (
+            id {uuid_pk},
+            name VARCHAR(255) NOT NULL UNIQUE,
+            description TEXT,
+            status VARCHAR(20) DEFAULT 'active',
+            max_groups INT DEFAULT 100,
+            max_biz_tags INT DEFAULT 1000,
+            strict_generation BOOLEAN NOT NULL DEFAULT true,
+            created_at {ts} DEFAULT CURRENT_TIMESTAMP,
+            updated_at {ts} DEFAULT CURRENT_TIMESTAMP
+        )
+        "#
+        ),
+        // Groups table
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {groups} (
+            id {uuid_pk},
+            workspace_id {uuid} NOT NULL,
+            name VARCHAR(255) NOT NULL,
+            description TEXT,
+            max_biz_tags INT DEFAULT 100,
+            created_at {ts} DEFAULT CURRENT_TIMESTAMP,
+            updated_at {ts} DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE(workspace_id, name),
+            FOREIGN KEY (workspace_id) REFERENCES {workspaces}(id) ON DELETE CASCADE
+        )
+        "#
+        ),
+        // BizTags table
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {biz_tags} (
+            id {uuid_pk},
+            workspace_id {uuid} NOT NULL,
+            group_id {uuid} NOT NULL,
+            name VARCHAR(255) NOT NULL,
+            description TEXT,
+            algorithm VARCHAR(20) DEFAULT 'segment',
+            format VARCHAR(20) DEFAULT 'numeric',
+            prefix VARCHAR(50) DEFAULT '',
+            base_step INT DEFAULT 1000,
+            max_step INT DEFAULT 100000,
+            datacenter_ids {datacenter_ids},
+            created_at {ts} DEFAULT CURRENT_TIMESTAMP,
+            updated_at {ts} DEFAULT CURRENT_TIMESTAMP,
+            UNIQUE(workspace_id, group_id, name),
+            FOREIGN KEY (workspace_id) REFERENCES {workspaces}(id) ON DELETE CASCADE,
+            FOREIGN KEY (group_id) REFERENCES {groups}(id) ON DELETE CASCADE
+        )
+        "#,
+            datacenter_ids = dialect.text_default("[]")
+        ),
+        // Nebula segments table
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {nebula_segments} (
+            id {serial_pk},
+            workspace_id VARCHAR(255) NOT NULL,
+            biz_tag VARCHAR(255) NOT NULL,
+            current_id BIGINT NOT NULL,
+            max_id BIGINT NOT NULL,
+            step INT NOT NULL DEFAULT 1000,
+            delta INT NOT NULL DEFAULT 1,
+            dc_id INT NOT NULL DEFAULT 0,
+            created_at {ts} DEFAULT CURRENT_TIMESTAMP,
+            updated_at {ts} DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+            serial_pk = dialect.serial_primary_key()
+        ),
+        // Audit events table（id 为审计事件 ID，按时间单调递增）
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {audit_events} (
+            id BIGINT PRIMARY KEY,
+            occurred_at {ts} NOT NULL,
+            event_type VARCHAR(64) NOT NULL,
+            workspace_id VARCHAR(255),
+            actor VARCHAR(255),
+            action VARCHAR(255) NOT NULL,
+            resource TEXT NOT NULL,
+            result VARCHAR(20) NOT NULL,
+            details TEXT,
+            client_ip VARCHAR(64),
+            user_agent VARCHAR(64),
+            duration_ms BIGINT NOT NULL DEFAULT 0,
+            error_message TEXT
+        )
+        "#
+        ),
+        format!(
+            r#"
+        {create_index} idx_audit_events_workspace_id
+            ON {audit_events} (workspace_id, id)
+        "#,
+            create_index = dialect.create_index()
+        ),
+        format!(
+            r#"
+        {create_index} idx_audit_events_occurred_at
+            ON {audit_events} (occurred_at)
+        "#,
+            create_index = dialect.create_index()
+        ),
+        // API key 变更表：吊销 / 轮换 / 删除后各实例据此失效本地凭证缓存（seq 为轮询游标）
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {api_key_changes} (
+            seq {serial_pk},
+            api_key_id {uuid} NOT NULL,
+            key_id VARCHAR(64) NOT NULL,
+            change_type VARCHAR(20) NOT NULL,
+            changed_at {ts} NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+            serial_pk = dialect.serial_primary_key()
+        ),
+        // 认证失败封禁表：各实例共享封禁状态，历史记录用于计算封禁升级
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {auth_bans} (
+            id {uuid} PRIMARY KEY,
+            target_type VARCHAR(20) NOT NULL,
+            target VARCHAR(255) NOT NULL,
+            level INTEGER NOT NULL DEFAULT 1,
+            reason VARCHAR(255) NOT NULL,
+            banned_at {ts} NOT NULL,
+            expires_at {ts} NOT NULL,
+            lifted_at {ts},
+            lifted_by VARCHAR(255)
+        )
+        "#
+        ),
+        format!(
+            r#"
+        {create_index} idx_auth_bans_target
+            ON {auth_bans} (target_type, target, banned_at)
+        "#,
+            create_index = dialect.create_index()
+        ),
+    ];
+
+    if dialect == SqlDialect::Postgres {
+        // 早期 PostgreSQL 部署创建的表缺少这些列
+        statements.extend([
+            format!(
+                "ALTER TABLE {workspaces} ADD COLUMN IF NOT EXISTS strict_generation BOOLEAN NOT NULL DEFAULT true"
+            ),
+            format!("ALTER TABLE {api_keys} ADD COLUMN IF NOT EXISTS scopes TEXT"),
+            format!("ALTER TABLE {api_keys} ADD COLUMN IF NOT EXISTS signing_key VARCHAR(64)"),
+        ]);
+    }
+
+    statements
+}
+
+fn baseline_down(dialect: SqlDialect) -> Vec<String> {
+    // 按外键依赖逆序删除
+    [
+        "auth_bans",
+        "api_key_changes",
+        "audit_events",
+        "nebula_segments",
+        "biz_tags",
+        "groups",
+        "workspaces",
+        "api_keys",
+    ]
+    .iter()
+    .map(|table| format!("DROP TABLE IF EXISTS {}", dialect.table(table)))
+    .collect()
+}
+
+/// 版本 2：号段按 (workspace_id, biz_tag, dc_id) 唯一。
+///
+/// 无锁分配依赖该约束：并发首次分配时只有一个 INSERT 成功，其余转为更新已有行。
+/// 已存在重复号段行的旧库需先手工合并，否则本迁移失败。
+fn segment_unique_key_up(dialect: SqlDialect) -> Vec<String> {
+    vec![format!(
+        "{} uq_nebula_segments_key ON {} (workspace_id, biz_tag, dc_id)",
+        dialect.create_unique_index(),
+        dialect.table("nebula_segments")
+    )]
+}
+
+fn segment_unique_key_down(dialect: SqlDialect) -> Vec<String> {
+    let sql = match dialect {
+        SqlDialect::Postgres => format!(
+            "DROP INDEX IF EXISTS {}",
+            dialect.table("uq_nebula_segments_key")
+        ),
+        SqlDialect::MySql => format!(
+            "DROP INDEX uq_nebula_segments_key ON {}",
+            dialect.table("nebula_segments")
+        ),
+        SqlDialect::Sqlite => "DROP INDEX IF EXISTS uq_nebula_segments_key".to_string(),
+    };
+    vec![sql]
+}
+
+/// 版本 3：认证失败计数表，各实例共享同一目标的失败次数。
+///
+/// 每个目标一行，`window_started_at` 为当前统计窗口的起点，窗口过期后的下一次
+/// 失败从 1 重新计数。
+fn auth_failures_up(dialect: SqlDialect) -> Vec<String> {
+    let ts = dialect.timestamp_type();
+    let auth_failures = dialect.table("auth_failures");
+    vec![
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {auth_failures} (
+            target_type VARCHAR(20) NOT NULL,
+            target VARCHAR(255) NOT NULL,
+            failures BIGINT NOT NULL DEFAULT 0,
+            window_started_at {ts} NOT NULL,
+            PRIMARY KEY (target_type, target)
+        )
+        "#
+        ),
+        format!(
+            r#"
+        {create_index} idx_auth_failures_window
+            ON {auth_failures} (window_started_at)
+        "#,
+            create_index = dialect.create_index()
+        ),
+    ]
+}
+
+fn auth_failures_down(dialect: SqlDialect) -> Vec<String> {
+    vec![format!(
+        "DROP TABLE IF EXISTS {}",
+        dialect.table("auth_failures")
+    )]
+}
+
+/// 版本 4：将 `scripts/init.sql` 建出的 PostgreSQL 枚举列改为 VARCHAR。
+///
+/// 实体按字符串绑定 `algorithm` / `format` / `status`，枚举列会在写入时报类型不匹配。
+/// 由基线迁移建表的库本就是 VARCHAR，此处改类型为空操作；其他方言无需处理。
+fn enum_columns_to_varchar_up(dialect: SqlDialect) -> Vec<String> {
+    if dialect != SqlDialect::Postgres {
+        return Vec::new();
+    }
+    let biz_tags = dialect.table("biz_tags");
+    let workspaces = dialect.table("workspaces");
+    let mut statements = Vec::new();
+    for (table, column, default) in [
+        (&biz_tags, "algorithm", "segment"),
+        (&biz_tags, "format", "numeric"),
+        (&workspaces, "status", "active"),
+    ] {
+        // 枚举类型的默认值无法自动转换，先去掉再按 VARCHAR 重新设置
+        statements.push(format!(
+            "ALTER TABLE {table} ALTER COLUMN {column} DROP DEFAULT, \
+             ALTER COLUMN {column} TYPE VARCHAR(20) USING {column}::text, \
+             ALTER COLUMN {column} SET DEFAULT '{default}'"
+        ));
+    }
+    for enum_type in ["algorithm_type", "id_format", "workspace_status"] {
+        statements.push(format!("DROP TYPE IF EXISTS {}", dialect.table(enum_type)));
+    }
+    statements
+}
+
+/// 不回退为枚举类型：VARCHAR 是基线迁移的列类型
+fn enum_columns_to_varchar_down(_dialect: SqlDialect) -> Vec<String> {
+    Vec::new()
+}
+
+/// 版本 5：API key 生命周期通知登记表，各实例与重启后共享通知去重状态。
+fn api_key_notifications_up(dialect: SqlDialect) -> Vec<String> {
+    let ts = dialect.timestamp_type();
+    let api_key_notifications = dialect.table("api_key_notifications");
+    vec![format!(
+        r#"
+        CREATE TABLE IF NOT EXISTS {api_key_notifications} (
+            key_id VARCHAR(64) NOT NULL,
+            reason VARCHAR(20) NOT NULL,
+            notified_at {ts} NOT NULL,
+            PRIMARY KEY (key_id, reason)
+        )
+        "#
+    )]
+}
+
+fn api_key_notifications_down(dialect: SqlDialect) -> Vec<String> {
+    vec![format!(
+        "DROP TABLE IF EXISTS {}",
+        dialect.table("api_key_notifications")
+    )]
+}
+
+/// 版本 6：保存轮换前的密钥，宽限期内旧密钥仍可认证和签名。
+///
+/// 逐列 `ADD COLUMN`：SQLite 每条语句只能加一列。`scripts/init.sql` 建出的
+/// PostgreSQL 库已有这些列，因此 PostgreSQL 使用 `IF NOT EXISTS`。
+fn api_key_rotation_grace_up(dialect: SqlDialect) -> Vec<String> {
+    let ts = dialect.timestamp_type();
+    let api_keys = dialect.table("api_keys");
+    let add_column = match dialect {
+        SqlDialect::Postgres => "ADD COLUMN IF NOT EXISTS",
+        SqlDialect::MySql | SqlDialect::Sqlite => "ADD COLUMN",
+    };
+    vec![
+        format!("ALTER TABLE {api_keys} {add_column} previous_secret_hash VARCHAR(128)"),
+        format!("ALTER TABLE {api_keys} {add_column} previous_signing_key VARCHAR(64)"),
+        format!("ALTER TABLE {api_keys} {add_column} previous_secret_expires_at {ts}"),
+    ]
+}
+
+fn api_key_rotation_grace_down(dialect: SqlDialect) -> Vec<String> {
+    let api_keys = dialect.table("api_keys");
+    [
+        "previous_secret_expires_at",
+        "previous_signing_key",
+        "previous_secret_hash",
+    ]
+    .iter()
+    .map(|column| format!("ALTER TABLE {api_keys} DROP COLUMN {column}"))
+    .collect()
+}
+
+/// 版本 7：签名请求 nonce 登记表，各实例共享重放检查。
+fn request_nonces_up(dialect: SqlDialect) -> Vec<String> {
+    let ts = dialect.timestamp_type();
+    let request_nonces = dialect.table("request_nonces");
+    vec![
+        format!(
+            r#"
+        CREATE TABLE IF NOT EXISTS {request_nonces} (
+            key_id VARCHAR(64) NOT NULL,
+            nonce VARCHAR(64) NOT NULL,
+            expires_at {ts} NOT NULL,
+            PRIMARY KEY (key_id, nonce)
+        )
+        "#
+        ),
+        format!(
+            r#"
+        {create_index} idx_request_nonces_expires_at
+            ON {request_nonces} (expires_at)
+        "#,
+            create_index = dialect.create_index()
+        ),
+    ]
+}
+
+fn request_nonces_down(dialect: SqlDialect) -> Vec<String> {
+    vec![format!(
+        "DROP TABLE IF EXISTS {}",
+        dialect.table("request_nonces")
+    )]
+}
+
+/// 版本 8：密钥哈希列加宽到 VARCHAR(255)，与 `scripts/init.sql` 一致。
+///
+/// Argon2id PHC 字符串约 96 字符，参数调高后可能超过 128。SQLite 不限制 VARCHAR
+/// 长度，无需处理。
+fn api_key_hash_width_up(dialect: SqlDialect) -> Vec<String> {
+    api_key_hash_width(dialect, 255)
+}
+
+fn api_key_hash_width_down(dialect: SqlDialect) -> Vec<String> {
+    api_key_hash_width(dialect, 128)
+}
+
+fn api_key_hash_width(dialect: SqlDialect, width: u32) -> Vec<String> {
+    let api_keys = dialect.table("api_keys");
+    match dialect {
+        SqlDialect::Postgres => vec![format!(
+            "ALTER TABLE {api_keys} \
+             ALTER COLUMN key_secret_hash TYPE VARCHAR({width}), \
+             ALTER COLUMN previous_secret_hash TYPE VARCHAR({width})"
+        )],
+        SqlDialect::MySql => vec![format!(
+            "ALTER TABLE {api_keys} \
+             MODIFY COLUMN key_secret_hash VARCHAR({width}) NOT NULL, \
+             MODIFY COLUMN previous_secret_hash VARCHAR({width}) NULL"
+        )],
+        SqlDialect::Sqlite => Vec::new(),
+    }
+}
+
+/// 单条迁移在数据库中的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationState {
+    /// 已应用且校验和一致
+    Applied,
+    /// 尚未应用
+    Pending,
+    /// 已应用，但当前二进制中的 SQL 与应用时不同
+    ChecksumMismatch,
+    /// 数据库中存在、当前二进制不认识的版本（由更新的版本应用）
+    Unknown,
+}
+
+impl std::fmt::Display for MigrationState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationState::Applied => write!(f, "applied"),
+            MigrationState::Pending => write!(f, "pending"),
+            MigrationState::ChecksumMismatch => write!(f, "checksum mismatch"),
+            MigrationState::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub state: MigrationState,
+    pub applied_at: Option<NaiveDateTime>,
+}
+
+struct AppliedMigration {
+    version: i64,
+    name: String,
+    checksum: String,
+    applied_at: Option<NaiveDateTime>,
+}
+
+/// 创建 schema 与版本表（幂等）
+async fn ensure_migrations_table(
+    db: &DatabaseConnection,
+    dialect: SqlDialect,
+) -> Result<(), CoreError> {
+    if dialect.has_schema() {
+        // 无 CREATE 权限但 schema 已存在时 CREATE SCHEMA 也会失败，此时继续
+        let create_schema_sql = format!("CREATE SCHEMA IF NOT EXISTS {}", NEBULA_SCHEMA);
+        // sea-orm 2.0: execute() 要求 StatementBuilder trait，原始 SQL 用 execute_unprepared
+        if let Err(e) = db.execute_unprepared(&create_schema_sql).await {
+            warn!(
+                event = "db_schema_create_failed",
+                schema = NEBULA_SCHEMA,
+                error = %e,
+                "could not create schema, assuming it already exists"
+            );
+        }
+    }
+
+    let sql = format!(
+        r#"
+        CREATE TABLE IF NOT EXISTS {} (
+            version BIGINT PRIMARY KEY,
+            name VARCHAR(255) NOT NULL,
+            checksum VARCHAR(64) NOT NULL,
+            applied_at {} NOT NULL DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+        dialect.table(MIGRATIONS_TABLE),
+        dialect.timestamp_type()
+    );
+    db.execute_unprepared(&sql).await.map_err(|e| {
+        // MEDIUM-2（CWE-209）：原始错误只写服务端日志
+        error!(event = "db_migrations_table_failed", error = %e, "failed to create migrations table");
+        CoreError::DatabaseError(
+            "Failed to create migrations table (see server logs for details)".to_string(),
+        )
+    })?;
+    Ok(())
+}
+
+async fn load_applied<C: ConnectionTrait>(
+    db: &C,
+    dialect: SqlDialect,
+) -> Result<Vec<AppliedMigration>, CoreError> {
+    let sql = format!(
+        "SELECT version, name, checksum, applied_at FROM {} ORDER BY version",
+        dialect.table(MIGRATIONS_TABLE)
+    );
+    let rows = db
+        .query_all_raw(Statement::from_string(db.get_database_backend(), sql))
+        .await?;
+    rows.iter()
+        .map(|row| -> Result<AppliedMigration, CoreError> {
+            Ok(AppliedMigration {
+                version: row.try_get("", "version")?,
+                name: row.try_get("", "name")?,
+                checksum: row.try_get("", "checksum")?,
+                applied_at: row.try_get("", "applied_at").ok(),
+            })
+        })
+        .collect()
+}
+
+/// 合并二进制内置迁移与数据库记录，按版本升序
+fn merge_status(applied: &[AppliedMigration], dialect: SqlDialect) -> Vec<MigrationStatus> {
+    let mut statuses: Vec<MigrationStatus> = MIGRATIONS
+        .iter()
+        .map(|migration| {
+            let record = applied.iter().find(|a| a.version == migration.version);
+            let state = match record {
+                None => MigrationState::Pending,
+                Some(a) if a.checksum == migration.checksum(dialect) => MigrationState::Applied,
+                Some(_) => MigrationState::ChecksumMismatch,
+            };
+            MigrationStatus {
+                version: migration.version,
+                name: migration.name.to_string(),
+                state,
+                applied_at: record.and_then(|a| a.applied_at),
+            }
+        })
+        .collect();
+
+    statuses.extend(
+        applied
+            .iter()
+            .filter(|a| !MIGRATIONS.iter().any(|m| m.version == a.version))
+            .map(|a| MigrationStatus {
+                version: a.version,
+                name: a.name.clone(),
+                state: MigrationState::Unknown,
+                applied_at: a.applied_at,
+            }),
+    );
+    statuses.sort_by_key(|s| s.version);
+    statuses
+}
+
+/// 数据库 schema 比当前二进制新或迁移被改动时拒绝继续
+fn verify(statuses: &[MigrationStatus]) -> Result<(), CoreError> {
+    if let Some(unknown) = statuses
+        .iter()
+        .filter(|s| s.state == MigrationState::Unknown)
+        .max_by_key(|s| s.version)
+    {
+        return Err(CoreError::ConfigurationError(format!(
+            "Database schema version {} ({}) is newer than the latest version supported by this build ({}); upgrade nebula-id",
+            unknown.version,
+            unknown.name,
+            latest_version()
+        )));
+    }
+    if let Some(modified) = statuses
+        .iter()
+        .find(|s| s.state == MigrationState::ChecksumMismatch)
+    {
+        return Err(CoreError::ConfigurationError(format!(
+            "Checksum mismatch for applied migration {} ({}); released migrations must not be edited",
+            modified.version, modified.name
+        )));
+    }
+    Ok(())
+}
+
+/// 开启持有跨实例迁移锁的事务。多个实例同时启动时，读取版本表、校验与执行迁移
+/// 都在锁内进行，后到的实例等待并重新读取已应用的版本。
+///
+/// - PostgreSQL：`pg_advisory_xact_lock`，随事务提交或回滚释放，出错时不会把锁
+///   留在连接池的连接上
+/// - MySQL：`GET_LOCK` 会话锁，DDL 的隐式提交不会释放它，由 [`finish_locked`] 释放
+/// - SQLite：事务以 `BEGIN`（DEFERRED）开始，首条写语句即取得数据库写锁，
+///   其他实例的写事务在 busy timeout 内等待，效果同 `BEGIN EXCLUSIVE`
+async fn begin_locked(
+    db: &DatabaseConnection,
+    dialect: SqlDialect,
+) -> Result<DatabaseTransaction, CoreError> {
+    let txn = db.begin().await?;
+    match dialect {
+        SqlDialect::Postgres => {
+            txn.execute_unprepared(&format!(
+                "SELECT pg_advisory_xact_lock({})",
+                MIGRATION_LOCK_KEY
+            ))
+            .await?;
+        }
+        SqlDialect::MySql => {
+            let row = txn
+                .query_one_raw(Statement::from_string(
+                    DbBackend::MySql,
+                    format!(
+                        "SELECT GET_LOCK('{}', {}) AS locked",
+                        MIGRATION_LOCK_NAME, MIGRATION_LOCK_TIMEOUT_SECS
+                    ),
+                ))
+                .await?;
+            let locked = match row {
+                Some(row) => row.try_get::<Option<i64>>("", "locked")?,
+                None => None,
+            };
+            if locked != Some(1) {
+                return Err(CoreError::DatabaseError(format!(
+                    "Timed out after {}s waiting for the migration lock held by another instance",
+                    MIGRATION_LOCK_TIMEOUT_SECS
+                )));
+            }
+        }
+        SqlDialect::Sqlite => {
+            txn.execute_unprepared(&format!(
+                "DELETE FROM {} WHERE version IS NULL",
+                dialect.table(MIGRATIONS_TABLE)
+            ))
+            .await?;
+        }
+    }
+    Ok(txn)
+}
+
+/// 释放迁移锁并结束事务：`result` 成功时提交，失败时回滚
+async fn finish_locked<T>(
+    txn: DatabaseTransaction,
+    dialect: SqlDialect,
+    result: Result<T, CoreError>,
+) -> Result<T, CoreError> {
+    if dialect == SqlDialect::MySql {
+        let release_sql = format!("SELECT RELEASE_LOCK('{}')", MIGRATION_LOCK_NAME);
+        if let Err(e) = txn.execute_unprepared(&release_sql).await {
+            // 连接断开时会话锁随之释放
+            warn!(
+                event = "db_migration_lock_release_failed",
+                error = %e,
+                "failed to release migration lock"
+            );
+        }
+    }
+    match result {
+        Ok(value) => {
+            txn.commit().await?;
+            Ok(value)
+        }
+        Err(e) => {
+            let _ = txn.rollback().await;
+            Err(e)
+        }
+    }
+}
+
+/// 在迁移锁事务中执行迁移语句并更新版本表。
+///
+/// PostgreSQL / SQLite 上本次运行的迁移随锁事务一起提交，任一失败则全部回滚；
+/// MySQL 的 DDL 会隐式提交，失败时可能留下部分变更。
+async fn run_statements(
+    txn: &DatabaseTransaction,
+    version: i64,
+    statements: Vec<String>,
+) -> Result<(), CoreError> {
+    for sql in statements {
+        if let Err(e) = txn.execute_unprepared(&sql).await {
+            // MEDIUM-2 修复（CWE-209）：不将 SeaORM 原始错误消息嵌入
+            // CoreError::DatabaseError（可能含 schema/表名/字段名/SQL 片段）。
+            error!(
+                event = "db_migration_failed",
+                version = version,
+                error = %e,
+                "database migration failed"
+            );
+            return Err(CoreError::DatabaseError(format!(
+                "Failed to run migration {} (see server logs for details)",
+                version
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 查询所有迁移的状态（不执行任何迁移）
+pub async fn migration_status(db: &DatabaseConnection) -> Result<Vec<MigrationStatus>, CoreError> {
+    let dialect = SqlDialect::from_backend(db.get_database_backend());
+    ensure_migrations_table(db, dialect).await?;
+    let applied = load_applied(db, dialect).await?;
+    Ok(merge_status(&applied, dialect))
+}
+
+/// 按版本顺序应用所有未应用的迁移，直到 `target`（默认最新版本）。
+///
+/// 在跨实例迁移锁内读取、校验并执行（见 [`begin_locked`]）。返回本次应用的版本号。
+pub async fn migrate_up(
+    db: &DatabaseConnection,
+    target: Option<i64>,
+) -> Result<Vec<i64>, CoreError> {
+    let target = target.unwrap_or_else(latest_version);
+    if !MIGRATIONS.iter().any(|m| m.version == target) {
+        return Err(CoreError::InvalidInput(format!(
+            "Unknown migration version: {}",
+            target
+        )));
+    }
+
+    let dialect = SqlDialect::from_backend(db.get_database_backend());
+    ensure_migrations_table(db, dialect).await?;
+    let txn = begin_locked(db, dialect).await?;
+    let result = apply_up(&txn, dialect, target).await;
+    finish_locked(txn, dialect, result).await
+}
+
+async fn apply_up(
+    txn: &DatabaseTransaction,
+    dialect: SqlDialect,
+    target: i64,
+) -> Result<Vec<i64>, CoreError> {
+    let statuses = merge_status(&load_applied(txn, dialect).await?, dialect);
+    verify(&statuses)?;
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version <= target) {
+        let pending = statuses
+            .iter()
+            .any(|s| s.version == migration.version && s.state == MigrationState::Pending);
+        if !pending {
+            continue;
+        }
+
+        let mut statements = migration.up_sql(dialect);
+        statements.push(format!(
+            "INSERT INTO {} (version, name, checksum) VALUES ({}, '{}', '{}')",
+            dialect.table(MIGRATIONS_TABLE),
+            migration.version,
+            migration.name,
+            migration.checksum(dialect)
+        ));
+        run_statements(txn, migration.version, statements).await?;
+        info!(
+            event = "db_migration_applied",
+            version = migration.version,
+            name = migration.name,
+            "database migration applied"
+        );
+        applied.push(migration.version);
+    }
+    Ok(applied)
+}
+
+/// 按版本倒序回滚最近 `steps` 个已应用的迁移，返回回滚的版本号。
+pub async fn migrate_down(db: &DatabaseConnection, steps: usize) -> Result<Vec<i64>, CoreError> {
+    let dialect = SqlDialect::from_backend(db.get_database_backend());
+    ensure_migrations_table(db, dialect).await?;
+    let txn = begin_locked(db, dialect).await?;
+    let result = apply_down(&txn, dialect, steps).await;
+    finish_locked(txn, dialect, result).await
+}
+
+async fn apply_down(
+    txn: &DatabaseTransaction,
+    dialect: SqlDialect,
+    steps: usize,
+) -> Result<Vec<i64>, CoreError> {
+    let statuses = merge_status(&load_applied(txn, dialect).await?, dialect);
+    verify(&statuses)?;
+
+    let mut reverted = Vec::new();
+    for status in statuses
+        .iter()
+        .rev()
+        .filter(|s| s.state == MigrationState::Applied)
+        .take(steps)
+    {
+        let Some(migration) = MIGRATIONS.iter().find(|m| m.version == status.version) else {
+            continue;
+        };
+        let mut statements = migration.down_sql(dialect);
+        statements.push(format!(
+            "DELETE FROM {} WHERE version = {}",
+            dialect.table(MIGRATIONS_TABLE),
+            migration.version
+        ));
+        run_statements(txn, migration.version, statements).await?;
+        info!(
+            event = "db_migration_reverted",
+            version = migration.version,
+            name = migration.name,
+            "database migration reverted"
+        );
+        reverted.push(migration.version);
+    }
+    Ok(reverted)
+}
+
+/// 启动时执行：应用全部未应用的迁移；schema 比当前二进制新时拒绝启动
+pub async fn run_migrations(db: &DatabaseConnection) -> Result<(), CoreError> {
+    info!("{}", t!("log.core.database.connection.running_migrations"));
+    migrate_up(db, None).await?;
+    info!(
+        "{}",
+        t!("log.core.database.connection.migrations_completed")
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbnexus::sea_orm::{DbErr, MockDatabase, MockExecResult, RuntimeErr, Value};
+    use std::collections::BTreeMap;
+
+    fn ok_results(n: usize) -> Vec<MockExecResult> {
+        (0..n)
+            .map(|_| MockExecResult {
+                last_insert_id: 0,
+                rows_affected: 0,
+            })
+            .collect()
+    }
+
+    fn applied_row(version: i64, name: &str, checksum: &str) -> BTreeMap<String, Value> {
+        let applied_at = chrono::NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let mut row = BTreeMap::new();
+        row.insert("version".to_string(), Value::BigInt(Some(version)));
+        row.insert("name".to_string(), Value::from(name.to_string()));
+        row.insert("checksum".to_string(), Value::from(checksum.to_string()));
+        row.insert("applied_at".to_string(), Value::from(applied_at));
+        row
+    }
+
+    fn no_rows() -> Vec<BTreeMap<String, Value>> {
+        Vec::new()
+    }
+
+    fn baseline_row() -> BTreeMap<String, Value> {
+        applied_row(1, "baseline", &MIGRATIONS[0].checksum(SqlDialect::Postgres))
+    }
+
+    fn all_applied_rows() -> Vec<BTreeMap<String, Value>> {
+        MIGRATIONS
+            .iter()
+            .map(|m| applied_row(m.version, m.name, &m.checksum(SqlDialect::Postgres)))
+            .collect()
+    }
+
+    /// 全新 PostgreSQL：schema + 版本表 + 迁移锁 + 每个迁移的语句与版本登记
+    fn fresh_pg_exec_count() -> usize {
+        3 + MIGRATIONS
+            .iter()
+            .map(|m| m.up_sql(SqlDialect::Postgres).len() + 1)
+            .sum::<usize>()
+    }
+
+    #[test]
+    fn test_migration_versions_are_strictly_increasing() {
+        assert!(MIGRATIONS.windows(2).all(|w| w[0].version < w[1].version));
+        assert_eq!(latest_version(), MIGRATIONS.last().unwrap().version);
+    }
+
+    #[test]
+    fn test_dialects_generate_engine_specific_sql() {
+        let pg = MIGRATIONS[0].up_sql(SqlDialect::Postgres).join("\n");
+        assert!(pg.contains("nebula_id.api_keys"));
+        assert!(pg.contains("gen_random_uuid()"));
+        assert!(pg.contains("BIGSERIAL"));
+
+        let sqlite = MIGRATIONS[0].up_sql(SqlDialect::Sqlite).join("\n");
+        assert!(!sqlite.contains("nebula_id."), "SQLite has no schemas");
+        assert!(!sqlite.contains("gen_random_uuid"));
+        assert!(sqlite.contains("AUTOINCREMENT"));
+
+        let mysql = MIGRATIONS[0].up_sql(SqlDialect::MySql).join("\n");
+        assert!(mysql.contains("AUTO_INCREMENT"));
+        assert!(!mysql.contains("CREATE INDEX IF NOT EXISTS"));
+        assert!(!mysql.contains("ADD COLUMN IF NOT EXISTS"));
+
+        let unique_key = &MIGRATIONS[1];
+        assert!(unique_key.up_sql(SqlDialect::Postgres)[0]
+            .starts_with("CREATE UNIQUE INDEX IF NOT EXISTS uq_nebula_segments_key"));
+        assert!(unique_key.up_sql(SqlDialect::MySql)[0]
+            .starts_with("CREATE UNIQUE INDEX uq_nebula_segments_key"));
+        assert_eq!(
+            unique_key.down_sql(SqlDialect::MySql),
+            vec!["DROP INDEX uq_nebula_segments_key ON nebula_id.nebula_segments".to_string()]
+        );
+
+        let enum_columns = &MIGRATIONS[3];
+        let pg = enum_columns.up_sql(SqlDialect::Postgres).join("\n");
+        assert!(pg.contains("ALTER TABLE nebula_id.biz_tags ALTER COLUMN algorithm DROP DEFAULT"));
+        assert!(pg.contains("ALTER COLUMN format TYPE VARCHAR(20) USING format::text"));
+        assert!(pg.contains("DROP TYPE IF EXISTS nebula_id.algorithm_type"));
+        assert!(enum_columns.up_sql(SqlDialect::MySql).is_empty());
+        assert!(enum_columns.up_sql(SqlDialect::Sqlite).is_empty());
+
+        let rotation_grace = &MIGRATIONS[5];
+        assert_eq!(
+            rotation_grace.up_sql(SqlDialect::Sqlite)[0],
+            "ALTER TABLE api_keys ADD COLUMN previous_secret_hash VARCHAR(128)"
+        );
+        assert!(rotation_grace.up_sql(SqlDialect::Postgres)[2].starts_with(
+            "ALTER TABLE nebula_id.api_keys ADD COLUMN IF NOT EXISTS previous_secret_expires_at"
+        ));
+
+        let hash_width = &MIGRATIONS[7];
+        let pg = hash_width.up_sql(SqlDialect::Postgres).join("\n");
+        assert!(pg.contains("ALTER COLUMN key_secret_hash TYPE VARCHAR(255)"));
+        assert!(pg.contains("ALTER COLUMN previous_secret_hash TYPE VARCHAR(255)"));
+        let mysql = hash_width.up_sql(SqlDialect::MySql).join("\n");
+        assert!(mysql.contains("MODIFY COLUMN key_secret_hash VARCHAR(255) NOT NULL"));
+        assert!(hash_width.up_sql(SqlDialect::Sqlite).is_empty());
+        assert!(hash_width.down_sql(SqlDialect::Postgres)[0].contains("TYPE VARCHAR(128)"));
+    }
+
+    #[test]
+    fn test_checksum_is_stable_and_dialect_specific() {
+        let migration = &MIGRATIONS[0];
+        assert_eq!(
+            migration.checksum(SqlDialect::Postgres),
+            migration.checksum(SqlDialect::Postgres)
+        );
+        assert_eq!(migration.checksum(SqlDialect::Sqlite).len(), 64);
+        assert_ne!(
+            migration.checksum(SqlDialect::Postgres),
+            migration.checksum(SqlDialect::Sqlite)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_migrations_applies_baseline_on_empty_database() {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![no_rows()])
+            .append_exec_results(ok_results(fresh_pg_exec_count()))
+            .into_connection();
+
+        let result = run_migrations(&db).await;
+        assert!(result.is_ok(), "fresh database should migrate: {result:?}");
+    }
+
+    #[tokio::test]
+    async fn test_run_migrations_continues_when_schema_create_fails() {
+        // 无 CREATE 权限时 schema 创建失败仅记录 warn
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_exec_errors(vec![DbErr::Query(RuntimeErr::Internal(
+                "schema creation permission denied".to_string(),
+            ))])
+            .append_exec_results(ok_results(fresh_pg_exec_count() - 1))
+            .append_query_results(vec![no_rows()])
+            .into_connection();
+
+        let result = run_migrations(&db).await;
+        assert!(
+            result.is_ok(),
+            "schema create failure should not abort migrations: {result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_migrations_returns_generic_error_when_statement_fails() {
+        // schema + 版本表 + 迁移锁 + 第一条 baseline 语句成功，第二条失败
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![no_rows()])
+            .append_exec_results(ok_results(4))
+            .append_exec_errors(vec![DbErr::Query(RuntimeErr::Internal(
+                "syntax error near 'FOO'".to_string(),
+            ))])
+            .into_connection();
+
+        match run_migrations(&db).await {
+            Err(CoreError::DatabaseError(msg)) => {
+                assert!(msg.contains("migration 1"), "got: {msg}");
+                assert!(
+                    !msg.contains("syntax error"),
+                    "should NOT leak SQL error details (CWE-209), got: {msg}"
+                );
+            }
+            other => panic!("expected DatabaseError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_migrations_skips_applied_versions() {
+        // 只有 schema + 版本表 + 迁移锁三条执行，已应用的迁移不会重复执行
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![all_applied_rows()])
+            .append_exec_results(ok_results(3))
+            .into_connection();
+
+        assert_eq!(migrate_up(&db, None).await.unwrap(), Vec::<i64>::new());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_up_applies_only_pending_versions() {
+        let pending = &MIGRATIONS[1..];
+        let pending_len: usize = pending
+            .iter()
+            .map(|m| m.up_sql(SqlDialect::Postgres).len() + 1)
+            .sum();
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![baseline_row()]])
+            .append_exec_results(ok_results(3 + pending_len))
+            .into_connection();
+
+        assert_eq!(
+            migrate_up(&db, None).await.unwrap(),
+            pending.iter().map(|m| m.version).collect::<Vec<_>>()
+        );
+
+        let log = db.into_transaction_log();
+        let sql = format!("{log:?}");
+        assert!(sql.contains("uq_nebula_segments_key"), "got: {sql}");
+        assert!(!sql.contains("CREATE TABLE IF NOT EXISTS nebula_id.api_keys"));
+    }
+
+    #[tokio::test]
+    async fn test_run_migrations_refuses_newer_schema() {
+        let newer = latest_version() + 1;
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![
+                baseline_row(),
+                applied_row(newer, "from_the_future", "abc"),
+            ]])
+            .append_exec_results(ok_results(3))
+            .into_connection();
+
+        match run_migrations(&db).await {
+            Err(CoreError::ConfigurationError(msg)) => {
+                assert!(msg.contains("newer"), "got: {msg}");
+                assert!(msg.contains(&newer.to_string()), "got: {msg}");
+            }
+            other => panic!("expected ConfigurationError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_migrations_refuses_checksum_mismatch() {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![applied_row(1, "baseline", "edited")]])
+            .append_exec_results(ok_results(3))
+            .into_connection();
+
+        match run_migrations(&db).await {
+            Err(CoreError::ConfigurationError(msg)) => {
+                assert!(msg.contains("Checksum mismatch"), "got: {msg}")
+            }
+            other => panic!("expected ConfigurationError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migration_status_reports_pending_and_applied() {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![no_rows(), vec![baseline_row()]])
+            .append_exec_results(ok_results(4))
+            .into_connection();
+
+        let pending = migration_status(&db).await.unwrap();
+        assert_eq!(pending[0].state, MigrationState::Pending);
+        assert!(pending[0].applied_at.is_none());
+
+        let applied = migration_status(&db).await.unwrap();
+        assert_eq!(applied[0].state, MigrationState::Applied);
+        assert!(applied[0].applied_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_down_reverts_latest_applied_version() {
+        let down_len = MIGRATIONS[0].down_sql(SqlDialect::Postgres).len();
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![vec![baseline_row()]])
+            .append_exec_results(ok_results(3 + down_len + 1))
+            .into_connection();
+
+        assert_eq!(migrate_down(&db, 1).await.unwrap(), vec![1]);
+    }
+
+    fn applied_rows(dialect: SqlDialect) -> Vec<BTreeMap<String, Value>> {
+        MIGRATIONS
+            .iter()
+            .map(|m| applied_row(m.version, m.name, &m.checksum(dialect)))
+            .collect()
+    }
+
+    fn lock_row(locked: i64) -> BTreeMap<String, Value> {
+        BTreeMap::from([("locked".to_string(), Value::BigInt(Some(locked)))])
+    }
+
+    /// 迁移锁须在读取版本表之前取得，后到的实例才能看到先到实例已应用的版本
+    fn assert_locked_before_load(db: DatabaseConnection, lock_sql: &str) -> String {
+        let sql = format!("{:?}", db.into_transaction_log());
+        let lock = sql.find(lock_sql).expect("lock statement issued");
+        let load = sql
+            .find("SELECT version, name")
+            .expect("applied versions loaded");
+        assert!(lock < load, "got: {sql}");
+        sql
+    }
+
+    #[tokio::test]
+    async fn test_migrate_up_takes_advisory_lock_before_reading_versions_on_postgres() {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![all_applied_rows()])
+            .append_exec_results(ok_results(3))
+            .into_connection();
+
+        assert_eq!(migrate_up(&db, None).await.unwrap(), Vec::<i64>::new());
+        assert_locked_before_load(db, "pg_advisory_xact_lock");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_up_takes_and_releases_named_lock_on_mysql() {
+        // schema + 版本表 + 释放锁
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_query_results(vec![vec![lock_row(1)], applied_rows(SqlDialect::MySql)])
+            .append_exec_results(ok_results(3))
+            .into_connection();
+
+        assert_eq!(migrate_up(&db, None).await.unwrap(), Vec::<i64>::new());
+        let sql = assert_locked_before_load(db, "GET_LOCK");
+        assert!(sql.contains("RELEASE_LOCK"), "got: {sql}");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_up_fails_when_mysql_lock_times_out() {
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_query_results(vec![vec![lock_row(0)]])
+            .append_exec_results(ok_results(2))
+            .into_connection();
+
+        match migrate_up(&db, None).await {
+            Err(CoreError::DatabaseError(msg)) => {
+                assert!(msg.contains("migration lock"), "got: {msg}")
+            }
+            other => panic!("expected DatabaseError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_up_takes_write_lock_before_reading_versions_on_sqlite() {
+        // 版本表 + 写锁
+        let db = MockDatabase::new(DbBackend::Sqlite)
+            .append_query_results(vec![applied_rows(SqlDialect::Sqlite)])
+            .append_exec_results(ok_results(2))
+            .into_connection();
+
+        assert_eq!(migrate_up(&db, None).await.unwrap(), Vec::<i64>::new());
+        assert_locked_before_load(db, "DELETE FROM schema_migrations WHERE version IS NULL");
+    }
+
+    #[tokio::test]
+    async fn test_migrate_up_rejects_unknown_target() {
+        let db = MockDatabase::new(DbBackend::Postgres).into_connection();
+        assert!(matches!(
+            migrate_up(&db, Some(latest_version() + 1)).await,
+            Err(CoreError::InvalidInput(_))
+        ));
+    }
+}
@@ -14,25 +14,41 @@
 
 #![allow(unused_imports)]
 
+mod api_key_change_entity;
 mod api_key_entity;
+mod api_key_notification_entity;
+mod audit_event_entity;
+mod auth_ban_entity;
+mod auth_failure_entity;
 mod biz_tag_entity;
 mod connection;
 mod group_entity;
+mod migrations;
+mod replica;
 mod repository;
+mod request_nonce_entity;
 mod segment_entity;
 mod workspace_entity;
 
 pub use crate::core::types::id::{AlgorithmType, IdFormat};
+pub use api_key_change_entity::{ApiKeyChange, ApiKeyChangeKind};
 pub use api_key_entity::{
-    ApiKey, ApiKeyInfo, ApiKeyResponse, ApiKeyRole, ApiKeyWithSecret, CreateApiKeyRequest,
+    ApiKey, ApiKeyInfo, ApiKeyResponse, ApiKeyRole, ApiKeySigningKey, ApiKeyWithSecret,
+    CreateApiKeyRequest,
 };
+pub use audit_event_entity::{AuditEventQuery, AuditEventRecord};
+pub use auth_ban_entity::{Ban, BanTargetKind};
 pub use biz_tag_entity::{BizTag, CreateBizTagRequest, UpdateBizTagRequest};
 pub use connection::create_connection;
-pub use connection::run_migrations;
 pub use group_entity::{CreateGroupRequest, Group, UpdateGroupRequest};
+pub use migrations::{
+    latest_version, migrate_down, migrate_up, migration_status, migrations, run_migrations,
+    Migration, MigrationState, MigrationStatus, SqlDialect, MIGRATIONS_TABLE,
+};
+pub use replica::ReplicaSet;
 pub use repository::{
-    ApiKeyRepository, BizTagRepository, GroupRepository, SeaOrmRepository, SegmentRepository,
-    WorkspaceRepository,
+    ApiKeyRepository, AuditEventRepository, BanRepository, BizTagRepository, GroupRepository,
+    SeaOrmRepository, SegmentRepository, WorkspaceRepository,
 };
 pub use workspace_entity::{
     CreateWorkspaceRequest, UpdateWorkspaceRequest, Workspace, WorkspaceStatus,
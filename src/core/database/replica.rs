@@ -0,0 +1,387 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use dbnexus::sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend, Statement};
+use tracing::{debug, info, warn};
+
+use super::connection::{create_connection, is_full_url, redact_db_url};
+use crate::core::config::{DatabaseConfig, ReplicaConfig};
+use crate::core::types::CoreError;
+
+/// PostgreSQL 复制延迟：主库或已追平全部 WAL 时为 0；
+/// 空闲主库上 `pg_last_xact_replay_timestamp` 会持续变旧，因此先比较 LSN
+const PG_LAG_SQL: &str = r#"
+    SELECT (CASE
+        WHEN NOT pg_is_in_recovery() THEN 0
+        WHEN pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0
+        ELSE COALESCE(EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp()), 0)
+    END)::DOUBLE PRECISION AS lag_seconds
+"#;
+
+struct Replica {
+    /// 脱敏后的 URL，仅用于日志
+    name: String,
+    db: DatabaseConnection,
+    healthy: AtomicBool,
+}
+
+/// 只读副本集合：轮询选择健康且复制延迟不超过容忍值的副本。
+///
+/// 副本在首次健康检查通过前不承接读请求；没有可用副本时 [`ReplicaSet::pick`]
+/// 返回 `None`，调用方回退主库。
+pub struct ReplicaSet {
+    replicas: Vec<Replica>,
+    max_lag_seconds: u64,
+    next: AtomicUsize,
+}
+
+impl ReplicaSet {
+    /// 由已建立的副本连接构建，`replicas` 为（日志名称，连接）
+    pub fn new(replicas: Vec<(String, DatabaseConnection)>, max_lag_seconds: u64) -> Self {
+        Self {
+            replicas: replicas
+                .into_iter()
+                .map(|(name, db)| Replica {
+                    name,
+                    db,
+                    healthy: AtomicBool::new(false),
+                })
+                .collect(),
+            max_lag_seconds,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// 按 `database.replicas` 连接全部副本。
+    ///
+    /// 副本 URL 必须是完整连接串，否则返回 `ConfigurationError`（不完整的 URL
+    /// 会按主库的 host 等字段拼接，读请求将悄悄落回主库）。连接失败的副本
+    /// 记录告警后跳过；未配置副本或全部连接失败时返回 `None`。
+    pub async fn connect(config: &DatabaseConfig) -> Result<Option<Self>, CoreError> {
+        let replica_config = &config.replicas;
+        if let Some(url) = replica_config.urls.iter().find(|url| !is_full_url(url)) {
+            return Err(CoreError::ConfigurationError(format!(
+                "Replica URL must be a full connection URL: {}",
+                redact_db_url(url)
+            )));
+        }
+
+        let mut replicas = Vec::new();
+        for url in &replica_config.urls {
+            let name = redact_db_url(url);
+            let replica_db_config = DatabaseConfig {
+                url: url.clone(),
+                max_connections: replica_config.max_connections,
+                min_connections: config.min_connections.min(replica_config.max_connections),
+                replicas: ReplicaConfig::default(),
+                ..config.clone()
+            };
+            match create_connection(&replica_db_config).await {
+                Ok(db) => replicas.push((name, db)),
+                Err(e) => warn!(
+                    event = "db_replica_connect_failed",
+                    replica = %name,
+                    error = %e,
+                    "failed to connect to read replica, skipping it"
+                ),
+            }
+        }
+
+        if replicas.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self::new(replicas, replica_config.max_lag_seconds)))
+    }
+
+    /// 副本总数
+    pub fn len(&self) -> usize {
+        self.replicas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replicas.is_empty()
+    }
+
+    /// 当前承接读请求的副本数
+    pub fn healthy_count(&self) -> usize {
+        self.replicas
+            .iter()
+            .filter(|r| r.healthy.load(Ordering::Acquire))
+            .count()
+    }
+
+    /// 轮询选择一个健康副本；没有健康副本时返回 `None`
+    pub fn pick(&self) -> Option<&DatabaseConnection> {
+        let len = self.replicas.len();
+        if len == 0 {
+            return None;
+        }
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        (0..len)
+            .map(|i| &self.replicas[(start + i) % len])
+            .find(|r| r.healthy.load(Ordering::Acquire))
+            .map(|r| &r.db)
+    }
+
+    /// 检查全部副本的连通性与复制延迟，更新其是否承接读请求
+    pub async fn check_health(&self) {
+        for replica in &self.replicas {
+            let result = replication_lag_seconds(&replica.db).await;
+            let healthy = matches!(result, Ok(lag) if lag <= self.max_lag_seconds as f64);
+            let was_healthy = replica.healthy.swap(healthy, Ordering::AcqRel);
+
+            match result {
+                Ok(lag) if healthy => {
+                    if !was_healthy {
+                        info!(
+                            event = "db_replica_available",
+                            replica = %replica.name,
+                            lag_seconds = lag,
+                            "read replica is serving reads"
+                        );
+                    }
+                }
+                Ok(lag) if was_healthy => warn!(
+                    event = "db_replica_lagging",
+                    replica = %replica.name,
+                    lag_seconds = lag,
+                    max_lag_seconds = self.max_lag_seconds,
+                    "read replica lag exceeds tolerance, routing its reads elsewhere"
+                ),
+                Err(e) if was_healthy => warn!(
+                    event = "db_replica_unhealthy",
+                    replica = %replica.name,
+                    error = %e,
+                    "read replica health check failed, routing its reads elsewhere"
+                ),
+                Ok(lag) => debug!(
+                    event = "db_replica_lagging",
+                    replica = %replica.name,
+                    lag_seconds = lag,
+                    "read replica still lagging"
+                ),
+                Err(e) => debug!(
+                    event = "db_replica_unhealthy",
+                    replica = %replica.name,
+                    error = %e,
+                    "read replica still unhealthy"
+                ),
+            }
+        }
+    }
+
+    /// 按 `interval` 周期检查副本健康（首次检查由调用方在启动时完成）
+    pub fn spawn_health_checks(
+        self: &Arc<Self>,
+        interval: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let replicas = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                replicas.check_health().await;
+            }
+        })
+    }
+}
+
+/// 查询副本的复制延迟（秒）；SQLite 无复制，连通即视为无延迟
+async fn replication_lag_seconds(db: &DatabaseConnection) -> Result<f64, CoreError> {
+    let backend = db.get_database_backend();
+    let sql = match backend {
+        DbBackend::MySql => "SHOW REPLICA STATUS",
+        DbBackend::Sqlite => "SELECT 1",
+        _ => PG_LAG_SQL,
+    };
+    let row = db
+        .query_one_raw(Statement::from_string(backend, sql))
+        .await?;
+
+    match backend {
+        DbBackend::Sqlite => Ok(0.0),
+        DbBackend::MySql => {
+            // 无复制状态说明连接的是主库本身
+            let Some(row) = row else {
+                return Ok(0.0);
+            };
+            let seconds: Option<i64> = row.try_get("", "Seconds_Behind_Source").or_else(|_| {
+                row.try_get::<Option<u64>>("", "Seconds_Behind_Source")
+                    .map(|v| v.map(|v| v as i64))
+            })?;
+            seconds.map(|s| s as f64).ok_or_else(|| {
+                CoreError::DatabaseError("Replication is not running on the replica".to_string())
+            })
+        }
+        _ => {
+            let row = row.ok_or_else(|| {
+                CoreError::DatabaseError("Replication lag query returned no rows".to_string())
+            })?;
+            Ok(row.try_get("", "lag_seconds")?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbnexus::sea_orm::{DbErr, MockDatabase, RuntimeErr, Value};
+    use std::collections::BTreeMap;
+
+    fn pg_lag(seconds: f64) -> Vec<BTreeMap<String, Value>> {
+        let mut row = BTreeMap::new();
+        row.insert("lag_seconds".to_string(), Value::Double(Some(seconds)));
+        vec![row]
+    }
+
+    fn pg_replica(lags: Vec<Vec<BTreeMap<String, Value>>>) -> DatabaseConnection {
+        MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(lags)
+            .into_connection()
+    }
+
+    fn replica_set(replicas: Vec<DatabaseConnection>) -> ReplicaSet {
+        ReplicaSet::new(
+            replicas
+                .into_iter()
+                .enumerate()
+                .map(|(i, db)| (format!("replica-{i}"), db))
+                .collect(),
+            5,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_replicas_serve_reads_only_after_passing_health_check() {
+        let set = replica_set(vec![pg_replica(vec![pg_lag(0.5)])]);
+        assert!(
+            set.pick().is_none(),
+            "unchecked replicas must not serve reads"
+        );
+
+        set.check_health().await;
+        assert_eq!(set.healthy_count(), 1);
+        assert!(set.pick().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_pick_round_robins_across_healthy_replicas() {
+        let set = replica_set(vec![
+            pg_replica(vec![pg_lag(0.0)]),
+            pg_replica(vec![pg_lag(0.0)]),
+        ]);
+        set.check_health().await;
+
+        let first = set.pick().unwrap() as *const DatabaseConnection;
+        let second = set.pick().unwrap() as *const DatabaseConnection;
+        let third = set.pick().unwrap() as *const DatabaseConnection;
+        assert_ne!(first, second);
+        assert_eq!(first, third);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_replica_stops_serving_reads() {
+        let set = replica_set(vec![
+            pg_replica(vec![pg_lag(0.0), pg_lag(30.0)]),
+            pg_replica(vec![pg_lag(0.0), pg_lag(1.0)]),
+        ]);
+        set.check_health().await;
+        assert_eq!(set.healthy_count(), 2);
+
+        set.check_health().await;
+        assert_eq!(set.healthy_count(), 1, "replica beyond max lag is skipped");
+        let healthy = set.pick().unwrap() as *const DatabaseConnection;
+        for _ in 0..3 {
+            assert_eq!(set.pick().unwrap() as *const DatabaseConnection, healthy);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_all_replicas_unhealthy_falls_back_and_recovers() {
+        let db = MockDatabase::new(DbBackend::Postgres)
+            .append_query_results(vec![pg_lag(0.0)])
+            .append_query_errors(vec![DbErr::Query(RuntimeErr::Internal(
+                "connection reset".to_string(),
+            ))])
+            .append_query_results(vec![pg_lag(0.0)])
+            .into_connection();
+        let set = replica_set(vec![db]);
+
+        set.check_health().await;
+        assert!(set.pick().is_some());
+
+        set.check_health().await;
+        assert!(set.pick().is_none(), "callers fall back to the primary");
+
+        set.check_health().await;
+        assert!(set.pick().is_some(), "replica rejoins once healthy again");
+    }
+
+    #[tokio::test]
+    async fn test_mysql_replication_lag_reads_seconds_behind_source() {
+        let mut stopped = BTreeMap::new();
+        stopped.insert(
+            "Seconds_Behind_Source".to_string(),
+            Value::BigInt(None::<i64>),
+        );
+        let mut running = BTreeMap::new();
+        running.insert("Seconds_Behind_Source".to_string(), Value::BigInt(Some(3)));
+        let db = MockDatabase::new(DbBackend::MySql)
+            .append_query_results(vec![vec![running], vec![stopped], vec![]])
+            .into_connection();
+
+        assert_eq!(replication_lag_seconds(&db).await.unwrap(), 3.0);
+        assert!(
+            replication_lag_seconds(&db).await.is_err(),
+            "stopped replication must mark the replica unhealthy"
+        );
+        assert_eq!(
+            replication_lag_seconds(&db).await.unwrap(),
+            0.0,
+            "no replica status means the primary itself"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connect_rejects_partial_replica_url() {
+        let config = DatabaseConfig {
+            url: "postgresql://primary/db".to_string(),
+            replicas: ReplicaConfig {
+                urls: vec!["replica-host:5432".to_string()],
+                ..ReplicaConfig::default()
+            },
+            ..DatabaseConfig::default()
+        };
+
+        assert!(matches!(
+            ReplicaSet::connect(&config).await,
+            Err(CoreError::ConfigurationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_connect_without_replicas_returns_none() {
+        let config = DatabaseConfig {
+            replicas: ReplicaConfig::default(),
+            ..DatabaseConfig::default()
+        };
+        assert!(ReplicaSet::connect(&config).await.unwrap().is_none());
+    }
+}
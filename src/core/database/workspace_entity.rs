@@ -27,6 +27,8 @@ pub struct Model {
     pub status: String,
     pub max_groups: i32,
     pub max_biz_tags: i32,
+    /// 严格生成模式：生成 ID 要求 workspace 为 active 且 biz_tag 已注册
+    pub strict_generation: bool,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
@@ -53,6 +55,7 @@ pub struct Workspace {
     pub status: WorkspaceStatus,
     pub max_groups: i32,
     pub max_biz_tags: i32,
+    pub strict_generation: bool,
     pub created_at: DateTime,
     pub updated_at: DateTime,
 }
@@ -118,6 +121,7 @@ pub struct UpdateWorkspaceRequest {
     pub status: Option<WorkspaceStatus>,
     pub max_groups: Option<i32>,
     pub max_biz_tags: Option<i32>,
+    pub strict_generation: Option<bool>,
 }
 
 impl From<Model> for Workspace {
@@ -129,6 +133,7 @@ impl From<Model> for Workspace {
             status: model.status.into(),
             max_groups: model.max_groups,
             max_biz_tags: model.max_biz_tags,
+            strict_generation: model.strict_generation,
             created_at: model.created_at,
             updated_at: model.updated_at,
         }
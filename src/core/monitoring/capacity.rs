@@ -0,0 +1,659 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 号段容量预测（segment exhaustion forecasting）。
+//!
+//! 号段 ID 在 `nebula_segments` / `SegmentInfo` 中是 `i64`，上限为
+//! `i64::MAX`（可通过 `monitoring.capacity.id_ceiling` 调低）。
+//!
+//! 接入号段仓库后，[`CapacityForecaster::sample`] 周期读取 `nebula_segments`
+//! 各行的号段上界，消耗速率取预测窗口内上界的推进量：所有实例写同一张表，
+//! 速率覆盖整个集群，且重启后从数据库重新采样。未接入数据库时退回
+//! [`UsageTracker`] 记录的本进程号段分配（仅反映单实例）。
+//!
+//! - 号段消耗速率 = 预测窗口内分配的 ID 数 / 窗口秒数；
+//! - 剩余 ID = `id_ceiling - 最近一次号段上界`；
+//! - 预计耗尽时间 = 剩余 ID / 消耗速率。
+//!
+//! 预计耗尽时间低于阈值时由内置告警规则（[`capacity_alert_rules`]）经
+//! [`CapacityAlertEvaluator`] 触发 Warning / Critical 告警。
+
+use crate::core::config::CapacityConfig;
+use crate::core::database::SegmentRepository;
+use crate::core::monitoring::core::{AlertEvaluator, AlertRule, AlertSeverity, DefaultEvaluator};
+use crate::core::monitoring::UsageTracker;
+use crate::core::types::{GlobalMetrics, Result, SegmentInfo};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+
+/// 告警表达式：`segment_exhaustion_secs < <阈值秒数>`
+pub const EXHAUSTION_EXPRESSION_PREFIX: &str = "segment_exhaustion_secs < ";
+
+/// 内置 Warning 规则名
+pub const EXHAUSTION_WARNING_RULE: &str = "segment_exhaustion_warning";
+
+/// 内置 Critical 规则名
+pub const EXHAUSTION_CRITICAL_RULE: &str = "segment_exhaustion_critical";
+
+/// 告警 current_value 中最多列出的 key 数
+const MAX_KEYS_IN_ALERT_VALUE: usize = 5;
+
+/// 单个 biz_tag 的容量状态
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum CapacityStatus {
+    /// 已到达上限
+    Exhausted,
+    /// 预计耗尽时间低于 critical 阈值
+    Critical,
+    /// 预计耗尽时间低于 warning 阈值
+    Warning,
+    /// 预计耗尽时间充足
+    Ok,
+    /// 预测窗口内无号段分配，无法预测
+    Idle,
+}
+
+impl std::fmt::Display for CapacityStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CapacityStatus::Exhausted => write!(f, "exhausted"),
+            CapacityStatus::Critical => write!(f, "critical"),
+            CapacityStatus::Warning => write!(f, "warning"),
+            CapacityStatus::Ok => write!(f, "ok"),
+            CapacityStatus::Idle => write!(f, "idle"),
+        }
+    }
+}
+
+/// 单个 (workspace, biz_tag) 的耗尽预测
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SegmentCapacityForecast {
+    pub workspace: String,
+    pub biz_tag: String,
+    /// 最近一次号段分配的上界
+    pub current_max_id: u64,
+    /// 剩余可分配 ID 数
+    pub remaining_ids: u64,
+    /// 已使用比例 `current_max_id / id_ceiling`
+    pub utilization: f64,
+    /// 预测窗口内的号段消耗速率（ID/秒）
+    pub burn_rate_per_sec: f64,
+    /// 预计耗尽秒数（`Idle` 时为 `None`）
+    pub seconds_to_exhaustion: Option<u64>,
+    /// 预计耗尽时间（Unix 秒）
+    pub projected_exhaustion_at_secs: Option<u64>,
+    pub status: CapacityStatus,
+}
+
+/// 容量预测报告
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CapacityReport {
+    /// 实际使用的预测窗口（秒）
+    pub window_secs: u64,
+    pub id_ceiling: u64,
+    pub warning_threshold_secs: u64,
+    pub critical_threshold_secs: u64,
+    /// 按紧急程度排序：状态优先，其次预计耗尽时间升序
+    pub forecasts: Vec<SegmentCapacityForecast>,
+}
+
+impl CapacityReport {
+    /// 指定状态的预测数
+    pub fn count_by_status(&self, status: CapacityStatus) -> usize {
+        self.forecasts.iter().filter(|f| f.status == status).count()
+    }
+
+    /// 全部 key 中最短的预计耗尽秒数
+    pub fn min_seconds_to_exhaustion(&self) -> Option<u64> {
+        self.forecasts
+            .iter()
+            .filter_map(|f| f.seconds_to_exhaustion)
+            .min()
+    }
+}
+
+/// 单个号段行在预测窗口内的上界采样
+struct SegmentSamples {
+    workspace: String,
+    biz_tag: String,
+    /// (采样时间 Unix 秒, 号段上界)，按时间升序
+    points: VecDeque<(u64, u64)>,
+}
+
+impl SegmentSamples {
+    fn latest_max_id(&self) -> u64 {
+        self.points.back().map(|&(_, max_id)| max_id).unwrap_or(0)
+    }
+
+    /// 窗口内首尾两次采样之间的上界推进速率（ID/秒）
+    fn burn_rate_per_sec(&self) -> f64 {
+        match (self.points.front(), self.points.back()) {
+            (Some(&(t0, v0)), Some(&(t1, v1))) if t1 > t0 => {
+                v1.saturating_sub(v0) as f64 / (t1 - t0) as f64
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// 单个 (workspace, biz_tag) 的号段上界与消耗速率
+struct KeyBurn {
+    workspace: String,
+    biz_tag: String,
+    current_max_id: u64,
+    burn_rate_per_sec: f64,
+}
+
+/// 号段耗尽预测器
+pub struct CapacityForecaster {
+    tracker: Arc<UsageTracker>,
+    config: CapacityConfig,
+    segments: Option<Arc<dyn SegmentRepository>>,
+    /// 按号段行 id 索引的采样，仅在接入号段仓库后使用
+    samples: Mutex<HashMap<i64, SegmentSamples>>,
+}
+
+impl CapacityForecaster {
+    pub fn new(tracker: Arc<UsageTracker>, config: CapacityConfig) -> Self {
+        Self {
+            tracker,
+            config,
+            segments: None,
+            samples: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 改为从 `nebula_segments` 采样消耗速率（需周期调用 [`Self::sample`]）
+    pub fn with_segment_repository(mut self, segments: Arc<dyn SegmentRepository>) -> Self {
+        self.segments = Some(segments);
+        self
+    }
+
+    pub fn config(&self) -> &CapacityConfig {
+        &self.config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn now_secs() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    /// 读取全部号段行并记录一次上界采样；未接入号段仓库时为空操作
+    pub async fn sample(&self) -> Result<()> {
+        let Some(segments) = &self.segments else {
+            return Ok(());
+        };
+        let rows = segments.list_all_segments().await?;
+        self.record_samples(Self::now_secs(), &rows);
+        Ok(())
+    }
+
+    /// 追加一次采样并丢弃预测窗口之外的旧点；已删除的号段行随之移除
+    fn record_samples(&self, now: u64, rows: &[SegmentInfo]) {
+        let window_start = now.saturating_sub(self.config.forecast_window_secs);
+        let mut samples = self.samples.lock();
+        let mut current = HashMap::with_capacity(rows.len());
+        for row in rows {
+            let mut entry = samples.remove(&row.id).unwrap_or_else(|| SegmentSamples {
+                workspace: row.workspace_id.clone(),
+                biz_tag: row.biz_tag.clone(),
+                points: VecDeque::new(),
+            });
+            // atomic 分配只推进 current_id，locked 分配同时更新两列，取较大者为上界
+            let max_id = row.current_id.max(row.max_id).max(0) as u64;
+            entry.points.push_back((now, max_id));
+            while entry.points.len() > 1
+                && entry.points.front().is_some_and(|&(t, _)| t < window_start)
+            {
+                entry.points.pop_front();
+            }
+            current.insert(row.id, entry);
+        }
+        *samples = current;
+    }
+
+    /// 按 (workspace, biz_tag) 汇总数据库采样：上界取各行最大值，速率为各行之和
+    fn sampled_burns(&self, workspace: Option<&str>) -> Vec<KeyBurn> {
+        let samples = self.samples.lock();
+        let mut keys: BTreeMap<(&str, &str), (u64, f64)> = BTreeMap::new();
+        for row in samples.values() {
+            if workspace.is_some_and(|ws| ws != row.workspace) {
+                continue;
+            }
+            let key = keys
+                .entry((row.workspace.as_str(), row.biz_tag.as_str()))
+                .or_insert((0, 0.0));
+            key.0 = key.0.max(row.latest_max_id());
+            key.1 += row.burn_rate_per_sec();
+        }
+        keys.into_iter()
+            .map(
+                |((workspace, biz_tag), (current_max_id, burn_rate_per_sec))| KeyBurn {
+                    workspace: workspace.to_string(),
+                    biz_tag: biz_tag.to_string(),
+                    current_max_id,
+                    burn_rate_per_sec,
+                },
+            )
+            .collect()
+    }
+
+    /// 本进程 [`UsageTracker`] 记录的号段分配，返回 (实际窗口秒数, 各 key 消耗)
+    fn tracked_burns(&self, workspace: Option<&str>) -> (u64, Vec<KeyBurn>) {
+        let usage = self.tracker.report(
+            Some(self.config.forecast_window_secs),
+            workspace,
+            usize::MAX,
+        );
+        let burns = usage
+            .biz_tags
+            .into_iter()
+            .map(|u| KeyBurn {
+                workspace: u.workspace,
+                biz_tag: u.biz_tag,
+                current_max_id: u.last_segment_end,
+                burn_rate_per_sec: u.segment_burn_rate_per_sec,
+            })
+            .collect();
+        (usage.window_secs, burns)
+    }
+
+    /// 根据剩余 ID 与消耗速率判定状态
+    fn classify(&self, remaining_ids: u64, seconds_to_exhaustion: Option<u64>) -> CapacityStatus {
+        if remaining_ids == 0 {
+            return CapacityStatus::Exhausted;
+        }
+        match seconds_to_exhaustion {
+            None => CapacityStatus::Idle,
+            Some(secs) if secs < self.config.critical_threshold_secs => CapacityStatus::Critical,
+            Some(secs) if secs < self.config.warning_threshold_secs => CapacityStatus::Warning,
+            Some(_) => CapacityStatus::Ok,
+        }
+    }
+
+    /// 生成容量预测；`workspace` 为 `Some` 时仅预测该 workspace。
+    ///
+    /// 只包含至少观测到一次号段分配的 key（无号段上界时无法计算剩余量）。
+    /// 接入号段仓库时使用最近的数据库采样，不足两次采样的 key 为 `Idle`。
+    pub fn forecast(&self, workspace: Option<&str>) -> CapacityReport {
+        let ceiling = self.config.id_ceiling;
+        let (window_secs, burns) = if self.segments.is_some() {
+            (
+                self.config.forecast_window_secs,
+                self.sampled_burns(workspace),
+            )
+        } else {
+            self.tracked_burns(workspace)
+        };
+        let now = Self::now_secs();
+
+        let mut forecasts: Vec<SegmentCapacityForecast> = burns
+            .into_iter()
+            .filter(|u| u.current_max_id > 0)
+            .map(|u| {
+                let remaining_ids = ceiling.saturating_sub(u.current_max_id);
+                let seconds_to_exhaustion = if u.burn_rate_per_sec > 0.0 {
+                    Some((remaining_ids as f64 / u.burn_rate_per_sec) as u64)
+                } else {
+                    None
+                };
+                let status = self.classify(remaining_ids, seconds_to_exhaustion);
+                SegmentCapacityForecast {
+                    workspace: u.workspace,
+                    biz_tag: u.biz_tag,
+                    current_max_id: u.current_max_id,
+                    remaining_ids,
+                    utilization: (u.current_max_id as f64 / ceiling as f64).min(1.0),
+                    burn_rate_per_sec: u.burn_rate_per_sec,
+                    seconds_to_exhaustion,
+                    projected_exhaustion_at_secs: seconds_to_exhaustion
+                        .map(|secs| now.saturating_add(secs)),
+                    status,
+                }
+            })
+            .collect();
+
+        forecasts.sort_by(|a, b| {
+            a.status
+                .cmp(&b.status)
+                .then_with(|| {
+                    a.seconds_to_exhaustion
+                        .unwrap_or(u64::MAX)
+                        .cmp(&b.seconds_to_exhaustion.unwrap_or(u64::MAX))
+                })
+                .then_with(|| a.workspace.cmp(&b.workspace))
+                .then_with(|| a.biz_tag.cmp(&b.biz_tag))
+        });
+
+        CapacityReport {
+            window_secs,
+            id_ceiling: ceiling,
+            warning_threshold_secs: self.config.warning_threshold_secs,
+            critical_threshold_secs: self.config.critical_threshold_secs,
+            forecasts,
+        }
+    }
+}
+
+/// 内置容量告警规则：预计耗尽时间低于 warning / critical 阈值时触发。
+///
+/// `for_duration = 0`：消耗速率本身已是窗口平均值，无需再等待持续期。
+pub fn capacity_alert_rules(config: &CapacityConfig) -> Vec<AlertRule> {
+    let rule = |name: &str, threshold: u64, severity: AlertSeverity, summary: &str| {
+        let mut rule = AlertRule::new(
+            name.to_string(),
+            format!("{}{}", EXHAUSTION_EXPRESSION_PREFIX, threshold),
+            severity,
+        );
+        rule.for_duration = 0;
+        rule.description = summary.to_string();
+        rule.labels = HashMap::from([("component".to_string(), "segment_capacity".to_string())]);
+        rule.annotations = HashMap::from([("summary".to_string(), summary.to_string())]);
+        rule
+    };
+
+    vec![
+        rule(
+            EXHAUSTION_WARNING_RULE,
+            config.warning_threshold_secs,
+            AlertSeverity::Warning,
+            "Segment ID space projected to run out within the warning threshold",
+        ),
+        rule(
+            EXHAUSTION_CRITICAL_RULE,
+            config.critical_threshold_secs,
+            AlertSeverity::Critical,
+            "Segment ID space projected to run out within the critical threshold",
+        ),
+    ]
+}
+
+/// 容量告警求值器：处理 `segment_exhaustion_secs < N` 表达式，
+/// 其余表达式委托给 [`DefaultEvaluator`]。
+pub struct CapacityAlertEvaluator {
+    forecaster: Arc<CapacityForecaster>,
+}
+
+impl CapacityAlertEvaluator {
+    pub fn new(forecaster: Arc<CapacityForecaster>) -> Self {
+        Self { forecaster }
+    }
+}
+
+impl AlertEvaluator for CapacityAlertEvaluator {
+    fn evaluate(&self, rule: &AlertRule, metrics: &GlobalMetrics) -> (bool, Option<String>) {
+        let Some(threshold) = rule
+            .expression
+            .trim()
+            .strip_prefix(EXHAUSTION_EXPRESSION_PREFIX)
+        else {
+            return DefaultEvaluator.evaluate(rule, metrics);
+        };
+        let Ok(threshold) = threshold.trim().parse::<u64>() else {
+            return (false, None);
+        };
+
+        let report = self.forecaster.forecast(None);
+        let breaching: Vec<String> = report
+            .forecasts
+            .iter()
+            .filter(|f| {
+                f.status == CapacityStatus::Exhausted
+                    || f.seconds_to_exhaustion.is_some_and(|secs| secs < threshold)
+            })
+            .take(MAX_KEYS_IN_ALERT_VALUE)
+            .map(|f| match f.seconds_to_exhaustion {
+                Some(secs) if f.status != CapacityStatus::Exhausted => {
+                    format!("{}/{}: {}s", f.workspace, f.biz_tag, secs)
+                }
+                _ => format!("{}/{}: exhausted", f.workspace, f.biz_tag),
+            })
+            .collect();
+
+        if breaching.is_empty() {
+            (false, None)
+        } else {
+            (true, Some(breaching.join(", ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::UsageConfig;
+
+    fn forecaster(config: CapacityConfig) -> (Arc<UsageTracker>, CapacityForecaster) {
+        let tracker = Arc::new(UsageTracker::new(UsageConfig::default()));
+        let forecaster = CapacityForecaster::new(tracker.clone(), config);
+        (tracker, forecaster)
+    }
+
+    /// 接入号段仓库的预测器；测试直接调用 `record_samples`，仓库不会被查询
+    fn sampled_forecaster(config: CapacityConfig) -> CapacityForecaster {
+        let db = dbnexus::sea_orm::MockDatabase::new(dbnexus::sea_orm::DbBackend::Postgres)
+            .into_connection();
+        let repo = Arc::new(crate::core::database::SeaOrmRepository::new(
+            db,
+            "test-salt".to_string(),
+        ));
+        CapacityForecaster::new(Arc::new(UsageTracker::new(UsageConfig::default())), config)
+            .with_segment_repository(repo)
+    }
+
+    fn segment_row(id: i64, workspace: &str, biz_tag: &str, current_id: i64) -> SegmentInfo {
+        SegmentInfo {
+            id,
+            workspace_id: workspace.to_string(),
+            biz_tag: biz_tag.to_string(),
+            current_id,
+            max_id: 1_000,
+            step: 1_000,
+            delta: 1,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_forecast_projects_time_to_exhaustion() {
+        let (tracker, forecaster) = forecaster(CapacityConfig {
+            forecast_window_secs: 100,
+            id_ceiling: 1_000_000,
+            ..Default::default()
+        });
+        // 窗口 100 秒内分配 10_000 个 ID → 100 ID/秒；剩余 990_000 → 9_900 秒
+        tracker.record_segment_allocation("ws", "orders", 10_000, 10_000);
+
+        let report = forecaster.forecast(None);
+        assert_eq!(report.forecasts.len(), 1);
+        let f = &report.forecasts[0];
+        assert_eq!(f.current_max_id, 10_000);
+        assert_eq!(f.remaining_ids, 990_000);
+        assert_eq!(f.seconds_to_exhaustion, Some(9_900));
+        assert!((f.utilization - 0.01).abs() < 1e-9);
+        assert_eq!(f.status, CapacityStatus::Critical);
+    }
+
+    #[test]
+    fn test_forecast_default_ceiling_is_ok_for_small_burn() {
+        let (tracker, forecaster) = forecaster(CapacityConfig::default());
+        tracker.record_segment_allocation("ws", "orders", 1_000, 1_000);
+        let report = forecaster.forecast(None);
+        assert_eq!(report.id_ceiling, i64::MAX as u64);
+        assert_eq!(report.forecasts[0].status, CapacityStatus::Ok);
+    }
+
+    #[test]
+    fn test_forecast_marks_exhausted_when_ceiling_reached() {
+        let (tracker, forecaster) = forecaster(CapacityConfig {
+            id_ceiling: 5_000,
+            ..Default::default()
+        });
+        tracker.record_segment_allocation("ws", "full", 6_000, 1_000);
+        let report = forecaster.forecast(None);
+        assert_eq!(report.forecasts[0].remaining_ids, 0);
+        assert_eq!(report.forecasts[0].status, CapacityStatus::Exhausted);
+        assert_eq!(report.count_by_status(CapacityStatus::Exhausted), 1);
+    }
+
+    #[test]
+    fn test_forecast_skips_keys_without_segment_allocations() {
+        let (tracker, forecaster) = forecaster(CapacityConfig::default());
+        tracker.record_generation("ws", "snowflake-tag", 10, true);
+        assert!(forecaster.forecast(None).forecasts.is_empty());
+    }
+
+    #[test]
+    fn test_forecast_sorts_most_urgent_first() {
+        let (tracker, forecaster) = forecaster(CapacityConfig {
+            forecast_window_secs: 100,
+            id_ceiling: 1_000_000,
+            critical_threshold_secs: 10,
+            warning_threshold_secs: 20,
+            ..Default::default()
+        });
+        tracker.record_segment_allocation("ws", "slow", 1_000, 100);
+        tracker.record_segment_allocation("ws", "fast", 900_000, 100_000);
+        let report = forecaster.forecast(None);
+        assert_eq!(report.forecasts[0].biz_tag, "fast");
+        assert_eq!(report.min_seconds_to_exhaustion(), Some(100));
+    }
+
+    #[test]
+    fn test_sampled_forecast_uses_database_progress() {
+        let forecaster = sampled_forecaster(CapacityConfig {
+            forecast_window_secs: 1_000,
+            id_ceiling: 1_000_000,
+            ..Default::default()
+        });
+        // 其他实例推进的号段同样体现在行上界中：100 秒内推进 10_000 → 100 ID/秒
+        forecaster.record_samples(1_000, &[segment_row(1, "ws", "orders", 10_000)]);
+        assert_eq!(
+            forecaster.forecast(None).forecasts[0].status,
+            CapacityStatus::Idle
+        );
+        forecaster.record_samples(1_100, &[segment_row(1, "ws", "orders", 20_000)]);
+
+        let report = forecaster.forecast(None);
+        assert_eq!(report.window_secs, 1_000);
+        let f = &report.forecasts[0];
+        assert_eq!(f.current_max_id, 20_000);
+        assert!((f.burn_rate_per_sec - 100.0).abs() < 1e-9);
+        assert_eq!(f.seconds_to_exhaustion, Some(9_800));
+    }
+
+    #[test]
+    fn test_sampled_forecast_sums_rows_and_drops_old_points() {
+        let forecaster = sampled_forecaster(CapacityConfig {
+            forecast_window_secs: 100,
+            id_ceiling: 1_000_000,
+            ..Default::default()
+        });
+        forecaster.record_samples(
+            0,
+            &[
+                segment_row(1, "ws", "orders", 0),
+                segment_row(2, "ws", "orders", 500_000),
+                segment_row(3, "other", "orders", 0),
+            ],
+        );
+        forecaster.record_samples(
+            100,
+            &[
+                segment_row(1, "ws", "orders", 1_000),
+                segment_row(2, "ws", "orders", 501_000),
+            ],
+        );
+        // t=0 的采样已超出窗口，速率只按 t=100..200 计算；已删除的行 3 不再出现
+        forecaster.record_samples(
+            200,
+            &[
+                segment_row(1, "ws", "orders", 1_500),
+                segment_row(2, "ws", "orders", 501_500),
+            ],
+        );
+
+        let report = forecaster.forecast(None);
+        assert_eq!(report.forecasts.len(), 1);
+        let f = &report.forecasts[0];
+        assert_eq!(f.current_max_id, 501_500);
+        assert!((f.burn_rate_per_sec - 10.0).abs() < 1e-9);
+        assert!(forecaster.forecast(Some("other")).forecasts.is_empty());
+    }
+
+    #[test]
+    fn test_capacity_alert_rules_use_configured_thresholds() {
+        let config = CapacityConfig {
+            warning_threshold_secs: 600,
+            critical_threshold_secs: 60,
+            ..Default::default()
+        };
+        let rules = capacity_alert_rules(&config);
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].name, EXHAUSTION_WARNING_RULE);
+        assert_eq!(rules[0].expression, "segment_exhaustion_secs < 600");
+        assert_eq!(rules[0].severity, AlertSeverity::Warning);
+        assert_eq!(rules[1].name, EXHAUSTION_CRITICAL_RULE);
+        assert_eq!(rules[1].expression, "segment_exhaustion_secs < 60");
+        assert_eq!(rules[1].severity, AlertSeverity::Critical);
+        assert_eq!(rules[1].for_duration, 0);
+    }
+
+    #[test]
+    fn test_capacity_evaluator_fires_below_threshold() {
+        let (tracker, forecaster) = forecaster(CapacityConfig {
+            forecast_window_secs: 100,
+            id_ceiling: 1_000_000,
+            ..Default::default()
+        });
+        tracker.record_segment_allocation("ws", "orders", 10_000, 10_000);
+        let evaluator = CapacityAlertEvaluator::new(Arc::new(forecaster));
+        let metrics = GlobalMetrics::new();
+
+        let rule = AlertRule::new(
+            "r",
+            "segment_exhaustion_secs < 10000",
+            AlertSeverity::Warning,
+        );
+        let (firing, value) = evaluator.evaluate(&rule, &metrics);
+        assert!(firing);
+        assert_eq!(value.as_deref(), Some("ws/orders: 9900s"));
+
+        let rule = AlertRule::new("r", "segment_exhaustion_secs < 100", AlertSeverity::Warning);
+        assert_eq!(evaluator.evaluate(&rule, &metrics), (false, None));
+    }
+
+    #[test]
+    fn test_capacity_evaluator_delegates_other_expressions() {
+        let (_, forecaster) = forecaster(CapacityConfig::default());
+        let evaluator = CapacityAlertEvaluator::new(Arc::new(forecaster));
+        let metrics = GlobalMetrics::new();
+        metrics
+            .total_errors
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let rule = AlertRule::new("r", "id_generation_failed", AlertSeverity::Critical);
+        let (firing, _) = evaluator.evaluate(&rule, &metrics);
+        assert!(firing);
+    }
+}
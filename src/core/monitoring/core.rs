@@ -754,6 +754,17 @@ impl AlertManager {
         (manager, alerts_rx)
     }
 
+    /// 替换规则求值器（如 `CapacityAlertEvaluator`，用于非 `GlobalMetrics` 数据源的规则）
+    pub fn with_evaluator(mut self, evaluator: Arc<dyn AlertEvaluator>) -> Self {
+        self.evaluator = evaluator;
+        self
+    }
+
+    /// 立即对全部启用规则求值一次（由外部定时任务驱动，无需 `start` 的内部循环）
+    pub async fn evaluate_once(&self) {
+        self.evaluate_all_rules().await;
+    }
+
     pub async fn start(&mut self) {
         if self.running.swap(true, Ordering::SeqCst) {
             warn!(
@@ -14,9 +14,18 @@
 
 //! Monitoring module for Nebula ID.
 
+pub mod capacity;
 pub mod core;
+pub mod telemetry;
+pub mod usage;
 
+pub use capacity::{
+    capacity_alert_rules, CapacityAlertEvaluator, CapacityForecaster, CapacityReport,
+    CapacityStatus, SegmentCapacityForecast,
+};
 pub use core::{
     Alert, AlertError, AlertManager, AlertNotificationSender, AlertRule, AlertSeverity, AlertState,
     AlertStatus, AlertingConfig, ChannelType, DefaultEvaluator, NotificationChannel,
 };
+pub use telemetry::{init_tracing_with_otlp, TelemetryGuard};
+pub use usage::{BizTagUsage, UsageReport, UsageTracker, WorkspaceUsage};
@@ -0,0 +1,293 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OpenTelemetry 分布式追踪导出。
+//!
+//! `monitoring.tracing_enabled = true` 时，`main.rs` 通过
+//! [`init_tracing_with_otlp`] 组装 `tracing` 订阅器：EnvFilter + JSON 日志层
+//! + `tracing-opentelemetry` 层，span 经 OTLP（gRPC 或 HTTP/protobuf）批量
+//! 导出到 `monitoring.otlp_endpoint`。
+//!
+//! 跨进程上下文采用 W3C Trace Context（`traceparent` / `tracestate`），
+//! 入站提取见 [`extract_remote_context`] 与 [`set_remote_parent`]。
+
+use crate::core::config::{MonitoringConfig, OtlpProtocol};
+use crate::core::types::{CoreError, Result};
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// OTLP/gRPC 默认端点（`otlp_endpoint` 为空时使用，与 OTel SDK 约定一致）
+pub const DEFAULT_OTLP_GRPC_ENDPOINT: &str = "http://localhost:4317";
+
+/// OTLP/HTTP 默认端点（`otlp_endpoint` 为空时使用，与 OTel SDK 约定一致）
+pub const DEFAULT_OTLP_HTTP_ENDPOINT: &str = "http://localhost:4318";
+
+/// W3C Trace Context 请求头名
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// OTLP/HTTP traces 信号路径
+const OTLP_HTTP_TRACES_PATH: &str = "/v1/traces";
+
+/// 根据协议计算实际导出端点。
+///
+/// - gRPC：原样使用（空值回退到 `localhost:4317`）。
+/// - HTTP：OTel 规范中 `OTEL_EXPORTER_OTLP_ENDPOINT` 是基础地址，需追加
+///   `/v1/traces`；若配置已包含该路径则不重复追加。
+pub fn resolve_otlp_endpoint(config: &MonitoringConfig) -> String {
+    let endpoint = config.otlp_endpoint.trim().trim_end_matches('/');
+    match config.otlp_protocol {
+        OtlpProtocol::Grpc => {
+            if endpoint.is_empty() {
+                DEFAULT_OTLP_GRPC_ENDPOINT.to_string()
+            } else {
+                endpoint.to_string()
+            }
+        }
+        OtlpProtocol::Http => {
+            let base = if endpoint.is_empty() {
+                DEFAULT_OTLP_HTTP_ENDPOINT
+            } else {
+                endpoint
+            };
+            if base.ends_with(OTLP_HTTP_TRACES_PATH) {
+                base.to_string()
+            } else {
+                format!("{}{}", base, OTLP_HTTP_TRACES_PATH)
+            }
+        }
+    }
+}
+
+/// 构建 OTLP span 导出的 TracerProvider（批量导出 + 父级优先比例采样）。
+pub fn build_tracer_provider(config: &MonitoringConfig) -> Result<SdkTracerProvider> {
+    let endpoint = resolve_otlp_endpoint(config);
+    let exporter = match config.otlp_protocol {
+        OtlpProtocol::Grpc => opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build(),
+        OtlpProtocol::Http => opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .build(),
+    }
+    .map_err(|e| CoreError::ConfigurationError(e.to_string()))?;
+
+    let resource = Resource::builder()
+        .with_service_name(config.service_name.clone())
+        .with_attribute(KeyValue::new("service.version", env!("CARGO_PKG_VERSION")))
+        .build();
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.trace_sample_ratio,
+        ))))
+        .with_resource(resource)
+        .build())
+}
+
+/// 追踪导出生命周期守卫。
+///
+/// 持有期间 span 持续导出；drop 时 flush 并关闭 exporter，避免进程退出时
+/// 丢失批处理队列中尚未发送的 span。
+pub struct TelemetryGuard {
+    provider: SdkTracerProvider,
+}
+
+impl TelemetryGuard {
+    /// 立即导出缓冲中的 span（优雅停机前调用）
+    pub fn flush(&self) {
+        if let Err(e) = self.provider.force_flush() {
+            tracing::warn!(error = %e, "OpenTelemetry span flush failed");
+        }
+    }
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            // 订阅器可能已随进程退出失效，直接写 stderr 保证可见
+            eprintln!("OpenTelemetry tracer provider shutdown failed: {}", e);
+        }
+    }
+}
+
+/// 安装带 OTLP 导出的全局 `tracing` 订阅器。
+///
+/// 日志仍以 JSON 格式输出到 stdout；过滤规则优先读取 `RUST_LOG`，未设置时
+/// 使用 `default_level`。同时注册 W3C Trace Context 全局传播器。
+pub fn init_tracing_with_otlp(
+    config: &MonitoringConfig,
+    default_level: &str,
+) -> Result<TelemetryGuard> {
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    let provider = build_tracer_provider(config)?;
+    let tracer = provider.tracer(config.service_name.clone());
+
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    opentelemetry::global::set_tracer_provider(provider.clone());
+
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(default_level));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().json())
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()
+        .map_err(|e| CoreError::ConfigurationError(e.to_string()))?;
+
+    tracing::info!(
+        endpoint = %resolve_otlp_endpoint(config),
+        protocol = %config.otlp_protocol,
+        sample_ratio = config.trace_sample_ratio,
+        "OpenTelemetry OTLP span export enabled"
+    );
+
+    Ok(TelemetryGuard { provider })
+}
+
+/// 从入站载体（HTTP header / gRPC metadata）提取远端 trace 上下文。
+///
+/// 未安装传播器（追踪关闭）时返回空上下文，调用方无需区分。
+pub fn extract_remote_context(extractor: &dyn Extractor) -> opentelemetry::Context {
+    opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(extractor))
+}
+
+/// 提取入站 `traceparent` 对应的父上下文；缺失或无法解析时返回 `None`。
+pub fn remote_parent(extractor: &dyn Extractor) -> Option<opentelemetry::Context> {
+    extractor.get(TRACEPARENT_HEADER)?;
+    let parent = extract_remote_context(extractor);
+    parent.span().span_context().is_valid().then_some(parent)
+}
+
+/// 将入站 `traceparent` 设为 `span` 的父上下文，使本服务的 span 接入调用方链路。
+///
+/// 载体中没有合法 `traceparent` 时不做任何修改，`span` 作为新 trace 的根。
+pub fn set_remote_parent(span: &tracing::Span, extractor: &dyn Extractor) {
+    let Some(parent) = remote_parent(extractor) else {
+        return;
+    };
+    if let Err(e) = span.set_parent(parent) {
+        tracing::debug!(error = ?e, "Failed to attach remote trace context to span");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    struct MapExtractor(HashMap<String, String>);
+
+    impl Extractor for MapExtractor {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).map(|v| v.as_str())
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(|k| k.as_str()).collect()
+        }
+    }
+
+    fn monitoring(protocol: OtlpProtocol, endpoint: &str) -> MonitoringConfig {
+        MonitoringConfig {
+            tracing_enabled: true,
+            otlp_endpoint: endpoint.to_string(),
+            otlp_protocol: protocol,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_grpc_endpoint_defaults_when_empty() {
+        let config = monitoring(OtlpProtocol::Grpc, "");
+        assert_eq!(resolve_otlp_endpoint(&config), DEFAULT_OTLP_GRPC_ENDPOINT);
+    }
+
+    #[test]
+    fn test_resolve_grpc_endpoint_keeps_configured_value() {
+        let config = monitoring(OtlpProtocol::Grpc, "http://otel:4317/");
+        assert_eq!(resolve_otlp_endpoint(&config), "http://otel:4317");
+    }
+
+    #[test]
+    fn test_resolve_http_endpoint_appends_traces_path() {
+        let config = monitoring(OtlpProtocol::Http, "http://otel:4318");
+        assert_eq!(resolve_otlp_endpoint(&config), "http://otel:4318/v1/traces");
+    }
+
+    #[test]
+    fn test_resolve_http_endpoint_does_not_duplicate_traces_path() {
+        let config = monitoring(OtlpProtocol::Http, "http://otel:4318/v1/traces");
+        assert_eq!(resolve_otlp_endpoint(&config), "http://otel:4318/v1/traces");
+    }
+
+    #[test]
+    fn test_resolve_http_endpoint_defaults_when_empty() {
+        let config = monitoring(OtlpProtocol::Http, "  ");
+        assert_eq!(
+            resolve_otlp_endpoint(&config),
+            "http://localhost:4318/v1/traces"
+        );
+    }
+
+    #[test]
+    fn test_trace_context_propagator_extracts_traceparent() {
+        use opentelemetry::propagation::TextMapPropagator;
+
+        let mut headers = HashMap::new();
+        headers.insert(
+            TRACEPARENT_HEADER.to_string(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+        );
+        let cx = TraceContextPropagator::new().extract(&MapExtractor(headers));
+        let span_context = cx.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(
+            span_context.trace_id().to_string(),
+            "4bf92f3577b34da6a3ce929d0e0e4736"
+        );
+        assert_eq!(span_context.span_id().to_string(), "00f067aa0ba902b7");
+    }
+
+    #[test]
+    fn test_set_remote_parent_without_traceparent_is_noop() {
+        let extractor = MapExtractor(HashMap::new());
+        assert!(remote_parent(&extractor).is_none());
+
+        let span = tracing::info_span!("test");
+        set_remote_parent(&span, &extractor);
+        assert!(!span.context().span().span_context().is_valid());
+    }
+
+    #[test]
+    fn test_remote_parent_rejects_malformed_traceparent() {
+        let mut headers = HashMap::new();
+        headers.insert(
+            TRACEPARENT_HEADER.to_string(),
+            "not-a-traceparent".to_string(),
+        );
+        assert!(remote_parent(&MapExtractor(headers)).is_none());
+    }
+}
@@ -0,0 +1,600 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! 租户维度（workspace / biz_tag）用量统计。
+//!
+//! `ApiMetrics` 与 `GlobalMetrics` 只有全局或按算法的计数，无法回答
+//! 「哪个租户在消耗 ID / 触发错误」。[`UsageTracker`] 按
+//! `(workspace, biz_tag)` 维护：
+//!
+//! - 累计计数（请求数、生成 ID 数、失败数、号段分配 ID 数）；
+//! - 瞬时 QPS（复用 [`QpsWindow`]）；
+//! - 固定宽度的时间桶历史，用于计算任意窗口（≤ `retention_secs`）内的
+//!   生成速率与号段消耗（burn）速率。
+//!
+//! # 基数控制
+//!
+//! workspace / biz_tag 来自请求参数，不可信。跟踪的 key 数量上限为
+//! `max_tracked_keys`：达到上限时先清理空闲超过 `idle_eviction_secs` 的
+//! key，仍满则按最近访问时间淘汰最旧的 10%。淘汰总数计入 `evicted_keys`，
+//! 在 `/api/v1/admin/usage` 中可见。
+
+use crate::core::config::UsageConfig;
+use crate::core::types::QpsWindow;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// 达到基数上限时单次淘汰的比例（最旧 10%），避免每次插入都全表扫描
+const EVICTION_BATCH_DIVISOR: usize = 10;
+
+/// 用量统计 key：(workspace, biz_tag)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UsageKey {
+    pub workspace: String,
+    pub biz_tag: String,
+}
+
+impl UsageKey {
+    pub fn new(workspace: impl Into<String>, biz_tag: impl Into<String>) -> Self {
+        Self {
+            workspace: workspace.into(),
+            biz_tag: biz_tag.into(),
+        }
+    }
+}
+
+/// 单个时间桶内的增量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct UsageBucket {
+    start_secs: u64,
+    requests: u64,
+    generated: u64,
+    failed: u64,
+    segment_ids: u64,
+    segment_allocations: u64,
+}
+
+/// 单个 (workspace, biz_tag) 的统计状态
+struct UsageEntry {
+    qps: QpsWindow,
+    total_requests: AtomicU64,
+    total_generated: AtomicU64,
+    total_failed: AtomicU64,
+    total_segment_ids: AtomicU64,
+    total_segment_allocations: AtomicU64,
+    /// 最近一次号段分配的结束 ID（号段上界）
+    last_segment_end: AtomicU64,
+    last_seen_secs: AtomicU64,
+    buckets: Mutex<VecDeque<UsageBucket>>,
+}
+
+impl UsageEntry {
+    fn new(now: u64) -> Self {
+        Self {
+            qps: QpsWindow::new(1),
+            total_requests: AtomicU64::new(0),
+            total_generated: AtomicU64::new(0),
+            total_failed: AtomicU64::new(0),
+            total_segment_ids: AtomicU64::new(0),
+            total_segment_allocations: AtomicU64::new(0),
+            last_segment_end: AtomicU64::new(0),
+            last_seen_secs: AtomicU64::new(now),
+            buckets: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// 在当前桶上应用增量；桶不存在时新建并裁剪超出保留期的旧桶
+    fn update_bucket(
+        &self,
+        now: u64,
+        bucket_secs: u64,
+        max_buckets: usize,
+        apply: impl FnOnce(&mut UsageBucket),
+    ) {
+        let bucket_start = now - now % bucket_secs;
+        let mut buckets = self.buckets.lock();
+        match buckets.back_mut() {
+            Some(last) if last.start_secs == bucket_start => apply(last),
+            _ => {
+                let mut bucket = UsageBucket {
+                    start_secs: bucket_start,
+                    ..Default::default()
+                };
+                apply(&mut bucket);
+                buckets.push_back(bucket);
+                while buckets.len() > max_buckets {
+                    buckets.pop_front();
+                }
+            }
+        }
+    }
+
+    /// 汇总 `[now - window_secs, now]` 内的桶
+    fn window_totals(&self, now: u64, window_secs: u64, bucket_secs: u64) -> UsageBucket {
+        let since = now.saturating_sub(window_secs);
+        let buckets = self.buckets.lock();
+        buckets
+            .iter()
+            .filter(|b| b.start_secs + bucket_secs > since)
+            .fold(UsageBucket::default(), |mut acc, b| {
+                acc.requests += b.requests;
+                acc.generated += b.generated;
+                acc.failed += b.failed;
+                acc.segment_ids += b.segment_ids;
+                acc.segment_allocations += b.segment_allocations;
+                acc
+            })
+    }
+}
+
+/// 单个 (workspace, biz_tag) 在查询窗口内的用量快照
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BizTagUsage {
+    pub workspace: String,
+    pub biz_tag: String,
+    /// 累计请求数（成功 + 失败）
+    pub total_requests: u64,
+    /// 累计生成 ID 数
+    pub total_generated: u64,
+    /// 累计失败请求数
+    pub total_failed: u64,
+    /// 瞬时 QPS（按生成 ID 数计）
+    pub current_qps: u64,
+    /// 窗口内生成 ID 数
+    pub window_generated: u64,
+    /// 窗口内失败请求数
+    pub window_failed: u64,
+    /// 窗口内平均生成速率（ID/秒）
+    pub generation_rate_per_sec: f64,
+    /// 窗口内号段分配的 ID 数
+    pub window_segment_ids: u64,
+    /// 窗口内号段消耗速率（ID/秒）
+    pub segment_burn_rate_per_sec: f64,
+    /// 最近一次号段分配的上界（未分配过号段时为 0）
+    pub last_segment_end: u64,
+    /// 最近一次访问时间（Unix 秒）
+    pub last_seen_secs: u64,
+}
+
+/// 单个 workspace 在查询窗口内的汇总
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceUsage {
+    pub workspace: String,
+    pub biz_tag_count: usize,
+    pub total_generated: u64,
+    pub window_generated: u64,
+    pub window_failed: u64,
+    pub generation_rate_per_sec: f64,
+    pub segment_burn_rate_per_sec: f64,
+}
+
+/// 用量报告
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UsageReport {
+    /// 实际使用的查询窗口（秒，已按保留期裁剪）
+    pub window_secs: u64,
+    /// 当前跟踪的 key 数
+    pub tracked_keys: usize,
+    /// 跟踪 key 数上限
+    pub max_tracked_keys: usize,
+    /// 累计淘汰的 key 数
+    pub evicted_keys: u64,
+    /// 按窗口内生成量降序的 workspace
+    pub top_workspaces: Vec<WorkspaceUsage>,
+    /// 按窗口内生成量降序的 biz_tag
+    pub biz_tags: Vec<BizTagUsage>,
+}
+
+/// 租户维度用量统计器（进程内共享，`Arc<UsageTracker>`）
+pub struct UsageTracker {
+    config: UsageConfig,
+    entries: RwLock<HashMap<UsageKey, Arc<UsageEntry>>>,
+    evicted_keys: AtomicU64,
+}
+
+impl Default for UsageTracker {
+    fn default() -> Self {
+        Self::new(UsageConfig::default())
+    }
+}
+
+impl UsageTracker {
+    pub fn new(mut config: UsageConfig) -> Self {
+        // 防御性修正：0 值会导致除零或保留期为空
+        config.bucket_secs = config.bucket_secs.max(1);
+        config.retention_secs = config.retention_secs.max(config.bucket_secs);
+        config.max_tracked_keys = config.max_tracked_keys.max(1);
+        Self {
+            config,
+            entries: RwLock::new(HashMap::new()),
+            evicted_keys: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &UsageConfig {
+        &self.config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    fn max_buckets(&self) -> usize {
+        self.config.retention_secs.div_ceil(self.config.bucket_secs) as usize + 1
+    }
+
+    fn now_secs() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    fn entry(&self, workspace: &str, biz_tag: &str, now: u64) -> Arc<UsageEntry> {
+        let key = UsageKey::new(workspace, biz_tag);
+        // 快路径：读锁命中
+        if let Some(entry) = self.entries.read().get(&key) {
+            entry.last_seen_secs.store(now, Ordering::Relaxed);
+            return entry.clone();
+        }
+
+        let mut entries = self.entries.write();
+        if let Some(entry) = entries.get(&key) {
+            entry.last_seen_secs.store(now, Ordering::Relaxed);
+            return entry.clone();
+        }
+        if entries.len() >= self.config.max_tracked_keys {
+            self.evict_locked(&mut entries, now);
+        }
+        let entry = Arc::new(UsageEntry::new(now));
+        entries.insert(key, entry.clone());
+        entry
+    }
+
+    /// 在持有写锁时淘汰：先清理空闲 key，仍满则淘汰最旧的一批
+    fn evict_locked(&self, entries: &mut HashMap<UsageKey, Arc<UsageEntry>>, now: u64) {
+        let before = entries.len();
+        let idle_cutoff = now.saturating_sub(self.config.idle_eviction_secs);
+        entries.retain(|_, e| e.last_seen_secs.load(Ordering::Relaxed) > idle_cutoff);
+
+        if entries.len() >= self.config.max_tracked_keys {
+            let batch = (self.config.max_tracked_keys / EVICTION_BATCH_DIVISOR).max(1);
+            let mut by_age: Vec<(u64, UsageKey)> = entries
+                .iter()
+                .map(|(k, e)| (e.last_seen_secs.load(Ordering::Relaxed), k.clone()))
+                .collect();
+            by_age.sort_unstable_by_key(|(seen, _)| *seen);
+            for (_, key) in by_age.into_iter().take(batch) {
+                entries.remove(&key);
+            }
+        }
+
+        let evicted = before.saturating_sub(entries.len()) as u64;
+        if evicted > 0 {
+            self.evicted_keys.fetch_add(evicted, Ordering::Relaxed);
+            tracing::debug!(
+                evicted,
+                tracked = entries.len(),
+                "Evicted usage tracking keys"
+            );
+        }
+    }
+
+    /// 清理空闲超过 `idle_eviction_secs` 的 key（生成报告时顺带调用）
+    pub fn evict_idle(&self) {
+        let now = Self::now_secs();
+        let idle_cutoff = now.saturating_sub(self.config.idle_eviction_secs);
+        let mut entries = self.entries.write();
+        let before = entries.len();
+        entries.retain(|_, e| e.last_seen_secs.load(Ordering::Relaxed) > idle_cutoff);
+        let evicted = before.saturating_sub(entries.len()) as u64;
+        if evicted > 0 {
+            self.evicted_keys.fetch_add(evicted, Ordering::Relaxed);
+        }
+    }
+
+    /// 记录一次生成请求（单个或批量）
+    pub fn record_generation(&self, workspace: &str, biz_tag: &str, count: u64, success: bool) {
+        if !self.config.enabled {
+            return;
+        }
+        let now = Self::now_secs();
+        let entry = self.entry(workspace, biz_tag, now);
+        entry.total_requests.fetch_add(1, Ordering::Relaxed);
+        if success {
+            entry.total_generated.fetch_add(count, Ordering::Relaxed);
+            entry.qps.record_batch(count as usize);
+        } else {
+            entry.total_failed.fetch_add(1, Ordering::Relaxed);
+        }
+        entry.update_bucket(now, self.config.bucket_secs, self.max_buckets(), |b| {
+            b.requests += 1;
+            if success {
+                b.generated += count;
+            } else {
+                b.failed += 1;
+            }
+        });
+    }
+
+    /// 记录一次号段分配：`[start_id, end_id)` 共 `step` 个 ID
+    pub fn record_segment_allocation(
+        &self,
+        workspace: &str,
+        biz_tag: &str,
+        end_id: u64,
+        step: u64,
+    ) {
+        if !self.config.enabled {
+            return;
+        }
+        let now = Self::now_secs();
+        let entry = self.entry(workspace, biz_tag, now);
+        entry.total_segment_ids.fetch_add(step, Ordering::Relaxed);
+        entry
+            .total_segment_allocations
+            .fetch_add(1, Ordering::Relaxed);
+        entry.last_segment_end.fetch_max(end_id, Ordering::Relaxed);
+        entry.update_bucket(now, self.config.bucket_secs, self.max_buckets(), |b| {
+            b.segment_ids += step;
+            b.segment_allocations += 1;
+        });
+    }
+
+    /// 当前跟踪的 key 数
+    pub fn tracked_keys(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    /// 累计淘汰的 key 数
+    pub fn evicted_keys(&self) -> u64 {
+        self.evicted_keys.load(Ordering::Relaxed)
+    }
+
+    /// 生成窗口用量报告。
+    ///
+    /// - `window_secs`：查询窗口，`None` 使用 `default_window_secs`，上限为
+    ///   `retention_secs`；
+    /// - `workspace`：仅统计指定 workspace；
+    /// - `limit`：`top_workspaces` 与 `biz_tags` 各自最多返回的条数。
+    pub fn report(
+        &self,
+        window_secs: Option<u64>,
+        workspace: Option<&str>,
+        limit: usize,
+    ) -> UsageReport {
+        self.evict_idle();
+
+        let now = Self::now_secs();
+        let window_secs = window_secs
+            .unwrap_or(self.config.default_window_secs)
+            .clamp(1, self.config.retention_secs);
+
+        let snapshot: Vec<(UsageKey, Arc<UsageEntry>)> = self
+            .entries
+            .read()
+            .iter()
+            .filter(|(k, _)| match workspace {
+                Some(ws) => k.workspace == ws,
+                None => true,
+            })
+            .map(|(k, e)| (k.clone(), e.clone()))
+            .collect();
+
+        let mut biz_tags: Vec<BizTagUsage> = snapshot
+            .iter()
+            .map(|(key, entry)| {
+                let window = entry.window_totals(now, window_secs, self.config.bucket_secs);
+                BizTagUsage {
+                    workspace: key.workspace.clone(),
+                    biz_tag: key.biz_tag.clone(),
+                    total_requests: entry.total_requests.load(Ordering::Relaxed),
+                    total_generated: entry.total_generated.load(Ordering::Relaxed),
+                    total_failed: entry.total_failed.load(Ordering::Relaxed),
+                    current_qps: entry.qps.get_qps(),
+                    window_generated: window.generated,
+                    window_failed: window.failed,
+                    generation_rate_per_sec: window.generated as f64 / window_secs as f64,
+                    window_segment_ids: window.segment_ids,
+                    segment_burn_rate_per_sec: window.segment_ids as f64 / window_secs as f64,
+                    last_segment_end: entry.last_segment_end.load(Ordering::Relaxed),
+                    last_seen_secs: entry.last_seen_secs.load(Ordering::Relaxed),
+                }
+            })
+            .collect();
+
+        let mut workspaces: HashMap<String, WorkspaceUsage> = HashMap::new();
+        for tag in &biz_tags {
+            let ws = workspaces
+                .entry(tag.workspace.clone())
+                .or_insert_with(|| WorkspaceUsage {
+                    workspace: tag.workspace.clone(),
+                    biz_tag_count: 0,
+                    total_generated: 0,
+                    window_generated: 0,
+                    window_failed: 0,
+                    generation_rate_per_sec: 0.0,
+                    segment_burn_rate_per_sec: 0.0,
+                });
+            ws.biz_tag_count += 1;
+            ws.total_generated += tag.total_generated;
+            ws.window_generated += tag.window_generated;
+            ws.window_failed += tag.window_failed;
+            ws.generation_rate_per_sec += tag.generation_rate_per_sec;
+            ws.segment_burn_rate_per_sec += tag.segment_burn_rate_per_sec;
+        }
+
+        let mut top_workspaces: Vec<WorkspaceUsage> = workspaces.into_values().collect();
+        top_workspaces.sort_by(|a, b| {
+            b.window_generated
+                .cmp(&a.window_generated)
+                .then_with(|| a.workspace.cmp(&b.workspace))
+        });
+        top_workspaces.truncate(limit);
+
+        biz_tags.sort_by(|a, b| {
+            b.window_generated
+                .cmp(&a.window_generated)
+                .then_with(|| a.workspace.cmp(&b.workspace))
+                .then_with(|| a.biz_tag.cmp(&b.biz_tag))
+        });
+        biz_tags.truncate(limit);
+
+        UsageReport {
+            window_secs,
+            tracked_keys: self.tracked_keys(),
+            max_tracked_keys: self.config.max_tracked_keys,
+            evicted_keys: self.evicted_keys(),
+            top_workspaces,
+            biz_tags,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(max_keys: usize) -> UsageTracker {
+        UsageTracker::new(UsageConfig {
+            max_tracked_keys: max_keys,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_record_generation_accumulates_per_key() {
+        let t = tracker(100);
+        t.record_generation("ws1", "orders", 1, true);
+        t.record_generation("ws1", "orders", 10, true);
+        t.record_generation("ws1", "orders", 0, false);
+        t.record_generation("ws2", "users", 5, true);
+
+        let report = t.report(None, None, 10);
+        assert_eq!(report.tracked_keys, 2);
+        let orders = report
+            .biz_tags
+            .iter()
+            .find(|u| u.biz_tag == "orders")
+            .unwrap();
+        assert_eq!(orders.total_requests, 3);
+        assert_eq!(orders.total_generated, 11);
+        assert_eq!(orders.total_failed, 1);
+        assert_eq!(orders.window_generated, 11);
+        assert_eq!(orders.window_failed, 1);
+        assert!(orders.generation_rate_per_sec > 0.0);
+    }
+
+    #[test]
+    fn test_report_orders_top_consumers_descending() {
+        let t = tracker(100);
+        t.record_generation("small", "a", 1, true);
+        t.record_generation("big", "a", 100, true);
+        t.record_generation("big", "b", 50, true);
+        t.record_generation("mid", "a", 20, true);
+
+        let report = t.report(None, None, 10);
+        let order: Vec<&str> = report
+            .top_workspaces
+            .iter()
+            .map(|w| w.workspace.as_str())
+            .collect();
+        assert_eq!(order, vec!["big", "mid", "small"]);
+        assert_eq!(report.top_workspaces[0].biz_tag_count, 2);
+        assert_eq!(report.top_workspaces[0].window_generated, 150);
+        assert_eq!(report.biz_tags[0].workspace, "big");
+        assert_eq!(report.biz_tags[0].biz_tag, "a");
+    }
+
+    #[test]
+    fn test_report_limit_and_workspace_filter() {
+        let t = tracker(100);
+        for i in 0..5 {
+            t.record_generation("ws1", &format!("tag{}", i), i + 1, true);
+        }
+        t.record_generation("ws2", "other", 1000, true);
+
+        let report = t.report(None, Some("ws1"), 2);
+        assert_eq!(report.biz_tags.len(), 2);
+        assert!(report.biz_tags.iter().all(|u| u.workspace == "ws1"));
+        assert_eq!(report.top_workspaces.len(), 1);
+        assert_eq!(report.top_workspaces[0].workspace, "ws1");
+    }
+
+    #[test]
+    fn test_cardinality_bound_evicts_oldest_batch() {
+        let t = tracker(10);
+        for i in 0..25 {
+            t.record_generation("ws", &format!("tag{}", i), 1, true);
+        }
+        assert!(t.tracked_keys() <= 10);
+        assert!(t.evicted_keys() >= 15);
+    }
+
+    #[test]
+    fn test_segment_allocation_tracks_burn_rate_and_upper_bound() {
+        let t = tracker(100);
+        t.record_segment_allocation("ws", "orders", 1001, 1000);
+        t.record_segment_allocation("ws", "orders", 3001, 2000);
+
+        let report = t.report(Some(60), None, 10);
+        let usage = &report.biz_tags[0];
+        assert_eq!(usage.window_segment_ids, 3000);
+        assert_eq!(usage.last_segment_end, 3001);
+        assert!((usage.segment_burn_rate_per_sec - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_window_clamped_to_retention() {
+        let t = UsageTracker::new(UsageConfig {
+            retention_secs: 600,
+            ..Default::default()
+        });
+        let report = t.report(Some(86_400), None, 10);
+        assert_eq!(report.window_secs, 600);
+        let report = t.report(Some(0), None, 10);
+        assert_eq!(report.window_secs, 1);
+    }
+
+    #[test]
+    fn test_disabled_tracker_records_nothing() {
+        let t = UsageTracker::new(UsageConfig {
+            enabled: false,
+            ..Default::default()
+        });
+        t.record_generation("ws", "tag", 1, true);
+        t.record_segment_allocation("ws", "tag", 100, 100);
+        assert_eq!(t.tracked_keys(), 0);
+    }
+
+    #[test]
+    fn test_bucket_history_is_bounded_by_retention() {
+        let t = UsageTracker::new(UsageConfig {
+            bucket_secs: 10,
+            retention_secs: 30,
+            ..Default::default()
+        });
+        let entry = t.entry("ws", "tag", 1_000);
+        for step in 0..20u64 {
+            entry.update_bucket(1_000 + step * 10, 10, t.max_buckets(), |b| b.generated += 1);
+        }
+        assert_eq!(entry.buckets.lock().len(), t.max_buckets());
+        // 窗口 30 秒只覆盖最近 3~4 个桶
+        let totals = entry.window_totals(1_190, 30, 10);
+        assert!(totals.generated <= 4);
+    }
+}
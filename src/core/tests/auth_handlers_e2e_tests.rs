@@ -103,6 +103,7 @@ impl ApiKeyRepository for MockApiKeyRepo {
                 enabled: true,
                 expires_at: None,
                 created_at: chrono::Utc::now().naive_utc(),
+                scopes: crate::core::auth::ApiKeyScopes::unrestricted(),
             },
             key_secret: "mock_secret".to_string(),
         })
@@ -430,7 +431,7 @@ async fn e2e_auth_failure_rate_blocks_after_10_failures() {
 
 #[tokio::test]
 async fn e2e_auth_failure_rate_allows_after_window_expires() {
-    // 由于 `check_auth_failure_rate` 使用 `Instant::now()` 且无法注入
+    // 由于 `BanManager` 的失败窗口使用 `Instant::now()` 且无法注入
     // mock 时间，这里采用「<10 次失败仍放行」的策略验证窗口边界：
     // 9 次失败后，第 10 次请求仍应返回 401（而非 429），
     // 表明失败窗口未满 10 次时不会误阻断。
@@ -74,9 +74,11 @@ use sdforge::tower::ServiceExt;
 use sdforge::tower_http::set_header::SetResponseHeaderLayer;
 use tempfile::NamedTempFile;
 
-use crate::core::config::{DatabaseConfig, DatabaseEngine, TlsConfig};
+use crate::core::config::{
+    DatabaseConfig, DatabaseEngine, ReplicaConfig, SegmentAllocationMode, TlsConfig,
+};
 use crate::core::container::AppContainer;
-use crate::core::database::{create_connection, run_migrations};
+use crate::core::database::{create_connection, migrations, run_migrations, SqlDialect};
 use crate::core::types::CoreError;
 use crate::server::config::tls::{TlsError, TlsManager};
 use crate::server::middleware::utils::get_client_ip;
@@ -531,6 +533,8 @@ async fn e2e_database_sqlite_memory_connection_succeeds() {
         min_connections: 1,
         acquire_timeout_seconds: 30,
         idle_timeout_seconds: 300,
+        segment_allocation: SegmentAllocationMode::Atomic,
+        replicas: ReplicaConfig::default(),
     };
 
     let conn = create_connection(&config).await;
@@ -539,26 +543,33 @@ async fn e2e_database_sqlite_memory_connection_succeeds() {
 
 /// E2E-DB-002: run_migrations 在所有 execute 成功时返回 Ok（迁移创建表）。
 ///
-/// run_migrations 发出 1 个 CREATE SCHEMA + 5 个 CREATE TABLE = 6 个
-/// execute 语句。用 MockDatabase 模拟真实数据库全部成功执行，验证
-/// 迁移逻辑的完整性（所有表创建语句都被发出且无错误）。
+/// 空库上 run_migrations 发出 CREATE SCHEMA + 创建版本表，在迁移锁事务中查询已应用
+/// 版本（为空），然后执行每个迁移的 up 语句并登记版本。用 MockDatabase
+/// 模拟真实数据库全部成功执行，验证迁移逻辑的完整性。
 #[tokio::test]
 async fn e2e_database_run_migrations_creates_tables() {
-    // 1 schema + 5 tables = 6 个成功的 execute
-    let results: Vec<MockExecResult> = (0..6)
+    // schema + 版本表 + 迁移锁 + 每个迁移的 up 语句与版本登记
+    let executes = 3 + migrations()
+        .iter()
+        .map(|m| m.up_sql(SqlDialect::Postgres).len() + 1)
+        .sum::<usize>();
+    let results: Vec<MockExecResult> = (0..executes)
         .map(|_| MockExecResult {
             last_insert_id: 0,
             rows_affected: 0,
         })
         .collect();
     let db = MockDatabase::new(DatabaseBackend::Postgres)
+        .append_query_results(vec![Vec::<
+            std::collections::BTreeMap<String, dbnexus::sea_orm::Value>,
+        >::new()])
         .append_exec_results(results)
         .into_connection();
 
     let result = run_migrations(&db).await;
     assert!(
         result.is_ok(),
-        "迁移应成功创建 schema + 5 张表: {:?}",
+        "迁移应在空库上应用全部版本: {:?}",
         result.err()
     );
 }
@@ -582,6 +593,8 @@ async fn e2e_database_password_with_env_var_rejected() {
         min_connections: 1,
         acquire_timeout_seconds: 5,
         idle_timeout_seconds: 300,
+        segment_allocation: SegmentAllocationMode::Atomic,
+        replicas: ReplicaConfig::default(),
     };
 
     let result = create_connection(&config).await;
@@ -53,6 +53,7 @@ use async_trait::async_trait;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::core::auth::ApiKeyScopes;
 use crate::core::database::{
     ApiKey, ApiKeyInfo, ApiKeyRepository, ApiKeyResponse, ApiKeyRole, ApiKeyWithSecret,
     CreateApiKeyRequest,
@@ -222,7 +223,7 @@ async fn e2e_key_rotation_grace_period_valid_range() {
     let mock_repo = Arc::new(RecordingApiKeyRepo::new());
     let handlers = build_handlers_with_repo_and_grace(mock_repo.clone(), VALID_SECONDS);
 
-    let _ = handlers.rotate_api_key("nino_test_key").await;
+    let _ = handlers.rotate_api_key("nino_test_key", None).await;
 
     let captured = mock_repo.captured_grace_seconds();
     assert_eq!(
@@ -239,7 +240,7 @@ async fn e2e_key_rotation_grace_period_below_minimum_clamped() {
     let mock_repo = Arc::new(RecordingApiKeyRepo::new());
     let handlers = build_handlers_with_repo_and_grace(mock_repo.clone(), 0);
 
-    let _ = handlers.rotate_api_key("nino_test_key").await;
+    let _ = handlers.rotate_api_key("nino_test_key", None).await;
 
     let captured = mock_repo.captured_grace_seconds();
     assert_eq!(
@@ -259,7 +260,7 @@ async fn e2e_key_rotation_grace_period_above_maximum_clamped() {
     let mock_repo = Arc::new(RecordingApiKeyRepo::new());
     let handlers = build_handlers_with_repo_and_grace(mock_repo.clone(), over_max);
 
-    let _ = handlers.rotate_api_key("nino_test_key").await;
+    let _ = handlers.rotate_api_key("nino_test_key", None).await;
 
     let captured = mock_repo.captured_grace_seconds();
     assert_eq!(
@@ -348,6 +349,8 @@ async fn e2e_repository_crud_create_and_get_api_key() {
         expires_at: None,
         key_secret: Some("test-secret-12345".to_string()),
         key_id: Some("nino_crud_test_key".to_string()),
+        scopes: None,
+        request_signing: false,
     };
 
     let created = repo
@@ -393,6 +396,8 @@ async fn e2e_repository_crud_list_api_keys() {
             expires_at: None,
             key_secret: Some(format!("secret-{i}-12345")),
             key_id: Some(format!("nino_list_key_{i}")),
+            scopes: None,
+            request_signing: false,
         };
         repo.create_api_key(&req)
             .await
@@ -409,6 +414,8 @@ async fn e2e_repository_crud_list_api_keys() {
         expires_at: None,
         key_secret: Some("other-secret-12345".to_string()),
         key_id: Some("nino_other_key".to_string()),
+        scopes: None,
+        request_signing: false,
     };
     repo.create_api_key(&req)
         .await
@@ -463,6 +470,8 @@ async fn e2e_repository_crud_revoke_api_key() {
         expires_at: None,
         key_secret: Some("revoke-secret-12345".to_string()),
         key_id: Some("nino_revoke_key".to_string()),
+        scopes: None,
+        request_signing: false,
     };
     let created = repo
         .create_api_key(&create_req)
@@ -572,6 +581,7 @@ impl ApiKeyRepository for CrudApiKeyRepo {
             expires_at,
             last_used_at: None,
             created_at: now,
+            scopes: ApiKeyScopes::unrestricted(),
         };
 
         self.keys.lock().unwrap().insert(id, info.clone());
@@ -592,6 +602,7 @@ impl ApiKeyRepository for CrudApiKeyRepo {
                 enabled: info.enabled,
                 expires_at: info.expires_at,
                 created_at: info.created_at,
+                scopes: ApiKeyScopes::unrestricted(),
             },
             key_secret,
         })
@@ -732,6 +743,7 @@ impl ApiKeyRepository for RecordingApiKeyRepo {
                 enabled: true,
                 expires_at: None,
                 created_at: chrono::Utc::now().naive_utc(),
+                scopes: ApiKeyScopes::unrestricted(),
             },
             key_secret: "mock-secret".to_string(),
         })
@@ -798,6 +810,7 @@ impl ApiKeyRepository for RecordingApiKeyRepo {
                 enabled: true,
                 expires_at: None,
                 created_at: chrono::Utc::now().naive_utc(),
+                scopes: ApiKeyScopes::unrestricted(),
             },
             key_secret: "rotated-secret".to_string(),
         })
@@ -1031,6 +1044,14 @@ impl crate::server::config::management::ConfigManagementService for MinimalConfi
         Ok(None)
     }
 
+    async fn update_workspace(
+        &self,
+        _name: &str,
+        _request: &crate::core::database::UpdateWorkspaceRequest,
+    ) -> crate::core::Result<Option<crate::server::models::WorkspaceResponse>> {
+        Ok(None)
+    }
+
     async fn create_group(
         &self,
         _req: crate::server::models::CreateGroupRequest,
@@ -65,6 +65,7 @@ use crate::server::api_version::{
     api_version_middleware, ApiVersion, ApiVersionErrorResponse, API_VERSION_HEADER,
     CURRENT_API_VERSION,
 };
+use crate::core::algorithm::AuditLogger as _;
 use crate::server::audit::{AuditEvent, AuditEventType, AuditLogger, AuditResult};
 use crate::server::config::cors::{
     create_cors_layer, create_dev_cors_layer, create_env_aware_cors_layer,
@@ -955,42 +956,42 @@ async fn e2e_audit_logger_file_persistence_appends_multiple_events() {
 }
 
 // ============================================================================
-// E2E-AUDIT-014: CoreAuditLoggerTrait impl 适配 core 层事件
+// E2E-AUDIT-014: core 层经 DynAuditLogger 记录的事件原样进入 server AuditLogger
 // ============================================================================
 
 #[tokio::test]
-async fn e2e_audit_logger_core_trait_impl_adapts_core_event() {
-    use crate::core::algorithm::{
-        AuditEvent as CoreAuditEvent, AuditLogger as CoreAuditLoggerTrait,
-    };
-    use chrono::Utc;
+async fn e2e_audit_logger_core_trait_keeps_event_identity_and_context() {
+    use crate::core::algorithm::{AuditContext, DynAuditLogger};
 
-    let logger = AuditLogger::new(100);
+    let logger = Arc::new(AuditLogger::new(100));
+    let core_logger: DynAuditLogger = logger.clone();
 
-    let core_event = CoreAuditEvent {
-        event_type: AuditEventType::IdGeneration,
-        workspace_id: Some("ws-core".to_string()),
-        action: "core_action".to_string(),
-        resource: "core_resource".to_string(),
-        result: AuditResult::Success,
-        details: Some(serde_json::json!({"k": "v"})),
-        timestamp: Utc::now(),
+    // 模拟核心层组件在请求作用域内构建事件：调用方与请求 ID 来自上下文
+    let context = AuditContext {
+        actor: Some("admin-key".to_string()),
+        request_id: Some("req-core-1".to_string()),
     };
-
-    // 通过 trait 调用（server AuditLogger impl CoreAuditLoggerTrait）。
-    // 必须用完全限定语法 `CoreAuditLoggerTrait::log(&logger, ...)`，
-    // 因为 AuditLogger 还有一个同名 inherent 方法 `log(&self, server AuditEvent)`，
-    // 直接 `logger.log(core_event)` 会匹配 inherent 方法导致类型不匹配。
-    CoreAuditLoggerTrait::log(&logger, core_event).await;
+    let event = context
+        .scope(async {
+            AuditEvent::builder(AuditEventType::IdGeneration, "core_action", "core_resource")
+                .workspace("ws-core")
+                .details(serde_json::json!({"k": "v"}))
+                .build()
+        })
+        .await;
+    let event_id = event.id;
+    core_logger.log(event).await;
 
     let events = logger.get_recent_events(10).await;
     assert_eq!(events.len(), 1);
     let e = &events[0];
+    assert_eq!(e.id, event_id, "事件 ID 不再在 server 端重新生成");
     assert_eq!(e.workspace_id.as_deref(), Some("ws-core"));
     assert_eq!(e.action, "core_action");
     assert_eq!(e.resource, "core_resource");
-    // server 端重新生成了 ID（next_audit_event_id）
-    assert_ne!(e.id, 0);
+    assert_eq!(e.result, AuditResult::Success);
+    assert_eq!(e.user_id.as_deref(), Some("admin-key"));
+    assert_eq!(e.request_id.as_deref(), Some("req-core-1"));
 }
 
 // ============================================================================
@@ -1369,11 +1370,11 @@ async fn e2e_audit_middleware_records_failure_event_for_4xx_response() {
 }
 
 // ============================================================================
-// E2E-AUDITMW-003: 审计中间件端到端 HTTP 流（5xx → Partial）
+// E2E-AUDITMW-003: 审计中间件端到端 HTTP 流（5xx → Failure）
 // ============================================================================
 
 #[tokio::test]
-async fn e2e_audit_middleware_records_partial_event_for_5xx_response() {
+async fn e2e_audit_middleware_records_failure_event_for_5xx_response() {
     let audit_logger = Arc::new(AuditLogger::new(100));
     let logger_clone = audit_logger.clone();
 
@@ -1393,12 +1394,10 @@ async fn e2e_audit_middleware_records_partial_event_for_5xx_response() {
                 let duration_ms = start.elapsed().as_millis() as u64;
                 let status_code = response.status().as_u16();
 
-                let result = if (200..300).contains(&status_code) {
-                    AuditResult::Success
-                } else if (400..500).contains(&status_code) {
+                let result = if status_code >= 400 {
                     AuditResult::Failure
                 } else {
-                    AuditResult::Partial
+                    AuditResult::Success
                 };
 
                 let event = AuditEvent::new(
@@ -1431,7 +1430,7 @@ async fn e2e_audit_middleware_records_partial_event_for_5xx_response() {
 
     let events = audit_logger.get_recent_events(10).await;
     assert_eq!(events.len(), 1);
-    assert_eq!(events[0].result, AuditResult::Partial);
+    assert_eq!(events[0].result, AuditResult::Failure);
 }
 
 // ============================================================================
@@ -562,6 +562,7 @@ fn e2e_core_error_all_variants_have_en_translation() {
         CoreError::CacheError("v".to_string()),
         CoreError::ConfigurationError("v".to_string()),
         CoreError::AuthenticationError("v".to_string()),
+        CoreError::PermissionDenied("v".to_string()),
         CoreError::RateLimitExceeded,
         CoreError::NotFound("v".to_string()),
         CoreError::WorkspaceDisabled("v".to_string()),
@@ -578,7 +579,7 @@ fn e2e_core_error_all_variants_have_en_translation() {
         CoreError::Unknown,
     ];
 
-    assert_eq!(variants.len(), 24, "E2E: CoreError should have 24 variants");
+    assert_eq!(variants.len(), 25, "E2E: CoreError should have 25 variants");
 
     for (i, err) in variants.iter().enumerate() {
         let msg = err.to_localized_string("en");
@@ -56,6 +56,9 @@ pub enum CoreError {
     #[error("{}", t!("error.authentication_error", value = _0))]
     AuthenticationError(String),
 
+    #[error("{}", t!("error.permission_denied", value = _0))]
+    PermissionDenied(String),
+
     #[error("{}", t!("error.rate_limit_exceeded"))]
     RateLimitExceeded,
 
@@ -193,6 +196,7 @@ impl CoreError {
             CoreError::CacheError(_) => "error.cache_error",
             CoreError::ConfigurationError(_) => "error.configuration_error",
             CoreError::AuthenticationError(_) => "error.authentication_error",
+            CoreError::PermissionDenied(_) => "error.permission_denied",
             CoreError::RateLimitExceeded => "error.rate_limit_exceeded",
             CoreError::NotFound(_) => "error.not_found",
             CoreError::WorkspaceDisabled(_) => "error.workspace_disabled",
@@ -245,6 +249,7 @@ impl CoreError {
             CoreError::AuthenticationError(s) => {
                 smallvec![("value", Cow::Borrowed(s.as_str()))]
             }
+            CoreError::PermissionDenied(s) => smallvec![("value", Cow::Borrowed(s.as_str()))],
             CoreError::RateLimitExceeded => smallvec![],
             CoreError::NotFound(s) => smallvec![("value", Cow::Borrowed(s.as_str()))],
             CoreError::WorkspaceDisabled(s) => smallvec![("value", Cow::Borrowed(s.as_str()))],
@@ -508,6 +513,10 @@ mod tests {
             CoreError::WorkspaceDisabled("v".to_string()).to_localized_string("en"),
             "Workspace disabled: v"
         );
+        assert_eq!(
+            CoreError::PermissionDenied("v".to_string()).to_localized_string("en"),
+            "Permission denied: v"
+        );
         assert_eq!(
             CoreError::BizTagNotFound("v".to_string()).to_localized_string("en"),
             "Biz tag not found: v"
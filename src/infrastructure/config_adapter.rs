@@ -18,12 +18,18 @@
 //! interface and Nebula ID's domain-specific configuration structures.
 
 use crate::core::config::{
-    AlgorithmConfig, AppConfig, AuthConfig, BatchGenerateConfig, DatabaseConfig, EtcdConfig,
-    LogLevel, LoggingConfig, MonitoringConfig, RateLimitConfig, SegmentAlgorithmConfig,
-    SnowflakeAlgorithmConfig, TlsConfig, UuidV7Config,
+    AlgorithmConfig, ApiKeyLifecycleConfig, AppConfig, AuditConfig, AuditOverflowPolicy,
+    AuditSinkDelivery, AuditSinkOverflowPolicy, AuditSinksConfig, AuthConfig, BanConfig,
+    BatchGenerateConfig, CapacityConfig, ClientAuthMode, CredentialCacheConfig, DatabaseConfig,
+    EtcdConfig, FileSinkConfig, HealthConfig, JwtConfig, LogLevel, LoggingConfig, MonitoringConfig,
+    OtlpProtocol, RateLimitConfig, ReplicaConfig, SegmentAlgorithmConfig, SegmentAllocationMode,
+    SnowflakeAlgorithmConfig, SyslogProtocol, SyslogSinkConfig, TlsConfig, UsageConfig,
+    UuidV7Config, WebhookSinkConfig,
 };
 // ARCH-MED-002 修复：统一引用 auth 模块的常量，避免默认值重复定义。
-use crate::core::config::auth::DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS;
+use crate::core::config::auth::{
+    DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS, DEFAULT_MAX_API_KEYS_PER_WORKSPACE,
+};
 use confers::interface::{ConfigProvider, ConfigProviderExt};
 use std::sync::Arc;
 
@@ -190,6 +196,7 @@ impl ConfigAdapter {
     /// - `auth.enabled` - Enable authentication
     /// - `auth.cache_ttl_seconds` - Cache TTL
     /// - `auth.api_key_salt` - Salt for API key hashing
+    /// - `auth.max_api_keys_per_workspace` - Self-service key limit (0 = unlimited)
     ///
     /// Phase 9 T043 (HIGH H1 / tiangang HIGH-1) — `api_key_salt` no
     /// longer falls back to a hard-coded value. If unset, returns an
@@ -215,6 +222,169 @@ impl ConfigAdapter {
                 .get_int("auth.key_rotation_grace_period_seconds")
                 .map(|v| v as u64)
                 .unwrap_or(DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS),
+            signature_max_skew_seconds: self
+                .provider
+                .get_int("auth.signature_max_skew_seconds")
+                .map(|v| v as u64)
+                .unwrap_or(crate::core::auth::signing::DEFAULT_MAX_CLOCK_SKEW_SECS),
+            max_api_keys_per_workspace: self
+                .provider
+                .get_int("auth.max_api_keys_per_workspace")
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(DEFAULT_MAX_API_KEYS_PER_WORKSPACE),
+            jwt: self.get_jwt_config(),
+            lifecycle: self.get_api_key_lifecycle_config(),
+            credential_cache: self.get_credential_cache_config(),
+            bans: self.get_ban_config(),
+        }
+    }
+
+    /// Get the JWT / OIDC bearer token configuration.
+    ///
+    /// Keys (under `auth.jwt`):
+    /// - `enabled` - Accept `Authorization: Bearer <jwt>`
+    /// - `issuer` / `audience` - Expected `iss` / `aud` (empty = not checked)
+    /// - `jwks_url` / `jwks_path` - JWKS source (URL takes precedence)
+    /// - `jwks_refresh_seconds`, `leeway_seconds`
+    /// - `workspace_claim`, `role_claim`, `admin_role`, `scopes_claim`
+    /// - `missing_scopes_unrestricted` - Treat tokens without scopes as unrestricted
+    pub fn get_jwt_config(&self) -> JwtConfig {
+        let defaults = JwtConfig::default();
+        let string = |key: &str, default: String| {
+            self.provider
+                .get_string(&format!("auth.jwt.{}", key))
+                .unwrap_or(default)
+        };
+        let int = |key: &str, default: u64| {
+            self.provider
+                .get_int(&format!("auth.jwt.{}", key))
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(default)
+        };
+        JwtConfig {
+            enabled: self
+                .provider
+                .get_bool("auth.jwt.enabled")
+                .unwrap_or(defaults.enabled),
+            issuer: string("issuer", defaults.issuer),
+            audience: string("audience", defaults.audience),
+            jwks_url: string("jwks_url", defaults.jwks_url),
+            jwks_path: string("jwks_path", defaults.jwks_path),
+            jwks_refresh_seconds: int("jwks_refresh_seconds", defaults.jwks_refresh_seconds),
+            leeway_seconds: int("leeway_seconds", defaults.leeway_seconds),
+            workspace_claim: string("workspace_claim", defaults.workspace_claim),
+            role_claim: string("role_claim", defaults.role_claim),
+            admin_role: string("admin_role", defaults.admin_role),
+            scopes_claim: string("scopes_claim", defaults.scopes_claim),
+            missing_scopes_unrestricted: self
+                .provider
+                .get_bool("auth.jwt.missing_scopes_unrestricted")
+                .unwrap_or(defaults.missing_scopes_unrestricted),
+        }
+    }
+
+    /// Get the API key lifecycle policy.
+    ///
+    /// Keys (under `auth.lifecycle`):
+    /// - `enabled`, `check_interval_seconds`
+    /// - `max_key_age_days` - Flag keys for rotation (0 = off)
+    /// - `auto_rotate` - Rotate flagged keys automatically
+    /// - `expiry_warning_days` - Warn before `expires_at` (0 = off)
+    /// - `disable_expired`, `disable_unused_days` (0 = off)
+    /// - `alert_webhook_url` - Extra alert channel (empty = log only)
+    pub fn get_api_key_lifecycle_config(&self) -> ApiKeyLifecycleConfig {
+        let defaults = ApiKeyLifecycleConfig::default();
+        let int = |key: &str, default: u64| {
+            self.provider
+                .get_int(&format!("auth.lifecycle.{}", key))
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(default)
+        };
+        ApiKeyLifecycleConfig {
+            enabled: self
+                .provider
+                .get_bool("auth.lifecycle.enabled")
+                .unwrap_or(defaults.enabled),
+            check_interval_seconds: int("check_interval_seconds", defaults.check_interval_seconds),
+            max_key_age_days: int("max_key_age_days", defaults.max_key_age_days),
+            auto_rotate: self
+                .provider
+                .get_bool("auth.lifecycle.auto_rotate")
+                .unwrap_or(defaults.auto_rotate),
+            expiry_warning_days: int("expiry_warning_days", defaults.expiry_warning_days),
+            disable_expired: self
+                .provider
+                .get_bool("auth.lifecycle.disable_expired")
+                .unwrap_or(defaults.disable_expired),
+            disable_unused_days: int("disable_unused_days", defaults.disable_unused_days),
+            alert_webhook_url: self
+                .provider
+                .get_string("auth.lifecycle.alert_webhook_url")
+                .unwrap_or(defaults.alert_webhook_url),
+        }
+    }
+
+    /// Get the verified-credential cache settings.
+    ///
+    /// Keys (under `auth.credential_cache`):
+    /// - `enabled`, `ttl_seconds`, `max_entries`
+    /// - `poll_interval_ms` - Change-table poll interval (upper bound on propagation delay)
+    /// - `etcd_watch`, `etcd_prefix` - Push changes through etcd when configured
+    pub fn get_credential_cache_config(&self) -> CredentialCacheConfig {
+        let defaults = CredentialCacheConfig::default();
+        let int = |key: &str, default: u64| {
+            self.provider
+                .get_int(&format!("auth.credential_cache.{}", key))
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(default)
+        };
+        let flag = |key: &str, default: bool| {
+            self.provider
+                .get_bool(&format!("auth.credential_cache.{}", key))
+                .unwrap_or(default)
+        };
+        CredentialCacheConfig {
+            enabled: flag("enabled", defaults.enabled),
+            ttl_seconds: int("ttl_seconds", defaults.ttl_seconds),
+            max_entries: int("max_entries", defaults.max_entries as u64) as usize,
+            poll_interval_ms: int("poll_interval_ms", defaults.poll_interval_ms),
+            etcd_watch: flag("etcd_watch", defaults.etcd_watch),
+            etcd_prefix: self
+                .provider
+                .get_string("auth.credential_cache.etcd_prefix")
+                .unwrap_or(defaults.etcd_prefix),
+        }
+    }
+
+    /// Get the auth-failure ban policy.
+    ///
+    /// Keys (under `auth.bans`):
+    /// - `enabled`, `failure_threshold`, `failure_window_seconds`
+    /// - `base_ban_seconds`, `max_ban_seconds` - Duration doubles per level up to the max
+    /// - `escalation_window_seconds` - Bans within this window of the last one escalate
+    /// - `sync_interval_seconds` - How often bans are reloaded from the database
+    pub fn get_ban_config(&self) -> BanConfig {
+        let defaults = BanConfig::default();
+        let int = |key: &str, default: u64| {
+            self.provider
+                .get_int(&format!("auth.bans.{}", key))
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(default)
+        };
+        BanConfig {
+            enabled: self
+                .provider
+                .get_bool("auth.bans.enabled")
+                .unwrap_or(defaults.enabled),
+            failure_threshold: int("failure_threshold", defaults.failure_threshold),
+            failure_window_seconds: int("failure_window_seconds", defaults.failure_window_seconds),
+            base_ban_seconds: int("base_ban_seconds", defaults.base_ban_seconds),
+            max_ban_seconds: int("max_ban_seconds", defaults.max_ban_seconds),
+            escalation_window_seconds: int(
+                "escalation_window_seconds",
+                defaults.escalation_window_seconds,
+            ),
+            sync_interval_seconds: int("sync_interval_seconds", defaults.sync_interval_seconds),
         }
     }
 
@@ -226,6 +396,8 @@ impl ConfigAdapter {
     /// - `database.min_connections` - Minimum connections
     /// - `database.acquire_timeout_seconds` - Acquisition timeout
     /// - `database.idle_timeout_seconds` - Idle timeout
+    /// - `database.segment_allocation` - Segment allocation mode (`atomic` | `locked`)
+    /// - `database.replicas.*` - Read replicas, see [`Self::get_replica_config`]
     ///
     /// Phase 9 T043 (HIGH H2 / tiangang MEDIUM-1) — no hard-coded
     /// production fallback. Dev/test keeps `postgresql://localhost/nebula`
@@ -261,10 +433,53 @@ impl ConfigAdapter {
                 .provider
                 .get_int("database.idle_timeout_seconds")
                 .unwrap_or(300) as u64,
+            segment_allocation: self
+                .provider
+                .get_string("database.segment_allocation")
+                .as_deref()
+                .map(SegmentAllocationMode::from)
+                .unwrap_or_default(),
+            replicas: self.get_replica_config(),
             ..Default::default()
         }
     }
 
+    /// Get the read replica configuration.
+    ///
+    /// Keys (under `database.replicas`):
+    /// - `urls` - Comma-separated list of full replica connection URLs
+    /// - `max_connections` - Pool size per replica
+    /// - `max_lag_seconds` - Replicas lagging further behind stop serving reads
+    /// - `health_check_interval_seconds` - How often health and lag are checked
+    pub fn get_replica_config(&self) -> ReplicaConfig {
+        let defaults = ReplicaConfig::default();
+        let int = |key: &str, default: u64| {
+            self.provider
+                .get_int(&format!("database.replicas.{}", key))
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(default)
+        };
+        ReplicaConfig {
+            urls: self
+                .provider
+                .get_string("database.replicas.urls")
+                .map(|urls| {
+                    urls.split(',')
+                        .map(|s| s.trim())
+                        .filter(|s| !s.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or(defaults.urls),
+            max_connections: int("max_connections", defaults.max_connections as u64) as u32,
+            max_lag_seconds: int("max_lag_seconds", defaults.max_lag_seconds),
+            health_check_interval_seconds: int(
+                "health_check_interval_seconds",
+                defaults.health_check_interval_seconds,
+            ),
+        }
+    }
+
     /// Get the etcd configuration.
     ///
     /// Keys:
@@ -315,6 +530,9 @@ impl ConfigAdapter {
     /// - `monitoring.metrics_path` - Metrics endpoint path
     /// - `monitoring.tracing_enabled` - Enable OpenTelemetry tracing
     /// - `monitoring.otlp_endpoint` - OTLP collector endpoint
+    /// - `monitoring.otlp_protocol` - OTLP transport (grpc/http)
+    /// - `monitoring.service_name` - Reported `service.name`
+    /// - `monitoring.trace_sample_ratio` - Trace sampling ratio
     pub fn get_monitoring_config(&self) -> MonitoringConfig {
         MonitoringConfig {
             metrics_enabled: self
@@ -333,6 +551,140 @@ impl ConfigAdapter {
                 .provider
                 .get_string("monitoring.otlp_endpoint")
                 .unwrap_or_default(),
+            otlp_protocol: match self
+                .provider
+                .get_string("monitoring.otlp_protocol")
+                .as_deref()
+            {
+                Some("http") => OtlpProtocol::Http,
+                _ => OtlpProtocol::Grpc,
+            },
+            service_name: self
+                .provider
+                .get_string("monitoring.service_name")
+                .unwrap_or_else(|| "nebula-id".to_string()),
+            trace_sample_ratio: self
+                .provider
+                .get_float("monitoring.trace_sample_ratio")
+                .unwrap_or(1.0),
+            usage: self.get_usage_config(),
+            capacity: self.get_capacity_config(),
+            health: self.get_health_config(),
+        }
+    }
+
+    /// Get the per-tenant usage tracking configuration.
+    ///
+    /// Keys:
+    /// - `monitoring.usage.enabled` - Enable usage tracking
+    /// - `monitoring.usage.max_tracked_keys` - Cardinality bound
+    /// - `monitoring.usage.bucket_secs` - Aggregation bucket width
+    /// - `monitoring.usage.retention_secs` - History retention
+    /// - `monitoring.usage.default_window_secs` - Default query window
+    /// - `monitoring.usage.idle_eviction_secs` - Idle key eviction threshold
+    pub fn get_usage_config(&self) -> UsageConfig {
+        let defaults = UsageConfig::default();
+        UsageConfig {
+            enabled: self
+                .provider
+                .get_bool("monitoring.usage.enabled")
+                .unwrap_or(defaults.enabled),
+            max_tracked_keys: self
+                .provider
+                .get_int("monitoring.usage.max_tracked_keys")
+                .map(|v| v.max(1) as usize)
+                .unwrap_or(defaults.max_tracked_keys),
+            bucket_secs: self
+                .provider
+                .get_int("monitoring.usage.bucket_secs")
+                .map(|v| v.max(1) as u64)
+                .unwrap_or(defaults.bucket_secs),
+            retention_secs: self
+                .provider
+                .get_int("monitoring.usage.retention_secs")
+                .map(|v| v.max(1) as u64)
+                .unwrap_or(defaults.retention_secs),
+            default_window_secs: self
+                .provider
+                .get_int("monitoring.usage.default_window_secs")
+                .map(|v| v.max(1) as u64)
+                .unwrap_or(defaults.default_window_secs),
+            idle_eviction_secs: self
+                .provider
+                .get_int("monitoring.usage.idle_eviction_secs")
+                .map(|v| v.max(1) as u64)
+                .unwrap_or(defaults.idle_eviction_secs),
+        }
+    }
+
+    /// Get the segment exhaustion forecasting configuration.
+    ///
+    /// Keys:
+    /// - `monitoring.capacity.enabled` - Enable forecasting and capacity alerts
+    /// - `monitoring.capacity.forecast_window_secs` - Burn rate measurement window
+    /// - `monitoring.capacity.id_ceiling` - Highest usable ID
+    /// - `monitoring.capacity.warning_threshold_secs` - Warning alert threshold
+    /// - `monitoring.capacity.critical_threshold_secs` - Critical alert threshold
+    /// - `monitoring.capacity.evaluation_interval_secs` - Alert evaluation interval
+    pub fn get_capacity_config(&self) -> CapacityConfig {
+        let defaults = CapacityConfig::default();
+        CapacityConfig {
+            enabled: self
+                .provider
+                .get_bool("monitoring.capacity.enabled")
+                .unwrap_or(defaults.enabled),
+            forecast_window_secs: self
+                .provider
+                .get_int("monitoring.capacity.forecast_window_secs")
+                .map(|v| v.max(1) as u64)
+                .unwrap_or(defaults.forecast_window_secs),
+            id_ceiling: self
+                .provider
+                .get_int("monitoring.capacity.id_ceiling")
+                .map(|v| v.max(1) as u64)
+                .unwrap_or(defaults.id_ceiling),
+            warning_threshold_secs: self
+                .provider
+                .get_int("monitoring.capacity.warning_threshold_secs")
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(defaults.warning_threshold_secs),
+            critical_threshold_secs: self
+                .provider
+                .get_int("monitoring.capacity.critical_threshold_secs")
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(defaults.critical_threshold_secs),
+            evaluation_interval_secs: self
+                .provider
+                .get_int("monitoring.capacity.evaluation_interval_secs")
+                .map(|v| v.max(1) as u64)
+                .unwrap_or(defaults.evaluation_interval_secs),
+        }
+    }
+
+    /// Get the component health / readiness policy configuration.
+    ///
+    /// Keys:
+    /// - `monitoring.health.readiness_required` - Comma-separated components
+    ///   that must not be unhealthy
+    /// - `monitoring.health.readiness_strict` - Comma-separated components
+    ///   that must be fully healthy
+    pub fn get_health_config(&self) -> HealthConfig {
+        let defaults = HealthConfig::default();
+        let components = |key: &str| {
+            self.provider.get_string(key).map(|value| {
+                value
+                    .split(',')
+                    .map(|s| s.trim())
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+            })
+        };
+        HealthConfig {
+            readiness_required: components("monitoring.health.readiness_required")
+                .unwrap_or(defaults.readiness_required),
+            readiness_strict: components("monitoring.health.readiness_strict")
+                .unwrap_or(defaults.readiness_strict),
         }
     }
 
@@ -392,7 +744,14 @@ impl ConfigAdapter {
     /// - `tls.ca_path` - CA certificate path (optional)
     /// - `tls.http_enabled` - Enable TLS for HTTP
     /// - `tls.grpc_enabled` - Enable TLS for gRPC
+    /// - `tls.client_auth` - Client certificate mode (none/optional/required)
+    /// - `tls.crl_paths` - Comma-separated CRL files checked against client certificates
+    /// - `tls.reload_interval_seconds` - Certificate file poll interval (0 = no hot reload)
+    /// - `tls.expiry_warning_days` - Warn when the certificate expires within this many days
+    ///
+    /// `tls.client_identities` is a table array and is only read from the config file.
     pub fn get_tls_config(&self) -> TlsConfig {
+        let defaults = TlsConfig::default();
         TlsConfig {
             enabled: self.provider.get_bool("tls.enabled").unwrap_or(false),
             cert_path: self
@@ -403,7 +762,34 @@ impl ConfigAdapter {
             ca_path: self.provider.get_string("tls.ca_path"),
             http_enabled: self.provider.get_bool("tls.http_enabled").unwrap_or(false),
             grpc_enabled: self.provider.get_bool("tls.grpc_enabled").unwrap_or(false),
-            ..Default::default()
+            client_auth: match self.provider.get_string("tls.client_auth").as_deref() {
+                Some("optional") => ClientAuthMode::Optional,
+                Some("required") => ClientAuthMode::Required,
+                _ => ClientAuthMode::None,
+            },
+            crl_paths: self
+                .provider
+                .get_string("tls.crl_paths")
+                .map(|value| {
+                    value
+                        .split(',')
+                        .map(|s| s.trim())
+                        .filter(|s| !s.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            reload_interval_seconds: self
+                .provider
+                .get_int("tls.reload_interval_seconds")
+                .map(|v| v as u64)
+                .unwrap_or(defaults.reload_interval_seconds),
+            expiry_warning_days: self
+                .provider
+                .get_int("tls.expiry_warning_days")
+                .map(|v| v as u64)
+                .unwrap_or(defaults.expiry_warning_days),
+            ..defaults
         }
     }
 
@@ -420,6 +806,206 @@ impl ConfigAdapter {
         }
     }
 
+    /// Get the audit log configuration.
+    ///
+    /// Keys:
+    /// - `audit.log_path` - Hash-chained audit log file (empty = memory only)
+    /// - `audit.checkpoint_interval` - Records between signed checkpoints
+    /// - `audit.signing_key` - Checkpoint HMAC key (falls back to `NEBULA_AUDIT_SIGNING_KEY`)
+    /// - `audit.max_file_size_mb` / `audit.rotation_interval_secs` - Rotation triggers (0 = off)
+    /// - `audit.retention_days` - Rotated file retention (0 = keep forever)
+    /// - `audit.compress_rotated` - Gzip rotated files
+    /// - `audit.channel_capacity` - Writer queue capacity
+    /// - `audit.overflow_policy` - Full queue policy (block/drop_oldest/spill)
+    /// - `audit.nonblocking_generation` - Drop instead of wait for ID generation events under block
+    /// - `audit.database_enabled` - Persist events to the `audit_events` table
+    /// - `audit.database_batch_size` / `audit.database_flush_interval_ms` - Database write batching
+    pub fn get_audit_config(&self) -> AuditConfig {
+        let defaults = AuditConfig::default();
+        AuditConfig {
+            log_path: self
+                .provider
+                .get_string("audit.log_path")
+                .unwrap_or(defaults.log_path),
+            checkpoint_interval: self
+                .provider
+                .get_int("audit.checkpoint_interval")
+                .map(|v| v as u64)
+                .unwrap_or(defaults.checkpoint_interval),
+            signing_key: self
+                .provider
+                .get_string("audit.signing_key")
+                .or_else(|| std::env::var("NEBULA_AUDIT_SIGNING_KEY").ok())
+                .unwrap_or(defaults.signing_key),
+            max_file_size_mb: self
+                .provider
+                .get_int("audit.max_file_size_mb")
+                .map(|v| v as u64)
+                .unwrap_or(defaults.max_file_size_mb),
+            rotation_interval_secs: self
+                .provider
+                .get_int("audit.rotation_interval_secs")
+                .map(|v| v as u64)
+                .unwrap_or(defaults.rotation_interval_secs),
+            retention_days: self
+                .provider
+                .get_int("audit.retention_days")
+                .map(|v| v as u64)
+                .unwrap_or(defaults.retention_days),
+            compress_rotated: self
+                .provider
+                .get_bool("audit.compress_rotated")
+                .unwrap_or(defaults.compress_rotated),
+            channel_capacity: self
+                .provider
+                .get_int("audit.channel_capacity")
+                .map(|v| v as usize)
+                .unwrap_or(defaults.channel_capacity),
+            overflow_policy: match self.provider.get_string("audit.overflow_policy").as_deref() {
+                Some("drop_oldest") => AuditOverflowPolicy::DropOldest,
+                Some("spill") => AuditOverflowPolicy::Spill,
+                Some("block") => AuditOverflowPolicy::Block,
+                _ => defaults.overflow_policy,
+            },
+            nonblocking_generation: self
+                .provider
+                .get_bool("audit.nonblocking_generation")
+                .unwrap_or(defaults.nonblocking_generation),
+            database_enabled: self
+                .provider
+                .get_bool("audit.database_enabled")
+                .unwrap_or(defaults.database_enabled),
+            database_batch_size: self
+                .provider
+                .get_int("audit.database_batch_size")
+                .map(|v| v as usize)
+                .unwrap_or(defaults.database_batch_size),
+            database_flush_interval_ms: self
+                .provider
+                .get_int("audit.database_flush_interval_ms")
+                .map(|v| v as u64)
+                .unwrap_or(defaults.database_flush_interval_ms),
+            sinks: self.get_audit_sinks_config(),
+        }
+    }
+
+    /// Get the external audit sink configuration.
+    ///
+    /// Keys (under `audit.sinks.syslog`, `audit.sinks.webhook` and
+    /// `audit.sinks.file`):
+    /// - `enabled` - Enable the sink
+    /// - `event_types` / `results` - Comma-separated filters (empty = all)
+    /// - `channel_capacity` / `overflow_policy` - Queue size and drop policy
+    ///   (drop_newest/drop_oldest)
+    /// - syslog: `protocol` (udp/tcp/tls), `address`, `ca_cert_path`,
+    ///   `facility`, `app_name`
+    /// - webhook: `url`, `auth_header`, `batch_size`, `flush_interval_ms`,
+    ///   `timeout_ms`, `max_retries`, `retry_backoff_ms`
+    /// - file: `path`, `max_file_size_mb`, `rotation_interval_secs`,
+    ///   `retention_days`, `compress_rotated`
+    pub fn get_audit_sinks_config(&self) -> AuditSinksConfig {
+        let defaults = AuditSinksConfig::default();
+        let string = |key: &str, default: String| self.provider.get_string(key).unwrap_or(default);
+        let int = |key: &str, default: u64| {
+            self.provider
+                .get_int(key)
+                .map(|v| v.max(0) as u64)
+                .unwrap_or(default)
+        };
+        let boolean = |key: &str, default: bool| self.provider.get_bool(key).unwrap_or(default);
+        let delivery = |prefix: &str, defaults: AuditSinkDelivery| {
+            let list = |key: &str| {
+                self.provider
+                    .get_string(&format!("{}.{}", prefix, key))
+                    .map(|value| {
+                        value
+                            .split(',')
+                            .map(|s| s.trim())
+                            .filter(|s| !s.is_empty())
+                            .map(String::from)
+                            .collect::<Vec<_>>()
+                    })
+            };
+            AuditSinkDelivery {
+                event_types: list("event_types").unwrap_or(defaults.event_types),
+                results: list("results").unwrap_or(defaults.results),
+                channel_capacity: int(
+                    &format!("{}.channel_capacity", prefix),
+                    defaults.channel_capacity as u64,
+                ) as usize,
+                overflow_policy: match self
+                    .provider
+                    .get_string(&format!("{}.overflow_policy", prefix))
+                    .as_deref()
+                {
+                    Some("drop_oldest") => AuditSinkOverflowPolicy::DropOldest,
+                    Some("drop_newest") => AuditSinkOverflowPolicy::DropNewest,
+                    _ => defaults.overflow_policy,
+                },
+            }
+        };
+
+        let syslog = defaults.syslog;
+        let webhook = defaults.webhook;
+        let file = defaults.file;
+        AuditSinksConfig {
+            syslog: SyslogSinkConfig {
+                enabled: boolean("audit.sinks.syslog.enabled", syslog.enabled),
+                protocol: match self
+                    .provider
+                    .get_string("audit.sinks.syslog.protocol")
+                    .as_deref()
+                {
+                    Some("tcp") => SyslogProtocol::Tcp,
+                    Some("tls") => SyslogProtocol::Tls,
+                    Some("udp") => SyslogProtocol::Udp,
+                    _ => syslog.protocol,
+                },
+                address: string("audit.sinks.syslog.address", syslog.address),
+                ca_cert_path: string("audit.sinks.syslog.ca_cert_path", syslog.ca_cert_path),
+                facility: int("audit.sinks.syslog.facility", syslog.facility as u64).min(23) as u8,
+                app_name: string("audit.sinks.syslog.app_name", syslog.app_name),
+                delivery: delivery("audit.sinks.syslog", syslog.delivery),
+            },
+            webhook: WebhookSinkConfig {
+                enabled: boolean("audit.sinks.webhook.enabled", webhook.enabled),
+                url: string("audit.sinks.webhook.url", webhook.url),
+                auth_header: string("audit.sinks.webhook.auth_header", webhook.auth_header),
+                batch_size: int("audit.sinks.webhook.batch_size", webhook.batch_size as u64)
+                    as usize,
+                flush_interval_ms: int(
+                    "audit.sinks.webhook.flush_interval_ms",
+                    webhook.flush_interval_ms,
+                ),
+                timeout_ms: int("audit.sinks.webhook.timeout_ms", webhook.timeout_ms),
+                max_retries: int(
+                    "audit.sinks.webhook.max_retries",
+                    webhook.max_retries as u64,
+                ) as u32,
+                retry_backoff_ms: int(
+                    "audit.sinks.webhook.retry_backoff_ms",
+                    webhook.retry_backoff_ms,
+                ),
+                delivery: delivery("audit.sinks.webhook", webhook.delivery),
+            },
+            file: FileSinkConfig {
+                enabled: boolean("audit.sinks.file.enabled", file.enabled),
+                path: string("audit.sinks.file.path", file.path),
+                max_file_size_mb: int("audit.sinks.file.max_file_size_mb", file.max_file_size_mb),
+                rotation_interval_secs: int(
+                    "audit.sinks.file.rotation_interval_secs",
+                    file.rotation_interval_secs,
+                ),
+                retention_days: int("audit.sinks.file.retention_days", file.retention_days),
+                compress_rotated: boolean(
+                    "audit.sinks.file.compress_rotated",
+                    file.compress_rotated,
+                ),
+                delivery: delivery("audit.sinks.file", file.delivery),
+            },
+        }
+    }
+
     /// Get Redis configuration.
     ///
     /// Keys:
@@ -468,6 +1054,7 @@ impl ConfigAdapter {
             rate_limit: self.get_rate_limit_config(),
             tls: self.get_tls_config(),
             batch_generate: self.get_batch_generate_config(),
+            audit: self.get_audit_config(),
         }
     }
 
@@ -838,6 +1425,88 @@ mod tests {
         restore_env("NEBULA_API_KEY_SALT", saved_salt);
     }
 
+    #[test]
+    fn test_get_jwt_config_reads_nested_keys() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_bool("auth.jwt.enabled", true)
+                .with_string("auth.jwt.issuer", "https://idp.internal")
+                .with_string("auth.jwt.jwks_path", "/etc/nebula/jwks.json")
+                .with_string("auth.jwt.role_claim", "realm_access.roles")
+                .with_int("auth.jwt.leeway_seconds", 5),
+        );
+        let config = ConfigAdapter::new(provider).get_auth_config().jwt;
+
+        assert!(config.enabled);
+        assert_eq!(config.issuer, "https://idp.internal");
+        assert_eq!(config.jwks_path, "/etc/nebula/jwks.json");
+        assert_eq!(config.role_claim, "realm_access.roles");
+        assert_eq!(config.leeway_seconds, 5);
+        assert_eq!(config.scopes_claim, "scope");
+        assert!(!config.missing_scopes_unrestricted);
+        assert_eq!(config.jwks_refresh_seconds, 300);
+    }
+
+    #[test]
+    fn test_get_api_key_lifecycle_config_reads_nested_keys() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_bool("auth.lifecycle.enabled", true)
+                .with_bool("auth.lifecycle.disable_expired", false)
+                .with_int("auth.lifecycle.disable_unused_days", 30)
+                .with_bool("auth.lifecycle.auto_rotate", true)
+                .with_string(
+                    "auth.lifecycle.alert_webhook_url",
+                    "https://hooks.internal/keys",
+                ),
+        );
+        let config = ConfigAdapter::new(provider).get_auth_config().lifecycle;
+
+        assert!(config.enabled);
+        assert!(!config.disable_expired);
+        assert_eq!(config.disable_unused_days, 30);
+        assert!(config.auto_rotate);
+        assert_eq!(config.alert_webhook_url, "https://hooks.internal/keys");
+        assert_eq!(config.check_interval_seconds, 3600);
+        assert_eq!(config.expiry_warning_days, 14);
+    }
+
+    #[test]
+    fn test_get_credential_cache_config_reads_nested_keys() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_bool("auth.credential_cache.enabled", false)
+                .with_int("auth.credential_cache.poll_interval_ms", 250)
+                .with_bool("auth.credential_cache.etcd_watch", false),
+        );
+        let config = ConfigAdapter::new(provider)
+            .get_auth_config()
+            .credential_cache;
+
+        assert!(!config.enabled);
+        assert_eq!(config.poll_interval_ms, 250);
+        assert!(!config.etcd_watch);
+        assert_eq!(config.ttl_seconds, 60);
+        assert_eq!(config.max_entries, 10_000);
+    }
+
+    #[test]
+    fn test_get_ban_config_reads_nested_keys() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_int("auth.bans.failure_threshold", 5)
+                .with_int("auth.bans.max_ban_seconds", 3600)
+                .with_bool("auth.bans.enabled", false),
+        );
+        let config = ConfigAdapter::new(provider).get_auth_config().bans;
+
+        assert!(!config.enabled);
+        assert_eq!(config.failure_threshold, 5);
+        assert_eq!(config.max_ban_seconds, 3600);
+        assert_eq!(config.base_ban_seconds, 300);
+        assert_eq!(config.sync_interval_seconds, 5);
+    }
+
     #[test]
     fn test_get_auth_config_env_fallback_for_salt() {
         let _guard = lock_env();
@@ -884,6 +1553,7 @@ mod tests {
         assert_eq!(config.min_connections, 10);
         assert_eq!(config.acquire_timeout_seconds, 30);
         assert_eq!(config.idle_timeout_seconds, 300);
+        assert_eq!(config.segment_allocation, SegmentAllocationMode::Atomic);
 
         restore_env("DATABASE_URL", saved_url);
     }
@@ -900,7 +1570,8 @@ mod tests {
                 .with_int("database.max_connections", 50)
                 .with_int("database.min_connections", 5)
                 .with_int("database.acquire_timeout_seconds", 60)
-                .with_int("database.idle_timeout_seconds", 120),
+                .with_int("database.idle_timeout_seconds", 120)
+                .with_string("database.segment_allocation", "locked"),
         );
         let adapter = ConfigAdapter::new(provider);
         let config = adapter.get_database_config();
@@ -910,6 +1581,7 @@ mod tests {
         assert_eq!(config.min_connections, 5);
         assert_eq!(config.acquire_timeout_seconds, 60);
         assert_eq!(config.idle_timeout_seconds, 120);
+        assert_eq!(config.segment_allocation, SegmentAllocationMode::Locked);
 
         restore_env("DATABASE_URL", saved_url);
     }
@@ -945,6 +1617,30 @@ mod tests {
         restore_env("DATABASE_URL", saved_url);
     }
 
+    #[test]
+    fn test_get_replica_config_reads_nested_keys() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_string(
+                    "database.replicas.urls",
+                    "postgresql://replica-a/db, postgresql://replica-b/db",
+                )
+                .with_int("database.replicas.max_lag_seconds", 2),
+        );
+        let config = ConfigAdapter::new(provider).get_database_config().replicas;
+
+        assert_eq!(
+            config.urls,
+            vec![
+                "postgresql://replica-a/db".to_string(),
+                "postgresql://replica-b/db".to_string()
+            ]
+        );
+        assert_eq!(config.max_lag_seconds, 2);
+        assert_eq!(config.max_connections, 20);
+        assert_eq!(config.health_check_interval_seconds, 10);
+    }
+
     // ===== get_etcd_config =====
 
     #[test]
@@ -1082,6 +1778,68 @@ mod tests {
         assert_eq!(config.metrics_path, "/metrics");
         assert!(!config.tracing_enabled);
         assert_eq!(config.otlp_endpoint, "http://custom:4317");
+        assert_eq!(config.otlp_protocol, OtlpProtocol::Grpc);
+    }
+
+    #[test]
+    fn test_get_monitoring_config_otlp_http_protocol() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_string("monitoring.otlp_protocol", "http")
+                .with_string("monitoring.service_name", "nebula-id-canary")
+                .with_float("monitoring.trace_sample_ratio", 0.25),
+        );
+        let adapter = ConfigAdapter::new(provider);
+        let config = adapter.get_monitoring_config();
+        assert_eq!(config.otlp_protocol, OtlpProtocol::Http);
+        assert_eq!(config.service_name, "nebula-id-canary");
+        assert_eq!(config.trace_sample_ratio, 0.25);
+    }
+
+    #[test]
+    fn test_get_usage_config_reads_and_clamps_values() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_bool("monitoring.usage.enabled", false)
+                .with_int("monitoring.usage.max_tracked_keys", 50)
+                .with_int("monitoring.usage.bucket_secs", 0),
+        );
+        let adapter = ConfigAdapter::new(provider);
+        let config = adapter.get_usage_config();
+        assert!(!config.enabled);
+        assert_eq!(config.max_tracked_keys, 50);
+        assert_eq!(config.bucket_secs, 1);
+        assert_eq!(config.retention_secs, UsageConfig::default().retention_secs);
+    }
+
+    #[test]
+    fn test_get_capacity_config_defaults_and_overrides() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_int("monitoring.capacity.id_ceiling", 9_007_199_254_740_991)
+                .with_int("monitoring.capacity.critical_threshold_secs", 3600),
+        );
+        let adapter = ConfigAdapter::new(provider);
+        let config = adapter.get_capacity_config();
+        assert!(config.enabled);
+        assert_eq!(config.id_ceiling, 9_007_199_254_740_991);
+        assert_eq!(config.critical_threshold_secs, 3600);
+        assert_eq!(
+            config.warning_threshold_secs,
+            CapacityConfig::default().warning_threshold_secs
+        );
+    }
+
+    #[test]
+    fn test_get_health_config_parses_component_lists() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_string("monitoring.health.readiness_strict", "degradation, etcd"),
+        );
+        let adapter = ConfigAdapter::new(provider);
+        let config = adapter.get_health_config();
+        assert_eq!(config.readiness_required, vec!["database", "cache"]);
+        assert_eq!(config.readiness_strict, vec!["degradation", "etcd"]);
     }
 
     // ===== get_logging_config =====
@@ -1207,6 +1965,26 @@ mod tests {
         assert_eq!(config.ca_path, None);
         assert!(config.http_enabled);
         assert!(!config.grpc_enabled);
+        assert_eq!(config.client_auth, ClientAuthMode::None);
+    }
+
+    #[test]
+    fn test_get_tls_config_reads_client_auth_and_crls() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_string("tls.client_auth", "required")
+                .with_string(
+                    "tls.crl_paths",
+                    "/etc/ssl/ca.crl, /etc/ssl/intermediate.crl,",
+                ),
+        );
+        let config = ConfigAdapter::new(provider).get_tls_config();
+        assert_eq!(config.client_auth, ClientAuthMode::Required);
+        assert_eq!(config.reload_interval_seconds, 30);
+        assert_eq!(
+            config.crl_paths,
+            vec!["/etc/ssl/ca.crl", "/etc/ssl/intermediate.crl"]
+        );
     }
 
     // ===== get_batch_generate_config =====
@@ -1228,6 +2006,104 @@ mod tests {
         assert_eq!(config.max_batch_size, 500);
     }
 
+    // ===== get_audit_config =====
+
+    #[test]
+    fn test_get_audit_config_custom() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_string("audit.log_path", "/var/log/nebulaid/audit.log")
+                .with_int("audit.checkpoint_interval", 50)
+                .with_string("audit.signing_key", "k"),
+        );
+        let adapter = ConfigAdapter::new(provider);
+        let config = adapter.get_audit_config();
+        assert_eq!(config.log_path, "/var/log/nebulaid/audit.log");
+        assert_eq!(config.checkpoint_interval, 50);
+        assert_eq!(config.signing_key, "k");
+        assert_eq!(config.overflow_policy, AuditOverflowPolicy::Spill);
+    }
+
+    #[test]
+    fn test_get_audit_config_rotation_and_overflow() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_int("audit.max_file_size_mb", 10)
+                .with_int("audit.rotation_interval_secs", 0)
+                .with_int("audit.retention_days", 7)
+                .with_int("audit.channel_capacity", 256)
+                .with_string("audit.overflow_policy", "block")
+                .with_bool("audit.nonblocking_generation", true),
+        );
+        let adapter = ConfigAdapter::new(provider);
+        let config = adapter.get_audit_config();
+        assert_eq!(config.max_file_size_mb, 10);
+        assert_eq!(config.rotation_interval_secs, 0);
+        assert_eq!(config.retention_days, 7);
+        assert!(config.compress_rotated);
+        assert_eq!(config.channel_capacity, 256);
+        assert_eq!(config.overflow_policy, AuditOverflowPolicy::Block);
+        assert!(config.nonblocking_generation);
+    }
+
+    #[test]
+    fn test_get_audit_config_database_persistence() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_bool("audit.database_enabled", false)
+                .with_int("audit.database_batch_size", 500)
+                .with_int("audit.database_flush_interval_ms", 250),
+        );
+        let adapter = ConfigAdapter::new(provider);
+        let config = adapter.get_audit_config();
+        assert!(!config.database_enabled);
+        assert_eq!(config.database_batch_size, 500);
+        assert_eq!(config.database_flush_interval_ms, 250);
+    }
+
+    #[test]
+    fn test_get_audit_sinks_config() {
+        let provider = Arc::new(
+            MockConfigProvider::new()
+                .with_bool("audit.sinks.syslog.enabled", true)
+                .with_string("audit.sinks.syslog.protocol", "tls")
+                .with_string("audit.sinks.syslog.address", "siem.internal:6514")
+                .with_string(
+                    "audit.sinks.syslog.event_types",
+                    "ConfigChange, ApiKeyCreated",
+                )
+                .with_string("audit.sinks.syslog.overflow_policy", "drop_oldest")
+                .with_bool("audit.sinks.webhook.enabled", true)
+                .with_string("audit.sinks.webhook.url", "https://siem.internal/ingest")
+                .with_int("audit.sinks.webhook.max_retries", 5)
+                .with_string("audit.sinks.file.results", "Failure"),
+        );
+        let adapter = ConfigAdapter::new(provider);
+        let sinks = adapter.get_audit_config().sinks;
+
+        assert!(sinks.syslog.enabled);
+        assert_eq!(sinks.syslog.protocol, SyslogProtocol::Tls);
+        assert_eq!(sinks.syslog.address, "siem.internal:6514");
+        assert_eq!(
+            sinks.syslog.delivery.event_types,
+            vec!["ConfigChange", "ApiKeyCreated"]
+        );
+        assert_eq!(
+            sinks.syslog.delivery.overflow_policy,
+            AuditSinkOverflowPolicy::DropOldest
+        );
+        assert_eq!(sinks.syslog.facility, 13);
+        assert!(sinks.webhook.enabled);
+        assert_eq!(sinks.webhook.max_retries, 5);
+        assert_eq!(sinks.webhook.batch_size, 100);
+        assert!(!sinks.file.enabled);
+        assert_eq!(sinks.file.delivery.results, vec!["Failure"]);
+        assert_eq!(
+            sinks.file.delivery.overflow_policy,
+            AuditSinkOverflowPolicy::DropNewest
+        );
+    }
+
     // ===== get_redis_config =====
 
     #[test]
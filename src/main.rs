@@ -13,15 +13,28 @@
 // limitations under the License.
 
 use nebulaid::core::algorithm::AlgorithmRouter;
-use nebulaid::core::config::Config;
+#[cfg(feature = "etcd")]
+use nebulaid::core::auth::EtcdRevocationNotifier;
+use nebulaid::core::auth::{
+    ApiKeyLifecycle, BanManager, CachedApiKeyRepository, ClientCertMapper, CredentialCache,
+    JwtValidator, RevocationSync,
+};
+use nebulaid::core::config::{ClientAuthMode, Config, JwtConfig, SegmentAllocationMode};
 #[cfg(feature = "etcd")]
 use nebulaid::core::coordinator::{EtcdClientWrapper, EtcdClusterHealthMonitor};
 use nebulaid::core::database::{self, ApiKeyRepository};
-use nebulaid::core::types::Result;
-use nebulaid::server::audit::AuditLogger;
+use nebulaid::core::monitoring::{
+    capacity_alert_rules, AlertManager, AlertNotificationSender, AlertingConfig,
+    CapacityAlertEvaluator, CapacityForecaster, ChannelType, NotificationChannel, UsageTracker,
+};
+use nebulaid::core::types::{GlobalMetrics, Result};
+use nebulaid::server::audit::{
+    verify_audit_log, AuditFileOptions, AuditLogger, AuditStoreOptions, CheckpointSigner,
+};
 use nebulaid::server::config::hot_reload::HotReloadConfig;
 use nebulaid::server::config::management::{ConfigManagementService, ConfigManager};
-use nebulaid::server::config::tls::TlsManager;
+use nebulaid::server::config::tls::{TlsConnectInfo, TlsListener, TlsManager};
+use nebulaid::server::generation_guard::{GenerationGuard, RepositoryRegistry};
 use nebulaid::server::grpc::GrpcServer;
 use nebulaid::server::handlers::ApiHandlers;
 use nebulaid::server::middleware::size_limit::create_size_limit_middleware;
@@ -34,6 +47,7 @@ use sdforge::tonic::transport::Server;
 use std::env;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tracing::warn;
 use tracing::{error, info};
@@ -116,6 +130,8 @@ async fn load_api_keys(
                 expires_at: None,
                 key_secret: Some(secret.to_string()),
                 key_id: admin_key_id_from_env,
+                scopes: None,
+                request_signing: false,
             };
 
             match repo.create_api_key(&request).await {
@@ -137,6 +153,7 @@ async fn load_api_keys(
 
                 let role = match first_key.role.to_lowercase().as_str() {
                     "admin" => ApiKeyRole::Admin,
+                    "workspace_admin" => ApiKeyRole::WorkspaceAdmin,
                     _ => ApiKeyRole::User,
                 };
 
@@ -153,6 +170,8 @@ async fn load_api_keys(
                     expires_at: None,
                     key_secret: Some(first_key.key_secret.clone()),
                     key_id: Some(first_key.key_id.clone()),
+                    scopes: None,
+                    request_signing: false,
                 };
 
                 match repo.create_api_key(&request).await {
@@ -198,6 +217,8 @@ async fn load_api_keys(
                         expires_at: None,
                         key_secret: None,
                         key_id: None,
+                        scopes: None,
+                        request_signing: false,
                     };
                     match repo.create_api_key(&admin_request).await {
                         Ok(key) => {
@@ -259,6 +280,8 @@ async fn load_api_keys(
                 expires_at: None,
                 key_secret: None,
                 key_id: None,
+                scopes: None,
+                request_signing: false,
             };
             match repo.create_api_key(&test_request).await {
                 Ok(key) => {
@@ -287,6 +310,7 @@ async fn create_id_generator(
     config: &Config,
     audit_logger: Arc<AuditLogger>,
     etcd_health_monitor: Option<Arc<EtcdClusterHealthMonitor>>,
+    usage_tracker: Arc<UsageTracker>,
 ) -> Result<Arc<AlgorithmRouter>> {
     info!("{}", t!("log.main.id_generators_initializing"));
 
@@ -297,7 +321,9 @@ async fn create_id_generator(
     let cpu_monitor = Arc::new(nebulaid::core::algorithm::CpuMonitor::new());
     let router = AlgorithmRouter::new(config.clone(), Some(audit_logger_for_core));
 
-    let router = router.with_cpu_monitor(cpu_monitor);
+    let router = router
+        .with_cpu_monitor(cpu_monitor)
+        .with_usage_tracker(usage_tracker);
     let router = if let Some(monitor) = etcd_health_monitor {
         Arc::new(router.with_etcd_health_monitor(monitor))
     } else {
@@ -315,6 +341,7 @@ async fn create_id_generator(
     config: &Config,
     audit_logger: Arc<AuditLogger>,
     _etcd_health_monitor: Option<Arc<()>>,
+    usage_tracker: Arc<UsageTracker>,
 ) -> Result<Arc<AlgorithmRouter>> {
     info!(
         "{}",
@@ -327,7 +354,9 @@ async fn create_id_generator(
     // Create CPU monitor
     let cpu_monitor = Arc::new(nebulaid::core::algorithm::CpuMonitor::new());
     let router = AlgorithmRouter::new(config.clone(), Some(audit_logger_for_core));
-    let router = router.with_cpu_monitor(cpu_monitor);
+    let router = router
+        .with_cpu_monitor(cpu_monitor)
+        .with_usage_tracker(usage_tracker);
     let router = Arc::new(router);
 
     router.initialize().await?;
@@ -352,30 +381,45 @@ async fn start_http_server(
         .layer(create_size_limit_middleware())
         .merge(merge_sdforge_routes(sdforge::axum::Router::new()));
 
-    // 检查是否启用 HTTPS (暂时回退到普通 HTTP，TLS 功能待完善)
-    if let Some(ref tls) = tls_manager {
-        if tls.is_http_enabled() {
-            info!("{}", t!("log.main.https_enabled_http_fallback"));
-        }
-    }
-
-    // 回退到普通 HTTP
     info!("{}", t!("log.main.starting_http_server", addr = addr));
     let listener = TcpListener::bind(addr).await?;
 
-    sdforge::axum::serve(listener, router)
-        .with_graceful_shutdown(async {
-            tokio::signal::ctrl_c().await.ok();
-            info!("{}", t!("log.main.shutting_down_http_server"));
-        })
+    let acceptor = tls_manager
+        .as_ref()
+        .filter(|tls| tls.is_http_enabled())
+        .and_then(|tls| {
+            tls.http_acceptor()
+                .cloned()
+                .map(|a| (a, tls.http_client_auth()))
+        });
+    if let Some((acceptor, client_auth)) = acceptor {
+        info!(event = "https_enabled", client_auth = %client_auth, "Serving HTTPS");
+        // 客户端证书经 ConnectInfo<TlsConnectInfo> 交给认证中间件
+        sdforge::axum::serve(
+            TlsListener::new(listener, acceptor)?,
+            router.into_make_service_with_connect_info::<TlsConnectInfo>(),
+        )
+        .with_graceful_shutdown(http_shutdown_signal())
         .await?;
+    } else {
+        sdforge::axum::serve(listener, router)
+            .with_graceful_shutdown(http_shutdown_signal())
+            .await?;
+    }
 
     Ok(())
 }
 
+async fn http_shutdown_signal() {
+    tokio::signal::ctrl_c().await.ok();
+    info!("{}", t!("log.main.shutting_down_http_server"));
+}
+
 async fn start_grpc_server(
     config: ServerConfig,
     handlers: Arc<ApiHandlers>,
+    auth: Arc<ApiKeyAuth>,
+    audit_logger: Arc<AuditLogger>,
     tls_manager: Option<Arc<TlsManager>>,
 ) -> Result<()> {
     let grpc_addr = SocketAddr::from(([0, 0, 0, 0], config.grpc_port));
@@ -385,37 +429,38 @@ async fn start_grpc_server(
         t!("log.main.configured_grpc_port", port = config.grpc_port)
     );
 
-    let grpc_server = GrpcServer::new(handlers);
+    let grpc_server = GrpcServer::new(handlers).with_auth(auth, audit_logger);
 
     let shutdown = async {
         tokio::signal::ctrl_c().await.ok();
         info!("{}", t!("log.main.shutting_down_grpc_server"));
     };
 
-    let mut server_builder = Server::builder();
-
-    if let Some(ref tls) = tls_manager {
-        if tls.is_grpc_enabled() {
-            info!("{}", t!("log.main.grpc_tls_enabled"));
-            if let Some(grpc_tls_config) = tls.grpc_tls_config() {
-                let config = grpc_tls_config.as_ref().clone();
-                server_builder = server_builder.tls_config(config).map_err(|e| {
-                    nebulaid::core::types::CoreError::InternalError(format!(
-                        "TLS config error: {}",
-                        e
-                    ))
-                })?;
-            }
-        }
-    }
+    let router = Server::builder().add_service(NebulaIdServiceServer::new(grpc_server));
 
-    server_builder
-        .add_service(NebulaIdServiceServer::new(grpc_server))
-        .serve_with_shutdown(grpc_addr, shutdown)
-        .await
-        .map_err(|e| {
-            nebulaid::core::types::CoreError::InternalError(format!("gRPC server error: {}", e))
-        })?;
+    let acceptor = tls_manager
+        .as_ref()
+        .filter(|tls| tls.is_grpc_enabled())
+        .and_then(|tls| {
+            tls.grpc_acceptor()
+                .cloned()
+                .map(|a| (a, tls.grpc_client_auth()))
+        });
+    let served = if let Some((acceptor, client_auth)) = acceptor {
+        info!("{}", t!("log.main.grpc_tls_enabled"));
+        info!(event = "grpc_tls_client_auth", client_auth = %client_auth, "gRPC client certificate mode");
+        // 握手在 TlsListener 中完成，tonic 从 TLS 流读取客户端证书（Request::peer_certs）
+        let incoming =
+            TlsListener::new(TcpListener::bind(grpc_addr).await?, acceptor)?.into_stream();
+        router
+            .serve_with_incoming_shutdown(incoming, shutdown)
+            .await
+    } else {
+        router.serve_with_shutdown(grpc_addr, shutdown).await
+    };
+    served.map_err(|e| {
+        nebulaid::core::types::CoreError::InternalError(format!("gRPC server error: {}", e))
+    })?;
 
     Ok(())
 }
@@ -454,22 +499,571 @@ fn build_api_handlers(
     id_generator: Arc<nebulaid::core::algorithm::AlgorithmRouter>,
     cs: Arc<dyn ConfigManagementService>,
     repo: Arc<dyn ApiKeyRepository>,
-    grace_period_seconds: u64,
+    auth: &nebulaid::core::config::AuthConfig,
+    usage_tracker: Arc<UsageTracker>,
+    capacity_forecaster: Arc<CapacityForecaster>,
 ) -> ApiHandlers {
     ApiHandlers::with_api_key_repository(id_generator, cs, repo)
-        .with_key_rotation_grace_period(grace_period_seconds)
+        .with_key_rotation_grace_period(auth.key_rotation_grace_period_seconds)
+        .with_max_api_keys_per_workspace(auth.max_api_keys_per_workspace)
+        .with_usage_tracker(usage_tracker)
+        .with_capacity_forecaster(capacity_forecaster)
+}
+
+/// 定期删除过期的签名请求 nonce 登记（`request_nonces`）；间隔为 nonce 的保留时长
+fn spawn_nonce_pruning(repo: Arc<dyn ApiKeyRepository>, max_skew_seconds: u64) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            max_skew_seconds.saturating_mul(2).max(1),
+        ));
+        loop {
+            interval.tick().await;
+            if let Err(e) = repo
+                .prune_request_nonces(chrono::Utc::now().naive_utc())
+                .await
+            {
+                warn!(
+                    event = "nonce_prune_failed",
+                    error = %e,
+                    "Failed to prune expired request nonces"
+                );
+            }
+        }
+    });
+}
+
+/// 启动号段容量告警：按 `evaluation_interval_secs` 周期采样 `nebula_segments`
+/// 并对内置耗尽规则求值，告警经日志通道输出（target = "alerts"）。
+fn spawn_capacity_alerts(forecaster: Arc<CapacityForecaster>) {
+    let config = forecaster.config().clone();
+    let alerting = AlertingConfig {
+        enabled: true,
+        evaluation_interval_ms: config.evaluation_interval_secs.saturating_mul(1000),
+        rules: capacity_alert_rules(&config),
+        channels: vec![NotificationChannel::default()],
+        global_labels: Default::default(),
+    };
+    let sender = Arc::new(AlertNotificationSender::new(alerting.channels.clone()));
+    let (manager, _alerts_rx) = AlertManager::new(alerting, Arc::new(GlobalMetrics::new()), sender);
+    let manager = manager.with_evaluator(Arc::new(CapacityAlertEvaluator::new(forecaster.clone())));
+
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(config.evaluation_interval_secs.max(1)));
+        loop {
+            interval.tick().await;
+            if let Err(e) = forecaster.sample().await {
+                warn!(
+                    event = "capacity_sample_failed",
+                    error = %e,
+                    "Failed to sample segment capacity"
+                );
+            }
+            manager.evaluate_once().await;
+        }
+    });
+}
+
+/// 启动 API key 生命周期检查；告警经日志通道输出，配置了
+/// `auth.lifecycle.alert_webhook_url` 时同时 POST 到该地址
+fn init_api_key_lifecycle(
+    config: &Config,
+    repo: Arc<dyn ApiKeyRepository>,
+    audit_logger: Arc<AuditLogger>,
+) -> Option<Arc<ApiKeyLifecycle>> {
+    let lifecycle_config = &config.auth.lifecycle;
+    if !lifecycle_config.enabled {
+        return None;
+    }
+
+    let mut channels = vec![NotificationChannel::default()];
+    if !lifecycle_config.alert_webhook_url.is_empty() {
+        channels.push(NotificationChannel {
+            name: "api_key_lifecycle_webhook".to_string(),
+            channel_type: ChannelType::Webhook,
+            config: [(
+                "url".to_string(),
+                lifecycle_config.alert_webhook_url.clone(),
+            )]
+            .into(),
+            enabled: true,
+        });
+    }
+
+    let lifecycle = Arc::new(
+        ApiKeyLifecycle::new(repo, lifecycle_config.clone())
+            .with_audit_logger(audit_logger as Arc<dyn nebulaid::core::algorithm::AuditLogger>)
+            .with_alert_sender(Arc::new(AlertNotificationSender::new(channels)))
+            .with_rotation_grace_period(config.auth.key_rotation_grace_period_seconds),
+    );
+    lifecycle.spawn();
+    info!(
+        event = "api_key_lifecycle_started",
+        interval_secs = lifecycle_config.check_interval_seconds,
+        "API key lifecycle checks enabled"
+    );
+    Some(lifecycle)
+}
+
+/// 创建认证失败封禁管理器。有数据库时封禁持久化并定期同步（含其他实例的
+/// 封禁与解封），否则只在进程内生效
+async fn init_ban_manager(
+    config: &Config,
+    repository: &Option<Arc<database::SeaOrmRepository>>,
+    audit_logger: Arc<AuditLogger>,
+) -> Arc<BanManager> {
+    let ban_config = &config.auth.bans;
+    let mut manager = BanManager::new(ban_config.clone())
+        .with_audit_logger(audit_logger as Arc<dyn nebulaid::core::algorithm::AuditLogger>);
+    if let Some(repo) = repository {
+        manager = manager.with_repository(repo.clone());
+    }
+    let manager = Arc::new(manager);
+    if !ban_config.enabled || repository.is_none() {
+        return manager;
+    }
+
+    if let Err(e) = manager.sync().await {
+        warn!(
+            event = "auth_ban_sync_failed",
+            error = %e,
+            "Failed to load active bans, retrying in the background"
+        );
+    }
+    manager.spawn_sync();
+    info!(
+        event = "auth_bans_enabled",
+        failure_threshold = ban_config.failure_threshold,
+        sync_interval_secs = ban_config.sync_interval_seconds,
+        "Persistent auth-failure bans enabled"
+    );
+    manager
+}
+
+/// 创建 locked 号段分配使用的分布式锁（M8 修复：避免无锁降级产生重复 ID）。
+///
+/// etcd feature 构建下，若 etcd 端点已配置，先创建 EtcdClientWrapper，再用它构造
+/// EtcdDistributedLock；否则或任一步失败时使用 LocalDistributedLock（进程内互斥）。
+#[cfg_attr(not(feature = "etcd"), allow(unused_variables))]
+async fn init_segment_lock(
+    config: &Config,
+) -> Arc<dyn nebulaid::core::coordinator::DistributedLock + Send + Sync> {
+    #[cfg(feature = "etcd")]
+    if !config.etcd.endpoints.is_empty() {
+        match nebulaid::core::coordinator::EtcdClientWrapper::new(config.etcd.endpoints.clone())
+            .await
+        {
+            Ok(client) => {
+                let client: Arc<dyn nebulaid::core::coordinator::EtcdClientOps> = Arc::new(client);
+                match nebulaid::core::coordinator::EtcdDistributedLock::new(
+                    client,
+                    "nebulaid-segment-lock".to_string(),
+                )
+                .await
+                {
+                    Ok(etcd_lock) => return Arc::new(etcd_lock),
+                    Err(_) => {
+                        warn!("Failed to create EtcdDistributedLock, falling back to LocalDistributedLock")
+                    }
+                }
+            }
+            Err(_) => {
+                warn!("Failed to create EtcdClientWrapper, falling back to LocalDistributedLock")
+            }
+        }
+    } else {
+        warn!("Etcd endpoints not configured, using LocalDistributedLock (single-process only)");
+    }
+
+    Arc::new(nebulaid::core::coordinator::LocalDistributedLock::new())
+}
+
+/// 连接 `database.replicas` 中配置的只读副本并启动周期健康检查。
+///
+/// 未配置副本或全部连接失败时返回 None，读请求照常走主库；副本地址配置错误时退出。
+async fn init_read_replicas(config: &Config) -> Option<Arc<database::ReplicaSet>> {
+    let replica_config = &config.database.replicas;
+    if replica_config.urls.is_empty() {
+        return None;
+    }
+    let replicas = match database::ReplicaSet::connect(&config.database).await {
+        Ok(Some(replicas)) => Arc::new(replicas),
+        Ok(None) => {
+            warn!(
+                event = "db_replicas_unavailable",
+                "No read replica could be connected, serving all reads from the primary"
+            );
+            return None;
+        }
+        Err(e) => {
+            error!(
+                event = "db_replicas_config_invalid",
+                error = %e,
+                "Invalid read replica configuration"
+            );
+            std::process::exit(1);
+        }
+    };
+    // 首次检查通过前副本不承接读请求
+    replicas.check_health().await;
+    let interval = replica_config.health_check_interval_seconds.max(1);
+    replicas.spawn_health_checks(Duration::from_secs(interval));
+    info!(
+        event = "db_replicas_configured",
+        total = replicas.len(),
+        healthy = replicas.healthy_count(),
+        max_lag_secs = replica_config.max_lag_seconds,
+        "Read replica routing enabled"
+    );
+    Some(replicas)
+}
+
+/// 用已校验凭证缓存包装 API key 仓库并启动吊销传播：轮询变更表，
+/// etcd 构建且配置了端点时另通过 watch 推送。未启用缓存时原样返回
+async fn init_credential_cache(
+    config: &Config,
+    repo: Arc<dyn ApiKeyRepository>,
+) -> Arc<dyn ApiKeyRepository> {
+    let cache_config = &config.auth.credential_cache;
+    if !cache_config.enabled {
+        return repo;
+    }
+
+    let cache = Arc::new(CredentialCache::from_config(cache_config));
+    let sync = Arc::new(RevocationSync::new(
+        repo.clone(),
+        cache.clone(),
+        Duration::from_millis(cache_config.poll_interval_ms),
+    ));
+    if let Err(e) = sync.start_from_latest().await {
+        warn!(
+            event = "credential_cache_sync_failed",
+            error = %e,
+            "Failed to read latest API key change, replaying change history"
+        );
+    }
+    sync.spawn();
+
+    #[allow(unused_mut)]
+    let mut cached = CachedApiKeyRepository::new(repo, cache.clone(), sync.clone());
+    #[cfg(feature = "etcd")]
+    {
+        if cache_config.etcd_watch && !config.etcd.endpoints.is_empty() {
+            match EtcdClientWrapper::new(config.etcd.endpoints.clone()).await {
+                Ok(client) => {
+                    let notifier =
+                        Arc::new(EtcdRevocationNotifier::new(Arc::new(client), cache_config));
+                    notifier.spawn_watch(cache.clone(), sync.poll_interval());
+                    cached = cached.with_notifier(notifier);
+                }
+                Err(e) => warn!(
+                    event = "credential_revocation_watch_failed",
+                    error = %e,
+                    "Failed to connect to etcd, API key changes propagate by polling only"
+                ),
+            }
+        }
+    }
+    info!(
+        event = "credential_cache_enabled",
+        ttl_secs = cache_config.ttl_seconds,
+        poll_interval_ms = cache_config.poll_interval_ms,
+        "API key credential cache enabled"
+    );
+    Arc::new(cached)
+}
+
+/// 加载 JWKS 并启动定期刷新；JWKS 不可用时无法校验任何 token，直接退出
+async fn init_jwt_validator(config: &JwtConfig) -> Arc<JwtValidator> {
+    match JwtValidator::new(config.clone()).await {
+        Ok(validator) => {
+            let validator = Arc::new(validator);
+            validator.spawn_refresh();
+            validator
+        }
+        Err(e) => {
+            error!(event = "jwt_init_failed", error = %e, "Failed to initialize JWT validation");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// 加载服务端证书并启动证书热加载；初始化失败时回退为不启用 TLS。
+/// 配置了客户端证书校验（`tls.client_auth` 非 none）时直接退出，
+/// 避免以明文方式放行本应出示证书的客户端。
+async fn init_tls_manager(config: &Config) -> Option<Arc<TlsManager>> {
+    let mut tls_manager = TlsManager::new(config.tls.clone());
+    if let Err(e) = tls_manager.initialize().await {
+        error!("{}", t!("log.main.tls_init_failed", error = e));
+        if config.tls.client_auth != ClientAuthMode::None {
+            error!(
+                event = "tls_client_auth_unavailable",
+                client_auth = %config.tls.client_auth,
+                "Client certificate authentication is configured but TLS failed to initialize"
+            );
+            std::process::exit(1);
+        }
+        info!("{}", t!("log.main.tls_disabled"));
+    }
+    if !tls_manager.is_http_enabled() && !tls_manager.is_grpc_enabled() {
+        return None;
+    }
+    let tls_manager = Arc::new(tls_manager);
+    let watcher = tls_manager.clone();
+    tokio::spawn(async move { watcher.watch().await });
+    Some(tls_manager)
+}
+
+/// 编译 `tls.client_identities`；未启用 TLS 或未配置映射时不启用证书认证。
+/// 映射无效时直接退出，避免以错误的身份放行客户端。
+fn init_client_cert_mapper(config: &Config) -> Option<Arc<ClientCertMapper>> {
+    if !config.tls.enabled || config.tls.client_identities.is_empty() {
+        return None;
+    }
+    match ClientCertMapper::new(&config.tls.client_identities) {
+        Ok(mapper) => Some(Arc::new(mapper)),
+        Err(e) => {
+            error!(event = "client_cert_mapping_invalid", error = %e, "Invalid tls.client_identities");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `nebula-id audit verify <log_path>`：校验审计日志 hash 链与签名检查点。
+///
+/// 签名密钥读取 `NEBULA_AUDIT_SIGNING_KEY`；未设置时只校验链结构与检查点一致性。
+/// 返回进程退出码：0 = 完整，1 = 检测到篡改，2 = 用法或 I/O 错误。
+fn run_audit_command(args: &[String]) -> i32 {
+    let (Some("verify"), Some(log_path)) = (args.first().map(String::as_str), args.get(1)) else {
+        eprintln!("usage: nebula-id audit verify <log_path>");
+        return 2;
+    };
+
+    let signer = CheckpointSigner::new(env::var("NEBULA_AUDIT_SIGNING_KEY").unwrap_or_default());
+    let report = match verify_audit_log(std::path::Path::new(log_path), signer.as_ref()) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("failed to read audit log {}: {}", log_path, e);
+            return 2;
+        }
+    };
+
+    println!(
+        "records: {} (legacy: {}), last seq: {}, checkpoints: {}{}",
+        report.records,
+        report.legacy_records,
+        report.last_seq,
+        report.checkpoints,
+        if report.signatures_checked {
+            ""
+        } else {
+            " (signatures not checked: NEBULA_AUDIT_SIGNING_KEY unset)"
+        }
+    );
+    for file in &report.files {
+        println!(
+            "{}: {} records (seq {}..={})",
+            file.path.display(),
+            file.records,
+            file.first_seq,
+            file.last_seq
+        );
+        for issue in &file.issues {
+            println!("  {}", issue);
+        }
+    }
+    for issue in report.issues.iter().filter(|i| i.is_checkpoint_issue()) {
+        println!("  {}", issue);
+    }
+    if report.is_valid() {
+        println!("audit log OK");
+        0
+    } else {
+        println!(
+            "audit log FAILED verification: {} issue(s)",
+            report.issues.len()
+        );
+        1
+    }
+}
+
+/// `nebula-id migrate <up [version] | down [steps] | status> [--config <path>]`：管理数据库 schema 版本。
+///
+/// `up` 应用到指定版本（默认最新），`down` 回滚最近 N 个版本（默认 1）。
+/// 返回进程退出码：0 = 成功，1 = 迁移失败或 status 发现未应用 / 异常版本，2 = 用法、配置或连接错误。
+async fn run_migrate_command(args: &[String]) -> i32 {
+    const USAGE: &str =
+        "usage: nebula-id migrate <up [version] | down [steps] | status> [--config <path>]";
+
+    let mut config_path = DEFAULT_CONFIG_PATH.to_string();
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--config" {
+            let Some(path) = iter.next() else {
+                eprintln!("{}", USAGE);
+                return 2;
+            };
+            config_path = path.clone();
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+
+    let (action, value) = match positional.as_slice() {
+        [action] => (*action, None),
+        [action, value] => match value.parse::<i64>() {
+            Ok(v) if v > 0 => (*action, Some(v)),
+            _ => {
+                eprintln!("{}", USAGE);
+                return 2;
+            }
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    };
+    if !matches!(action, "up" | "down" | "status") || (action == "status" && value.is_some()) {
+        eprintln!("{}", USAGE);
+        return 2;
+    }
+
+    let mut config = Config::load_from_file(&config_path).unwrap_or_else(|e| {
+        eprintln!(
+            "failed to load config {}: {} (using defaults)",
+            config_path, e
+        );
+        Config::default()
+    });
+    config.merge(Config::load_from_env().unwrap_or_default());
+
+    let conn = match database::create_connection(&config.database).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!(
+                "failed to connect to {} database: {}",
+                config.database.engine, e
+            );
+            return 2;
+        }
+    };
+
+    match action {
+        "up" => match database::migrate_up(&conn, value).await {
+            Ok(applied) if applied.is_empty() => {
+                println!("schema is up to date");
+                0
+            }
+            Ok(applied) => {
+                for version in applied {
+                    println!("applied migration {}", version);
+                }
+                0
+            }
+            Err(e) => {
+                eprintln!("migration failed: {}", e);
+                1
+            }
+        },
+        "down" => match database::migrate_down(&conn, value.unwrap_or(1) as usize).await {
+            Ok(reverted) if reverted.is_empty() => {
+                println!("no applied migrations to revert");
+                0
+            }
+            Ok(reverted) => {
+                for version in reverted {
+                    println!("reverted migration {}", version);
+                }
+                0
+            }
+            Err(e) => {
+                eprintln!("rollback failed: {}", e);
+                1
+            }
+        },
+        _ => match database::migration_status(&conn).await {
+            Ok(statuses) => {
+                for status in &statuses {
+                    println!(
+                        "{:>5}  {:<24} {:<18} {}",
+                        status.version,
+                        status.name,
+                        status.state.to_string(),
+                        status.applied_at.map(|t| t.to_string()).unwrap_or_default()
+                    );
+                }
+                let clean = statuses
+                    .iter()
+                    .all(|s| s.state == database::MigrationState::Applied);
+                println!(
+                    "engine: {}, supported schema version: {}",
+                    config.database.engine,
+                    database::latest_version()
+                );
+                if clean {
+                    0
+                } else {
+                    1
+                }
+            }
+            Err(e) => {
+                eprintln!("failed to read migration status: {}", e);
+                2
+            }
+        },
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // 日志初始化由 inklog 接管（替换原手写的 tracing_subscriber::fmt() 链）。
+    // Parse command line arguments
+    let args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("audit") {
+        std::process::exit(run_audit_command(&args[2..]));
+    }
+    if args.get(1).map(String::as_str) == Some("migrate") {
+        std::process::exit(run_migrate_command(&args[2..]).await);
+    }
+    let config_path = if args.len() > 2 && args[1] == "--config" {
+        args[2].clone()
+    } else {
+        DEFAULT_CONFIG_PATH.to_string()
+    };
+
+    // 配置需先于日志加载：monitoring.tracing_enabled 决定订阅器的组装方式。
+    // 此时订阅器尚未安装，加载错误暂存，待日志就绪后再输出。
+    let (mut config, config_load_error) = match Config::load_from_file(&config_path) {
+        Ok(c) => (c, None),
+        Err(e) => (Config::default(), Some(e)),
+    };
+
+    // Apply environment variable overrides
+    config.merge(Config::load_from_env().unwrap_or_default());
+
+    // 启用 OTLP 追踪时由 tracing-subscriber 组装 JSON 日志层 + OpenTelemetry 层；
+    // 否则日志初始化由 inklog 接管（替换原手写的 tracing_subscriber::fmt() 链）。
     // 本地 ../inklog 已切换至 EnvFilter，自动从 RUST_LOG 读取按模块过滤规则
     // （如 `RUST_LOG=nebulaid=debug,hyper=warn`），无需手动读取环境变量。
-    let _logger = inklog::LoggerManager::builder()
-        .level("info")
-        .format("json")
-        .build()
-        .await?;
+    let telemetry_guard = if config.monitoring.tracing_enabled {
+        Some(nebulaid::core::monitoring::init_tracing_with_otlp(
+            &config.monitoring,
+            "info",
+        )?)
+    } else {
+        None
+    };
+    let _logger = if telemetry_guard.is_none() {
+        Some(
+            inklog::LoggerManager::builder()
+                .level("info")
+                .format("json")
+                .build()
+                .await?,
+        )
+    } else {
+        None
+    };
 
     // Phase 8 ICU i18n — initialize default locale before any t!() lookup.
     nebulaid::core::i18n::init_i18n("en");
@@ -490,27 +1084,10 @@ async fn main() -> Result<()> {
         t!("log.main.sdforge_plugins_initialized")
     );
 
-    // Parse command line arguments
-    let args: Vec<String> = env::args().collect();
-    let config_path = if args.len() > 2 && args[1] == "--config" {
-        args[2].clone()
-    } else {
-        DEFAULT_CONFIG_PATH.to_string()
-    };
-
     info!("{}", t!("log.main.loading_config", path = config_path));
-
-    // Load config from file first, then merge with environment variables
-    let mut config = match Config::load_from_file(&config_path) {
-        Ok(c) => c,
-        Err(e) => {
-            error!("{}", t!("log.main.config_load_failed", error = e));
-            Config::default()
-        }
-    };
-
-    // Apply environment variable overrides
-    config.merge(Config::load_from_env().unwrap_or_default());
+    if let Some(e) = config_load_error {
+        error!("{}", t!("log.main.config_load_failed", error = e));
+    }
     info!("{}", t!("log.main.config_loaded"));
 
     let server_config = ServerConfig {
@@ -555,53 +1132,6 @@ async fn main() -> Result<()> {
     };
 
     let repository: Option<Arc<database::SeaOrmRepository>> = if let Some(conn) = db_connection {
-        // M8 修复：注入分布式锁，避免 allocate_segment 在无锁降级时产生重复 ID。
-        // 默认构建（无 etcd feature）使用 LocalDistributedLock（进程内互斥）。
-        #[cfg(not(feature = "etcd"))]
-        let lock: std::sync::Arc<
-            dyn nebulaid::core::coordinator::DistributedLock + Send + Sync,
-        > = std::sync::Arc::new(nebulaid::core::coordinator::LocalDistributedLock::new());
-        // etcd feature 构建下，若 etcd 端点已配置，先创建 EtcdClientWrapper，
-        // 再用它构造 EtcdDistributedLock；任一步失败回退到 LocalDistributedLock。
-        #[cfg(feature = "etcd")]
-        let lock: std::sync::Arc<
-            dyn nebulaid::core::coordinator::DistributedLock + Send + Sync,
-        > = if !config.etcd.endpoints.is_empty() {
-            match nebulaid::core::coordinator::EtcdClientWrapper::new(config.etcd.endpoints.clone())
-                .await
-            {
-                Ok(client) => {
-                    let client: std::sync::Arc<dyn nebulaid::core::coordinator::EtcdClientOps> =
-                        std::sync::Arc::new(client);
-                    match nebulaid::core::coordinator::EtcdDistributedLock::new(
-                        client,
-                        "nebulaid-segment-lock".to_string(),
-                    )
-                    .await
-                    {
-                        Ok(etcd_lock) => std::sync::Arc::new(etcd_lock),
-                        Err(_) => {
-                            warn!("Failed to create EtcdDistributedLock, falling back to LocalDistributedLock");
-                            std::sync::Arc::new(
-                                nebulaid::core::coordinator::LocalDistributedLock::new(),
-                            )
-                        }
-                    }
-                }
-                Err(_) => {
-                    warn!(
-                        "Failed to create EtcdClientWrapper, falling back to LocalDistributedLock"
-                    );
-                    std::sync::Arc::new(nebulaid::core::coordinator::LocalDistributedLock::new())
-                }
-            }
-        } else {
-            warn!(
-                "Etcd endpoints not configured, using LocalDistributedLock (single-process only)"
-            );
-            std::sync::Arc::new(nebulaid::core::coordinator::LocalDistributedLock::new())
-        };
-
         // tiangang C1 修复：生产环境强制校验 api_key_salt 非空且非弱默认值。
         // 规则 12（失败必须显性化）：校验失败时 panic，禁止弱 pepper 静默放行。
         if nebulaid::core::config::is_production() {
@@ -621,10 +1151,22 @@ async fn main() -> Result<()> {
             }
         }
 
-        let repo = Arc::new(
-            database::SeaOrmRepository::new(conn, config.auth.api_key_salt.clone())
-                .with_distributed_lock(lock),
+        let mode = config.database.segment_allocation;
+        let mut repo = database::SeaOrmRepository::new(conn, config.auth.api_key_salt.clone())
+            .with_segment_allocation(mode);
+        // 默认的 atomic 分配由数据库保证原子性，只有 locked 分配需要分布式锁
+        if mode == SegmentAllocationMode::Locked {
+            repo = repo.with_distributed_lock(init_segment_lock(&config).await);
+        }
+        info!(
+            event = "segment_allocation_mode",
+            mode = %mode,
+            "Segment allocation mode configured"
         );
+        if let Some(replicas) = init_read_replicas(&config).await {
+            repo = repo.with_replicas(replicas);
+        }
+        let repo = Arc::new(repo);
         info!("{}", t!("log.main.database_repository_initialized"));
         Some(repo)
     } else {
@@ -636,6 +1178,37 @@ async fn main() -> Result<()> {
         t!("log.main.auth_enabled", enabled = config.auth.enabled)
     );
 
+    // 认证、key 管理接口与生命周期检查共用同一个带缓存的仓库，
+    // 经任一入口的吊销都会立即失效本地缓存
+    let api_key_repo: Option<Arc<dyn ApiKeyRepository>> = match repository {
+        Some(ref repo) => Some(init_credential_cache(&config, repo.clone()).await),
+        None => None,
+    };
+
+    // Initialize audit logger and config (used by both etcd and non-etcd modes)
+    let audit_max_events = config.rate_limit.default_rps as usize;
+    let audit_logger = if config.audit.log_path.is_empty() {
+        AuditLogger::new(audit_max_events)
+    } else {
+        AuditLogger::with_file_options(
+            audit_max_events,
+            config.audit.log_path.clone(),
+            AuditFileOptions::from(&config.audit),
+        )
+        .await
+    };
+    // 转发到外部 SIEM（syslog / webhook / file），各 sink 独立排队，不阻塞请求
+    let audit_logger = audit_logger.with_configured_sinks(&config.audit.sinks);
+    // 审计事件批量写入 audit_events 表，重启后仍可通过 GET /api/v1/audit 查询
+    let audit_logger = Arc::new(match repository {
+        Some(ref repo) if config.audit.database_enabled => {
+            audit_logger.with_event_store(repo.clone(), AuditStoreOptions::from(&config.audit))
+        }
+        _ => audit_logger,
+    });
+    // 认证失败封禁：HTTP / gRPC 认证层与管理接口共用，封禁经数据库跨实例共享
+    let ban_manager = init_ban_manager(&config, &repository, audit_logger.clone()).await;
+
     // Create API key auth with repository for database-backed storage
     // Phase 9 T043 (HIGH H3) — configure trusted proxies so the auth
     // middleware only honors `X-Forwarded-For` / `X-Real-IP` when the
@@ -645,11 +1218,24 @@ async fn main() -> Result<()> {
         .ok()
         .map(|s| s.split(',').filter_map(|p| p.trim().parse().ok()).collect())
         .unwrap_or_default();
-    let auth: Arc<ApiKeyAuth> = if let Some(ref repo) = repository {
-        Arc::new(
-            ApiKeyAuth::new(repo.clone(), config.auth.enabled)
-                .with_trusted_proxies(trusted_proxies.clone()),
-        )
+    let auth: Arc<ApiKeyAuth> = if let Some(ref repo) = api_key_repo {
+        let auth = ApiKeyAuth::new(repo.clone(), config.auth.enabled)
+            .with_trusted_proxies(trusted_proxies.clone())
+            .with_ban_manager(ban_manager.clone())
+            .with_signature_max_skew(std::time::Duration::from_secs(
+                config.auth.signature_max_skew_seconds,
+            ));
+        // nonce 登记在数据库中共享，各实例拒绝其他实例已接受过的签名请求
+        spawn_nonce_pruning(repo.clone(), config.auth.signature_max_skew_seconds);
+        let auth = if config.auth.jwt.enabled {
+            auth.with_jwt(init_jwt_validator(&config.auth.jwt).await)
+        } else {
+            auth
+        };
+        Arc::new(match init_client_cert_mapper(&config) {
+            Some(mapper) => auth.with_client_cert_mapper(mapper),
+            None => auth,
+        })
     } else {
         error!("{}", t!("log.main.fatal_api_key_auth_requires_database"));
         std::process::exit(1);
@@ -657,8 +1243,27 @@ async fn main() -> Result<()> {
     let _ = trusted_proxies; // also consumed by router.rs via env var
     load_api_keys(&auth, &repository, &config).await;
 
-    // Initialize audit logger and config (used by both etcd and non-etcd modes)
-    let audit_logger = Arc::new(AuditLogger::new(config.rate_limit.default_rps as usize));
+    // 租户用量统计：号段算法（burn rate）与 HTTP handler（生成速率）共享同一实例
+    let usage_tracker = Arc::new(UsageTracker::new(config.monitoring.usage.clone()));
+    // 有数据库时消耗速率取自 nebula_segments（覆盖全部实例）；否则只能用本进程的用量统计
+    let capacity_forecaster =
+        CapacityForecaster::new(usage_tracker.clone(), config.monitoring.capacity.clone());
+    let capacity_forecaster = Arc::new(match repository {
+        Some(ref repo) => capacity_forecaster.with_segment_repository(repo.clone()),
+        None => {
+            if config.monitoring.capacity.enabled && !config.monitoring.usage.enabled {
+                warn!(
+                    event = "capacity_forecast_unavailable",
+                    "Capacity forecasting needs a database or monitoring.usage.enabled; \
+                     forecasts will stay empty"
+                );
+            }
+            capacity_forecaster
+        }
+    });
+    if capacity_forecaster.is_enabled() {
+        spawn_capacity_alerts(capacity_forecaster.clone());
+    }
     let hot_config = Arc::new(HotReloadConfig::new(
         config.clone(),
         "config/config.toml".to_string(),
@@ -709,46 +1314,68 @@ async fn main() -> Result<()> {
             &config,
             audit_logger.clone(),
             Some(etcd_health_monitor.clone()),
+            usage_tracker.clone(),
         )
         .await?;
 
-        let (handlers, config_service) = if let Some(ref repo) = repository {
-            let cs = Arc::new(ConfigManager::with_repository(
-                hot_config,
-                id_generator.clone(),
-                repo.clone(),
-                repo.clone(),
-                repo.clone(),
-            ));
-            let h = Arc::new(build_api_handlers(
-                id_generator.clone(),
-                cs.clone(),
-                repo.clone(),
-                config.auth.key_rotation_grace_period_seconds,
-            ));
-            (h, cs)
-        } else {
-            let cs = Arc::new(ConfigManager::new(hot_config, id_generator.clone()));
-            let h = Arc::new(ApiHandlers::new(id_generator.clone(), cs.clone()));
-            (h, cs)
-        };
+        let (handlers, config_service) =
+            if let (Some(repo), Some(api_keys)) = (&repository, &api_key_repo) {
+                let cs = Arc::new(ConfigManager::with_repository(
+                    hot_config,
+                    id_generator.clone(),
+                    repo.clone(),
+                    repo.clone(),
+                    repo.clone(),
+                ));
+                let h = build_api_handlers(
+                    id_generator.clone(),
+                    cs.clone(),
+                    api_keys.clone(),
+                    &config.auth,
+                    usage_tracker.clone(),
+                    capacity_forecaster.clone(),
+                );
+                (h, cs)
+            } else {
+                let cs = Arc::new(ConfigManager::new(hot_config, id_generator.clone()));
+                let h = ApiHandlers::new(id_generator.clone(), cs.clone())
+                    .with_usage_tracker(usage_tracker.clone())
+                    .with_capacity_forecaster(capacity_forecaster.clone());
+                (h, cs)
+            };
+        let mut handlers = handlers
+            .with_health_config(config.monitoring.health.clone())
+            .with_etcd_health_monitor(etcd_health_monitor.clone());
+        if let Some(metrics) = audit_logger.writer_metrics() {
+            handlers = handlers.with_audit_writer_metrics(metrics);
+        }
+        handlers = handlers.with_audit_sink_metrics(audit_logger.sink_metrics());
+        if let Some(ref repo) = repository {
+            handlers = handlers
+                .with_audit_event_repository(repo.clone())
+                .with_generation_guard(Arc::new(GenerationGuard::new(Arc::new(
+                    RepositoryRegistry::new(repo.clone(), repo.clone(), repo.clone()),
+                ))));
+        }
+        if let Some(ref api_keys) = api_key_repo {
+            if let Some(lifecycle) =
+                init_api_key_lifecycle(&config, api_keys.clone(), audit_logger.clone())
+            {
+                handlers = handlers.with_api_key_lifecycle(lifecycle);
+            }
+        }
+        handlers = handlers.with_ban_manager(ban_manager.clone());
+        let tls_manager = init_tls_manager(&config).await;
+        if let Some(ref tls) = tls_manager {
+            handlers = handlers.with_tls_certificate_metrics(tls.certificate_metrics());
+        }
+        let handlers = Arc::new(handlers);
 
         let rate_limiter = Arc::new(RateLimiter::new(
             config.rate_limit.default_rps,
             config.rate_limit.burst_size,
         ));
 
-        let mut tls_manager = TlsManager::new(config.tls.clone());
-        if let Err(e) = tls_manager.initialize().await {
-            error!("{}", t!("log.main.tls_init_failed", error = e));
-            info!("{}", t!("log.main.tls_disabled"));
-        }
-        let tls_manager = if tls_manager.is_http_enabled() || tls_manager.is_grpc_enabled() {
-            Some(Arc::new(tls_manager))
-        } else {
-            None
-        };
-
         info!("{}", t!("log.main.starting_degradation_check"));
         let degradation_manager = id_generator.get_degradation_manager();
         degradation_manager.start_background_check();
@@ -764,7 +1391,13 @@ async fn main() -> Result<()> {
             config_service.clone(),
             tls_manager.clone(),
         ));
-        let grpc_server = tokio::spawn(start_grpc_server(server_config, handlers, tls_manager));
+        let grpc_server = tokio::spawn(start_grpc_server(
+            server_config,
+            handlers,
+            auth,
+            audit_logger,
+            tls_manager,
+        ));
 
         tokio::select! {
             http_result = http_server => {
@@ -843,45 +1476,65 @@ async fn main() -> Result<()> {
         let router = AlgorithmRouter::new(config.clone(), Some(audit_logger_for_core));
         let _router = Arc::new(router);
 
-        let id_generator = create_id_generator(&config, audit_logger.clone(), None).await?;
-
-        let (handlers, config_service) = if let Some(ref repo) = repository {
-            let cs = Arc::new(ConfigManager::with_repository(
-                hot_config,
-                id_generator.clone(),
-                repo.clone(),
-                repo.clone(),
-                repo.clone(),
-            ));
-            let h = Arc::new(build_api_handlers(
-                id_generator.clone(),
-                cs.clone(),
-                repo.clone(),
-                config.auth.key_rotation_grace_period_seconds,
-            ));
-            (h, cs)
-        } else {
-            let cs = Arc::new(ConfigManager::new(hot_config, id_generator.clone()));
-            let h = Arc::new(ApiHandlers::new(id_generator.clone(), cs.clone()));
-            (h, cs)
-        };
+        let id_generator =
+            create_id_generator(&config, audit_logger.clone(), None, usage_tracker.clone()).await?;
+
+        let (handlers, config_service) =
+            if let (Some(repo), Some(api_keys)) = (&repository, &api_key_repo) {
+                let cs = Arc::new(ConfigManager::with_repository(
+                    hot_config,
+                    id_generator.clone(),
+                    repo.clone(),
+                    repo.clone(),
+                    repo.clone(),
+                ));
+                let h = build_api_handlers(
+                    id_generator.clone(),
+                    cs.clone(),
+                    api_keys.clone(),
+                    &config.auth,
+                    usage_tracker.clone(),
+                    capacity_forecaster.clone(),
+                );
+                (h, cs)
+            } else {
+                let cs = Arc::new(ConfigManager::new(hot_config, id_generator.clone()));
+                let h = ApiHandlers::new(id_generator.clone(), cs.clone())
+                    .with_usage_tracker(usage_tracker.clone())
+                    .with_capacity_forecaster(capacity_forecaster.clone());
+                (h, cs)
+            };
+        let mut handlers = handlers.with_health_config(config.monitoring.health.clone());
+        if let Some(metrics) = audit_logger.writer_metrics() {
+            handlers = handlers.with_audit_writer_metrics(metrics);
+        }
+        handlers = handlers.with_audit_sink_metrics(audit_logger.sink_metrics());
+        if let Some(ref repo) = repository {
+            handlers = handlers
+                .with_audit_event_repository(repo.clone())
+                .with_generation_guard(Arc::new(GenerationGuard::new(Arc::new(
+                    RepositoryRegistry::new(repo.clone(), repo.clone(), repo.clone()),
+                ))));
+        }
+        if let Some(ref api_keys) = api_key_repo {
+            if let Some(lifecycle) =
+                init_api_key_lifecycle(&config, api_keys.clone(), audit_logger.clone())
+            {
+                handlers = handlers.with_api_key_lifecycle(lifecycle);
+            }
+        }
+        handlers = handlers.with_ban_manager(ban_manager.clone());
+        let tls_manager = init_tls_manager(&config).await;
+        if let Some(ref tls) = tls_manager {
+            handlers = handlers.with_tls_certificate_metrics(tls.certificate_metrics());
+        }
+        let handlers = Arc::new(handlers);
 
         let rate_limiter = Arc::new(RateLimiter::new(
             config.rate_limit.default_rps,
             config.rate_limit.burst_size,
         ));
 
-        let mut tls_manager = TlsManager::new(config.tls.clone());
-        if let Err(e) = tls_manager.initialize().await {
-            error!("{}", t!("log.main.tls_init_failed", error = e));
-            info!("{}", t!("log.main.tls_disabled"));
-        }
-        let tls_manager = if tls_manager.is_http_enabled() || tls_manager.is_grpc_enabled() {
-            Some(Arc::new(tls_manager))
-        } else {
-            None
-        };
-
         info!("{}", t!("log.main.starting_degradation_check"));
         let degradation_manager = id_generator.get_degradation_manager();
         degradation_manager.start_background_check();
@@ -897,7 +1550,13 @@ async fn main() -> Result<()> {
             config_service.clone(),
             tls_manager.clone(),
         ));
-        let grpc_server = tokio::spawn(start_grpc_server(server_config, handlers, tls_manager));
+        let grpc_server = tokio::spawn(start_grpc_server(
+            server_config,
+            handlers,
+            auth,
+            audit_logger,
+            tls_manager,
+        ));
 
         tokio::select! {
             http_result = http_server => {
@@ -1015,6 +1674,7 @@ mod tests {
                         enabled: true,
                         expires_at: None,
                         created_at: chrono::Utc::now().naive_utc(),
+                        scopes: nebulaid::core::auth::ApiKeyScopes::unrestricted(),
                     },
                     key_secret: "mock_secret".to_string(),
                 })
@@ -1091,6 +1751,7 @@ mod tests {
                         enabled: true,
                         expires_at: None,
                         created_at: chrono::Utc::now().naive_utc(),
+                        scopes: nebulaid::core::auth::ApiKeyScopes::unrestricted(),
                     },
                     key_secret: "mock_rotated_secret".to_string(),
                 })
@@ -1152,6 +1813,7 @@ mod tests {
                         enabled: true,
                         expires_at: None,
                         created_at: chrono::Utc::now().naive_utc(),
+                        scopes: nebulaid::core::auth::ApiKeyScopes::unrestricted(),
                     },
                     key_secret: "mock_secret".to_string(),
                 })
@@ -1228,6 +1890,7 @@ mod tests {
                         enabled: true,
                         expires_at: None,
                         created_at: chrono::Utc::now().naive_utc(),
+                        scopes: nebulaid::core::auth::ApiKeyScopes::unrestricted(),
                     },
                     key_secret: "mock_rotated_secret".to_string(),
                 })
@@ -0,0 +1,861 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tamper-evident audit log: hash chain + signed checkpoints.
+//!
+//! 每条持久化记录携带 `seq`（从 1 开始连续递增）、`prev_hash`（上一条记录的
+//! `hash`，首条为 [`GENESIS_HASH`]）和 `hash`（去掉 `hash` 字段后记录 JSON 的
+//! SHA-256）。修改任一记录会使其 `hash` 不匹配；删除或调序会使 `seq` 不连续；
+//! 重算整条链则会与检查点冲突。
+//!
+//! 检查点每 `checkpoint_interval` 条记录（以及每次 flush）写入
+//! `<log_path>.checkpoints`，内容为 `(seq, hash, timestamp)` 的 HMAC-SHA256 签名，
+//! 没有签名密钥的攻击者无法伪造，也能发现日志尾部被截断。
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, KeyInit, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use super::rotation::{open_log_reader, rotated_files};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// 首条记录的 `prev_hash`
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// 默认每 1000 条记录生成一个检查点
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 1000;
+
+/// 轮转标记记录中的字段名：新日志文件的首条记录携带该字段，
+/// 校验时据此接受 `seq` 不从 1 开始的文件（更早的文件已轮转或按保留期删除）
+pub const ROTATION_FIELD: &str = "rotation";
+
+/// 构造轮转标记记录（随后由链写入器封装 `seq` / `hash`）
+pub(crate) fn rotation_marker(previous_file: &str, previous_seq: u64) -> Map<String, Value> {
+    let mut record = Map::new();
+    record.insert(
+        "timestamp".to_string(),
+        Value::String(Utc::now().to_rfc3339()),
+    );
+    record.insert(
+        "action".to_string(),
+        Value::String("audit_log_rotated".to_string()),
+    );
+    record.insert(
+        ROTATION_FIELD.to_string(),
+        serde_json::json!({
+            "previous_file": previous_file,
+            "previous_seq": previous_seq,
+        }),
+    );
+    record
+}
+
+/// 检查点文件路径：`<log_path>.checkpoints`
+pub fn checkpoint_path(log_path: &Path) -> PathBuf {
+    let mut name = log_path.as_os_str().to_owned();
+    name.push(".checkpoints");
+    PathBuf::from(name)
+}
+
+fn record_hash(record: &Map<String, Value>) -> String {
+    // Map 序列化不会失败（键均为字符串）
+    let bytes = serde_json::to_vec(record).unwrap_or_default();
+    hex::encode(Sha256::digest(&bytes))
+}
+
+/// 签名检查点：证明截至 `seq` 的链尾 hash 为 `hash`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditCheckpoint {
+    pub seq: u64,
+    pub hash: String,
+    pub timestamp: DateTime<Utc>,
+    pub signature: String,
+}
+
+/// 检查点 HMAC-SHA256 签名器
+#[derive(Clone)]
+pub struct CheckpointSigner {
+    key: Vec<u8>,
+}
+
+impl fmt::Debug for CheckpointSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckpointSigner")
+            .field("key", &"<redacted>")
+            .finish()
+    }
+}
+
+impl CheckpointSigner {
+    /// 空密钥返回 `None`（不生成检查点）
+    pub fn new(key: impl AsRef<[u8]>) -> Option<Self> {
+        let key = key.as_ref();
+        (!key.is_empty()).then(|| Self { key: key.to_vec() })
+    }
+
+    fn mac(&self, seq: u64, hash: &str, timestamp: &DateTime<Utc>) -> HmacSha256 {
+        let mut mac = <HmacSha256 as KeyInit>::new_from_slice(&self.key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(format!("{}:{}:{}", seq, hash, timestamp.to_rfc3339()).as_bytes());
+        mac
+    }
+
+    pub fn checkpoint(&self, seq: u64, hash: &str) -> AuditCheckpoint {
+        let timestamp = Utc::now();
+        let signature = hex::encode(self.mac(seq, hash, &timestamp).finalize().into_bytes());
+        AuditCheckpoint {
+            seq,
+            hash: hash.to_string(),
+            timestamp,
+            signature,
+        }
+    }
+
+    /// 常量时间比较签名
+    pub fn verify(&self, checkpoint: &AuditCheckpoint) -> bool {
+        let Ok(signature) = hex::decode(&checkpoint.signature) else {
+            return false;
+        };
+        self.mac(checkpoint.seq, &checkpoint.hash, &checkpoint.timestamp)
+            .verify_slice(&signature)
+            .is_ok()
+    }
+}
+
+/// 链尾状态：最后一条记录的 `seq` 与 `hash`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ChainState {
+    pub(crate) seq: u64,
+    pub(crate) last_hash: String,
+}
+
+impl ChainState {
+    pub(crate) fn genesis() -> Self {
+        Self {
+            seq: 0,
+            last_hash: GENESIS_HASH.to_string(),
+        }
+    }
+
+    fn from_record(record: &Map<String, Value>) -> Option<Self> {
+        Some(Self {
+            seq: record.get("seq")?.as_u64()?,
+            last_hash: record.get("hash")?.as_str()?.to_string(),
+        })
+    }
+
+    /// 从已有日志恢复链尾，使进程重启后继续同一条链。
+    ///
+    /// 先读取文件最后一行；若最后一行不是链式记录（崩溃导致的半行等），
+    /// 退化为全量扫描取最后一条链式记录。活动文件不存在或无链式记录时
+    /// 从最新的轮转文件恢复（轮转后、写入新文件前退出的情况），都没有则从创世开始。
+    pub(crate) fn resume(path: &Path) -> io::Result<Self> {
+        let state = Self::resume_file(path)?;
+        if state.seq > 0 {
+            return Ok(state);
+        }
+        match rotated_files(path)?.last() {
+            Some(latest) => Self::scan(open_log_reader(latest)?),
+            None => Ok(state),
+        }
+    }
+
+    fn resume_file(path: &Path) -> io::Result<Self> {
+        let file = match std::fs::File::open(path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::genesis()),
+            Err(e) => return Err(e),
+        };
+
+        if let Some(line) = read_last_line(&file)? {
+            if let Some(state) = serde_json::from_str::<Map<String, Value>>(&line)
+                .ok()
+                .as_ref()
+                .and_then(Self::from_record)
+            {
+                return Ok(state);
+            }
+        }
+        Self::scan(std::fs::File::open(path)?)
+    }
+
+    fn scan(reader: impl Read) -> io::Result<Self> {
+        let mut state = Self::genesis();
+        for line in io::BufReader::new(reader).lines() {
+            if let Some(found) = serde_json::from_str::<Map<String, Value>>(&line?)
+                .ok()
+                .as_ref()
+                .and_then(Self::from_record)
+            {
+                state = found;
+            }
+        }
+        Ok(state)
+    }
+
+    /// 为记录分配下一个 `seq` 并写入 `prev_hash` / `hash`，返回封装后的记录和
+    /// 新的链尾。链尾由调用方在记录成功落盘后再提交，写入失败不会留下跳号。
+    pub(crate) fn seal(&self, mut record: Map<String, Value>) -> (Value, ChainState) {
+        let seq = self.seq + 1;
+        record.insert("seq".to_string(), Value::from(seq));
+        record.insert(
+            "prev_hash".to_string(),
+            Value::String(self.last_hash.clone()),
+        );
+        let hash = record_hash(&record);
+        record.insert("hash".to_string(), Value::String(hash.clone()));
+        (
+            Value::Object(record),
+            ChainState {
+                seq,
+                last_hash: hash,
+            },
+        )
+    }
+}
+
+/// 读取文件最后一个非空行（从尾部按倍增窗口回读，避免扫描大文件）
+fn read_last_line(mut file: &std::fs::File) -> io::Result<Option<String>> {
+    let len = file.metadata()?.len();
+    let mut window: u64 = 8 * 1024;
+    loop {
+        let start = len.saturating_sub(window);
+        file.seek(SeekFrom::Start(start))?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        while buf.last().is_some_and(|b| b.is_ascii_whitespace()) {
+            buf.pop();
+        }
+        if let Some(pos) = buf.iter().rposition(|b| *b == b'\n') {
+            return Ok(Some(String::from_utf8_lossy(&buf[pos + 1..]).into_owned()));
+        }
+        if start == 0 {
+            return Ok((!buf.is_empty()).then(|| String::from_utf8_lossy(&buf).into_owned()));
+        }
+        window = window.saturating_mul(2);
+    }
+}
+
+/// writer task 持有的链写入器：封装记录并按间隔追加签名检查点
+pub(crate) struct ChainWriter {
+    state: ChainState,
+    signer: Option<CheckpointSigner>,
+    checkpoint_interval: u64,
+    since_checkpoint: u64,
+    checkpoint_path: PathBuf,
+}
+
+impl ChainWriter {
+    pub(crate) fn open(
+        log_path: &Path,
+        signer: Option<CheckpointSigner>,
+        checkpoint_interval: u64,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            state: ChainState::resume(log_path)?,
+            signer,
+            checkpoint_interval: checkpoint_interval.max(1),
+            since_checkpoint: 0,
+            checkpoint_path: checkpoint_path(log_path),
+        })
+    }
+
+    pub(crate) fn seal(&self, record: Map<String, Value>) -> (Value, ChainState) {
+        self.state.seal(record)
+    }
+
+    pub(crate) fn last_seq(&self) -> u64 {
+        self.state.seq
+    }
+
+    /// 记录已成功写入日志后推进链尾
+    pub(crate) fn commit(&mut self, next: ChainState) {
+        self.state = next;
+        self.since_checkpoint += 1;
+    }
+
+    /// 达到间隔（或 `force` 且有未覆盖记录）时写入检查点；未配置密钥时为空操作
+    pub(crate) fn maybe_checkpoint(&mut self, force: bool) -> io::Result<()> {
+        let Some(ref signer) = self.signer else {
+            return Ok(());
+        };
+        let due = self.since_checkpoint >= self.checkpoint_interval
+            || (force && self.since_checkpoint > 0);
+        if !due {
+            return Ok(());
+        }
+
+        let checkpoint = signer.checkpoint(self.state.seq, &self.state.last_hash);
+        let mut line = serde_json::to_vec(&checkpoint)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        line.push(b'\n');
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.checkpoint_path)?
+            .write_all(&line)?;
+        self.since_checkpoint = 0;
+        Ok(())
+    }
+}
+
+/// 校验发现的问题（`line` 为 1 起始的行号）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ChainIssue {
+    /// 行不是合法 JSON
+    Malformed { line: usize },
+    /// 链开始后出现不带 `seq` / `hash` 的记录（插入）
+    Unchained { line: usize },
+    /// `seq` 跳号（记录被删除）
+    Gap {
+        line: usize,
+        expected: u64,
+        found: u64,
+    },
+    /// `seq` 回退（记录被调序或重放）
+    Reordered {
+        line: usize,
+        expected: u64,
+        found: u64,
+    },
+    /// `prev_hash` 与上一条记录的 `hash` 不一致
+    BrokenLink { line: usize, seq: u64 },
+    /// 记录内容与 `hash` 不一致（记录被修改）
+    HashMismatch { line: usize, seq: u64 },
+    /// 链的首条记录既不是 seq 1 也不是轮转标记（开头的记录被删除）
+    MissingHead { line: usize, seq: u64 },
+    /// 检查点文件中的行不是合法检查点
+    CheckpointMalformed { line: usize },
+    /// 检查点签名无效（检查点被伪造或修改）
+    CheckpointSignatureInvalid { seq: u64 },
+    /// 日志中 `seq` 对应记录的 `hash` 与检查点不一致或记录缺失
+    CheckpointMismatch { seq: u64 },
+    /// 日志在检查点之前结束（尾部被截断）
+    Truncated { checkpoint_seq: u64, last_seq: u64 },
+}
+
+impl fmt::Display for ChainIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainIssue::Malformed { line } => write!(f, "line {}: malformed record", line),
+            ChainIssue::Unchained { line } => {
+                write!(f, "line {}: record without seq/hash inside the chain", line)
+            }
+            ChainIssue::Gap {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: sequence gap, expected {} found {}",
+                line, expected, found
+            ),
+            ChainIssue::Reordered {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: sequence out of order, expected {} found {}",
+                line, expected, found
+            ),
+            ChainIssue::BrokenLink { line, seq } => {
+                write!(f, "line {}: seq {} prev_hash does not match", line, seq)
+            }
+            ChainIssue::HashMismatch { line, seq } => {
+                write!(f, "line {}: seq {} was modified (hash mismatch)", line, seq)
+            }
+            ChainIssue::MissingHead { line, seq } => write!(
+                f,
+                "line {}: chain starts at seq {} without a rotation marker",
+                line, seq
+            ),
+            ChainIssue::CheckpointMalformed { line } => {
+                write!(f, "checkpoint line {}: malformed checkpoint", line)
+            }
+            ChainIssue::CheckpointSignatureInvalid { seq } => {
+                write!(f, "checkpoint at seq {}: invalid signature", seq)
+            }
+            ChainIssue::CheckpointMismatch { seq } => {
+                write!(
+                    f,
+                    "checkpoint at seq {}: log record missing or altered",
+                    seq
+                )
+            }
+            ChainIssue::Truncated {
+                checkpoint_seq,
+                last_seq,
+            } => write!(
+                f,
+                "log ends at seq {} but a checkpoint covers seq {}",
+                last_seq, checkpoint_seq
+            ),
+        }
+    }
+}
+
+impl ChainIssue {
+    /// 检查点相关问题（不属于某个日志文件的具体行）
+    pub fn is_checkpoint_issue(&self) -> bool {
+        matches!(
+            self,
+            ChainIssue::CheckpointMalformed { .. }
+                | ChainIssue::CheckpointSignatureInvalid { .. }
+                | ChainIssue::CheckpointMismatch { .. }
+                | ChainIssue::Truncated { .. }
+        )
+    }
+}
+
+/// 单个日志文件（轮转文件或活动文件）的校验结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifiedFile {
+    pub path: PathBuf,
+    pub records: u64,
+    /// 文件内第一条 / 最后一条链式记录的 seq（无链式记录时为 0）
+    pub first_seq: u64,
+    pub last_seq: u64,
+    /// 本文件内的问题（`line` 为文件内行号），同时计入 `VerifyReport::issues`
+    pub issues: Vec<ChainIssue>,
+}
+
+/// `verify_audit_log` 的结果
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VerifyReport {
+    /// 按链顺序校验的文件（轮转文件在前，活动文件在后）
+    pub files: Vec<VerifiedFile>,
+    /// 链式记录数
+    pub records: u64,
+    /// 链开始前的旧格式记录数（升级前写入，不参与校验）
+    pub legacy_records: u64,
+    /// 检查点数量
+    pub checkpoints: u64,
+    /// 是否校验了检查点签名（未提供密钥时为 false）
+    pub signatures_checked: bool,
+    /// 链上第一条记录的 seq（更早的记录已按保留期删除时大于 1）
+    pub first_seq: u64,
+    pub last_seq: u64,
+    pub issues: Vec<ChainIssue>,
+}
+
+impl VerifyReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+fn read_checkpoints(
+    log_path: &Path,
+    report: &mut VerifyReport,
+) -> io::Result<Vec<AuditCheckpoint>> {
+    let file = match std::fs::File::open(checkpoint_path(log_path)) {
+        Ok(f) => f,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut checkpoints = Vec::new();
+    for (idx, line) in io::BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<AuditCheckpoint>(&line) {
+            Ok(cp) => checkpoints.push(cp),
+            Err(_) => report
+                .issues
+                .push(ChainIssue::CheckpointMalformed { line: idx + 1 }),
+        }
+    }
+    Ok(checkpoints)
+}
+
+/// 逐文件校验时跨文件延续的链状态
+struct ChainVerifier<'a> {
+    chain: ChainState,
+    started: bool,
+    expected_hashes: HashMap<u64, &'a str>,
+    matched: HashSet<u64>,
+}
+
+impl ChainVerifier<'_> {
+    fn verify_file(&mut self, path: &Path, report: &mut VerifyReport) -> io::Result<VerifiedFile> {
+        let mut file = VerifiedFile {
+            path: path.to_path_buf(),
+            ..Default::default()
+        };
+
+        for (idx, line) in io::BufReader::new(open_log_reader(path)?)
+            .lines()
+            .enumerate()
+        {
+            let line_no = idx + 1;
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Ok(mut record) = serde_json::from_str::<Map<String, Value>>(&line) else {
+                file.issues.push(ChainIssue::Malformed { line: line_no });
+                continue;
+            };
+
+            let fields = (
+                record.get("seq").and_then(Value::as_u64),
+                record.get("prev_hash").and_then(Value::as_str),
+                record.get("hash").and_then(Value::as_str),
+            );
+            let (seq, prev_hash, stored_hash) = match fields {
+                (Some(seq), Some(prev), Some(hash)) => (seq, prev.to_string(), hash.to_string()),
+                _ => {
+                    if self.started {
+                        file.issues.push(ChainIssue::Unchained { line: line_no });
+                    } else {
+                        report.legacy_records += 1;
+                    }
+                    continue;
+                }
+            };
+
+            if !self.started {
+                self.started = true;
+                report.first_seq = seq;
+                if seq != 1 {
+                    // 更早的文件已删除：以轮转标记为锚点继续校验
+                    if !record.contains_key(ROTATION_FIELD) {
+                        file.issues
+                            .push(ChainIssue::MissingHead { line: line_no, seq });
+                    }
+                    self.chain = ChainState {
+                        seq: seq - 1,
+                        last_hash: prev_hash.clone(),
+                    };
+                }
+            }
+            file.records += 1;
+            if file.first_seq == 0 {
+                file.first_seq = seq;
+            }
+
+            let expected = self.chain.seq + 1;
+            if seq > expected {
+                file.issues.push(ChainIssue::Gap {
+                    line: line_no,
+                    expected,
+                    found: seq,
+                });
+            } else if seq < expected {
+                file.issues.push(ChainIssue::Reordered {
+                    line: line_no,
+                    expected,
+                    found: seq,
+                });
+            } else if prev_hash != self.chain.last_hash {
+                file.issues
+                    .push(ChainIssue::BrokenLink { line: line_no, seq });
+            }
+
+            record.remove("hash");
+            if record_hash(&record) != stored_hash {
+                file.issues
+                    .push(ChainIssue::HashMismatch { line: line_no, seq });
+            }
+
+            if let Some(expected_hash) = self.expected_hashes.get(&seq) {
+                if *expected_hash == stored_hash {
+                    self.matched.insert(seq);
+                }
+            }
+
+            // 只在 seq 前进时推进链尾：调序的记录报告一次后不影响后续记录的校验
+            if seq > self.chain.seq {
+                self.chain = ChainState {
+                    seq,
+                    last_hash: stored_hash,
+                };
+            }
+        }
+        file.last_seq = self.chain.seq;
+        report.records += file.records;
+        report.issues.extend(file.issues.iter().cloned());
+        Ok(file)
+    }
+}
+
+/// 校验审计日志的 hash 链与检查点，检测跳号、调序、修改与截断。
+///
+/// 轮转文件（含 `.gz`）按时间顺序与活动文件作为同一条链校验；按保留期删除的
+/// 最早文件之前的检查点不参与比对。`signer` 为 `None` 时仍校验检查点与日志的
+/// 一致性，但不校验签名。
+pub fn verify_audit_log(
+    log_path: &Path,
+    signer: Option<&CheckpointSigner>,
+) -> io::Result<VerifyReport> {
+    let mut report = VerifyReport {
+        signatures_checked: signer.is_some(),
+        ..Default::default()
+    };
+
+    let mut paths = rotated_files(log_path)?;
+    if log_path.exists() || paths.is_empty() {
+        // 活动文件与轮转文件都不存在时由打开活动文件报告 NotFound
+        paths.push(log_path.to_path_buf());
+    }
+
+    let checkpoints = read_checkpoints(log_path, &mut report)?;
+    report.checkpoints = checkpoints.len() as u64;
+    let mut verifier = ChainVerifier {
+        chain: ChainState::genesis(),
+        started: false,
+        expected_hashes: checkpoints
+            .iter()
+            .map(|cp| (cp.seq, cp.hash.as_str()))
+            .collect(),
+        matched: HashSet::new(),
+    };
+
+    for path in &paths {
+        let file = verifier.verify_file(path, &mut report)?;
+        report.files.push(file);
+    }
+    report.last_seq = verifier.chain.seq;
+
+    for cp in &checkpoints {
+        if let Some(signer) = signer {
+            if !signer.verify(cp) {
+                report
+                    .issues
+                    .push(ChainIssue::CheckpointSignatureInvalid { seq: cp.seq });
+                continue;
+            }
+        }
+        if cp.seq > report.last_seq {
+            report.issues.push(ChainIssue::Truncated {
+                checkpoint_seq: cp.seq,
+                last_seq: report.last_seq,
+            });
+        } else if cp.seq >= report.first_seq && !verifier.matched.contains(&cp.seq) {
+            report
+                .issues
+                .push(ChainIssue::CheckpointMismatch { seq: cp.seq });
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn record(action: &str) -> Map<String, Value> {
+        let Value::Object(map) = serde_json::json!({ "action": action, "result": "Success" })
+        else {
+            unreachable!()
+        };
+        map
+    }
+
+    /// 写入 n 条链式记录，返回各行文本
+    fn write_chain(path: &Path, n: usize, signer: Option<CheckpointSigner>) -> Vec<String> {
+        let mut writer = ChainWriter::open(path, signer, 2).unwrap();
+        let mut lines = Vec::new();
+        for i in 0..n {
+            let (sealed, next) = writer.seal(record(&format!("action-{}", i)));
+            lines.push(serde_json::to_string(&sealed).unwrap());
+            std::fs::write(path, lines.join("\n") + "\n").unwrap();
+            writer.commit(next);
+            writer.maybe_checkpoint(false).unwrap();
+        }
+        writer.maybe_checkpoint(true).unwrap();
+        lines
+    }
+
+    fn signer() -> CheckpointSigner {
+        CheckpointSigner::new("test-key").unwrap()
+    }
+
+    #[test]
+    fn test_intact_chain_verifies() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        write_chain(&path, 5, Some(signer()));
+
+        let report = verify_audit_log(&path, Some(&signer())).unwrap();
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.records, 5);
+        assert_eq!(report.last_seq, 5);
+        assert_eq!(report.checkpoints, 3);
+    }
+
+    #[test]
+    fn test_modified_record_is_detected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        let mut lines = write_chain(&path, 3, None);
+        lines[1] = lines[1].replace("action-1", "action-x");
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let report = verify_audit_log(&path, None).unwrap();
+        assert_eq!(
+            report.issues,
+            vec![ChainIssue::HashMismatch { line: 2, seq: 2 }]
+        );
+    }
+
+    #[test]
+    fn test_deleted_and_reordered_records_are_detected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        let lines = write_chain(&path, 4, None);
+
+        std::fs::write(
+            &path,
+            [&lines[0], &lines[2], &lines[3]]
+                .map(String::as_str)
+                .join("\n"),
+        )
+        .unwrap();
+        let report = verify_audit_log(&path, None).unwrap();
+        assert_eq!(
+            report.issues,
+            vec![ChainIssue::Gap {
+                line: 2,
+                expected: 2,
+                found: 3
+            }]
+        );
+
+        std::fs::write(
+            &path,
+            [&lines[0], &lines[2], &lines[1], &lines[3]]
+                .map(String::as_str)
+                .join("\n"),
+        )
+        .unwrap();
+        let report = verify_audit_log(&path, None).unwrap();
+        assert_eq!(
+            report.issues,
+            vec![
+                ChainIssue::Gap {
+                    line: 2,
+                    expected: 2,
+                    found: 3
+                },
+                ChainIssue::Reordered {
+                    line: 3,
+                    expected: 4,
+                    found: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_truncation_and_forged_checkpoint_are_detected() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        let lines = write_chain(&path, 4, Some(signer()));
+
+        // 截断尾部：检查点覆盖的 seq 4 已不在日志中
+        std::fs::write(&path, lines[..2].join("\n")).unwrap();
+        let report = verify_audit_log(&path, Some(&signer())).unwrap();
+        assert!(report.issues.contains(&ChainIssue::Truncated {
+            checkpoint_seq: 4,
+            last_seq: 2
+        }));
+
+        // 用错误密钥校验：所有检查点签名无效
+        let other = CheckpointSigner::new("other-key").unwrap();
+        let report = verify_audit_log(&path, Some(&other)).unwrap();
+        assert!(report
+            .issues
+            .iter()
+            .all(|i| matches!(i, ChainIssue::CheckpointSignatureInvalid { .. })));
+    }
+
+    #[test]
+    fn test_rotated_files_verify_as_one_chain() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        let rotated = dir.path().join("audit.log.20261001T000000.000Z");
+
+        let mut writer = ChainWriter::open(&path, None, 100).unwrap();
+        fn seal_into(writer: &mut ChainWriter, target: &Path, record: Map<String, Value>) {
+            let (sealed, next) = writer.seal(record);
+            let mut content = std::fs::read_to_string(target).unwrap_or_default();
+            content.push_str(&serde_json::to_string(&sealed).unwrap());
+            content.push('\n');
+            std::fs::write(target, content).unwrap();
+            writer.commit(next);
+        }
+        seal_into(&mut writer, &rotated, record("a"));
+        seal_into(&mut writer, &rotated, record("b"));
+        seal_into(
+            &mut writer,
+            &path,
+            rotation_marker("audit.log.20261001T000000.000Z", 2),
+        );
+        seal_into(&mut writer, &path, record("c"));
+
+        let report = verify_audit_log(&path, None).unwrap();
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.files.len(), 2);
+        assert_eq!(report.last_seq, 4);
+
+        // 旧文件按保留期删除后，以轮转标记为锚点仍可校验
+        std::fs::remove_file(&rotated).unwrap();
+        let report = verify_audit_log(&path, None).unwrap();
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.first_seq, 3);
+
+        // 删除轮转标记：开头缺失
+        let content = std::fs::read_to_string(&path).unwrap();
+        let tail: Vec<&str> = content.lines().skip(1).collect();
+        std::fs::write(&path, tail.join("\n")).unwrap();
+        let report = verify_audit_log(&path, None).unwrap();
+        assert_eq!(
+            report.issues,
+            vec![ChainIssue::MissingHead { line: 1, seq: 4 }]
+        );
+    }
+
+    #[test]
+    fn test_resume_continues_existing_chain() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        write_chain(&path, 3, None);
+
+        let state = ChainState::resume(&path).unwrap();
+        assert_eq!(state.seq, 3);
+        assert_ne!(state.last_hash, GENESIS_HASH);
+
+        // 末尾半行（崩溃）时回退为全量扫描
+        let mut content = std::fs::read_to_string(&path).unwrap();
+        content.push_str("{\"seq\":4,\"prev");
+        std::fs::write(&path, content).unwrap();
+        assert_eq!(ChainState::resume(&path).unwrap(), state);
+
+        let missing = dir.path().join("missing.log");
+        assert_eq!(ChainState::resume(&missing).unwrap(), ChainState::genesis());
+    }
+}
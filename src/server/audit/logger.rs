@@ -12,109 +12,68 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::core::algorithm::{AuditEvent as CoreAuditEvent, AuditLogger as CoreAuditLoggerTrait};
+use super::chain::{rotation_marker, ChainWriter, CheckpointSigner, DEFAULT_CHECKPOINT_INTERVAL};
+use super::queue::{
+    draining_spill_path, AuditCommand, AuditQueue, AuditQueueSender, AuditWriterMetrics, QueueItem,
+};
+use super::rotation::{compress_file, enforce_retention, LogRotator, RotationPolicy};
+use super::sinks::{
+    configured_sinks, AuditSink, AuditSinkHandle, AuditSinkMetrics, AuditSinkOptions,
+};
+use super::store::{AuditStoreHandle, AuditStoreOptions};
+use super::testing::AuditCapture;
+use crate::core::algorithm::AuditLogger as CoreAuditLoggerTrait;
+use crate::core::config::{AuditConfig, AuditOverflowPolicy, AuditSinksConfig};
+use crate::core::database::AuditEventRepository;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::Mutex;
 use tokio::task::JoinHandle;
-use tracing::info;
-
-// M4 修复：复用 core 层的 AuditEventType 和 AuditResult，
-// 消除 server/core 重复定义。pub use 使其成为本模块公共 API。
-pub use crate::core::algorithm::{AuditEventType, AuditResult};
-
-/// 生成审计事件 ID（M1 修复）。
-///
-/// 格式：`(unix_millis << 20) | (counter & 0xFFFFF)`
-/// - 高 44 位：Unix 毫秒时间戳，保证进程重启后 ID 单调递增、不冲突
-/// - 低 20 位：进程内计数器（最多 1M/ms，远超任何审计吞吐）
-///
-/// 这避免了原 `static COUNTER: AtomicU64` 在进程重启后从 0 开始导致 ID 冲突的问题。
-fn next_audit_event_id() -> u64 {
-    static COUNTER: AtomicU64 = AtomicU64::new(0);
-    let unix_ms = Utc::now().timestamp_millis() as u64;
-    let c = COUNTER.fetch_add(1, Ordering::SeqCst) & 0xFFFFF;
-    (unix_ms << 20) | c
-}
 
-// M4 修复：删除本地 AuditEventType 和 AuditResult 定义，
-// 改用 `crate::core::algorithm::{AuditEventType, AuditResult}`。
-// 这消除了 server/core 重复定义，未来新增事件类型只需修改 core 层一处。
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AuditEvent {
-    pub id: u64,
-    pub timestamp: DateTime<Utc>,
-    pub event_type: AuditEventType,
-    pub workspace_id: Option<String>,
-    pub user_id: Option<String>,
-    pub action: String,
-    pub resource: String,
-    pub result: AuditResult,
-    pub details: Option<serde_json::Value>,
-    pub client_ip: Option<String>,
-    pub user_agent: Option<String>,
-    pub duration_ms: u64,
-    pub error_message: Option<String>,
-}
+// 审计事件模型统一定义在 core 层（`crate::core::algorithm::audit_trait`），
+// 本模块只补充持久化相关的方法。pub use 使其成为本模块公共 API。
+pub use crate::core::algorithm::{AuditEvent, AuditEventType, AuditResult};
 
 impl AuditEvent {
-    pub fn new(
-        event_type: AuditEventType,
-        workspace_id: Option<String>,
-        action: String,
-        resource: String,
-        result: AuditResult,
-    ) -> Self {
-        Self {
-            id: next_audit_event_id(),
-            timestamp: Utc::now(),
-            event_type,
-            workspace_id,
-            user_id: None,
-            action,
-            resource,
-            result,
-            details: None,
-            client_ip: None,
-            user_agent: None,
-            duration_ms: 0,
-            error_message: None,
-        }
-    }
-
-    pub fn with_details(mut self, details: serde_json::Value) -> Self {
-        self.details = Some(details);
-        self
-    }
-
-    pub fn with_client_ip(mut self, ip: String) -> Self {
-        self.client_ip = Some(ip);
-        self
-    }
-
-    pub fn with_user_agent(mut self, ua: String) -> Self {
-        self.user_agent = Some(ua);
-        self
-    }
-
-    pub fn with_user_id(mut self, user_id: String) -> Self {
-        self.user_id = Some(user_id);
-        self
-    }
-
-    pub fn with_duration(mut self, ms: u64) -> Self {
-        self.duration_ms = ms;
-        self
-    }
+    /// 持久化形式：对 PII 字段脱敏后的 JSON 对象（尚未封装 hash 链字段）。
+    ///
+    /// LOW-3 修复（CWE-532）：
+    /// - `client_ip`：保留前 3 段（IPv4）或前 4 段（IPv6），末段用 `x` 替换
+    /// - `user_agent`：替换为固定字符串 `UA(redacted)`，避免记录完整 UA
+    /// - `user_id`：保留（API key 标识符，非个人身份信息）
+    ///
+    /// PERF-M1 修复：直接构建 `serde_json::Value`，仅对需要脱敏的 2 个字段做转换，
+    /// 其余字段通过 `&` 引用序列化，避免整个事件的深拷贝。
+    pub(crate) fn persisted_record(&self) -> serde_json::Map<String, serde_json::Value> {
+        let redacted_client_ip = self.client_ip.as_deref().map(AuditEvent::redact_ip);
+        let redacted_user_agent = self
+            .user_agent
+            .as_deref()
+            .map(|_| "UA(redacted)".to_string());
 
-    pub fn with_error(mut self, error: String) -> Self {
-        self.error_message = Some(error);
-        self
+        let serde_json::Value::Object(record) = serde_json::json!({
+            "id": self.id,
+            "timestamp": self.timestamp,
+            "event_type": self.event_type,
+            "workspace_id": self.workspace_id,
+            "user_id": self.user_id,
+            "action": self.action,
+            "resource": self.resource,
+            "result": self.result,
+            "details": self.details,
+            "client_ip": redacted_client_ip,
+            "user_agent": redacted_user_agent,
+            "duration_ms": self.duration_ms,
+            "error_message": self.error_message,
+            "request_id": self.request_id,
+        }) else {
+            unreachable!("json! object literal always yields an object")
+        };
+        record
     }
 
     /// LOW-3 修复 + SEC-LOW-001 修复：对 IP 地址进行部分遮蔽。
@@ -123,7 +82,7 @@ impl AuditEvent {
     /// 等 boundary case 的脱敏不一致。原 `rfind('.')` / `rfind(':')` 方案
     /// 对 `::1` 返回 `:x`（不正确）、对 `2001:db8::1` 返回 `2001:db8:x`
     /// （与注释承诺的 `2001:db8::x` 不符）。
-    fn redact_ip(ip: &str) -> String {
+    pub(crate) fn redact_ip(ip: &str) -> String {
         match ip.parse::<std::net::IpAddr>() {
             Ok(std::net::IpAddr::V4(v4)) => {
                 let octets = v4.octets();
@@ -150,15 +109,44 @@ impl AuditEvent {
     }
 }
 
-/// Writer task 命令：写入事件或显式 flush（sync_all 落盘）。
-///
-/// `Flush` 变体携带 oneshot Sender，writer task 处理完所有排队 Event 后
-/// sync_all 文件并回复，使调用方可以确定性等待持久化完成（替代 sleep）。
-enum AuditCommand {
-    /// 写入一条审计事件到文件
-    Event(Box<AuditEvent>),
-    /// Flush：sync_all 确保所有已写入数据落盘，完成后回复 oneshot
-    Flush(tokio::sync::oneshot::Sender<()>),
+/// 文件持久化选项：hash 链检查点、轮转与保留、写入队列
+#[derive(Debug, Clone)]
+pub struct AuditFileOptions {
+    /// 检查点签名器；`None` 时只写 hash 链，不生成检查点
+    pub signer: Option<CheckpointSigner>,
+    pub checkpoint_interval: u64,
+    pub rotation: RotationPolicy,
+    /// 写入队列容量（事件数）
+    pub channel_capacity: usize,
+    pub overflow_policy: AuditOverflowPolicy,
+    /// Block 策略下 [`AuditLogger::log_generation`] 不等待队列，满时丢弃
+    pub nonblocking_generation: bool,
+}
+
+impl Default for AuditFileOptions {
+    fn default() -> Self {
+        Self {
+            signer: None,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            rotation: RotationPolicy::default(),
+            channel_capacity: 10_000,
+            overflow_policy: AuditOverflowPolicy::Spill,
+            nonblocking_generation: false,
+        }
+    }
+}
+
+impl From<&AuditConfig> for AuditFileOptions {
+    fn from(config: &AuditConfig) -> Self {
+        Self {
+            signer: CheckpointSigner::new(&config.signing_key),
+            checkpoint_interval: config.checkpoint_interval,
+            rotation: RotationPolicy::from(config),
+            channel_capacity: config.channel_capacity,
+            overflow_policy: config.overflow_policy,
+            nonblocking_generation: config.nonblocking_generation,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -167,13 +155,25 @@ pub struct AuditLogger {
     max_events: usize,
     total_logged: Arc<AtomicU64>,
     total_errors: Arc<AtomicU64>,
-    /// 文件写入 channel：log 方法只发送事件（非阻塞），独立 task 消费并写文件。
-    /// 解决 H7：避免在 Mutex 持有期间执行文件 I/O。
-    file_tx: Option<mpsc::UnboundedSender<AuditCommand>>,
+    /// `try_log` 因内存中的最近事件被占用而未放入该缓冲区的事件数（事件本身照常持久化）
+    recent_skipped: Arc<AtomicU64>,
+    /// [`Self::log_generation`] 是否等待文件队列：Block 策略且未开启
+    /// `nonblocking_generation` 时为 true
+    generation_waits: bool,
+    /// 文件写入队列：log 方法只入队事件，独立 task 消费并写文件。
+    /// 解决 H7：避免在 Mutex 持有期间执行文件 I/O。队列有界，满时按
+    /// `AuditFileOptions::overflow_policy` 处理；最后一个克隆释放时关闭队列。
+    file_tx: Option<Arc<AuditQueueSender>>,
     /// 持有 writer task 的 JoinHandle，保持 task 存活。
     /// Arc 包装以便 Clone 时共享；JoinHandle drop 不会 cancel task（tokio 默认行为），
-    /// task 会在所有 sender drop 后自然退出。
+    /// task 会在队列关闭并写完剩余事件后自然退出。
     _writer_task: Option<Arc<JoinHandle<()>>>,
+    /// 数据库批量写入（`audit_events` 表）；由 [`Self::with_event_store`] 启用
+    store: Option<Arc<AuditStoreHandle>>,
+    /// 外部 sink（syslog / webhook / 文件），各自独立过滤与排队；由 [`Self::with_sink`] 添加
+    sinks: Vec<Arc<AuditSinkHandle>>,
+    /// 测试用事件捕获；由 [`Self::with_capture`] 启用
+    capture: Option<AuditCapture>,
 }
 
 impl AuditLogger {
@@ -183,22 +183,44 @@ impl AuditLogger {
             max_events,
             total_logged: Arc::new(AtomicU64::new(0)),
             total_errors: Arc::new(AtomicU64::new(0)),
+            recent_skipped: Arc::new(AtomicU64::new(0)),
+            generation_waits: false,
             file_tx: None,
             _writer_task: None,
+            store: None,
+            sinks: Vec::new(),
+            capture: None,
         }
     }
 
     /// 创建支持文件持久化的审计日志记录器。
     ///
-    /// 启动一个独立的后台 task 消费事件并写文件，`log` 方法只通过 channel
-    /// 发送事件（非阻塞），避免在 Mutex 持有期间执行文件 I/O（H7 修复）。
+    /// 启动一个独立的后台 task 消费事件并写文件，`log` 方法只把事件放入队列，
+    /// 避免在 Mutex 持有期间执行文件 I/O（H7 修复）。
     ///
     /// 必须在 tokio runtime 上下文中调用。
     ///
     /// LOW-2 修复（CWE-22 路径遍历）：验证 `log_file_path` 不包含 `..`
     /// 组件，防止攻击者通过配置注入如 `../../etc/passwd` 路径覆盖系统文件。
     /// 空路径也被拒绝。
+    ///
+    /// 使用默认选项：写入的记录带 hash 链（见 [`super::chain`]），不生成签名检查点，
+    /// 不轮转；需要时使用 [`Self::with_file_options`]。
     pub async fn with_file_logging(max_events: usize, log_file_path: String) -> Self {
+        Self::with_file_options(max_events, log_file_path, AuditFileOptions::default()).await
+    }
+
+    /// 按 `options` 创建文件审计日志记录器。
+    ///
+    /// 每条记录携带连续的 `seq`、`prev_hash` 与 `hash`；配置签名器时每
+    /// `checkpoint_interval` 条记录以及每次 `flush()` 向 `<log_file_path>.checkpoints`
+    /// 追加一个 HMAC 签名检查点。已有日志文件会从最后一条链式记录续写。
+    /// 轮转后的文件以时间戳命名（可选 gzip），新文件以链式轮转标记开头。
+    pub async fn with_file_options(
+        max_events: usize,
+        log_file_path: String,
+        options: AuditFileOptions,
+    ) -> Self {
         // LOW-2 修复：路径安全性验证
         if let Err(e) = Self::validate_log_path(&log_file_path) {
             tracing::error!(
@@ -212,35 +234,54 @@ impl AuditLogger {
             return Self::new(max_events);
         }
 
-        let (tx, mut rx) = mpsc::unbounded_channel::<AuditCommand>();
+        let path = Path::new(&log_file_path);
+        let chain = match ChainWriter::open(path, options.signer, options.checkpoint_interval) {
+            Ok(chain) => chain,
+            Err(e) => {
+                // 无法读取已有日志则无法续接 hash 链；与路径无效一样回退到内存记录器，
+                // 而不是从创世重新开始一条会被 verify 判定为断链的新链
+                tracing::error!(
+                    event = "audit_chain_resume_failed",
+                    path = %log_file_path,
+                    error = %e,
+                    "Failed to resume audit hash chain, file persistence disabled"
+                );
+                return Self::new(max_events);
+            }
+        };
+
+        let metrics = Arc::new(AuditWriterMetrics::new(
+            options.channel_capacity,
+            options.overflow_policy,
+        ));
+        let queue = Arc::new(AuditQueue::new(
+            path,
+            options.channel_capacity,
+            options.overflow_policy,
+            metrics.clone(),
+        ));
         let total_errors = Arc::new(AtomicU64::new(0));
-        let errors_clone = total_errors.clone();
-        let path_clone = log_file_path.clone();
+        let mut writer = FileWriter {
+            rotator: LogRotator::open(path, options.rotation),
+            path: log_file_path,
+            chain,
+            metrics,
+            errors: total_errors.clone(),
+        };
 
+        let consumer = queue.clone();
         let handle = tokio::spawn(async move {
-            while let Some(cmd) = rx.recv().await {
-                match cmd {
-                    AuditCommand::Event(event) => {
-                        if let Err(e) = Self::write_event_to_file(&event, &path_clone).await {
-                            errors_clone.fetch_add(1, Ordering::SeqCst);
-                            tracing::error!(
-                                "{}",
-                                t!("log.server.audit.logger.persist_failed", error = e)
-                            );
-                        }
+            writer.start().await;
+            while let Some(item) = consumer.pop().await {
+                match item {
+                    QueueItem::Command(AuditCommand::Event(event)) => {
+                        writer.write_event(&event).await;
                     }
-                    AuditCommand::Flush(ack) => {
-                        // 处理完所有排队 Event 后 sync_all 确保数据落盘。
-                        // 这替代了每事件 sync_all 的性能开销，仅按需 flush。
-                        if let Err(e) = Self::sync_file(&path_clone).await {
-                            errors_clone.fetch_add(1, Ordering::SeqCst);
-                            tracing::error!(
-                                "{}",
-                                t!("log.server.audit.logger.persist_failed", error = e)
-                            );
-                        }
+                    QueueItem::Command(AuditCommand::Flush(ack)) => {
+                        writer.flush().await;
                         let _ = ack.send(());
                     }
+                    QueueItem::Spilled(spill) => writer.replay_spill(&spill).await,
                 }
             }
         });
@@ -250,11 +291,68 @@ impl AuditLogger {
             max_events,
             total_logged: Arc::new(AtomicU64::new(0)),
             total_errors,
-            file_tx: Some(tx),
+            recent_skipped: Arc::new(AtomicU64::new(0)),
+            generation_waits: options.overflow_policy == AuditOverflowPolicy::Block
+                && !options.nonblocking_generation,
+            file_tx: Some(Arc::new(AuditQueueSender(queue))),
             _writer_task: Some(Arc::new(handle)),
+            store: None,
+            sinks: Vec::new(),
+            capture: None,
         }
     }
 
+    /// 同时将事件批量写入数据库，使重启后的审计查询（`GET /api/v1/audit`）
+    /// 不再局限于内存中的最近事件。必须在 tokio runtime 上下文中调用。
+    pub fn with_event_store(
+        mut self,
+        repo: Arc<dyn AuditEventRepository>,
+        options: AuditStoreOptions,
+    ) -> Self {
+        self.store = Some(Arc::new(AuditStoreHandle::spawn(
+            repo,
+            options,
+            self.total_errors.clone(),
+        )));
+        self
+    }
+
+    /// 将事件转发到外部 sink。`log` 只做过滤与非阻塞入队，sink 的故障与积压
+    /// 不会阻塞调用方。必须在 tokio runtime 上下文中调用。
+    pub fn with_sink(mut self, sink: Box<dyn AuditSink>, options: AuditSinkOptions) -> Self {
+        self.sinks
+            .push(Arc::new(AuditSinkHandle::spawn(sink, options)));
+        self
+    }
+
+    /// 按 `[audit.sinks]` 配置添加已启用的 sink
+    pub fn with_configured_sinks(self, config: &AuditSinksConfig) -> Self {
+        configured_sinks(config)
+            .into_iter()
+            .fold(self, |logger, (sink, options)| {
+                logger.with_sink(sink, options)
+            })
+    }
+
+    /// 将每个记录的事件同步复制到 `capture`，供测试断言
+    pub fn with_capture(mut self, capture: AuditCapture) -> Self {
+        self.capture = Some(capture);
+        self
+    }
+
+    /// 各外部 sink 的投递指标
+    pub fn sink_metrics(&self) -> Vec<Arc<AuditSinkMetrics>> {
+        self.sinks
+            .iter()
+            .map(|sink| sink.metrics().clone())
+            .collect()
+    }
+
+    /// 文件写入链路指标（队列深度、溢出处理、轮转）；未配置文件持久化时为 `None`
+    pub fn writer_metrics(&self) -> Option<Arc<AuditWriterMetrics>> {
+        self.file_tx.as_ref().map(|tx| tx.metrics().clone())
+    }
+
     /// LOW-2 修复（CWE-22）：验证审计日志路径安全性。
     ///
     /// 拒绝：
@@ -292,55 +390,23 @@ impl AuditLogger {
 
     pub async fn log(&self, event: AuditEvent) {
         // 锁内只做内存操作（push/pop），快速释放锁
-        {
-            let mut events = self.events.lock().await;
-            // M14 修复：VecDeque 满时丢弃最旧事件。如果未配置文件持久化，
-            // 丢弃意味着审计事件永久丢失（违反 SOC2/GDPR 合规）。
-            // 此处记录 warning 提示运维人员配置 `audit_log_path`。
-            if events.len() >= self.max_events {
-                let dropped_count = events.len() - self.max_events + 1;
-                for _ in 0..dropped_count {
-                    if let Some(dropped) = events.pop_front() {
-                        if self.file_tx.is_none() {
-                            // 未配置文件持久化：丢弃即永久丢失
-                            tracing::warn!(
-                                event_id = dropped.id,
-                                event_type = ?dropped.event_type,
-                                "{}",
-                                t!(
-                                    "log.server.audit.logger.event_dropped_no_persistence",
-                                    max_events = self.max_events
-                                )
-                            );
-                            self.total_errors.fetch_add(1, Ordering::SeqCst);
-                        }
-                        // 已配置 file_tx 的事件已被异步写入文件，内存丢弃可接受
-                    }
-                }
-            }
-            events.push_back(event.clone());
-            self.total_logged.fetch_add(1, Ordering::SeqCst);
-        }
+        self.remember(&mut *self.events.lock().await, &event);
 
-        // 锁外异步发送到文件 writer channel（非阻塞）
+        // 锁外入队到文件 writer（队列满时按溢出策略处理，Block 策略下在此等待）
         if let Some(ref tx) = self.file_tx {
-            if tx
-                .send(AuditCommand::Event(Box::new(event.clone())))
-                .is_err()
-            {
-                // channel 关闭（writer task panic 或退出）
+            if let Err(e) = tx.push_event(Box::new(event.clone())).await {
+                // 队列关闭（writer task panic 或退出）或溢写失败
                 self.total_errors.fetch_add(1, Ordering::SeqCst);
                 tracing::error!(
                     "{}",
-                    t!(
-                        "log.server.audit.logger.persist_failed",
-                        error = "file writer channel closed"
-                    )
+                    t!("log.server.audit.logger.persist_failed", error = e)
                 );
             }
         }
 
-        info!(
+        self.fan_out(&event);
+
+        tracing::debug!(
             event_id = event.id,
             event_type = ?event.event_type,
             workspace = ?event.workspace_id,
@@ -352,21 +418,115 @@ impl AuditLogger {
         );
     }
 
-    /// 将单个审计事件写入文件（由 writer task 调用，不在 Mutex 持有期间执行）。
+    /// 记录 ID 生成请求的审计事件。
     ///
-    /// LOW-3 修复（CWE-532）：写入文件前对 PII 字段脱敏。
-    /// - `client_ip`：保留前 3 段（IPv4）或前 4 段（IPv6），末段用 `x` 替换
-    /// - `user_agent`：替换为固定字符串 `UA(redacted)`，避免记录完整 UA
-    /// - `user_id`：保留（API key 标识符，非个人身份信息）
+    /// Block 策略承诺不丢事件，因此默认走等待队列的 [`Self::log`]；开启
+    /// `nonblocking_generation` 或使用其他溢出策略时走不等待的 [`Self::try_log`]。
+    pub async fn log_generation(&self, event: AuditEvent) {
+        if self.generation_waits {
+            self.log(event).await;
+        } else {
+            self.try_log(event);
+        }
+    }
+
+    /// 不等待的记录。
     ///
-    /// PERF-M1 修复：原实现先 `redact_for_persistence()` 创建完整 AuditEvent
-    /// 深拷贝（6-7 次 String clone + 1 次 Value 深拷贝），再 `to_string`。
-    /// 现直接构建 `serde_json::Value`，仅对需要脱敏的 2 个字段做转换，
-    /// 其余字段通过 `&` 引用序列化（serde 自动处理），避免深拷贝。
-    /// 10k QPS 场景下减少约 1-2 万次/秒的 String 堆分配（注：`json!` 宏
-    /// 仍会克隆 `redacted_client_ip` / `redacted_user_agent`，所以实际
-    /// 节省的是其余字段的深拷贝，量级为 1-2 万次/秒）。
-    async fn write_event_to_file(event: &AuditEvent, path: &str) -> std::io::Result<()> {
+    /// 内存中的最近事件被占用时不放入该缓冲区（计入 `total_logged` 与
+    /// [`Self::recent_skipped`]，文件与数据库照常写入）；文件队列满时按溢出策略处理，
+    /// Block 策略下丢弃该事件并计入 `dropped_total`。
+    pub fn try_log(&self, event: AuditEvent) {
+        match self.events.try_lock() {
+            Ok(mut events) => self.remember(&mut events, &event),
+            Err(_) => {
+                self.total_logged.fetch_add(1, Ordering::SeqCst);
+                self.recent_skipped.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        if let Some(ref tx) = self.file_tx {
+            if let Err(e) = tx.try_push_event(Box::new(event.clone())) {
+                self.total_errors.fetch_add(1, Ordering::SeqCst);
+                tracing::error!(
+                    "{}",
+                    t!("log.server.audit.logger.persist_failed", error = e)
+                );
+            }
+        }
+
+        self.fan_out(&event);
+    }
+
+    /// 追加到内存中的最近事件
+    fn remember(&self, events: &mut VecDeque<AuditEvent>, event: &AuditEvent) {
+        // M14 修复：VecDeque 满时丢弃最旧事件。如果未配置文件持久化，
+        // 丢弃意味着审计事件永久丢失（违反 SOC2/GDPR 合规）。
+        // 此处记录 warning 提示运维人员配置 `audit_log_path`。
+        if events.len() >= self.max_events {
+            let dropped_count = events.len() - self.max_events + 1;
+            for _ in 0..dropped_count {
+                if let Some(dropped) = events.pop_front() {
+                    if self.file_tx.is_none() {
+                        // 未配置文件持久化：丢弃即永久丢失
+                        tracing::warn!(
+                            event_id = dropped.id,
+                            event_type = ?dropped.event_type,
+                            "{}",
+                            t!(
+                                "log.server.audit.logger.event_dropped_no_persistence",
+                                max_events = self.max_events
+                            )
+                        );
+                        self.total_errors.fetch_add(1, Ordering::SeqCst);
+                    }
+                    // 已配置 file_tx 的事件已被异步写入文件，内存丢弃可接受
+                }
+            }
+        }
+        events.push_back(event.clone());
+        self.total_logged.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// 数据库、外部 sink 与测试捕获（均不等待）
+    fn fan_out(&self, event: &AuditEvent) {
+        if let Some(ref store) = self.store {
+            if !store.try_record(event) {
+                self.total_errors.fetch_add(1, Ordering::SeqCst);
+                tracing::warn!(
+                    event = "audit_db_queue_full",
+                    event_id = event.id,
+                    "Audit database queue full, event not persisted to database"
+                );
+            }
+        }
+
+        for sink in &self.sinks {
+            sink.offer(event);
+        }
+
+        if let Some(ref capture) = self.capture {
+            capture.record(event);
+        }
+    }
+
+    /// 将单个审计事件写入文件（由 writer task 调用，不在 Mutex 持有期间执行）。
+    ///
+    /// 写入前按 [`AuditEvent::persisted_record`] 对 PII 字段脱敏，再由 `chain`
+    /// 封装 `seq` / `prev_hash` / `hash`。返回写入的字节数（用于轮转判断）。
+    async fn write_event_to_file(
+        event: &AuditEvent,
+        path: &str,
+        chain: &mut ChainWriter,
+    ) -> std::io::Result<u64> {
+        Self::append_record(event.persisted_record(), path, chain)
+    }
+
+    /// 封装并追加一条记录，写入成功后才推进链尾。
+    fn append_record(
+        record: serde_json::Map<String, serde_json::Value>,
+        path: &str,
+        chain: &mut ChainWriter,
+    ) -> std::io::Result<u64> {
         // 使用同步 std::fs 而非 tokio::fs：writer task 是专用串行消费者，
         // 阻塞 I/O 可接受。同步 I/O 消除 tokio::fs::File 异步 drop 与后续
         // 读取之间的竞争（数据在 write_all 返回后立即可见，无需 sync_all）。
@@ -376,37 +536,16 @@ impl AuditLogger {
             .append(true)
             .open(path)?;
 
-        // 仅对需要脱敏的 client_ip / user_agent 做转换，其余字段引用序列化。
-        let redacted_client_ip = event.client_ip.as_deref().map(AuditEvent::redact_ip);
-        let redacted_user_agent = event
-            .user_agent
-            .as_deref()
-            .map(|_| "UA(redacted)".to_string());
-
-        let log_line = serde_json::json!({
-            "id": event.id,
-            "timestamp": event.timestamp,
-            "event_type": event.event_type,
-            "workspace_id": event.workspace_id,
-            "user_id": event.user_id,
-            "action": event.action,
-            "resource": event.resource,
-            "result": event.result,
-            "details": event.details,
-            "client_ip": redacted_client_ip,
-            "user_agent": redacted_user_agent,
-            "duration_ms": event.duration_ms,
-            "error_message": event.error_message,
-        });
-
-        let mut buf = Vec::with_capacity(512);
+        let (log_line, next) = chain.seal(record);
+        let mut buf = Vec::with_capacity(640);
         serde_json::to_writer(&mut buf, &log_line)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
         buf.push(b'\n');
         file.write_all(&buf)?;
         // 不在此处 sync_all：每事件 fsync 在高 QPS 场景下会成为 I/O 瓶颈。
         // 数据持久化由调用方按需调用 `flush()` 触发（writer task 执行 sync_all）。
-        Ok(())
+        chain.commit(next);
+        Ok(buf.len() as u64)
     }
 
     /// 对日志文件执行 sync_all，确保所有已写入数据落盘。
@@ -427,456 +566,18 @@ impl AuditLogger {
     pub async fn flush(&self) {
         if let Some(ref tx) = self.file_tx {
             let (ack_tx, ack_rx) = tokio::sync::oneshot::channel();
-            // Flush 命令排在所有已发送 Event 之后，writer task 会先处理完事件再 sync
-            if tx.send(AuditCommand::Flush(ack_tx)).is_ok() {
+            // Flush 命令排在所有已入队 Event（以及溢写事件）之后，writer task
+            // 会先处理完事件再 sync
+            if tx.push_flush(ack_tx).is_ok() {
                 let _ = ack_rx.await;
             }
         }
-    }
-
-    #[allow(clippy::too_many_arguments)]
-    pub async fn log_id_generation(
-        &self,
-        workspace_id: String,
-        biz_tag: String,
-        id: String,
-        algorithm: String,
-        client_ip: Option<String>,
-        duration_ms: u64,
-        success: bool,
-        error_message: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::IdGeneration,
-            Some(workspace_id),
-            "generate_id".to_string(),
-            format!("biz_tag:{}", biz_tag),
-            if success {
-                AuditResult::Success
-            } else {
-                AuditResult::Failure
-            },
-        )
-        .with_details(serde_json::json!({
-            "generated_id": id,
-            "algorithm": algorithm
-        }))
-        .with_client_ip(client_ip.unwrap_or_default())
-        .with_duration(duration_ms);
-
-        let event = if let Some(err) = error_message {
-            event.with_error(err)
-        } else {
-            event
-        };
-
-        self.log(event).await;
-    }
-
-    #[allow(clippy::too_many_arguments)]
-    pub async fn log_batch_generation(
-        &self,
-        workspace_id: String,
-        biz_tag: String,
-        size: usize,
-        client_ip: Option<String>,
-        duration_ms: u64,
-        success: bool,
-        error_message: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::BatchGeneration,
-            Some(workspace_id),
-            "batch_generate_ids".to_string(),
-            format!("biz_tag:{} size:{}", biz_tag, size),
-            if success {
-                AuditResult::Success
-            } else {
-                AuditResult::Failure
-            },
-        )
-        .with_details(serde_json::json!({
-            "batch_size": size,
-            "biz_tag": biz_tag
-        }))
-        .with_client_ip(client_ip.unwrap_or_default())
-        .with_duration(duration_ms);
-
-        let event = if let Some(err) = error_message {
-            event.with_error(err)
-        } else {
-            event
-        };
-
-        self.log(event).await;
-    }
-
-    pub async fn log_auth_event(
-        &self,
-        workspace_id: Option<String>,
-        action: String,
-        success: bool,
-        client_ip: Option<String>,
-        error_message: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::Authentication,
-            workspace_id,
-            action,
-            "authentication".to_string(),
-            if success {
-                AuditResult::Success
-            } else {
-                AuditResult::Failure
-            },
-        )
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        let event = if let Some(err) = error_message {
-            event.with_error(err)
-        } else {
-            event
-        };
-
-        self.log(event).await;
-    }
-
-    pub async fn log_config_change(
-        &self,
-        workspace_id: Option<String>,
-        action: String,
-        config_type: String,
-        details: serde_json::Value,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::ConfigChange,
-            workspace_id,
-            action,
-            format!("config:{}", config_type),
-            AuditResult::Success,
-        )
-        .with_details(details);
-
-        self.log(event).await;
-    }
-
-    pub async fn log_degradation_event(
-        &self,
-        workspace_id: Option<String>,
-        action: String,
-        algorithm_type: String,
-        previous_state: String,
-        current_state: String,
-        details: serde_json::Value,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::DegradationEvent,
-            workspace_id,
-            action,
-            format!("algorithm:{}", algorithm_type),
-            if current_state == "Critical" {
-                AuditResult::Failure
-            } else {
-                AuditResult::Partial
-            },
-        )
-        .with_details(serde_json::json!({
-            "previous_state": previous_state,
-            "current_state": current_state,
-            "algorithm_type": algorithm_type,
-            "details": details
-        }));
-
-        self.log(event).await;
-    }
-
-    pub async fn log_rate_limit_exceeded(
-        &self,
-        workspace_id: Option<String>,
-        client_ip: String,
-        endpoint: String,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::RateLimitExceeded,
-            workspace_id,
-            "rate_limit_exceeded".to_string(),
-            endpoint,
-            AuditResult::Failure,
-        )
-        .with_client_ip(client_ip)
-        .with_error("Rate limit exceeded".to_string());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_workspace_created(
-        &self,
-        workspace_id: String,
-        workspace_name: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::WorkspaceCreated,
-            Some(workspace_id),
-            "create_workspace".to_string(),
-            format!("workspace:{}", workspace_name),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_workspace_updated(
-        &self,
-        workspace_id: String,
-        workspace_name: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::WorkspaceUpdated,
-            Some(workspace_id),
-            "update_workspace".to_string(),
-            format!("workspace:{}", workspace_name),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_workspace_deleted(
-        &self,
-        workspace_id: String,
-        workspace_name: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::WorkspaceDeleted,
-            Some(workspace_id),
-            "delete_workspace".to_string(),
-            format!("workspace:{}", workspace_name),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_group_created(
-        &self,
-        workspace_id: String,
-        group_id: String,
-        group_name: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::GroupCreated,
-            Some(workspace_id),
-            "create_group".to_string(),
-            format!("group:{}:{}", group_id, group_name),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_group_updated(
-        &self,
-        workspace_id: String,
-        group_id: String,
-        group_name: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::GroupUpdated,
-            Some(workspace_id),
-            "update_group".to_string(),
-            format!("group:{}:{}", group_id, group_name),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_group_deleted(
-        &self,
-        workspace_id: String,
-        group_id: String,
-        group_name: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::GroupDeleted,
-            Some(workspace_id),
-            "delete_group".to_string(),
-            format!("group:{}:{}", group_id, group_name),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_biz_tag_created(
-        &self,
-        workspace_id: String,
-        biz_tag_id: String,
-        biz_tag_name: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::BizTagCreated,
-            Some(workspace_id),
-            "create_biz_tag".to_string(),
-            format!("biz_tag:{}:{}", biz_tag_id, biz_tag_name),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_biz_tag_updated(
-        &self,
-        workspace_id: String,
-        biz_tag_id: String,
-        biz_tag_name: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::BizTagUpdated,
-            Some(workspace_id),
-            "update_biz_tag".to_string(),
-            format!("biz_tag:{}:{}", biz_tag_id, biz_tag_name),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_biz_tag_deleted(
-        &self,
-        workspace_id: String,
-        biz_tag_id: String,
-        biz_tag_name: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::BizTagDeleted,
-            Some(workspace_id),
-            "delete_biz_tag".to_string(),
-            format!("biz_tag:{}:{}", biz_tag_id, biz_tag_name),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_api_key_created(
-        &self,
-        workspace_id: Option<String>,
-        key_id: String,
-        key_role: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::ApiKeyCreated,
-            workspace_id,
-            "create_api_key".to_string(),
-            format!("api_key:{}:{}", key_id, key_role),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_api_key_updated(
-        &self,
-        workspace_id: Option<String>,
-        key_id: String,
-        key_role: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::ApiKeyUpdated,
-            workspace_id,
-            "update_api_key".to_string(),
-            format!("api_key:{}:{}", key_id, key_role),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_api_key_deleted(
-        &self,
-        workspace_id: Option<String>,
-        key_id: String,
-        key_role: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::ApiKeyDeleted,
-            workspace_id,
-            "delete_api_key".to_string(),
-            format!("api_key:{}:{}", key_id, key_role),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
-    }
-
-    pub async fn log_api_key_regenerated(
-        &self,
-        workspace_id: String,
-        key_id: String,
-        key_role: String,
-        user_id: Option<String>,
-        client_ip: Option<String>,
-    ) {
-        let event = AuditEvent::new(
-            AuditEventType::ApiKeyRegenerated,
-            Some(workspace_id),
-            "regenerate_api_key".to_string(),
-            format!("api_key:{}:{}", key_id, key_role),
-            AuditResult::Success,
-        )
-        .with_user_id(user_id.unwrap_or_default())
-        .with_client_ip(client_ip.unwrap_or_default());
-
-        self.log(event).await;
+        if let Some(ref store) = self.store {
+            store.flush().await;
+        }
+        for sink in &self.sinks {
+            sink.flush().await;
+        }
     }
 
     pub async fn get_recent_events(&self, limit: usize) -> Vec<AuditEvent> {
@@ -910,6 +611,11 @@ impl AuditLogger {
         self.total_errors.load(Ordering::SeqCst)
     }
 
+    /// 已记录但因锁竞争未进入内存最近事件的事件数
+    pub fn recent_skipped(&self) -> u64 {
+        self.recent_skipped.load(Ordering::SeqCst)
+    }
+
     pub fn get_total_logged(&self) -> u64 {
         self.total_logged.load(Ordering::SeqCst)
     }
@@ -920,35 +626,213 @@ impl AuditLogger {
     }
 }
 
+/// 事件类型的便捷方法（`log_id_generation`、`log_config_change` 等）由 trait
+/// 默认实现提供，调用时需引入 `crate::core::algorithm::AuditLogger`。
 #[async_trait]
 impl CoreAuditLoggerTrait for AuditLogger {
-    async fn log(&self, event: CoreAuditEvent) {
-        // M4 修复：AuditEventType/AuditResult 已统一为 core 层定义，
-        // 无需 match 转换。CoreAuditEvent.result 可能是 Unknown，
-        // server 端保留该语义（不再强制映射为 Failure，避免信息丢失）。
-        let server_event = AuditEvent {
-            id: next_audit_event_id(),
-            timestamp: event.timestamp,
-            event_type: event.event_type,
-            workspace_id: event.workspace_id,
-            user_id: None,
-            action: event.action,
-            resource: event.resource,
-            result: event.result,
-            details: event.details,
-            client_ip: None,
-            user_agent: None,
-            duration_ms: 0,
-            error_message: None,
+    async fn log(&self, event: AuditEvent) {
+        AuditLogger::log(self, event).await;
+    }
+}
+
+/// writer task 独占的文件写入状态：hash 链、轮转与溢写回放
+struct FileWriter {
+    path: String,
+    chain: ChainWriter,
+    rotator: LogRotator,
+    metrics: Arc<AuditWriterMetrics>,
+    errors: Arc<AtomicU64>,
+}
+
+impl FileWriter {
+    fn report_error(&self, error: impl std::fmt::Display) {
+        self.errors.fetch_add(1, Ordering::SeqCst);
+        tracing::error!(
+            "{}",
+            t!("log.server.audit.logger.persist_failed", error = error)
+        );
+    }
+
+    /// 启动时：活动文件为空但链已延续（轮转后、写入标记前退出）时补写轮转标记，
+    /// 回放上次未完成的溢写文件，并按保留期清理旧文件
+    async fn start(&mut self) {
+        let path = PathBuf::from(&self.path);
+        let active_is_empty = std::fs::metadata(&path).map_or(true, |m| m.len() == 0);
+        if active_is_empty && self.chain.last_seq() > 0 {
+            let previous = super::rotation::rotated_files(&path)
+                .ok()
+                .and_then(|files| files.last().cloned())
+                .unwrap_or_default();
+            self.write_rotation_marker(&previous);
+        }
+
+        let draining = draining_spill_path(&path);
+        if draining.exists() {
+            self.replay_spill(&draining).await;
+        }
+
+        if let Some(retention) = self.rotator.policy().retention {
+            let metrics = self.metrics.clone();
+            let errors = self.errors.clone();
+            tokio::task::spawn_blocking(move || {
+                apply_retention(&path, retention, &metrics, &errors)
+            });
+        }
+    }
+
+    async fn write_event(&mut self, event: &AuditEvent) {
+        self.rotate_if_due();
+        let result = AuditLogger::write_event_to_file(event, &self.path, &mut self.chain).await;
+        self.after_write(result);
+    }
+
+    fn after_write(&mut self, result: std::io::Result<u64>) {
+        let result = result.and_then(|bytes| {
+            self.rotator.record_write(bytes);
+            self.chain.maybe_checkpoint(false)
+        });
+        if let Err(e) = result {
+            self.report_error(e);
+        }
+    }
+
+    /// 处理完所有排队 Event 后 sync_all 确保数据落盘，并为尚未被检查点覆盖的
+    /// 记录补一个检查点。这替代了每事件 sync_all 的性能开销，仅按需 flush。
+    async fn flush(&mut self) {
+        let result = AuditLogger::sync_file(&self.path)
+            .await
+            .and_then(|_| self.chain.maybe_checkpoint(true));
+        if let Err(e) = result {
+            self.report_error(e);
+        }
+    }
+
+    /// 达到大小或时间阈值时轮转：先为旧文件的链尾补检查点，改名后新文件以
+    /// 轮转标记开头；压缩与保留清理在阻塞线程池执行，不阻塞写入。
+    fn rotate_if_due(&mut self) {
+        if !self.rotator.should_rotate() {
+            return;
+        }
+        if let Err(e) = self.chain.maybe_checkpoint(true) {
+            self.report_error(e);
+        }
+        let rotated = match self.rotator.rotate() {
+            Ok(rotated) => rotated,
+            Err(e) => {
+                self.report_error(e);
+                return;
+            }
         };
+        self.metrics.rotations_total.fetch_add(1, Ordering::Relaxed);
+        tracing::info!(
+            event = "audit_log_rotated",
+            rotated = %rotated.display(),
+            seq = self.chain.last_seq(),
+            "Audit log rotated"
+        );
+        self.write_rotation_marker(&rotated);
+
+        let policy = self.rotator.policy().clone();
+        let log_path = PathBuf::from(&self.path);
+        let metrics = self.metrics.clone();
+        let errors = self.errors.clone();
+        tokio::task::spawn_blocking(move || {
+            if policy.compress {
+                match compress_file(&rotated) {
+                    Ok(_) => {
+                        metrics.compressed_total.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(e) => {
+                        errors.fetch_add(1, Ordering::SeqCst);
+                        tracing::error!(
+                            event = "audit_log_compress_failed",
+                            file = %rotated.display(),
+                            error = %e,
+                            "Failed to compress rotated audit log"
+                        );
+                    }
+                }
+            }
+            if let Some(retention) = policy.retention {
+                apply_retention(&log_path, retention, &metrics, &errors);
+            }
+        });
+    }
 
-        AuditLogger::log(self, server_event).await;
+    fn write_rotation_marker(&mut self, previous: &Path) {
+        let previous_file = previous
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let marker = rotation_marker(&previous_file, self.chain.last_seq());
+        let result = AuditLogger::append_record(marker, &self.path, &mut self.chain);
+        self.after_write(result);
+    }
+
+    /// 按原顺序回放溢写文件中的记录（已脱敏），完成后删除该文件
+    async fn replay_spill(&mut self, spill: &Path) {
+        let file = match std::fs::File::open(spill) {
+            Ok(file) => file,
+            Err(e) => {
+                self.report_error(e);
+                return;
+            }
+        };
+        let mut replayed = 0u64;
+        for line in std::io::BufReader::new(file).lines() {
+            let record = line.map_err(|e| e.to_string()).and_then(|line| {
+                serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&line)
+                    .map_err(|e| e.to_string())
+            });
+            match record {
+                Ok(record) => {
+                    self.rotate_if_due();
+                    let result = AuditLogger::append_record(record, &self.path, &mut self.chain);
+                    self.after_write(result);
+                    replayed += 1;
+                }
+                // 崩溃导致的半行等：计入错误并继续回放其余记录
+                Err(e) => self.report_error(e),
+            }
+        }
+        tracing::info!(
+            event = "audit_spill_replayed",
+            records = replayed,
+            "Replayed spilled audit events"
+        );
+        if let Err(e) = std::fs::remove_file(spill) {
+            self.report_error(e);
+        }
+    }
+}
+
+fn apply_retention(
+    log_path: &Path,
+    retention: std::time::Duration,
+    metrics: &AuditWriterMetrics,
+    errors: &AtomicU64,
+) {
+    match enforce_retention(log_path, retention) {
+        Ok(removed) => {
+            metrics
+                .retention_deleted_total
+                .fetch_add(removed as u64, Ordering::Relaxed);
+        }
+        Err(e) => {
+            errors.fetch_add(1, Ordering::SeqCst);
+            tracing::error!(
+                event = "audit_log_retention_failed",
+                error = %e,
+                "Failed to remove expired audit logs"
+            );
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::server::audit::chain::{checkpoint_path, verify_audit_log};
 
     #[tokio::test]
     async fn test_audit_logger_creation() {
@@ -1387,25 +1271,6 @@ mod tests {
         assert!(events.is_empty());
     }
 
-    #[test]
-    fn test_next_audit_event_id_generates_unique_ids() {
-        let id1 = next_audit_event_id();
-        let id2 = next_audit_event_id();
-        let id3 = next_audit_event_id();
-        assert_ne!(id1, id2);
-        assert_ne!(id2, id3);
-        assert_ne!(id1, id3);
-    }
-
-    #[test]
-    fn test_next_audit_event_id_uses_high_44_bits_for_timestamp() {
-        // Low 20 bits are counter; high 44 bits should be >= some recent unix_ms
-        let id = next_audit_event_id();
-        let high_44 = id >> 20;
-        // Unix millis for 2024-01-01 is around 1704067200000
-        assert!(high_44 >= 1704067200000);
-    }
-
     #[test]
     fn test_audit_event_new_defaults() {
         let event = AuditEvent::new(
@@ -1480,7 +1345,7 @@ mod tests {
             .await;
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].result, AuditResult::Failure);
-        assert_eq!(events[0].client_ip.as_deref(), Some(""));
+        assert!(events[0].client_ip.is_none());
         assert_eq!(
             events[0].error_message.as_deref(),
             Some("database unavailable")
@@ -1604,14 +1469,13 @@ mod tests {
             .get_events_by_type(AuditEventType::DegradationEvent)
             .await;
         assert_eq!(events.len(), 1);
-        // Non-Critical → Partial (per server-side rule; core layer maps Normal→Success,
-        // but server logger only special-cases "Critical")
+        // 既非 Critical 也非 Normal 的状态为部分可用
         assert_eq!(events[0].result, AuditResult::Partial);
     }
 
     #[tokio::test]
-    async fn test_log_degradation_event_normal_state_is_partial() {
-        // Server-side log_degradation_event only checks == "Critical"; everything else → Partial.
+    async fn test_log_degradation_event_normal_state_is_success() {
+        // 与 core 层一致：回到 Normal 表示恢复成功
         let logger = AuditLogger::new(10);
         logger
             .log_degradation_event(
@@ -1627,7 +1491,7 @@ mod tests {
         let events = logger
             .get_events_by_type(AuditEventType::DegradationEvent)
             .await;
-        assert_eq!(events[0].result, AuditResult::Partial);
+        assert_eq!(events[0].result, AuditResult::Success);
     }
 
     // ========== log_rate_limit_exceeded coverage ==========
@@ -1701,7 +1565,7 @@ mod tests {
             .await;
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].result, AuditResult::Failure);
-        assert_eq!(events[0].client_ip.as_deref(), Some(""));
+        assert!(events[0].client_ip.is_none());
         assert_eq!(
             events[0].error_message.as_deref(),
             Some("segment exhausted")
@@ -1749,8 +1613,8 @@ mod tests {
             .get_events_by_type(AuditEventType::WorkspaceCreated)
             .await;
         assert_eq!(events.len(), 1);
-        assert_eq!(events[0].user_id.as_deref(), Some(""));
-        assert_eq!(events[0].client_ip.as_deref(), Some(""));
+        assert!(events[0].user_id.is_none());
+        assert!(events[0].client_ip.is_none());
     }
 
     #[tokio::test]
@@ -1841,8 +1705,8 @@ mod tests {
         assert_eq!(events[0].action, "update_group");
         assert_eq!(events[0].resource, "group:grp-1:Engineering Renamed");
         // None → unwrap_or_default → empty string
-        assert_eq!(events[0].user_id.as_deref(), Some(""));
-        assert_eq!(events[0].client_ip.as_deref(), Some(""));
+        assert!(events[0].user_id.is_none());
+        assert!(events[0].client_ip.is_none());
     }
 
     #[tokio::test]
@@ -1976,8 +1840,8 @@ mod tests {
             .await;
         assert_eq!(events.len(), 1);
         assert!(events[0].workspace_id.is_none());
-        assert_eq!(events[0].user_id.as_deref(), Some(""));
-        assert_eq!(events[0].client_ip.as_deref(), Some(""));
+        assert!(events[0].user_id.is_none());
+        assert!(events[0].client_ip.is_none());
     }
 
     #[tokio::test]
@@ -2064,8 +1928,8 @@ mod tests {
             .get_events_by_type(AuditEventType::ApiKeyRegenerated)
             .await;
         assert_eq!(events.len(), 1);
-        assert_eq!(events[0].user_id.as_deref(), Some(""));
-        assert_eq!(events[0].client_ip.as_deref(), Some(""));
+        assert!(events[0].user_id.is_none());
+        assert!(events[0].client_ip.is_none());
     }
 
     // ========== with_file_logging coverage ==========
@@ -2205,8 +2069,206 @@ mod tests {
         assert_eq!(logger.total_errors(), 1);
     }
 
+    #[tokio::test]
+    async fn test_with_file_options_writes_verifiable_chain_and_checkpoints() {
+        let tmp = tempfile::tempdir().expect("create temp dir");
+        let log_path = tmp.path().join("chained.log");
+        let path_str = log_path.to_str().expect("path is utf-8").to_string();
+        let signer = CheckpointSigner::new("audit-test-key");
+
+        let options = AuditFileOptions {
+            signer: signer.clone(),
+            checkpoint_interval: 2,
+            ..Default::default()
+        };
+
+        let logger = AuditLogger::with_file_options(100, path_str.clone(), options.clone()).await;
+        for i in 0..3 {
+            logger
+                .log(AuditEvent::new(
+                    AuditEventType::ConfigChange,
+                    None,
+                    format!("act-{i}"),
+                    "res".to_string(),
+                    AuditResult::Success,
+                ))
+                .await;
+        }
+        logger.flush().await;
+
+        // seq 2 到达间隔，flush 为 seq 3 补一个检查点
+        let checkpoints = std::fs::read_to_string(checkpoint_path(&log_path))
+            .expect("checkpoint file should exist");
+        assert_eq!(checkpoints.lines().count(), 2);
+
+        // 重启后续写同一条链
+        let logger = AuditLogger::with_file_options(100, path_str, options).await;
+        logger
+            .log(AuditEvent::new(
+                AuditEventType::ConfigChange,
+                None,
+                "after-restart".to_string(),
+                "res".to_string(),
+                AuditResult::Success,
+            ))
+            .await;
+        logger.flush().await;
+
+        let report = verify_audit_log(&log_path, signer.as_ref()).expect("verify");
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.last_seq, 4);
+        assert_eq!(report.checkpoints, 3);
+        assert_eq!(logger.total_errors(), 0);
+    }
+
+    fn generation_event(i: usize) -> AuditEvent {
+        AuditEvent::new(
+            AuditEventType::IdGeneration,
+            Some("ws".to_string()),
+            format!("gen-{i}"),
+            "res".to_string(),
+            AuditResult::Success,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_log_generation_waits_for_queue_under_block_policy() {
+        let tmp = tempfile::tempdir().expect("create temp dir");
+        let log_path = tmp.path().join("block.log");
+        let options = AuditFileOptions {
+            channel_capacity: 1,
+            overflow_policy: AuditOverflowPolicy::Block,
+            ..Default::default()
+        };
+        let logger =
+            AuditLogger::with_file_options(100, log_path.to_str().unwrap().to_string(), options)
+                .await;
+
+        for i in 0..5 {
+            logger.log_generation(generation_event(i)).await;
+        }
+        logger.flush().await;
+
+        let metrics = logger.writer_metrics().expect("file logging enabled");
+        assert_eq!(metrics.dropped_total.load(Ordering::Relaxed), 0);
+        assert_eq!(metrics.enqueued_total.load(Ordering::Relaxed), 5);
+        assert!(metrics.blocked_total.load(Ordering::Relaxed) > 0);
+        assert_eq!(logger.total_logged(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_log_generation_drops_when_nonblocking_generation_enabled() {
+        let tmp = tempfile::tempdir().expect("create temp dir");
+        let log_path = tmp.path().join("nonblocking.log");
+        let options = AuditFileOptions {
+            channel_capacity: 1,
+            overflow_policy: AuditOverflowPolicy::Block,
+            nonblocking_generation: true,
+            ..Default::default()
+        };
+        let logger =
+            AuditLogger::with_file_options(100, log_path.to_str().unwrap().to_string(), options)
+                .await;
+
+        // 单线程 runtime 下 writer 在循环期间得不到调度，队列始终满
+        for i in 0..5 {
+            logger.log_generation(generation_event(i)).await;
+        }
+
+        let metrics = logger.writer_metrics().expect("file logging enabled");
+        assert_eq!(metrics.dropped_total.load(Ordering::Relaxed), 4);
+        assert_eq!(metrics.blocked_total.load(Ordering::Relaxed), 0);
+        assert_eq!(logger.total_logged(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_try_log_counts_event_when_recent_buffer_is_busy() {
+        let logger = AuditLogger::new(100);
+        {
+            let _guard = logger.events.lock().await;
+            logger.try_log(generation_event(0));
+        }
+        logger.try_log(generation_event(1));
+
+        assert_eq!(logger.total_logged(), 2);
+        assert_eq!(logger.recent_skipped(), 1);
+        let events = logger.get_recent_events(10).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].action, "gen-1");
+    }
+
+    #[tokio::test]
+    async fn test_with_file_options_rotates_compresses_and_keeps_chain() {
+        let tmp = tempfile::tempdir().expect("create temp dir");
+        let log_path = tmp.path().join("rotating.log");
+        let path_str = log_path.to_str().expect("path is utf-8").to_string();
+        let signer = CheckpointSigner::new("audit-test-key");
+
+        let logger = AuditLogger::with_file_options(
+            100,
+            path_str,
+            AuditFileOptions {
+                signer: signer.clone(),
+                rotation: RotationPolicy {
+                    // 每条记录约 300 字节：每写两条轮转一次
+                    max_bytes: Some(400),
+                    compress: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .await;
+        for i in 0..5 {
+            logger
+                .log(AuditEvent::new(
+                    AuditEventType::IdGeneration,
+                    Some("ws".to_string()),
+                    format!("act-{i}"),
+                    "res".to_string(),
+                    AuditResult::Success,
+                ))
+                .await;
+        }
+        logger.flush().await;
+
+        let metrics = logger.writer_metrics().expect("file logging enabled");
+        let rotations = metrics.rotations_total.load(Ordering::Relaxed);
+        assert!(rotations >= 2, "expected rotations, got {rotations}");
+        assert_eq!(metrics.enqueued_total.load(Ordering::Relaxed), 5);
+
+        // 等待后台压缩完成
+        for _ in 0..50 {
+            if metrics.compressed_total.load(Ordering::Relaxed) == rotations {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let rotated = crate::server::audit::rotation::rotated_files(&log_path).unwrap();
+        assert_eq!(rotated.len() as u64, rotations);
+        assert!(rotated
+            .iter()
+            .all(|p| p.extension().is_some_and(|e| e == "gz")));
+
+        // 5 个事件 + 每次轮转一条标记，跨文件构成一条完整的链
+        let report = verify_audit_log(&log_path, signer.as_ref()).expect("verify");
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.last_seq, 5 + rotations);
+        assert_eq!(report.files.len() as u64, rotations + 1);
+        assert_eq!(logger.total_errors(), 0);
+    }
+
     // ========== write_event_to_file direct tests ==========
 
+    fn chain_for(path: &str) -> ChainWriter {
+        ChainWriter::open(
+            std::path::Path::new(path),
+            None,
+            DEFAULT_CHECKPOINT_INTERVAL,
+        )
+        .expect("resume chain")
+    }
+
     #[tokio::test]
     async fn test_write_event_to_file_invalid_path_returns_err() {
         let event = AuditEvent::new(
@@ -2217,7 +2279,8 @@ mod tests {
             AuditResult::Success,
         );
         // Path to a directory that doesn't exist
-        let result = AuditLogger::write_event_to_file(&event, "/nonexistent_dir/audit.log").await;
+        let path = "/nonexistent_dir/audit.log";
+        let result = AuditLogger::write_event_to_file(&event, path, &mut chain_for(path)).await;
         assert!(result.is_err());
     }
 
@@ -2240,7 +2303,7 @@ mod tests {
         .with_duration(5)
         .with_error("no error".to_string());
 
-        AuditLogger::write_event_to_file(&event, &path_str)
+        AuditLogger::write_event_to_file(&event, &path_str, &mut chain_for(&path_str))
             .await
             .expect("write should succeed");
 
@@ -2270,7 +2333,8 @@ mod tests {
                 "res".to_string(),
                 AuditResult::Success,
             );
-            AuditLogger::write_event_to_file(&event, &path_str)
+            // 每次重新打开链写入器，验证从已有文件续接 seq
+            AuditLogger::write_event_to_file(&event, &path_str, &mut chain_for(&path_str))
                 .await
                 .expect("write should succeed");
         }
@@ -2278,6 +2342,13 @@ mod tests {
         let content = std::fs::read_to_string(&log_path).expect("file should exist");
         let lines: Vec<&str> = content.trim().lines().collect();
         assert_eq!(lines.len(), 3);
+        for (i, line) in lines.iter().enumerate() {
+            let parsed: serde_json::Value = serde_json::from_str(line).expect("valid JSON");
+            assert_eq!(parsed["seq"], i as u64 + 1);
+        }
+        assert!(verify_audit_log(&log_path, None)
+            .expect("verify should read the log")
+            .is_valid());
     }
 
     #[tokio::test]
@@ -2296,7 +2367,7 @@ mod tests {
         .with_client_ip("2001:db8::1".to_string())
         .with_user_agent("UA".to_string());
 
-        AuditLogger::write_event_to_file(&event, &path_str)
+        AuditLogger::write_event_to_file(&event, &path_str, &mut chain_for(&path_str))
             .await
             .expect("write should succeed");
 
@@ -2413,11 +2484,9 @@ mod tests {
     // ========== CoreAuditLoggerTrait impl coverage ==========
 
     #[tokio::test]
-    async fn test_core_audit_logger_trait_log_converts_and_logs() {
-        use crate::core::algorithm::AuditEvent as CoreAuditEvent;
-
+    async fn test_core_audit_logger_trait_log_delegates_to_logger() {
         let logger = AuditLogger::new(10);
-        let core_event = CoreAuditEvent::new(
+        let core_event = AuditEvent::new(
             AuditEventType::DegradationEvent,
             Some("ws-core".to_string()),
             "degrade".to_string(),
@@ -2427,14 +2496,12 @@ mod tests {
         .with_details(serde_json::json!({"reason": "circuit_open"}));
 
         // Use the trait method (not the inherent method)
-        use crate::core::algorithm::AuditLogger as CoreAuditLoggerTrait;
         CoreAuditLoggerTrait::log(&logger, core_event).await;
 
         assert_eq!(logger.total_logged(), 1);
         let events = logger.get_recent_events(1).await;
         assert_eq!(events.len(), 1);
-        // Server-side AuditEvent has id (from next_audit_event_id), client_ip=None,
-        // user_agent=None, duration_ms=0, error_message=None
+        // 未设置的字段保持默认：client_ip / user_agent / error_message 为 None
         assert!(events[0].id > 0);
         assert!(events[0].client_ip.is_none());
         assert!(events[0].user_agent.is_none());
@@ -2443,30 +2510,19 @@ mod tests {
         assert_eq!(events[0].workspace_id.as_deref(), Some("ws-core"));
         assert_eq!(events[0].result, AuditResult::Partial);
         assert_eq!(events[0].event_type, AuditEventType::DegradationEvent);
-        // details carried over from core event
+        // details carried over unchanged
         assert!(events[0].details.is_some());
     }
 
     #[tokio::test]
     async fn test_core_audit_logger_trait_log_unknown_result_preserved() {
-        // CoreAuditEvent can have result=Unknown; server-side preserves it
-        // (M4 fix: no longer forced to Failure).
-        use crate::core::algorithm::{
-            AuditEvent as CoreAuditEvent, AuditLogger as CoreAuditLoggerTrait,
-        };
-
+        // result=Unknown is preserved (M4 fix: no longer forced to Failure).
         let logger = AuditLogger::new(10);
-        let core_event = CoreAuditEvent {
-            event_type: AuditEventType::HealthCheck,
-            workspace_id: None,
-            action: "check".to_string(),
-            resource: "health".to_string(),
-            result: AuditResult::Unknown,
-            details: None,
-            timestamp: chrono::Utc::now(),
-        };
+        let event = AuditEvent::builder(AuditEventType::HealthCheck, "check", "health")
+            .result(AuditResult::Unknown)
+            .build();
 
-        CoreAuditLoggerTrait::log(&logger, core_event).await;
+        CoreAuditLoggerTrait::log(&logger, event).await;
 
         let events = logger.get_recent_events(1).await;
         assert_eq!(events[0].result, AuditResult::Unknown);
@@ -2476,10 +2532,17 @@ mod tests {
 
     #[tokio::test]
     async fn test_log_with_closed_file_channel_increments_errors() {
-        // Construct a logger manually with a dead sender (receiver dropped).
-        // Tests the `tx.send().is_err()` branch in log().
-        let (tx, rx) = mpsc::unbounded_channel::<AuditCommand>();
-        drop(rx); // Close the channel
+        // Construct a logger manually with a closed queue (writer gone).
+        // Tests the `push_event().is_err()` branch in log().
+        let tmp = tempfile::tempdir().expect("create temp dir");
+        let queue = Arc::new(AuditQueue::new(
+            &tmp.path().join("closed.log"),
+            10,
+            AuditOverflowPolicy::Block,
+            Arc::new(AuditWriterMetrics::new(10, AuditOverflowPolicy::Block)),
+        ));
+        queue.close();
+        let tx = Arc::new(AuditQueueSender(queue));
 
         let logger = AuditLogger {
             events: Arc::new(Mutex::new(VecDeque::with_capacity(10))),
@@ -2488,6 +2551,9 @@ mod tests {
             total_errors: Arc::new(AtomicU64::new(0)),
             file_tx: Some(tx),
             _writer_task: None,
+            store: None,
+            sinks: Vec::new(),
+            capture: None,
         };
 
         let event = AuditEvent::new(
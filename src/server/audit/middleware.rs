@@ -12,16 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! HTTP audit middleware.
+//!
+//! 每个请求记录一条审计事件：按方法与路由归类事件类型（见 [`classify_request`]），
+//! 以认证层附加的 [`AuthenticatedKey`] 归因调用方（`user_id` 为 key_id，
+//! workspace 为 key 绑定的 workspace），并在 details 中带上 `request_id`，
+//! 与响应头 `x-request-id` 和 `ApiErrorResponse.request_id` 对应。
+//!
+//! ID 生成请求的审计事件用 [`AuditLogger::log_generation`] 记录：Block 策略下等待写入队列
+//! （不丢事件），除非开启 `audit.nonblocking_generation`；其他策略下不等待。
+
 use crate::server::audit::{AuditEventType, AuditLogger, AuditResult};
-use crate::server::middleware::ApiKeyAuth;
+use crate::server::middleware::{ApiKeyAuth, AuthenticatedKey, RequestId};
 use crate::server::rate_limit::RateLimiter;
 use sdforge::axum::body::Body;
-use sdforge::axum::http::Request;
+use sdforge::axum::extract::State;
+use sdforge::axum::http::{Method, Request, StatusCode};
 use sdforge::axum::response::Response;
 use std::net::IpAddr;
 use std::sync::Arc;
 use tracing::debug;
 
+/// 探针端点调用频繁且不涉及数据，不写入审计轨迹
+const UNAUDITED_PATHS: [&str; 3] = ["/health", "/ready", "/live"];
+
 #[derive(Clone)]
 #[allow(dead_code)]
 pub struct AuditMiddleware {
@@ -55,52 +69,85 @@ impl AuditMiddleware {
         req: Request<Body>,
         next: sdforge::axum::middleware::Next,
     ) -> Response {
-        let start = std::time::Instant::now();
         let path = req.uri().path().to_string();
-        let method = req.method().to_string();
+        if UNAUDITED_PATHS.contains(&path.as_str()) {
+            return next.run(req).await;
+        }
+
+        let start = std::time::Instant::now();
+        let method = req.method().clone();
         let client_ip = get_client_ip(&req, &self.trusted_proxies);
         let user_agent = req
             .headers()
             .get("user-agent")
             .and_then(|h| h.to_str().ok())
             .map(|s| s.to_string());
-
-        let workspace_id = req.extensions().get::<String>().cloned();
+        let request_id = req.extensions().get::<RequestId>().map(|id| id.0.clone());
+        // 审计层在认证层外侧时，调用方只能从响应扩展取回；内侧时请求中已存在
+        let request_principal = req.extensions().get::<AuthenticatedKey>().cloned();
 
         let response = next.run(req).await;
 
         let duration_ms = start.elapsed().as_millis() as u64;
-        let status_code = response.status().as_u16();
-
-        let result = if (200..300).contains(&status_code) {
-            AuditResult::Success
-        } else if (400..500).contains(&status_code) {
-            AuditResult::Failure
-        } else {
-            AuditResult::Partial
+        let status = response.status();
+        let principal = response
+            .extensions()
+            .get::<AuthenticatedKey>()
+            .cloned()
+            .or(request_principal);
+
+        let event_type = match status {
+            StatusCode::UNAUTHORIZED if principal.is_none() => AuditEventType::Authentication,
+            StatusCode::TOO_MANY_REQUESTS => AuditEventType::RateLimitExceeded,
+            _ => classify_request(&method, &path),
         };
-
-        let action = format!("{} {}", method, path);
-
-        let audit_event = crate::server::audit::AuditEvent::new(
-            AuditEventType::IdGeneration,
+        let is_generation = matches!(
+            event_type,
+            AuditEventType::IdGeneration | AuditEventType::BatchGeneration
+        );
+        let result = result_for_status(status);
+        let workspace_id = principal
+            .as_ref()
+            .and_then(|p| p.workspace_id)
+            .map(|id| id.to_string());
+
+        let mut audit_event = crate::server::audit::AuditEvent::new(
+            event_type,
             workspace_id.clone(),
-            action,
+            format!("{} {}", method, path),
             path.clone(),
-            result,
+            result.clone(),
         )
+        .with_details(serde_json::json!({
+            "request_id": request_id,
+            "status": status.as_u16(),
+            "role": principal.as_ref().map(|p| p.role.to_string()),
+        }))
         .with_client_ip(client_ip.unwrap_or_default())
         .with_user_agent(user_agent.unwrap_or_default())
         .with_duration(duration_ms);
+        if let Some(ref principal) = principal {
+            audit_event = audit_event.with_user_id(principal.key_id.clone());
+        }
+        if let Some(ref request_id) = request_id {
+            audit_event = audit_event.with_request_id(request_id.clone());
+        }
+        if result == AuditResult::Failure {
+            audit_event = audit_event.with_error(format!("HTTP {}", status));
+        }
 
-        self.audit_logger.log(audit_event).await;
+        if is_generation {
+            self.audit_logger.log_generation(audit_event).await;
+        } else {
+            self.audit_logger.log(audit_event).await;
+        }
 
         if let Some(ws_id) = workspace_id {
             debug!(
                 workspace_id = ws_id,
                 path = path,
-                method = method,
-                status = status_code,
+                method = %method,
+                status = status.as_u16(),
                 duration_ms = duration_ms,
                 "{}",
                 t!("log.server.audit.middleware.request_recorded")
@@ -111,6 +158,91 @@ impl AuditMiddleware {
     }
 }
 
+/// `from_fn_with_state` 适配函数（axum 要求自由函数）
+pub async fn audit_middleware_fn(
+    State(mid): State<Arc<AuditMiddleware>>,
+    req: Request<Body>,
+    next: sdforge::axum::middleware::Next,
+) -> Response {
+    mid.audit_middleware(req, next).await
+}
+
+/// 按方法与 `/api/{version}` 之后的路由归类事件类型；只读请求及未知路由
+/// 归为 `ApiAccess`。
+pub fn classify_request(method: &Method, path: &str) -> AuditEventType {
+    if path == "/metrics" {
+        return AuditEventType::MetricsAccess;
+    }
+    // 去掉 `/api/v1` 前缀，兼容后续版本
+    let route = path
+        .strip_prefix("/api/")
+        .and_then(|rest| rest.find('/').map(|i| &rest[i..]))
+        .unwrap_or(path);
+    let segments: Vec<&str> = route.split('/').filter(|s| !s.is_empty()).collect();
+
+    match segments.as_slice() {
+        ["generate", "batch"] if method == Method::POST => AuditEventType::BatchGeneration,
+        ["generate"] if method == Method::POST => AuditEventType::IdGeneration,
+        _ if is_read(method) => AuditEventType::ApiAccess,
+        ["config", ..] => AuditEventType::ConfigChange,
+        ["admin", "degradation", ..] => AuditEventType::DegradationEvent,
+        ["admin", "bans", ..] => AuditEventType::AuthBanLifted,
+        ["workspaces", _, "regenerate-user-key"] => AuditEventType::ApiKeyRegenerated,
+        ["api-keys", _, "rotate"] => AuditEventType::ApiKeyRegenerated,
+        ["api-keys", ..] => by_method(
+            method,
+            AuditEventType::ApiKeyCreated,
+            AuditEventType::ApiKeyUpdated,
+            AuditEventType::ApiKeyDeleted,
+        ),
+        ["workspaces", ..] => by_method(
+            method,
+            AuditEventType::WorkspaceCreated,
+            AuditEventType::WorkspaceUpdated,
+            AuditEventType::WorkspaceDeleted,
+        ),
+        ["groups", ..] => by_method(
+            method,
+            AuditEventType::GroupCreated,
+            AuditEventType::GroupUpdated,
+            AuditEventType::GroupDeleted,
+        ),
+        ["biz-tags", ..] => by_method(
+            method,
+            AuditEventType::BizTagCreated,
+            AuditEventType::BizTagUpdated,
+            AuditEventType::BizTagDeleted,
+        ),
+        _ => AuditEventType::ApiAccess,
+    }
+}
+
+fn is_read(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+fn by_method(
+    method: &Method,
+    created: AuditEventType,
+    updated: AuditEventType,
+    deleted: AuditEventType,
+) -> AuditEventType {
+    match *method {
+        Method::POST => created,
+        Method::DELETE => deleted,
+        _ => updated,
+    }
+}
+
+/// 1xx–3xx 为成功，4xx/5xx 均为失败；`Partial` 留给批量操作部分成功的场景
+pub fn result_for_status(status: StatusCode) -> AuditResult {
+    if status.is_client_error() || status.is_server_error() {
+        AuditResult::Failure
+    } else {
+        AuditResult::Success
+    }
+}
+
 fn get_client_ip(req: &Request<Body>, trusted_proxies: &[IpAddr]) -> Option<String> {
     // Phase 9 T043 (LOW L3) — delegate to the single shared implementation
     // in `server::middleware::utils`. Previously this was a duplicate copy
@@ -158,6 +290,7 @@ mod tests {
                         enabled: true,
                         expires_at: None,
                         created_at: chrono::Utc::now().naive_utc(),
+                        scopes: crate::core::auth::ApiKeyScopes::unrestricted(),
                     },
                     key_secret: "mock_secret".to_string(),
                 })
@@ -274,9 +407,8 @@ mod tests {
     // 提取到模块级以便多个测试共享。
 
     use async_trait::async_trait;
-    use sdforge::axum::extract::State;
-    use sdforge::axum::http::StatusCode;
     use sdforge::axum::middleware::from_fn_with_state;
+    use sdforge::axum::response::IntoResponse;
     use sdforge::axum::routing::get;
     use sdforge::axum::Router;
     use sdforge::tower::ServiceExt;
@@ -303,6 +435,7 @@ mod tests {
                     enabled: true,
                     expires_at: None,
                     created_at: chrono::Utc::now().naive_utc(),
+                    scopes: crate::core::auth::ApiKeyScopes::unrestricted(),
                 },
                 key_secret: "mock_secret".to_string(),
             })
@@ -399,16 +532,6 @@ mod tests {
         (mid, audit_logger)
     }
 
-    /// 包装 AuditMiddleware::audit_middleware 方法为 axum middleware 函数。
-    /// from_fn_with_state 要求自由函数，方法必须通过 wrapper 调用。
-    async fn audit_middleware_fn(
-        State(mid): State<Arc<AuditMiddleware>>,
-        req: Request<Body>,
-        next: sdforge::axum::middleware::Next,
-    ) -> Response {
-        mid.audit_middleware(req, next).await
-    }
-
     fn build_ok_router(mid: Arc<AuditMiddleware>) -> Router {
         Router::new()
             .route("/test", get(|| async { "ok" }))
@@ -460,7 +583,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_audit_middleware_5xx_response_logs_partial_result() {
+    async fn test_audit_middleware_5xx_response_logs_failure_result() {
         let (mid, logger) = make_audit_middleware();
         let router = build_status_router(mid, StatusCode::INTERNAL_SERVER_ERROR);
         let resp = router
@@ -470,12 +593,16 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
         let events = logger.get_recent_events(10).await;
         assert_eq!(events.len(), 1);
-        assert_eq!(events[0].result, AuditResult::Partial);
+        assert_eq!(events[0].result, AuditResult::Failure);
+        assert_eq!(
+            events[0].error_message.as_deref(),
+            Some("HTTP 500 Internal Server Error")
+        );
     }
 
     #[tokio::test]
-    async fn test_audit_middleware_3xx_response_logs_partial_result() {
-        // 3xx 不在 200..300 也不在 400..500，应归为 Partial
+    async fn test_audit_middleware_3xx_response_logs_success_result() {
+        // 重定向不是失败
         let (mid, logger) = make_audit_middleware();
         let router = build_status_router(mid, StatusCode::MOVED_PERMANENTLY);
         let resp = router
@@ -485,7 +612,7 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
         let events = logger.get_recent_events(10).await;
         assert_eq!(events.len(), 1);
-        assert_eq!(events[0].result, AuditResult::Partial);
+        assert_eq!(events[0].result, AuditResult::Success);
     }
 
     // ========== audit_middleware request enrichment tests ==========
@@ -521,19 +648,188 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_audit_middleware_with_workspace_id_extension_records_it() {
+    async fn test_audit_middleware_attributes_authenticated_key() {
         let (mid, logger) = make_audit_middleware();
         let router = build_ok_router(mid);
+        let workspace = Uuid::new_v4();
         let req = Request::builder()
             .method("GET")
             .uri("/test")
-            .extension("workspace-123".to_string())
+            .extension(AuthenticatedKey {
+                key_id: "nino_user_key".to_string(),
+                role: crate::server::middleware::ApiKeyRole::User,
+                workspace_id: Some(workspace),
+            })
             .body(Body::empty())
             .unwrap();
         let _resp = router.oneshot(req).await.unwrap();
         let events = logger.get_recent_events(10).await;
         assert_eq!(events.len(), 1);
-        assert_eq!(events[0].workspace_id.as_deref(), Some("workspace-123"));
+        assert_eq!(events[0].workspace_id, Some(workspace.to_string()));
+        assert_eq!(events[0].user_id.as_deref(), Some("nino_user_key"));
+        assert_eq!(events[0].details.as_ref().unwrap()["role"], "user");
+    }
+
+    #[tokio::test]
+    async fn test_audit_middleware_records_generation_without_waiting() {
+        let (mid, logger) = make_audit_middleware();
+        let router = Router::new()
+            .route(
+                "/api/v1/generate/batch",
+                sdforge::axum::routing::post(|| async { "ok" }),
+            )
+            .layer(from_fn_with_state(mid, audit_middleware_fn));
+        let _resp = router
+            .oneshot(make_test_request("POST", "/api/v1/generate/batch"))
+            .await
+            .unwrap();
+        let events = logger.get_recent_events(10).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, AuditEventType::BatchGeneration);
+    }
+
+    #[tokio::test]
+    async fn test_audit_middleware_reads_principal_from_response_extensions() {
+        // 生产中审计层位于认证层外侧，调用方由认证层写入响应扩展
+        let (mid, logger) = make_audit_middleware();
+        let router = Router::new()
+            .route(
+                "/api/v1/config/reload",
+                sdforge::axum::routing::post(|| async {
+                    let mut resp = "ok".into_response();
+                    resp.extensions_mut().insert(AuthenticatedKey {
+                        key_id: "nino_admin_key".to_string(),
+                        role: crate::server::middleware::ApiKeyRole::Admin,
+                        workspace_id: None,
+                    });
+                    resp
+                }),
+            )
+            .layer(from_fn_with_state(mid, audit_middleware_fn));
+        let _resp = router
+            .oneshot(make_test_request("POST", "/api/v1/config/reload"))
+            .await
+            .unwrap();
+        let events = logger.get_recent_events(10).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, AuditEventType::ConfigChange);
+        assert_eq!(events[0].user_id.as_deref(), Some("nino_admin_key"));
+        assert!(events[0].workspace_id.is_none());
+        assert_eq!(events[0].details.as_ref().unwrap()["role"], "admin");
+    }
+
+    #[tokio::test]
+    async fn test_audit_middleware_unauthenticated_401_is_authentication_event() {
+        let (mid, logger) = make_audit_middleware();
+        let router = build_status_router(mid, StatusCode::UNAUTHORIZED);
+        let _resp = router
+            .oneshot(make_test_request("GET", "/test"))
+            .await
+            .unwrap();
+        let events = logger.get_recent_events(10).await;
+        assert_eq!(events[0].event_type, AuditEventType::Authentication);
+        assert!(events[0].user_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_audit_middleware_records_request_id() {
+        let (mid, logger) = make_audit_middleware();
+        let router = build_ok_router(mid).layer(sdforge::axum::middleware::from_fn(
+            crate::server::middleware::request_id_middleware,
+        ));
+        let req = Request::builder()
+            .uri("/test")
+            .header("x-request-id", "req-42")
+            .body(Body::empty())
+            .unwrap();
+        let resp = router.oneshot(req).await.unwrap();
+        assert_eq!(resp.headers()["x-request-id"], "req-42");
+        let events = logger.get_recent_events(10).await;
+        assert_eq!(events[0].details.as_ref().unwrap()["request_id"], "req-42");
+    }
+
+    #[tokio::test]
+    async fn test_audit_middleware_skips_probe_endpoints() {
+        let (mid, logger) = make_audit_middleware();
+        let router = Router::new()
+            .route("/live", get(|| async { "ok" }))
+            .layer(from_fn_with_state(mid, audit_middleware_fn));
+        let _resp = router
+            .oneshot(make_test_request("GET", "/live"))
+            .await
+            .unwrap();
+        assert_eq!(logger.get_total_logged(), 0);
+    }
+
+    #[test]
+    fn test_classify_request_maps_routes_to_event_types() {
+        let cases = [
+            ("POST", "/api/v1/generate", AuditEventType::IdGeneration),
+            (
+                "POST",
+                "/api/v1/generate/batch",
+                AuditEventType::BatchGeneration,
+            ),
+            (
+                "POST",
+                "/api/v1/config/algorithm",
+                AuditEventType::ConfigChange,
+            ),
+            ("GET", "/api/v1/config", AuditEventType::ApiAccess),
+            ("POST", "/api/v1/api-keys", AuditEventType::ApiKeyCreated),
+            (
+                "DELETE",
+                "/api/v1/api-keys/abc",
+                AuditEventType::ApiKeyDeleted,
+            ),
+            (
+                "POST",
+                "/api/v1/api-keys/nino_abc/rotate",
+                AuditEventType::ApiKeyRegenerated,
+            ),
+            (
+                "POST",
+                "/api/v1/workspaces/acme/regenerate-user-key",
+                AuditEventType::ApiKeyRegenerated,
+            ),
+            (
+                "POST",
+                "/api/v1/workspaces",
+                AuditEventType::WorkspaceCreated,
+            ),
+            ("POST", "/api/v1/groups", AuditEventType::GroupCreated),
+            ("POST", "/api/v1/biz-tags", AuditEventType::BizTagCreated),
+            ("PUT", "/api/v1/biz-tags/1", AuditEventType::BizTagUpdated),
+            (
+                "DELETE",
+                "/api/v1/biz-tags/1",
+                AuditEventType::BizTagDeleted,
+            ),
+            (
+                "POST",
+                "/api/v1/admin/degradation/segment/degrade",
+                AuditEventType::DegradationEvent,
+            ),
+            (
+                "DELETE",
+                "/api/v1/admin/bans/0190a1b2-0000-7000-8000-000000000000",
+                AuditEventType::AuthBanLifted,
+            ),
+            ("GET", "/api/v1/admin/bans", AuditEventType::ApiAccess),
+            ("GET", "/api/v1/biz-tags", AuditEventType::ApiAccess),
+            ("POST", "/api/v1/parse", AuditEventType::ApiAccess),
+            ("GET", "/metrics", AuditEventType::MetricsAccess),
+        ];
+        for (method, path, expected) in cases {
+            let method: Method = method.parse().unwrap();
+            assert_eq!(
+                classify_request(&method, path),
+                expected,
+                "{} {}",
+                method,
+                path
+            );
+        }
     }
 
     #[tokio::test]
@@ -635,7 +931,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_audit_middleware_event_type_is_id_generation() {
+    async fn test_audit_middleware_unknown_read_is_api_access() {
         let (mid, logger) = make_audit_middleware();
         let router = build_ok_router(mid);
         let _resp = router
@@ -644,7 +940,7 @@ mod tests {
             .unwrap();
         let events = logger.get_recent_events(10).await;
         assert_eq!(events.len(), 1);
-        assert_eq!(events[0].event_type, AuditEventType::IdGeneration);
+        assert_eq!(events[0].event_type, AuditEventType::ApiAccess);
     }
 
     #[tokio::test]
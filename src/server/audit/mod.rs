@@ -14,9 +14,21 @@
 
 //! Audit module for logging and middleware.
 
+pub mod chain;
 pub mod logger;
 pub mod middleware;
+pub mod queue;
+pub mod rotation;
+pub mod sinks;
+pub mod store;
+pub mod testing;
 
 // Re-exports
-pub use logger::{AuditEvent, AuditEventType, AuditLogger, AuditResult};
+pub use chain::{verify_audit_log, AuditCheckpoint, ChainIssue, CheckpointSigner, VerifyReport};
+pub use logger::{AuditEvent, AuditEventType, AuditFileOptions, AuditLogger, AuditResult};
 pub use middleware::AuditMiddleware;
+pub use queue::AuditWriterMetrics;
+pub use rotation::RotationPolicy;
+pub use sinks::{AuditSink, AuditSinkMetrics, AuditSinkOptions, SinkFilter};
+pub use store::AuditStoreOptions;
+pub use testing::AuditCapture;
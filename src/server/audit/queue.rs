@@ -0,0 +1,550 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bounded queue between `AuditLogger::log` and the file writer task.
+//!
+//! 替代原 `mpsc::unbounded_channel`：磁盘写入停顿时队列不再无限增长，
+//! 满队列按 [`AuditOverflowPolicy`] 处理（阻塞 / 丢弃最旧 / 溢写磁盘），
+//! 处理结果计入 [`AuditWriterMetrics`]。
+//!
+//! Spill 模式下一旦发生溢写，后续事件也追加到溢写文件（保持时间顺序），
+//! writer 处理完队列中更早的事件后整体回放溢写文件，再恢复走内存队列。
+//! 溢写只在队列锁内序列化并暂存，文件追加在 `spawn_blocking` 中完成，
+//! 入队方不在 tokio worker 上做磁盘 I/O。
+
+use super::logger::AuditEvent;
+use crate::core::config::AuditOverflowPolicy;
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// Writer task 命令：写入事件或显式 flush（sync_all 落盘）。
+///
+/// `Flush` 变体携带 oneshot Sender，writer task 处理完所有排队 Event 后
+/// sync_all 文件并回复，使调用方可以确定性等待持久化完成（替代 sleep）。
+pub(crate) enum AuditCommand {
+    /// 写入一条审计事件到文件
+    Event(Box<AuditEvent>),
+    /// Flush：sync_all 确保所有已写入数据落盘，完成后回复 oneshot
+    Flush(tokio::sync::oneshot::Sender<()>),
+}
+
+/// writer task 从队列取出的下一项工作
+pub(crate) enum QueueItem {
+    Command(AuditCommand),
+    /// 回放溢写文件（已从 `<log_path>.spill` 改名，回放完成后由 writer 删除）
+    Spilled(PathBuf),
+}
+
+/// 审计写入链路指标（队列、溢出策略、轮转），由 `/metrics` 暴露
+#[derive(Debug)]
+pub struct AuditWriterMetrics {
+    pub capacity: usize,
+    pub overflow_policy: AuditOverflowPolicy,
+    /// 当前排队事件数
+    pub depth: AtomicU64,
+    pub enqueued_total: AtomicU64,
+    /// 丢弃的事件数：DropOldest 挤出的，以及 Block 策略下不等待入队时丢弃的
+    pub dropped_total: AtomicU64,
+    /// Spill 写入溢写文件的事件数
+    pub spilled_total: AtomicU64,
+    /// Block 模式下因队列满而等待的入队次数
+    pub blocked_total: AtomicU64,
+    pub rotations_total: AtomicU64,
+    pub compressed_total: AtomicU64,
+    pub retention_deleted_total: AtomicU64,
+}
+
+impl AuditWriterMetrics {
+    pub fn new(capacity: usize, overflow_policy: AuditOverflowPolicy) -> Self {
+        Self {
+            capacity,
+            overflow_policy,
+            depth: AtomicU64::new(0),
+            enqueued_total: AtomicU64::new(0),
+            dropped_total: AtomicU64::new(0),
+            spilled_total: AtomicU64::new(0),
+            blocked_total: AtomicU64::new(0),
+            rotations_total: AtomicU64::new(0),
+            compressed_total: AtomicU64::new(0),
+            retention_deleted_total: AtomicU64::new(0),
+        }
+    }
+}
+
+/// 入队失败：writer 已退出（队列关闭）或溢写文件写入失败
+#[derive(Debug)]
+pub(crate) enum EnqueueError {
+    Closed,
+    Spill(io::Error),
+}
+
+impl std::fmt::Display for EnqueueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnqueueError::Closed => write!(f, "file writer channel closed"),
+            EnqueueError::Spill(e) => write!(f, "audit spill write failed: {}", e),
+        }
+    }
+}
+
+/// [`AuditQueue::enqueue`] 的结果
+enum Enqueue {
+    Done(Result<(), EnqueueError>),
+    /// Block 策略下队列已满，事件原样交还
+    Full(Box<AuditEvent>),
+}
+
+struct QueueState {
+    buf: VecDeque<AuditCommand>,
+    /// `buf` 中 Event 的数量（Flush 不占容量）
+    events: usize,
+    /// 溢写文件中有待回放的事件
+    spilling: bool,
+    closed: bool,
+}
+
+/// 待追加到溢写文件的记录；`writing` 为真时已有 blocking 任务在追加
+#[derive(Default)]
+struct SpillPending {
+    lines: Vec<u8>,
+    writing: bool,
+}
+
+/// 溢写文件追加器，由 blocking 任务按入队顺序写入暂存的记录
+struct SpillWriter {
+    path: PathBuf,
+    pending: std::sync::Mutex<SpillPending>,
+    /// 追加完成后唤醒 writer 回放
+    not_empty: Arc<Notify>,
+}
+
+impl SpillWriter {
+    fn lock(&self) -> std::sync::MutexGuard<'_, SpillPending> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 没有暂存或正在追加的记录，溢写文件可以改名回放
+    fn is_idle(&self) -> bool {
+        let pending = self.lock();
+        pending.lines.is_empty() && !pending.writing
+    }
+
+    /// 暂存一条记录；返回 true 时调用方需启动追加任务
+    fn stage(&self, line: &[u8]) -> bool {
+        let mut pending = self.lock();
+        pending.lines.extend_from_slice(line);
+        !std::mem::replace(&mut pending.writing, true)
+    }
+
+    /// 追加暂存的记录直到清空（在 blocking 线程上运行）
+    fn drain(&self) {
+        loop {
+            let lines = {
+                let mut pending = self.lock();
+                if pending.lines.is_empty() {
+                    pending.writing = false;
+                    break;
+                }
+                std::mem::take(&mut pending.lines)
+            };
+            let written = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .and_then(|mut f| f.write_all(&lines));
+            if let Err(e) = written {
+                tracing::error!(
+                    event = "audit_spill_write_failed",
+                    path = %self.path.display(),
+                    error = %e,
+                    "Failed to append audit events to the spill file"
+                );
+            }
+        }
+        self.not_empty.notify_one();
+    }
+}
+
+pub(crate) struct AuditQueue {
+    state: std::sync::Mutex<QueueState>,
+    capacity: usize,
+    policy: AuditOverflowPolicy,
+    spill: Arc<SpillWriter>,
+    draining_path: PathBuf,
+    not_empty: Arc<Notify>,
+    not_full: Notify,
+    metrics: Arc<AuditWriterMetrics>,
+}
+
+pub(crate) fn spill_path(log_path: &Path) -> PathBuf {
+    let mut name = log_path.as_os_str().to_owned();
+    name.push(".spill");
+    PathBuf::from(name)
+}
+
+/// 回放中的溢写文件；进程在回放中途退出时下次启动继续回放
+pub(crate) fn draining_spill_path(log_path: &Path) -> PathBuf {
+    let mut name = spill_path(log_path).into_os_string();
+    name.push(".draining");
+    PathBuf::from(name)
+}
+
+impl AuditQueue {
+    pub(crate) fn new(
+        log_path: &Path,
+        capacity: usize,
+        policy: AuditOverflowPolicy,
+        metrics: Arc<AuditWriterMetrics>,
+    ) -> Self {
+        let spill_path = spill_path(log_path);
+        let not_empty = Arc::new(Notify::new());
+        Self {
+            draining_path: draining_spill_path(log_path),
+            state: std::sync::Mutex::new(QueueState {
+                buf: VecDeque::new(),
+                events: 0,
+                // 上次进程退出前未回放完的溢写事件
+                spilling: spill_path.exists(),
+                closed: false,
+            }),
+            capacity: capacity.max(1),
+            policy,
+            spill: Arc::new(SpillWriter {
+                path: spill_path,
+                pending: std::sync::Mutex::new(SpillPending::default()),
+                not_empty: not_empty.clone(),
+            }),
+            not_empty,
+            not_full: Notify::new(),
+            metrics,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub(crate) fn metrics(&self) -> &Arc<AuditWriterMetrics> {
+        &self.metrics
+    }
+
+    fn push_locked(&self, state: &mut QueueState, cmd: AuditCommand) {
+        if matches!(cmd, AuditCommand::Event(_)) {
+            state.events += 1;
+            self.metrics.enqueued_total.fetch_add(1, Ordering::Relaxed);
+            self.metrics
+                .depth
+                .store(state.events as u64, Ordering::Relaxed);
+        }
+        state.buf.push_back(cmd);
+        self.not_empty.notify_one();
+    }
+
+    /// 入队一条事件；队列满时按溢出策略处理
+    pub(crate) async fn push_event(&self, event: Box<AuditEvent>) -> Result<(), EnqueueError> {
+        let mut event = event;
+        let mut waited = false;
+        loop {
+            // 先注册等待再检查状态，避免检查与等待之间的唤醒丢失
+            let notified = self.not_full.notified();
+            match self.enqueue(event) {
+                Enqueue::Done(result) => return result,
+                Enqueue::Full(returned) => {
+                    event = returned;
+                    if !waited {
+                        waited = true;
+                        self.metrics.blocked_total.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// 不等待的入队；Block 策略下队列满时丢弃该事件并计入 `dropped_total`。
+    /// 只在调用方显式放弃等待时使用（`audit.nonblocking_generation`）
+    pub(crate) fn try_push_event(&self, event: Box<AuditEvent>) -> Result<(), EnqueueError> {
+        match self.enqueue(event) {
+            Enqueue::Done(result) => result,
+            Enqueue::Full(_) => {
+                self.metrics.dropped_total.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+        }
+    }
+
+    /// 按溢出策略入队；Block 策略下队列满时交还事件由调用方决定等待或丢弃
+    fn enqueue(&self, event: Box<AuditEvent>) -> Enqueue {
+        let mut state = self.lock();
+        if state.closed {
+            return Enqueue::Done(Err(EnqueueError::Closed));
+        }
+        if state.spilling && self.policy == AuditOverflowPolicy::Spill {
+            return Enqueue::Done(self.spill(&event));
+        }
+        if state.events < self.capacity {
+            self.push_locked(&mut state, AuditCommand::Event(event));
+            return Enqueue::Done(Ok(()));
+        }
+        match self.policy {
+            AuditOverflowPolicy::DropOldest => {
+                if let Some(pos) = state
+                    .buf
+                    .iter()
+                    .position(|c| matches!(c, AuditCommand::Event(_)))
+                {
+                    state.buf.remove(pos);
+                    state.events -= 1;
+                    self.metrics.dropped_total.fetch_add(1, Ordering::Relaxed);
+                }
+                self.push_locked(&mut state, AuditCommand::Event(event));
+                Enqueue::Done(Ok(()))
+            }
+            AuditOverflowPolicy::Spill => {
+                state.spilling = true;
+                Enqueue::Done(self.spill(&event))
+            }
+            AuditOverflowPolicy::Block => Enqueue::Full(event),
+        }
+    }
+
+    /// 暂存到溢写文件（调用方持有队列锁，保证暂存顺序与入队顺序一致）。
+    ///
+    /// 写入的是脱敏后、未封装 hash 链的记录，回放时再分配 `seq`。
+    /// 文件追加交给 blocking 任务，写入失败只记录日志。
+    fn spill(&self, event: &AuditEvent) -> Result<(), EnqueueError> {
+        let mut line = serde_json::to_vec(&event.persisted_record())
+            .map_err(|e| EnqueueError::Spill(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+        line.push(b'\n');
+        if self.spill.stage(&line) {
+            let spill = Arc::clone(&self.spill);
+            tokio::task::spawn_blocking(move || spill.drain());
+        }
+        self.metrics.spilled_total.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Flush 命令不受容量限制，排在所有已入队事件之后
+    pub(crate) fn push_flush(
+        &self,
+        ack: tokio::sync::oneshot::Sender<()>,
+    ) -> Result<(), EnqueueError> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(EnqueueError::Closed);
+        }
+        self.push_locked(&mut state, AuditCommand::Flush(ack));
+        Ok(())
+    }
+
+    /// 取下一项工作；队列关闭且已清空时返回 `None`
+    pub(crate) async fn pop(&self) -> Option<QueueItem> {
+        loop {
+            let notified = self.not_empty.notified();
+            {
+                let mut state = self.lock();
+                // 内存队列中的事件都早于溢写事件：先写完事件，遇到 Flush 或队列空时
+                // 回放溢写文件，保证 Flush 返回时溢写事件也已落盘。暂存的记录
+                // 追加完成后才改名，追加完成时会再次唤醒这里
+                let front_is_event = matches!(state.buf.front(), Some(AuditCommand::Event(_)));
+                let spill_idle = self.spill.is_idle();
+                if state.spilling && !front_is_event && spill_idle {
+                    state.spilling = false;
+                    match std::fs::rename(&self.spill.path, &self.draining_path) {
+                        Ok(()) => return Some(QueueItem::Spilled(self.draining_path.clone())),
+                        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                        Err(e) => {
+                            tracing::error!(
+                                event = "audit_spill_drain_failed",
+                                error = %e,
+                                "Failed to claim audit spill file for replay"
+                            );
+                        }
+                    }
+                }
+                // 溢写仍在追加时 Flush 与关闭都要等回放完成后再处理
+                let awaiting_spill = state.spilling && !front_is_event;
+                if !awaiting_spill {
+                    if let Some(cmd) = state.buf.pop_front() {
+                        if matches!(cmd, AuditCommand::Event(_)) {
+                            state.events -= 1;
+                            self.metrics
+                                .depth
+                                .store(state.events as u64, Ordering::Relaxed);
+                            self.not_full.notify_one();
+                        }
+                        return Some(QueueItem::Command(cmd));
+                    }
+                    if state.closed {
+                        return None;
+                    }
+                }
+            }
+            notified.await;
+        }
+    }
+
+    /// 关闭队列：已入队事件仍会被写完，之后 `pop` 返回 `None`
+    pub(crate) fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_one();
+        self.not_full.notify_waiters();
+    }
+}
+
+/// 发送端句柄：所有 `AuditLogger` 克隆共享同一个句柄，最后一个克隆释放时关闭队列，
+/// writer task 写完剩余事件后退出（对应原 channel 所有 sender drop 的语义）。
+pub(crate) struct AuditQueueSender(pub(crate) Arc<AuditQueue>);
+
+impl std::ops::Deref for AuditQueueSender {
+    type Target = AuditQueue;
+
+    fn deref(&self) -> &AuditQueue {
+        &self.0
+    }
+}
+
+impl Drop for AuditQueueSender {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::audit::{AuditEventType, AuditResult};
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    fn event(action: &str) -> Box<AuditEvent> {
+        Box::new(AuditEvent::new(
+            AuditEventType::IdGeneration,
+            None,
+            action.to_string(),
+            "res".to_string(),
+            AuditResult::Success,
+        ))
+    }
+
+    fn queue(dir: &Path, capacity: usize, policy: AuditOverflowPolicy) -> AuditQueue {
+        AuditQueue::new(
+            &dir.join("audit.log"),
+            capacity,
+            policy,
+            Arc::new(AuditWriterMetrics::new(capacity, policy)),
+        )
+    }
+
+    async fn pop_action(queue: &AuditQueue) -> String {
+        match queue.pop().await {
+            Some(QueueItem::Command(AuditCommand::Event(e))) => e.action,
+            _ => panic!("expected an event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_evicts_front_and_counts() {
+        let dir = tempdir().unwrap();
+        let queue = queue(dir.path(), 2, AuditOverflowPolicy::DropOldest);
+        for action in ["a", "b", "c"] {
+            queue.push_event(event(action)).await.unwrap();
+        }
+
+        let metrics = queue.metrics();
+        assert_eq!(metrics.dropped_total.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.depth.load(Ordering::Relaxed), 2);
+        assert_eq!(pop_action(&queue).await, "b");
+        assert_eq!(pop_action(&queue).await, "c");
+    }
+
+    #[tokio::test]
+    async fn test_block_waits_for_space() {
+        let dir = tempdir().unwrap();
+        let queue = Arc::new(queue(dir.path(), 1, AuditOverflowPolicy::Block));
+        queue.push_event(event("a")).await.unwrap();
+
+        let pusher = {
+            let queue = queue.clone();
+            tokio::spawn(async move { queue.push_event(event("b")).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!pusher.is_finished(), "push should block while full");
+        assert_eq!(queue.metrics().blocked_total.load(Ordering::Relaxed), 1);
+
+        assert_eq!(pop_action(&queue).await, "a");
+        pusher.await.unwrap().unwrap();
+        assert_eq!(pop_action(&queue).await, "b");
+    }
+
+    #[tokio::test]
+    async fn test_try_push_drops_instead_of_blocking() {
+        let dir = tempdir().unwrap();
+        let queue = queue(dir.path(), 1, AuditOverflowPolicy::Block);
+        queue.try_push_event(event("a")).unwrap();
+        queue.try_push_event(event("b")).unwrap();
+
+        let metrics = queue.metrics();
+        assert_eq!(metrics.dropped_total.load(Ordering::Relaxed), 1);
+        assert_eq!(metrics.blocked_total.load(Ordering::Relaxed), 0);
+        assert_eq!(pop_action(&queue).await, "a");
+    }
+
+    #[tokio::test]
+    async fn test_spill_preserves_order_and_drains_before_flush() {
+        let dir = tempdir().unwrap();
+        let queue = queue(dir.path(), 1, AuditOverflowPolicy::Spill);
+        queue.push_event(event("a")).await.unwrap();
+        queue.push_event(event("b")).await.unwrap();
+        let (ack, _rx) = tokio::sync::oneshot::channel();
+        queue.push_flush(ack).unwrap();
+        // 溢写期间即使队列有空间也继续溢写，保持顺序
+        assert_eq!(pop_action(&queue).await, "a");
+        queue.push_event(event("c")).await.unwrap();
+        assert_eq!(queue.metrics().spilled_total.load(Ordering::Relaxed), 2);
+
+        let Some(QueueItem::Spilled(path)) = queue.pop().await else {
+            panic!("expected spill replay before flush");
+        };
+        let content = std::fs::read_to_string(&path).unwrap();
+        let actions: Vec<String> = content
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap()["action"].to_string())
+            .collect();
+        assert_eq!(actions, vec!["\"b\"", "\"c\""]);
+        assert!(matches!(
+            queue.pop().await,
+            Some(QueueItem::Command(AuditCommand::Flush(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_close_rejects_new_events_and_ends_pop() {
+        let dir = tempdir().unwrap();
+        let queue = queue(dir.path(), 4, AuditOverflowPolicy::Block);
+        queue.push_event(event("a")).await.unwrap();
+        queue.close();
+
+        assert!(matches!(
+            queue.push_event(event("b")).await,
+            Err(EnqueueError::Closed)
+        ));
+        assert_eq!(pop_action(&queue).await, "a");
+        assert!(queue.pop().await.is_none());
+    }
+}
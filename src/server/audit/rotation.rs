@@ -0,0 +1,322 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Audit log rotation, retention and gzip compression.
+//!
+//! 轮转文件命名为 `<log_path>.<UTC 时间戳>`（压缩后追加 `.gz`），时间戳按字典序
+//! 即时间顺序，`rotated_files` 据此还原整条 hash 链的文件顺序。
+
+use chrono::Utc;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const ROTATED_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%.3fZ";
+
+/// 轮转与保留策略；各项为 `None` / `false` 时关闭对应功能
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RotationPolicy {
+    /// 文件达到该字节数后轮转
+    pub max_bytes: Option<u64>,
+    /// 文件打开超过该时长后轮转（在下一次写入时触发）
+    pub max_age: Option<Duration>,
+    /// 轮转文件超过该时长后删除
+    pub retention: Option<Duration>,
+    /// 轮转后 gzip 压缩
+    pub compress: bool,
+}
+
+impl RotationPolicy {
+    pub fn is_enabled(&self) -> bool {
+        self.max_bytes.is_some() || self.max_age.is_some()
+    }
+}
+
+impl From<&crate::core::config::AuditConfig> for RotationPolicy {
+    fn from(config: &crate::core::config::AuditConfig) -> Self {
+        let non_zero = |v: u64| (v > 0).then_some(v);
+        Self {
+            max_bytes: non_zero(config.max_file_size_mb).map(|mb| mb.saturating_mul(1024 * 1024)),
+            max_age: non_zero(config.rotation_interval_secs).map(Duration::from_secs),
+            retention: non_zero(config.retention_days)
+                .map(|days| Duration::from_secs(days.saturating_mul(86_400))),
+            compress: config.compress_rotated,
+        }
+    }
+}
+
+/// 当前活动日志文件的轮转状态（由 writer task 独占）
+pub(crate) struct LogRotator {
+    path: PathBuf,
+    policy: RotationPolicy,
+    size: u64,
+    opened_at: SystemTime,
+}
+
+impl LogRotator {
+    pub(crate) fn open(path: &Path, policy: RotationPolicy) -> Self {
+        let metadata = std::fs::metadata(path).ok();
+        Self {
+            path: path.to_path_buf(),
+            policy,
+            size: metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+            // 不支持 birth time 的文件系统上从进程启动时重新计时
+            opened_at: metadata
+                .and_then(|m| m.created().ok())
+                .unwrap_or_else(SystemTime::now),
+        }
+    }
+
+    pub(crate) fn policy(&self) -> &RotationPolicy {
+        &self.policy
+    }
+
+    pub(crate) fn record_write(&mut self, bytes: u64) {
+        self.size += bytes;
+    }
+
+    /// 空文件从不轮转，避免产生只有轮转标记的文件
+    pub(crate) fn should_rotate(&self) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        let too_large = self.policy.max_bytes.is_some_and(|max| self.size >= max);
+        let too_old = self.policy.max_age.is_some_and(|max| {
+            SystemTime::now()
+                .duration_since(self.opened_at)
+                .is_ok_and(|age| age >= max)
+        });
+        too_large || too_old
+    }
+
+    /// 将活动文件重命名为带时间戳的轮转文件，返回轮转后的路径
+    pub(crate) fn rotate(&mut self) -> io::Result<PathBuf> {
+        let stamp = Utc::now().format(ROTATED_TIMESTAMP_FORMAT).to_string();
+        let mut target = suffixed(&self.path, &stamp);
+        let mut n = 1;
+        while target.exists() || suffixed(&target, "gz").exists() {
+            target = suffixed(&self.path, &format!("{}-{}", stamp, n));
+            n += 1;
+        }
+        std::fs::rename(&self.path, &target)?;
+        self.size = 0;
+        self.opened_at = SystemTime::now();
+        Ok(target)
+    }
+}
+
+fn suffixed(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// 轮转文件的后缀（去掉 `.gz` 后）以时间戳开头
+fn is_rotated_suffix(suffix: &str) -> bool {
+    let suffix = suffix.strip_suffix(".gz").unwrap_or(suffix);
+    suffix.len() >= 15
+        && suffix.starts_with(|c: char| c.is_ascii_digit())
+        && suffix
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, 'T' | 'Z' | '.' | '-'))
+}
+
+/// 列出 `log_path` 的轮转文件（按时间从旧到新）。
+///
+/// 压缩过程中 `x` 与 `x.gz` 可能短暂并存，此时只返回未压缩的 `x`。
+pub fn rotated_files(log_path: &Path) -> io::Result<Vec<PathBuf>> {
+    let Some(file_name) = log_path.file_name().and_then(|n| n.to_str()) else {
+        return Ok(Vec::new());
+    };
+    let dir = match log_path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let prefix = format!("{}.", file_name);
+
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut names: Vec<String> = Vec::new();
+    for entry in entries {
+        let name = entry?.file_name().to_string_lossy().into_owned();
+        if name.strip_prefix(&prefix).is_some_and(is_rotated_suffix) {
+            names.push(name);
+        }
+    }
+    let plain: std::collections::HashSet<String> = names
+        .iter()
+        .filter(|n| !n.ends_with(".gz"))
+        .cloned()
+        .collect();
+    names.retain(|n| {
+        n.strip_suffix(".gz")
+            .is_none_or(|uncompressed| !plain.contains(uncompressed))
+    });
+    names.sort_by(|a, b| {
+        a.strip_suffix(".gz")
+            .unwrap_or(a)
+            .cmp(b.strip_suffix(".gz").unwrap_or(b))
+    });
+    Ok(names.into_iter().map(|n| dir.join(n)).collect())
+}
+
+/// gzip 压缩轮转文件：先写 `<file>.gz.tmp`，重命名为 `<file>.gz` 后删除原文件
+pub(crate) fn compress_file(path: &Path) -> io::Result<PathBuf> {
+    let target = suffixed(path, "gz");
+    let tmp = suffixed(&target, "tmp");
+    {
+        let mut input = std::fs::File::open(path)?;
+        let output = std::fs::File::create(&tmp)?;
+        let mut encoder = flate2::write::GzEncoder::new(output, flate2::Compression::default());
+        io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+    }
+    std::fs::rename(&tmp, &target)?;
+    std::fs::remove_file(path)?;
+    Ok(target)
+}
+
+/// 删除修改时间早于 `retention` 的轮转文件，返回删除数量
+pub(crate) fn enforce_retention(log_path: &Path, retention: Duration) -> io::Result<usize> {
+    let Some(cutoff) = SystemTime::now().checked_sub(retention) else {
+        return Ok(0);
+    };
+    let mut removed = 0;
+    for file in rotated_files(log_path)? {
+        let modified = std::fs::metadata(&file)?.modified()?;
+        if modified < cutoff {
+            std::fs::remove_file(&file)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// 打开日志文件读取（`.gz` 透明解压）
+pub fn open_log_reader(path: &Path) -> io::Result<Box<dyn Read>> {
+    let file = std::fs::File::open(path)?;
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        Ok(Box::new(flate2::read::GzDecoder::new(file)))
+    } else {
+        Ok(Box::new(file))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_rotate_by_size_and_compress() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        std::fs::write(&path, "line-1\nline-2\n").unwrap();
+
+        let mut rotator = LogRotator::open(
+            &path,
+            RotationPolicy {
+                max_bytes: Some(10),
+                ..Default::default()
+            },
+        );
+        assert!(rotator.should_rotate());
+
+        let rotated = rotator.rotate().unwrap();
+        assert!(!path.exists());
+        assert!(!rotator.should_rotate());
+
+        let compressed = compress_file(&rotated).unwrap();
+        assert!(!rotated.exists());
+        let mut content = String::new();
+        open_log_reader(&compressed)
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "line-1\nline-2\n");
+        assert_eq!(rotated_files(&path).unwrap(), vec![compressed]);
+    }
+
+    #[test]
+    fn test_rotated_files_are_ordered_and_exclude_sidecars() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        for name in [
+            "audit.log.20261002T000000.000Z.gz",
+            "audit.log.20261001T000000.000Z.gz",
+            "audit.log.20261003T000000.000Z",
+            // 压缩中：未压缩版本优先
+            "audit.log.20261003T000000.000Z.gz",
+            "audit.log.checkpoints",
+            "audit.log.spill",
+            "other.log.20261001T000000.000Z",
+        ] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        let names: Vec<String> = rotated_files(&path)
+            .unwrap()
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "audit.log.20261001T000000.000Z.gz",
+                "audit.log.20261002T000000.000Z.gz",
+                "audit.log.20261003T000000.000Z",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enforce_retention_removes_expired_files() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        std::fs::write(&path, "active").unwrap();
+        std::fs::write(dir.path().join("audit.log.20261001T000000.000Z.gz"), "").unwrap();
+
+        // 保留期内不删除
+        assert_eq!(
+            enforce_retention(&path, Duration::from_secs(3600)).unwrap(),
+            0
+        );
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(
+            enforce_retention(&path, Duration::from_millis(10)).unwrap(),
+            1
+        );
+        assert!(rotated_files(&path).unwrap().is_empty());
+        assert!(path.exists(), "active file is never removed");
+    }
+
+    #[test]
+    fn test_policy_from_config_treats_zero_as_disabled() {
+        let config = crate::core::config::AuditConfig {
+            max_file_size_mb: 0,
+            rotation_interval_secs: 60,
+            retention_days: 0,
+            ..Default::default()
+        };
+        let policy = RotationPolicy::from(&config);
+        assert_eq!(policy.max_bytes, None);
+        assert_eq!(policy.max_age, Some(Duration::from_secs(60)));
+        assert_eq!(policy.retention, None);
+        assert!(policy.is_enabled());
+    }
+}
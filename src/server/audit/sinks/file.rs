@@ -0,0 +1,187 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rotating JSON-lines file sink.
+//!
+//! 与 `audit.log_path` 的防篡改日志不同，这里只写脱敏后的记录（无 hash 链字段），
+//! 供 Filebeat / Fluent Bit 等采集器读取。轮转命名、压缩与保留规则复用
+//! [`crate::server::audit::rotation`]。
+
+use super::{export_record, AuditSink};
+use crate::core::config::FileSinkConfig;
+use crate::server::audit::logger::AuditEvent;
+use crate::server::audit::rotation::{
+    compress_file, enforce_retention, LogRotator, RotationPolicy,
+};
+use async_trait::async_trait;
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+pub struct FileSink {
+    path: PathBuf,
+    rotator: LogRotator,
+    file: Option<tokio::fs::File>,
+}
+
+impl FileSink {
+    pub fn new(config: &FileSinkConfig) -> Self {
+        let path = PathBuf::from(&config.path);
+        let non_zero = |v: u64| (v > 0).then_some(v);
+        let policy = RotationPolicy {
+            max_bytes: non_zero(config.max_file_size_mb).map(|mb| mb.saturating_mul(1024 * 1024)),
+            max_age: non_zero(config.rotation_interval_secs).map(Duration::from_secs),
+            retention: non_zero(config.retention_days)
+                .map(|days| Duration::from_secs(days.saturating_mul(86_400))),
+            compress: config.compress_rotated,
+        };
+        Self {
+            rotator: LogRotator::open(&path, policy),
+            path,
+            file: None,
+        }
+    }
+
+    async fn file(&mut self) -> io::Result<&mut tokio::fs::File> {
+        if self.file.is_none() {
+            if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)
+                .await?;
+            self.file = Some(file);
+        }
+        Ok(self.file.as_mut().expect("file opened above"))
+    }
+
+    /// 达到阈值时轮转；压缩与保留清理在阻塞线程池执行
+    async fn rotate_if_due(&mut self) -> io::Result<()> {
+        if !self.rotator.should_rotate() {
+            return Ok(());
+        }
+        if let Some(mut file) = self.file.take() {
+            file.flush().await?;
+        }
+        let rotated = self.rotator.rotate()?;
+        let policy = self.rotator.policy().clone();
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || {
+            if policy.compress {
+                if let Err(e) = compress_file(&rotated) {
+                    tracing::warn!(
+                        event = "audit_sink_compress_failed",
+                        file = %rotated.display(),
+                        error = %e,
+                        "Failed to compress rotated audit sink file"
+                    );
+                }
+            }
+            if let Some(retention) = policy.retention {
+                if let Err(e) = enforce_retention(&path, retention) {
+                    tracing::warn!(
+                        event = "audit_sink_retention_failed",
+                        error = %e,
+                        "Failed to apply audit sink retention"
+                    );
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditSink for FileSink {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&mut self, events: &[AuditEvent]) -> io::Result<()> {
+        for event in events {
+            self.rotate_if_due().await?;
+            let mut line = export_record(event).to_string();
+            line.push('\n');
+            self.file().await?.write_all(line.as_bytes()).await?;
+            self.rotator.record_write(line.len() as u64);
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        if let Some(ref mut file) = self.file {
+            file.flush().await?;
+            file.sync_all().await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::audit::logger::{AuditEventType, AuditResult};
+    use crate::server::audit::rotation::rotated_files;
+
+    fn event(resource: &str) -> AuditEvent {
+        AuditEvent::new(
+            AuditEventType::BizTagDeleted,
+            Some("ws-1".to_string()),
+            "delete".to_string(),
+            resource.to_string(),
+            AuditResult::Success,
+        )
+        .with_user_agent("curl/8.0".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_file_sink_writes_redacted_lines_and_rotates() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("siem").join("audit.jsonl");
+        let mut sink = FileSink::new(&FileSinkConfig {
+            enabled: true,
+            path: path.to_string_lossy().into_owned(),
+            compress_rotated: false,
+            ..Default::default()
+        });
+        // 每条记录都超过 1 字节上限，第二条写入前轮转
+        sink.rotator = LogRotator::open(
+            &path,
+            RotationPolicy {
+                max_bytes: Some(1),
+                ..Default::default()
+            },
+        );
+
+        sink.send(&[event("biz_tag:a"), event("biz_tag:b")])
+            .await
+            .unwrap();
+        sink.flush().await.unwrap();
+
+        let rotated = rotated_files(&path).unwrap();
+        assert_eq!(rotated.len(), 1);
+        let old: serde_json::Value =
+            serde_json::from_str(std::fs::read_to_string(&rotated[0]).unwrap().trim()).unwrap();
+        assert_eq!(old["resource"], "biz_tag:a");
+
+        let current = std::fs::read_to_string(&path).unwrap();
+        let record: serde_json::Value = serde_json::from_str(current.trim()).unwrap();
+        assert_eq!(record["resource"], "biz_tag:b");
+        assert_eq!(record["user_agent"], "UA(redacted)");
+        assert!(record.get("seq").is_none(), "no hash chain fields");
+    }
+}
@@ -0,0 +1,659 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Streaming audit export to external sinks (SIEM).
+//!
+//! 每个 sink 有独立的过滤器、有界队列和后台 task。`AuditLogger::log` 只做过滤与
+//! 非阻塞入队，队列满时按 [`AuditSinkOverflowPolicy`] 丢弃并计数；sink 的网络或
+//! 磁盘故障只影响它自己的队列，不会拖慢 ID 生成，也不影响其他 sink。
+//!
+//! 转发的是脱敏后的记录（与文件持久化相同，见 `AuditEvent::persisted_record`）。
+
+mod file;
+mod syslog;
+mod webhook;
+
+pub use file::FileSink;
+pub use syslog::SyslogSink;
+pub use webhook::WebhookSink;
+
+use super::logger::{AuditEvent, AuditEventType, AuditResult};
+use crate::core::config::{AuditSinkDelivery, AuditSinkOverflowPolicy, AuditSinksConfig};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{oneshot, Notify};
+use tokio::task::JoinHandle;
+
+/// 审计事件的外部投递目标。
+///
+/// 每个实例由自己的后台 task 独占调用，实现内部无需同步。
+#[async_trait]
+pub trait AuditSink: Send {
+    /// 用于日志与 `/metrics` 的名称
+    fn name(&self) -> &'static str;
+
+    /// 投递一批事件。返回错误时整批计为失败并丢弃，需要重试的实现应在内部完成重试。
+    async fn send(&mut self, events: &[AuditEvent]) -> io::Result<()>;
+
+    /// 将缓冲数据落地；`AuditLogger::flush` 与关闭时调用
+    async fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 按事件类型与结果过滤；对应列表为空时不限制
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SinkFilter {
+    pub event_types: Vec<AuditEventType>,
+    pub results: Vec<AuditResult>,
+}
+
+impl SinkFilter {
+    /// 未知名称被忽略（`Config::validate` 已拒绝未知名称）
+    pub fn from_delivery(delivery: &AuditSinkDelivery) -> Self {
+        fn parse<T: serde::de::DeserializeOwned>(names: &[String]) -> Vec<T> {
+            names
+                .iter()
+                .filter_map(|name| {
+                    serde_json::from_value(serde_json::Value::String(name.clone())).ok()
+                })
+                .collect()
+        }
+        Self {
+            event_types: parse(&delivery.event_types),
+            results: parse(&delivery.results),
+        }
+    }
+
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        (self.event_types.is_empty() || self.event_types.contains(&event.event_type))
+            && (self.results.is_empty() || self.results.contains(&event.result))
+    }
+}
+
+/// sink 的过滤、队列与攒批选项
+#[derive(Debug, Clone)]
+pub struct AuditSinkOptions {
+    pub filter: SinkFilter,
+    pub channel_capacity: usize,
+    pub overflow_policy: AuditSinkOverflowPolicy,
+    /// 每次 `send` 的最大事件数
+    pub batch_size: usize,
+    /// 未攒满一批时最多等待多久再发送；为零时有事件即发送
+    pub linger: Duration,
+}
+
+impl Default for AuditSinkOptions {
+    fn default() -> Self {
+        Self {
+            filter: SinkFilter::default(),
+            channel_capacity: 1000,
+            overflow_policy: AuditSinkOverflowPolicy::DropNewest,
+            batch_size: 100,
+            linger: Duration::ZERO,
+        }
+    }
+}
+
+impl From<&AuditSinkDelivery> for AuditSinkOptions {
+    fn from(delivery: &AuditSinkDelivery) -> Self {
+        Self {
+            filter: SinkFilter::from_delivery(delivery),
+            channel_capacity: delivery.channel_capacity.max(1),
+            overflow_policy: delivery.overflow_policy,
+            ..Self::default()
+        }
+    }
+}
+
+/// 单个 sink 的投递指标，由 `/metrics` 暴露
+#[derive(Debug)]
+pub struct AuditSinkMetrics {
+    pub name: &'static str,
+    pub capacity: usize,
+    pub overflow_policy: AuditSinkOverflowPolicy,
+    /// 当前排队事件数
+    pub depth: AtomicU64,
+    pub delivered_total: AtomicU64,
+    /// 投递失败（含重试耗尽）后丢弃的事件数
+    pub failed_total: AtomicU64,
+    /// 队列满时按溢出策略丢弃的事件数
+    pub dropped_total: AtomicU64,
+}
+
+impl AuditSinkMetrics {
+    fn new(name: &'static str, options: &AuditSinkOptions) -> Self {
+        Self {
+            name,
+            capacity: options.channel_capacity,
+            overflow_policy: options.overflow_policy,
+            depth: AtomicU64::new(0),
+            delivered_total: AtomicU64::new(0),
+            failed_total: AtomicU64::new(0),
+            dropped_total: AtomicU64::new(0),
+        }
+    }
+}
+
+/// 按配置创建已启用的 sink；创建失败（如 CA 证书无法读取）的 sink 记录错误后跳过
+pub fn configured_sinks(config: &AuditSinksConfig) -> Vec<(Box<dyn AuditSink>, AuditSinkOptions)> {
+    let mut sinks: Vec<(Box<dyn AuditSink>, AuditSinkOptions)> = Vec::new();
+
+    if config.syslog.enabled {
+        match SyslogSink::new(&config.syslog) {
+            Ok(sink) => sinks.push((
+                Box::new(sink),
+                AuditSinkOptions::from(&config.syslog.delivery),
+            )),
+            Err(e) => report_build_failure("syslog", &e),
+        }
+    }
+    if config.webhook.enabled {
+        match WebhookSink::new(&config.webhook) {
+            Ok(sink) => sinks.push((
+                Box::new(sink),
+                AuditSinkOptions {
+                    batch_size: config.webhook.batch_size.max(1),
+                    linger: Duration::from_millis(config.webhook.flush_interval_ms),
+                    ..AuditSinkOptions::from(&config.webhook.delivery)
+                },
+            )),
+            Err(e) => report_build_failure("webhook", &e),
+        }
+    }
+    if config.file.enabled {
+        sinks.push((
+            Box::new(FileSink::new(&config.file)),
+            AuditSinkOptions::from(&config.file.delivery),
+        ));
+    }
+    sinks
+}
+
+fn report_build_failure(sink: &str, error: &io::Error) {
+    tracing::error!(
+        event = "audit_sink_init_failed",
+        sink,
+        error = %error,
+        "Failed to initialize audit sink, it will be disabled"
+    );
+}
+
+struct SinkQueueState {
+    events: VecDeque<AuditEvent>,
+    flush_waiters: Vec<oneshot::Sender<()>>,
+    closed: bool,
+}
+
+struct SinkQueue {
+    state: std::sync::Mutex<SinkQueueState>,
+    capacity: usize,
+    policy: AuditSinkOverflowPolicy,
+    notify: Notify,
+    metrics: Arc<AuditSinkMetrics>,
+}
+
+impl SinkQueue {
+    fn lock(&self) -> std::sync::MutexGuard<'_, SinkQueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push(&self, event: AuditEvent) {
+        let mut state = self.lock();
+        if state.closed {
+            return;
+        }
+        if state.events.len() >= self.capacity {
+            self.metrics.dropped_total.fetch_add(1, Ordering::Relaxed);
+            match self.policy {
+                AuditSinkOverflowPolicy::DropNewest => return,
+                AuditSinkOverflowPolicy::DropOldest => {
+                    state.events.pop_front();
+                }
+            }
+        }
+        state.events.push_back(event);
+        self.metrics
+            .depth
+            .store(state.events.len() as u64, Ordering::Relaxed);
+        drop(state);
+        self.notify.notify_one();
+    }
+
+    fn take_batch(&self, max: usize) -> Vec<AuditEvent> {
+        let mut state = self.lock();
+        let n = state.events.len().min(max);
+        let batch: Vec<AuditEvent> = state.events.drain(..n).collect();
+        self.metrics
+            .depth
+            .store(state.events.len() as u64, Ordering::Relaxed);
+        batch
+    }
+
+    /// 有事件、flush 请求或已关闭
+    fn has_work(&self) -> bool {
+        let state = self.lock();
+        !state.events.is_empty() || !state.flush_waiters.is_empty() || state.closed
+    }
+
+    /// 已攒满一批，或有 flush 请求 / 已关闭（需要立即发送）
+    fn batch_ready(&self, batch_size: usize) -> bool {
+        let state = self.lock();
+        state.events.len() >= batch_size || !state.flush_waiters.is_empty() || state.closed
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.notify.notify_one();
+    }
+}
+
+/// 一个已启动的 sink：过滤器、队列和后台投递 task。
+///
+/// 释放时关闭队列，task 投递完剩余事件后退出。
+pub(crate) struct AuditSinkHandle {
+    filter: SinkFilter,
+    queue: Arc<SinkQueue>,
+    _task: JoinHandle<()>,
+}
+
+impl AuditSinkHandle {
+    /// 启动投递 task；必须在 tokio runtime 上下文中调用
+    pub(crate) fn spawn(sink: Box<dyn AuditSink>, options: AuditSinkOptions) -> Self {
+        let metrics = Arc::new(AuditSinkMetrics::new(sink.name(), &options));
+        let queue = Arc::new(SinkQueue {
+            state: std::sync::Mutex::new(SinkQueueState {
+                events: VecDeque::new(),
+                flush_waiters: Vec::new(),
+                closed: false,
+            }),
+            capacity: options.channel_capacity.max(1),
+            policy: options.overflow_policy,
+            notify: Notify::new(),
+            metrics,
+        });
+        let worker = SinkWorker {
+            sink,
+            queue: queue.clone(),
+            batch_size: options.batch_size.max(1),
+            linger: options.linger,
+        };
+        Self {
+            filter: options.filter,
+            queue,
+            _task: tokio::spawn(worker.run()),
+        }
+    }
+
+    /// 过滤后非阻塞入队
+    pub(crate) fn offer(&self, event: &AuditEvent) {
+        if self.filter.matches(event) {
+            self.queue.push(event.clone());
+        }
+    }
+
+    /// 等待此前入队的事件投递完成（投递失败的事件已计数丢弃）
+    pub(crate) async fn flush(&self) {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        {
+            let mut state = self.queue.lock();
+            if state.closed {
+                return;
+            }
+            state.flush_waiters.push(ack_tx);
+        }
+        self.queue.notify.notify_one();
+        let _ = ack_rx.await;
+    }
+
+    pub(crate) fn metrics(&self) -> &Arc<AuditSinkMetrics> {
+        &self.queue.metrics
+    }
+}
+
+impl Drop for AuditSinkHandle {
+    fn drop(&mut self) {
+        self.queue.close();
+    }
+}
+
+struct SinkWorker {
+    sink: Box<dyn AuditSink>,
+    queue: Arc<SinkQueue>,
+    batch_size: usize,
+    linger: Duration,
+}
+
+impl SinkWorker {
+    async fn run(mut self) {
+        loop {
+            while !self.queue.has_work() {
+                self.queue.notify.notified().await;
+            }
+            // 攒批：linger 内攒满一批或收到 flush / 关闭时提前发送
+            if !self.linger.is_zero() {
+                let deadline = tokio::time::Instant::now() + self.linger;
+                while !self.queue.batch_ready(self.batch_size) {
+                    let notified = self.queue.notify.notified();
+                    if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                        break;
+                    }
+                }
+            }
+            if self.drain().await {
+                break;
+            }
+        }
+    }
+
+    /// 投递全部排队事件并回复 flush 请求；队列已关闭时返回 `true`
+    async fn drain(&mut self) -> bool {
+        loop {
+            let batch = self.queue.take_batch(self.batch_size);
+            if batch.is_empty() {
+                break;
+            }
+            self.deliver(&batch).await;
+        }
+
+        let (waiters, closed) = {
+            let mut state = self.queue.lock();
+            (std::mem::take(&mut state.flush_waiters), state.closed)
+        };
+        if !waiters.is_empty() || closed {
+            if let Err(e) = self.sink.flush().await {
+                self.report_failure(0, &e);
+            }
+        }
+        for waiter in waiters {
+            let _ = waiter.send(());
+        }
+        closed && self.queue.lock().events.is_empty()
+    }
+
+    async fn deliver(&mut self, batch: &[AuditEvent]) {
+        let metrics = &self.queue.metrics;
+        match self.sink.send(batch).await {
+            Ok(()) => {
+                metrics
+                    .delivered_total
+                    .fetch_add(batch.len() as u64, Ordering::Relaxed);
+            }
+            Err(e) => {
+                metrics
+                    .failed_total
+                    .fetch_add(batch.len() as u64, Ordering::Relaxed);
+                self.report_failure(batch.len(), &e);
+            }
+        }
+    }
+
+    fn report_failure(&self, events: usize, error: &io::Error) {
+        tracing::warn!(
+            event = "audit_sink_delivery_failed",
+            sink = self.sink.name(),
+            events,
+            error = %error,
+            "Audit sink delivery failed, events dropped"
+        );
+    }
+}
+
+/// 转发给外部系统的记录：脱敏字段，附带 serde 名称的事件类型与结果
+pub(crate) fn export_record(event: &AuditEvent) -> serde_json::Value {
+    serde_json::Value::Object(event.persisted_record())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// 记录收到的批次；`fail` 为真时返回错误，`gate` 用于模拟卡住的下游
+    #[derive(Clone, Default)]
+    struct RecordingSink {
+        batches: Arc<Mutex<Vec<Vec<String>>>>,
+        fail: Arc<std::sync::atomic::AtomicBool>,
+        gate: Option<Arc<tokio::sync::Semaphore>>,
+    }
+
+    #[async_trait]
+    impl AuditSink for RecordingSink {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn send(&mut self, events: &[AuditEvent]) -> io::Result<()> {
+            if let Some(ref gate) = self.gate {
+                let _permit = gate.acquire().await;
+            }
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(io::Error::other("collector down"));
+            }
+            self.batches
+                .lock()
+                .unwrap()
+                .push(events.iter().map(|e| e.resource.clone()).collect());
+            Ok(())
+        }
+    }
+
+    fn event(event_type: AuditEventType, result: AuditResult, resource: &str) -> AuditEvent {
+        AuditEvent::new(
+            event_type,
+            None,
+            "test".to_string(),
+            resource.to_string(),
+            result,
+        )
+    }
+
+    #[test]
+    fn test_filter_matches_event_types_and_results() {
+        let filter = SinkFilter::from_delivery(&AuditSinkDelivery {
+            event_types: vec!["ConfigChange".to_string(), "ApiKeyCreated".to_string()],
+            results: vec!["Failure".to_string()],
+            ..Default::default()
+        });
+        assert!(filter.matches(&event(
+            AuditEventType::ConfigChange,
+            AuditResult::Failure,
+            "a"
+        )));
+        assert!(!filter.matches(&event(
+            AuditEventType::ConfigChange,
+            AuditResult::Success,
+            "a"
+        )));
+        assert!(!filter.matches(&event(
+            AuditEventType::IdGeneration,
+            AuditResult::Failure,
+            "a"
+        )));
+        assert!(SinkFilter::default().matches(&event(
+            AuditEventType::IdGeneration,
+            AuditResult::Success,
+            "a"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_sink_batches_and_flushes_filtered_events() {
+        let sink = RecordingSink::default();
+        let handle = AuditSinkHandle::spawn(
+            Box::new(sink.clone()),
+            AuditSinkOptions {
+                filter: SinkFilter {
+                    event_types: vec![AuditEventType::ConfigChange],
+                    results: Vec::new(),
+                },
+                batch_size: 2,
+                linger: Duration::from_secs(3600),
+                ..Default::default()
+            },
+        );
+
+        for i in 0..3 {
+            handle.offer(&event(
+                AuditEventType::ConfigChange,
+                AuditResult::Success,
+                &format!("config:{}", i),
+            ));
+            handle.offer(&event(
+                AuditEventType::IdGeneration,
+                AuditResult::Success,
+                "ignored",
+            ));
+        }
+        handle.flush().await;
+
+        let batches = sink.batches.lock().unwrap().clone();
+        assert_eq!(
+            batches,
+            vec![
+                vec!["config:0".to_string(), "config:1".to_string()],
+                vec!["config:2".to_string()],
+            ]
+        );
+        assert_eq!(handle.metrics().delivered_total.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_sink_never_blocks_and_applies_overflow_policy() {
+        for (policy, expected) in [
+            (AuditSinkOverflowPolicy::DropNewest, vec!["e1", "e2"]),
+            (AuditSinkOverflowPolicy::DropOldest, vec!["e3", "e4"]),
+        ] {
+            let gate = Arc::new(tokio::sync::Semaphore::new(0));
+            let sink = RecordingSink {
+                gate: Some(gate.clone()),
+                ..Default::default()
+            };
+            let handle = AuditSinkHandle::spawn(
+                Box::new(sink.clone()),
+                AuditSinkOptions {
+                    channel_capacity: 2,
+                    overflow_policy: policy,
+                    batch_size: 1,
+                    ..Default::default()
+                },
+            );
+
+            // e0 被 worker 取走后卡在 gate 上，之后的事件只能排队
+            handle.offer(&event(
+                AuditEventType::ConfigChange,
+                AuditResult::Success,
+                "e0",
+            ));
+            while handle.metrics().depth.load(Ordering::SeqCst) != 0 {
+                tokio::task::yield_now().await;
+            }
+            for i in 1..5 {
+                handle.offer(&event(
+                    AuditEventType::ConfigChange,
+                    AuditResult::Success,
+                    &format!("e{}", i),
+                ));
+            }
+            assert_eq!(handle.metrics().dropped_total.load(Ordering::SeqCst), 2);
+
+            gate.add_permits(10);
+            handle.flush().await;
+            let delivered: Vec<String> = sink
+                .batches
+                .lock()
+                .unwrap()
+                .iter()
+                .flatten()
+                .cloned()
+                .collect();
+            let mut want = vec!["e0".to_string()];
+            want.extend(expected.iter().map(|s| s.to_string()));
+            assert_eq!(delivered, want, "{:?}", policy);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_logger_is_not_blocked_by_stalled_sink() {
+        let gate = Arc::new(tokio::sync::Semaphore::new(0));
+        let stalled = RecordingSink {
+            gate: Some(gate.clone()),
+            ..Default::default()
+        };
+        let healthy = RecordingSink::default();
+        let logger = super::super::AuditLogger::new(100)
+            .with_sink(
+                Box::new(stalled),
+                AuditSinkOptions {
+                    channel_capacity: 4,
+                    ..Default::default()
+                },
+            )
+            .with_sink(Box::new(healthy.clone()), AuditSinkOptions::default());
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            for i in 0..50 {
+                logger
+                    .log(event(
+                        AuditEventType::IdGeneration,
+                        AuditResult::Success,
+                        &format!("id:{}", i),
+                    ))
+                    .await;
+            }
+        })
+        .await
+        .expect("log must not wait for a stalled sink");
+
+        let metrics = logger.sink_metrics();
+        assert!(metrics[0].dropped_total.load(Ordering::SeqCst) > 0);
+        assert_eq!(metrics[1].dropped_total.load(Ordering::SeqCst), 0);
+
+        gate.add_permits(100);
+        logger.flush().await;
+        let delivered: usize = healthy.batches.lock().unwrap().iter().map(Vec::len).sum();
+        assert_eq!(delivered, 50);
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_counted_and_dropped() {
+        let sink = RecordingSink::default();
+        sink.fail.store(true, Ordering::SeqCst);
+        let handle = AuditSinkHandle::spawn(Box::new(sink.clone()), AuditSinkOptions::default());
+
+        handle.offer(&event(
+            AuditEventType::ConfigChange,
+            AuditResult::Success,
+            "a",
+        ));
+        handle.flush().await;
+        assert_eq!(handle.metrics().failed_total.load(Ordering::SeqCst), 1);
+
+        sink.fail.store(false, Ordering::SeqCst);
+        handle.offer(&event(
+            AuditEventType::ConfigChange,
+            AuditResult::Success,
+            "b",
+        ));
+        handle.flush().await;
+        assert_eq!(
+            sink.batches.lock().unwrap().clone(),
+            vec![vec!["b".to_string()]]
+        );
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RFC 5424 syslog sink over UDP (RFC 5426), TCP (RFC 6587) or TLS (RFC 5425).
+//!
+//! MSGID 为事件类型，结构化数据 `audit@32473` 携带事件 ID、结果与 workspace，
+//! MSG 为 BOM + 脱敏后的 JSON 记录。TCP/TLS 使用 octet-counting 帧，连接断开时
+//! 重连一次后重发当前消息。
+
+use super::{export_record, AuditSink};
+use crate::core::config::{SyslogProtocol, SyslogSinkConfig};
+use crate::server::audit::logger::{AuditEvent, AuditResult};
+use crate::server::audit::store::variant_name;
+use async_trait::async_trait;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::sync::Arc;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+/// 结构化数据 ID（RFC 5612 文档示例企业号）
+const SD_ID: &str = "audit@32473";
+
+/// UTF-8 BOM，RFC 5424 要求 UTF-8 MSG 以其开头
+const BOM: &str = "\u{feff}";
+
+type Stream = Box<dyn AsyncWrite + Unpin + Send + Sync>;
+
+enum Transport {
+    Udp(Option<UdpSocket>),
+    Tcp(Option<Stream>),
+    Tls(Option<Stream>, TlsConnector),
+}
+
+pub struct SyslogSink {
+    address: String,
+    facility: u8,
+    app_name: String,
+    hostname: String,
+    transport: Transport,
+}
+
+impl SyslogSink {
+    /// TLS 协议下读取 CA 证书失败时返回错误；连接在首次发送时建立
+    pub fn new(config: &SyslogSinkConfig) -> io::Result<Self> {
+        let transport = match config.protocol {
+            SyslogProtocol::Udp => Transport::Udp(None),
+            SyslogProtocol::Tcp => Transport::Tcp(None),
+            SyslogProtocol::Tls => Transport::Tls(None, tls_connector(&config.ca_cert_path)?),
+        };
+        Ok(Self {
+            address: config.address.clone(),
+            facility: config.facility.min(23),
+            app_name: config.app_name.clone(),
+            hostname: std::env::var("HOSTNAME")
+                .ok()
+                .filter(|h| !h.is_empty())
+                .unwrap_or_else(|| "-".to_string()),
+            transport,
+        })
+    }
+
+    /// 格式化一条 RFC 5424 消息（不含传输帧）
+    pub(crate) fn format(&self, event: &AuditEvent) -> String {
+        let severity = match event.result {
+            AuditResult::Failure => 4, // warning
+            AuditResult::Partial => 5, // notice
+            _ => 6,                    // informational
+        };
+        let pri = u16::from(self.facility) * 8 + severity;
+        let workspace = event
+            .workspace_id
+            .as_deref()
+            .map(|w| format!(" workspace=\"{}\"", escape_param(w)))
+            .unwrap_or_default();
+
+        format!(
+            "<{}>1 {} {} {} {} {} [{} id=\"{}\" result=\"{}\"{}] {}{}",
+            pri,
+            event
+                .timestamp
+                .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            header_field(&self.hostname, 255),
+            header_field(&self.app_name, 48),
+            std::process::id(),
+            header_field(&variant_name(&event.event_type), 32),
+            SD_ID,
+            event.id,
+            escape_param(&variant_name(&event.result)),
+            workspace,
+            BOM,
+            export_record(event),
+        )
+    }
+
+    async fn send_udp(&mut self, message: &str) -> io::Result<()> {
+        let Transport::Udp(ref mut socket) = self.transport else {
+            unreachable!("send_udp called on a stream transport")
+        };
+        if socket.is_none() {
+            let target = tokio::net::lookup_host(self.address.as_str())
+                .await?
+                .next()
+                .ok_or_else(|| io::Error::other("syslog address did not resolve"))?;
+            let bind = if target.is_ipv4() {
+                "0.0.0.0:0"
+            } else {
+                "[::]:0"
+            };
+            let udp = UdpSocket::bind(bind).await?;
+            udp.connect(target).await?;
+            *socket = Some(udp);
+        }
+        if let Some(ref udp) = socket {
+            udp.send(message.as_bytes()).await?;
+        }
+        Ok(())
+    }
+
+    async fn connect_stream(&self) -> io::Result<Stream> {
+        let tcp = TcpStream::connect(self.address.as_str()).await?;
+        match self.transport {
+            Transport::Tls(_, ref connector) => {
+                let host = self
+                    .address
+                    .rsplit_once(':')
+                    .map(|(host, _)| host.trim_start_matches('[').trim_end_matches(']'))
+                    .unwrap_or(&self.address)
+                    .to_string();
+                let server_name = ServerName::try_from(host)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+                Ok(Box::new(connector.connect(server_name, tcp).await?))
+            }
+            _ => Ok(Box::new(tcp)),
+        }
+    }
+
+    fn stream_slot(&mut self) -> &mut Option<Stream> {
+        match self.transport {
+            Transport::Tcp(ref mut stream) | Transport::Tls(ref mut stream, _) => stream,
+            Transport::Udp(_) => unreachable!("stream_slot called on UDP transport"),
+        }
+    }
+
+    /// 写入一帧；已有连接写入失败时重连一次并重发
+    async fn send_framed(&mut self, frame: &[u8]) -> io::Result<()> {
+        let reused = self.stream_slot().is_some();
+        if reused {
+            if let Some(stream) = self.stream_slot().as_mut() {
+                if stream.write_all(frame).await.is_ok() {
+                    return Ok(());
+                }
+            }
+            *self.stream_slot() = None;
+        }
+
+        let mut stream = self.connect_stream().await?;
+        stream.write_all(frame).await?;
+        *self.stream_slot() = Some(stream);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuditSink for SyslogSink {
+    fn name(&self) -> &'static str {
+        "syslog"
+    }
+
+    async fn send(&mut self, events: &[AuditEvent]) -> io::Result<()> {
+        for event in events {
+            let message = self.format(event);
+            if matches!(self.transport, Transport::Udp(_)) {
+                self.send_udp(&message).await?;
+            } else {
+                let frame = format!("{} {}", message.len(), message);
+                self.send_framed(frame.as_bytes()).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> io::Result<()> {
+        if matches!(self.transport, Transport::Udp(_)) {
+            return Ok(());
+        }
+        if let Some(stream) = self.stream_slot().as_mut() {
+            stream.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+/// 从 PEM 文件加载受信任 CA，构建 TLS 连接器
+fn tls_connector(ca_cert_path: &str) -> io::Result<TlsConnector> {
+    let mut reader = BufReader::new(File::open(ca_cert_path)?);
+    let mut roots = RootCertStore::empty();
+    while let Some(item) = rustls_pemfile::read_one(&mut reader)? {
+        if let rustls_pemfile::Item::X509Certificate(cert) = item {
+            roots
+                .add(cert)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+    }
+    if roots.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no CA certificate found in {}", ca_cert_path),
+        ));
+    }
+    let config = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+/// HEADER 字段：可打印 ASCII（33..=126），为空时为 NILVALUE `-`
+fn header_field(value: &str, max_len: usize) -> String {
+    let field: String = value
+        .chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max_len)
+        .collect();
+    if field.is_empty() {
+        "-".to_string()
+    } else {
+        field
+    }
+}
+
+/// SD-PARAM 值中 `"`、`\` 与 `]` 必须转义
+fn escape_param(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::audit::logger::AuditEventType;
+
+    fn sink(address: &str) -> SyslogSink {
+        let mut sink = SyslogSink::new(&SyslogSinkConfig {
+            enabled: true,
+            address: address.to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        sink.hostname = "idgen-1".to_string();
+        sink
+    }
+
+    fn failed_event() -> AuditEvent {
+        AuditEvent::new(
+            AuditEventType::ApiKeyRevoked,
+            Some("ws\"1]".to_string()),
+            "revoke".to_string(),
+            "api_key:k1".to_string(),
+            AuditResult::Failure,
+        )
+        .with_client_ip("10.1.2.3".to_string())
+    }
+
+    #[test]
+    fn test_format_rfc5424_message() {
+        let event = failed_event();
+        let message = sink("127.0.0.1:514").format(&event);
+
+        // facility 13 (log audit) * 8 + severity 4 (warning)
+        let prefix = format!(
+            "<108>1 {} idgen-1 nebula-id {} ApiKeyRevoked [audit@32473 id=\"{}\" result=\"Failure\" workspace=\"ws\\\"1\\]\"] \u{feff}",
+            event.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            std::process::id(),
+            event.id,
+        );
+        assert!(message.starts_with(&prefix), "{}", message);
+
+        let json: serde_json::Value = serde_json::from_str(&message[prefix.len()..]).unwrap();
+        assert_eq!(json["client_ip"], "10.1.2.x", "PII is redacted");
+        assert_eq!(json["event_type"], "ApiKeyRevoked");
+    }
+
+    #[tokio::test]
+    async fn test_udp_sink_sends_one_datagram_per_event() {
+        let collector = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut sink = sink(&collector.local_addr().unwrap().to_string());
+
+        let event = failed_event();
+        sink.send(&[event.clone(), event.clone()]).await.unwrap();
+
+        let mut buf = vec![0u8; 8192];
+        for _ in 0..2 {
+            let n = collector.recv(&mut buf).await.unwrap();
+            assert_eq!(std::str::from_utf8(&buf[..n]).unwrap(), sink.format(&event));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tcp_sink_uses_octet_counting_and_reconnects() {
+        use tokio::io::AsyncReadExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut sink = SyslogSink::new(&SyslogSinkConfig {
+            enabled: true,
+            protocol: SyslogProtocol::Tcp,
+            address: listener.local_addr().unwrap().to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+        let event = failed_event();
+        let message = sink.format(&event);
+        let expected = format!("{} {}", message.len(), message);
+
+        sink.send(std::slice::from_ref(&event)).await.unwrap();
+        sink.flush().await.unwrap();
+        let (mut conn, _) = listener.accept().await.unwrap();
+        let mut buf = vec![0u8; expected.len()];
+        conn.read_exact(&mut buf).await.unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+
+        // 收集端断开后，下一次发送重连
+        drop(conn);
+        let accept = tokio::spawn(async move { listener.accept().await.unwrap().0 });
+        let mut sent = false;
+        // 首次写入可能仍落在已关闭连接的内核缓冲区，RST 到达后的写入才会失败
+        for _ in 0..5 {
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            sent |= sink.send(std::slice::from_ref(&event)).await.is_ok();
+        }
+        assert!(sent);
+        let mut conn = accept.await.unwrap();
+        let mut buf = vec![0u8; expected.len()];
+        conn.read_exact(&mut buf).await.unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_tls_sink_requires_readable_ca() {
+        let result = SyslogSink::new(&SyslogSinkConfig {
+            enabled: true,
+            protocol: SyslogProtocol::Tls,
+            address: "siem.example.com:6514".to_string(),
+            ca_cert_path: "/nonexistent/ca.pem".to_string(),
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! HTTP batch webhook sink.
+//!
+//! 每批事件以 JSON 数组 POST 到 `url`。网络错误、429 与 5xx 按
+//! `retry_backoff_ms * 2^n` 退避重试，最多 `max_retries` 次；其他 4xx 视为
+//! 配置错误，不重试。重试在 sink 自己的 task 中进行，期间新事件在队列中排队。
+
+use super::{export_record, AuditSink};
+use crate::core::config::WebhookSinkConfig;
+use crate::server::audit::logger::AuditEvent;
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use std::io;
+use std::time::Duration;
+
+/// 退避上限，避免配置过大的重试次数导致长时间阻塞队列
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+    auth_header: Option<String>,
+    max_retries: u32,
+    retry_backoff: Duration,
+}
+
+impl WebhookSink {
+    pub fn new(config: &WebhookSinkConfig) -> io::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms.max(1)))
+            .build()
+            .map_err(io::Error::other)?;
+        Ok(Self {
+            client,
+            url: config.url.clone(),
+            auth_header: (!config.auth_header.is_empty()).then(|| config.auth_header.clone()),
+            max_retries: config.max_retries,
+            retry_backoff: Duration::from_millis(config.retry_backoff_ms),
+        })
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(MAX_BACKOFF)
+    }
+
+    async fn post(&self, body: &serde_json::Value) -> Result<(), (bool, String)> {
+        let mut request = self.client.post(&self.url).json(body);
+        if let Some(ref auth) = self.auth_header {
+            request = request.header(reqwest::header::AUTHORIZATION, auth);
+        }
+        match request.send().await {
+            Ok(response) if response.status().is_success() => Ok(()),
+            Ok(response) => {
+                let status = response.status();
+                Err((is_retryable(status), format!("HTTP {}", status)))
+            }
+            Err(e) => Err((true, e.to_string())),
+        }
+    }
+}
+
+/// 429 与 5xx 是收集端的临时状态，值得重试
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+#[async_trait]
+impl AuditSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn send(&mut self, events: &[AuditEvent]) -> io::Result<()> {
+        let body = serde_json::Value::Array(events.iter().map(export_record).collect());
+        let mut attempt = 0;
+        loop {
+            match self.post(&body).await {
+                Ok(()) => return Ok(()),
+                Err((retryable, error)) => {
+                    if !retryable || attempt >= self.max_retries {
+                        return Err(io::Error::other(format!(
+                            "webhook delivery failed after {} attempt(s): {}",
+                            attempt + 1,
+                            error
+                        )));
+                    }
+                    tracing::debug!(
+                        event = "audit_webhook_retry",
+                        attempt = attempt + 1,
+                        error = %error,
+                        "Retrying audit webhook delivery"
+                    );
+                    tokio::time::sleep(self.backoff(attempt)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::audit::logger::{AuditEventType, AuditResult};
+    use sdforge::axum::http::HeaderMap;
+    use sdforge::axum::routing::post;
+    use sdforge::axum::{Json, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Collector {
+        attempts: AtomicUsize,
+        /// 前 N 次请求返回该状态码
+        fail_first: usize,
+        fail_status: u16,
+        received: Mutex<Vec<(Option<String>, serde_json::Value)>>,
+    }
+
+    async fn serve(collector: Arc<Collector>) -> String {
+        let app = Router::new().route(
+            "/audit",
+            post(
+                move |headers: HeaderMap, Json(body): Json<serde_json::Value>| {
+                    let collector = collector.clone();
+                    async move {
+                        let attempt = collector.attempts.fetch_add(1, Ordering::SeqCst);
+                        if attempt < collector.fail_first {
+                            return sdforge::axum::http::StatusCode::from_u16(
+                                collector.fail_status,
+                            )
+                            .unwrap();
+                        }
+                        let auth = headers
+                            .get("authorization")
+                            .and_then(|v| v.to_str().ok())
+                            .map(str::to_string);
+                        collector.received.lock().unwrap().push((auth, body));
+                        sdforge::axum::http::StatusCode::NO_CONTENT
+                    }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            sdforge::axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}/audit", addr)
+    }
+
+    fn sink(url: String, max_retries: u32) -> WebhookSink {
+        WebhookSink::new(&WebhookSinkConfig {
+            enabled: true,
+            url,
+            auth_header: "Bearer siem-token".to_string(),
+            max_retries,
+            retry_backoff_ms: 1,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn events() -> Vec<AuditEvent> {
+        ["a", "b"]
+            .iter()
+            .map(|r| {
+                AuditEvent::new(
+                    AuditEventType::ConfigChange,
+                    None,
+                    "update".to_string(),
+                    r.to_string(),
+                    AuditResult::Success,
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_webhook_retries_server_errors_then_delivers_batch() {
+        let collector = Arc::new(Collector {
+            fail_first: 2,
+            fail_status: 503,
+            ..Default::default()
+        });
+        let mut sink = sink(serve(collector.clone()).await, 3);
+
+        sink.send(&events()).await.unwrap();
+
+        assert_eq!(collector.attempts.load(Ordering::SeqCst), 3);
+        let received = collector.received.lock().unwrap().clone();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].0.as_deref(), Some("Bearer siem-token"));
+        assert_eq!(received[0].1[1]["resource"], "b");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_gives_up_on_client_error_and_exhausted_retries() {
+        let rejected = Arc::new(Collector {
+            fail_first: usize::MAX,
+            fail_status: 400,
+            ..Default::default()
+        });
+        let mut sink_400 = sink(serve(rejected.clone()).await, 3);
+        assert!(sink_400.send(&events()).await.is_err());
+        assert_eq!(
+            rejected.attempts.load(Ordering::SeqCst),
+            1,
+            "4xx is not retried"
+        );
+
+        let down = Arc::new(Collector {
+            fail_first: usize::MAX,
+            fail_status: 500,
+            ..Default::default()
+        });
+        let mut sink_500 = sink(serve(down.clone()).await, 2);
+        assert!(sink_500.send(&events()).await.is_err());
+        assert_eq!(down.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(is_retryable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable(StatusCode::NOT_FOUND));
+    }
+}
@@ -0,0 +1,330 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batched persistence of audit events to the `audit_events` table.
+//!
+//! `AuditLogger::log` 只把事件 `try_send` 到有界通道（满时丢弃并计入错误数，
+//! 不拖慢请求），后台 task 攒满 `batch_size` 或每隔 `flush_interval` 批量写入。
+//! 写入失败的事件保留到下一轮重试（主键去重），积压超过通道容量时丢弃最旧事件。
+
+use super::logger::AuditEvent;
+use crate::core::config::AuditConfig;
+use crate::core::database::{AuditEventRecord, AuditEventRepository};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+/// 数据库写入选项
+#[derive(Debug, Clone)]
+pub struct AuditStoreOptions {
+    pub batch_size: usize,
+    pub flush_interval: Duration,
+    /// 通道容量，同时是写入失败时保留重试的事件上限
+    pub channel_capacity: usize,
+}
+
+impl Default for AuditStoreOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            flush_interval: Duration::from_secs(1),
+            channel_capacity: 10_000,
+        }
+    }
+}
+
+impl From<&AuditConfig> for AuditStoreOptions {
+    fn from(config: &AuditConfig) -> Self {
+        Self {
+            batch_size: config.database_batch_size.max(1),
+            flush_interval: Duration::from_millis(config.database_flush_interval_ms.max(1)),
+            channel_capacity: config.channel_capacity.max(1),
+        }
+    }
+}
+
+enum StoreCommand {
+    Event(Box<AuditEventRecord>),
+    /// 写入所有待写事件后回复
+    Flush(oneshot::Sender<()>),
+}
+
+impl From<&AuditEvent> for AuditEventRecord {
+    fn from(event: &AuditEvent) -> Self {
+        AuditEventRecord {
+            // 审计事件 ID 高位为毫秒时间戳左移 20 位，远小于 i64::MAX
+            id: event.id as i64,
+            occurred_at: event.timestamp.naive_utc(),
+            event_type: variant_name(&event.event_type),
+            workspace_id: event.workspace_id.clone(),
+            actor: event.user_id.clone(),
+            action: event.action.clone(),
+            resource: event.resource.clone(),
+            result: variant_name(&event.result),
+            details: event.details.clone(),
+            // 与文件持久化一致的脱敏规则（见 `AuditEvent::persisted_record`）
+            client_ip: event.client_ip.as_deref().map(AuditEvent::redact_ip),
+            user_agent: event
+                .user_agent
+                .as_deref()
+                .map(|_| "UA(redacted)".to_string()),
+            duration_ms: i64::try_from(event.duration_ms).unwrap_or(i64::MAX),
+            error_message: event.error_message.clone(),
+        }
+    }
+}
+
+/// 枚举的 serde 名称（如 `BizTagCreated`），与 API 过滤参数一致
+pub(crate) fn variant_name<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+/// 数据库写入 task 的发送端；最后一个 `AuditLogger` 克隆释放后通道关闭，
+/// task 写完剩余事件后退出
+pub(crate) struct AuditStoreHandle {
+    tx: mpsc::Sender<StoreCommand>,
+    _task: JoinHandle<()>,
+}
+
+impl AuditStoreHandle {
+    /// 启动后台写入 task；必须在 tokio runtime 上下文中调用
+    pub(crate) fn spawn(
+        repo: Arc<dyn AuditEventRepository>,
+        options: AuditStoreOptions,
+        errors: Arc<AtomicU64>,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel(options.channel_capacity);
+        let writer = StoreWriter {
+            repo,
+            pending: Vec::new(),
+            batch_size: options.batch_size,
+            max_pending: options.channel_capacity,
+            errors,
+        };
+        let task = tokio::spawn(writer.run(rx, options.flush_interval));
+        Self { tx, _task: task }
+    }
+
+    /// 非阻塞入队；通道满或已关闭时返回 `false`
+    pub(crate) fn try_record(&self, event: &AuditEvent) -> bool {
+        self.tx
+            .try_send(StoreCommand::Event(Box::new(AuditEventRecord::from(event))))
+            .is_ok()
+    }
+
+    /// 等待此前入队的事件写入数据库（写入失败的事件仍留待重试）
+    pub(crate) async fn flush(&self) {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        if self.tx.send(StoreCommand::Flush(ack_tx)).await.is_ok() {
+            let _ = ack_rx.await;
+        }
+    }
+}
+
+struct StoreWriter {
+    repo: Arc<dyn AuditEventRepository>,
+    pending: Vec<AuditEventRecord>,
+    batch_size: usize,
+    max_pending: usize,
+    errors: Arc<AtomicU64>,
+}
+
+impl StoreWriter {
+    async fn run(mut self, mut rx: mpsc::Receiver<StoreCommand>, flush_interval: Duration) {
+        // 首次触发推迟一个间隔，避免启动时立即写出不满一批的事件
+        let mut ticker =
+            tokio::time::interval_at(tokio::time::Instant::now() + flush_interval, flush_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                command = rx.recv() => match command {
+                    Some(StoreCommand::Event(record)) => {
+                        self.pending.push(*record);
+                        if self.pending.len() >= self.batch_size {
+                            self.write_pending().await;
+                        }
+                    }
+                    Some(StoreCommand::Flush(ack)) => {
+                        self.write_pending().await;
+                        let _ = ack.send(());
+                    }
+                    None => {
+                        self.write_pending().await;
+                        break;
+                    }
+                },
+                _ = ticker.tick() => self.write_pending().await,
+            }
+        }
+    }
+
+    /// 按批写入全部待写事件；某批失败时停止，保留剩余事件等待下一轮
+    async fn write_pending(&mut self) {
+        while !self.pending.is_empty() {
+            let end = self.pending.len().min(self.batch_size);
+            match self.repo.insert_audit_events(&self.pending[..end]).await {
+                Ok(_) => {
+                    self.pending.drain(..end);
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "{}",
+                        t!("log.server.audit.logger.persist_failed", error = e)
+                    );
+                    self.trim_backlog();
+                    return;
+                }
+            }
+        }
+    }
+
+    fn trim_backlog(&mut self) {
+        if self.pending.len() <= self.max_pending {
+            return;
+        }
+        let dropped = self.pending.len() - self.max_pending;
+        self.pending.drain(..dropped);
+        self.errors.fetch_add(dropped as u64, Ordering::SeqCst);
+        tracing::warn!(
+            event = "audit_db_backlog_dropped",
+            dropped,
+            max_pending = self.max_pending,
+            "Audit database backlog full, dropped oldest events"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::database::AuditEventQuery;
+    use crate::core::types::Result;
+    use crate::server::audit::{AuditEventType, AuditResult};
+    use async_trait::async_trait;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MemoryAuditRepo {
+        rows: Mutex<Vec<AuditEventRecord>>,
+        batches: AtomicU64,
+        fail: AtomicBool,
+    }
+
+    #[async_trait]
+    impl AuditEventRepository for MemoryAuditRepo {
+        async fn insert_audit_events(&self, events: &[AuditEventRecord]) -> Result<u64> {
+            if self.fail.load(Ordering::SeqCst) {
+                return Err(crate::core::CoreError::DatabaseError("down".to_string()));
+            }
+            self.batches.fetch_add(1, Ordering::SeqCst);
+            self.rows.lock().unwrap().extend_from_slice(events);
+            Ok(events.len() as u64)
+        }
+
+        async fn query_audit_events(
+            &self,
+            _query: &AuditEventQuery,
+        ) -> Result<Vec<AuditEventRecord>> {
+            Ok(self.rows.lock().unwrap().clone())
+        }
+    }
+
+    fn event(resource: &str) -> AuditEvent {
+        AuditEvent::new(
+            AuditEventType::BizTagCreated,
+            Some("ws-1".to_string()),
+            "create_biz_tag".to_string(),
+            resource.to_string(),
+            AuditResult::Success,
+        )
+        .with_user_id("nino_key".to_string())
+        .with_client_ip("192.168.1.23".to_string())
+        .with_user_agent("curl/8.0".to_string())
+    }
+
+    #[test]
+    fn test_record_from_event_uses_serde_names_and_redacts() {
+        let record = AuditEventRecord::from(&event("biz_tag:orders"));
+        assert_eq!(record.event_type, "BizTagCreated");
+        assert_eq!(record.result, "Success");
+        assert_eq!(record.actor.as_deref(), Some("nino_key"));
+        assert_eq!(record.client_ip.as_deref(), Some("192.168.1.x"));
+        assert_eq!(record.user_agent.as_deref(), Some("UA(redacted)"));
+    }
+
+    #[tokio::test]
+    async fn test_store_writes_in_batches_and_flushes_remainder() {
+        let repo = Arc::new(MemoryAuditRepo::default());
+        let handle = AuditStoreHandle::spawn(
+            repo.clone(),
+            AuditStoreOptions {
+                batch_size: 2,
+                flush_interval: Duration::from_secs(3600),
+                channel_capacity: 16,
+            },
+            Arc::new(AtomicU64::new(0)),
+        );
+
+        for i in 0..5 {
+            assert!(handle.try_record(&event(&format!("biz_tag:{}", i))));
+        }
+        handle.flush().await;
+
+        let rows = repo.rows.lock().unwrap().clone();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[4].resource, "biz_tag:4");
+        assert_eq!(repo.batches.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_store_retries_failed_batch_and_bounds_backlog() {
+        let repo = Arc::new(MemoryAuditRepo::default());
+        repo.fail.store(true, Ordering::SeqCst);
+        let errors = Arc::new(AtomicU64::new(0));
+        let handle = AuditStoreHandle::spawn(
+            repo.clone(),
+            AuditStoreOptions {
+                batch_size: 10,
+                flush_interval: Duration::from_secs(3600),
+                channel_capacity: 3,
+            },
+            errors.clone(),
+        );
+
+        for i in 0..4 {
+            handle.try_record(&event(&format!("biz_tag:{}", i)));
+            handle.flush().await;
+        }
+        // 积压上限 3：最旧的一条被丢弃
+        assert_eq!(errors.load(Ordering::SeqCst), 1);
+        assert!(repo.rows.lock().unwrap().is_empty());
+
+        repo.fail.store(false, Ordering::SeqCst);
+        handle.flush().await;
+        let resources: Vec<String> = repo
+            .rows
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.resource.clone())
+            .collect();
+        assert_eq!(resources, vec!["biz_tag:1", "biz_tag:2", "biz_tag:3"]);
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Audit capture harness for tests.
+//!
+//! [`AuditCapture`] 同步记录事件，不经过内存环形缓冲、文件队列或 sink，
+//! 因此断言不受容量淘汰与异步投递的影响。两种用法：
+//!
+//! - `AuditLogger::new(n).with_capture(capture.clone())`：服务端 logger 的完整
+//!   `log` 路径照常执行，同时复制一份事件；
+//! - 直接作为 [`DynAuditLogger`](crate::core::algorithm::DynAuditLogger)
+//!   注入只依赖核心 trait 的组件（如降级管理器）。
+
+use super::logger::{AuditEvent, AuditEventType, AuditLogger};
+use crate::core::algorithm::AuditLogger as CoreAuditLoggerTrait;
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// 共享的事件捕获器；克隆共享同一份事件列表
+#[derive(Debug, Clone, Default)]
+pub struct AuditCapture {
+    events: Arc<Mutex<Vec<AuditEvent>>>,
+}
+
+impl AuditCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 附带本捕获器的内存 logger
+    pub fn logger(&self, max_events: usize) -> AuditLogger {
+        AuditLogger::new(max_events).with_capture(self.clone())
+    }
+
+    pub(crate) fn record(&self, event: &AuditEvent) {
+        self.events.lock().push(event.clone());
+    }
+
+    /// 按记录顺序返回全部事件
+    pub fn events(&self) -> Vec<AuditEvent> {
+        self.events.lock().clone()
+    }
+
+    pub fn of_type(&self, event_type: &AuditEventType) -> Vec<AuditEvent> {
+        self.find_all(|event| &event.event_type == event_type)
+    }
+
+    pub fn find_all(&self, predicate: impl Fn(&AuditEvent) -> bool) -> Vec<AuditEvent> {
+        self.events
+            .lock()
+            .iter()
+            .filter(|event| predicate(event))
+            .cloned()
+            .collect()
+    }
+
+    pub fn last(&self) -> Option<AuditEvent> {
+        self.events.lock().last().cloned()
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.lock().is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.events.lock().clear();
+    }
+
+    /// 断言恰好记录了一条 `event_type` + `action` 的事件并返回它；
+    /// 失败时列出已捕获的事件，便于定位
+    #[track_caller]
+    pub fn expect_one(&self, event_type: &AuditEventType, action: &str) -> AuditEvent {
+        let matches =
+            self.find_all(|event| &event.event_type == event_type && event.action == action);
+        match matches.as_slice() {
+            [event] => event.clone(),
+            _ => panic!(
+                "expected exactly one {:?} '{}' audit event, found {}; captured: {:?}",
+                event_type,
+                action,
+                matches.len(),
+                self.events()
+                    .iter()
+                    .map(|e| format!("{:?} {}", e.event_type, e.action))
+                    .collect::<Vec<_>>()
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl CoreAuditLoggerTrait for AuditCapture {
+    async fn log(&self, event: AuditEvent) {
+        self.record(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::algorithm::{AuditContext, AuditResult, DynAuditLogger};
+
+    #[tokio::test]
+    async fn test_capture_attached_to_logger_sees_evicted_events() {
+        let capture = AuditCapture::new();
+        let logger = capture.logger(1);
+
+        logger
+            .log_workspace_created("ws-1".to_string(), "alpha".to_string(), None, None)
+            .await;
+        logger
+            .log_workspace_deleted("ws-1".to_string(), "alpha".to_string(), None, None)
+            .await;
+
+        // 内存缓冲只保留 1 条，捕获器保留全部
+        assert_eq!(logger.get_recent_events(10).await.len(), 1);
+        assert_eq!(capture.len(), 2);
+        let created = capture.expect_one(&AuditEventType::WorkspaceCreated, "create_workspace");
+        assert_eq!(created.resource, "workspace:alpha");
+        assert_eq!(
+            capture.last().unwrap().event_type,
+            AuditEventType::WorkspaceDeleted
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capture_as_core_logger_records_context() {
+        let capture = AuditCapture::new();
+        let dyn_logger: DynAuditLogger = Arc::new(capture.clone());
+
+        AuditContext {
+            actor: Some("key-7".to_string()),
+            request_id: Some("req-7".to_string()),
+        }
+        .scope(dyn_logger.log_auth_event(
+            None,
+            "api_key_auth".to_string(),
+            false,
+            Some("10.0.0.1".to_string()),
+            Some("invalid key".to_string()),
+        ))
+        .await;
+
+        let events = capture.of_type(&AuditEventType::Authentication);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].result, AuditResult::Failure);
+        assert_eq!(events[0].user_id.as_deref(), Some("key-7"));
+        assert_eq!(events[0].request_id.as_deref(), Some("req-7"));
+
+        capture.clear();
+        assert!(capture.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected exactly one")]
+    fn test_expect_one_panics_when_missing() {
+        AuditCapture::new().expect_one(&AuditEventType::ConfigChange, "update");
+    }
+}
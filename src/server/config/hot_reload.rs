@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::core::algorithm::AuditLogger as _;
 use crate::core::config::app_config::parse_toml_config;
 use crate::core::config::Config;
 use crate::core::types::id::AlgorithmType;
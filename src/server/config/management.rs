@@ -85,6 +85,12 @@ pub trait ConfigManagementService: Send + Sync {
     ) -> crate::core::Result<WorkspaceResponse>;
     async fn list_workspaces(&self) -> crate::core::Result<WorkspaceListResponse>;
     async fn get_workspace(&self, name: &str) -> crate::core::Result<Option<WorkspaceResponse>>;
+    /// 按名称更新 workspace；不存在时返回 `Ok(None)`
+    async fn update_workspace(
+        &self,
+        name: &str,
+        request: &crate::core::database::UpdateWorkspaceRequest,
+    ) -> crate::core::Result<Option<WorkspaceResponse>>;
     async fn create_group(&self, req: CreateGroupRequest) -> crate::core::Result<GroupResponse>;
     async fn list_groups(&self, workspace: &str) -> crate::core::Result<GroupListResponse>;
 
@@ -144,6 +150,21 @@ impl ConfigManager {
         }
     }
 
+    fn workspace_to_response(workspace: crate::core::database::Workspace) -> WorkspaceResponse {
+        WorkspaceResponse {
+            id: workspace.id.to_string(),
+            name: workspace.name,
+            description: workspace.description,
+            status: workspace.status.to_string(),
+            max_groups: workspace.max_groups,
+            max_biz_tags: workspace.max_biz_tags,
+            strict_generation: workspace.strict_generation,
+            created_at: workspace.created_at.and_utc().to_rfc3339(),
+            updated_at: workspace.updated_at.and_utc().to_rfc3339(),
+            user_api_key: None,
+        }
+    }
+
     fn config_to_response(config: &Config) -> ConfigResponse {
         ConfigResponse {
             app: AppConfigInfo {
@@ -483,17 +504,7 @@ impl ConfigManagementService for ConfigManager {
                 max_biz_tags: req.max_biz_tags,
             };
             let workspace = repo.create_workspace(&request).await?;
-            Ok(WorkspaceResponse {
-                id: workspace.id.to_string(),
-                name: workspace.name,
-                description: workspace.description,
-                status: workspace.status.to_string(),
-                max_groups: workspace.max_groups,
-                max_biz_tags: workspace.max_biz_tags,
-                created_at: workspace.created_at.and_utc().to_rfc3339(),
-                updated_at: workspace.updated_at.and_utc().to_rfc3339(),
-                user_api_key: None,
-            })
+            Ok(Self::workspace_to_response(workspace))
         } else {
             Err(crate::core::CoreError::InternalError(
                 "Workspace repository not configured".to_string(),
@@ -507,17 +518,7 @@ impl ConfigManagementService for ConfigManager {
             let total = workspaces.len() as u64;
             let workspace_responses: Vec<WorkspaceResponse> = workspaces
                 .into_iter()
-                .map(|w| WorkspaceResponse {
-                    id: w.id.to_string(),
-                    name: w.name,
-                    description: w.description,
-                    status: w.status.to_string(),
-                    max_groups: w.max_groups,
-                    max_biz_tags: w.max_biz_tags,
-                    created_at: w.created_at.and_utc().to_rfc3339(),
-                    updated_at: w.updated_at.and_utc().to_rfc3339(),
-                    user_api_key: None,
-                })
+                .map(Self::workspace_to_response)
                 .collect();
             Ok(WorkspaceListResponse {
                 workspaces: workspace_responses,
@@ -533,17 +534,25 @@ impl ConfigManagementService for ConfigManager {
     async fn get_workspace(&self, name: &str) -> crate::core::Result<Option<WorkspaceResponse>> {
         if let Some(ref repo) = self.workspace_repository {
             let workspace = repo.get_workspace_by_name(name).await?;
-            Ok(workspace.map(|w| WorkspaceResponse {
-                id: w.id.to_string(),
-                name: w.name,
-                description: w.description,
-                status: w.status.to_string(),
-                max_groups: w.max_groups,
-                max_biz_tags: w.max_biz_tags,
-                created_at: w.created_at.and_utc().to_rfc3339(),
-                updated_at: w.updated_at.and_utc().to_rfc3339(),
-                user_api_key: None,
-            }))
+            Ok(workspace.map(Self::workspace_to_response))
+        } else {
+            Err(crate::core::CoreError::InternalError(
+                "Workspace repository not configured".to_string(),
+            ))
+        }
+    }
+
+    async fn update_workspace(
+        &self,
+        name: &str,
+        request: &crate::core::database::UpdateWorkspaceRequest,
+    ) -> crate::core::Result<Option<WorkspaceResponse>> {
+        if let Some(ref repo) = self.workspace_repository {
+            let Some(existing) = repo.get_workspace_by_name(name).await? else {
+                return Ok(None);
+            };
+            let workspace = repo.update_workspace(existing.id, request).await?;
+            Ok(Some(Self::workspace_to_response(workspace)))
         } else {
             Err(crate::core::CoreError::InternalError(
                 "Workspace repository not configured".to_string(),
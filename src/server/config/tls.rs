@@ -14,17 +14,51 @@
 
 //! TLS 服务器模块
 //! 提供 HTTPS 和 gRPC TLS 支持
-
-use crate::core::config::TlsConfig;
-use rustls::pki_types::PrivateKeyDer;
-use sdforge::tonic::transport::{Certificate, Identity, ServerTlsConfig};
+//!
+//! HTTP 与 gRPC 共用同一套 rustls 配置（ALPN 不同），客户端证书按
+//! `tls.client_auth` 以 `ca_path` 为信任根校验，并检查 `crl_paths` 中的吊销列表。
+//! [`TlsListener`] 在独立任务中完成握手，[`TlsConnectInfo`] 把客户端证书带到
+//! 请求扩展中供认证中间件映射调用方。
+//!
+//! 服务端证书通过 `CertificateResolver` 提供，[`TlsManager::watch`] 检测到证书文件
+//! 变化后原子替换，监听器与已建立的连接（含 gRPC 流）不受影响。
+
+use crate::core::config::{ClientAuthMode, TlsConfig};
+use arc_swap::ArcSwap;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, CertificateRevocationListDer, PrivateKeyDer};
+use rustls::server::{ClientHello, ResolvesServerCert, WebPkiClientVerifier};
+use rustls::sign::CertifiedKey;
+use rustls::RootCertStore;
+use sdforge::axum::extract::connect_info::Connected;
+use sdforge::axum::serve::{IncomingStream, Listener};
 use std::fs::File;
 use std::io::BufReader;
+use std::net::SocketAddr;
 use std::path::Path;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 use thiserror::Error;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
 use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::server::TlsStream;
 use tokio_rustls::TlsAcceptor;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+
+/// 单个连接完成 TLS 握手的最长时间
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 已完成握手、等待服务端取走的连接数
+const TLS_ACCEPT_BACKLOG: usize = 128;
+
+/// `accept` 失败（如文件描述符耗尽）后的退避
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// 证书临近过期时两次告警的最小间隔
+const EXPIRY_WARNING_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
 
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum TlsError {
@@ -36,15 +70,65 @@ pub enum TlsError {
 
     #[error("Invalid TLS configuration: {}", _0)]
     InvalidConfig(String),
+
+    #[error("Failed to load certificate revocation list: {}", _0)]
+    RevocationListLoadError(String),
 }
 
 pub type TlsResult<T> = std::result::Result<T, TlsError>;
 
+/// 服务端证书指标
+#[derive(Debug, Default)]
+pub struct TlsCertificateMetrics {
+    /// 当前证书的 notAfter（Unix 秒），未加载证书时为 0
+    pub not_after: AtomicI64,
+    pub reloads_total: AtomicU64,
+    pub reload_failures_total: AtomicU64,
+}
+
+impl TlsCertificateMetrics {
+    /// 距离证书过期的秒数（已过期为负数）；未加载证书时为 `None`
+    pub fn seconds_until_expiry(&self) -> Option<i64> {
+        let not_after = self.not_after.load(Ordering::Relaxed);
+        (not_after != 0).then(|| not_after - chrono::Utc::now().timestamp())
+    }
+
+    fn record_certificate(&self, certified_key: &CertifiedKey) {
+        let not_after = certified_key
+            .end_entity_cert()
+            .ok()
+            .and_then(|cert| x509_parser::parse_x509_certificate(cert).ok())
+            .map(|(_, cert)| cert.validity().not_after.timestamp())
+            .unwrap_or_default();
+        self.not_after.store(not_after, Ordering::Relaxed);
+    }
+}
+
+/// 为每次握手提供当前证书；[`TlsManager::reload_certificate`] 原子替换
+#[derive(Debug)]
+struct CertificateResolver {
+    current: ArcSwap<CertifiedKey>,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ResolvesServerCert for CertificateResolver {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.load_full())
+    }
+}
+
+async fn modified_time(path: &str) -> Option<SystemTime> {
+    tokio::fs::metadata(path).await.ok()?.modified().ok()
+}
+
 #[derive(Clone)]
 pub struct TlsManager {
     config: TlsConfig,
     http_acceptor: Option<TlsAcceptor>,
-    grpc_tls_config: Option<Arc<ServerTlsConfig>>,
+    grpc_acceptor: Option<TlsAcceptor>,
+    /// HTTP 与 gRPC 共用，热加载时替换其中的证书
+    resolver: Option<Arc<CertificateResolver>>,
+    metrics: Arc<TlsCertificateMetrics>,
 }
 
 impl TlsManager {
@@ -52,7 +136,9 @@ impl TlsManager {
         Self {
             config,
             http_acceptor: None,
-            grpc_tls_config: None,
+            grpc_acceptor: None,
+            resolver: None,
+            metrics: Arc::new(TlsCertificateMetrics::default()),
         }
     }
 
@@ -61,15 +147,33 @@ impl TlsManager {
     }
 
     pub fn is_grpc_enabled(&self) -> bool {
-        self.config.grpc_enabled && self.grpc_tls_config.is_some()
+        self.config.grpc_enabled && self.grpc_acceptor.is_some()
     }
 
     pub fn http_acceptor(&self) -> Option<&TlsAcceptor> {
         self.http_acceptor.as_ref()
     }
 
-    pub fn grpc_tls_config(&self) -> Option<&Arc<ServerTlsConfig>> {
-        self.grpc_tls_config.as_ref()
+    pub fn grpc_acceptor(&self) -> Option<&TlsAcceptor> {
+        self.grpc_acceptor.as_ref()
+    }
+
+    /// 当前服务端证书的有效期与热加载计数，出现在 `/metrics`
+    pub fn certificate_metrics(&self) -> Arc<TlsCertificateMetrics> {
+        self.metrics.clone()
+    }
+
+    pub fn http_client_auth(&self) -> ClientAuthMode {
+        self.config.client_auth
+    }
+
+    /// gRPC 的客户端证书模式。未配置 `client_auth` 时，设置了 `ca_path` 即要求
+    /// 客户端证书，与改用 rustls 配置之前 tonic `client_ca_root` 的行为一致
+    pub fn grpc_client_auth(&self) -> ClientAuthMode {
+        match self.config.client_auth {
+            ClientAuthMode::None if self.config.ca_path.is_some() => ClientAuthMode::Required,
+            mode => mode,
+        }
     }
 
     pub async fn initialize(&mut self) -> TlsResult<()> {
@@ -88,6 +192,75 @@ impl TlsManager {
             }
         }
 
+        let provider = ServerConfig::builder().crypto_provider().clone();
+        let certified_key = self.load_certified_key(&provider)?;
+        self.metrics.record_certificate(&certified_key);
+        let resolver = Arc::new(CertificateResolver {
+            current: ArcSwap::new(certified_key),
+            provider,
+        });
+
+        // 为 HTTP 配置 TLS with version enforcement
+        if self.config.http_enabled {
+            let mut config_with_alpn =
+                self.server_config(self.http_client_auth(), resolver.clone())?;
+
+            // tiangang H1 部分修复：rustls 0.23 的 ServerConfig 不暴露 protocol_versions
+            // 公开字段，with_no_client_auth() 快捷方法跳过 versions 配置。
+            // 当前依赖 rustls 默认（TLS 1.2+1.3），并通过 min_tls_version 配置记录意图。
+            // 完整强制需迁移到 rustls CryptoProvider 自定义流程，延后到 v0.3.0。
+            match self.config.min_tls_version {
+                crate::core::config::TlsVersion::Tls12 => {
+                    tracing::info!(
+                        "{}",
+                        t!("log.server.config.tls.tls12_configured_rustls_default")
+                    );
+                }
+                crate::core::config::TlsVersion::Tls13 => {
+                    tracing::warn!(
+                        "{}",
+                        t!("log.server.config.tls.tls13_only_requested_auto_negotiate")
+                    );
+                }
+            }
+
+            // 配置 ALPN 协议 (HTTP/2 支持)
+            if !self.config.alpn_protocols.is_empty() {
+                let alpn_protocols: Vec<Vec<u8>> = self
+                    .config
+                    .alpn_protocols
+                    .iter()
+                    .map(|s| s.as_bytes().to_vec())
+                    .collect();
+                config_with_alpn.alpn_protocols = alpn_protocols;
+            }
+            self.http_acceptor = Some(TlsAcceptor::from(Arc::new(config_with_alpn)));
+        }
+
+        // 为 gRPC 配置 TLS（gRPC 只走 HTTP/2）
+        if self.config.grpc_enabled {
+            let mut grpc_config = self.server_config(self.grpc_client_auth(), resolver.clone())?;
+            grpc_config.alpn_protocols = vec![b"h2".to_vec()];
+            self.grpc_acceptor = Some(TlsAcceptor::from(Arc::new(grpc_config)));
+        }
+        self.resolver = Some(resolver);
+
+        tracing::info!(
+            event = "tls_initialized",
+            http_enabled = %self.config.http_enabled,
+            grpc_enabled = %self.config.grpc_enabled,
+            min_version = %self.config.min_tls_version,
+            http_client_auth = %self.http_client_auth(),
+            grpc_client_auth = %self.grpc_client_auth(),
+            "{}",
+            t!("log.server.config.tls.tls_initialized")
+        );
+
+        Ok(())
+    }
+
+    /// 读取 `cert_path` / `key_path`，校验私钥与证书匹配
+    fn load_certified_key(&self, provider: &CryptoProvider) -> TlsResult<Arc<CertifiedKey>> {
         let cert_path = Path::new(&self.config.cert_path);
         let key_path = Path::new(&self.config.key_path);
 
@@ -158,102 +331,257 @@ impl TlsManager {
         let private_key_der = private_key_der
             .ok_or_else(|| TlsError::PrivateKeyLoadError("Empty private key".to_string()))?;
 
-        // 获取 PEM 字节用于 gRPC Identity
-        let cert_pem = cert_der.as_ref();
-        let key_pem: &[u8] = match &private_key_der {
-            PrivateKeyDer::Pkcs1(k) => k.secret_pkcs1_der(),
-            PrivateKeyDer::Pkcs8(k) => k.secret_pkcs8_der(),
-            PrivateKeyDer::Sec1(k) => k.secret_sec1_der(),
-            _ => unreachable!(),
+        CertifiedKey::from_der(vec![cert_der], private_key_der, provider)
+            .map(Arc::new)
+            .map_err(|e| TlsError::InvalidConfig(e.to_string()))
+    }
+
+    /// 重新读取证书与私钥并原子替换，只影响之后的新握手，已建立的连接不受影响。
+    /// 内容未变化时返回 `Ok(false)`；失败时保留当前证书并计入 `reload_failures_total`。
+    pub fn reload_certificate(&self) -> TlsResult<bool> {
+        let Some(resolver) = self.resolver.as_ref() else {
+            return Ok(false);
+        };
+        let certified_key = match self.load_certified_key(&resolver.provider) {
+            Ok(certified_key) => certified_key,
+            Err(e) => {
+                self.metrics
+                    .reload_failures_total
+                    .fetch_add(1, Ordering::Relaxed);
+                return Err(e);
+            }
         };
+        if certified_key.cert == resolver.current.load().cert {
+            return Ok(false);
+        }
+        resolver.current.store(certified_key.clone());
+        self.metrics.record_certificate(&certified_key);
+        self.metrics.reloads_total.fetch_add(1, Ordering::Relaxed);
+        tracing::info!(
+            event = "tls_certificate_reloaded",
+            cert_path = %self.config.cert_path,
+            not_after = self.metrics.not_after.load(Ordering::Relaxed),
+            "TLS certificate reloaded"
+        );
+        Ok(true)
+    }
 
-        // 创建 Identity 用于 gRPC
-        let identity = Identity::from_pem(cert_pem, key_pem);
+    /// 按 `reload_interval_seconds` 轮询证书与私钥的修改时间（同 `HotReloadConfig::watch`），
+    /// 变化时调用 [`Self::reload_certificate`]；失败时下一轮重试（如证书与私钥未同时写完）。
+    /// 每轮检查剩余有效期，低于 `expiry_warning_days` 时每天告警一次。
+    pub async fn watch(&self) {
+        if self.resolver.is_none() || self.config.reload_interval_seconds == 0 {
+            return;
+        }
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.config.reload_interval_seconds));
+        let mut last_modified = None;
+        let mut last_expiry_warning: Option<Instant> = None;
 
-        // 为 HTTP 配置 TLS with version enforcement
-        if self.config.http_enabled {
-            let mut config_with_alpn = ServerConfig::builder()
-                .with_no_client_auth()
-                .with_single_cert(vec![cert_der.clone()], private_key_der.clone_key())
-                .map_err(|e| TlsError::InvalidConfig(e.to_string()))?;
+        loop {
+            interval.tick().await;
+
+            let modified = (
+                modified_time(&self.config.cert_path).await,
+                modified_time(&self.config.key_path).await,
+            );
+            match last_modified {
+                None => last_modified = Some(modified),
+                Some(previous) if previous != modified => match self.reload_certificate() {
+                    Ok(_) => last_modified = Some(modified),
+                    Err(e) => tracing::warn!(
+                        event = "tls_certificate_reload_failed",
+                        cert_path = %self.config.cert_path,
+                        error = %e,
+                        "Keeping the current TLS certificate"
+                    ),
+                },
+                Some(_) => {}
+            }
 
-            // tiangang H1 部分修复：rustls 0.23 的 ServerConfig 不暴露 protocol_versions
-            // 公开字段，with_no_client_auth() 快捷方法跳过 versions 配置。
-            // 当前依赖 rustls 默认（TLS 1.2+1.3），并通过 min_tls_version 配置记录意图。
-            // 完整强制需迁移到 rustls CryptoProvider 自定义流程，延后到 v0.3.0。
-            match self.config.min_tls_version {
-                crate::core::config::TlsVersion::Tls12 => {
-                    tracing::info!(
-                        "{}",
-                        t!("log.server.config.tls.tls12_configured_rustls_default")
-                    );
-                }
-                crate::core::config::TlsVersion::Tls13 => {
-                    tracing::warn!(
-                        "{}",
-                        t!("log.server.config.tls.tls13_only_requested_auto_negotiate")
-                    );
-                }
+            if last_expiry_warning.is_some_and(|at| at.elapsed() < EXPIRY_WARNING_INTERVAL) {
+                continue;
             }
+            let Some(remaining) = self.metrics.seconds_until_expiry() else {
+                continue;
+            };
+            let threshold = self.config.expiry_warning_days.saturating_mul(86_400);
+            if remaining < i64::try_from(threshold).unwrap_or(i64::MAX) {
+                tracing::warn!(
+                    event = "tls_certificate_expiring",
+                    cert_path = %self.config.cert_path,
+                    days_remaining = remaining / 86_400,
+                    "TLS certificate is close to expiry"
+                );
+                last_expiry_warning = Some(Instant::now());
+            }
+        }
+    }
 
-            // 配置 ALPN 协议 (HTTP/2 支持)
-            if !self.config.alpn_protocols.is_empty() {
-                let alpn_protocols: Vec<Vec<u8>> = self
-                    .config
-                    .alpn_protocols
-                    .iter()
-                    .map(|s| s.as_bytes().to_vec())
-                    .collect();
-                config_with_alpn.alpn_protocols = alpn_protocols;
+    fn server_config(
+        &self,
+        client_auth: ClientAuthMode,
+        resolver: Arc<CertificateResolver>,
+    ) -> TlsResult<ServerConfig> {
+        let builder = ServerConfig::builder();
+        let builder = match client_auth {
+            ClientAuthMode::None => builder.with_no_client_auth(),
+            mode => builder.with_client_cert_verifier(self.client_verifier(mode)?),
+        };
+        Ok(builder.with_cert_resolver(resolver))
+    }
+
+    /// 以 `ca_path` 为信任根校验客户端证书链，并按 `crl_paths` 检查吊销。
+    /// 吊销列表未覆盖的签发者视为未吊销。
+    fn client_verifier(
+        &self,
+        client_auth: ClientAuthMode,
+    ) -> TlsResult<Arc<dyn rustls::server::danger::ClientCertVerifier>> {
+        let ca_path = self.config.ca_path.as_deref().ok_or_else(|| {
+            TlsError::InvalidConfig(format!("client_auth = {} requires ca_path", client_auth))
+        })?;
+        let mut roots = RootCertStore::empty();
+        for cert in load_certificates(Path::new(ca_path))? {
+            roots
+                .add(cert)
+                .map_err(|e| TlsError::CertificateLoadError(e.to_string()))?;
+        }
+        if roots.is_empty() {
+            return Err(TlsError::CertificateLoadError(
+                "Empty CA certificate".to_string(),
+            ));
+        }
+
+        let mut builder = WebPkiClientVerifier::builder(Arc::new(roots))
+            .with_crls(self.load_revocation_lists()?)
+            .allow_unknown_revocation_status();
+        if client_auth == ClientAuthMode::Optional {
+            builder = builder.allow_unauthenticated();
+        }
+        builder
+            .build()
+            .map_err(|e| TlsError::InvalidConfig(e.to_string()))
+    }
+
+    fn load_revocation_lists(&self) -> TlsResult<Vec<CertificateRevocationListDer<'static>>> {
+        let mut crls = Vec::new();
+        for path in &self.config.crl_paths {
+            let file = File::open(path)
+                .map_err(|e| TlsError::RevocationListLoadError(format!("{}: {}", path, e)))?;
+            for crl in rustls_pemfile::crls(&mut BufReader::new(file)) {
+                crls.push(
+                    crl.map_err(|e| TlsError::RevocationListLoadError(format!("{}: {}", path, e)))?,
+                );
             }
-            self.http_acceptor = Some(TlsAcceptor::from(Arc::new(config_with_alpn)));
         }
+        Ok(crls)
+    }
+}
 
-        // 为 gRPC 配置 TLS
-        if self.config.grpc_enabled {
-            let mut grpc_config = ServerTlsConfig::new().identity(identity);
-
-            if let Some(ref ca_path) = self.config.ca_path {
-                let ca_file = File::open(ca_path)
-                    .map_err(|e| TlsError::CertificateLoadError(e.to_string()))?;
-                let mut ca_reader = BufReader::new(ca_file);
-
-                // 读取 CA 证书
-                let mut ca_certs = Vec::new();
-                loop {
-                    match rustls_pemfile::read_one(&mut ca_reader) {
-                        Ok(Some(rustls_pemfile::Item::X509Certificate(cert))) => {
-                            ca_certs.push(cert);
-                            break;
+fn load_certificates(path: &Path) -> TlsResult<Vec<CertificateDer<'static>>> {
+    let file = File::open(path).map_err(|e| TlsError::CertificateLoadError(e.to_string()))?;
+    rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| TlsError::CertificateLoadError(e.to_string()))
+}
+
+/// TLS 监听器：接受 TCP 连接后在独立任务中握手（带超时），慢客户端或握手失败
+/// 不会阻塞其他连接。HTTP 通过 [`Listener`] 交给 `axum::serve`，gRPC 通过
+/// [`TlsListener::into_stream`] 交给 tonic 的 `serve_with_incoming`。
+pub struct TlsListener {
+    local_addr: SocketAddr,
+    connections: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+}
+
+impl TlsListener {
+    pub fn new(listener: TcpListener, acceptor: TlsAcceptor) -> std::io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let (tx, connections) = mpsc::channel(TLS_ACCEPT_BACKLOG);
+        tokio::spawn(async move {
+            while !tx.is_closed() {
+                let (stream, remote_addr) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        tracing::warn!(event = "tcp_accept_failed", error = %e);
+                        tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                        continue;
+                    }
+                };
+                let acceptor = acceptor.clone();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await
+                    {
+                        Ok(Ok(stream)) => {
+                            let _ = tx.send((stream, remote_addr)).await;
                         }
-                        Ok(Some(_)) => continue,
-                        Ok(None) => break,
-                        Err(e) => return Err(TlsError::CertificateLoadError(e.to_string())),
+                        // 未出示、未通过校验或已吊销的客户端证书都在这里失败
+                        Ok(Err(e)) => tracing::debug!(
+                            event = "tls_handshake_failed",
+                            remote_addr = %remote_addr,
+                            error = %e
+                        ),
+                        Err(_) => tracing::debug!(
+                            event = "tls_handshake_timeout",
+                            remote_addr = %remote_addr
+                        ),
                     }
-                }
+                });
+            }
+        });
+        Ok(Self {
+            local_addr,
+            connections,
+        })
+    }
 
-                let ca_cert = ca_certs.into_iter().next().ok_or_else(|| {
-                    TlsError::CertificateLoadError("Empty CA certificate".to_string())
-                })?;
+    /// 已完成握手的连接流（tonic 从 TLS 流中读取客户端证书）
+    pub fn into_stream(self) -> impl Stream<Item = std::io::Result<TlsStream<TcpStream>>> {
+        ReceiverStream::new(self.connections).map(|(stream, _)| Ok(stream))
+    }
+}
 
-                let ca_cert_bytes = ca_cert.as_ref().to_vec();
-                let ca_cert_tonic = Certificate::from_pem(ca_cert_bytes);
-                grpc_config = grpc_config.client_ca_root(ca_cert_tonic);
-            }
+impl Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
 
-            self.grpc_tls_config = Some(Arc::new(grpc_config));
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.connections.recv().await {
+            Some(connection) => connection,
+            // 接收任务不会先于监听器结束
+            None => std::future::pending().await,
         }
+    }
 
-        tracing::info!(
-            event = "tls_initialized",
-            http_enabled = %self.config.http_enabled,
-            grpc_enabled = %self.config.grpc_enabled,
-            min_version = %self.config.min_tls_version,
-            "{}",
-            t!("log.server.config.tls.tls_initialized")
-        );
+    fn local_addr(&self) -> std::io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
 
-        Ok(())
+/// HTTPS 连接信息，通过 `into_make_service_with_connect_info::<TlsConnectInfo>()`
+/// 以 `ConnectInfo<TlsConnectInfo>` 扩展出现在每个请求中
+#[derive(Debug, Clone)]
+pub struct TlsConnectInfo {
+    pub remote_addr: SocketAddr,
+    /// 客户端证书链（叶子证书在前），已通过校验；未出示时为 `None`
+    pub peer_certificates: Option<Arc<Vec<CertificateDer<'static>>>>,
+}
+
+impl TlsConnectInfo {
+    pub fn peer_certificate(&self) -> Option<&CertificateDer<'static>> {
+        self.peer_certificates.as_ref()?.first()
+    }
+}
+
+impl Connected<IncomingStream<'_, TlsListener>> for TlsConnectInfo {
+    fn connect_info(stream: IncomingStream<'_, TlsListener>) -> Self {
+        let (_, session) = stream.io().get_ref();
+        Self {
+            remote_addr: *stream.remote_addr(),
+            peer_certificates: session
+                .peer_certificates()
+                .map(|certificates| Arc::new(certificates.to_vec())),
+        }
     }
 }
 
@@ -350,7 +678,7 @@ mod tests {
         assert!(!manager.is_http_enabled());
         assert!(!manager.is_grpc_enabled());
         assert!(manager.http_acceptor().is_none());
-        assert!(manager.grpc_tls_config().is_none());
+        assert!(manager.grpc_acceptor().is_none());
     }
 
     #[test]
@@ -367,7 +695,7 @@ mod tests {
         assert!(!manager.is_http_enabled());
         assert!(!manager.is_grpc_enabled());
         assert!(manager.http_acceptor().is_none());
-        assert!(manager.grpc_tls_config().is_none());
+        assert!(manager.grpc_acceptor().is_none());
     }
 
     // ===== TlsManager::initialize — disabled config returns Ok =====
@@ -379,7 +707,7 @@ mod tests {
         assert!(result.is_ok());
         // Even after initialize, disabled means acceptors remain None
         assert!(manager.http_acceptor().is_none());
-        assert!(manager.grpc_tls_config().is_none());
+        assert!(manager.grpc_acceptor().is_none());
         assert!(!manager.is_http_enabled());
         assert!(!manager.is_grpc_enabled());
     }
@@ -489,13 +817,13 @@ mod tests {
         assert!(manager.is_http_enabled(), "http should be enabled");
         assert!(!manager.is_grpc_enabled(), "grpc should not be enabled");
         assert!(manager.http_acceptor().is_some());
-        assert!(manager.grpc_tls_config().is_none());
+        assert!(manager.grpc_acceptor().is_none());
     }
 
     // ===== TlsManager::initialize — valid cert+key, grpc_enabled =====
 
     #[tokio::test]
-    async fn test_initialize_valid_grpc_enabled_creates_tls_config() {
+    async fn test_initialize_valid_grpc_enabled_creates_acceptor() {
         let (cert_file, key_file) = generate_test_cert_files();
         let config = TlsConfig {
             enabled: true,
@@ -511,7 +839,7 @@ mod tests {
         assert!(!manager.is_http_enabled());
         assert!(manager.is_grpc_enabled(), "grpc should be enabled");
         assert!(manager.http_acceptor().is_none());
-        assert!(manager.grpc_tls_config().is_some());
+        assert!(manager.grpc_acceptor().is_some());
     }
 
     // ===== TlsManager::initialize — both http + grpc enabled =====
@@ -533,7 +861,7 @@ mod tests {
         assert!(manager.is_http_enabled());
         assert!(manager.is_grpc_enabled());
         assert!(manager.http_acceptor().is_some());
-        assert!(manager.grpc_tls_config().is_some());
+        assert!(manager.grpc_acceptor().is_some());
     }
 
     // ===== TlsManager::initialize — TLS 1.2 min version =====
@@ -639,7 +967,7 @@ mod tests {
         let result = manager.initialize().await;
         assert!(result.is_ok(), "gRPC with CA should succeed: {:?}", result);
         assert!(manager.is_grpc_enabled());
-        assert!(manager.grpc_tls_config().is_some());
+        assert!(manager.grpc_acceptor().is_some());
     }
 
     // ===== TlsManager::initialize — gRPC with empty CA file =====
@@ -708,7 +1036,7 @@ mod tests {
         assert!(!manager.is_http_enabled());
         assert!(!manager.is_grpc_enabled());
         assert!(manager.http_acceptor().is_none());
-        assert!(manager.grpc_tls_config().is_none());
+        assert!(manager.grpc_acceptor().is_none());
     }
 
     // ===== TlsManager — accessor methods =====
@@ -720,9 +1048,9 @@ mod tests {
     }
 
     #[test]
-    fn test_grpc_tls_config_returns_none_before_initialize() {
+    fn test_grpc_acceptor_returns_none_before_initialize() {
         let manager = TlsManager::new(TlsConfig::default());
-        assert!(manager.grpc_tls_config().is_none());
+        assert!(manager.grpc_acceptor().is_none());
     }
 
     #[test]
@@ -731,4 +1059,402 @@ mod tests {
         // TlsManager derives Clone — required for use in axum State.
         let _cloned = manager.clone();
     }
+
+    // ===== 客户端证书（mTLS） =====
+
+    /// 测试 PKI：CA、由 CA 签发的 localhost 服务端证书与两张客户端证书
+    /// （`revoked-svc` 序列号为 0x2a，写入 CRL）
+    struct TestPki {
+        ca: rcgen::Certificate,
+        ca_key: rcgen::KeyPair,
+        server: (NamedTempFile, NamedTempFile),
+        ca_file: NamedTempFile,
+    }
+
+    impl TestPki {
+        fn new() -> Self {
+            let _ = rustls::crypto::ring::default_provider().install_default();
+            let ca_key = rcgen::KeyPair::generate().unwrap();
+            let mut ca_params = rcgen::CertificateParams::new(Vec::<String>::new()).unwrap();
+            ca_params
+                .distinguished_name
+                .push(rcgen::DnType::CommonName, "NebulaId Test CA");
+            ca_params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+            ca_params.key_usages = vec![
+                rcgen::KeyUsagePurpose::KeyCertSign,
+                rcgen::KeyUsagePurpose::CrlSign,
+            ];
+            let ca = ca_params.self_signed(&ca_key).unwrap();
+
+            let (server_cert, server_key) = Self::issue(
+                &ca,
+                &ca_key,
+                "localhost",
+                rcgen::ExtendedKeyUsagePurpose::ServerAuth,
+                1,
+            );
+            Self {
+                ca_file: pem_file(&ca.pem()),
+                server: (
+                    pem_file(&server_cert.pem()),
+                    pem_file(&server_key.serialize_pem()),
+                ),
+                ca,
+                ca_key,
+            }
+        }
+
+        fn issue(
+            ca: &rcgen::Certificate,
+            ca_key: &rcgen::KeyPair,
+            name: &str,
+            usage: rcgen::ExtendedKeyUsagePurpose,
+            serial: u8,
+        ) -> (rcgen::Certificate, rcgen::KeyPair) {
+            let key = rcgen::KeyPair::generate().unwrap();
+            let mut params = rcgen::CertificateParams::new(vec![name.to_string()]).unwrap();
+            params
+                .distinguished_name
+                .push(rcgen::DnType::CommonName, name);
+            params.extended_key_usages = vec![usage];
+            params.serial_number = Some(rcgen::SerialNumber::from_slice(&[serial]));
+            (params.signed_by(&key, ca, ca_key).unwrap(), key)
+        }
+
+        fn client(&self, name: &str, serial: u8) -> (rcgen::Certificate, rcgen::KeyPair) {
+            Self::issue(
+                &self.ca,
+                &self.ca_key,
+                name,
+                rcgen::ExtendedKeyUsagePurpose::ClientAuth,
+                serial,
+            )
+        }
+
+        fn crl_revoking(&self, serial: u8) -> NamedTempFile {
+            let crl = rcgen::CertificateRevocationListParams {
+                this_update: rcgen::date_time_ymd(2024, 1, 1),
+                next_update: rcgen::date_time_ymd(2099, 1, 1),
+                crl_number: rcgen::SerialNumber::from_slice(&[1]),
+                issuing_distribution_point: None,
+                revoked_certs: vec![rcgen::RevokedCertParams {
+                    serial_number: rcgen::SerialNumber::from_slice(&[serial]),
+                    revocation_time: rcgen::date_time_ymd(2024, 1, 1),
+                    reason_code: Some(rcgen::RevocationReason::KeyCompromise),
+                    invalidity_date: None,
+                }],
+                key_identifier_method: rcgen::KeyIdMethod::Sha256,
+            }
+            .signed_by(&self.ca, &self.ca_key)
+            .unwrap();
+            pem_file(&crl.pem().unwrap())
+        }
+
+        fn config(&self, client_auth: ClientAuthMode, crl_paths: Vec<String>) -> TlsConfig {
+            TlsConfig {
+                enabled: true,
+                cert_path: self.server.0.path().to_str().unwrap().to_string(),
+                key_path: self.server.1.path().to_str().unwrap().to_string(),
+                ca_path: Some(self.ca_file.path().to_str().unwrap().to_string()),
+                http_enabled: true,
+                grpc_enabled: false,
+                alpn_protocols: Vec::new(),
+                client_auth,
+                crl_paths,
+                ..Default::default()
+            }
+        }
+
+        /// 以可选的客户端证书发起 HTTPS 请求；握手或读取失败时返回 `None`
+        async fn get(
+            &self,
+            addr: SocketAddr,
+            client: Option<&(rcgen::Certificate, rcgen::KeyPair)>,
+        ) -> Option<String> {
+            use rustls::pki_types::{PrivatePkcs8KeyDer, ServerName};
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+            let mut roots = RootCertStore::empty();
+            roots.add(self.ca.der().clone()).unwrap();
+            let builder = rustls::ClientConfig::builder().with_root_certificates(roots);
+            let config = match client {
+                Some((cert, key)) => builder
+                    .with_client_auth_cert(
+                        vec![cert.der().clone()],
+                        PrivatePkcs8KeyDer::from(key.serialize_der()).into(),
+                    )
+                    .unwrap(),
+                None => builder.with_no_client_auth(),
+            };
+            let tcp = TcpStream::connect(addr).await.ok()?;
+            let mut stream = tokio_rustls::TlsConnector::from(Arc::new(config))
+                .connect(ServerName::try_from("localhost").unwrap(), tcp)
+                .await
+                .ok()?;
+            stream
+                .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                .await
+                .ok()?;
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.ok()?;
+            Some(response).filter(|r| r.starts_with("HTTP/1.1 200"))
+        }
+    }
+
+    fn pem_file(pem: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(pem.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    /// 启动 HTTPS 服务，响应体为客户端证书 CN（无证书时为 `anonymous`）
+    async fn serve_https(config: TlsConfig) -> SocketAddr {
+        use crate::core::auth::client_cert::certificate_names;
+        use sdforge::axum::extract::ConnectInfo;
+        use sdforge::axum::routing::get;
+
+        let mut manager = TlsManager::new(config);
+        manager.initialize().await.unwrap();
+        let listener = TlsListener::new(
+            TcpListener::bind("127.0.0.1:0").await.unwrap(),
+            manager.http_acceptor().unwrap().clone(),
+        )
+        .unwrap();
+        let addr = Listener::local_addr(&listener).unwrap();
+        let router = sdforge::axum::Router::new().route(
+            "/",
+            get(
+                |ConnectInfo(info): ConnectInfo<TlsConnectInfo>| async move {
+                    info.peer_certificate()
+                        .and_then(|cert| certificate_names(cert.as_ref()))
+                        .and_then(|names| names.into_iter().next())
+                        .unwrap_or_else(|| "anonymous".to_string())
+                },
+            ),
+        );
+        tokio::spawn(async move {
+            sdforge::axum::serve(
+                listener,
+                router.into_make_service_with_connect_info::<TlsConnectInfo>(),
+            )
+            .await
+            .unwrap();
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_client_auth_without_ca_path_is_invalid_config() {
+        let pki = TestPki::new();
+        let config = TlsConfig {
+            ca_path: None,
+            ..pki.config(ClientAuthMode::Optional, Vec::new())
+        };
+        let mut manager = TlsManager::new(config);
+        assert!(matches!(
+            manager.initialize().await,
+            Err(TlsError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_grpc_requires_client_certs_when_ca_path_is_set() {
+        let pki = TestPki::new();
+        let manager = TlsManager::new(pki.config(ClientAuthMode::None, Vec::new()));
+        assert_eq!(manager.http_client_auth(), ClientAuthMode::None);
+        assert_eq!(manager.grpc_client_auth(), ClientAuthMode::Required);
+
+        let manager = TlsManager::new(pki.config(ClientAuthMode::Optional, Vec::new()));
+        assert_eq!(manager.grpc_client_auth(), ClientAuthMode::Optional);
+    }
+
+    #[tokio::test]
+    async fn test_required_client_auth_rejects_missing_certificate() {
+        let pki = TestPki::new();
+        let addr = serve_https(pki.config(ClientAuthMode::Required, Vec::new())).await;
+
+        let client = pki.client("orders-svc", 2);
+        let response = pki.get(addr, Some(&client)).await.unwrap();
+        assert!(response.ends_with("orders-svc"), "{}", response);
+        assert!(pki.get(addr, None).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_optional_client_auth_allows_anonymous_clients() {
+        let pki = TestPki::new();
+        let addr = serve_https(pki.config(ClientAuthMode::Optional, Vec::new())).await;
+
+        let response = pki.get(addr, None).await.unwrap();
+        assert!(response.ends_with("anonymous"), "{}", response);
+        let client = pki.client("orders-svc", 2);
+        let response = pki.get(addr, Some(&client)).await.unwrap();
+        assert!(response.ends_with("orders-svc"), "{}", response);
+    }
+
+    #[tokio::test]
+    async fn test_revoked_client_certificate_is_rejected() {
+        let pki = TestPki::new();
+        let crl = pki.crl_revoking(0x2a);
+        let addr = serve_https(pki.config(
+            ClientAuthMode::Required,
+            vec![crl.path().to_str().unwrap().to_string()],
+        ))
+        .await;
+
+        assert!(pki
+            .get(addr, Some(&pki.client("revoked-svc", 0x2a)))
+            .await
+            .is_none());
+        assert!(pki
+            .get(addr, Some(&pki.client("orders-svc", 2)))
+            .await
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn test_missing_crl_file_is_reported() {
+        let pki = TestPki::new();
+        let mut manager = TlsManager::new(pki.config(
+            ClientAuthMode::Required,
+            vec!["/nonexistent/ca.crl".to_string()],
+        ));
+        assert!(matches!(
+            manager.initialize().await,
+            Err(TlsError::RevocationListLoadError(_))
+        ));
+    }
+
+    // ===== 证书热加载与有效期指标 =====
+
+    fn write_certificate(cert_file: &NamedTempFile, key_file: &NamedTempFile, not_after_year: i32) {
+        let mut params = rcgen::CertificateParams::new(vec!["localhost".to_string()]).unwrap();
+        params.not_after = rcgen::date_time_ymd(not_after_year, 1, 1);
+        let key = rcgen::KeyPair::generate().unwrap();
+        let cert = params.self_signed(&key).unwrap();
+        std::fs::write(cert_file.path(), cert.pem()).unwrap();
+        std::fs::write(key_file.path(), key.serialize_pem()).unwrap();
+    }
+
+    async fn initialized_manager(
+        cert_file: &NamedTempFile,
+        key_file: &NamedTempFile,
+        reload_interval_seconds: u64,
+    ) -> TlsManager {
+        let mut manager = TlsManager::new(TlsConfig {
+            enabled: true,
+            cert_path: cert_file.path().to_str().unwrap().to_string(),
+            key_path: key_file.path().to_str().unwrap().to_string(),
+            http_enabled: true,
+            grpc_enabled: true,
+            reload_interval_seconds,
+            ..Default::default()
+        });
+        manager.initialize().await.unwrap();
+        manager
+    }
+
+    fn current_certificate(manager: &TlsManager) -> CertificateDer<'static> {
+        manager.resolver.as_ref().unwrap().current.load().cert[0].clone()
+    }
+
+    #[tokio::test]
+    async fn test_reload_swaps_certificate_for_new_handshakes() {
+        let (cert_file, key_file) = generate_test_cert_files();
+        let manager = initialized_manager(&cert_file, &key_file, 0).await;
+        let original = current_certificate(&manager);
+
+        write_certificate(&cert_file, &key_file, 2098);
+        assert_eq!(manager.reload_certificate(), Ok(true));
+        let rotated = current_certificate(&manager);
+        assert_ne!(rotated, original);
+        assert_eq!(
+            manager
+                .certificate_metrics()
+                .not_after
+                .load(Ordering::Relaxed),
+            rcgen::date_time_ymd(2098, 1, 1).unix_timestamp()
+        );
+
+        // 内容未变化时不替换
+        assert_eq!(manager.reload_certificate(), Ok(false));
+        assert_eq!(
+            manager
+                .certificate_metrics()
+                .reloads_total
+                .load(Ordering::Relaxed),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reload_with_mismatched_key_keeps_current_certificate() {
+        let (cert_file, key_file) = generate_test_cert_files();
+        let manager = initialized_manager(&cert_file, &key_file, 0).await;
+        let original = current_certificate(&manager);
+
+        // 证书已轮换、私钥尚未写入
+        let other_key = NamedTempFile::new().unwrap();
+        write_certificate(&cert_file, &other_key, 2098);
+        assert!(matches!(
+            manager.reload_certificate(),
+            Err(TlsError::InvalidConfig(_))
+        ));
+        assert_eq!(current_certificate(&manager), original);
+        assert_eq!(
+            manager
+                .certificate_metrics()
+                .reload_failures_total
+                .load(Ordering::Relaxed),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_certificate_metrics_report_seconds_until_expiry() {
+        let (cert_file, key_file) = generate_test_cert_files();
+        write_certificate(&cert_file, &key_file, 2099);
+        let manager = initialized_manager(&cert_file, &key_file, 0).await;
+
+        let expected =
+            rcgen::date_time_ymd(2099, 1, 1).unix_timestamp() - chrono::Utc::now().timestamp();
+        let remaining = manager
+            .certificate_metrics()
+            .seconds_until_expiry()
+            .unwrap();
+        assert!(
+            (expected - remaining).abs() < 5,
+            "{} vs {}",
+            expected,
+            remaining
+        );
+        assert!(TlsManager::new(TlsConfig::default())
+            .certificate_metrics()
+            .seconds_until_expiry()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_watch_reloads_rotated_certificate_files() {
+        let (cert_file, key_file) = generate_test_cert_files();
+        let manager = Arc::new(initialized_manager(&cert_file, &key_file, 1).await);
+        let original = current_certificate(&manager);
+
+        let watcher = manager.clone();
+        let handle = tokio::spawn(async move { watcher.watch().await });
+        // 第一轮只记录修改时间
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        write_certificate(&cert_file, &key_file, 2098);
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+        handle.abort();
+
+        assert_ne!(current_certificate(&manager), original);
+        assert_eq!(
+            manager
+                .certificate_metrics()
+                .reloads_total
+                .load(Ordering::Relaxed),
+            1
+        );
+    }
 }
@@ -0,0 +1,400 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strict generation checks.
+//!
+//! `strict_generation` 开启（默认）的 workspace 只能为已注册的
+//! `group/biz_tag` 生成 ID，且 workspace 必须为 `active`；关闭时保持旧行为，
+//! 任意 biz_tag 字符串都可生成。
+//!
+//! 查询结果（包括"不存在"）按 TTL 缓存在进程内，生成热路径通常不访问数据库。
+//! 本实例内的 workspace / biz_tag 变更通过 `invalidate_*` 立即失效；其他实例或
+//! 直接改库的变更在 TTL 内可见。
+
+use crate::core::database::{
+    BizTagRepository, GroupRepository, Workspace, WorkspaceRepository, WorkspaceStatus,
+};
+use crate::core::{CoreError, Result};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// 缓存条目默认存活时间
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// 严格模式所需的注册信息查询
+#[async_trait]
+pub trait GenerationRegistry: Send + Sync {
+    async fn find_workspace(&self, name: &str) -> Result<Option<Workspace>>;
+    async fn is_biz_tag_registered(
+        &self,
+        workspace_id: Uuid,
+        group: &str,
+        biz_tag: &str,
+    ) -> Result<bool>;
+}
+
+/// 基于 workspace / group / biz_tag 仓储的 [`GenerationRegistry`]
+pub struct RepositoryRegistry {
+    workspaces: Arc<dyn WorkspaceRepository>,
+    groups: Arc<dyn GroupRepository>,
+    biz_tags: Arc<dyn BizTagRepository>,
+}
+
+impl RepositoryRegistry {
+    pub fn new(
+        workspaces: Arc<dyn WorkspaceRepository>,
+        groups: Arc<dyn GroupRepository>,
+        biz_tags: Arc<dyn BizTagRepository>,
+    ) -> Self {
+        Self {
+            workspaces,
+            groups,
+            biz_tags,
+        }
+    }
+}
+
+#[async_trait]
+impl GenerationRegistry for RepositoryRegistry {
+    async fn find_workspace(&self, name: &str) -> Result<Option<Workspace>> {
+        self.workspaces.get_workspace_by_name(name).await
+    }
+
+    async fn is_biz_tag_registered(
+        &self,
+        workspace_id: Uuid,
+        group: &str,
+        biz_tag: &str,
+    ) -> Result<bool> {
+        let Some(group) = self
+            .groups
+            .get_group_by_workspace_and_name(workspace_id, group)
+            .await?
+        else {
+            return Ok(false);
+        };
+        Ok(self
+            .biz_tags
+            .get_biz_tag_by_workspace_group_and_name(workspace_id, group.id, biz_tag)
+            .await?
+            .is_some())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WorkspaceState {
+    id: Uuid,
+    status: WorkspaceStatus,
+    strict_generation: bool,
+}
+
+struct Cached<T> {
+    value: T,
+    expire_at: Instant,
+}
+
+/// `(workspace_id, group, biz_tag)`
+type BizTagKey = (Uuid, String, String);
+
+/// 生成前的 workspace 状态与 biz_tag 注册检查
+pub struct GenerationGuard {
+    registry: Arc<dyn GenerationRegistry>,
+    ttl: Duration,
+    /// workspace 名称 → 状态；`None` 表示不存在
+    workspaces: RwLock<HashMap<String, Cached<Option<WorkspaceState>>>>,
+    biz_tags: RwLock<HashMap<BizTagKey, Cached<bool>>>,
+}
+
+impl GenerationGuard {
+    pub fn new(registry: Arc<dyn GenerationRegistry>) -> Self {
+        Self {
+            registry,
+            ttl: DEFAULT_CACHE_TTL,
+            workspaces: RwLock::new(HashMap::new()),
+            biz_tags: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// 检查 `workspace/group/biz_tag` 是否允许生成 ID。
+    ///
+    /// - workspace 不存在：`NotFound`
+    /// - 严格模式且 workspace 非 active：`WorkspaceDisabled`
+    /// - 严格模式且 biz_tag 未在该 group 下注册：`BizTagNotFound`
+    pub async fn check(&self, workspace: &str, group: &str, biz_tag: &str) -> Result<()> {
+        let state = self.workspace_state(workspace).await?.ok_or_else(|| {
+            CoreError::NotFound(
+                t!(
+                    "api.error.handlers.workspace_handlers.not_found",
+                    name = workspace
+                )
+                .to_string(),
+            )
+        })?;
+        if !state.strict_generation {
+            return Ok(());
+        }
+        if state.status != WorkspaceStatus::Active {
+            return Err(CoreError::WorkspaceDisabled(format!(
+                "{} ({})",
+                workspace, state.status
+            )));
+        }
+        if !self.biz_tag_registered(state.id, group, biz_tag).await? {
+            return Err(CoreError::BizTagNotFound(format!(
+                "{}/{}/{}",
+                workspace, group, biz_tag
+            )));
+        }
+        Ok(())
+    }
+
+    async fn workspace_state(&self, name: &str) -> Result<Option<WorkspaceState>> {
+        if let Some(cached) = self.workspaces.read().get(name) {
+            if cached.expire_at > Instant::now() {
+                return Ok(cached.value.clone());
+            }
+        }
+        let state = self
+            .registry
+            .find_workspace(name)
+            .await?
+            .map(|w| WorkspaceState {
+                id: w.id,
+                status: w.status,
+                strict_generation: w.strict_generation,
+            });
+        self.workspaces.write().insert(
+            name.to_string(),
+            Cached {
+                value: state.clone(),
+                expire_at: Instant::now() + self.ttl,
+            },
+        );
+        Ok(state)
+    }
+
+    async fn biz_tag_registered(
+        &self,
+        workspace_id: Uuid,
+        group: &str,
+        biz_tag: &str,
+    ) -> Result<bool> {
+        let key = (workspace_id, group.to_string(), biz_tag.to_string());
+        if let Some(cached) = self.biz_tags.read().get(&key) {
+            if cached.expire_at > Instant::now() {
+                return Ok(cached.value);
+            }
+        }
+        let registered = self
+            .registry
+            .is_biz_tag_registered(workspace_id, group, biz_tag)
+            .await?;
+        self.biz_tags.write().insert(
+            key,
+            Cached {
+                value: registered,
+                expire_at: Instant::now() + self.ttl,
+            },
+        );
+        Ok(registered)
+    }
+
+    /// workspace 创建、更新后调用（按名称失效，包括"不存在"的缓存）
+    pub fn invalidate_workspace(&self, name: &str) {
+        self.workspaces.write().remove(name);
+    }
+
+    /// biz_tag 创建、更新后调用：失效该 workspace 下所有 biz_tag 条目
+    pub fn invalidate_biz_tags(&self, workspace_id: Uuid) {
+        self.biz_tags
+            .write()
+            .retain(|(id, _, _), _| *id != workspace_id);
+    }
+
+    /// 无法确定所属 workspace 的变更（如按 ID 删除 biz_tag）后调用
+    pub fn invalidate_all_biz_tags(&self) {
+        self.biz_tags.write().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct FakeRegistry {
+        workspaces: RwLock<HashMap<String, Workspace>>,
+        biz_tags: RwLock<Vec<(Uuid, String, String)>>,
+        lookups: AtomicUsize,
+    }
+
+    impl FakeRegistry {
+        fn add_workspace(&self, name: &str, status: WorkspaceStatus, strict: bool) -> Uuid {
+            let id = Uuid::new_v4();
+            let now = chrono::Utc::now().naive_utc();
+            self.workspaces.write().insert(
+                name.to_string(),
+                Workspace {
+                    id,
+                    name: name.to_string(),
+                    description: None,
+                    status,
+                    max_groups: 10,
+                    max_biz_tags: 100,
+                    strict_generation: strict,
+                    created_at: now,
+                    updated_at: now,
+                },
+            );
+            id
+        }
+
+        fn set_status(&self, name: &str, status: WorkspaceStatus) {
+            self.workspaces.write().get_mut(name).unwrap().status = status;
+        }
+
+        fn register(&self, workspace_id: Uuid, group: &str, biz_tag: &str) {
+            self.biz_tags
+                .write()
+                .push((workspace_id, group.to_string(), biz_tag.to_string()));
+        }
+    }
+
+    #[async_trait]
+    impl GenerationRegistry for FakeRegistry {
+        async fn find_workspace(&self, name: &str) -> Result<Option<Workspace>> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            Ok(self.workspaces.read().get(name).cloned())
+        }
+
+        async fn is_biz_tag_registered(
+            &self,
+            workspace_id: Uuid,
+            group: &str,
+            biz_tag: &str,
+        ) -> Result<bool> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            Ok(self
+                .biz_tags
+                .read()
+                .iter()
+                .any(|(id, g, t)| *id == workspace_id && g == group && t == biz_tag))
+        }
+    }
+
+    fn guard(registry: &Arc<FakeRegistry>) -> GenerationGuard {
+        GenerationGuard::new(registry.clone())
+    }
+
+    #[tokio::test]
+    async fn test_strict_workspace_requires_registered_biz_tag() {
+        let registry = Arc::new(FakeRegistry::default());
+        let ws = registry.add_workspace("acme", WorkspaceStatus::Active, true);
+        registry.register(ws, "orders", "order-id");
+        let guard = guard(&registry);
+
+        assert!(guard.check("acme", "orders", "order-id").await.is_ok());
+        match guard.check("acme", "orders", "typo-id").await {
+            Err(CoreError::BizTagNotFound(value)) => assert_eq!(value, "acme/orders/typo-id"),
+            other => panic!("expected BizTagNotFound, got {other:?}"),
+        }
+        // 同名 biz_tag 在其他 group 下不算注册
+        assert!(matches!(
+            guard.check("acme", "billing", "order-id").await,
+            Err(CoreError::BizTagNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_strict_workspace_must_be_active() {
+        let registry = Arc::new(FakeRegistry::default());
+        let ws = registry.add_workspace("acme", WorkspaceStatus::Suspended, true);
+        registry.register(ws, "orders", "order-id");
+
+        match guard(&registry).check("acme", "orders", "order-id").await {
+            Err(CoreError::WorkspaceDisabled(value)) => assert_eq!(value, "acme (suspended)"),
+            other => panic!("expected WorkspaceDisabled, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_non_strict_workspace_keeps_legacy_behaviour() {
+        let registry = Arc::new(FakeRegistry::default());
+        registry.add_workspace("legacy", WorkspaceStatus::Inactive, false);
+
+        assert!(guard(&registry)
+            .check("legacy", "any", "anything")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_workspace_is_not_found() {
+        let registry = Arc::new(FakeRegistry::default());
+        assert!(matches!(
+            guard(&registry).check("ghost", "g", "t").await,
+            Err(CoreError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lookups_are_cached_until_invalidated() {
+        let registry = Arc::new(FakeRegistry::default());
+        let ws = registry.add_workspace("acme", WorkspaceStatus::Active, true);
+        let guard = guard(&registry);
+
+        assert!(guard.check("acme", "orders", "order-id").await.is_err());
+        assert!(guard.check("acme", "orders", "order-id").await.is_err());
+        assert_eq!(
+            registry.lookups.load(Ordering::SeqCst),
+            2,
+            "second check hits cache"
+        );
+
+        // 注册后未失效仍命中负缓存；失效后立即可用
+        registry.register(ws, "orders", "order-id");
+        assert!(guard.check("acme", "orders", "order-id").await.is_err());
+        guard.invalidate_biz_tags(ws);
+        assert!(guard.check("acme", "orders", "order-id").await.is_ok());
+
+        registry.set_status("acme", WorkspaceStatus::Inactive);
+        assert!(guard.check("acme", "orders", "order-id").await.is_ok());
+        guard.invalidate_workspace("acme");
+        assert!(matches!(
+            guard.check("acme", "orders", "order-id").await,
+            Err(CoreError::WorkspaceDisabled(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_cache_entries_expire_after_ttl() {
+        let registry = Arc::new(FakeRegistry::default());
+        let ws = registry.add_workspace("acme", WorkspaceStatus::Active, true);
+        let guard = GenerationGuard::new(registry.clone()).with_cache_ttl(Duration::ZERO);
+
+        assert!(guard.check("acme", "orders", "order-id").await.is_err());
+        registry.register(ws, "orders", "order-id");
+        assert!(guard.check("acme", "orders", "order-id").await.is_ok());
+    }
+}
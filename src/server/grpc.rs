@@ -12,38 +12,356 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::core::algorithm::DynAuditLogger;
+use crate::core::auth::{ApiKeyScopes, Scope, ScopeResource};
+use crate::core::database::{ApiKeyRole, BanTargetKind};
+use crate::core::monitoring::telemetry::set_remote_parent;
+use crate::core::CoreError;
 use crate::server::handlers::ApiHandlers;
-use crate::server::models::{BatchGenerateRequest, GenerateRequest, ParseRequest};
+use crate::server::middleware::api_key_auth::parse_credentials;
+use crate::server::middleware::{ApiKeyAuth, HeaderExtractor};
+use crate::server::models::{
+    ApiKeyResponse, ApiKeyWithSecretResponse, BatchGenerateRequest, CreateApiKeyRequest,
+    GenerateRequest, ParseRequest,
+};
 use async_trait::async_trait;
+use rustls::pki_types::CertificateDer;
+use sdforge::tonic::metadata::MetadataMap;
 use sdforge::tonic::{Request, Response, Status};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::StreamExt;
+use tracing::Instrument;
+use validator::Validate;
 
 // Use pre-generated proto modules
 use crate::server::proto::nebula::id::v1;
 
 use v1::nebula_id_service_server::NebulaIdService;
 use v1::{
+    ApiKey as GrpcApiKey, ApiKeyWithSecret as GrpcApiKeyWithSecret,
     BatchGenerateRequest as GrpcBatchGenerateRequest,
     BatchGenerateResponse as GrpcBatchGenerateResponse, BatchGenerateStreamRequest,
-    BatchGenerateStreamResponse, GenerateRequest as GrpcGenerateRequest,
-    GenerateResponse as GrpcGenerateResponse, HealthCheckRequest, HealthCheckResponse,
-    ParseRequest as GrpcParseRequest, ParseResponse as GrpcParseResponse,
+    BatchGenerateStreamResponse, CreateApiKeyRequest as GrpcCreateApiKeyRequest,
+    GenerateRequest as GrpcGenerateRequest, GenerateResponse as GrpcGenerateResponse,
+    HealthCheckRequest, HealthCheckResponse, ListApiKeysRequest as GrpcListApiKeysRequest,
+    ListApiKeysResponse as GrpcListApiKeysResponse, ParseRequest as GrpcParseRequest,
+    ParseResponse as GrpcParseResponse, RevokeApiKeyRequest as GrpcRevokeApiKeyRequest,
+    RevokeApiKeyResponse as GrpcRevokeApiKeyResponse,
+    RotateApiKeyRequest as GrpcRotateApiKeyRequest,
 };
 
+#[derive(Clone)]
 pub struct GrpcServer {
     handlers: Arc<ApiHandlers>,
+    auth: Option<Arc<ApiKeyAuth>>,
+    audit_logger: Option<DynAuditLogger>,
+}
+
+/// 通过认证的 gRPC 调用方；未配置或禁用认证时不受限
+struct GrpcCaller {
+    workspace_id: Option<uuid::Uuid>,
+    role: ApiKeyRole,
+    scopes: ApiKeyScopes,
 }
 
 impl GrpcServer {
     pub fn new(handlers: Arc<ApiHandlers>) -> Self {
-        Self { handlers }
+        Self {
+            handlers,
+            auth: None,
+            audit_logger: None,
+        }
+    }
+
+    /// 按 `authorization` metadata 认证调用方（API key 或 JWT，格式同 HTTP；未携带时使用 mTLS
+    /// 客户端证书），并校验作用域
+    /// 与 workspace 绑定；作用域拒绝写入 `audit_logger`
+    pub fn with_auth(mut self, auth: Arc<ApiKeyAuth>, audit_logger: DynAuditLogger) -> Self {
+        self.auth = Some(auth);
+        self.audit_logger = Some(audit_logger);
+        self
+    }
+
+    /// `peer_certs` 为 `Request::peer_certs()`；取出后再传入，避免跨 await 借用请求体
+    async fn authenticate(
+        &self,
+        metadata: &MetadataMap,
+        peer_certs: Option<Arc<Vec<CertificateDer<'static>>>>,
+    ) -> Result<GrpcCaller, Status> {
+        let Some(auth) = self.auth.as_ref().filter(|auth| auth.enabled) else {
+            return Ok(GrpcCaller {
+                workspace_id: None,
+                role: ApiKeyRole::User,
+                scopes: ApiKeyScopes::unrestricted(),
+            });
+        };
+
+        let value = metadata
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .ok_or("missing_auth_header");
+        // mTLS：未携带 authorization 时按已校验的客户端证书认证
+        if let Some(certificates) =
+            peer_certs.filter(|_| value.is_err() && auth.accepts_client_certs())
+        {
+            let certificate = certificates
+                .first()
+                .ok_or_else(|| Status::unauthenticated("Invalid or missing API key"))?;
+            let (principal, scopes) = auth
+                .authenticate_client_cert(certificate.as_ref())
+                .map_err(|reason| {
+                    tracing::warn!(event = "grpc_auth_failure", reason = reason);
+                    Status::unauthenticated("Invalid or missing API key")
+                })?;
+            return Ok(GrpcCaller {
+                workspace_id: principal.workspace_id,
+                role: principal.role,
+                scopes,
+            });
+        }
+        if let Some(token) = value
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .filter(|_| auth.accepts_bearer_tokens())
+        {
+            let (principal, scopes) =
+                auth.validate_bearer_token(token).await.map_err(|reason| {
+                    tracing::warn!(event = "grpc_auth_failure", reason = reason);
+                    Status::unauthenticated("Invalid or missing API key")
+                })?;
+            return Ok(GrpcCaller {
+                workspace_id: principal.workspace_id,
+                role: principal.role,
+                scopes,
+            });
+        }
+        let (key_id, key_secret) = value.and_then(parse_credentials).map_err(|reason| {
+            tracing::warn!(event = "grpc_auth_failure", reason = reason);
+            Status::unauthenticated("Invalid or missing API key")
+        })?;
+        // gRPC 元数据中没有可信的客户端 IP，只按 key_id 封禁
+        let bans = auth.ban_manager();
+        if let Some(ban) = bans.active_ban(BanTargetKind::KeyId, &key_id) {
+            tracing::warn!(event = "grpc_auth_failure", reason = "key_id_banned", ban_id = %ban.id);
+            return Err(Status::resource_exhausted(
+                "Too many authentication attempts. Please try again later.",
+            ));
+        }
+        let Some((workspace_id, role, scopes)) =
+            auth.validate_key_with_scopes(&key_id, &key_secret).await
+        else {
+            tracing::warn!(event = "grpc_auth_failure", reason = "invalid_credentials");
+            bans.record_failure(BanTargetKind::KeyId, &key_id).await;
+            return Err(Status::unauthenticated("Invalid or missing API key"));
+        };
+        Ok(GrpcCaller {
+            workspace_id,
+            role,
+            scopes,
+        })
+    }
+
+    /// ID 生成：拒绝 Admin key，校验 workspace 绑定与 `generate:<tag>/<tag>` 作用域
+    async fn authorize_generate(
+        &self,
+        caller: &GrpcCaller,
+        rpc: &str,
+        namespace: &str,
+        tag: &str,
+    ) -> Result<(), Status> {
+        if caller.role == ApiKeyRole::Admin {
+            return Err(Status::permission_denied(
+                "Admin API keys cannot generate IDs",
+            ));
+        }
+        if let Some(key_workspace_id) = caller.workspace_id {
+            let workspace = self
+                .handlers
+                .get_workspace(namespace)
+                .await
+                .map_err(|e| Status::internal(format!("{}", e)))?;
+            let bound = workspace
+                .and_then(|ws| uuid::Uuid::parse_str(&ws.id).ok())
+                .is_some_and(|id| id == key_workspace_id);
+            if !bound {
+                return Err(Status::permission_denied(
+                    "API key is not authorized for this workspace",
+                ));
+            }
+        }
+        check_scope(
+            self.audit_logger.as_ref(),
+            caller,
+            rpc,
+            &Scope::generate(tag, tag),
+        )
+        .await
+    }
+
+    /// API key 管理：仅 Admin 与 workspace 管理员可调用，并校验 `api_keys`
+    /// 作用域。返回 workspace 管理员所属的 workspace（Admin 为 `None`）
+    async fn authorize_key_management(
+        &self,
+        caller: &GrpcCaller,
+        rpc: &str,
+        required: &Scope,
+    ) -> Result<Option<uuid::Uuid>, Status> {
+        let workspace = match caller.role {
+            ApiKeyRole::Admin => None,
+            ApiKeyRole::WorkspaceAdmin => Some(caller.workspace_id.ok_or_else(|| {
+                Status::permission_denied("Workspace admin API key is not bound to a workspace")
+            })?),
+            _ => {
+                return Err(Status::permission_denied(
+                    "Admin or workspace admin API key required",
+                ))
+            }
+        };
+        check_scope(self.audit_logger.as_ref(), caller, rpc, required).await?;
+        Ok(workspace)
+    }
+
+    async fn audit_key_change(
+        &self,
+        event: KeyChange,
+        workspace_id: Option<uuid::Uuid>,
+        key_id: String,
+        role: String,
+    ) {
+        let Some(audit_logger) = self.audit_logger.as_ref() else {
+            return;
+        };
+        let workspace_id = workspace_id.map(|id| id.to_string());
+        match event {
+            KeyChange::Created => {
+                audit_logger
+                    .log_api_key_created(workspace_id, key_id, role, None, None)
+                    .await
+            }
+            KeyChange::Rotated => {
+                audit_logger
+                    .log_api_key_regenerated(
+                        workspace_id.unwrap_or_default(),
+                        key_id,
+                        role,
+                        None,
+                        None,
+                    )
+                    .await
+            }
+            KeyChange::Revoked => {
+                audit_logger
+                    .log_api_key_deleted(workspace_id, key_id, role, None, None)
+                    .await
+            }
+        }
+    }
+}
+
+/// gRPC 直接调用 handler，没有 HTTP 审计中间件，由 RPC 自行记录 key 变更
+enum KeyChange {
+    Created,
+    Rotated,
+    Revoked,
+}
+
+/// 将 handler 错误映射为 gRPC 状态码（与 HTTP `core_error_status_code` 对齐）
+fn core_error_status(error: CoreError) -> Status {
+    match error {
+        CoreError::InvalidInput(_) | CoreError::InvalidIdFormat(_) => {
+            Status::invalid_argument(error.to_string())
+        }
+        CoreError::NotFound(_) => Status::not_found(error.to_string()),
+        CoreError::AuthenticationError(_) | CoreError::PermissionDenied(_) => {
+            Status::permission_denied(error.to_string())
+        }
+        _ => Status::internal(error.to_string()),
+    }
+}
+
+fn parse_uuid(value: &str, field: &str) -> Result<uuid::Uuid, Status> {
+    uuid::Uuid::parse_str(value)
+        .map_err(|_| Status::invalid_argument(format!("{} is not a valid UUID", field)))
+}
+
+/// proto3 的空字符串表示字段缺省
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+fn api_key_to_grpc(key: ApiKeyResponse) -> GrpcApiKey {
+    GrpcApiKey {
+        id: key.id,
+        key_id: key.key_id,
+        key_prefix: key.key_prefix,
+        name: key.name,
+        description: key.description.unwrap_or_default(),
+        role: key.role,
+        rate_limit: key.rate_limit,
+        enabled: key.enabled,
+        expires_at: key.expires_at.unwrap_or_default(),
+        created_at: key.created_at,
+        scopes: key.scopes.unwrap_or_default(),
     }
 }
 
+fn key_with_secret_to_grpc(key: ApiKeyWithSecretResponse) -> GrpcApiKeyWithSecret {
+    GrpcApiKeyWithSecret {
+        key: Some(api_key_to_grpc(key.key)),
+        key_secret: key.key_secret,
+    }
+}
+
+/// 作用域不足时返回 `PermissionDenied` 并记录审计事件
+async fn check_scope(
+    audit_logger: Option<&DynAuditLogger>,
+    caller: &GrpcCaller,
+    rpc: &str,
+    required: &Scope,
+) -> Result<(), Status> {
+    if caller.scopes.allows(required) {
+        return Ok(());
+    }
+    tracing::warn!(
+        event = "scope_denied",
+        rpc = rpc,
+        required_scope = %required,
+        "API key lacks required scope"
+    );
+    if let Some(audit_logger) = audit_logger {
+        audit_logger
+            .log_scope_denied(
+                caller.workspace_id.map(|id| id.to_string()),
+                format!("grpc {}", rpc),
+                required.to_string(),
+                None,
+            )
+            .await;
+    }
+    Err(Status::permission_denied(format!(
+        "API key lacks the required scope '{}'",
+        required
+    )))
+}
+
+/// 为一次 gRPC 调用创建 server span，并接入 metadata 中的 W3C `traceparent`。
+fn grpc_server_span(method: &'static str, metadata: &MetadataMap) -> tracing::Span {
+    let span = tracing::info_span!(
+        "grpc.request",
+        otel.kind = "server",
+        otel.name = %format!("nebula.id.v1.NebulaIdService/{}", method),
+        rpc.system = "grpc",
+        rpc.service = "nebula.id.v1.NebulaIdService",
+        rpc.method = method,
+    );
+    let headers = metadata.clone().into_headers();
+    set_remote_parent(&span, &HeaderExtractor(&headers));
+    span
+}
+
 #[async_trait]
 impl NebulaIdService for GrpcServer {
     type BatchGenerateStreamStream = ReceiverStream<Result<BatchGenerateStreamResponse, Status>>;
@@ -52,8 +370,14 @@ impl NebulaIdService for GrpcServer {
         &self,
         request: Request<GrpcGenerateRequest>,
     ) -> Result<Response<GrpcGenerateResponse>, Status> {
+        let span = grpc_server_span("Generate", request.metadata());
+        let caller = self
+            .authenticate(request.metadata(), request.peer_certs())
+            .await?;
         let req = request.into_inner();
         let tag = req.tag.clone();
+        self.authorize_generate(&caller, "Generate", &req.namespace, &tag)
+            .await?;
 
         let generate_req = GenerateRequest {
             workspace: req.namespace,
@@ -62,7 +386,7 @@ impl NebulaIdService for GrpcServer {
             algorithm: None,
         };
 
-        match self.handlers.generate(generate_req).await {
+        match self.handlers.generate(generate_req).instrument(span).await {
             Ok(resp) => {
                 let timestamp = resp.timestamp.parse().unwrap_or(0);
                 Ok(Response::new(GrpcGenerateResponse {
@@ -81,8 +405,14 @@ impl NebulaIdService for GrpcServer {
         &self,
         request: Request<GrpcBatchGenerateRequest>,
     ) -> Result<Response<GrpcBatchGenerateResponse>, Status> {
+        let span = grpc_server_span("BatchGenerate", request.metadata());
+        let caller = self
+            .authenticate(request.metadata(), request.peer_certs())
+            .await?;
         let req = request.into_inner();
         let tag = req.tag.clone();
+        self.authorize_generate(&caller, "BatchGenerate", &req.namespace, &tag)
+            .await?;
 
         tracing::info!(
             "{}",
@@ -127,7 +457,12 @@ impl NebulaIdService for GrpcServer {
             algorithm: None,
         };
 
-        match self.handlers.batch_generate(batch_req).await {
+        match self
+            .handlers
+            .batch_generate(batch_req)
+            .instrument(span)
+            .await
+        {
             Ok(resp) => {
                 let timestamp = resp.timestamp.parse().unwrap_or(0);
                 let ids = resp
@@ -152,9 +487,14 @@ impl NebulaIdService for GrpcServer {
         &self,
         request: Request<sdforge::tonic::Streaming<BatchGenerateStreamRequest>>,
     ) -> Result<Response<Self::BatchGenerateStreamStream>, Status> {
+        let span = grpc_server_span("BatchGenerateStream", request.metadata());
+        let caller = self
+            .authenticate(request.metadata(), request.peer_certs())
+            .await?;
         let mut stream = request.into_inner();
         let (tx, rx) = mpsc::channel(128);
 
+        let server = self.clone();
         let handlers = self.handlers.clone();
 
         tokio::spawn(async move {
@@ -162,6 +502,19 @@ impl NebulaIdService for GrpcServer {
                 match req {
                     Ok(stream_req) => {
                         let tag = stream_req.tag.clone();
+                        // 每条消息可指向不同的 namespace / tag，逐条授权
+                        if let Err(status) = server
+                            .authorize_generate(
+                                &caller,
+                                "BatchGenerateStream",
+                                &stream_req.namespace,
+                                &tag,
+                            )
+                            .await
+                        {
+                            let _ = tx.send(Err(status)).await;
+                            break;
+                        }
                         let batch_req = BatchGenerateRequest {
                             workspace: stream_req.namespace,
                             group: tag.clone(),
@@ -170,7 +523,11 @@ impl NebulaIdService for GrpcServer {
                             algorithm: None,
                         };
 
-                        match handlers.batch_generate(batch_req).await {
+                        match handlers
+                            .batch_generate(batch_req)
+                            .instrument(span.clone())
+                            .await
+                        {
                             Ok(resp) => {
                                 let timestamp = resp.timestamp.parse().unwrap_or(0);
                                 for id in resp.ids {
@@ -229,6 +586,11 @@ impl NebulaIdService for GrpcServer {
         &self,
         request: Request<GrpcParseRequest>,
     ) -> Result<Response<GrpcParseResponse>, Status> {
+        let span = grpc_server_span("Parse", request.metadata());
+        let caller = self
+            .authenticate(request.metadata(), request.peer_certs())
+            .await?;
+        check_scope(self.audit_logger.as_ref(), &caller, "Parse", &Scope::Parse).await?;
         let req = request.into_inner();
 
         let parse_req = ParseRequest {
@@ -239,7 +601,7 @@ impl NebulaIdService for GrpcServer {
             algorithm: String::new(),
         };
 
-        match self.handlers.parse(parse_req).await {
+        match self.handlers.parse(parse_req).instrument(span).await {
             Ok(resp) => {
                 let timestamp = resp.timestamp.parse().unwrap_or(0);
                 let metadata: HashMap<String, String> = vec![
@@ -269,6 +631,220 @@ impl NebulaIdService for GrpcServer {
         }
     }
 
+    async fn create_api_key(
+        &self,
+        request: Request<GrpcCreateApiKeyRequest>,
+    ) -> Result<Response<GrpcApiKeyWithSecret>, Status> {
+        let span = grpc_server_span("CreateApiKey", request.metadata());
+        let caller = self
+            .authenticate(request.metadata(), request.peer_certs())
+            .await?;
+        let own_workspace = self
+            .authorize_key_management(
+                &caller,
+                "CreateApiKey",
+                &Scope::write(ScopeResource::ApiKeys),
+            )
+            .await?;
+        let req = request.into_inner();
+        let create_req = CreateApiKeyRequest {
+            workspace_id: non_empty(req.workspace_id),
+            name: req.name,
+            description: non_empty(req.description),
+            role: non_empty(req.role),
+            rate_limit: (req.rate_limit != 0).then_some(req.rate_limit),
+            expires_at: non_empty(req.expires_at),
+            scopes: (!req.scopes.is_empty()).then_some(req.scopes),
+            request_signing: req.request_signing,
+        };
+        create_req
+            .validate()
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+        let requested = create_req
+            .workspace_id
+            .as_deref()
+            .map(|ws| parse_uuid(ws, "workspace_id"))
+            .transpose()?;
+        let (workspace_id, created) = match own_workspace {
+            // workspace 管理员：key 固定落在自己的 workspace
+            Some(own) => {
+                if requested.is_some_and(|ws| ws != own) {
+                    return Err(Status::permission_denied(
+                        "API key is not authorized for this workspace",
+                    ));
+                }
+                let created = self
+                    .handlers
+                    .create_workspace_api_key(own, &caller.scopes, create_req)
+                    .instrument(span)
+                    .await;
+                (Some(own), created)
+            }
+            None => {
+                let workspace_id = if create_req.role.as_deref() == Some("admin") {
+                    None
+                } else {
+                    Some(
+                        requested
+                            .ok_or_else(|| Status::invalid_argument("workspace_id is required"))?,
+                    )
+                };
+                let created = self
+                    .handlers
+                    .create_api_key(workspace_id, create_req)
+                    .instrument(span)
+                    .await;
+                (workspace_id, created)
+            }
+        };
+        let created = created.map_err(core_error_status)?;
+
+        self.audit_key_change(
+            KeyChange::Created,
+            workspace_id,
+            created.key.key_id.clone(),
+            created.key.role.clone(),
+        )
+        .await;
+        Ok(Response::new(key_with_secret_to_grpc(created)))
+    }
+
+    async fn list_api_keys(
+        &self,
+        request: Request<GrpcListApiKeysRequest>,
+    ) -> Result<Response<GrpcListApiKeysResponse>, Status> {
+        let span = grpc_server_span("ListApiKeys", request.metadata());
+        let caller = self
+            .authenticate(request.metadata(), request.peer_certs())
+            .await?;
+        let own_workspace = self
+            .authorize_key_management(&caller, "ListApiKeys", &Scope::read(ScopeResource::ApiKeys))
+            .await?;
+        let req = request.into_inner();
+
+        let requested = non_empty(req.workspace_id)
+            .map(|ws| parse_uuid(&ws, "workspace_id"))
+            .transpose()?;
+        let workspace_id = match (own_workspace, requested) {
+            (Some(own), Some(ws)) if ws != own => {
+                return Err(Status::permission_denied(
+                    "API key is not authorized for this workspace",
+                ))
+            }
+            (Some(own), _) => own,
+            // Admin 未指定 workspace 时沿用 HTTP 语义：nil UUID
+            (None, requested) => requested.unwrap_or(uuid::Uuid::nil()),
+        };
+
+        let page = req.page.max(1);
+        let page_size = if req.page_size == 0 {
+            20
+        } else {
+            req.page_size.min(100)
+        };
+        let listed = self
+            .handlers
+            .list_api_keys(
+                workspace_id,
+                Some(page_size),
+                Some((page - 1).saturating_mul(page_size)),
+            )
+            .instrument(span)
+            .await
+            .map_err(core_error_status)?;
+
+        Ok(Response::new(GrpcListApiKeysResponse {
+            api_keys: listed.api_keys.into_iter().map(api_key_to_grpc).collect(),
+            total: listed.total,
+        }))
+    }
+
+    async fn rotate_api_key(
+        &self,
+        request: Request<GrpcRotateApiKeyRequest>,
+    ) -> Result<Response<GrpcApiKeyWithSecret>, Status> {
+        let span = grpc_server_span("RotateApiKey", request.metadata());
+        let caller = self
+            .authenticate(request.metadata(), request.peer_certs())
+            .await?;
+        let own_workspace = self
+            .authorize_key_management(
+                &caller,
+                "RotateApiKey",
+                &Scope::write(ScopeResource::ApiKeys),
+            )
+            .await?;
+        let req = request.into_inner();
+
+        let rotated = match own_workspace {
+            Some(own) => {
+                self.handlers
+                    .rotate_workspace_api_key(own, &req.key_id, req.request_signing)
+                    .instrument(span)
+                    .await
+            }
+            None => {
+                self.handlers
+                    .rotate_api_key(&req.key_id, req.request_signing)
+                    .instrument(span)
+                    .await
+            }
+        }
+        .map_err(core_error_status)?;
+
+        self.audit_key_change(
+            KeyChange::Rotated,
+            own_workspace,
+            rotated.key.key_id.clone(),
+            rotated.key.role.clone(),
+        )
+        .await;
+        Ok(Response::new(key_with_secret_to_grpc(rotated)))
+    }
+
+    async fn revoke_api_key(
+        &self,
+        request: Request<GrpcRevokeApiKeyRequest>,
+    ) -> Result<Response<GrpcRevokeApiKeyResponse>, Status> {
+        let span = grpc_server_span("RevokeApiKey", request.metadata());
+        let caller = self
+            .authenticate(request.metadata(), request.peer_certs())
+            .await?;
+        let own_workspace = self
+            .authorize_key_management(
+                &caller,
+                "RevokeApiKey",
+                &Scope::write(ScopeResource::ApiKeys),
+            )
+            .await?;
+        let id = parse_uuid(&request.into_inner().id, "id")?;
+
+        let revoked = match own_workspace {
+            Some(own) => {
+                self.handlers
+                    .revoke_workspace_api_key(own, id)
+                    .instrument(span)
+                    .await
+            }
+            None => self.handlers.revoke_api_key(id).instrument(span).await,
+        }
+        .map_err(core_error_status)?;
+
+        // 吊销只按记录 id 定位，角色未知时以 `unknown` 记录
+        self.audit_key_change(
+            KeyChange::Revoked,
+            own_workspace,
+            id.to_string(),
+            "unknown".to_string(),
+        )
+        .await;
+        Ok(Response::new(GrpcRevokeApiKeyResponse {
+            success: revoked.success,
+            message: revoked.message,
+        }))
+    }
+
     async fn health_check(
         &self,
         _request: Request<HealthCheckRequest>,
@@ -340,6 +916,24 @@ mod tests {
         assert_eq!(inner.worker_id, 0);
     }
 
+    #[tokio::test]
+    async fn test_generate_with_traceparent_metadata_succeeds() {
+        let server = create_test_grpc_server();
+        let mut req = Request::new(GrpcGenerateRequest {
+            namespace: "test-ns".to_string(),
+            tag: "test-tag".to_string(),
+            metadata: Default::default(),
+        });
+        req.metadata_mut().insert(
+            "traceparent",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+                .parse()
+                .unwrap(),
+        );
+        let resp = server.generate(req).await;
+        assert!(resp.is_ok(), "generate should succeed: {:?}", resp);
+    }
+
     #[tokio::test]
     async fn test_generate_empty_namespace_returns_internal_error() {
         // MockIdGenerator returns InvalidInput when workspace is empty.
@@ -544,4 +1138,295 @@ mod tests {
             v1::health_check_response::ServingStatus::Serving as i32
         );
     }
+
+    // ===== authentication & scopes =====
+
+    mod scoped {
+        use super::*;
+        use crate::core::algorithm::AuditEventType;
+        use crate::core::database::{
+            ApiKeyInfo, ApiKeyRepository, ApiKeyWithSecret, CreateApiKeyRequest,
+        };
+        use crate::core::Result;
+        use crate::server::audit::testing::AuditCapture;
+        use mockall::mock;
+        use uuid::Uuid;
+
+        mock! {
+            pub ApiKeyRepository {}
+
+            #[async_trait::async_trait]
+            impl ApiKeyRepository for ApiKeyRepository {
+                async fn create_api_key(&self, request: &CreateApiKeyRequest) -> Result<ApiKeyWithSecret>;
+                async fn get_api_key_by_id(&self, key_id: &str) -> Result<Option<ApiKeyInfo>>;
+                async fn get_api_key(&self, id: Uuid) -> Result<Option<ApiKeyInfo>>;
+                async fn validate_api_key(&self, key_id: &str, key_secret: &str) -> Result<Option<(Option<Uuid>, ApiKeyRole)>>;
+                async fn validate_api_key_with_scopes(&self, key_id: &str, key_secret: &str) -> Result<Option<(Option<Uuid>, ApiKeyRole, ApiKeyScopes)>>;
+                async fn list_api_keys(&self, workspace_id: Uuid, limit: Option<u32>, offset: Option<u32>) -> Result<Vec<ApiKeyInfo>>;
+                async fn delete_api_key(&self, id: Uuid) -> Result<()>;
+                async fn revoke_api_key(&self, id: Uuid) -> Result<()>;
+                async fn update_last_used(&self, id: Uuid) -> Result<()>;
+                async fn get_admin_api_key(&self, workspace_id: Uuid) -> Result<Option<ApiKeyInfo>>;
+                async fn count_api_keys(&self, workspace_id: Uuid) -> Result<u64>;
+                async fn count_enabled_api_keys(&self, workspace_id: Uuid) -> Result<u64>;
+                async fn rotate_api_key(&self, key_id: &str, grace_period_seconds: u64) -> Result<ApiKeyWithSecret>;
+                async fn get_keys_older_than(&self, age_threshold_days: i64) -> Result<Vec<ApiKeyInfo>>;
+            }
+        }
+
+        /// 只接受 `svc:secret`，作用域为 `generate:orders/*` + `parse`
+        fn scoped_server(capture: AuditCapture) -> GrpcServer {
+            let mut repo = MockApiKeyRepository::new();
+            repo.expect_validate_api_key_with_scopes()
+                .returning(|key_id, key_secret| {
+                    if key_id == "svc" && key_secret == "secret" {
+                        let scopes = ApiKeyScopes::parse(&["generate:orders/*", "parse"]).unwrap();
+                        Ok(Some((None, ApiKeyRole::User, scopes)))
+                    } else {
+                        Ok(None)
+                    }
+                });
+            let auth = Arc::new(ApiKeyAuth::new(Arc::new(repo), true));
+            create_test_grpc_server().with_auth(auth, Arc::new(capture))
+        }
+
+        fn authorized<T>(message: T) -> Request<T> {
+            let mut request = Request::new(message);
+            request
+                .metadata_mut()
+                .insert("authorization", "ApiKey svc:secret".parse().unwrap());
+            request
+        }
+
+        fn generate_request(tag: &str) -> GrpcGenerateRequest {
+            GrpcGenerateRequest {
+                namespace: "test-ns".to_string(),
+                tag: tag.to_string(),
+                metadata: Default::default(),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_missing_credentials_returns_unauthenticated() {
+            let server = scoped_server(AuditCapture::new());
+            let err = server
+                .generate(Request::new(generate_request("orders")))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), sdforge::tonic::Code::Unauthenticated);
+        }
+
+        #[tokio::test]
+        async fn test_generate_within_scope_succeeds() {
+            let server = scoped_server(AuditCapture::new());
+            let resp = server
+                .generate(authorized(generate_request("orders")))
+                .await;
+            assert!(resp.is_ok(), "generate should succeed: {:?}", resp);
+        }
+
+        #[tokio::test]
+        async fn test_generate_outside_scope_is_denied_and_audited() {
+            let capture = AuditCapture::new();
+            let server = scoped_server(capture.clone());
+            let err = server
+                .generate(authorized(generate_request("payments")))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), sdforge::tonic::Code::PermissionDenied);
+
+            let event = capture.expect_one(&AuditEventType::AuthorizationDenied, "scope_denied");
+            assert_eq!(event.resource, "grpc Generate");
+            assert_eq!(
+                event.details,
+                Some(serde_json::json!({"required_scope": "generate:payments/payments"}))
+            );
+        }
+
+        /// `wsadmin:secret` 为绑定 `ws` 的 workspace 管理员，`scopedadmin:secret`
+        /// 为只持有 `api_keys:write` + `generate:orders/*` 的 workspace 管理员，
+        /// `svc:secret` 为同一 workspace 的普通 user key；handler 与认证共用同一个 mock 仓库
+        fn key_management_server(
+            ws: Uuid,
+            configure: impl FnOnce(&mut MockApiKeyRepository),
+        ) -> GrpcServer {
+            let mut repo = MockApiKeyRepository::new();
+            repo.expect_validate_api_key_with_scopes()
+                .returning(move |key_id, key_secret| match (key_id, key_secret) {
+                    ("wsadmin", "secret") => Ok(Some((
+                        Some(ws),
+                        ApiKeyRole::WorkspaceAdmin,
+                        ApiKeyScopes::unrestricted(),
+                    ))),
+                    ("scopedadmin", "secret") => Ok(Some((
+                        Some(ws),
+                        ApiKeyRole::WorkspaceAdmin,
+                        ApiKeyScopes::parse(&["api_keys:write", "generate:orders/*"]).unwrap(),
+                    ))),
+                    ("svc", "secret") => Ok(Some((
+                        Some(ws),
+                        ApiKeyRole::User,
+                        ApiKeyScopes::unrestricted(),
+                    ))),
+                    _ => Ok(None),
+                });
+            configure(&mut repo);
+            let repo: Arc<dyn ApiKeyRepository> = Arc::new(repo);
+
+            let config = Config::default();
+            let hot_config = Arc::new(HotReloadConfig::new(
+                config.clone(),
+                "config/config.toml".to_string(),
+            ));
+            let algorithm_router = Arc::new(AlgorithmRouter::new(config, None));
+            let config_service: Arc<dyn ConfigManagementService> =
+                Arc::new(ConfigManager::new(hot_config, algorithm_router));
+            let id_generator: Arc<dyn crate::core::algorithm::IdGenerator> =
+                Arc::new(MockIdGenerator::new());
+            let handlers = Arc::new(
+                ApiHandlers::with_api_key_repository(id_generator, config_service, repo.clone())
+                    .with_max_api_keys_per_workspace(2),
+            );
+            let auth = Arc::new(ApiKeyAuth::new(repo, true));
+            GrpcServer::new(handlers).with_auth(auth, Arc::new(AuditCapture::new()))
+        }
+
+        fn as_workspace_admin<T>(message: T) -> Request<T> {
+            let mut request = Request::new(message);
+            request
+                .metadata_mut()
+                .insert("authorization", "ApiKey wsadmin:secret".parse().unwrap());
+            request
+        }
+
+        fn create_key_request(role: &str) -> GrpcCreateApiKeyRequest {
+            GrpcCreateApiKeyRequest {
+                name: "svc-orders".to_string(),
+                role: role.to_string(),
+                ..Default::default()
+            }
+        }
+
+        #[tokio::test]
+        async fn test_user_key_cannot_manage_api_keys() {
+            let server = key_management_server(Uuid::new_v4(), |_| {});
+            let err = server
+                .list_api_keys(authorized(GrpcListApiKeysRequest::default()))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), sdforge::tonic::Code::PermissionDenied);
+        }
+
+        #[tokio::test]
+        async fn test_workspace_admin_lists_own_workspace_keys() {
+            let ws = Uuid::new_v4();
+            let server = key_management_server(ws, |repo| {
+                repo.expect_list_api_keys()
+                    .withf(move |workspace_id, _, _| *workspace_id == ws)
+                    .returning(|_, _, _| Ok(Vec::new()));
+                repo.expect_count_api_keys().returning(|_| Ok(0));
+            });
+            let resp = server
+                .list_api_keys(as_workspace_admin(GrpcListApiKeysRequest::default()))
+                .await
+                .unwrap()
+                .into_inner();
+            assert!(resp.api_keys.is_empty());
+            assert_eq!(resp.total, 0);
+        }
+
+        #[tokio::test]
+        async fn test_workspace_admin_cannot_list_other_workspace() {
+            let server = key_management_server(Uuid::new_v4(), |_| {});
+            let err = server
+                .list_api_keys(as_workspace_admin(GrpcListApiKeysRequest {
+                    workspace_id: Uuid::new_v4().to_string(),
+                    ..Default::default()
+                }))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), sdforge::tonic::Code::PermissionDenied);
+        }
+
+        #[tokio::test]
+        async fn test_workspace_admin_cannot_create_admin_key() {
+            let server = key_management_server(Uuid::new_v4(), |_| {});
+            for role in ["admin", "workspace_admin"] {
+                let err = server
+                    .create_api_key(as_workspace_admin(create_key_request(role)))
+                    .await
+                    .unwrap_err();
+                assert_eq!(
+                    err.code(),
+                    sdforge::tonic::Code::PermissionDenied,
+                    "role {role}"
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn test_workspace_admin_cannot_grant_unheld_scopes() {
+            let server = key_management_server(Uuid::new_v4(), |_| {});
+            // 空列表（不受限）、`*` 以及调用方未持有的作用域都被拒绝
+            for scopes in [
+                vec![],
+                vec!["*".to_string()],
+                vec!["generate:payments/*".to_string()],
+                vec!["generate:orders/*".to_string(), "config:write".to_string()],
+            ] {
+                let mut request = Request::new(GrpcCreateApiKeyRequest {
+                    scopes: scopes.clone(),
+                    ..create_key_request("user")
+                });
+                request.metadata_mut().insert(
+                    "authorization",
+                    "ApiKey scopedadmin:secret".parse().unwrap(),
+                );
+                let err = server.create_api_key(request).await.unwrap_err();
+                assert_eq!(
+                    err.code(),
+                    sdforge::tonic::Code::PermissionDenied,
+                    "scopes {scopes:?}"
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn test_workspace_admin_create_respects_key_limit() {
+            let server = key_management_server(Uuid::new_v4(), |repo| {
+                repo.expect_count_enabled_api_keys().returning(|_| Ok(2));
+            });
+            let err = server
+                .create_api_key(as_workspace_admin(create_key_request("user")))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), sdforge::tonic::Code::InvalidArgument);
+        }
+
+        #[tokio::test]
+        async fn test_workspace_admin_cannot_rotate_other_workspace_key() {
+            let server = key_management_server(Uuid::new_v4(), |repo| {
+                repo.expect_get_api_key_by_id().returning(|_| Ok(None));
+            });
+            let err = server
+                .rotate_api_key(as_workspace_admin(GrpcRotateApiKeyRequest {
+                    key_id: "nino_foreign".to_string(),
+                    request_signing: None,
+                }))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code(), sdforge::tonic::Code::NotFound);
+        }
+
+        #[tokio::test]
+        async fn test_parse_scope_allows_parse() {
+            let server = scoped_server(AuditCapture::new());
+            let resp = server
+                .parse(authorized(GrpcParseRequest {
+                    id: "12345".to_string(),
+                }))
+                .await;
+            assert!(resp.is_ok(), "parse should succeed: {:?}", resp);
+        }
+    }
 }
@@ -15,11 +15,13 @@
 //! API Key management handlers + `KeyRotationHandle` (rule 25 split).
 
 use super::helpers::map_db_error;
+use crate::core::auth::{ApiKeyScopes, Scope};
 use crate::core::database::{ApiKeyRole, CreateApiKeyRequest as CoreCreateApiKeyRequest};
 use crate::core::{CoreError, Result};
 use crate::server::models::{
-    naive_to_rfc3339, ApiKeyListResponse, ApiKeyResponse, ApiKeyWithSecretResponse,
-    CreateApiKeyRequest, RevokeApiKeyResponse,
+    naive_to_rfc3339, scopes_to_response, ApiKeyAttentionInfo, ApiKeyAttentionResponse,
+    ApiKeyListResponse, ApiKeyResponse, ApiKeyWithSecretResponse, CreateApiKeyRequest,
+    RevokeApiKeyResponse,
 };
 
 /// Handle for managing the key rotation background task.
@@ -35,12 +37,42 @@ impl KeyRotationHandle {
     }
 }
 
+/// 解析管理 API 传入的作用域，错误信息指出第一个无效项
+fn parse_scopes(scopes: &[String]) -> Result<ApiKeyScopes> {
+    let parsed = scopes
+        .iter()
+        .map(|scope| {
+            scope.trim().parse::<Scope>().map_err(|_| {
+                CoreError::InvalidInput(
+                    t!(
+                        "api.error.handlers.api_key_handlers.invalid_scope",
+                        scope = scope
+                    )
+                    .to_string(),
+                )
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    ApiKeyScopes::new(parsed)
+}
+
 impl super::ApiHandlers {
     /// Create a new API Key (admin only).
     pub async fn create_api_key(
         &self,
         workspace_id: Option<uuid::Uuid>,
         req: CreateApiKeyRequest,
+    ) -> Result<ApiKeyWithSecretResponse> {
+        self.insert_api_key(workspace_id, req, None).await
+    }
+
+    /// 创建 key；`key_limit` 为 `Some` 时 workspace 内启用的 key 数量与插入在
+    /// 同一事务内检查，并发创建不会越过配额
+    async fn insert_api_key(
+        &self,
+        workspace_id: Option<uuid::Uuid>,
+        req: CreateApiKeyRequest,
+        key_limit: Option<u64>,
     ) -> Result<ApiKeyWithSecretResponse> {
         let repo = self.api_key_repo.as_ref().ok_or_else(|| {
             CoreError::NotFound(
@@ -51,6 +83,7 @@ impl super::ApiHandlers {
         let role = match req.role.as_deref() {
             Some("admin") => ApiKeyRole::Admin,
             Some("user") | None => ApiKeyRole::User,
+            Some("workspace_admin") => ApiKeyRole::WorkspaceAdmin,
             Some(r) => {
                 return Err(CoreError::AuthenticationError(
                     t!("api.error.handlers.api_key_handlers.invalid_role", role = r).to_string(),
@@ -87,7 +120,18 @@ impl super::ApiHandlers {
             }
         }
 
-        if role == ApiKeyRole::User {
+        // workspace 管理员 key 必须绑定 workspace（与 `check_admin_key` 约束一致）
+        if role == ApiKeyRole::WorkspaceAdmin && workspace_id.is_none() {
+            return Err(CoreError::InvalidInput(
+                t!("api.error.workspace_id_required").to_string(),
+            ));
+        }
+
+        let scopes = req.scopes.as_deref().map(parse_scopes).transpose()?;
+
+        // 每个 workspace 只有一个全权限 user key；带作用域的 key（按微服务
+        // 最小授权）可以有多个
+        if role == ApiKeyRole::User && scopes.is_none() {
             let ws_id = workspace_id.ok_or_else(|| {
                 CoreError::InvalidInput(t!("api.error.workspace_id_required").to_string())
             })?;
@@ -97,9 +141,9 @@ impl super::ApiHandlers {
                 .await
                 .map_err(map_db_error)?;
 
-            let has_user_key = existing_keys
-                .iter()
-                .any(|k| k.role == crate::core::database::ApiKeyRole::User);
+            let has_user_key = existing_keys.iter().any(|k| {
+                k.role == crate::core::database::ApiKeyRole::User && k.scopes.is_unrestricted()
+            });
 
             if has_user_key {
                 return Err(CoreError::AuthenticationError(
@@ -133,9 +177,27 @@ impl super::ApiHandlers {
             expires_at,
             key_secret: None,
             key_id: None,
+            scopes,
+            request_signing: req.request_signing,
         };
 
-        let key_with_secret = repo.create_api_key(&core_req).await.map_err(map_db_error)?;
+        let key_with_secret = match key_limit {
+            Some(limit) => repo
+                .create_api_key_within_limit(&core_req, limit)
+                .await
+                .map_err(map_db_error)?
+                .ok_or_else(|| {
+                    CoreError::InvalidInput(
+                        t!(
+                            "api.error.handlers.api_key_handlers.key_limit_reached",
+                            workspace_id = workspace_id.unwrap_or_default(),
+                            limit = limit
+                        )
+                        .to_string(),
+                    )
+                })?,
+            None => repo.create_api_key(&core_req).await.map_err(map_db_error)?,
+        };
 
         Ok(ApiKeyWithSecretResponse {
             key: ApiKeyResponse {
@@ -149,6 +211,7 @@ impl super::ApiHandlers {
                 enabled: key_with_secret.key.enabled,
                 expires_at: key_with_secret.key.expires_at.map(naive_to_rfc3339),
                 created_at: naive_to_rfc3339(key_with_secret.key.created_at),
+                scopes: scopes_to_response(&key_with_secret.key.scopes),
             },
             key_secret: key_with_secret.key_secret,
         })
@@ -185,6 +248,7 @@ impl super::ApiHandlers {
                 enabled: k.enabled,
                 expires_at: k.expires_at.map(naive_to_rfc3339),
                 created_at: naive_to_rfc3339(k.created_at),
+                scopes: scopes_to_response(&k.scopes),
             })
             .collect();
 
@@ -241,7 +305,12 @@ impl super::ApiHandlers {
     }
 
     /// Rotate an API Key (generate new secret, keep old key active during grace period).
-    pub async fn rotate_api_key(&self, key_id: &str) -> Result<ApiKeyWithSecretResponse> {
+    /// `request_signing` turns request signing on or off; `None` keeps the key's choice.
+    pub async fn rotate_api_key(
+        &self,
+        key_id: &str,
+        request_signing: Option<bool>,
+    ) -> Result<ApiKeyWithSecretResponse> {
         use crate::server::models::ApiKeyResponse;
 
         if key_id.is_empty() {
@@ -264,7 +333,7 @@ impl super::ApiHandlers {
         let grace_period_seconds = self.key_rotation_grace_period_seconds;
 
         let key_with_secret = repo
-            .rotate_api_key(key_id, grace_period_seconds)
+            .rotate_api_key_with_signing(key_id, grace_period_seconds, request_signing)
             .await
             .map_err(map_db_error)?;
 
@@ -282,8 +351,132 @@ impl super::ApiHandlers {
                 enabled: key_with_secret.key.enabled,
                 expires_at: key_with_secret.key.expires_at.map(naive_to_rfc3339),
                 created_at: naive_to_rfc3339(key_with_secret.key.created_at),
+                scopes: scopes_to_response(&key_with_secret.key.scopes),
             },
             key_secret: key_with_secret.key_secret,
         })
     }
+
+    /// Create a key inside the caller's own workspace (workspace admin
+    /// self-service). Admin and workspace admin keys are never issued here,
+    /// the new key cannot hold a scope the caller lacks (`caller_scopes`),
+    /// and the number of enabled keys in the workspace is capped by
+    /// `max_api_keys_per_workspace`.
+    pub async fn create_workspace_api_key(
+        &self,
+        workspace_id: uuid::Uuid,
+        caller_scopes: &ApiKeyScopes,
+        req: CreateApiKeyRequest,
+    ) -> Result<ApiKeyWithSecretResponse> {
+        if matches!(req.role.as_deref(), Some("admin") | Some("workspace_admin")) {
+            return Err(CoreError::PermissionDenied(
+                t!("api.error.handlers.api_key_handlers.admin_role_not_allowed").to_string(),
+            ));
+        }
+
+        // 未指定作用域即不受限：只有不受限的调用方才能签发
+        let requested = req
+            .scopes
+            .as_deref()
+            .map(parse_scopes)
+            .transpose()?
+            .unwrap_or_else(ApiKeyScopes::unrestricted);
+        if let Some(scope) = requested
+            .scopes()
+            .iter()
+            .find(|scope| !caller_scopes.allows(scope))
+        {
+            tracing::warn!(
+                event = "api_key_scope_escalation_blocked",
+                workspace_id = %workspace_id,
+                scope = %scope,
+                "workspace admin requested a scope it does not hold"
+            );
+            return Err(CoreError::PermissionDenied(
+                t!(
+                    "api.error.handlers.api_key_handlers.scope_not_held",
+                    scope = scope
+                )
+                .to_string(),
+            ));
+        }
+
+        // 只统计启用的 key：吊销或被生命周期检查禁用的 key 不再占用名额
+        let key_limit =
+            (self.max_api_keys_per_workspace > 0).then_some(self.max_api_keys_per_workspace);
+        self.insert_api_key(Some(workspace_id), req, key_limit)
+            .await
+    }
+
+    /// Revoke a key that belongs to `workspace_id` (workspace admin
+    /// self-service). Keys of other workspaces report not found so their
+    /// existence is not disclosed.
+    pub async fn revoke_workspace_api_key(
+        &self,
+        workspace_id: uuid::Uuid,
+        id: uuid::Uuid,
+    ) -> Result<RevokeApiKeyResponse> {
+        let repo = self.api_key_repo.as_ref().ok_or_else(|| {
+            CoreError::NotFound(
+                t!("api.error.handlers.workspace_handlers.api_key_repo_not_configured").to_string(),
+            )
+        })?;
+
+        let key = repo.get_api_key(id).await.map_err(map_db_error)?;
+        if !key.is_some_and(|k| k.workspace_id == Some(workspace_id)) {
+            return Err(CoreError::NotFound(
+                t!("api.error.handlers.api_key_handlers.key_not_found", id = id).to_string(),
+            ));
+        }
+
+        self.revoke_api_key(id).await
+    }
+
+    /// Rotate a key that belongs to `workspace_id` (workspace admin
+    /// self-service). Keys of other workspaces report not found.
+    pub async fn rotate_workspace_api_key(
+        &self,
+        workspace_id: uuid::Uuid,
+        key_id: &str,
+        request_signing: Option<bool>,
+    ) -> Result<ApiKeyWithSecretResponse> {
+        let repo = self.api_key_repo.as_ref().ok_or_else(|| {
+            CoreError::NotFound(
+                t!("api.error.handlers.workspace_handlers.api_key_repo_not_configured").to_string(),
+            )
+        })?;
+
+        let key = repo.get_api_key_by_id(key_id).await.map_err(map_db_error)?;
+        if !key.is_some_and(|k| k.workspace_id == Some(workspace_id)) {
+            return Err(CoreError::NotFound(
+                t!(
+                    "api.error.handlers.api_key_handlers.key_not_found",
+                    id = key_id
+                )
+                .to_string(),
+            ));
+        }
+
+        self.rotate_api_key(key_id, request_signing).await
+    }
+
+    /// Keys that are expired, expiring soon, unused or past the maximum age
+    /// under the lifecycle policy (admin only). Read-only; the background
+    /// lifecycle check performs the disabling.
+    pub async fn api_key_attention(&self) -> Result<ApiKeyAttentionResponse> {
+        let lifecycle = self.api_key_lifecycle.as_ref().ok_or_else(|| {
+            CoreError::ConfigurationError("API key lifecycle is not configured".to_string())
+        })?;
+
+        let keys = lifecycle.attention().await?;
+        let config = lifecycle.config();
+
+        Ok(ApiKeyAttentionResponse {
+            max_key_age_days: config.max_key_age_days,
+            expiry_warning_days: config.expiry_warning_days,
+            disable_unused_days: config.disable_unused_days,
+            keys: keys.into_iter().map(ApiKeyAttentionInfo::from).collect(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        })
+    }
 }
@@ -0,0 +1,304 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Audit trail query handler (rule 25 split).
+//!
+//! 查询数据库中的 `audit_events`，按事件 ID 降序游标分页：`next_cursor` 为本页
+//! 最后一条事件的 ID，下一页只返回更早的事件，翻页期间新写入的事件不会打乱分页。
+
+use crate::core::database::AuditEventQuery;
+use crate::core::{CoreError, Result};
+use crate::server::audit::{AuditEventType, AuditResult};
+use crate::server::models::{AuditEventInfo, AuditQueryParams, AuditQueryResponse};
+use chrono::NaiveDateTime;
+
+fn parse_time(field: &str, value: &str) -> Result<NaiveDateTime> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.naive_utc())
+        .map_err(|_| CoreError::InvalidInput(format!("{} must be an RFC 3339 timestamp", field)))
+}
+
+/// 校验枚举过滤值（如 `BizTagCreated`），拼写错误时报错而不是静默返回空结果
+fn parse_variant<T: serde::de::DeserializeOwned>(field: &str, value: &str) -> Result<String> {
+    serde_json::from_value::<T>(serde_json::Value::String(value.to_string()))
+        .map(|_| value.to_string())
+        .map_err(|_| CoreError::InvalidInput(format!("unknown {}: {}", field, value)))
+}
+
+/// 将请求参数转换为仓储查询；`workspace_scope` 非空时覆盖请求中的 workspace 过滤
+fn build_query(
+    params: &AuditQueryParams,
+    workspace_scope: Option<String>,
+) -> Result<AuditEventQuery> {
+    let start = params
+        .start
+        .as_deref()
+        .map(|v| parse_time("start", v))
+        .transpose()?;
+    let end = params
+        .end
+        .as_deref()
+        .map(|v| parse_time("end", v))
+        .transpose()?;
+    if let (Some(start), Some(end)) = (start, end) {
+        if start >= end {
+            return Err(CoreError::InvalidInput(
+                "start must be earlier than end".to_string(),
+            ));
+        }
+    }
+
+    Ok(AuditEventQuery {
+        start,
+        end,
+        workspace_id: workspace_scope.or_else(|| params.workspace.clone()),
+        event_type: params
+            .event_type
+            .as_deref()
+            .map(|v| parse_variant::<AuditEventType>("event_type", v))
+            .transpose()?,
+        result: params
+            .result
+            .as_deref()
+            .map(|v| parse_variant::<AuditResult>("result", v))
+            .transpose()?,
+        actor: params.actor.clone(),
+        resource: params.resource.clone(),
+        before_id: params
+            .cursor
+            .as_deref()
+            .map(|c| {
+                c.parse::<i64>()
+                    .map_err(|_| CoreError::InvalidInput("invalid cursor".to_string()))
+            })
+            .transpose()?,
+        limit: params.limit,
+    })
+}
+
+impl super::ApiHandlers {
+    /// Query persisted audit events, newest first.
+    ///
+    /// `workspace_scope` is set for workspace-bound callers and restricts the
+    /// result to that workspace regardless of the `workspace` parameter.
+    pub async fn query_audit_events(
+        &self,
+        params: &AuditQueryParams,
+        workspace_scope: Option<String>,
+    ) -> Result<AuditQueryResponse> {
+        let repo = self.audit_event_repo.as_ref().ok_or_else(|| {
+            CoreError::ConfigurationError("audit event store is not configured".to_string())
+        })?;
+
+        let query = build_query(params, workspace_scope)?;
+        let page_size = query.limit;
+        // 多取一条用于判断是否还有下一页
+        let mut records = repo
+            .query_audit_events(&AuditEventQuery {
+                limit: page_size + 1,
+                ..query
+            })
+            .await?;
+
+        let next_cursor = if records.len() as u64 > page_size {
+            records.truncate(page_size as usize);
+            records.last().map(|r| r.id.to_string())
+        } else {
+            None
+        };
+
+        Ok(AuditQueryResponse {
+            events: records.into_iter().map(AuditEventInfo::from).collect(),
+            next_cursor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::Config;
+    use crate::core::database::{AuditEventRecord, AuditEventRepository};
+    use crate::server::config::management::{ConfigManagementService, ConfigManager};
+    use crate::server::config::HotReloadConfig;
+    use crate::server::handlers::mock_generator::MockIdGenerator;
+    use crate::server::handlers::ApiHandlers;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    /// 按 `AuditEventQuery` 过滤内存中的记录，并记录最后一次查询
+    struct FakeAuditRepo {
+        records: Vec<AuditEventRecord>,
+        last_query: Mutex<Option<AuditEventQuery>>,
+    }
+
+    #[async_trait]
+    impl AuditEventRepository for FakeAuditRepo {
+        async fn insert_audit_events(&self, _events: &[AuditEventRecord]) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn query_audit_events(
+            &self,
+            query: &AuditEventQuery,
+        ) -> Result<Vec<AuditEventRecord>> {
+            *self.last_query.lock().unwrap() = Some(query.clone());
+            let mut matched: Vec<AuditEventRecord> = self
+                .records
+                .iter()
+                .filter(|r| query.before_id.is_none_or(|c| r.id < c))
+                .filter(|r| {
+                    query
+                        .workspace_id
+                        .as_ref()
+                        .is_none_or(|w| r.workspace_id.as_ref() == Some(w))
+                })
+                .cloned()
+                .collect();
+            matched.sort_by(|a, b| b.id.cmp(&a.id));
+            matched.truncate(query.limit as usize);
+            Ok(matched)
+        }
+    }
+
+    fn record(id: i64, workspace: &str) -> AuditEventRecord {
+        AuditEventRecord {
+            id,
+            occurred_at: chrono::DateTime::from_timestamp(1_700_000_000 + id, 0)
+                .unwrap()
+                .naive_utc(),
+            event_type: "IdGeneration".to_string(),
+            workspace_id: Some(workspace.to_string()),
+            actor: None,
+            action: "POST /api/v1/generate".to_string(),
+            resource: "/api/v1/generate".to_string(),
+            result: "Success".to_string(),
+            details: None,
+            client_ip: None,
+            user_agent: None,
+            duration_ms: 1,
+            error_message: None,
+        }
+    }
+
+    fn params(json: &str) -> AuditQueryParams {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn create_handlers() -> ApiHandlers {
+        let config = Config::default();
+        let hot_config = Arc::new(HotReloadConfig::new(
+            config.clone(),
+            "config/config.toml".to_string(),
+        ));
+        let router = Arc::new(crate::core::algorithm::AlgorithmRouter::new(config, None));
+        let config_service: Arc<dyn ConfigManagementService> =
+            Arc::new(ConfigManager::new(hot_config, router));
+        ApiHandlers::new(Arc::new(MockIdGenerator::new()), config_service)
+    }
+
+    fn handlers_with(repo: Arc<FakeAuditRepo>) -> ApiHandlers {
+        create_handlers().with_audit_event_repository(repo)
+    }
+
+    #[tokio::test]
+    async fn test_query_audit_events_paginates_with_cursor() {
+        let repo = Arc::new(FakeAuditRepo {
+            records: (1..=5).map(|id| record(id, "ws-a")).collect(),
+            last_query: Mutex::new(None),
+        });
+        let handlers = handlers_with(repo);
+
+        let first = handlers
+            .query_audit_events(&params(r#"{"limit": 2}"#), None)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = first.events.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["5", "4"]);
+        assert_eq!(first.next_cursor.as_deref(), Some("4"));
+
+        let last = handlers
+            .query_audit_events(&params(r#"{"limit": 3, "cursor": "3"}"#), None)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = last.events.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["2", "1"]);
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_query_audit_events_scope_overrides_workspace_filter() {
+        let repo = Arc::new(FakeAuditRepo {
+            records: vec![record(1, "ws-a"), record(2, "ws-b")],
+            last_query: Mutex::new(None),
+        });
+        let handlers = handlers_with(repo.clone());
+
+        let page = handlers
+            .query_audit_events(
+                &params(r#"{"workspace": "ws-b"}"#),
+                Some("ws-a".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(page.events.len(), 1);
+        assert_eq!(page.events[0].workspace_id.as_deref(), Some("ws-a"));
+        let query = repo.last_query.lock().unwrap().clone().unwrap();
+        assert_eq!(query.workspace_id.as_deref(), Some("ws-a"));
+        assert_eq!(
+            query.limit, 51,
+            "one extra row is fetched to detect the next page"
+        );
+    }
+
+    #[test]
+    fn test_build_query_rejects_invalid_filters() {
+        for json in [
+            r#"{"start": "yesterday"}"#,
+            r#"{"start": "2026-10-02T00:00:00Z", "end": "2026-10-01T00:00:00Z"}"#,
+            r#"{"event_type": "NotAnEvent"}"#,
+            r#"{"result": "ok"}"#,
+            r#"{"cursor": "abc"}"#,
+        ] {
+            assert!(
+                matches!(
+                    build_query(&params(json), None),
+                    Err(CoreError::InvalidInput(_))
+                ),
+                "{} should be rejected",
+                json
+            );
+        }
+
+        let query = build_query(
+            &params(r#"{"event_type": "BizTagCreated", "result": "Failure", "start": "2026-10-01T08:00:00+08:00"}"#),
+            None,
+        )
+        .unwrap();
+        assert_eq!(query.event_type.as_deref(), Some("BizTagCreated"));
+        assert_eq!(query.result.as_deref(), Some("Failure"));
+        assert_eq!(
+            query.start.unwrap().to_string(),
+            "2026-10-01 00:00:00",
+            "start is normalized to UTC"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_audit_events_without_store_is_configuration_error() {
+        let handlers = create_handlers();
+        let result = handlers.query_audit_events(&params("{}"), None).await;
+        assert!(matches!(result, Err(CoreError::ConfigurationError(_))));
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Auth-failure ban handlers (admin only).
+//!
+//! 列出与解除认证失败封禁。解封在本实例立即生效，其他实例在下一次封禁同步
+//! （`auth.bans.sync_interval_seconds`）后生效。
+
+use crate::core::auth::BanManager;
+use crate::core::{CoreError, Result};
+use crate::server::models::{BanInfo, BanListParams, BanListResponse};
+use std::sync::Arc;
+
+impl super::ApiHandlers {
+    fn bans(&self) -> Result<&Arc<BanManager>> {
+        self.ban_manager.as_ref().ok_or_else(|| {
+            CoreError::ConfigurationError("auth ban manager is not configured".to_string())
+        })
+    }
+
+    /// Bans, newest first.
+    pub async fn list_bans(&self, params: &BanListParams) -> Result<BanListResponse> {
+        let bans = self.bans()?.list(params.active, params.limit).await?;
+        Ok(BanListResponse {
+            bans: bans.into_iter().map(BanInfo::from).collect(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        })
+    }
+
+    /// Lift a ban; `actor` is the admin key id recorded on the ban and in the
+    /// audit trail.
+    pub async fn lift_ban(&self, id: &str, actor: &str) -> Result<BanInfo> {
+        let id = uuid::Uuid::parse_str(id)
+            .map_err(|_| CoreError::InvalidInput(format!("invalid ban id: {}", id)))?;
+        self.bans()?.lift(id, actor).await.map(BanInfo::from)
+    }
+}
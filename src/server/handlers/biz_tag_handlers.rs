@@ -49,6 +49,9 @@ impl super::ApiHandlers {
         };
 
         let biz_tag = self.config_service.create_biz_tag(&core_req).await?;
+        if let Some(ref guard) = self.generation_guard {
+            guard.invalidate_biz_tags(biz_tag.workspace_id);
+        }
 
         Ok(BizTagResponse {
             id: biz_tag.id.to_string(),
@@ -90,6 +93,9 @@ impl super::ApiHandlers {
         };
 
         let biz_tag = self.config_service.update_biz_tag(id, &core_req).await?;
+        if let Some(ref guard) = self.generation_guard {
+            guard.invalidate_biz_tags(biz_tag.workspace_id);
+        }
 
         Ok(BizTagResponse {
             id: biz_tag.id.to_string(),
@@ -246,7 +252,11 @@ impl super::ApiHandlers {
     }
 
     pub async fn delete_biz_tag(&self, id: uuid::Uuid) -> Result<()> {
-        self.config_service.delete_biz_tag(id).await
+        self.config_service.delete_biz_tag(id).await?;
+        if let Some(ref guard) = self.generation_guard {
+            guard.invalidate_all_biz_tags();
+        }
+        Ok(())
     }
 }
 
@@ -285,6 +295,7 @@ mod tests {
             async fn create_workspace(&self, req: CreateWorkspaceRequest) -> crate::core::Result<WorkspaceResponse>;
             async fn list_workspaces(&self) -> crate::core::Result<WorkspaceListResponse>;
             async fn get_workspace(&self, name: &str) -> crate::core::Result<Option<WorkspaceResponse>>;
+            async fn update_workspace(&self, name: &str, request: &crate::core::database::UpdateWorkspaceRequest) -> crate::core::Result<Option<WorkspaceResponse>>;
             async fn create_group(&self, req: CreateGroupRequest) -> crate::core::Result<GroupResponse>;
             async fn list_groups(&self, workspace: &str) -> crate::core::Result<GroupListResponse>;
             async fn get_database_metrics(&self) -> DatabaseMetrics;
@@ -0,0 +1,238 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Admin degradation control handlers (rule 25 split).
+//!
+//! 运维在事故期间通过这些入口查看各算法健康/熔断状态、手动降级或恢复算法、
+//! 运行时调整降级链。所有变更都由 `DegradationManager` 记录 `DegradationEvent` 审计。
+
+use crate::core::algorithm::degradation_manager::{CircuitBreakerState, DegradationState};
+use crate::core::{AlgorithmType, CoreError, Result};
+use crate::server::models::{
+    AlgorithmDegradationInfo, DegradationActionRequest, DegradationStatusResponse,
+    UpdateFallbackChainRequest,
+};
+
+fn parse_algorithm(name: &str) -> Result<AlgorithmType> {
+    name.parse()
+        .map_err(|_| CoreError::InvalidAlgorithmType(name.to_string()))
+}
+
+fn state_name(state: &DegradationState) -> &'static str {
+    match state {
+        DegradationState::Normal => "normal",
+        DegradationState::Degraded(_) => "degraded",
+        DegradationState::Critical => "critical",
+    }
+}
+
+fn circuit_name(state: &CircuitBreakerState) -> &'static str {
+    match state {
+        CircuitBreakerState::Closed => "closed",
+        CircuitBreakerState::Open => "open",
+        CircuitBreakerState::HalfOpen => "half_open",
+    }
+}
+
+impl super::ApiHandlers {
+    /// Current degradation state, per-algorithm health and fallback chains.
+    pub async fn degradation_status(&self) -> DegradationStatusResponse {
+        let dm = self.id_generator.get_degradation_manager();
+
+        let mut algorithms: Vec<AlgorithmDegradationInfo> = dm
+            .get_all_states()
+            .into_iter()
+            .map(|s| AlgorithmDegradationInfo {
+                algorithm: s.alg_type.to_string(),
+                is_healthy: s.is_healthy,
+                is_degraded: s.is_degraded,
+                manually_degraded: dm.is_manually_degraded(s.alg_type),
+                circuit_breaker_state: circuit_name(&s.circuit_breaker_state).to_string(),
+                consecutive_failures: s.consecutive_failures,
+                consecutive_successes: s.consecutive_successes,
+            })
+            .collect();
+        algorithms.sort_by(|a, b| a.algorithm.cmp(&b.algorithm));
+
+        DegradationStatusResponse {
+            state: state_name(&dm.get_current_state()).to_string(),
+            primary_algorithm: dm.get_primary_algorithm().to_string(),
+            effective_algorithm: dm.get_effective_algorithm().await.to_string(),
+            fallback_chain: dm
+                .get_fallback_chain()
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+            routable_fallback_chain: dm
+                .routable_fallback_chain()
+                .iter()
+                .map(|a| a.to_string())
+                .collect(),
+            algorithms,
+            last_check_secs_ago: dm.last_check_elapsed().as_secs(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Force traffic off `algorithm` until it is explicitly recovered.
+    pub async fn degrade_algorithm(
+        &self,
+        algorithm: &str,
+        req: DegradationActionRequest,
+    ) -> Result<DegradationStatusResponse> {
+        let alg_type = parse_algorithm(algorithm)?;
+        self.id_generator
+            .get_degradation_manager()
+            .admin_degrade(alg_type, req.reason)
+            .await?;
+        Ok(self.degradation_status().await)
+    }
+
+    /// Clear a manual degradation and reset the algorithm's circuit breaker.
+    pub async fn recover_algorithm(
+        &self,
+        algorithm: &str,
+        req: DegradationActionRequest,
+    ) -> Result<DegradationStatusResponse> {
+        let alg_type = parse_algorithm(algorithm)?;
+        self.id_generator
+            .get_degradation_manager()
+            .admin_recover(alg_type, req.reason)
+            .await?;
+        Ok(self.degradation_status().await)
+    }
+
+    /// Replace the fallback chain used by routing.
+    pub async fn update_fallback_chain(
+        &self,
+        req: UpdateFallbackChainRequest,
+    ) -> Result<DegradationStatusResponse> {
+        let chain = req
+            .chain
+            .iter()
+            .map(|name| parse_algorithm(name))
+            .collect::<Result<Vec<_>>>()?;
+        self.id_generator
+            .get_degradation_manager()
+            .admin_set_fallback_chain(chain, req.reason)
+            .await?;
+        Ok(self.degradation_status().await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::algorithm::IdGenerator;
+    use crate::core::config::Config;
+    use crate::server::config::management::{ConfigManagementService, ConfigManager};
+    use crate::server::config::HotReloadConfig;
+    use crate::server::handlers::mock_generator::MockIdGenerator;
+    use crate::server::handlers::ApiHandlers;
+    use std::sync::Arc;
+
+    fn handlers_with_registered_algorithms() -> ApiHandlers {
+        let config = Config::default();
+        let hot_config = Arc::new(HotReloadConfig::new(
+            config.clone(),
+            "config/config.toml".to_string(),
+        ));
+        let router = Arc::new(crate::core::algorithm::AlgorithmRouter::new(config, None));
+        let config_service: Arc<dyn ConfigManagementService> =
+            Arc::new(ConfigManager::new(hot_config, router));
+
+        let generator = MockIdGenerator::new();
+        let dm = generator.get_degradation_manager();
+        dm.set_primary_algorithm(AlgorithmType::Segment);
+        dm.set_fallback_chain(vec![AlgorithmType::Snowflake, AlgorithmType::UuidV7]);
+        // manual_degrade 会为未注册的算法创建健康状态，随后 recover 回到正常，
+        // 用来在没有真实算法实例的情况下完成注册
+        for alg_type in [
+            AlgorithmType::Segment,
+            AlgorithmType::Snowflake,
+            AlgorithmType::UuidV7,
+        ] {
+            dm.manual_degrade(alg_type);
+            dm.manual_recover(alg_type);
+        }
+        ApiHandlers::new(Arc::new(generator), config_service)
+    }
+
+    #[tokio::test]
+    async fn test_degradation_status_lists_algorithms_and_chain() {
+        let handlers = handlers_with_registered_algorithms();
+        let status = handlers.degradation_status().await;
+        assert_eq!(status.state, "normal");
+        assert_eq!(status.primary_algorithm, "segment");
+        assert_eq!(status.fallback_chain, vec!["snowflake", "uuid_v7"]);
+        assert_eq!(status.algorithms.len(), 3);
+        assert!(status
+            .algorithms
+            .iter()
+            .all(|a| a.circuit_breaker_state == "closed" && !a.manually_degraded));
+    }
+
+    #[tokio::test]
+    async fn test_degrade_and_recover_algorithm_round_trip() {
+        let handlers = handlers_with_registered_algorithms();
+
+        let status = handlers
+            .degrade_algorithm("segment", DegradationActionRequest::default())
+            .await
+            .unwrap();
+        assert_eq!(status.state, "degraded");
+        assert_eq!(status.effective_algorithm, "snowflake");
+        assert!(status
+            .algorithms
+            .iter()
+            .any(|a| a.algorithm == "segment" && a.manually_degraded));
+
+        let status = handlers
+            .recover_algorithm("segment", DegradationActionRequest::default())
+            .await
+            .unwrap();
+        assert_eq!(status.state, "normal");
+        assert_eq!(status.effective_algorithm, "segment");
+    }
+
+    #[tokio::test]
+    async fn test_degrade_unknown_algorithm_is_rejected() {
+        let handlers = handlers_with_registered_algorithms();
+        let result = handlers
+            .degrade_algorithm("bogus", DegradationActionRequest::default())
+            .await;
+        assert!(matches!(result, Err(CoreError::InvalidAlgorithmType(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_fallback_chain_rejects_duplicates() {
+        let handlers = handlers_with_registered_algorithms();
+        let result = handlers
+            .update_fallback_chain(UpdateFallbackChainRequest {
+                chain: vec!["snowflake".to_string(), "snowflake".to_string()],
+                reason: None,
+            })
+            .await;
+        assert!(matches!(result, Err(CoreError::InvalidInput(_))));
+
+        let status = handlers
+            .update_fallback_chain(UpdateFallbackChainRequest {
+                chain: vec!["uuid_v7".to_string()],
+                reason: Some("snowflake clock skew".to_string()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(status.fallback_chain, vec!["uuid_v7"]);
+    }
+}
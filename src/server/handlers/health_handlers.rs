@@ -0,0 +1,417 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Component health probes behind `/health`, `/ready` and `/live`
+//! (rule 25 split).
+//!
+//! `/health` only reports in-process components (algorithms, degradation
+//! state, circuit breakers, segment buffers, etcd monitor, worker lease) so
+//! it never blocks on network I/O; `/ready` additionally probes the
+//! database and cache and applies the `monitoring.health` readiness policy.
+
+use crate::core::algorithm::degradation_manager::{CircuitBreakerState, DegradationState};
+use crate::core::algorithm::HealthStatus as CoreHealthStatus;
+use crate::core::config::HealthConfig;
+use crate::core::coordinator::EtcdClusterStatus;
+use crate::core::types::AlgorithmType;
+use crate::server::models::{ComponentHealth, HealthStatus, LiveResponse};
+use std::time::{Duration, Instant};
+
+/// Build one component entry. `started` is when the probe began (latency),
+/// `checked_ago` how old the underlying state is (zero for live probes).
+fn component(
+    name: &str,
+    status: HealthStatus,
+    message: Option<String>,
+    started: Instant,
+    checked_ago: Duration,
+) -> ComponentHealth {
+    let checked_ago =
+        chrono::Duration::from_std(checked_ago).unwrap_or_else(|_| chrono::Duration::zero());
+    ComponentHealth {
+        name: name.to_string(),
+        status,
+        last_checked: (chrono::Utc::now() - checked_ago).to_rfc3339(),
+        latency_us: started.elapsed().as_micros() as u64,
+        message,
+    }
+}
+
+fn from_core_status(status: CoreHealthStatus) -> (HealthStatus, Option<String>) {
+    match status {
+        CoreHealthStatus::Healthy => (HealthStatus::Healthy, None),
+        CoreHealthStatus::Degraded(reason) => (HealthStatus::Degraded, Some(reason)),
+        CoreHealthStatus::Unhealthy(reason) => (HealthStatus::Unhealthy, Some(reason)),
+    }
+}
+
+fn join_algorithms(algorithms: &[AlgorithmType]) -> String {
+    algorithms
+        .iter()
+        .map(|alg| alg.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Names of the components that violate `policy`.
+///
+/// A required component fails when it is `unhealthy`, a strict one when it
+/// is anything but `healthy`; a component missing from the report fails
+/// either way (e.g. `etcd` required on a deployment without etcd).
+pub(super) fn readiness_failures(
+    policy: &HealthConfig,
+    components: &[ComponentHealth],
+) -> Vec<String> {
+    let mut failed: Vec<String> = Vec::new();
+    let rules = policy
+        .readiness_required
+        .iter()
+        .map(|name| (name, false))
+        .chain(policy.readiness_strict.iter().map(|name| (name, true)));
+    for (name, strict) in rules {
+        let passes = components
+            .iter()
+            .find(|c| &c.name == name)
+            .is_some_and(|c| match c.status {
+                HealthStatus::Healthy => true,
+                HealthStatus::Degraded => !strict,
+                HealthStatus::Unhealthy => false,
+            });
+        if !passes && !failed.contains(name) {
+            failed.push(name.clone());
+        }
+    }
+    failed
+}
+
+impl super::ApiHandlers {
+    /// In-process components; no network I/O.
+    pub async fn local_components(&self) -> Vec<ComponentHealth> {
+        let mut components = Vec::with_capacity(6);
+
+        let started = Instant::now();
+        let (status, message) = from_core_status(self.id_generator.health_check().await);
+        components.push(component(
+            "algorithms",
+            status,
+            message,
+            started,
+            Duration::ZERO,
+        ));
+
+        components.extend(self.degradation_components());
+
+        let started = Instant::now();
+        let segment = self
+            .id_generator
+            .algorithm_health()
+            .await
+            .into_iter()
+            .find(|(alg, _)| *alg == AlgorithmType::Segment);
+        if let Some((_, health)) = segment {
+            let (status, message) = from_core_status(health);
+            components.push(component(
+                "segment_buffer",
+                status,
+                message,
+                started,
+                Duration::ZERO,
+            ));
+        }
+
+        if let Some(ref monitor) = self.etcd_health_monitor {
+            let started = Instant::now();
+            let (status, message) = match monitor.get_status() {
+                EtcdClusterStatus::Healthy => (HealthStatus::Healthy, None),
+                EtcdClusterStatus::Degraded => (
+                    HealthStatus::Degraded,
+                    Some("etcd health checks are failing".to_string()),
+                ),
+                EtcdClusterStatus::Failed => (
+                    HealthStatus::Unhealthy,
+                    Some("etcd cluster unreachable".to_string()),
+                ),
+            };
+            let message = if monitor.is_using_cache() {
+                Some(match message {
+                    Some(m) => format!("{}; serving from local cache", m),
+                    None => "serving from local cache".to_string(),
+                })
+            } else {
+                message
+            };
+            components.push(component("etcd", status, message, started, Duration::ZERO));
+        }
+
+        if let Some(ref allocator) = self.worker_allocator {
+            let started = Instant::now();
+            let (status, message) = match (allocator.get_allocated_id(), allocator.is_healthy()) {
+                (Some(id), true) => (HealthStatus::Healthy, Some(format!("worker_id {}", id))),
+                (Some(id), false) => (
+                    HealthStatus::Unhealthy,
+                    Some(format!("lease for worker_id {} is not valid", id)),
+                ),
+                (None, _) => (
+                    HealthStatus::Unhealthy,
+                    Some("no worker ID allocated".to_string()),
+                ),
+            };
+            components.push(component(
+                "worker_lease",
+                status,
+                message,
+                started,
+                Duration::ZERO,
+            ));
+        }
+
+        components
+    }
+
+    /// Database and cache, probed through the config service.
+    pub async fn dependency_components(&self) -> Vec<ComponentHealth> {
+        let started = Instant::now();
+        let db = self.config_service.get_database_metrics().await;
+        let database = component(
+            "database",
+            db.status,
+            db.last_error,
+            started,
+            Duration::ZERO,
+        );
+
+        let started = Instant::now();
+        let cache = self.config_service.get_cache_metrics().await;
+        let cache = component(
+            "cache",
+            cache.status,
+            (!cache.has_cache).then(|| "no caching algorithm active".to_string()),
+            started,
+            Duration::ZERO,
+        );
+
+        vec![database, cache]
+    }
+
+    /// `degradation` and `circuit_breakers`, as of the last background check.
+    fn degradation_components(&self) -> [ComponentHealth; 2] {
+        let started = Instant::now();
+        let manager = self.id_generator.get_degradation_manager();
+        let checked_ago = manager.last_check_elapsed();
+        let primary = manager.get_primary_algorithm();
+        let states = manager.get_all_states();
+
+        let primary_marked = states
+            .iter()
+            .any(|state| state.alg_type == primary && state.is_degraded);
+        let (status, message) = match manager.get_current_state() {
+            DegradationState::Normal if primary_marked => (
+                HealthStatus::Degraded,
+                Some(format!("primary algorithm {} is marked degraded", primary)),
+            ),
+            DegradationState::Normal => (
+                HealthStatus::Healthy,
+                Some(format!("serving with primary algorithm {}", primary)),
+            ),
+            DegradationState::Degraded(fallback) => (
+                HealthStatus::Degraded,
+                Some(format!(
+                    "primary algorithm {} degraded, serving from fallback {}",
+                    primary, fallback
+                )),
+            ),
+            DegradationState::Critical => (
+                HealthStatus::Unhealthy,
+                Some("all algorithms in the fallback chain are degraded".to_string()),
+            ),
+        };
+        let degradation = component("degradation", status, message, started, checked_ago);
+
+        let started = Instant::now();
+        let open: Vec<AlgorithmType> = states
+            .iter()
+            .filter(|s| s.circuit_breaker_state == CircuitBreakerState::Open)
+            .map(|s| s.alg_type)
+            .collect();
+        let half_open: Vec<AlgorithmType> = states
+            .iter()
+            .filter(|s| s.circuit_breaker_state == CircuitBreakerState::HalfOpen)
+            .map(|s| s.alg_type)
+            .collect();
+        let status = if !states.is_empty() && open.len() == states.len() {
+            HealthStatus::Unhealthy
+        } else if !open.is_empty() || !half_open.is_empty() {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        };
+        let mut parts = Vec::new();
+        if !open.is_empty() {
+            parts.push(format!("open: {}", join_algorithms(&open)));
+        }
+        if !half_open.is_empty() {
+            parts.push(format!("half-open: {}", join_algorithms(&half_open)));
+        }
+        let circuit_breakers = component(
+            "circuit_breakers",
+            status,
+            (!parts.is_empty()).then(|| parts.join("; ")),
+            started,
+            checked_ago,
+        );
+
+        [degradation, circuit_breakers]
+    }
+
+    /// Liveness: answers as long as the HTTP server is running.
+    pub fn live(&self) -> LiveResponse {
+        LiveResponse {
+            alive: true,
+            uptime_seconds: self.start_time.elapsed().as_secs(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::Config;
+    use crate::core::coordinator::{WorkerAllocatorError, WorkerIdAllocator};
+    use crate::server::config::management::{ConfigManagementService, ConfigManager};
+    use crate::server::config::HotReloadConfig;
+    use crate::server::handlers::mock_generator::MockIdGenerator;
+    use crate::server::handlers::ApiHandlers;
+    use async_trait::async_trait;
+    use std::sync::Arc;
+
+    fn create_handlers() -> ApiHandlers {
+        let config = Config::default();
+        let hot_config = Arc::new(HotReloadConfig::new(
+            config.clone(),
+            "config/config.toml".to_string(),
+        ));
+        let router = Arc::new(crate::core::algorithm::AlgorithmRouter::new(config, None));
+        let config_service: Arc<dyn ConfigManagementService> =
+            Arc::new(ConfigManager::new(hot_config, router));
+        ApiHandlers::new(Arc::new(MockIdGenerator::new()), config_service)
+    }
+
+    fn entry(name: &str, status: HealthStatus) -> ComponentHealth {
+        component(name, status, None, Instant::now(), Duration::ZERO)
+    }
+
+    struct StubAllocator {
+        id: Option<u16>,
+        healthy: bool,
+    }
+
+    #[async_trait]
+    impl WorkerIdAllocator for StubAllocator {
+        async fn allocate(&self) -> std::result::Result<u16, WorkerAllocatorError> {
+            self.id.ok_or(WorkerAllocatorError::NoAvailableId)
+        }
+        async fn release(&self, _worker_id: u16) -> std::result::Result<(), WorkerAllocatorError> {
+            Ok(())
+        }
+        fn get_allocated_id(&self) -> Option<u16> {
+            self.id
+        }
+        fn is_healthy(&self) -> bool {
+            self.healthy
+        }
+    }
+
+    #[test]
+    fn test_readiness_failures_default_policy_ignores_degraded() {
+        let policy = HealthConfig::default();
+        let components = vec![
+            entry("database", HealthStatus::Healthy),
+            entry("cache", HealthStatus::Degraded),
+            entry("degradation", HealthStatus::Unhealthy),
+        ];
+        assert!(readiness_failures(&policy, &components).is_empty());
+    }
+
+    #[test]
+    fn test_readiness_failures_strict_and_missing_components() {
+        let policy = HealthConfig {
+            readiness_required: vec!["database".to_string(), "etcd".to_string()],
+            readiness_strict: vec!["degradation".to_string(), "database".to_string()],
+        };
+        let components = vec![
+            entry("database", HealthStatus::Unhealthy),
+            entry("degradation", HealthStatus::Degraded),
+        ];
+        assert_eq!(
+            readiness_failures(&policy, &components),
+            vec!["database", "etcd", "degradation"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_components_report_degradation_and_breakers() {
+        let handlers = create_handlers();
+        let components = handlers.local_components().await;
+        let names: Vec<&str> = components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["algorithms", "degradation", "circuit_breakers"]);
+        assert!(components.iter().all(|c| c.status == HealthStatus::Healthy));
+        assert!(chrono::DateTime::parse_from_rfc3339(&components[1].last_checked).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_local_components_flag_manually_degraded_primary() {
+        let handlers = create_handlers();
+        handlers
+            .id_generator
+            .get_degradation_manager()
+            .manual_degrade(AlgorithmType::Segment);
+        let components = handlers.local_components().await;
+        let degradation = components.iter().find(|c| c.name == "degradation").unwrap();
+        assert_eq!(degradation.status, HealthStatus::Degraded);
+
+        let policy = HealthConfig {
+            readiness_strict: vec!["degradation".to_string()],
+            ..HealthConfig::default()
+        };
+        assert_eq!(
+            readiness_failures(&policy, &components),
+            vec!["database", "cache", "degradation"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worker_lease_component() {
+        let handlers = create_handlers().with_worker_allocator(Arc::new(StubAllocator {
+            id: Some(7),
+            healthy: false,
+        }));
+        let components = handlers.local_components().await;
+        let lease = components
+            .iter()
+            .find(|c| c.name == "worker_lease")
+            .unwrap();
+        assert_eq!(lease.status, HealthStatus::Unhealthy);
+        assert_eq!(
+            lease.message.as_deref(),
+            Some("lease for worker_id 7 is not valid")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_live_reports_alive() {
+        let handlers = create_handlers();
+        let response = handlers.live();
+        assert!(response.alive);
+    }
+}
@@ -83,7 +83,7 @@ fn core_error_status_code(e: &CoreError) -> StatusCode {
         | CoreError::InvalidApiKeySignature
         | CoreError::ApiKeyDisabled
         | CoreError::ApiKeyExpired => StatusCode::UNAUTHORIZED,
-        CoreError::WorkspaceDisabled(_) => StatusCode::FORBIDDEN,
+        CoreError::PermissionDenied(_) | CoreError::WorkspaceDisabled(_) => StatusCode::FORBIDDEN,
         CoreError::NotFound(_) | CoreError::BizTagNotFound(_) => StatusCode::NOT_FOUND,
         CoreError::RateLimitExceeded => StatusCode::TOO_MANY_REQUESTS,
         CoreError::TimeoutError => StatusCode::SERVICE_UNAVAILABLE,
@@ -135,7 +135,7 @@ fn sanitize_for_production(msg: &str) -> String {
 ///   looked up under `api.error.<variant>` — never the raw `String`.
 /// - **4xx-class errors with caller-supplied `String`** (`InvalidInput`,
 ///   `NotFound`, `BizTagNotFound`, `AuthenticationError`,
-///   `WorkspaceDisabled`, `InvalidIdFormat`, `InvalidIdString`,
+///   `PermissionDenied`, `WorkspaceDisabled`, `InvalidIdFormat`, `InvalidIdString`,
 ///   `InvalidAlgorithmType`, `ParseError`): the localized message is
 ///   generated via `CoreError::to_localized_string` and then run through
 ///   `sanitize_for_production` to cap length at
@@ -230,6 +230,7 @@ pub fn core_error_to_response(e: &CoreError, locale: Locale) -> (StatusCode, Jso
         | CoreError::NotFound(_)
         | CoreError::BizTagNotFound(_)
         | CoreError::AuthenticationError(_)
+        | CoreError::PermissionDenied(_)
         | CoreError::WorkspaceDisabled(_)
         | CoreError::InvalidIdFormat(_)
         | CoreError::InvalidIdString(_)
@@ -341,6 +342,22 @@ pub(crate) fn workspace_mismatch_response(locale: Locale) -> (StatusCode, Json<E
     )
 }
 
+/// Build a 403 response for an API key missing the scope an operation requires.
+pub(crate) fn scope_denied_response(
+    scope: &crate::core::auth::Scope,
+    locale: Locale,
+) -> (StatusCode, Json<ErrorResponse>) {
+    let message = translate_with_locale_args(
+        locale.as_str(),
+        "api.error.scope_denied",
+        &[("scope", scope.to_string())],
+    );
+    (
+        StatusCode::FORBIDDEN,
+        Json(ErrorResponse::new(403, message)),
+    )
+}
+
 /// Build a 404 response for "Workspace '<name>' not found".
 ///
 /// Phase 8 T041 (LOW L-4 fix) — `name` originates from a URL path
@@ -495,6 +512,9 @@ mod tests {
         let (s, _) =
             core_error_to_response(&CoreError::WorkspaceDisabled("x".to_string()), Locale::En);
         assert_eq!(s, StatusCode::FORBIDDEN);
+        let (s, _) =
+            core_error_to_response(&CoreError::PermissionDenied("x".to_string()), Locale::En);
+        assert_eq!(s, StatusCode::FORBIDDEN);
 
         // 404
         let (s, _) = core_error_to_response(&CoreError::NotFound("x".to_string()), Locale::En);
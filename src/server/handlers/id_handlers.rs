@@ -38,6 +38,9 @@ impl super::ApiHandlers {
             biz_tag = %req.biz_tag,
         );
 
+        self.check_generation_allowed(&req.workspace, &req.group, &req.biz_tag)
+            .await?;
+
         // L6 修复：提前 parse algorithm，复用结果避免重复 parse。
         let parsed_algorithm: Option<crate::core::types::AlgorithmType> =
             if let Some(ref alg_str) = req.algorithm {
@@ -62,9 +65,13 @@ impl super::ApiHandlers {
                 self.metrics
                     .failed_generations
                     .fetch_add(1, Ordering::SeqCst);
+                self.usage_tracker
+                    .record_generation(&req.workspace, &req.biz_tag, 0, false);
                 return Err(e.clone());
             }
         };
+        self.usage_tracker
+            .record_generation(&req.workspace, &req.biz_tag, 1, true);
 
         let elapsed = start.elapsed();
         self.metrics.total_requests.fetch_add(1, Ordering::SeqCst);
@@ -127,6 +134,9 @@ impl super::ApiHandlers {
             ));
         }
 
+        self.check_generation_allowed(&req.workspace, &req.group, &req.biz_tag)
+            .await?;
+
         let result = if let Some(ref alg_str) = req.algorithm {
             let algorithm: crate::core::types::AlgorithmType = alg_str.parse()?;
             self.id_generator
@@ -150,9 +160,13 @@ impl super::ApiHandlers {
                 self.metrics
                     .failed_generations
                     .fetch_add(1, Ordering::SeqCst);
+                self.usage_tracker
+                    .record_generation(&req.workspace, &req.biz_tag, 0, false);
                 return Err(e.clone());
             }
         };
+        self.usage_tracker
+            .record_generation(&req.workspace, &req.biz_tag, ids.len() as u64, true);
 
         let elapsed = start.elapsed();
         self.metrics.total_requests.fetch_add(1, Ordering::SeqCst);
@@ -185,6 +199,33 @@ impl super::ApiHandlers {
         })
     }
 
+    /// 严格模式检查；拒绝计入失败生成次数
+    async fn check_generation_allowed(
+        &self,
+        workspace: &str,
+        group: &str,
+        biz_tag: &str,
+    ) -> Result<()> {
+        let Some(ref guard) = self.generation_guard else {
+            return Ok(());
+        };
+        guard
+            .check(workspace, group, biz_tag)
+            .await
+            .inspect_err(|e| {
+                self.metrics
+                    .failed_generations
+                    .fetch_add(1, Ordering::SeqCst);
+                tracing::debug!(
+                    event = "generation_rejected",
+                    workspace = %workspace,
+                    group = %group,
+                    biz_tag = %biz_tag,
+                    error = ?e,
+                );
+            })
+    }
+
     pub async fn parse(&self, req: ParseRequest) -> Result<ParseResponse> {
         let id = Id::from_string(&req.id).map_err(|e| {
             CoreError::InvalidIdString(
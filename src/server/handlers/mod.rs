@@ -16,15 +16,28 @@
 //!
 //! `ApiHandlers` struct + constructors live here; per-domain method impls
 //! are split into sub-modules (`id_handlers`, `system_handlers`,
-//! `biz_tag_handlers`, `workspace_handlers`, `api_key_handlers`)
+//! `health_handlers`, `biz_tag_handlers`, `workspace_handlers`,
+//! `api_key_handlers`, `degradation_handlers`, `audit_handlers`,
+//! `ban_handlers`)
 //! (rule 25: mod.rs 只放 trait + pub struct + re-export).
 
-use crate::core::database::ApiKeyRepository;
+use crate::core::auth::{ApiKeyLifecycle, BanManager};
+use crate::core::config::{CapacityConfig, HealthConfig};
+use crate::core::coordinator::{EtcdClusterHealthMonitor, WorkerIdAllocator};
+use crate::core::database::{ApiKeyRepository, AuditEventRepository};
+use crate::core::monitoring::{CapacityForecaster, UsageTracker};
+use crate::server::audit::{AuditSinkMetrics, AuditWriterMetrics};
 use crate::server::config::management::ConfigManagementService;
+use crate::server::config::tls::TlsCertificateMetrics;
+use crate::server::generation_guard::GenerationGuard;
 use std::sync::Arc;
 
 pub mod api_key_handlers;
+pub mod audit_handlers;
+pub mod ban_handlers;
 pub mod biz_tag_handlers;
+pub mod degradation_handlers;
+pub mod health_handlers;
 pub mod helpers;
 pub mod id_handlers;
 // pre-existing test helper module (MockIdGenerator); not part of T027-T033 split,
@@ -48,6 +61,35 @@ pub struct ApiHandlers {
     /// 现移到 `AuthConfig::key_rotation_grace_period_seconds`，由
     /// `with_key_rotation_grace_period` builder 方法注入。
     pub(super) key_rotation_grace_period_seconds: u64,
+    /// 按 workspace / biz_tag 维度的用量统计（`/api/v1/admin/usage`）。
+    /// 默认使用独立实例；`main.rs` 通过 `with_usage_tracker` 注入与
+    /// `AlgorithmRouter` 共享的实例，使号段消耗速率与生成速率出现在同一报告中。
+    pub(super) usage_tracker: Arc<UsageTracker>,
+    /// 号段耗尽预测（`/api/v1/admin/capacity` 与 `/metrics` 汇总），
+    /// 基于 `usage_tracker` 的号段分配数据。
+    pub(super) capacity_forecaster: Arc<CapacityForecaster>,
+    /// 就绪策略（`monitoring.health`），决定哪些组件状态会让 `/ready` 失败。
+    pub(super) health_config: HealthConfig,
+    /// etcd 集群健康监控器；仅 etcd 部署注入，未注入时不报告 `etcd` 组件。
+    pub(super) etcd_health_monitor: Option<Arc<EtcdClusterHealthMonitor>>,
+    /// Worker ID 分配器；注入后以 `worker_lease` 组件报告租约有效性。
+    pub(super) worker_allocator: Option<Arc<dyn WorkerIdAllocator>>,
+    /// 审计文件写入链路指标；配置了 `audit.log_path` 时注入，出现在 `/metrics`。
+    pub(super) audit_writer_metrics: Option<Arc<AuditWriterMetrics>>,
+    /// 外部审计 sink 投递指标，出现在 `/metrics`。
+    pub(super) audit_sink_metrics: Vec<Arc<AuditSinkMetrics>>,
+    /// 服务端 TLS 证书指标；启用 TLS 时注入，出现在 `/metrics`。
+    pub(super) tls_certificate_metrics: Option<Arc<TlsCertificateMetrics>>,
+    /// 持久化审计事件仓储（`GET /api/v1/audit`）；未注入时查询返回配置错误。
+    pub(super) audit_event_repo: Option<Arc<dyn AuditEventRepository>>,
+    /// 严格生成检查（workspace 状态 + biz_tag 注册）；未注入时不检查
+    pub(super) generation_guard: Option<Arc<GenerationGuard>>,
+    /// API key 生命周期检查（`GET /api/v1/api-keys/attention`）；未注入时返回配置错误
+    pub(super) api_key_lifecycle: Option<Arc<ApiKeyLifecycle>>,
+    /// 认证失败封禁（`GET /api/v1/admin/bans`）；未注入时返回配置错误
+    pub(super) ban_manager: Option<Arc<BanManager>>,
+    /// workspace 管理员自助创建 key 时每个 workspace 的数量上限（0 = 不限制）
+    pub(super) max_api_keys_per_workspace: u64,
 }
 
 #[derive(Default)]
@@ -65,13 +107,16 @@ pub struct ApiMetrics {
 ///
 /// ARCH-MED-002 修复：删除本地 `const`，统一引用
 /// `crate::core::config::auth::DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS`。
-use crate::core::config::auth::DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS;
+use crate::core::config::auth::{
+    DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS, DEFAULT_MAX_API_KEYS_PER_WORKSPACE,
+};
 
 impl ApiHandlers {
     pub fn new(
         id_generator: Arc<dyn crate::core::algorithm::IdGenerator>,
         config_service: Arc<dyn ConfigManagementService>,
     ) -> Self {
+        let usage_tracker = Arc::new(UsageTracker::default());
         Self {
             id_generator,
             metrics: ApiMetrics::default(),
@@ -79,6 +124,22 @@ impl ApiHandlers {
             config_service,
             api_key_repo: None,
             key_rotation_grace_period_seconds: DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS,
+            capacity_forecaster: Arc::new(CapacityForecaster::new(
+                usage_tracker.clone(),
+                CapacityConfig::default(),
+            )),
+            usage_tracker,
+            health_config: HealthConfig::default(),
+            etcd_health_monitor: None,
+            worker_allocator: None,
+            audit_writer_metrics: None,
+            audit_sink_metrics: Vec::new(),
+            tls_certificate_metrics: None,
+            audit_event_repo: None,
+            generation_guard: None,
+            api_key_lifecycle: None,
+            ban_manager: None,
+            max_api_keys_per_workspace: DEFAULT_MAX_API_KEYS_PER_WORKSPACE,
         }
     }
 
@@ -87,6 +148,7 @@ impl ApiHandlers {
         config_service: Arc<dyn ConfigManagementService>,
         api_key_repo: Arc<dyn ApiKeyRepository>,
     ) -> Self {
+        let usage_tracker = Arc::new(UsageTracker::default());
         Self {
             id_generator,
             metrics: ApiMetrics::default(),
@@ -94,6 +156,22 @@ impl ApiHandlers {
             config_service,
             api_key_repo: Some(api_key_repo),
             key_rotation_grace_period_seconds: DEFAULT_KEY_ROTATION_GRACE_PERIOD_SECONDS,
+            capacity_forecaster: Arc::new(CapacityForecaster::new(
+                usage_tracker.clone(),
+                CapacityConfig::default(),
+            )),
+            usage_tracker,
+            health_config: HealthConfig::default(),
+            etcd_health_monitor: None,
+            worker_allocator: None,
+            audit_writer_metrics: None,
+            audit_sink_metrics: Vec::new(),
+            tls_certificate_metrics: None,
+            audit_event_repo: None,
+            generation_guard: None,
+            api_key_lifecycle: None,
+            ban_manager: None,
+            max_api_keys_per_workspace: DEFAULT_MAX_API_KEYS_PER_WORKSPACE,
         }
     }
 
@@ -130,6 +208,95 @@ impl ApiHandlers {
         self
     }
 
+    /// 注入共享的用量统计器（与 `AlgorithmRouter::with_usage_tracker` 同一实例）
+    ///
+    /// 容量预测器随之改为基于新的统计器（保留原容量配置）。
+    pub fn with_usage_tracker(mut self, tracker: Arc<UsageTracker>) -> Self {
+        self.capacity_forecaster = Arc::new(CapacityForecaster::new(
+            tracker.clone(),
+            self.capacity_forecaster.config().clone(),
+        ));
+        self.usage_tracker = tracker;
+        self
+    }
+
+    /// 注入共享的号段容量预测器（与容量告警任务同一实例）
+    pub fn with_capacity_forecaster(mut self, forecaster: Arc<CapacityForecaster>) -> Self {
+        self.capacity_forecaster = forecaster;
+        self
+    }
+
+    /// 注入就绪策略（`Config::monitoring.health`）
+    pub fn with_health_config(mut self, config: HealthConfig) -> Self {
+        self.health_config = config;
+        self
+    }
+
+    /// 注入 etcd 集群健康监控器（与 `AlgorithmRouter` 同一实例）
+    pub fn with_etcd_health_monitor(mut self, monitor: Arc<EtcdClusterHealthMonitor>) -> Self {
+        self.etcd_health_monitor = Some(monitor);
+        self
+    }
+
+    /// 注入 Worker ID 分配器，使健康报告包含租约状态
+    pub fn with_worker_allocator(mut self, allocator: Arc<dyn WorkerIdAllocator>) -> Self {
+        self.worker_allocator = Some(allocator);
+        self
+    }
+
+    /// 注入审计文件写入指标（`AuditLogger::writer_metrics`）
+    pub fn with_audit_writer_metrics(mut self, metrics: Arc<AuditWriterMetrics>) -> Self {
+        self.audit_writer_metrics = Some(metrics);
+        self
+    }
+
+    /// 注入外部审计 sink 指标（`AuditLogger::sink_metrics`）
+    pub fn with_audit_sink_metrics(mut self, metrics: Vec<Arc<AuditSinkMetrics>>) -> Self {
+        self.audit_sink_metrics = metrics;
+        self
+    }
+
+    /// 注入服务端 TLS 证书指标（`TlsManager::certificate_metrics`）
+    pub fn with_tls_certificate_metrics(mut self, metrics: Arc<TlsCertificateMetrics>) -> Self {
+        self.tls_certificate_metrics = Some(metrics);
+        self
+    }
+
+    /// 注入持久化审计事件仓储，启用 `GET /api/v1/audit`
+    pub fn with_audit_event_repository(mut self, repo: Arc<dyn AuditEventRepository>) -> Self {
+        self.audit_event_repo = Some(repo);
+        self
+    }
+
+    /// 注入 API key 生命周期检查，启用 `GET /api/v1/api-keys/attention`
+    pub fn with_api_key_lifecycle(mut self, lifecycle: Arc<ApiKeyLifecycle>) -> Self {
+        self.api_key_lifecycle = Some(lifecycle);
+        self
+    }
+
+    /// 注入认证失败封禁管理器，启用 `/api/v1/admin/bans`
+    pub fn with_ban_manager(mut self, manager: Arc<BanManager>) -> Self {
+        self.ban_manager = Some(manager);
+        self
+    }
+
+    /// 注入 `AuthConfig::max_api_keys_per_workspace`；未调用时默认 50
+    pub fn with_max_api_keys_per_workspace(mut self, max: u64) -> Self {
+        self.max_api_keys_per_workspace = max;
+        self
+    }
+
+    /// 注入严格生成检查；`generate` / `batch_generate` 在生成前校验
+    /// workspace 状态与 biz_tag 注册，CRUD 处理器负责失效其缓存
+    pub fn with_generation_guard(mut self, guard: Arc<GenerationGuard>) -> Self {
+        self.generation_guard = Some(guard);
+        self
+    }
+
+    pub fn usage_tracker(&self) -> Arc<UsageTracker> {
+        self.usage_tracker.clone()
+    }
+
     pub fn get_config_service(&self) -> Arc<dyn ConfigManagementService> {
         self.config_service.clone()
     }
@@ -149,6 +316,7 @@ impl ApiHandlers {
 mod mock_tests {
     use super::*;
     use crate::core::algorithm::AlgorithmMetricsSnapshot;
+    use crate::core::auth::ApiKeyScopes;
     use crate::core::database::{
         ApiKey, ApiKeyInfo, ApiKeyRepository, ApiKeyResponse as CoreApiKeyResponse, ApiKeyRole,
         ApiKeyWithSecret, BizTag, CreateApiKeyRequest as CoreCreateApiKeyRequest,
@@ -183,6 +351,7 @@ mod mock_tests {
             async fn create_workspace(&self, req: CreateWorkspaceRequest) -> crate::core::Result<WorkspaceResponse>;
             async fn list_workspaces(&self) -> crate::core::Result<WorkspaceListResponse>;
             async fn get_workspace(&self, name: &str) -> crate::core::Result<Option<WorkspaceResponse>>;
+            async fn update_workspace(&self, name: &str, request: &crate::core::database::UpdateWorkspaceRequest) -> crate::core::Result<Option<WorkspaceResponse>>;
             async fn create_group(&self, req: CreateGroupRequest) -> crate::core::Result<GroupResponse>;
             async fn list_groups(&self, workspace: &str) -> crate::core::Result<GroupListResponse>;
             async fn get_database_metrics(&self) -> DatabaseMetrics;
@@ -198,6 +367,7 @@ mod mock_tests {
         impl ApiKeyRepository for ApiKeyRepository {
             async fn create_api_key(&self, request: &CoreCreateApiKeyRequest) -> Result<ApiKeyWithSecret>;
             async fn get_api_key_by_id(&self, key_id: &str) -> Result<Option<ApiKeyInfo>>;
+            async fn get_api_key(&self, id: Uuid) -> Result<Option<ApiKeyInfo>>;
             async fn validate_api_key(&self, key_id: &str, key_secret: &str) -> Result<Option<(Option<Uuid>, ApiKeyRole)>>;
             async fn list_api_keys(&self, workspace_id: Uuid, limit: Option<u32>, offset: Option<u32>) -> Result<Vec<ApiKeyInfo>>;
             async fn delete_api_key(&self, id: Uuid) -> Result<()>;
@@ -205,6 +375,7 @@ mod mock_tests {
             async fn update_last_used(&self, id: Uuid) -> Result<()>;
             async fn get_admin_api_key(&self, workspace_id: Uuid) -> Result<Option<ApiKeyInfo>>;
             async fn count_api_keys(&self, workspace_id: Uuid) -> Result<u64>;
+            async fn count_enabled_api_keys(&self, workspace_id: Uuid) -> Result<u64>;
             async fn rotate_api_key(&self, key_id: &str, grace_period_seconds: u64) -> Result<ApiKeyWithSecret>;
             async fn get_keys_older_than(&self, age_threshold_days: i64) -> Result<Vec<ApiKeyInfo>>;
         }
@@ -258,6 +429,7 @@ mod mock_tests {
             status: "active".to_string(),
             max_groups: 10,
             max_biz_tags: 100,
+            strict_generation: true,
             created_at: chrono::Utc::now().to_rfc3339(),
             updated_at: chrono::Utc::now().to_rfc3339(),
             user_api_key: None,
@@ -290,6 +462,7 @@ mod mock_tests {
                 enabled: true,
                 expires_at: None,
                 created_at: chrono::Utc::now().naive_utc(),
+                scopes: ApiKeyScopes::unrestricted(),
             },
             key_secret: "test-secret-value-12345".to_string(),
         }
@@ -309,6 +482,7 @@ mod mock_tests {
             expires_at: None,
             last_used_at: None,
             created_at: chrono::Utc::now().naive_utc(),
+            scopes: ApiKeyScopes::unrestricted(),
         }
     }
 
@@ -1242,6 +1416,8 @@ mod mock_tests {
             role: Some("admin".to_string()),
             rate_limit: None,
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
         let result = handlers.create_api_key(None, req).await;
         assert!(result.is_err());
@@ -1265,6 +1441,8 @@ mod mock_tests {
             role: Some("superadmin".to_string()),
             rate_limit: None,
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
         let result = handlers.create_api_key(None, req).await;
         assert!(result.is_err());
@@ -1293,6 +1471,8 @@ mod mock_tests {
             role: Some("user".to_string()),
             rate_limit: None,
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
         let result = handlers.create_api_key(Some(ws_id), req).await;
         assert!(result.is_ok());
@@ -1301,6 +1481,87 @@ mod mock_tests {
         assert_eq!(response.key.role, "user");
     }
 
+    #[tokio::test]
+    async fn mock_test_workspace_key_revoke_frees_quota_slot() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+
+        let ws_id = Uuid::new_v4();
+        let key = ApiKey {
+            workspace_id: Some(ws_id),
+            ..test_api_key(ApiKeyRole::User)
+        };
+        let key_id = key.id;
+        // 启用的 key 数量：吊销后减一
+        let enabled = Arc::new(AtomicU64::new(1));
+        let mut mock_repo = MockApiKeyRepository::new();
+        let count = enabled.clone();
+        mock_repo
+            .expect_count_enabled_api_keys()
+            .returning(move |_| Ok(count.load(Ordering::SeqCst)));
+        mock_repo
+            .expect_get_api_key()
+            .returning(move |_| Ok(Some(key.clone())));
+        mock_repo.expect_get_api_key_by_id().returning(|_| Ok(None));
+        let revoked = enabled.clone();
+        mock_repo.expect_delete_api_key().returning(move |_| {
+            revoked.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        });
+        mock_repo
+            .expect_list_api_keys()
+            .returning(|_, _, _| Ok(vec![]));
+        mock_repo
+            .expect_create_api_key()
+            .returning(|_| Ok(test_api_key_with_secret()));
+        let handlers = Arc::try_unwrap(create_mock_handlers_with_repo(
+            MockConfigManagementService::new(),
+            mock_repo,
+        ))
+        .unwrap_or_else(|_| unreachable!("handlers are not shared yet"))
+        .with_max_api_keys_per_workspace(1);
+        let req = || CreateApiKeyRequest {
+            workspace_id: None,
+            name: "svc".to_string(),
+            description: None,
+            role: Some("user".to_string()),
+            rate_limit: None,
+            expires_at: None,
+            scopes: None,
+            request_signing: false,
+        };
+
+        let err = handlers
+            .create_workspace_api_key(ws_id, &ApiKeyScopes::unrestricted(), req())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::InvalidInput(_)), "got {err:?}");
+
+        handlers
+            .revoke_workspace_api_key(ws_id, key_id)
+            .await
+            .unwrap();
+        assert!(handlers
+            .create_workspace_api_key(ws_id, &ApiKeyScopes::unrestricted(), req())
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn mock_test_workspace_key_revoke_rejects_foreign_key() {
+        let mut mock_repo = MockApiKeyRepository::new();
+        mock_repo
+            .expect_get_api_key()
+            .return_once(|_| Ok(Some(test_api_key(ApiKeyRole::User))));
+        let handlers =
+            create_mock_handlers_with_repo(MockConfigManagementService::new(), mock_repo);
+
+        let err = handlers
+            .revoke_workspace_api_key(Uuid::new_v4(), Uuid::new_v4())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CoreError::NotFound(_)), "got {err:?}");
+    }
+
     #[tokio::test]
     async fn mock_test_list_api_keys_no_repo() {
         let handlers = create_mock_handlers(MockConfigManagementService::new());
@@ -1403,7 +1664,7 @@ mod mock_tests {
     #[tokio::test]
     async fn mock_test_rotate_api_key_empty_key_id() {
         let handlers = create_mock_handlers(MockConfigManagementService::new());
-        let result = handlers.rotate_api_key("").await;
+        let result = handlers.rotate_api_key("", None).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             CoreError::InvalidInput(msg) => assert!(msg.contains("key_id cannot be empty")),
@@ -1414,7 +1675,7 @@ mod mock_tests {
     #[tokio::test]
     async fn mock_test_rotate_api_key_no_repo() {
         let handlers = create_mock_handlers(MockConfigManagementService::new());
-        let result = handlers.rotate_api_key("nino_some-key-id").await;
+        let result = handlers.rotate_api_key("nino_some-key-id", None).await;
         assert!(result.is_err());
         match result.unwrap_err() {
             CoreError::NotFound(msg) => {
@@ -1432,7 +1693,7 @@ mod mock_tests {
             .return_once(|_, _| Ok(test_api_key_with_secret()));
         let handlers =
             create_mock_handlers_with_repo(MockConfigManagementService::new(), mock_repo);
-        let result = handlers.rotate_api_key("nino_test-key-id").await;
+        let result = handlers.rotate_api_key("nino_test-key-id", None).await;
         assert!(result.is_ok());
         let response = result.unwrap();
         assert!(!response.key_secret.is_empty());
@@ -14,13 +14,19 @@
 
 //! System / observability handlers: health, readiness, metrics,
 //! and the background key-rotation task launcher (rule 25 split).
+//! Component probes behind health/readiness live in `health_handlers`.
 
-use crate::server::models::{AlgorithmMetrics, HealthResponse, MetricsResponse, ReadyResponse};
+use crate::server::models::{
+    AlgorithmMetrics, AuditSinkSummary, AuditWriterSummary, CapacityQueryParams, CapacityResponse,
+    HealthResponse, MetricsResponse, ReadyResponse, SegmentCapacitySummary, TlsCertificateSummary,
+    UsageQueryParams, UsageResponse,
+};
 use std::sync::atomic::Ordering;
 
 // KeyRotationHandle lives in `api_key_handlers` (it owns the API key repo
 // shutdown channel); we only return it from here.
 use super::api_key_handlers::KeyRotationHandle;
+use super::health_handlers::readiness_failures;
 
 impl super::ApiHandlers {
     pub async fn health(&self) -> HealthResponse {
@@ -32,26 +38,47 @@ impl super::ApiHandlers {
                 crate::server::models::HealthStatus::Degraded
             },
             algorithm: self.id_generator.get_primary_algorithm().await.to_string(),
+            components: self.local_components().await,
         }
     }
 
+    /// Readiness under the `monitoring.health` policy (default: database
+    /// and cache must not be unhealthy).
     pub async fn ready(&self) -> ReadyResponse {
-        let db_metrics = self.config_service.get_database_metrics().await;
-        let cache_metrics = self.config_service.get_cache_metrics().await;
+        let mut components = self.dependency_components().await;
+        components.extend(self.local_components().await);
 
-        let db_healthy = db_metrics.status == crate::server::models::HealthStatus::Healthy;
-        let cache_healthy = cache_metrics.status == crate::server::models::HealthStatus::Healthy;
-
-        let ready = db_healthy && cache_healthy;
+        let is_healthy = |name: &str| {
+            components
+                .iter()
+                .any(|c| c.name == name && c.status == crate::server::models::HealthStatus::Healthy)
+        };
+        let db_healthy = is_healthy("database");
+        let cache_healthy = is_healthy("cache");
+
+        let failed_components = readiness_failures(&self.health_config, &components);
+        let ready = failed_components.is_empty();
+        let message = if ready {
+            t!("api.success.system_handlers.ready").to_string()
+        } else if failed_components
+            .iter()
+            .all(|name| name == "database" || name == "cache")
+        {
+            t!("api.error.system_handlers.not_ready").to_string()
+        } else {
+            t!(
+                "api.error.system_handlers.not_ready_components",
+                components = failed_components.join(", ")
+            )
+            .to_string()
+        };
         ReadyResponse {
             ready,
             database: db_healthy,
             cache: cache_healthy,
-            message: if ready {
-                t!("api.success.system_handlers.ready").to_string()
-            } else {
-                t!("api.error.system_handlers.not_ready").to_string()
-            },
+            message,
+            components,
+            failed_components,
         }
     }
 
@@ -88,9 +115,46 @@ impl super::ApiHandlers {
             database,
             cache,
             algorithms,
+            segment_capacity: self
+                .capacity_forecaster
+                .is_enabled()
+                .then(|| SegmentCapacitySummary::from(&self.capacity_forecaster.forecast(None))),
+            audit_writer: self
+                .audit_writer_metrics
+                .as_deref()
+                .map(AuditWriterSummary::from),
+            audit_sinks: self
+                .audit_sink_metrics
+                .iter()
+                .map(|m| AuditSinkSummary::from(m.as_ref()))
+                .collect(),
+            tls_certificate: self
+                .tls_certificate_metrics
+                .as_deref()
+                .map(TlsCertificateSummary::from),
         }
     }
 
+    /// Per-workspace / per-biz_tag usage over the requested window.
+    pub fn usage(&self, params: &UsageQueryParams) -> UsageResponse {
+        self.usage_tracker
+            .report(
+                params.window_secs,
+                params.workspace.as_deref(),
+                params.limit,
+            )
+            .into()
+    }
+
+    /// Segment exhaustion forecast per (workspace, biz_tag).
+    pub fn capacity(&self, params: &CapacityQueryParams) -> CapacityResponse {
+        CapacityResponse::from_report(
+            self.capacity_forecaster
+                .forecast(params.workspace.as_deref()),
+            self.capacity_forecaster.is_enabled(),
+        )
+    }
+
     /// Start background key rotation task.
     /// Returns a handle that can be used to stop the task.
     pub fn start_key_rotation_task(
@@ -177,6 +241,7 @@ mod tests {
     use crate::core::algorithm::{
         DegradationManager, HealthStatus as CoreHealthStatus, IdGenerator as CoreIdGenerator,
     };
+    use crate::core::auth::ApiKeyScopes;
     use crate::core::database::{
         ApiKeyInfo, ApiKeyRepository, ApiKeyRole, ApiKeyWithSecret, BizTag,
     };
@@ -347,6 +412,7 @@ mod tests {
             async fn create_workspace(&self, req: CreateWorkspaceRequest) -> crate::core::Result<WorkspaceResponse>;
             async fn list_workspaces(&self) -> crate::core::Result<WorkspaceListResponse>;
             async fn get_workspace(&self, name: &str) -> crate::core::Result<Option<WorkspaceResponse>>;
+            async fn update_workspace(&self, name: &str, request: &crate::core::database::UpdateWorkspaceRequest) -> crate::core::Result<Option<WorkspaceResponse>>;
             async fn create_group(&self, req: CreateGroupRequest) -> crate::core::Result<GroupResponse>;
             async fn list_groups(&self, workspace: &str) -> crate::core::Result<GroupListResponse>;
             async fn get_database_metrics(&self) -> DatabaseMetrics;
@@ -556,6 +622,35 @@ mod tests {
         assert!(!response.cache);
     }
 
+    #[tokio::test]
+    async fn test_ready_false_when_strict_component_degraded() {
+        let mut mock_config = MockSysMockConfigService::new();
+        mock_config
+            .expect_get_database_metrics()
+            .return_once(healthy_db_metrics);
+        mock_config
+            .expect_get_cache_metrics()
+            .return_once(healthy_cache_metrics);
+        let gen = Arc::new(MockIdGenerator::new());
+        gen.get_degradation_manager()
+            .manual_degrade(AlgorithmType::Segment);
+
+        let handlers = super::super::ApiHandlers::new(gen, Arc::new(mock_config))
+            .with_health_config(crate::core::config::HealthConfig {
+                readiness_strict: vec!["degradation".to_string()],
+                ..Default::default()
+            });
+        let response = handlers.ready().await;
+        assert!(!response.ready);
+        assert!(response.database);
+        assert!(response.cache);
+        assert_eq!(response.failed_components, vec!["degradation"]);
+        assert_eq!(
+            response.message,
+            "Not ready: degradation failed the readiness policy"
+        );
+    }
+
     // ===== metrics() =====
 
     #[tokio::test]
@@ -803,6 +898,7 @@ mod tests {
             expires_at: None,
             last_used_at: None,
             created_at: chrono::Utc::now().naive_utc(),
+            scopes: ApiKeyScopes::unrestricted(),
         };
 
         let mut mock_repo = MockSysMockApiKeyRepo::new();
@@ -15,11 +15,12 @@
 //! Workspace / Group management handlers (rule 25 split).
 
 use super::helpers::{map_db_error, map_uuid_error};
+use crate::core::database::WorkspaceStatus;
 use crate::core::{CoreError, Result};
 use crate::server::models::{
-    naive_to_rfc3339, ApiKeyResponse, ApiKeyWithSecretResponse, CreateGroupRequest,
-    CreateWorkspaceRequest, GroupListResponse, GroupResponse, UserApiKeyInfo,
-    WorkspaceListResponse, WorkspaceResponse,
+    naive_to_rfc3339, scopes_to_response, ApiKeyResponse, ApiKeyWithSecretResponse,
+    CreateGroupRequest, CreateWorkspaceRequest, GroupListResponse, GroupResponse,
+    UpdateWorkspaceRequest, UserApiKeyInfo, WorkspaceListResponse, WorkspaceResponse,
 };
 
 impl super::ApiHandlers {
@@ -30,6 +31,9 @@ impl super::ApiHandlers {
             .create_workspace(req.clone())
             .await
             .map_err(map_db_error)?;
+        if let Some(ref guard) = self.generation_guard {
+            guard.invalidate_workspace(&workspace.name);
+        }
 
         let repo = self.api_key_repo.as_ref().ok_or_else(|| {
             CoreError::NotFound(
@@ -48,6 +52,8 @@ impl super::ApiHandlers {
             expires_at: None,
             key_secret: None,
             key_id: None,
+            scopes: None,
+            request_signing: false,
         };
 
         let user_key = repo
@@ -62,6 +68,7 @@ impl super::ApiHandlers {
             status: workspace.status,
             max_groups: workspace.max_groups,
             max_biz_tags: workspace.max_biz_tags,
+            strict_generation: workspace.strict_generation,
             created_at: workspace.created_at,
             updated_at: workspace.updated_at,
             user_api_key: Some(UserApiKeyInfo {
@@ -105,8 +112,9 @@ impl super::ApiHandlers {
             .await
             .map_err(map_db_error)?;
 
+        // 只替换 workspace 的全权限 user key；带作用域的服务 key 保持不变
         for key in existing_keys {
-            if key.role == crate::core::database::ApiKeyRole::User {
+            if key.role == crate::core::database::ApiKeyRole::User && key.scopes.is_unrestricted() {
                 repo.delete_api_key(key.id).await.map_err(map_db_error)?;
             }
         }
@@ -120,6 +128,8 @@ impl super::ApiHandlers {
             expires_at: None,
             key_secret: None,
             key_id: None,
+            scopes: None,
+            request_signing: false,
         };
 
         let user_key = repo
@@ -137,6 +147,9 @@ impl super::ApiHandlers {
                 role: match user_key.key.role {
                     crate::core::database::ApiKeyRole::Admin => "admin".to_string(),
                     crate::core::database::ApiKeyRole::User => "user".to_string(),
+                    crate::core::database::ApiKeyRole::WorkspaceAdmin => {
+                        "workspace_admin".to_string()
+                    }
                     // LOW-1 修复：Anonymous 不会被持久化到数据库，这里只是穷尽匹配。
                     // 如果运行到这里说明数据库被外部直接写入了 Anonymous，返回错误标记。
                     crate::core::database::ApiKeyRole::Anonymous => {
@@ -149,6 +162,7 @@ impl super::ApiHandlers {
                 enabled: user_key.key.enabled,
                 expires_at: user_key.key.expires_at.map(naive_to_rfc3339),
                 created_at: naive_to_rfc3339(user_key.key.created_at),
+                scopes: scopes_to_response(&user_key.key.scopes),
             },
             key_secret: user_key.key_secret,
         })
@@ -167,6 +181,55 @@ impl super::ApiHandlers {
         self.config_service.get_workspace(name).await
     }
 
+    /// Update a Workspace's status, limits or `strict_generation` flag.
+    pub async fn update_workspace(
+        &self,
+        name: &str,
+        req: UpdateWorkspaceRequest,
+    ) -> Result<WorkspaceResponse> {
+        let status = req
+            .status
+            .map(|status| match status.as_str() {
+                "active" => Ok(WorkspaceStatus::Active),
+                "inactive" => Ok(WorkspaceStatus::Inactive),
+                "suspended" => Ok(WorkspaceStatus::Suspended),
+                _ => Err(CoreError::InvalidInput(
+                    t!(
+                        "api.error.handlers.workspace_handlers.invalid_status",
+                        status = status
+                    )
+                    .to_string(),
+                )),
+            })
+            .transpose()?;
+        let core_req = crate::core::database::UpdateWorkspaceRequest {
+            name: None,
+            description: req.description,
+            status,
+            max_groups: req.max_groups,
+            max_biz_tags: req.max_biz_tags,
+            strict_generation: req.strict_generation,
+        };
+
+        let workspace = self
+            .config_service
+            .update_workspace(name, &core_req)
+            .await?
+            .ok_or_else(|| {
+                CoreError::NotFound(
+                    t!(
+                        "api.error.handlers.workspace_handlers.not_found",
+                        name = name
+                    )
+                    .to_string(),
+                )
+            })?;
+        if let Some(ref guard) = self.generation_guard {
+            guard.invalidate_workspace(name);
+        }
+        Ok(workspace)
+    }
+
     /// Create a new Group.
     pub async fn create_group(&self, req: CreateGroupRequest) -> Result<GroupResponse> {
         self.config_service.create_group(req).await
@@ -180,9 +243,10 @@ impl super::ApiHandlers {
 
 #[cfg(test)]
 mod tests {
+    use crate::core::auth::ApiKeyScopes;
     use crate::core::database::{
         ApiKeyInfo, ApiKeyRepository, ApiKeyResponse as CoreApiKeyResponse, ApiKeyRole,
-        ApiKeyWithSecret, BizTag,
+        ApiKeyWithSecret, BizTag, WorkspaceStatus,
     };
     use crate::core::types::AlgorithmType;
     use crate::core::{CoreError, Result};
@@ -216,6 +280,7 @@ mod tests {
             async fn create_workspace(&self, req: CreateWorkspaceRequest) -> crate::core::Result<WorkspaceResponse>;
             async fn list_workspaces(&self) -> crate::core::Result<WorkspaceListResponse>;
             async fn get_workspace(&self, name: &str) -> crate::core::Result<Option<WorkspaceResponse>>;
+            async fn update_workspace(&self, name: &str, request: &crate::core::database::UpdateWorkspaceRequest) -> crate::core::Result<Option<WorkspaceResponse>>;
             async fn create_group(&self, req: CreateGroupRequest) -> crate::core::Result<GroupResponse>;
             async fn list_groups(&self, workspace: &str) -> crate::core::Result<GroupListResponse>;
             async fn get_database_metrics(&self) -> DatabaseMetrics;
@@ -270,6 +335,7 @@ mod tests {
             status: "active".to_string(),
             max_groups: 10,
             max_biz_tags: 100,
+            strict_generation: true,
             created_at: chrono::Utc::now().to_rfc3339(),
             updated_at: chrono::Utc::now().to_rfc3339(),
             user_api_key: None,
@@ -289,6 +355,7 @@ mod tests {
                 enabled: true,
                 expires_at: None,
                 created_at: chrono::Utc::now().naive_utc(),
+                scopes: ApiKeyScopes::unrestricted(),
             },
             key_secret: "test-secret-value-12345".to_string(),
         }
@@ -308,6 +375,7 @@ mod tests {
             expires_at: None,
             last_used_at: None,
             created_at: chrono::Utc::now().naive_utc(),
+            scopes: ApiKeyScopes::unrestricted(),
         }
     }
 
@@ -432,6 +500,70 @@ mod tests {
         }
     }
 
+    // ===== update_workspace =====
+
+    #[tokio::test]
+    async fn test_update_workspace_maps_fields_to_core_request() {
+        let ws = WorkspaceResponse {
+            strict_generation: false,
+            ..make_workspace_response()
+        };
+        let mut mock_config = MockWorkspaceTestService::new();
+        mock_config
+            .expect_update_workspace()
+            .withf(|name, req| {
+                name == "test-workspace"
+                    && req.name.is_none()
+                    && req.status == Some(WorkspaceStatus::Suspended)
+                    && req.strict_generation == Some(false)
+            })
+            .return_once(move |_, _| Ok(Some(ws)));
+
+        let handlers = make_handlers_no_repo(mock_config);
+        let response = handlers
+            .update_workspace(
+                "test-workspace",
+                UpdateWorkspaceRequest {
+                    status: Some("suspended".to_string()),
+                    strict_generation: Some(false),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(!response.strict_generation);
+    }
+
+    #[tokio::test]
+    async fn test_update_workspace_rejects_unknown_status() {
+        // 状态在调用配置服务之前校验，mock 不设期望
+        let handlers = make_handlers_no_repo(MockWorkspaceTestService::new());
+        let result = handlers
+            .update_workspace(
+                "test-workspace",
+                UpdateWorkspaceRequest {
+                    status: Some("archived".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+        assert!(matches!(result, Err(CoreError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_workspace_missing_returns_not_found() {
+        let mut mock_config = MockWorkspaceTestService::new();
+        mock_config
+            .expect_update_workspace()
+            .return_once(|_, _| Ok(None));
+
+        let handlers = make_handlers_no_repo(mock_config);
+        let result = handlers
+            .update_workspace("ghost", UpdateWorkspaceRequest::default())
+            .await;
+        assert!(matches!(result, Err(CoreError::NotFound(_))));
+    }
+
     // ===== regenerate_user_api_key =====
 
     #[tokio::test]
@@ -12,11 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::core::database::ApiKeyRepository;
+use crate::core::algorithm::AuditContext;
+use crate::core::auth::signing::DEFAULT_MAX_CLOCK_SKEW_SECS;
+use crate::core::auth::{
+    ApiKeyScopes, BanManager, ClientCertMapper, JwtValidator, SignedAuthorization,
+};
+use crate::core::config::BanConfig;
+use crate::core::database::{ApiKeyRepository, Ban, BanTargetKind};
+use crate::core::types::CoreError;
+use crate::server::config::tls::TlsConnectInfo;
+use crate::server::handlers::helpers::core_error_to_response;
+use crate::server::middleware::locale::Locale;
+use crate::server::middleware::size_limit::MAX_REQUEST_SIZE;
 use base64::Engine;
-use parking_lot::RwLock;
+use parking_lot::Mutex;
 use sdforge::axum::body::Body;
-use sdforge::axum::extract::State;
+use sdforge::axum::extract::{ConnectInfo, OriginalUri, State};
 use sdforge::axum::http::{Request, StatusCode};
 use sdforge::axum::middleware::Next;
 use sdforge::axum::response::IntoResponse;
@@ -29,20 +40,35 @@ use std::time::{Duration, Instant};
 // Re-export ApiKeyRole locally for use in this module
 pub use crate::core::database::ApiKeyRole;
 
-/// Phase 9 T043 (HIGH H6) — hard cap on the number of distinct IPs
-/// tracked in `auth_failures`. When the map reaches this size, the
-/// oldest entries are evicted to bound memory usage. Prevents an
-/// attacker (especially one able to spoof IPs via the now-fixed
-/// `X-Forwarded-For` issue, H3) from OOMing the process by sending
-/// requests from many distinct source IPs.
-const MAX_TRACKED_AUTH_FAILURE_IPS: usize = 10_000;
+/// 签名请求 nonce 缓存的容量上限；过期条目清理后仍满时拒绝新的签名请求
+const MAX_TRACKED_NONCES: usize = 100_000;
+
+/// 通过认证的调用方。`auth_middleware` 同时写入请求扩展与响应扩展：
+/// 审计中间件位于认证层之外，只能从响应中取回调用方以完成归因。
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthenticatedKey {
+    pub key_id: String,
+    pub role: ApiKeyRole,
+    /// Admin key 为 `None`
+    pub workspace_id: Option<uuid::Uuid>,
+}
 
 #[derive(Clone)]
 pub struct ApiKeyAuth {
     pub(crate) repo: Arc<dyn ApiKeyRepository>,
     pub(crate) enabled: bool,
     trusted_proxies: Vec<IpAddr>,
-    auth_failures: Arc<RwLock<HashMap<String, Vec<Instant>>>>,
+    /// 认证失败封禁（IP 与 key_id）；默认只在进程内生效
+    bans: Arc<BanManager>,
+    /// 签名请求允许的时间戳偏差
+    signature_max_skew: Duration,
+    /// 本实例已使用的 `(key_id, nonce)` 及其过期时刻；跨实例的重放检查
+    /// 经 [`ApiKeyRepository::claim_request_nonce`] 共享
+    seen_nonces: Arc<Mutex<HashMap<(String, String), Instant>>>,
+    /// 配置后接受 `Authorization: Bearer <jwt>`
+    jwt: Option<Arc<JwtValidator>>,
+    /// 配置后，未携带 `Authorization` 的 mTLS 连接按客户端证书认证
+    client_certs: Option<Arc<ClientCertMapper>>,
 }
 
 impl ApiKeyAuth {
@@ -51,10 +77,79 @@ impl ApiKeyAuth {
             repo,
             enabled,
             trusted_proxies: Vec::new(),
-            auth_failures: Arc::new(RwLock::new(HashMap::new())),
+            bans: Arc::new(BanManager::new(BanConfig::default())),
+            signature_max_skew: Duration::from_secs(DEFAULT_MAX_CLOCK_SKEW_SECS),
+            seen_nonces: Arc::new(Mutex::new(HashMap::new())),
+            jwt: None,
+            client_certs: None,
         }
     }
 
+    /// 启用 JWT / OIDC bearer token 认证（`auth.jwt`），与 API key 并存
+    pub fn with_jwt(mut self, validator: Arc<JwtValidator>) -> Self {
+        self.jwt = Some(validator);
+        self
+    }
+
+    pub fn accepts_bearer_tokens(&self) -> bool {
+        self.jwt.is_some()
+    }
+
+    /// 校验 JWT 并映射为调用方；`key_id` 为 `jwt:<sub>`，用于审计归因。
+    /// 失败时返回用于日志的原因标识。
+    pub async fn validate_bearer_token(
+        &self,
+        token: &str,
+    ) -> Result<(AuthenticatedKey, ApiKeyScopes), &'static str> {
+        let validator = self.jwt.as_ref().ok_or("jwt_not_configured")?;
+        let principal = validator.validate(token).await?;
+        Ok((
+            AuthenticatedKey {
+                key_id: format!("jwt:{}", principal.subject),
+                role: principal.role,
+                workspace_id: principal.workspace_id,
+            },
+            principal.scopes,
+        ))
+    }
+
+    /// 启用客户端证书认证（`tls.client_identities`），证书链已由 TLS 层校验
+    pub fn with_client_cert_mapper(mut self, mapper: Arc<ClientCertMapper>) -> Self {
+        self.client_certs = Some(mapper);
+        self
+    }
+
+    pub fn accepts_client_certs(&self) -> bool {
+        self.client_certs.is_some()
+    }
+
+    /// 把已校验的客户端叶子证书（DER）映射为调用方；`key_id` 为
+    /// `cert:<subject>`，用于审计归因。失败时返回用于日志的原因标识。
+    pub fn authenticate_client_cert(
+        &self,
+        certificate_der: &[u8],
+    ) -> Result<(AuthenticatedKey, ApiKeyScopes), &'static str> {
+        let mapper = self
+            .client_certs
+            .as_ref()
+            .ok_or("client_cert_auth_not_configured")?;
+        let identity = mapper.resolve(certificate_der)?;
+        Ok((
+            AuthenticatedKey {
+                key_id: format!("cert:{}", identity.subject),
+                role: identity.role,
+                workspace_id: identity.workspace_id,
+            },
+            identity.scopes,
+        ))
+    }
+
+    /// 设置签名请求允许的时间戳偏差（`auth.signature_max_skew_seconds`）
+    pub fn with_signature_max_skew(mut self, max_skew: Duration) -> Self {
+        self.signature_max_skew = max_skew;
+        self
+    }
+
     /// Phase 9 T043 (HIGH H3) — set the list of trusted proxy IPs.
     /// Requests whose direct peer IP appears in this list will have
     /// their `X-Forwarded-For` / `X-Real-IP` headers honored when
@@ -66,65 +161,51 @@ impl ApiKeyAuth {
         self
     }
 
-    fn check_auth_failure_rate(&self, client_ip: &str) -> bool {
-        let now = Instant::now();
-        let mut failures_map = self.auth_failures.write();
-        let failures = failures_map.entry(client_ip.to_string()).or_default();
-
-        // 移除 5 分钟前的记录
-        failures.retain(|t| now.duration_since(*t) < Duration::from_secs(300));
-
-        // Phase 9 T043 (HIGH H6) — evict empty entries so a long-lived
-        // process does not accumulate one dead `Vec` per unique IP ever
-        // seen. Without this, an attacker rotating IPs can OOM the
-        // process even after the per-IP failure windows expire.
-        if failures.is_empty() {
-            failures_map.remove(client_ip);
-            return true;
-        }
-
-        // 如果 5 分钟内失败超过 10 次，则阻止
-        if failures.len() >= 10 {
-            tracing::warn!(
-                client_ip = %client_ip,
-                failure_count = failures.len(),
-                "{}",
-                t!("log.server.middleware.api_key_auth.too_many_auth_failures")
-            );
-            return false;
-        }
-
-        // Phase 9 T043 (HIGH H6) — bound the map size. If we are at
-        // capacity, drop the entry we just inserted (it has zero
-        // failures) plus a sweep of any other empty entries. This
-        // favors keeping actively-failing IPs over fresh ones.
-        if failures_map.len() > MAX_TRACKED_AUTH_FAILURE_IPS {
-            failures_map.retain(|_, v| !v.is_empty());
-            if failures_map.len() > MAX_TRACKED_AUTH_FAILURE_IPS {
-                // Still over capacity — clear the map entirely. This
-                // is a last-resort safety valve; under normal load the
-                // per-IP 5-minute window keeps the map small.
-                failures_map.clear();
-            }
-        }
+    /// 使用共享的封禁管理器（持久化封禁、跨实例同步）
+    pub fn with_ban_manager(mut self, bans: Arc<BanManager>) -> Self {
+        self.bans = bans;
+        self
+    }
 
-        true
+    pub fn ban_manager(&self) -> &Arc<BanManager> {
+        &self.bans
     }
 
-    fn record_auth_failure(&self, client_ip: &str) {
-        let now = Instant::now();
-        let mut failures_map = self.auth_failures.write();
-        let failures = failures_map.entry(client_ip.to_string()).or_default();
-        failures.push(now);
+    /// 记录认证失败；`key_id` 为请求中出现的 key_id（凭证格式无法解析时为 `None`）
+    async fn record_auth_failure(&self, client_ip: &str, key_id: Option<&str>) {
+        self.bans.record_failure(BanTargetKind::Ip, client_ip).await;
+        if let Some(key_id) = key_id.filter(|key_id| !key_id.is_empty()) {
+            self.bans.record_failure(BanTargetKind::KeyId, key_id).await;
+        }
     }
 
-    fn too_many_requests_response(&self) -> Response {
+    /// IP 或 key_id 处于封禁中；`Retry-After` 为剩余封禁秒数
+    fn too_many_requests_response(&self, ban: &Ban) -> Response {
+        tracing::warn!(
+            event = "auth_banned",
+            ban_id = %ban.id,
+            target_type = %ban.target_kind,
+            level = ban.level,
+            "{}",
+            t!("log.server.middleware.api_key_auth.too_many_auth_failures")
+        );
+        let retry_after = (ban.expires_at - chrono::Utc::now().naive_utc())
+            .num_seconds()
+            .max(1);
         let response = sdforge::axum::Json(serde_json::json!({
             "code": 429,
             "message": "Too many authentication attempts. Please try again later."
         }))
         .into_response();
-        (StatusCode::TOO_MANY_REQUESTS, response).into_response()
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(
+                sdforge::axum::http::header::RETRY_AFTER,
+                retry_after.to_string(),
+            )],
+            response,
+        )
+            .into_response()
     }
 
     fn get_client_ip(&self, req: &Request<Body>) -> Option<String> {
@@ -147,6 +228,120 @@ impl ApiKeyAuth {
             .flatten()
     }
 
+    /// 同 [`validate_key`](Self::validate_key)，并返回 key 的作用域
+    pub async fn validate_key_with_scopes(
+        &self,
+        key_id: &str,
+        key_secret: &str,
+    ) -> Option<(Option<uuid::Uuid>, ApiKeyRole, ApiKeyScopes)> {
+        self.repo
+            .validate_api_key_with_scopes(key_id, key_secret)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    /// 校验 `NEBULA-HMAC-SHA256` 签名请求：时间戳须在允许偏差内，签名覆盖
+    /// 方法、原始路径（含 query）与请求体，`(key_id, nonce)` 在窗口内只能使用
+    /// 一次。任何失败都返回 `InvalidApiKeySignature`，具体原因只写日志。
+    pub async fn verify_signed_request(
+        &self,
+        authorization: &str,
+        method: &str,
+        path_and_query: &str,
+        body: &[u8],
+    ) -> crate::core::Result<(String, Option<uuid::Uuid>, ApiKeyRole, ApiKeyScopes)> {
+        let reject = |reason: &'static str, key_id: &str| {
+            let key_id_prefix = key_id.chars().take(8).collect::<String>();
+            tracing::warn!(
+                event = "auth_failure",
+                reason = reason,
+                key_id_prefix = %key_id_prefix,
+                "Signed request rejected"
+            );
+            CoreError::InvalidApiKeySignature
+        };
+
+        let signed = SignedAuthorization::parse(authorization)
+            .map_err(|_| reject("invalid_signature_format", ""))?;
+
+        let skew = chrono::Utc::now().timestamp().abs_diff(signed.timestamp);
+        if skew > self.signature_max_skew.as_secs() {
+            return Err(reject("signature_timestamp_out_of_window", &signed.key_id));
+        }
+
+        let key = self
+            .repo
+            .get_signing_key(&signed.key_id)
+            .await
+            .ok()
+            .flatten()
+            .ok_or_else(|| reject("signing_key_unavailable", &signed.key_id))?;
+        // 轮换宽限期内旧签名密钥仍可用
+        let verified = signed.verify(&key.signing_key, method, path_and_query, body)
+            || key
+                .previous_signing_key
+                .as_deref()
+                .is_some_and(|previous| signed.verify(previous, method, path_and_query, body));
+        if !verified {
+            return Err(reject("signature_mismatch", &signed.key_id));
+        }
+
+        // 签名有效后才登记 nonce，伪造请求无法占满缓存
+        if !self.remember_nonce(&signed.key_id, &signed.nonce).await {
+            return Err(reject("nonce_replayed", &signed.key_id));
+        }
+
+        Ok((signed.key_id, key.workspace_id, key.role, key.scopes))
+    }
+
+    /// 登记 nonce；窗口内已在本实例或其他实例出现、缓存已满或无法登记到数据库时
+    /// 返回 `false`
+    async fn remember_nonce(&self, key_id: &str, nonce: &str) -> bool {
+        // 时间戳可在当前时间前后各偏差一个窗口，nonce 需保留两个窗口
+        let retention = self.signature_max_skew * 2;
+        {
+            let now = Instant::now();
+            let mut seen = self.seen_nonces.lock();
+            if seen.len() >= MAX_TRACKED_NONCES {
+                seen.retain(|_, expires_at| *expires_at > now);
+                if seen.len() >= MAX_TRACKED_NONCES {
+                    tracing::warn!(event = "nonce_cache_full", size = seen.len());
+                    return false;
+                }
+            }
+
+            let entry = (key_id.to_string(), nonce.to_string());
+            if seen.get(&entry).is_some_and(|expires_at| *expires_at > now) {
+                return false;
+            }
+            seen.insert(entry, now + retention);
+        }
+
+        let now = chrono::Utc::now().naive_utc();
+        let expires_at = chrono::Duration::from_std(retention)
+            .ok()
+            .and_then(|retention| now.checked_add_signed(retention))
+            .unwrap_or(chrono::NaiveDateTime::MAX);
+        match self
+            .repo
+            .claim_request_nonce(key_id, nonce, expires_at)
+            .await
+        {
+            Ok(claimed) => claimed,
+            Err(e) => {
+                // 无法确认其他实例未使用过该 nonce，按重放处理
+                tracing::warn!(
+                    event = "nonce_store_unavailable",
+                    key_id = %key_id,
+                    error = %e,
+                    "Failed to record request nonce"
+                );
+                false
+            }
+        }
+    }
+
     pub async fn auth_middleware(&self, mut req: Request<Body>, next: Next) -> Response {
         let start_time = Instant::now();
         let path = req.uri().path().to_string();
@@ -183,6 +378,7 @@ impl ApiKeyAuth {
             // 权限低于 User，只能访问公开端点（health/ready/metrics），
             // 其他端点由 `router.rs::verify_user_role` 拒绝。
             req.extensions_mut().insert(ApiKeyRole::Anonymous);
+            req.extensions_mut().insert(ApiKeyScopes::unrestricted());
 
             // 记录审计日志（异步，不阻塞请求）
             tokio::spawn(async move {
@@ -200,109 +396,101 @@ impl ApiKeyAuth {
             return next.run(req).await;
         }
 
-        // 检查认证失败速率
-        if !self.check_auth_failure_rate(&client_ip) {
-            return self.too_many_requests_response();
+        // 来源 IP 被封禁时直接拒绝
+        if let Some(ban) = self.bans.active_ban(BanTargetKind::Ip, &client_ip) {
+            return self.too_many_requests_response(&ban);
         }
 
         let auth_header = req.headers().get("authorization").cloned();
 
-        if let Some(header) = auth_header {
-            if let Ok(value) = header.to_str() {
-                // Support both "Basic base64(key_id:key_secret)" and "ApiKey key_id:key_secret"
-                let (key_id, key_secret) = if let Some(credentials) = value.strip_prefix("Basic ") {
-                    if let Ok(decoded) =
-                        base64::engine::general_purpose::STANDARD.decode(credentials)
-                    {
-                        if let Ok(cred_str) = String::from_utf8(decoded) {
-                            let parts: Vec<&str> = cred_str.splitn(2, ':').collect();
-                            if parts.len() == 2 {
-                                (parts[0].to_string(), parts[1].to_string())
-                            } else {
-                                tracing::warn!(
-                                    event = "auth_failure",
-                                    reason = "invalid_basic_format",
-                                    client_ip = %client_ip,
-                                    "{}",
-                                    t!("log.server.middleware.api_key_auth.invalid_basic_format")
-                                );
-                                return self.unauthorized_response(&client_ip);
-                            }
-                        } else {
-                            tracing::warn!(
-                                event = "auth_failure",
-                                reason = "invalid_encoding",
-                                client_ip = %client_ip,
-                                "{}",
-                                t!("log.server.middleware.api_key_auth.invalid_base64_encoding")
-                            );
-                            return self.unauthorized_response(&client_ip);
-                        }
-                    } else {
+        if let Some(value) = auth_header
+            .as_ref()
+            .and_then(|header| header.to_str().ok())
+            .filter(|value| SignedAuthorization::is_signed(value))
+        {
+            return self
+                .signed_auth_middleware(req, next, value, &client_ip, start_time)
+                .await;
+        }
+
+        if let Some(token) = auth_header
+            .as_ref()
+            .and_then(|header| header.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .filter(|_| self.accepts_bearer_tokens())
+        {
+            return match self.validate_bearer_token(token).await {
+                Ok((principal, scopes)) => {
+                    self.run_authenticated(req, next, principal, scopes, &client_ip, start_time)
+                        .await
+                }
+                Err(reason) => {
+                    tracing::warn!(
+                        event = "auth_failure",
+                        reason = reason,
+                        client_ip = %client_ip,
+                        "Bearer token rejected"
+                    );
+                    self.unauthorized_response(&client_ip, None).await
+                }
+            };
+        }
+
+        if auth_header.is_none() && self.accepts_client_certs() {
+            let certificate = req
+                .extensions()
+                .get::<ConnectInfo<TlsConnectInfo>>()
+                .and_then(|ConnectInfo(info)| info.peer_certificate().cloned());
+            if let Some(certificate) = certificate {
+                return match self.authenticate_client_cert(&certificate) {
+                    Ok((principal, scopes)) => {
+                        self.run_authenticated(req, next, principal, scopes, &client_ip, start_time)
+                            .await
+                    }
+                    Err(reason) => {
                         tracing::warn!(
                             event = "auth_failure",
-                            reason = "base64_decode_failed",
+                            reason = reason,
                             client_ip = %client_ip,
-                            "{}",
-                            t!("log.server.middleware.api_key_auth.base64_decode_failed")
+                            "Client certificate rejected"
                         );
-                        return self.unauthorized_response(&client_ip);
+                        self.unauthorized_response(&client_ip, None).await
                     }
-                } else if let Some(api_key) = value.strip_prefix("ApiKey ") {
-                    let parts: Vec<&str> = api_key.splitn(2, ':').collect();
-                    if parts.len() == 2 {
-                        (parts[0].to_string(), parts[1].to_string())
-                    } else {
+                };
+            }
+        }
+
+        if let Some(header) = auth_header {
+            if let Ok(value) = header.to_str() {
+                let (key_id, key_secret) = match parse_credentials(value) {
+                    Ok(credentials) => credentials,
+                    Err(reason) => {
                         tracing::warn!(
                             event = "auth_failure",
-                            reason = "invalid_apikey_format",
+                            reason = reason,
                             client_ip = %client_ip,
                             "{}",
-                            t!("log.server.middleware.api_key_auth.invalid_apikey_format")
+                            credential_failure_message(reason)
                         );
-                        return self.unauthorized_response(&client_ip);
+                        return self.unauthorized_response(&client_ip, None).await;
                     }
-                } else {
-                    tracing::warn!(
-                        event = "auth_failure",
-                        reason = "unsupported_format",
-                        client_ip = %client_ip,
-                        "{}",
-                        t!("log.server.middleware.api_key_auth.unsupported_auth_format")
-                    );
-                    return self.unauthorized_response(&client_ip);
                 };
 
-                // Validate input lengths to prevent empty credentials
-                if key_id.is_empty() || key_secret.is_empty() {
-                    tracing::warn!(
-                        event = "auth_failure",
-                        reason = "empty_credentials",
-                        client_ip = %client_ip,
-                        "{}",
-                        t!("log.server.middleware.api_key_auth.empty_credentials")
-                    );
-                    return self.unauthorized_response(&client_ip);
+                if let Some(ban) = self.bans.active_ban(BanTargetKind::KeyId, &key_id) {
+                    return self.too_many_requests_response(&ban);
                 }
 
-                if let Some((workspace_id, role)) = self.validate_key(&key_id, &key_secret).await {
-                    req.extensions_mut().insert(workspace_id);
-                    req.extensions_mut().insert(role.clone());
-
-                    // Log successful authentication
-                    let duration = start_time.elapsed().as_millis() as u64;
-                    let key_id_prefix = key_id.chars().take(8).collect::<String>();
-                    tracing::info!(
-                        event = "auth_success",
-                        key_id_prefix = %key_id_prefix,
-                        role = ?role,
-                        client_ip = %client_ip,
-                        duration_ms = duration,
-                        "{}",
-                        t!("log.server.middleware.api_key_auth.authentication_successful")
-                    );
-
-                    return next.run(req).await;
+                if let Some((workspace_id, role, scopes)) =
+                    self.validate_key_with_scopes(&key_id, &key_secret).await
+                {
+                    let principal = AuthenticatedKey {
+                        key_id,
+                        role,
+                        workspace_id,
+                    };
+                    return self
+                        .run_authenticated(req, next, principal, scopes, &client_ip, start_time)
+                        .await;
                 } else {
                     // Log auth failure with key_id prefix (masked for security)
                     let key_id_prefix = key_id.chars().take(8).collect::<String>();
@@ -314,6 +502,7 @@ impl ApiKeyAuth {
                         "{}",
                         t!("log.server.middleware.api_key_auth.invalid_credentials")
                     );
+                    return self.unauthorized_response(&client_ip, Some(&key_id)).await;
                 }
             }
         } else {
@@ -329,11 +518,115 @@ impl ApiKeyAuth {
 
         // Return 401 for both unknown routes and missing auth to avoid information disclosure
         // This prevents attackers from discovering which API endpoints exist
-        self.unauthorized_response(&client_ip)
+        self.unauthorized_response(&client_ip, None).await
     }
 
-    fn unauthorized_response(&self, client_ip: &str) -> Response {
-        self.record_auth_failure(client_ip);
+    /// 签名请求：缓冲请求体后按原始 URI 校验签名，请求体原样交给下游
+    async fn signed_auth_middleware(
+        &self,
+        req: Request<Body>,
+        next: Next,
+        authorization: &str,
+        client_ip: &str,
+        start_time: Instant,
+    ) -> Response {
+        // 嵌套路由内 `uri()` 已去掉 `/api/v1` 前缀，签名覆盖的是客户端请求的完整路径
+        let path_and_query = req
+            .extensions()
+            .get::<OriginalUri>()
+            .map(|uri| &uri.0)
+            .unwrap_or(req.uri())
+            .path_and_query()
+            .map(|pq| pq.as_str().to_string())
+            .unwrap_or_default();
+        let locale = req
+            .extensions()
+            .get::<Locale>()
+            .copied()
+            .unwrap_or_default();
+        let key_id = SignedAuthorization::parse(authorization)
+            .ok()
+            .map(|signed| signed.key_id);
+        if let Some(ban) = key_id
+            .as_deref()
+            .and_then(|key_id| self.bans.active_ban(BanTargetKind::KeyId, key_id))
+        {
+            return self.too_many_requests_response(&ban);
+        }
+        let (parts, body) = req.into_parts();
+        let verified = match sdforge::axum::body::to_bytes(body, MAX_REQUEST_SIZE).await {
+            Ok(body) => self
+                .verify_signed_request(authorization, parts.method.as_str(), &path_and_query, &body)
+                .await
+                .map(|verified| (verified, body)),
+            Err(_) => {
+                tracing::warn!(
+                    event = "auth_failure",
+                    reason = "signed_body_unreadable",
+                    client_ip = %client_ip,
+                    "Signed request body could not be read"
+                );
+                Err(CoreError::InvalidApiKeySignature)
+            }
+        };
+
+        match verified {
+            Ok(((key_id, workspace_id, role, scopes), body)) => {
+                let req = Request::from_parts(parts, Body::from(body));
+                let principal = AuthenticatedKey {
+                    key_id,
+                    role,
+                    workspace_id,
+                };
+                self.run_authenticated(req, next, principal, scopes, client_ip, start_time)
+                    .await
+            }
+            Err(e) => {
+                self.record_auth_failure(client_ip, key_id.as_deref()).await;
+                core_error_to_response(&e, locale).into_response()
+            }
+        }
+    }
+
+    async fn run_authenticated(
+        &self,
+        mut req: Request<Body>,
+        next: Next,
+        principal: AuthenticatedKey,
+        scopes: ApiKeyScopes,
+        client_ip: &str,
+        start_time: Instant,
+    ) -> Response {
+        req.extensions_mut().insert(principal.workspace_id);
+        req.extensions_mut().insert(principal.role.clone());
+        req.extensions_mut().insert(scopes);
+
+        // Log successful authentication
+        let duration = start_time.elapsed().as_millis() as u64;
+        let key_id_prefix = principal.key_id.chars().take(8).collect::<String>();
+        tracing::info!(
+            event = "auth_success",
+            key_id_prefix = %key_id_prefix,
+            role = ?principal.role,
+            client_ip = %client_ip,
+            duration_ms = duration,
+            "{}",
+            t!("log.server.middleware.api_key_auth.authentication_successful")
+        );
+
+        req.extensions_mut().insert(principal.clone());
+        // 请求内产生的审计事件（含核心层事件）归因到该 key
+        let context = AuditContext {
+            actor: Some(principal.key_id.clone()),
+            ..AuditContext::current().unwrap_or_default()
+        };
+        let mut response = context.scope(next.run(req)).await;
+        response.extensions_mut().insert(principal);
+        response
+    }
+
+    async fn unauthorized_response(&self, client_ip: &str, key_id: Option<&str>) -> Response {
+        self.record_auth_failure(client_ip, key_id).await;
         let response = sdforge::axum::Json(serde_json::json!({
             "code": 401,
             "message": "Invalid or missing API key"
@@ -343,6 +636,42 @@ impl ApiKeyAuth {
     }
 }
 
+/// 解析 `Authorization` 取值，支持 `Basic base64(key_id:key_secret)` 与
+/// `ApiKey key_id:key_secret`。失败时返回用于日志的原因标识。
+pub(crate) fn parse_credentials(value: &str) -> Result<(String, String), &'static str> {
+    let (key_id, key_secret) = if let Some(credentials) = value.strip_prefix("Basic ") {
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(credentials)
+            .map_err(|_| "base64_decode_failed")?;
+        let cred_str = String::from_utf8(decoded).map_err(|_| "invalid_encoding")?;
+        let (key_id, key_secret) = cred_str.split_once(':').ok_or("invalid_basic_format")?;
+        (key_id.to_string(), key_secret.to_string())
+    } else if let Some(api_key) = value.strip_prefix("ApiKey ") {
+        let (key_id, key_secret) = api_key.split_once(':').ok_or("invalid_apikey_format")?;
+        (key_id.to_string(), key_secret.to_string())
+    } else {
+        return Err("unsupported_format");
+    };
+
+    // Validate input lengths to prevent empty credentials
+    if key_id.is_empty() || key_secret.is_empty() {
+        return Err("empty_credentials");
+    }
+    Ok((key_id, key_secret))
+}
+
+fn credential_failure_message(reason: &str) -> String {
+    match reason {
+        "invalid_basic_format" => t!("log.server.middleware.api_key_auth.invalid_basic_format"),
+        "invalid_encoding" => t!("log.server.middleware.api_key_auth.invalid_base64_encoding"),
+        "base64_decode_failed" => t!("log.server.middleware.api_key_auth.base64_decode_failed"),
+        "invalid_apikey_format" => t!("log.server.middleware.api_key_auth.invalid_apikey_format"),
+        "empty_credentials" => t!("log.server.middleware.api_key_auth.empty_credentials"),
+        _ => t!("log.server.middleware.api_key_auth.unsupported_auth_format"),
+    }
+    .to_string()
+}
+
 pub async fn admin_required_middleware(req: Request<Body>, next: Next) -> Response {
     if let Some(role) = req.extensions().get::<ApiKeyRole>() {
         tracing::debug!(event = "admin_check", role = ?role, "{}", t!("log.server.middleware.api_key_auth.checking_admin_role"));
@@ -365,6 +694,30 @@ pub async fn admin_required_middleware(req: Request<Body>, next: Next) -> Respon
     (StatusCode::FORBIDDEN, response).into_response()
 }
 
+/// API key 管理端点的角色校验：全局 Admin 或 workspace 管理员可通过；
+/// workspace 管理员能操作哪些 key 由各 handler 按其 workspace 再收窄。
+pub async fn key_manager_required_middleware(req: Request<Body>, next: Next) -> Response {
+    match req.extensions().get::<ApiKeyRole>() {
+        Some(ApiKeyRole::Admin | ApiKeyRole::WorkspaceAdmin) => return next.run(req).await,
+        Some(role) => {
+            tracing::debug!(event = "key_manager_check", role = ?role, "key management denied");
+        }
+        None => {
+            tracing::warn!(
+                event = "key_manager_check",
+                "ApiKeyRole extension missing; denying key management request"
+            );
+        }
+    }
+
+    let response = sdforge::axum::Json(serde_json::json!({
+        "code": 403,
+        "message": "Admin or workspace admin access required"
+    }))
+    .into_response();
+    (StatusCode::FORBIDDEN, response).into_response()
+}
+
 pub async fn auth_middleware_fn(
     State(auth): State<Arc<ApiKeyAuth>>,
     req: Request<Body>,
@@ -376,9 +729,11 @@ pub async fn auth_middleware_fn(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::auth::signing::derive_signing_key;
+    use crate::core::auth::RequestSigner;
     use crate::core::database::{
-        ApiKeyInfo, ApiKeyRepository, ApiKeyResponse, ApiKeyRole, ApiKeyWithSecret,
-        CreateApiKeyRequest,
+        ApiKeyInfo, ApiKeyRepository, ApiKeyResponse, ApiKeyRole, ApiKeySigningKey,
+        ApiKeyWithSecret, CreateApiKeyRequest,
     };
     use crate::core::types::Result;
     use async_trait::async_trait;
@@ -394,6 +749,8 @@ mod tests {
     #[derive(Clone)]
     struct MockApiKeyRepo {
         keys: std::collections::HashMap<String, (String, ApiKeyRole)>,
+        /// 模拟数据库中的 nonce 登记，多个 `ApiKeyAuth` 实例共享
+        nonces: Mutex<std::collections::HashSet<(String, String)>>,
     }
 
     impl MockApiKeyRepo {
@@ -419,6 +776,7 @@ mod tests {
                     enabled: true,
                     expires_at: None,
                     created_at: chrono::Utc::now().naive_utc(),
+                    scopes: crate::core::auth::ApiKeyScopes::unrestricted(),
                 },
                 key_secret: "mock_secret".to_string(),
             })
@@ -495,6 +853,30 @@ mod tests {
         async fn get_keys_older_than(&self, _age_threshold_days: i64) -> Result<Vec<ApiKeyInfo>> {
             Ok(vec![])
         }
+
+        async fn claim_request_nonce(
+            &self,
+            key_id: &str,
+            nonce: &str,
+            _: chrono::NaiveDateTime,
+        ) -> Result<bool> {
+            Ok(self
+                .nonces
+                .lock()
+                .insert((key_id.to_string(), nonce.to_string())))
+        }
+
+        async fn get_signing_key(&self, key_id: &str) -> Result<Option<ApiKeySigningKey>> {
+            // 只有 user-key 有签名密钥，模拟轮换前创建的旧 key 没有；
+            // user-key 处于轮换宽限期，旧密钥为 user-old-secret
+            Ok((key_id == "user-key").then(|| ApiKeySigningKey {
+                workspace_id: Some(Uuid::nil()),
+                role: ApiKeyRole::User,
+                scopes: crate::core::auth::ApiKeyScopes::unrestricted(),
+                signing_key: derive_signing_key("user-secret").to_vec(),
+                previous_signing_key: Some(derive_signing_key("user-old-secret").to_vec()),
+            }))
+        }
     }
 
     #[tokio::test]
@@ -513,7 +895,10 @@ mod tests {
             ),
         );
 
-        let repo = MockApiKeyRepo { keys: mock_keys };
+        let repo = MockApiKeyRepo {
+            keys: mock_keys,
+            nonces: Default::default(),
+        };
         let auth = ApiKeyAuth::new(Arc::new(repo), true);
 
         // Test valid user key
@@ -550,7 +935,10 @@ mod tests {
                 ApiKeyRole::Admin,
             ),
         );
-        MockApiKeyRepo { keys: mock_keys }
+        MockApiKeyRepo {
+            keys: mock_keys,
+            nonces: Default::default(),
+        }
     }
 
     fn build_test_router(auth: Arc<ApiKeyAuth>) -> Router {
@@ -751,6 +1139,52 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
     }
 
+    #[tokio::test]
+    async fn test_auth_middleware_banned_key_id_returns_429_until_lifted() {
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let bans = Arc::new(BanManager::new(BanConfig {
+            failure_threshold: 1,
+            ..BanConfig::default()
+        }));
+        let auth = Arc::new(ApiKeyAuth::new(repo, true).with_ban_manager(bans.clone()));
+        let router = build_test_router(auth);
+        let ban = bans
+            .record_failure(BanTargetKind::KeyId, "user-key")
+            .await
+            .unwrap();
+
+        // 封禁针对 key_id：正确的密钥也被拒绝，同一 IP 上的其他 key 不受影响
+        let resp = router
+            .clone()
+            .oneshot(make_request(Some(&basic_auth_header(
+                "user-key",
+                "user-secret",
+            ))))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(resp.headers().contains_key("retry-after"));
+        let resp = router
+            .clone()
+            .oneshot(make_request(Some(&basic_auth_header(
+                "admin-key",
+                "admin-secret",
+            ))))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        bans.lift(ban.id, "admin-key").await.unwrap();
+        let resp = router
+            .oneshot(make_request(Some(&basic_auth_header(
+                "user-key",
+                "user-secret",
+            ))))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
     #[tokio::test]
     async fn test_auth_middleware_empty_authorization_value_returns_401() {
         let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
@@ -816,6 +1250,28 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::FORBIDDEN);
     }
 
+    #[tokio::test]
+    async fn test_key_manager_required_allows_admin_and_workspace_admin() {
+        let router = Router::new().route("/test", get(|| async { "ok" })).layer(
+            sdforge::axum::middleware::from_fn(key_manager_required_middleware),
+        );
+        for (role, expected) in [
+            (ApiKeyRole::Admin, StatusCode::OK),
+            (ApiKeyRole::WorkspaceAdmin, StatusCode::OK),
+            (ApiKeyRole::User, StatusCode::FORBIDDEN),
+            (ApiKeyRole::Anonymous, StatusCode::FORBIDDEN),
+        ] {
+            let resp = router
+                .clone()
+                .oneshot(make_request_with_role(role.clone()))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), expected, "role {:?}", role);
+        }
+        let resp = router.oneshot(make_request(None)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
     // ========== Role extension injection tests ==========
 
     fn build_role_check_router(auth: Arc<ApiKeyAuth>) -> Router {
@@ -879,6 +1335,21 @@ mod tests {
         assert_eq!(body, "Admin");
     }
 
+    #[tokio::test]
+    async fn test_auth_success_exposes_principal_on_response() {
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let auth = Arc::new(ApiKeyAuth::new(repo, true));
+        let router = build_role_check_router(auth);
+        let header = basic_auth_header("admin-key", "admin-secret");
+        let resp = router.oneshot(make_request(Some(&header))).await.unwrap();
+        let principal = resp
+            .extensions()
+            .get::<AuthenticatedKey>()
+            .expect("principal should be attached to the response");
+        assert_eq!(principal.key_id, "admin-key");
+        assert_eq!(principal.role, ApiKeyRole::Admin);
+    }
+
     #[tokio::test]
     async fn test_auth_valid_api_key_injects_user_role() {
         let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
@@ -933,4 +1404,290 @@ mod tests {
         assert!(workspace_id.is_some());
         assert_eq!(workspace_id.unwrap(), Uuid::nil());
     }
+
+    #[tokio::test]
+    async fn test_validate_key_with_scopes_defaults_to_unrestricted() {
+        // MockApiKeyRepo 未覆盖 validate_api_key_with_scopes，走 trait 默认实现
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let auth = ApiKeyAuth::new(repo, true);
+        let (_, role, scopes) = auth
+            .validate_key_with_scopes("user-key", "user-secret")
+            .await
+            .expect("valid key");
+        assert_eq!(role, ApiKeyRole::User);
+        assert!(scopes.is_unrestricted());
+    }
+
+    #[test]
+    fn test_parse_credentials_reports_failure_reason() {
+        let basic = base64::engine::general_purpose::STANDARD.encode("id:secret");
+        assert_eq!(
+            parse_credentials(&format!("Basic {}", basic)),
+            Ok(("id".to_string(), "secret".to_string()))
+        );
+        assert_eq!(
+            parse_credentials("ApiKey id:sec:ret"),
+            Ok(("id".to_string(), "sec:ret".to_string()))
+        );
+        assert_eq!(parse_credentials("ApiKey id"), Err("invalid_apikey_format"));
+        assert_eq!(
+            parse_credentials("ApiKey :secret"),
+            Err("empty_credentials")
+        );
+        assert_eq!(parse_credentials("Basic !!"), Err("base64_decode_failed"));
+        assert_eq!(parse_credentials("Bearer token"), Err("unsupported_format"));
+    }
+
+    // ========== signed request tests ==========
+
+    const SIGNED_BODY: &[u8] = br#"{"workspace":"acme"}"#;
+
+    fn signed_request(authorization: &str, body: &'static [u8]) -> Request<Body> {
+        Request::builder()
+            .uri("/test?x=1")
+            .method("POST")
+            .header("authorization", authorization)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn signed_router(auth: Arc<ApiKeyAuth>) -> Router {
+        // 下游 handler 回显请求体，确认签名校验后请求体原样传递
+        Router::new()
+            .route(
+                "/test",
+                sdforge::axum::routing::post(|body: String| async move { body }),
+            )
+            .layer(from_fn_with_state(auth, auth_middleware_fn))
+    }
+
+    fn sign_now(key_id: &str, secret: &str, nonce: &str) -> String {
+        RequestSigner::new(key_id, secret)
+            .sign_at(
+                "POST",
+                "/test?x=1",
+                SIGNED_BODY,
+                chrono::Utc::now().timestamp(),
+                nonce,
+            )
+            .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_signed_request_is_authenticated_and_keeps_body() {
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let router = signed_router(Arc::new(ApiKeyAuth::new(repo, true)));
+        let header =
+            RequestSigner::new("user-key", "user-secret").sign("POST", "/test?x=1", SIGNED_BODY);
+        let resp = router
+            .oneshot(signed_request(&header, SIGNED_BODY))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.extensions()
+                .get::<AuthenticatedKey>()
+                .map(|k| k.role.clone()),
+            Some(ApiKeyRole::User)
+        );
+        let body = sdforge::axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], SIGNED_BODY);
+    }
+
+    #[tokio::test]
+    async fn test_signed_request_with_previous_secret_is_authenticated_during_grace() {
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let router = signed_router(Arc::new(ApiKeyAuth::new(repo, true)));
+        let previous = sign_now("user-key", "user-old-secret", "grace-nonce");
+        let unknown = sign_now("user-key", "never-issued-secret", "unknown-nonce");
+
+        let resp = router
+            .clone()
+            .oneshot(signed_request(&previous, SIGNED_BODY))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = router
+            .oneshot(signed_request(&unknown, SIGNED_BODY))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_signed_request_replayed_nonce_returns_401() {
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let router = signed_router(Arc::new(ApiKeyAuth::new(repo, true)));
+        let header = sign_now("user-key", "user-secret", "replayed-nonce");
+
+        let first = router
+            .clone()
+            .oneshot(signed_request(&header, SIGNED_BODY))
+            .await
+            .unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        let replay = router
+            .oneshot(signed_request(&header, SIGNED_BODY))
+            .await
+            .unwrap();
+        assert_eq!(replay.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_signed_request_replayed_on_another_instance_returns_401() {
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let first = signed_router(Arc::new(ApiKeyAuth::new(repo.clone(), true)));
+        let second = signed_router(Arc::new(ApiKeyAuth::new(repo, true)));
+        let header = sign_now("user-key", "user-secret", "shared-nonce");
+
+        let resp = first
+            .oneshot(signed_request(&header, SIGNED_BODY))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let replay = second
+            .oneshot(signed_request(&header, SIGNED_BODY))
+            .await
+            .unwrap();
+        assert_eq!(replay.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_signed_request_rejections() {
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let auth = ApiKeyAuth::new(repo, true).with_signature_max_skew(Duration::from_secs(60));
+        let stale = RequestSigner::new("user-key", "user-secret")
+            .sign_at(
+                "POST",
+                "/test?x=1",
+                SIGNED_BODY,
+                chrono::Utc::now().timestamp() - 120,
+                "stale-nonce",
+            )
+            .to_string();
+
+        let cases = [
+            // 超出时间窗口
+            (stale, SIGNED_BODY),
+            // 请求体被篡改
+            (
+                sign_now("user-key", "user-secret", "tamper-nonce"),
+                br#"{"workspace":"evil"}"#.as_slice(),
+            ),
+            // 密钥错误
+            (
+                sign_now("user-key", "wrong-secret", "wrong-nonce"),
+                SIGNED_BODY,
+            ),
+            // 没有签名密钥的旧 key
+            (
+                sign_now("admin-key", "admin-secret", "legacy-nonce"),
+                SIGNED_BODY,
+            ),
+        ];
+        let router = signed_router(Arc::new(auth));
+        for (header, body) in cases {
+            let resp = router
+                .clone()
+                .oneshot(signed_request(&header, body))
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::UNAUTHORIZED, "{}", header);
+        }
+    }
+
+    // ========== bearer token tests ==========
+
+    #[tokio::test]
+    async fn test_bearer_token_is_authenticated_with_jwt_principal() {
+        use crate::core::auth::jwt::testing::{validator, write_jwks, TestKey};
+        use crate::core::config::JwtConfig;
+
+        let key = TestKey::generate("k1");
+        let file = tempfile::NamedTempFile::new().unwrap();
+        write_jwks(&file, &[&key]);
+        let jwt = Arc::new(validator(&file, JwtConfig::default()).await);
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let router = build_test_router(Arc::new(ApiKeyAuth::new(repo, true).with_jwt(jwt)));
+
+        let token = key.sign(&serde_json::json!({
+            "sub": "svc-orders",
+            "exp": chrono::Utc::now().timestamp() + 300,
+            "workspace_id": Uuid::nil().to_string(),
+        }));
+        let resp = router
+            .clone()
+            .oneshot(make_request(Some(&format!("Bearer {}", token))))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.extensions().get::<AuthenticatedKey>(),
+            Some(&AuthenticatedKey {
+                key_id: "jwt:svc-orders".to_string(),
+                role: ApiKeyRole::User,
+                workspace_id: Some(Uuid::nil()),
+            })
+        );
+
+        let forged = TestKey::generate("k1").sign(&serde_json::json!({
+            "sub": "svc-orders",
+            "exp": chrono::Utc::now().timestamp() + 300,
+            "workspace_id": Uuid::nil().to_string(),
+        }));
+        let resp = router
+            .oneshot(make_request(Some(&format!("Bearer {}", forged))))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_client_certificate_is_mapped_to_caller() {
+        use crate::core::config::ClientIdentityMapping;
+
+        let mapper = ClientCertMapper::new(&[ClientIdentityMapping {
+            pattern: "orders-svc".to_string(),
+            role: "user".to_string(),
+            workspace_id: Some(Uuid::nil()),
+            scopes: Vec::new(),
+        }])
+        .unwrap();
+        let repo = Arc::new(make_mock_repo()) as Arc<dyn ApiKeyRepository>;
+        let router = build_test_router(Arc::new(
+            ApiKeyAuth::new(repo, true).with_client_cert_mapper(Arc::new(mapper)),
+        ));
+        let request_with_cert = |name: &str| {
+            let cert = rcgen::generate_simple_self_signed(vec![name.to_string()]).unwrap();
+            let mut req = make_request(None);
+            req.extensions_mut().insert(ConnectInfo(TlsConnectInfo {
+                remote_addr: "127.0.0.1:40000".parse().unwrap(),
+                peer_certificates: Some(Arc::new(vec![cert.cert.der().clone()])),
+            }));
+            req
+        };
+
+        let resp = router
+            .clone()
+            .oneshot(request_with_cert("orders-svc"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.extensions().get::<AuthenticatedKey>(),
+            Some(&AuthenticatedKey {
+                key_id: "cert:orders-svc".to_string(),
+                role: ApiKeyRole::User,
+                workspace_id: Some(Uuid::nil()),
+            })
+        );
+
+        let resp = router
+            .oneshot(request_with_cert("billing-svc"))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
 }
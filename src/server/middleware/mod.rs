@@ -19,14 +19,29 @@
 
 pub mod api_key_auth;
 pub mod locale;
+pub mod request_id;
+pub mod scope;
 pub mod size_limit;
+pub mod trace_context;
 pub(crate) mod utils;
 
 // Re-export ApiKeyRole for use in router.rs (unified with core::database::ApiKeyRole)
 pub use crate::core::database::ApiKeyRole;
 
 // Re-export API key auth components (backward compatibility)
-pub use api_key_auth::{admin_required_middleware, auth_middleware_fn, ApiKeyAuth};
+pub use api_key_auth::{
+    admin_required_middleware, auth_middleware_fn, key_manager_required_middleware, ApiKeyAuth,
+    AuthenticatedKey,
+};
 
 // Re-export locale middleware components (Phase 8 T040)
 pub use locale::{locale_middleware, Locale};
+
+// Re-export request ID middleware
+pub use request_id::{current_request_id, request_id_middleware, RequestId, REQUEST_ID_HEADER};
+
+// Re-export API key scope enforcement
+pub use scope::{required_scope, scope_required_middleware};
+
+// Re-export trace context propagation middleware
+pub use trace_context::{trace_context_middleware, HeaderExtractor};
@@ -0,0 +1,164 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Request ID middleware.
+//!
+//! 为每个请求分配 `x-request-id`（沿用调用方传入的合法值，否则生成 UUID），
+//! 写入请求扩展 [`RequestId`] 与响应头，并在请求处理期间通过 [`AuditContext`]
+//! 暴露给 [`current_request_id`]，使 `ApiErrorResponse.request_id`、审计事件
+//! （包括核心层在请求内产生的事件）与响应头使用同一个 ID。
+
+use crate::core::algorithm::AuditContext;
+use sdforge::axum::body::Body;
+use sdforge::axum::extract::Request;
+use sdforge::axum::http::HeaderValue;
+use sdforge::axum::middleware::Next;
+use sdforge::axum::response::Response;
+
+/// 请求 ID 头（已在 CORS `EXPOSED_HEADERS` 中暴露）
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 调用方传入的请求 ID 最大长度
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// 当前请求的 ID（请求扩展）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+/// 当前请求的 ID；不在 [`request_id_middleware`] 作用域内时返回 `None`
+pub fn current_request_id() -> Option<String> {
+    AuditContext::current().and_then(|ctx| ctx.request_id)
+}
+
+/// 只接受可安全写入日志与响应头的调用方 ID
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+pub async fn request_id_middleware(mut req: Request<Body>, next: Next) -> Response {
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+    let context = AuditContext {
+        actor: None,
+        request_id: Some(request_id.clone()),
+    };
+    let mut response = context.scope(next.run(req)).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::models::{ApiErrorCode, ApiErrorResponse};
+    use sdforge::axum::http::StatusCode;
+    use sdforge::axum::response::IntoResponse;
+    use sdforge::axum::routing::get;
+    use sdforge::axum::{Extension, Router};
+    use sdforge::tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/echo",
+                get(|Extension(id): Extension<RequestId>| async move { id.0 }),
+            )
+            .route(
+                "/fail",
+                get(|| async {
+                    let body = ApiErrorResponse::new(ApiErrorCode::InternalError, "boom".into());
+                    (StatusCode::INTERNAL_SERVER_ERROR, sdforge::axum::Json(body)).into_response()
+                }),
+            )
+            .layer(sdforge::axum::middleware::from_fn(request_id_middleware))
+    }
+
+    async fn body_string(response: Response) -> String {
+        let bytes = sdforge::axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_id_reuses_valid_caller_id() {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/echo")
+                    .header(REQUEST_ID_HEADER, "req-123.abc")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "req-123.abc");
+        assert_eq!(body_string(response).await, "req-123.abc");
+    }
+
+    #[tokio::test]
+    async fn test_request_id_replaces_invalid_caller_id() {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/echo")
+                    .header(REQUEST_ID_HEADER, "bad id\twith spaces")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let header = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(uuid::Uuid::parse_str(&header).is_ok());
+        assert_eq!(body_string(response).await, header);
+    }
+
+    #[tokio::test]
+    async fn test_error_response_carries_request_id() {
+        let response = app()
+            .oneshot(Request::builder().uri("/fail").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let header = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body: ApiErrorResponse = serde_json::from_str(&body_string(response).await).unwrap();
+        assert_eq!(body.request_id, header);
+    }
+
+    #[test]
+    fn test_current_request_id_outside_scope_is_none() {
+        assert_eq!(current_request_id(), None);
+    }
+}
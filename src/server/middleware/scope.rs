@@ -0,0 +1,218 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API key scope enforcement middleware.
+//!
+//! 位于认证层内侧：按方法与路由求出所需作用域（[`required_scope`]），与
+//! 认证层写入的 [`ApiKeyScopes`] 扩展比对，不满足时返回 403 并记录
+//! `AuthorizationDenied` 审计事件。ID 生成的作用域依赖请求体中的
+//! group / biz_tag，由 handler 自行校验。
+
+use crate::core::algorithm::DynAuditLogger;
+use crate::core::auth::{ApiKeyScopes, Scope, ScopeResource};
+use crate::server::handlers::helpers::scope_denied_response;
+use crate::server::middleware::locale::Locale;
+use crate::server::middleware::AuthenticatedKey;
+use crate::server::models::ErrorResponse;
+use sdforge::axum::body::Body;
+use sdforge::axum::extract::{Request, State};
+use sdforge::axum::http::{Method, StatusCode};
+use sdforge::axum::middleware::Next;
+use sdforge::axum::response::{IntoResponse, Response};
+use sdforge::axum::Json;
+
+/// 请求所需的作用域；`None` 表示路由层不限制（公开端点、ID 生成）
+pub fn required_scope(method: &Method, path: &str) -> Option<Scope> {
+    // 去掉 `/api/v1` 前缀，兼容后续版本
+    let route = path
+        .strip_prefix("/api/")
+        .and_then(|rest| rest.find('/').map(|i| &rest[i..]))
+        .unwrap_or(path);
+    let segments: Vec<&str> = route.split('/').filter(|s| !s.is_empty()).collect();
+    let read = matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+    let access = |resource| {
+        if read {
+            Scope::read(resource)
+        } else {
+            Scope::write(resource)
+        }
+    };
+
+    match segments.as_slice() {
+        ["generate", ..] => None,
+        ["parse"] => Some(Scope::Parse),
+        ["config", ..] => Some(access(ScopeResource::Config)),
+        ["workspaces", ..] => Some(access(ScopeResource::Workspaces)),
+        ["groups", ..] => Some(access(ScopeResource::Groups)),
+        ["biz-tags", ..] => Some(access(ScopeResource::BizTags)),
+        ["audit", ..] => Some(access(ScopeResource::Audit)),
+        ["api-keys", ..] => Some(access(ScopeResource::ApiKeys)),
+        ["admin", ..] => Some(access(ScopeResource::Admin)),
+        _ => None,
+    }
+}
+
+/// 记录作用域拒绝并构造 403 响应；HTTP handler 与中间件共用
+pub(crate) async fn deny_scope(
+    audit_logger: &DynAuditLogger,
+    workspace_id: Option<uuid::Uuid>,
+    resource: String,
+    scope: &Scope,
+    locale: Locale,
+) -> (StatusCode, Json<ErrorResponse>) {
+    tracing::warn!(
+        event = "scope_denied",
+        resource = %resource,
+        required_scope = %scope,
+        "API key lacks required scope"
+    );
+    audit_logger
+        .log_scope_denied(
+            workspace_id.map(|id| id.to_string()),
+            resource,
+            scope.to_string(),
+            None,
+        )
+        .await;
+    scope_denied_response(scope, locale)
+}
+
+pub async fn scope_required_middleware(
+    State(audit_logger): State<DynAuditLogger>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let Some(required) = required_scope(req.method(), req.uri().path()) else {
+        return next.run(req).await;
+    };
+    // 认证禁用时没有 AuthenticatedKey，作用域扩展为不受限
+    let allowed = req
+        .extensions()
+        .get::<ApiKeyScopes>()
+        .is_none_or(|scopes| scopes.allows(&required));
+    if allowed {
+        return next.run(req).await;
+    }
+
+    let workspace_id = req
+        .extensions()
+        .get::<AuthenticatedKey>()
+        .and_then(|key| key.workspace_id);
+    let locale = req
+        .extensions()
+        .get::<Locale>()
+        .copied()
+        .unwrap_or_default();
+    let resource = format!("{} {}", req.method(), req.uri().path());
+    deny_scope(&audit_logger, workspace_id, resource, &required, locale)
+        .await
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::algorithm::AuditEventType;
+    use crate::server::audit::testing::AuditCapture;
+    use sdforge::axum::middleware::{from_fn, from_fn_with_state};
+    use sdforge::axum::routing::post;
+    use sdforge::axum::Router;
+    use sdforge::tower::ServiceExt;
+    use std::sync::Arc;
+
+    /// 模拟认证层：写入给定作用域后进入作用域中间件
+    fn scoped_router(scopes: &'static [&'static str], capture: AuditCapture) -> Router {
+        let audit_logger: DynAuditLogger = Arc::new(capture);
+        Router::new()
+            .route("/biz-tags", post(|| async { "created" }))
+            .layer(from_fn_with_state(audit_logger, scope_required_middleware))
+            .layer(from_fn(
+                move |mut req: Request<Body>, next: Next| async move {
+                    req.extensions_mut()
+                        .insert(ApiKeyScopes::parse(scopes).unwrap());
+                    next.run(req).await
+                },
+            ))
+    }
+
+    fn post_biz_tag() -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri("/biz-tags")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_missing_scope_is_denied_and_audited() {
+        let capture = AuditCapture::new();
+        let response = scoped_router(&["biz_tags:read"], capture.clone())
+            .oneshot(post_biz_tag())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let event = capture.expect_one(&AuditEventType::AuthorizationDenied, "scope_denied");
+        assert_eq!(event.resource, "POST /biz-tags");
+        assert_eq!(
+            event.details,
+            Some(serde_json::json!({"required_scope": "biz_tags:write"}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_scope_is_allowed() {
+        let capture = AuditCapture::new();
+        let response = scoped_router(&["biz_tags:write"], capture.clone())
+            .oneshot(post_biz_tag())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(capture.is_empty());
+    }
+
+    #[test]
+    fn test_required_scope_maps_routes_by_method() {
+        let cases = [
+            ("POST", "/api/v1/generate", None),
+            ("POST", "/api/v1/generate/batch", None),
+            ("POST", "/api/v1/parse", Some("parse")),
+            ("GET", "/api/v1/config", Some("config:read")),
+            ("POST", "/api/v1/config/reload", Some("config:write")),
+            ("GET", "/api/v1/workspaces/acme", Some("workspaces:read")),
+            ("PATCH", "/api/v1/workspaces/acme", Some("workspaces:write")),
+            ("GET", "/api/v1/groups", Some("groups:read")),
+            ("DELETE", "/api/v1/biz-tags/1", Some("biz_tags:write")),
+            ("GET", "/api/v1/audit", Some("audit:read")),
+            ("POST", "/api/v1/api-keys", Some("api_keys:write")),
+            (
+                "PUT",
+                "/api/v1/admin/degradation/fallback-chain",
+                Some("admin:write"),
+            ),
+            ("GET", "/groups", Some("groups:read")),
+            ("GET", "/api/v1/", None),
+        ];
+        for (method, path, expected) in cases {
+            let method: Method = method.parse().unwrap();
+            assert_eq!(
+                required_scope(&method, path).map(|s| s.to_string()),
+                expected.map(str::to_string),
+                "{} {}",
+                method,
+                path
+            );
+        }
+    }
+}
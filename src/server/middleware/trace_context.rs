@@ -0,0 +1,113 @@
+// Copyright © 2026 Kirky.X
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! W3C Trace Context propagation middleware.
+//!
+//! Opens one server span per HTTP request and, when the caller sent a
+//! `traceparent` header, parents it to the caller's span so the request
+//! joins the upstream trace. gRPC requests reuse [`HeaderExtractor`] over
+//! the tonic metadata (see `server::grpc`).
+//!
+//! When `monitoring.tracing_enabled` is off no OpenTelemetry layer is
+//! installed, the span is a plain `tracing` span and the parent
+//! assignment is a no-op.
+
+use crate::core::monitoring::telemetry::set_remote_parent;
+use opentelemetry::propagation::Extractor;
+use sdforge::axum::body::Body;
+use sdforge::axum::extract::Request;
+use sdforge::axum::http::HeaderMap;
+use sdforge::axum::middleware::Next;
+use sdforge::axum::response::Response;
+use tracing::Instrument;
+
+/// OpenTelemetry extractor over HTTP headers (also used for gRPC metadata
+/// via `MetadataMap::as_ref()`).
+pub struct HeaderExtractor<'a>(pub &'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+/// Wrap the request in an `http.request` server span linked to the
+/// inbound `traceparent`.
+pub async fn trace_context_middleware(req: Request<Body>, next: Next) -> Response {
+    let span = tracing::info_span!(
+        "http.request",
+        otel.kind = "server",
+        otel.name = %format!("{} {}", req.method(), req.uri().path()),
+        http.request.method = %req.method(),
+        url.path = %req.uri().path(),
+        http.response.status_code = tracing::field::Empty,
+    );
+    set_remote_parent(&span, &HeaderExtractor(req.headers()));
+
+    let response = next.run(req).instrument(span.clone()).await;
+    span.record("http.response.status_code", response.status().as_u16());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdforge::axum::http::{HeaderValue, StatusCode};
+    use sdforge::axum::routing::get;
+    use sdforge::axum::Router;
+    use sdforge::tower::ServiceExt;
+
+    #[test]
+    fn test_header_extractor_reads_traceparent() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "traceparent",
+            HeaderValue::from_static("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+        );
+        let extractor = HeaderExtractor(&headers);
+        assert_eq!(
+            extractor.get("traceparent"),
+            Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+        );
+        assert_eq!(extractor.keys(), vec!["traceparent"]);
+        assert_eq!(extractor.get("tracestate"), None);
+    }
+
+    #[tokio::test]
+    async fn test_trace_context_middleware_passes_request_through() {
+        let app = Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(sdforge::axum::middleware::from_fn(trace_context_middleware));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/ping")
+                    .header(
+                        "traceparent",
+                        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
@@ -26,7 +26,9 @@
 //! All four copies now delegate to [`get_client_ip`] below, which
 //! enforces the `trusted_proxies` check uniformly.
 
+use crate::server::config::tls::TlsConnectInfo;
 use sdforge::axum::body::Body;
+use sdforge::axum::extract::ConnectInfo;
 use sdforge::axum::http::Request;
 use std::net::{IpAddr, SocketAddr};
 
@@ -46,7 +48,16 @@ use std::net::{IpAddr, SocketAddr};
 /// limiting or auth-failure tracking. An empty `trusted_proxies` slice
 /// disables header-based IP discovery entirely (default).
 pub fn get_client_ip(req: &Request<Body>, trusted_proxies: &[IpAddr]) -> Option<String> {
-    let connection_ip = req.extensions().get::<SocketAddr>().map(|addr| addr.ip());
+    // Connections accepted by `TlsListener` carry the peer address in `TlsConnectInfo`.
+    let connection_ip = req
+        .extensions()
+        .get::<SocketAddr>()
+        .map(|addr| addr.ip())
+        .or_else(|| {
+            req.extensions()
+                .get::<ConnectInfo<TlsConnectInfo>>()
+                .map(|ConnectInfo(info)| info.remote_addr.ip())
+        });
 
     if let Some(conn_ip) = connection_ip {
         if trusted_proxies.contains(&conn_ip) {
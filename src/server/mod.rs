@@ -28,6 +28,7 @@ pub mod auth;
 // Users should only use types re-exported in lib.rs
 pub mod audit;
 pub mod config;
+pub mod generation_guard;
 pub mod handlers;
 pub mod middleware;
 pub mod models;
@@ -103,10 +103,28 @@ pub struct BatchGenerateResponse {
     pub timestamp: String,
 }
 
+/// Health of one component in the `/health` and `/ready` reports
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ComponentHealth {
+    /// Component name (see `monitoring.health` for the full list)
+    pub name: String,
+    pub status: HealthStatus,
+    /// When the underlying state was last checked (RFC3339)
+    pub last_checked: String,
+    /// Time spent probing the component in microseconds
+    pub latency_us: u64,
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct HealthResponse {
+    /// Whether the ID generator can serve requests
     pub status: HealthStatus,
     pub algorithm: String,
+    /// In-process components (no network I/O); dependencies such as the
+    /// database are reported by `/ready`
+    #[serde(default)]
+    pub components: Vec<ComponentHealth>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -115,6 +133,18 @@ pub struct ReadyResponse {
     pub database: bool,
     pub cache: bool,
     pub message: String,
+    #[serde(default)]
+    pub components: Vec<ComponentHealth>,
+    /// Components that violated the readiness policy
+    #[serde(default)]
+    pub failed_components: Vec<String>,
+}
+
+/// Liveness probe: the process is up and serving HTTP
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LiveResponse {
+    pub alive: bool,
+    pub uptime_seconds: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -287,7 +317,9 @@ impl ApiErrorResponse {
             code: code.to_string(),
             message,
             details: None,
-            request_id: uuid::Uuid::new_v4().to_string(),
+            // 与响应头 x-request-id 及审计事件一致；中间件作用域外生成新 ID
+            request_id: crate::server::middleware::current_request_id()
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
             timestamp: chrono::Utc::now().timestamp_millis(),
         }
     }
@@ -336,6 +368,123 @@ pub struct MetricsResponse {
     pub database: DatabaseMetrics,
     pub cache: CacheMetrics,
     pub algorithms: Vec<AlgorithmMetrics>,
+    /// 号段容量汇总（`monitoring.capacity.enabled = false` 时为 `None`）。
+    /// 仅含计数与最短耗尽时间，不暴露租户名；明细见 `/api/v1/admin/capacity`。
+    #[serde(default)]
+    pub segment_capacity: Option<SegmentCapacitySummary>,
+    /// 审计文件写入队列与轮转指标（未配置 `audit.log_path` 时为 `None`）。
+    #[serde(default)]
+    pub audit_writer: Option<AuditWriterSummary>,
+    /// 外部审计 sink（syslog / webhook / file）的投递指标
+    #[serde(default)]
+    pub audit_sinks: Vec<AuditSinkSummary>,
+    /// 服务端 TLS 证书有效期与热加载计数（未启用 TLS 时为 `None`）
+    #[serde(default)]
+    pub tls_certificate: Option<TlsCertificateSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TlsCertificateSummary {
+    /// 证书 notAfter（Unix 秒）
+    pub not_after: i64,
+    /// 距离过期的秒数，已过期为负数
+    pub seconds_until_expiry: i64,
+    pub reloads_total: u64,
+    pub reload_failures_total: u64,
+}
+
+impl From<&crate::server::config::tls::TlsCertificateMetrics> for TlsCertificateSummary {
+    fn from(metrics: &crate::server::config::tls::TlsCertificateMetrics) -> Self {
+        use std::sync::atomic::Ordering::Relaxed;
+        Self {
+            not_after: metrics.not_after.load(Relaxed),
+            seconds_until_expiry: metrics.seconds_until_expiry().unwrap_or_default(),
+            reloads_total: metrics.reloads_total.load(Relaxed),
+            reload_failures_total: metrics.reload_failures_total.load(Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditWriterSummary {
+    pub capacity: usize,
+    /// 队列满时的策略：block / drop_oldest / spill
+    pub overflow_policy: String,
+    pub depth: u64,
+    pub enqueued_total: u64,
+    pub dropped_total: u64,
+    pub spilled_total: u64,
+    pub blocked_total: u64,
+    pub rotations_total: u64,
+    pub compressed_total: u64,
+    pub retention_deleted_total: u64,
+}
+
+impl From<&crate::server::audit::AuditWriterMetrics> for AuditWriterSummary {
+    fn from(metrics: &crate::server::audit::AuditWriterMetrics) -> Self {
+        use std::sync::atomic::Ordering::Relaxed;
+        Self {
+            capacity: metrics.capacity,
+            overflow_policy: metrics.overflow_policy.to_string(),
+            depth: metrics.depth.load(Relaxed),
+            enqueued_total: metrics.enqueued_total.load(Relaxed),
+            dropped_total: metrics.dropped_total.load(Relaxed),
+            spilled_total: metrics.spilled_total.load(Relaxed),
+            blocked_total: metrics.blocked_total.load(Relaxed),
+            rotations_total: metrics.rotations_total.load(Relaxed),
+            compressed_total: metrics.compressed_total.load(Relaxed),
+            retention_deleted_total: metrics.retention_deleted_total.load(Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditSinkSummary {
+    pub sink: String,
+    pub capacity: usize,
+    /// 队列满时的策略：drop_newest / drop_oldest
+    pub overflow_policy: String,
+    pub depth: u64,
+    pub delivered_total: u64,
+    pub failed_total: u64,
+    pub dropped_total: u64,
+}
+
+impl From<&crate::server::audit::AuditSinkMetrics> for AuditSinkSummary {
+    fn from(metrics: &crate::server::audit::AuditSinkMetrics) -> Self {
+        use std::sync::atomic::Ordering::Relaxed;
+        Self {
+            sink: metrics.name.to_string(),
+            capacity: metrics.capacity,
+            overflow_policy: metrics.overflow_policy.to_string(),
+            depth: metrics.depth.load(Relaxed),
+            delivered_total: metrics.delivered_total.load(Relaxed),
+            failed_total: metrics.failed_total.load(Relaxed),
+            dropped_total: metrics.dropped_total.load(Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SegmentCapacitySummary {
+    pub tracked_biz_tags: usize,
+    pub warning: usize,
+    pub critical: usize,
+    pub exhausted: usize,
+    pub min_seconds_to_exhaustion: Option<u64>,
+}
+
+impl From<&crate::core::monitoring::CapacityReport> for SegmentCapacitySummary {
+    fn from(report: &crate::core::monitoring::CapacityReport) -> Self {
+        use crate::core::monitoring::CapacityStatus;
+        Self {
+            tracked_biz_tags: report.forecasts.len(),
+            warning: report.count_by_status(CapacityStatus::Warning),
+            critical: report.count_by_status(CapacityStatus::Critical),
+            exhausted: report.count_by_status(CapacityStatus::Exhausted),
+            min_seconds_to_exhaustion: report.min_seconds_to_exhaustion(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -688,6 +837,11 @@ pub fn datetime_to_rfc3339(dt: chrono::DateTime<chrono::FixedOffset>) -> String
     dt.to_rfc3339()
 }
 
+/// Shared utility: API key scopes as returned by the admin API (`None` = unrestricted)
+pub fn scopes_to_response(scopes: &crate::core::auth::ApiKeyScopes) -> Option<Vec<String>> {
+    (!scopes.is_unrestricted()).then(|| scopes.to_strings())
+}
+
 // ========== API Key Models ==========
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
@@ -705,6 +859,21 @@ pub struct CreateApiKeyRequest {
     pub rate_limit: Option<i32>,
 
     pub expires_at: Option<String>, // RFC3339 format
+
+    /// 作用域，如 `["generate:orders/*", "parse"]`；省略时 key 不受作用域限制
+    #[validate(length(min = 1, max = 32))]
+    pub scopes: Option<Vec<String>>,
+
+    /// 开启请求签名（`NEBULA-HMAC-SHA256`）；默认关闭，关闭时服务端不保存签名密钥
+    #[serde(default)]
+    pub request_signing: bool,
+}
+
+/// Query params for `POST /api/v1/api-keys/{key_id}/rotate`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RotateApiKeyParams {
+    /// 开启或关闭请求签名；省略时保持 key 当前的选择
+    pub request_signing: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -719,6 +888,8 @@ pub struct ApiKeyResponse {
     pub enabled: bool,
     pub expires_at: Option<String>,
     pub created_at: String,
+    /// `None`：不受作用域限制
+    pub scopes: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -739,6 +910,52 @@ pub struct RevokeApiKeyResponse {
     pub message: String,
 }
 
+/// A key flagged by the lifecycle policy (`GET /api/v1/api-keys/attention`)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyAttentionInfo {
+    pub id: String,
+    pub key_id: String,
+    pub name: String,
+    pub role: String,
+    pub workspace_id: Option<String>,
+    /// expired | expiring_soon | unused | rotation_due
+    pub reason: String,
+    /// The next lifecycle check disables this key
+    pub auto_disable: bool,
+    /// The lifecycle check rotates this key when it first reports it
+    pub auto_rotate: bool,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub created_at: String,
+}
+
+impl From<crate::core::auth::KeyAttention> for ApiKeyAttentionInfo {
+    fn from(attention: crate::core::auth::KeyAttention) -> Self {
+        Self {
+            id: attention.id.to_string(),
+            key_id: attention.key_id,
+            name: attention.name,
+            role: attention.role.to_string(),
+            workspace_id: attention.workspace_id.map(|id| id.to_string()),
+            reason: attention.reason.to_string(),
+            auto_disable: attention.auto_disable,
+            auto_rotate: attention.auto_rotate,
+            expires_at: attention.expires_at.map(naive_to_rfc3339),
+            last_used_at: attention.last_used_at.map(naive_to_rfc3339),
+            created_at: naive_to_rfc3339(attention.created_at),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyAttentionResponse {
+    pub max_key_age_days: u64,
+    pub expiry_warning_days: u64,
+    pub disable_unused_days: u64,
+    pub keys: Vec<ApiKeyAttentionInfo>,
+    pub timestamp: String,
+}
+
 // ========== Workspace Models ==========
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
@@ -755,6 +972,23 @@ pub struct CreateWorkspaceRequest {
     pub max_biz_tags: Option<i32>,
 }
 
+/// `PATCH /api/v1/workspaces/{name}`：只更新提供的字段
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateWorkspaceRequest {
+    pub description: Option<String>,
+
+    /// `active` / `inactive` / `suspended`
+    pub status: Option<String>,
+
+    #[validate(range(min = 1, max = 1000))]
+    pub max_groups: Option<i32>,
+
+    #[validate(range(min = 1, max = 10000))]
+    pub max_biz_tags: Option<i32>,
+
+    pub strict_generation: Option<bool>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UserApiKeyInfo {
     pub key_id: String,
@@ -770,6 +1004,8 @@ pub struct WorkspaceResponse {
     pub status: String,
     pub max_groups: i32,
     pub max_biz_tags: i32,
+    /// 严格生成模式：生成 ID 要求 workspace 为 active 且 biz_tag 已注册
+    pub strict_generation: bool,
     pub created_at: String,
     pub updated_at: String,
     pub user_api_key: Option<UserApiKeyInfo>,
@@ -815,6 +1051,369 @@ pub struct GroupListResponse {
     pub total: u64,
 }
 
+// ========== Capacity Models ==========
+
+/// Query params for `/api/v1/admin/capacity`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CapacityQueryParams {
+    /// Only forecast this workspace
+    pub workspace: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SegmentCapacityInfo {
+    pub workspace: String,
+    pub biz_tag: String,
+    pub current_max_id: u64,
+    pub remaining_ids: u64,
+    pub utilization: f64,
+    pub burn_rate_per_sec: f64,
+    pub seconds_to_exhaustion: Option<u64>,
+    pub projected_exhaustion_at: Option<String>,
+    /// exhausted | critical | warning | ok | idle
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CapacityResponse {
+    pub enabled: bool,
+    pub window_secs: u64,
+    pub id_ceiling: u64,
+    pub warning_threshold_secs: u64,
+    pub critical_threshold_secs: u64,
+    pub forecasts: Vec<SegmentCapacityInfo>,
+    pub timestamp: String,
+}
+
+impl CapacityResponse {
+    pub fn from_report(report: crate::core::monitoring::CapacityReport, enabled: bool) -> Self {
+        Self {
+            enabled,
+            window_secs: report.window_secs,
+            id_ceiling: report.id_ceiling,
+            warning_threshold_secs: report.warning_threshold_secs,
+            critical_threshold_secs: report.critical_threshold_secs,
+            forecasts: report
+                .forecasts
+                .into_iter()
+                .map(|f| SegmentCapacityInfo {
+                    workspace: f.workspace,
+                    biz_tag: f.biz_tag,
+                    current_max_id: f.current_max_id,
+                    remaining_ids: f.remaining_ids,
+                    utilization: f.utilization,
+                    burn_rate_per_sec: f.burn_rate_per_sec,
+                    seconds_to_exhaustion: f.seconds_to_exhaustion,
+                    projected_exhaustion_at: f.projected_exhaustion_at_secs.and_then(|secs| {
+                        chrono::DateTime::from_timestamp(i64::try_from(secs).ok()?, 0)
+                            .map(|dt| dt.to_rfc3339())
+                    }),
+                    status: f.status.to_string(),
+                })
+                .collect(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+// ========== Usage Models ==========
+
+/// Query params for `/api/v1/admin/usage`
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UsageQueryParams {
+    /// Query window in seconds (default: `monitoring.usage.default_window_secs`,
+    /// capped at `monitoring.usage.retention_secs`)
+    pub window_secs: Option<u64>,
+    /// Only report this workspace
+    pub workspace: Option<String>,
+    /// Maximum number of top workspaces / biz tags returned
+    #[serde(default = "default_usage_limit")]
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: usize,
+}
+
+fn default_usage_limit() -> usize {
+    20
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkspaceUsageInfo {
+    pub workspace: String,
+    pub biz_tag_count: usize,
+    pub total_generated: u64,
+    pub window_generated: u64,
+    pub window_failed: u64,
+    pub generation_rate_per_sec: f64,
+    pub segment_burn_rate_per_sec: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BizTagUsageInfo {
+    pub workspace: String,
+    pub biz_tag: String,
+    pub total_requests: u64,
+    pub total_generated: u64,
+    pub total_failed: u64,
+    pub current_qps: u64,
+    pub window_generated: u64,
+    pub window_failed: u64,
+    pub generation_rate_per_sec: f64,
+    pub window_segment_ids: u64,
+    pub segment_burn_rate_per_sec: f64,
+    pub last_segment_end: u64,
+    pub last_seen: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UsageResponse {
+    pub window_secs: u64,
+    pub tracked_keys: usize,
+    pub max_tracked_keys: usize,
+    pub evicted_keys: u64,
+    pub top_workspaces: Vec<WorkspaceUsageInfo>,
+    pub biz_tags: Vec<BizTagUsageInfo>,
+    pub timestamp: String,
+}
+
+impl From<crate::core::monitoring::UsageReport> for UsageResponse {
+    fn from(report: crate::core::monitoring::UsageReport) -> Self {
+        Self {
+            window_secs: report.window_secs,
+            tracked_keys: report.tracked_keys,
+            max_tracked_keys: report.max_tracked_keys,
+            evicted_keys: report.evicted_keys,
+            top_workspaces: report
+                .top_workspaces
+                .into_iter()
+                .map(|w| WorkspaceUsageInfo {
+                    workspace: w.workspace,
+                    biz_tag_count: w.biz_tag_count,
+                    total_generated: w.total_generated,
+                    window_generated: w.window_generated,
+                    window_failed: w.window_failed,
+                    generation_rate_per_sec: w.generation_rate_per_sec,
+                    segment_burn_rate_per_sec: w.segment_burn_rate_per_sec,
+                })
+                .collect(),
+            biz_tags: report
+                .biz_tags
+                .into_iter()
+                .map(|u| BizTagUsageInfo {
+                    workspace: u.workspace,
+                    biz_tag: u.biz_tag,
+                    total_requests: u.total_requests,
+                    total_generated: u.total_generated,
+                    total_failed: u.total_failed,
+                    current_qps: u.current_qps,
+                    window_generated: u.window_generated,
+                    window_failed: u.window_failed,
+                    generation_rate_per_sec: u.generation_rate_per_sec,
+                    window_segment_ids: u.window_segment_ids,
+                    segment_burn_rate_per_sec: u.segment_burn_rate_per_sec,
+                    last_segment_end: u.last_segment_end,
+                    last_seen: chrono::DateTime::from_timestamp(u.last_seen_secs as i64, 0)
+                        .unwrap_or_default()
+                        .to_rfc3339(),
+                })
+                .collect(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+// ========== Degradation Control Models ==========
+
+/// Per-algorithm degradation and circuit-breaker state
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AlgorithmDegradationInfo {
+    pub algorithm: String,
+    pub is_healthy: bool,
+    pub is_degraded: bool,
+    /// Forced off by an operator; cleared only by the recover endpoint
+    pub manually_degraded: bool,
+    /// closed | open | half_open
+    pub circuit_breaker_state: String,
+    pub consecutive_failures: u8,
+    pub consecutive_successes: u8,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DegradationStatusResponse {
+    /// normal | degraded | critical
+    pub state: String,
+    pub primary_algorithm: String,
+    pub effective_algorithm: String,
+    /// Configured fallback chain, in order
+    pub fallback_chain: Vec<String>,
+    /// Fallback chain actually used for routing (manually degraded algorithms removed)
+    pub routable_fallback_chain: Vec<String>,
+    pub algorithms: Vec<AlgorithmDegradationInfo>,
+    pub last_check_secs_ago: u64,
+    pub timestamp: String,
+}
+
+/// Body for `POST /api/v1/admin/degradation/{algorithm}/degrade|recover`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, ToSchema)]
+pub struct DegradationActionRequest {
+    /// Free-form reason recorded in the audit log
+    #[validate(length(max = 256))]
+    pub reason: Option<String>,
+}
+
+/// Body for `PUT /api/v1/admin/degradation/fallback-chain`
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateFallbackChainRequest {
+    /// Algorithms in fallback order, e.g. `["snowflake", "uuid_v7"]`
+    #[validate(length(max = 8))]
+    pub chain: Vec<String>,
+    /// Free-form reason recorded in the audit log
+    #[validate(length(max = 256))]
+    pub reason: Option<String>,
+}
+
+// ========== Audit Query Models ==========
+
+/// Query params for `GET /api/v1/audit`
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AuditQueryParams {
+    /// RFC 3339 start time (inclusive)
+    pub start: Option<String>,
+    /// RFC 3339 end time (exclusive)
+    pub end: Option<String>,
+    /// Workspace filter; user keys are always limited to their own workspace
+    #[validate(length(min = 1, max = 255))]
+    pub workspace: Option<String>,
+    /// Event type, e.g. `BizTagCreated`
+    pub event_type: Option<String>,
+    /// Success | Failure | Partial | Unknown
+    pub result: Option<String>,
+    /// Acting API key id
+    #[validate(length(min = 1, max = 255))]
+    pub actor: Option<String>,
+    /// Resource prefix, e.g. `biz_tag:`
+    #[validate(length(min = 1, max = 255))]
+    pub resource: Option<String>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    #[serde(default = "default_audit_limit")]
+    #[validate(range(min = 1, max = 500))]
+    pub limit: u64,
+}
+
+fn default_audit_limit() -> u64 {
+    50
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditEventInfo {
+    pub id: String,
+    pub timestamp: String,
+    pub event_type: String,
+    pub workspace_id: Option<String>,
+    /// Acting API key id
+    pub actor: Option<String>,
+    pub action: String,
+    pub resource: String,
+    pub result: String,
+    #[schema(value_type = Option<Object>)]
+    pub details: Option<serde_json::Value>,
+    /// Redacted
+    pub client_ip: Option<String>,
+    /// Redacted
+    pub user_agent: Option<String>,
+    pub duration_ms: i64,
+    pub error_message: Option<String>,
+}
+
+impl From<crate::core::database::AuditEventRecord> for AuditEventInfo {
+    fn from(record: crate::core::database::AuditEventRecord) -> Self {
+        Self {
+            id: record.id.to_string(),
+            timestamp: naive_to_rfc3339(record.occurred_at),
+            event_type: record.event_type,
+            workspace_id: record.workspace_id,
+            actor: record.actor,
+            action: record.action,
+            resource: record.resource,
+            result: record.result,
+            details: record.details,
+            client_ip: record.client_ip,
+            user_agent: record.user_agent,
+            duration_ms: record.duration_ms,
+            error_message: record.error_message,
+        }
+    }
+}
+
+/// Newest events first; pass `next_cursor` back as `cursor` for the next page
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditQueryResponse {
+    pub events: Vec<AuditEventInfo>,
+    /// Absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+// ========== Auth Ban Models ==========
+
+/// Query params for `GET /api/v1/admin/bans`
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, ToSchema)]
+pub struct BanListParams {
+    /// Only bans that are still in effect (default); `false` includes expired and lifted bans
+    #[serde(default = "default_ban_active")]
+    pub active: bool,
+    #[serde(default = "default_ban_limit")]
+    #[validate(range(min = 1, max = 1000))]
+    pub limit: u64,
+}
+
+fn default_ban_active() -> bool {
+    true
+}
+
+fn default_ban_limit() -> u64 {
+    100
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BanInfo {
+    pub id: String,
+    /// ip | key_id
+    pub target_type: String,
+    pub target: String,
+    /// Escalation level; each level doubles the ban duration
+    pub level: i32,
+    pub reason: String,
+    pub banned_at: String,
+    pub expires_at: String,
+    pub lifted_at: Option<String>,
+    /// API key that lifted the ban
+    pub lifted_by: Option<String>,
+    pub active: bool,
+}
+
+impl From<crate::core::database::Ban> for BanInfo {
+    fn from(ban: crate::core::database::Ban) -> Self {
+        Self {
+            active: ban.is_active(chrono::Utc::now().naive_utc()),
+            id: ban.id.to_string(),
+            target_type: ban.target_kind.to_string(),
+            target: ban.target,
+            level: ban.level,
+            reason: ban.reason,
+            banned_at: naive_to_rfc3339(ban.banned_at),
+            expires_at: naive_to_rfc3339(ban.expires_at),
+            lifted_at: ban.lifted_at.map(naive_to_rfc3339),
+            lifted_by: ban.lifted_by,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BanListResponse {
+    pub bans: Vec<BanInfo>,
+    pub timestamp: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1218,6 +1817,24 @@ mod tests {
         assert_eq!(params.page_size, 20);
     }
 
+    #[test]
+    fn test_usage_query_params_uses_defaults() {
+        let params: UsageQueryParams = serde_json::from_str("{}").unwrap();
+        assert_eq!(params.window_secs, None);
+        assert_eq!(params.workspace, None);
+        assert_eq!(params.limit, 20);
+    }
+
+    #[test]
+    fn test_audit_query_params_default_limit_and_bounds() {
+        let params: AuditQueryParams = serde_json::from_str("{}").unwrap();
+        assert_eq!(params.limit, 50);
+        assert!(params.validate().is_ok());
+
+        let params: AuditQueryParams = serde_json::from_str(r#"{"limit": 501}"#).unwrap();
+        assert!(params.validate().is_err());
+    }
+
     // ========== datetime_to_rfc3339 ==========
 
     #[test]
@@ -1431,6 +2048,8 @@ mod tests {
             role: Some("admin".to_string()),
             rate_limit: Some(1000),
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
         assert!(req.validate().is_ok());
     }
@@ -1444,6 +2063,8 @@ mod tests {
             role: Some("user".to_string()),
             rate_limit: Some(50), // below min=100
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
         assert!(req.validate().is_err());
     }
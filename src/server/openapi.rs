@@ -16,14 +16,19 @@ use sdforge::axum::Router;
 use sdforge::utoipa::OpenApi;
 
 use crate::server::models::{
-    ApiErrorResponse, ApiInfoResponse, ApiKeyListResponse, ApiKeyResponse,
-    ApiKeyWithSecretResponse, BatchGenerateRequest, BatchGenerateResponse, BizTagListResponse,
-    BizTagResponse, CreateApiKeyRequest, CreateBizTagRequest, CreateGroupRequest,
-    CreateWorkspaceRequest, ErrorResponse, GenerateRequest, GenerateResponse, GroupListResponse,
-    GroupResponse, HealthResponse, MetricsResponse, PaginationParams, ParseRequest, ParseResponse,
-    ReadyResponse, RevokeApiKeyResponse, SecureConfigResponse, SetAlgorithmRequest,
-    SetAlgorithmResponse, UpdateBizTagRequest, UpdateConfigResponse, UpdateLoggingRequest,
-    UpdateRateLimitRequest, WorkspaceListResponse, WorkspaceResponse,
+    AlgorithmDegradationInfo, ApiErrorResponse, ApiInfoResponse, ApiKeyAttentionInfo,
+    ApiKeyAttentionResponse, ApiKeyListResponse, ApiKeyResponse, ApiKeyWithSecretResponse,
+    AuditEventInfo, AuditQueryParams, AuditQueryResponse, AuditSinkSummary, AuditWriterSummary,
+    BatchGenerateRequest, BatchGenerateResponse, BizTagListResponse, BizTagResponse,
+    CapacityQueryParams, CapacityResponse, ComponentHealth, CreateApiKeyRequest,
+    CreateBizTagRequest, CreateGroupRequest, CreateWorkspaceRequest, DegradationActionRequest,
+    DegradationStatusResponse, ErrorResponse, GenerateRequest, GenerateResponse, GroupListResponse,
+    GroupResponse, HealthResponse, LiveResponse, MetricsResponse, PaginationParams, ParseRequest,
+    ParseResponse, ReadyResponse, RevokeApiKeyResponse, SecureConfigResponse, SegmentCapacityInfo,
+    SegmentCapacitySummary, SetAlgorithmRequest, SetAlgorithmResponse, TlsCertificateSummary,
+    UpdateBizTagRequest, UpdateConfigResponse, UpdateFallbackChainRequest, UpdateLoggingRequest,
+    UpdateRateLimitRequest, UpdateWorkspaceRequest, UsageQueryParams, UsageResponse,
+    WorkspaceListResponse, WorkspaceResponse,
 };
 
 /// OpenAPI 文档定义
@@ -85,7 +90,10 @@ use crate::server::models::{
     components(
         schemas(
             ApiErrorResponse,
+            AlgorithmDegradationInfo,
             ApiInfoResponse,
+            ApiKeyAttentionInfo,
+            ApiKeyAttentionResponse,
             ApiKeyListResponse,
             ApiKeyResponse,
             ApiKeyWithSecretResponse,
@@ -93,29 +101,47 @@ use crate::server::models::{
             BatchGenerateResponse,
             BizTagListResponse,
             BizTagResponse,
+            CapacityQueryParams,
+            CapacityResponse,
+            ComponentHealth,
             CreateApiKeyRequest,
             CreateBizTagRequest,
             CreateGroupRequest,
             CreateWorkspaceRequest,
+            DegradationActionRequest,
+            DegradationStatusResponse,
             ErrorResponse,
             GenerateRequest,
             GenerateResponse,
             GroupListResponse,
             GroupResponse,
             HealthResponse,
+            LiveResponse,
             MetricsResponse,
             PaginationParams,
             ParseRequest,
             ParseResponse,
             ReadyResponse,
             RevokeApiKeyResponse,
+            SegmentCapacityInfo,
+            SegmentCapacitySummary,
+            AuditWriterSummary,
+            AuditSinkSummary,
+            TlsCertificateSummary,
+            AuditEventInfo,
+            AuditQueryParams,
+            AuditQueryResponse,
             SecureConfigResponse,
             SetAlgorithmRequest,
             SetAlgorithmResponse,
             UpdateBizTagRequest,
             UpdateConfigResponse,
+            UpdateFallbackChainRequest,
             UpdateLoggingRequest,
             UpdateRateLimitRequest,
+            UpdateWorkspaceRequest,
+            UsageQueryParams,
+            UsageResponse,
             WorkspaceListResponse,
             WorkspaceResponse,
         )
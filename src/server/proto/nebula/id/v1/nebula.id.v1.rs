@@ -119,5 +119,100 @@ pub mod health_check_response {
         }
     }
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::sdforge::prost::Message)]
+pub struct ApiKey {
+    #[prost(string, tag="1")]
+    pub id: ::sdforge::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub key_id: ::sdforge::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub key_prefix: ::sdforge::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub name: ::sdforge::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub description: ::sdforge::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub role: ::sdforge::prost::alloc::string::String,
+    #[prost(int32, tag="7")]
+    pub rate_limit: i32,
+    #[prost(bool, tag="8")]
+    pub enabled: bool,
+    /// RFC 3339; empty when the key never expires
+    #[prost(string, tag="9")]
+    pub expires_at: ::sdforge::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub created_at: ::sdforge::prost::alloc::string::String,
+    /// Empty when the key is unrestricted
+    #[prost(string, repeated, tag="11")]
+    pub scopes: ::sdforge::prost::alloc::vec::Vec<::sdforge::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::sdforge::prost::Message)]
+pub struct CreateApiKeyRequest {
+    /// Ignored for admin keys; defaults to the caller's workspace for workspace admins
+    #[prost(string, tag="1")]
+    pub workspace_id: ::sdforge::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub name: ::sdforge::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub description: ::sdforge::prost::alloc::string::String,
+    /// "user" (default), "workspace_admin" or "admin"
+    #[prost(string, tag="4")]
+    pub role: ::sdforge::prost::alloc::string::String,
+    /// 0 uses the server default
+    #[prost(int32, tag="5")]
+    pub rate_limit: i32,
+    #[prost(string, tag="6")]
+    pub expires_at: ::sdforge::prost::alloc::string::String,
+    #[prost(string, repeated, tag="7")]
+    pub scopes: ::sdforge::prost::alloc::vec::Vec<::sdforge::prost::alloc::string::String>,
+    /// Store a signing key so the key can sign requests (NEBULA-HMAC-SHA256)
+    #[prost(bool, tag="8")]
+    pub request_signing: bool,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::sdforge::prost::Message)]
+pub struct ApiKeyWithSecret {
+    #[prost(message, optional, tag="1")]
+    pub key: ::core::option::Option<ApiKey>,
+    /// Only returned on creation and rotation
+    #[prost(string, tag="2")]
+    pub key_secret: ::sdforge::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::sdforge::prost::Message)]
+pub struct ListApiKeysRequest {
+    /// Defaults to the caller's workspace for workspace admins
+    #[prost(string, tag="1")]
+    pub workspace_id: ::sdforge::prost::alloc::string::String,
+    #[prost(uint32, tag="2")]
+    pub page: u32,
+    #[prost(uint32, tag="3")]
+    pub page_size: u32,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::sdforge::prost::Message)]
+pub struct ListApiKeysResponse {
+    #[prost(message, repeated, tag="1")]
+    pub api_keys: ::sdforge::prost::alloc::vec::Vec<ApiKey>,
+    #[prost(uint64, tag="2")]
+    pub total: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::sdforge::prost::Message)]
+pub struct RotateApiKeyRequest {
+    #[prost(string, tag="1")]
+    pub key_id: ::sdforge::prost::alloc::string::String,
+    /// Turn request signing on or off; unset keeps the key's current choice
+    #[prost(bool, optional, tag="2")]
+    pub request_signing: ::core::option::Option<bool>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::sdforge::prost::Message)]
+pub struct RevokeApiKeyRequest {
+    #[prost(string, tag="1")]
+    pub id: ::sdforge::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::sdforge::prost::Message)]
+pub struct RevokeApiKeyResponse {
+    #[prost(bool, tag="1")]
+    pub success: bool,
+    #[prost(string, tag="2")]
+    pub message: ::sdforge::prost::alloc::string::String,
+}
 include!("nebula.id.v1.tonic.rs");
 // @@protoc_insertion_point(module)
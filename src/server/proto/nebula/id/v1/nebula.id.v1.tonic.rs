@@ -216,6 +216,102 @@ pub mod nebula_id_service_client {
                 );
             self.inner.streaming(req, path, codec).await
         }
+        pub async fn create_api_key(
+            &mut self,
+            request: impl sdforge::tonic::IntoRequest<super::CreateApiKeyRequest>,
+        ) -> std::result::Result<
+            sdforge::tonic::Response<super::ApiKeyWithSecret>,
+            sdforge::tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    sdforge::tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nebula.id.v1.NebulaIdService/CreateApiKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nebula.id.v1.NebulaIdService", "CreateApiKey"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_api_keys(
+            &mut self,
+            request: impl sdforge::tonic::IntoRequest<super::ListApiKeysRequest>,
+        ) -> std::result::Result<
+            sdforge::tonic::Response<super::ListApiKeysResponse>,
+            sdforge::tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    sdforge::tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nebula.id.v1.NebulaIdService/ListApiKeys",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nebula.id.v1.NebulaIdService", "ListApiKeys"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn rotate_api_key(
+            &mut self,
+            request: impl sdforge::tonic::IntoRequest<super::RotateApiKeyRequest>,
+        ) -> std::result::Result<
+            sdforge::tonic::Response<super::ApiKeyWithSecret>,
+            sdforge::tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    sdforge::tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nebula.id.v1.NebulaIdService/RotateApiKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nebula.id.v1.NebulaIdService", "RotateApiKey"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn revoke_api_key(
+            &mut self,
+            request: impl sdforge::tonic::IntoRequest<super::RevokeApiKeyRequest>,
+        ) -> std::result::Result<
+            sdforge::tonic::Response<super::RevokeApiKeyResponse>,
+            sdforge::tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    sdforge::tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/nebula.id.v1.NebulaIdService/RevokeApiKey",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("nebula.id.v1.NebulaIdService", "RevokeApiKey"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -272,6 +368,34 @@ pub mod nebula_id_service_server {
             sdforge::tonic::Response<Self::BatchGenerateStreamStream>,
             sdforge::tonic::Status,
         >;
+        async fn create_api_key(
+            &self,
+            request: sdforge::tonic::Request<super::CreateApiKeyRequest>,
+        ) -> std::result::Result<
+            sdforge::tonic::Response<super::ApiKeyWithSecret>,
+            sdforge::tonic::Status,
+        >;
+        async fn list_api_keys(
+            &self,
+            request: sdforge::tonic::Request<super::ListApiKeysRequest>,
+        ) -> std::result::Result<
+            sdforge::tonic::Response<super::ListApiKeysResponse>,
+            sdforge::tonic::Status,
+        >;
+        async fn rotate_api_key(
+            &self,
+            request: sdforge::tonic::Request<super::RotateApiKeyRequest>,
+        ) -> std::result::Result<
+            sdforge::tonic::Response<super::ApiKeyWithSecret>,
+            sdforge::tonic::Status,
+        >;
+        async fn revoke_api_key(
+            &self,
+            request: sdforge::tonic::Request<super::RevokeApiKeyRequest>,
+        ) -> std::result::Result<
+            sdforge::tonic::Response<super::RevokeApiKeyResponse>,
+            sdforge::tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct NebulaIdServiceServer<T> {
@@ -581,6 +705,186 @@ pub mod nebula_id_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/nebula.id.v1.NebulaIdService/CreateApiKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateApiKeySvc<T: NebulaIdService>(pub Arc<T>);
+                    impl<
+                        T: NebulaIdService,
+                    > sdforge::tonic::server::UnaryService<super::CreateApiKeyRequest>
+                    for CreateApiKeySvc<T> {
+                        type Response = super::ApiKeyWithSecret;
+                        type Future = BoxFuture<
+                            sdforge::tonic::Response<Self::Response>,
+                            sdforge::tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: sdforge::tonic::Request<super::CreateApiKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NebulaIdService>::create_api_key(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateApiKeySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = sdforge::tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nebula.id.v1.NebulaIdService/ListApiKeys" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListApiKeysSvc<T: NebulaIdService>(pub Arc<T>);
+                    impl<
+                        T: NebulaIdService,
+                    > sdforge::tonic::server::UnaryService<super::ListApiKeysRequest>
+                    for ListApiKeysSvc<T> {
+                        type Response = super::ListApiKeysResponse;
+                        type Future = BoxFuture<
+                            sdforge::tonic::Response<Self::Response>,
+                            sdforge::tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: sdforge::tonic::Request<super::ListApiKeysRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NebulaIdService>::list_api_keys(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListApiKeysSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = sdforge::tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nebula.id.v1.NebulaIdService/RotateApiKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct RotateApiKeySvc<T: NebulaIdService>(pub Arc<T>);
+                    impl<
+                        T: NebulaIdService,
+                    > sdforge::tonic::server::UnaryService<super::RotateApiKeyRequest>
+                    for RotateApiKeySvc<T> {
+                        type Response = super::ApiKeyWithSecret;
+                        type Future = BoxFuture<
+                            sdforge::tonic::Response<Self::Response>,
+                            sdforge::tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: sdforge::tonic::Request<super::RotateApiKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NebulaIdService>::rotate_api_key(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RotateApiKeySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = sdforge::tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nebula.id.v1.NebulaIdService/RevokeApiKey" => {
+                    #[allow(non_camel_case_types)]
+                    struct RevokeApiKeySvc<T: NebulaIdService>(pub Arc<T>);
+                    impl<
+                        T: NebulaIdService,
+                    > sdforge::tonic::server::UnaryService<super::RevokeApiKeyRequest>
+                    for RevokeApiKeySvc<T> {
+                        type Response = super::RevokeApiKeyResponse;
+                        type Future = BoxFuture<
+                            sdforge::tonic::Response<Self::Response>,
+                            sdforge::tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: sdforge::tonic::Request<super::RevokeApiKeyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as NebulaIdService>::revoke_api_key(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RevokeApiKeySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = sdforge::tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
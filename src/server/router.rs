@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::core::algorithm::DynAuditLogger;
+use crate::core::auth::{ApiKeyScopes, Scope};
 use crate::server::api_version::{api_version_middleware, API_V1};
 use crate::server::audit::{AuditLogger, AuditMiddleware};
 use crate::server::config::{cors, management::ConfigManagementService};
@@ -23,22 +25,26 @@ use crate::server::handlers::helpers::{
 };
 use crate::server::handlers::ApiHandlers;
 use crate::server::middleware::locale::Locale;
-use crate::server::middleware::{locale_middleware, ApiKeyAuth};
+use crate::server::middleware::{locale_middleware, ApiKeyAuth, AuthenticatedKey};
 use crate::server::models::{
-    ApiInfoResponse, ApiKeyListResponse, ApiKeyWithSecretResponse, BatchGenerateRequest,
-    BatchGenerateResponse, BizTagListResponse, BizTagResponse, CreateApiKeyRequest,
-    CreateBizTagRequest, CreateGroupRequest, CreateWorkspaceRequest, ErrorResponse,
-    GenerateRequest, GenerateResponse, GroupListParams, GroupListResponse, GroupResponse,
-    HealthResponse, MetricsResponse, PaginationParams, ParseRequest, ParseResponse, ReadyResponse,
-    RevokeApiKeyResponse, SecureConfigResponse, SetAlgorithmRequest, SetAlgorithmResponse,
-    UpdateBizTagRequest, UpdateConfigResponse, UpdateLoggingRequest, UpdateRateLimitRequest,
+    ApiInfoResponse, ApiKeyAttentionResponse, ApiKeyListResponse, ApiKeyWithSecretResponse,
+    AuditQueryParams, AuditQueryResponse, BanInfo, BanListParams, BanListResponse,
+    BatchGenerateRequest, BatchGenerateResponse, BizTagListResponse, BizTagResponse,
+    CapacityQueryParams, CapacityResponse, CreateApiKeyRequest, CreateBizTagRequest,
+    CreateGroupRequest, CreateWorkspaceRequest, DegradationActionRequest,
+    DegradationStatusResponse, ErrorResponse, GenerateRequest, GenerateResponse, GroupListParams,
+    GroupListResponse, GroupResponse, HealthResponse, LiveResponse, MetricsResponse,
+    PaginationParams, ParseRequest, ParseResponse, ReadyResponse, RevokeApiKeyResponse,
+    RotateApiKeyParams, SecureConfigResponse, SetAlgorithmRequest, SetAlgorithmResponse,
+    UpdateBizTagRequest, UpdateConfigResponse, UpdateFallbackChainRequest, UpdateLoggingRequest,
+    UpdateRateLimitRequest, UpdateWorkspaceRequest, UsageQueryParams, UsageResponse,
     WorkspaceListResponse, WorkspaceResponse,
 };
 use crate::server::rate_limit::{limiter::RateLimiter, middleware::RateLimitMiddleware};
 use sdforge::axum::{
     extract::{Path, Query, State},
     http::{header, HeaderValue, StatusCode},
-    routing::{delete, get, post},
+    routing::{delete, get, patch, post, put},
     Extension, Json, Router,
 };
 use sdforge::tower_http::set_header::SetResponseHeaderLayer;
@@ -50,6 +56,8 @@ pub struct AppState {
     pub handlers: Arc<ApiHandlers>,
     pub auth: Arc<ApiKeyAuth>,
     pub config_service: Arc<dyn ConfigManagementService>,
+    /// 作用域拒绝等路由层事件的审计出口
+    pub audit_logger: DynAuditLogger,
 }
 
 pub async fn create_router(
@@ -76,33 +84,29 @@ pub async fn create_router(
 
     let rate_limit_middleware = RateLimitMiddleware::new(rate_limiter.clone())
         .with_trusted_proxies(trusted_proxies.clone());
-    let audit_middleware = AuditMiddleware::new(audit_logger.clone(), auth.clone(), rate_limiter)
-        .with_trusted_proxies(trusted_proxies);
+    let audit_middleware = Arc::new(
+        AuditMiddleware::new(audit_logger.clone(), auth.clone(), rate_limiter)
+            .with_trusted_proxies(trusted_proxies),
+    );
 
     let config_service = handlers.get_config_service();
 
+    let scope_audit_logger: DynAuditLogger = audit_logger.clone();
     let app_state = AppState {
         handlers: handlers.clone(),
         auth: auth.clone(),
         config_service: config_service.clone(),
+        audit_logger: scope_audit_logger.clone(),
     };
 
     // ========== V1 API Routes ==========
     // Admin-only endpoints (require admin API key)
     let v1_admin_routes = Router::new()
-        // API Key management endpoints (admin only)
-        .route(
-            "/api-keys",
-            post(handle_create_api_key).get(handle_list_api_keys),
-        )
-        .route("/api-keys/{id}", delete(handle_revoke_api_key))
-        // Workspace creation (admin only)
+        // Lifecycle report spans every workspace (admin only)
+        .route("/api-keys/attention", get(handle_api_key_attention))
+        // Workspace creation / update (admin only)
         .route("/workspaces", post(handle_create_workspace))
-        // Workspace user key regeneration (admin only)
-        .route(
-            "/workspaces/{name}/regenerate-user-key",
-            post(handle_regenerate_user_key),
-        )
+        .route("/workspaces/{name}", patch(handle_update_workspace))
         // SEC-CRITICAL-002 修复（CWE-862 / strix vuln-0002）：服务级配置变更
         // 端点（速率限制、日志、热重载、默认算法）必须由 Admin 角色执行。
         // 原本错放在 v1_authenticated_routes，导致任何 User API key 都能
@@ -111,6 +115,34 @@ pub async fn create_router(
         .route("/config/logging", post(handle_update_logging))
         .route("/config/reload", post(handle_reload_config))
         .route("/config/algorithm", post(handle_set_algorithm))
+        // Per-tenant usage (top consumers, generation and segment burn rates)
+        .route("/admin/usage", get(handle_get_usage))
+        // Segment exhaustion forecast (time-to-exhaustion per biz tag)
+        .route("/admin/capacity", get(handle_get_capacity))
+        // Auth-failure bans (IP / key_id) shared across instances; lifting is
+        // audited as AuthBanLifted
+        .route("/admin/bans", get(handle_list_bans))
+        .route("/admin/bans/{id}", delete(handle_lift_ban))
+        // Degradation control: per-algorithm health / circuit state, manual
+        // degrade & recover, runtime fallback chain (audited as DegradationEvent)
+        .route("/admin/degradation", get(handle_get_degradation))
+        .route(
+            "/admin/degradation/fallback-chain",
+            put(handle_update_fallback_chain),
+        )
+        .route(
+            "/admin/degradation/{algorithm}/degrade",
+            post(handle_degrade_algorithm),
+        )
+        .route(
+            "/admin/degradation/{algorithm}/recover",
+            post(handle_recover_algorithm),
+        )
+        // 作用域校验最内层：认证与 Admin 角色校验通过后才比对 key 的作用域
+        .layer(sdforge::axum::middleware::from_fn_with_state(
+            scope_audit_logger.clone(),
+            crate::server::middleware::scope_required_middleware,
+        ))
         // Apply admin requirement middleware first, then auth middleware
         // This ensures auth runs first to set the ApiKeyRole extension
         .layer(sdforge::axum::middleware::from_fn(
@@ -121,6 +153,31 @@ pub async fn create_router(
             crate::server::middleware::auth_middleware_fn,
         ));
 
+    // API key management: Admin manages every key, workspace admins manage
+    // the keys of their own workspace (handlers narrow by workspace)
+    let v1_key_management_routes = Router::new()
+        .route(
+            "/api-keys",
+            post(handle_create_api_key).get(handle_list_api_keys),
+        )
+        .route("/api-keys/{id}", delete(handle_revoke_api_key))
+        .route("/api-keys/{id}/rotate", post(handle_rotate_api_key))
+        .route(
+            "/workspaces/{name}/regenerate-user-key",
+            post(handle_regenerate_user_key),
+        )
+        .layer(sdforge::axum::middleware::from_fn_with_state(
+            scope_audit_logger.clone(),
+            crate::server::middleware::scope_required_middleware,
+        ))
+        .layer(sdforge::axum::middleware::from_fn(
+            crate::server::middleware::key_manager_required_middleware,
+        ))
+        .layer(sdforge::axum::middleware::from_fn_with_state(
+            auth.clone(),
+            crate::server::middleware::auth_middleware_fn,
+        ));
+
     // Authenticated endpoints (require API key)
     let v1_authenticated_routes = Router::new()
         .route("/config", get(handle_get_config))
@@ -144,6 +201,8 @@ pub async fn create_router(
                 .put(handle_update_biz_tag)
                 .delete(handle_delete_biz_tag),
         )
+        // Persisted audit trail; user keys only see their own workspace
+        .route("/audit", get(handle_query_audit_events))
         // Apply auth middleware
         //
         // SEC-CRITICAL-001 修复（CWE-1188）—— axum 0.8.x layer 语义：
@@ -153,6 +212,10 @@ pub async fn create_router(
         // `ApiKeyRole` 扩展）。这样 `anonymous_block_middleware` 执行时
         // `ApiKeyRole` 扩展已由 `auth_middleware_fn` 注入，可正确拒绝
         // Anonymous 角色。前次修复顺序相反，导致中间件完全无效。
+        .layer(sdforge::axum::middleware::from_fn_with_state(
+            scope_audit_logger,
+            crate::server::middleware::scope_required_middleware,
+        ))
         .layer(sdforge::axum::middleware::from_fn(
             anonymous_block_middleware,
         ))
@@ -165,6 +228,7 @@ pub async fn create_router(
     let v1_public_routes = Router::new()
         .route("/", get(handle_api_info))
         .merge(v1_authenticated_routes)
+        .merge(v1_key_management_routes)
         .merge(v1_admin_routes);
 
     // ========== API Versioning ==========
@@ -186,6 +250,7 @@ pub async fn create_router(
     Router::new()
         .route("/health", get(handle_health))
         .route("/ready", get(handle_ready))
+        .route("/live", get(handle_live))
         .route("/metrics", get(handle_metrics))
         .route(
             "/api-docs/openapi.json",
@@ -193,6 +258,15 @@ pub async fn create_router(
         )
         .merge(api_v1_routes)
         .with_state(app_state)
+        // 审计层包住全部路由（含认证层），调用方由认证层写入响应扩展后在此归因
+        .layer(sdforge::axum::middleware::from_fn_with_state(
+            audit_middleware,
+            crate::server::audit::middleware::audit_middleware_fn,
+        ))
+        // 请求 ID 位于审计层外侧，审计事件、错误响应与响应头共用同一个 ID
+        .layer(sdforge::axum::middleware::from_fn(
+            crate::server::middleware::request_id_middleware,
+        ))
         // Security headers
         .layer(SetResponseHeaderLayer::overriding(
             header::X_CONTENT_TYPE_OPTIONS,
@@ -219,8 +293,12 @@ pub async fn create_router(
             HeaderValue::from_static("strict-origin-when-cross-origin"),
         ))
         .layer(cors)
+        // W3C traceparent 提取 + 每请求 server span；放在最外层，使认证、
+        // 限流等中间件产生的日志/子 span 都挂在同一 trace 下
+        .layer(sdforge::axum::middleware::from_fn(
+            crate::server::middleware::trace_context_middleware,
+        ))
         .layer(sdforge::axum::Extension(rate_limit_middleware))
-        .layer(sdforge::axum::Extension(audit_middleware))
         .layer(sdforge::axum::Extension(audit_logger))
 }
 
@@ -341,6 +419,29 @@ async fn verify_user_workspace(
     verify_workspace_id_match(workspace_uuid, key_workspace_id, locale)
 }
 
+/// 校验 key 的作用域覆盖 `generate:<group>/<biz_tag>`；拒绝时记录审计事件
+async fn verify_generate_scope(
+    state: &AppState,
+    scopes: &ApiKeyScopes,
+    group: &str,
+    biz_tag: &str,
+    key_workspace_id: &Option<uuid::Uuid>,
+    locale: Locale,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    let required = Scope::generate(group, biz_tag);
+    if scopes.allows(&required) {
+        return Ok(());
+    }
+    Err(crate::server::middleware::scope::deny_scope(
+        &state.audit_logger,
+        *key_workspace_id,
+        format!("generate:{}/{}", group, biz_tag),
+        &required,
+        locale,
+    )
+    .await)
+}
+
 /// 提取通用的 workspace_id 比较逻辑
 ///
 /// Phase 8 T041 — returns locale-translated error on mismatch.
@@ -359,6 +460,43 @@ fn verify_workspace_id_match(
     Ok(())
 }
 
+/// 审计查询的 workspace 范围：Admin 不受限（`None`）；workspace 绑定的 key
+/// 只能查询自己的 workspace，显式请求其他 workspace 时返回 403。
+fn audit_workspace_scope(
+    role: crate::server::middleware::ApiKeyRole,
+    key_workspace_id: Option<uuid::Uuid>,
+    requested_workspace: Option<&str>,
+    locale: Locale,
+) -> Result<Option<String>, (StatusCode, Json<ErrorResponse>)> {
+    if role == crate::server::middleware::ApiKeyRole::Admin {
+        return Ok(None);
+    }
+    let Some(key_workspace_id) = key_workspace_id else {
+        return Err(auth_required_response(locale));
+    };
+    let own = key_workspace_id.to_string();
+    if requested_workspace.is_some_and(|w| w != own) {
+        return Err(workspace_mismatch_response(locale));
+    }
+    Ok(Some(own))
+}
+
+/// API key 管理的 workspace 范围：Admin 不受限（`None`）；workspace 管理员
+/// 只能管理自己 workspace 的 key（`Some`）。
+fn key_manager_workspace(
+    role: crate::server::middleware::ApiKeyRole,
+    key_workspace_id: Option<uuid::Uuid>,
+    locale: Locale,
+) -> Result<Option<uuid::Uuid>, (StatusCode, Json<ErrorResponse>)> {
+    match role {
+        crate::server::middleware::ApiKeyRole::Admin => Ok(None),
+        crate::server::middleware::ApiKeyRole::WorkspaceAdmin => key_workspace_id
+            .map(Some)
+            .ok_or_else(|| auth_required_response(locale)),
+        _ => Err(auth_required_response(locale)),
+    }
+}
+
 /// Verify workspace_id match for User API Key (direct Uuid comparison)
 fn verify_workspace_id(
     req_workspace_id: uuid::Uuid,
@@ -372,6 +510,7 @@ async fn handle_generate(
     State(state): State<AppState>,
     extensions: sdforge::axum::Extension<Option<uuid::Uuid>>,
     extensions_role: sdforge::axum::Extension<crate::server::middleware::ApiKeyRole>,
+    Extension(scopes): Extension<ApiKeyScopes>,
     Extension(locale): Extension<Locale>,
     Json(req): Json<GenerateRequest>,
 ) -> Result<Json<GenerateResponse>, (StatusCode, Json<ErrorResponse>)> {
@@ -381,6 +520,16 @@ async fn handle_generate(
     // Validate request parameters
     validate_request(&req, locale)?;
 
+    verify_generate_scope(
+        &state,
+        &scopes,
+        &req.group,
+        &req.biz_tag,
+        &extensions.0,
+        locale,
+    )
+    .await?;
+
     // Verify workspace_id match for User API Key
     verify_user_workspace(&req.workspace, &extensions.0, &state.handlers, locale).await?;
 
@@ -396,6 +545,7 @@ async fn handle_batch_generate(
     State(state): State<AppState>,
     extensions: sdforge::axum::Extension<Option<uuid::Uuid>>,
     extensions_role: sdforge::axum::Extension<crate::server::middleware::ApiKeyRole>,
+    Extension(scopes): Extension<ApiKeyScopes>,
     Extension(locale): Extension<Locale>,
     Json(req): Json<BatchGenerateRequest>,
 ) -> Result<Json<BatchGenerateResponse>, (StatusCode, Json<ErrorResponse>)> {
@@ -418,6 +568,16 @@ async fn handle_batch_generate(
     // Validate request parameters
     validate_request(&req, locale)?;
 
+    verify_generate_scope(
+        &state,
+        &scopes,
+        &req.group,
+        &req.biz_tag,
+        &extensions.0,
+        locale,
+    )
+    .await?;
+
     // Verify workspace_id match for User API Key
     verify_user_workspace(&req.workspace, &extensions.0, &state.handlers, locale).await?;
 
@@ -448,8 +608,19 @@ async fn handle_health(State(state): State<AppState>) -> Json<HealthResponse> {
     Json(state.handlers.health().await)
 }
 
-async fn handle_ready(State(state): State<AppState>) -> Json<ReadyResponse> {
-    Json(state.handlers.ready().await)
+/// 未就绪时返回 503，使负载均衡与 k8s readiness probe 摘除该实例
+async fn handle_ready(State(state): State<AppState>) -> (StatusCode, Json<ReadyResponse>) {
+    let resp = state.handlers.ready().await;
+    let status = if resp.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(resp))
+}
+
+async fn handle_live(State(state): State<AppState>) -> Json<LiveResponse> {
+    Json(state.handlers.live())
 }
 
 async fn handle_metrics(State(state): State<AppState>) -> Json<MetricsResponse> {
@@ -493,6 +664,124 @@ async fn handle_get_config(State(state): State<AppState>) -> Json<SecureConfigRe
     Json(state.config_service.get_secure_config())
 }
 
+async fn handle_get_usage(
+    State(state): State<AppState>,
+    Extension(locale): Extension<Locale>,
+    Query(params): Query<UsageQueryParams>,
+) -> Result<Json<UsageResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_request(&params, locale)?;
+    Ok(Json(state.handlers.usage(&params)))
+}
+
+async fn handle_get_capacity(
+    State(state): State<AppState>,
+    Query(params): Query<CapacityQueryParams>,
+) -> Json<CapacityResponse> {
+    Json(state.handlers.capacity(&params))
+}
+
+async fn handle_query_audit_events(
+    State(state): State<AppState>,
+    extensions: sdforge::axum::Extension<Option<uuid::Uuid>>,
+    extensions_role: sdforge::axum::Extension<crate::server::middleware::ApiKeyRole>,
+    Extension(locale): Extension<Locale>,
+    Query(params): Query<AuditQueryParams>,
+) -> Result<Json<AuditQueryResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_request(&params, locale)?;
+    let scope = audit_workspace_scope(
+        extensions_role.0,
+        extensions.0,
+        params.workspace.as_deref(),
+        locale,
+    )?;
+
+    state
+        .handlers
+        .query_audit_events(&params, scope)
+        .await
+        .map(Json)
+        .map_err(|e| core_error_to_response(&e, locale))
+}
+
+async fn handle_list_bans(
+    State(state): State<AppState>,
+    Extension(locale): Extension<Locale>,
+    Query(params): Query<BanListParams>,
+) -> Result<Json<BanListResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_request(&params, locale)?;
+    state
+        .handlers
+        .list_bans(&params)
+        .await
+        .map(Json)
+        .map_err(|e| core_error_to_response(&e, locale))
+}
+
+async fn handle_lift_ban(
+    State(state): State<AppState>,
+    caller: Option<Extension<AuthenticatedKey>>,
+    Extension(locale): Extension<Locale>,
+    Path(id): Path<String>,
+) -> Result<Json<BanInfo>, (StatusCode, Json<ErrorResponse>)> {
+    let actor = caller
+        .map(|Extension(caller)| caller.key_id)
+        .unwrap_or_else(|| "unknown".to_string());
+    state
+        .handlers
+        .lift_ban(&id, &actor)
+        .await
+        .map(Json)
+        .map_err(|e| core_error_to_response(&e, locale))
+}
+
+async fn handle_get_degradation(State(state): State<AppState>) -> Json<DegradationStatusResponse> {
+    Json(state.handlers.degradation_status().await)
+}
+
+async fn handle_degrade_algorithm(
+    State(state): State<AppState>,
+    Extension(locale): Extension<Locale>,
+    Path(algorithm): Path<String>,
+    Json(req): Json<DegradationActionRequest>,
+) -> Result<Json<DegradationStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_request(&req, locale)?;
+    state
+        .handlers
+        .degrade_algorithm(&algorithm, req)
+        .await
+        .map(Json)
+        .map_err(|e| core_error_to_response(&e, locale))
+}
+
+async fn handle_recover_algorithm(
+    State(state): State<AppState>,
+    Extension(locale): Extension<Locale>,
+    Path(algorithm): Path<String>,
+    Json(req): Json<DegradationActionRequest>,
+) -> Result<Json<DegradationStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_request(&req, locale)?;
+    state
+        .handlers
+        .recover_algorithm(&algorithm, req)
+        .await
+        .map(Json)
+        .map_err(|e| core_error_to_response(&e, locale))
+}
+
+async fn handle_update_fallback_chain(
+    State(state): State<AppState>,
+    Extension(locale): Extension<Locale>,
+    Json(req): Json<UpdateFallbackChainRequest>,
+) -> Result<Json<DegradationStatusResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_request(&req, locale)?;
+    state
+        .handlers
+        .update_fallback_chain(req)
+        .await
+        .map(Json)
+        .map_err(|e| core_error_to_response(&e, locale))
+}
+
 async fn handle_update_rate_limit(
     State(state): State<AppState>,
     Extension(locale): Extension<Locale>,
@@ -551,6 +840,7 @@ async fn handle_api_info() -> Json<ApiInfoResponse> {
         endpoints: vec![
             "GET /health - Health check".to_string(),
             "GET /ready - Readiness probe".to_string(),
+            "GET /live - Liveness probe".to_string(),
             "GET /metrics - Prometheus metrics".to_string(),
             "GET /api/v1 - API information".to_string(),
             "POST /api/v1/generate - Generate ID".to_string(),
@@ -561,11 +851,23 @@ async fn handle_api_info() -> Json<ApiInfoResponse> {
             "POST /api/v1/config/logging - Update logging".to_string(),
             "POST /api/v1/config/reload - Reload configuration".to_string(),
             "POST /api/v1/config/algorithm - Set algorithm".to_string(),
+            "POST /api/v1/api-keys/:key_id/rotate - Rotate API key".to_string(),
+            "GET /api/v1/api-keys/attention - API keys needing attention".to_string(),
+            "GET /api/v1/admin/usage - Per-tenant usage".to_string(),
+            "GET /api/v1/admin/capacity - Segment exhaustion forecast".to_string(),
+            "GET /api/v1/admin/bans - List auth-failure bans".to_string(),
+            "DELETE /api/v1/admin/bans/:id - Lift ban".to_string(),
+            "GET /api/v1/admin/degradation - Algorithm degradation state".to_string(),
+            "POST /api/v1/admin/degradation/:algorithm/degrade - Force algorithm off".to_string(),
+            "POST /api/v1/admin/degradation/:algorithm/recover - Recover algorithm".to_string(),
+            "PUT /api/v1/admin/degradation/fallback-chain - Set fallback chain".to_string(),
+            "GET /api/v1/audit - Query audit trail".to_string(),
             "POST /api/v1/biz-tags - Create biz tag".to_string(),
             "GET /api/v1/biz-tags - List biz tags".to_string(),
             "GET /api/v1/biz-tags/:id - Get biz tag".to_string(),
             "PUT /api/v1/biz-tags/:id - Update biz tag".to_string(),
             "DELETE /api/v1/biz-tags/:id - Delete biz tag".to_string(),
+            "PATCH /api/v1/workspaces/:name - Update workspace".to_string(),
         ],
     })
 }
@@ -764,6 +1066,22 @@ async fn handle_get_workspace(
     }
 }
 
+async fn handle_update_workspace(
+    State(state): State<AppState>,
+    Extension(locale): Extension<Locale>,
+    Path(name): Path<String>,
+    Json(req): Json<UpdateWorkspaceRequest>,
+) -> Result<Json<WorkspaceResponse>, (StatusCode, Json<ErrorResponse>)> {
+    validate_request(&req, locale)?;
+
+    state
+        .handlers
+        .update_workspace(&name, req)
+        .await
+        .map(Json)
+        .map_err(|e| core_error_to_response(&e, locale))
+}
+
 // ========== Group Handlers ==========
 
 async fn handle_create_group(
@@ -792,9 +1110,15 @@ async fn handle_create_group(
 
 async fn handle_regenerate_user_key(
     State(state): State<AppState>,
+    Extension(key_workspace_id): Extension<Option<uuid::Uuid>>,
+    Extension(role): Extension<crate::server::middleware::ApiKeyRole>,
     Extension(locale): Extension<Locale>,
     Path(name): Path<String>,
 ) -> Result<Json<ApiKeyWithSecretResponse>, (StatusCode, Json<ErrorResponse>)> {
+    if let Some(own) = key_manager_workspace(role, key_workspace_id, locale)? {
+        verify_user_workspace(&name, &Some(own), &state.handlers, locale).await?;
+    }
+
     state
         .handlers
         .regenerate_user_api_key(&name)
@@ -828,11 +1152,32 @@ async fn handle_list_groups(
 
 async fn handle_create_api_key(
     State(state): State<AppState>,
+    Extension(key_workspace_id): Extension<Option<uuid::Uuid>>,
+    Extension(role): Extension<crate::server::middleware::ApiKeyRole>,
+    Extension(scopes): Extension<ApiKeyScopes>,
     Extension(locale): Extension<Locale>,
     Json(req): Json<CreateApiKeyRequest>,
 ) -> Result<Json<ApiKeyWithSecretResponse>, (StatusCode, Json<ErrorResponse>)> {
     validate_request(&req, locale)?;
 
+    // workspace 管理员：key 固定落在自己的 workspace；显式指定其他
+    // workspace 时返回 403，而不是静默改写
+    if let Some(own) = key_manager_workspace(role, key_workspace_id, locale)? {
+        if let Some(raw) = req.workspace_id.as_deref() {
+            let requested =
+                uuid::Uuid::parse_str(raw).map_err(|_| invalid_uuid_response(locale))?;
+            if requested != own {
+                return Err(workspace_mismatch_response(locale));
+            }
+        }
+        return state
+            .handlers
+            .create_workspace_api_key(own, &scopes, req)
+            .await
+            .map(Json)
+            .map_err(|e| core_error_to_response(&e, locale));
+    }
+
     // Phase 8 T041 (MEDIUM M-5 fix) — distinguish `workspace_id`
     // *missing* (return `workspace_id_required_response`) from
     // `workspace_id` *malformed* (return `invalid_uuid_response`).
@@ -872,6 +1217,8 @@ async fn handle_create_api_key(
 
 async fn handle_list_api_keys(
     State(state): State<AppState>,
+    Extension(key_workspace_id): Extension<Option<uuid::Uuid>>,
+    Extension(role): Extension<crate::server::middleware::ApiKeyRole>,
     Extension(locale): Extension<Locale>,
     Query(params): Query<PaginationParams>,
 ) -> Result<Json<ApiKeyListResponse>, (StatusCode, Json<ErrorResponse>)> {
@@ -905,6 +1252,14 @@ async fn handle_list_api_keys(
         None => uuid::Uuid::nil(),
     };
 
+    // workspace 管理员只能列出自己 workspace 的 key
+    let workspace_id = match key_manager_workspace(role, key_workspace_id, locale)? {
+        Some(own) if params.workspace_id.is_none() => own,
+        Some(own) if workspace_id == own => own,
+        Some(_) => return Err(workspace_mismatch_response(locale)),
+        None => workspace_id,
+    };
+
     let response = state
         .handlers
         .list_api_keys(workspace_id, Some(page_size as u32), Some(offset as u32))
@@ -915,17 +1270,61 @@ async fn handle_list_api_keys(
 
 async fn handle_revoke_api_key(
     State(state): State<AppState>,
+    Extension(key_workspace_id): Extension<Option<uuid::Uuid>>,
+    Extension(role): Extension<crate::server::middleware::ApiKeyRole>,
     Extension(locale): Extension<Locale>,
     Path(id): Path<String>,
 ) -> Result<Json<RevokeApiKeyResponse>, (StatusCode, Json<ErrorResponse>)> {
     let uuid = uuid::Uuid::parse_str(&id).map_err(|_| invalid_uuid_response(locale))?;
 
+    let result = match key_manager_workspace(role, key_workspace_id, locale)? {
+        Some(own) => state.handlers.revoke_workspace_api_key(own, uuid).await,
+        None => state.handlers.revoke_api_key(uuid).await,
+    };
+    result
+        .map(Json)
+        .map_err(|e: crate::core::types::CoreError| core_error_to_response(&e, locale))
+}
+
+/// 轮换 key（路径参数为 `key_id`）：旧 secret 在宽限期内仍有效
+async fn handle_rotate_api_key(
+    State(state): State<AppState>,
+    Extension(key_workspace_id): Extension<Option<uuid::Uuid>>,
+    Extension(role): Extension<crate::server::middleware::ApiKeyRole>,
+    Extension(locale): Extension<Locale>,
+    Path(key_id): Path<String>,
+    Query(params): Query<RotateApiKeyParams>,
+) -> Result<Json<ApiKeyWithSecretResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let request_signing = params.request_signing;
+    let result = match key_manager_workspace(role, key_workspace_id, locale)? {
+        Some(own) => {
+            state
+                .handlers
+                .rotate_workspace_api_key(own, &key_id, request_signing)
+                .await
+        }
+        None => {
+            state
+                .handlers
+                .rotate_api_key(&key_id, request_signing)
+                .await
+        }
+    };
+    result
+        .map(Json)
+        .map_err(|e: crate::core::types::CoreError| core_error_to_response(&e, locale))
+}
+
+async fn handle_api_key_attention(
+    State(state): State<AppState>,
+    Extension(locale): Extension<Locale>,
+) -> Result<Json<ApiKeyAttentionResponse>, (StatusCode, Json<ErrorResponse>)> {
     state
         .handlers
-        .revoke_api_key(uuid)
+        .api_key_attention()
         .await
         .map(Json)
-        .map_err(|e: crate::core::types::CoreError| core_error_to_response(&e, locale))
+        .map_err(|e| core_error_to_response(&e, locale))
 }
 
 #[cfg(test)]
@@ -982,6 +1381,7 @@ mod tests {
                         enabled: true,
                         expires_at: None,
                         created_at: chrono::Utc::now().naive_utc(),
+                        scopes: crate::core::auth::ApiKeyScopes::unrestricted(),
                     },
                     key_secret: "mock_secret".to_string(),
                 })
@@ -1233,6 +1633,36 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    // ========== audit_workspace_scope tests ==========
+
+    #[test]
+    fn test_audit_workspace_scope_admin_is_unrestricted() {
+        let scope = audit_workspace_scope(
+            crate::server::middleware::ApiKeyRole::Admin,
+            None,
+            Some("any"),
+            Locale::En,
+        );
+        assert_eq!(scope.unwrap(), None);
+    }
+
+    #[test]
+    fn test_audit_workspace_scope_user_is_limited_to_own_workspace() {
+        let ws = uuid::Uuid::new_v4();
+        let role = crate::server::middleware::ApiKeyRole::User;
+
+        let scope = audit_workspace_scope(role.clone(), Some(ws), None, Locale::En);
+        assert_eq!(scope.unwrap(), Some(ws.to_string()));
+
+        let own = ws.to_string();
+        let scope = audit_workspace_scope(role.clone(), Some(ws), Some(&own), Locale::En);
+        assert_eq!(scope.unwrap(), Some(own.clone()));
+
+        let (status, _) =
+            audit_workspace_scope(role, Some(ws), Some("other"), Locale::En).unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
     // ========== verify_workspace_id tests ==========
 
     #[test]
@@ -1314,7 +1744,8 @@ mod tests {
             )
             .await
             .unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
+        // No repository in tests, so the instance is not ready.
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
     }
 
     #[tokio::test]
@@ -1499,6 +1930,7 @@ mod tests {
             handlers,
             auth,
             config_service,
+            audit_logger: Arc::new(AuditLogger::new(100)),
         }
     }
 
@@ -1562,7 +1994,7 @@ mod tests {
     #[tokio::test]
     async fn test_handle_ready_returns_ready_flag_and_components() {
         let state = create_test_app_state();
-        let resp = handle_ready(State(state)).await;
+        let (_, resp) = handle_ready(State(state)).await;
         // ready/database/cache booleans are populated; message is a string.
         let _ = resp.ready;
         let _ = resp.database;
@@ -1570,6 +2002,60 @@ mod tests {
         assert!(resp.message.is_empty() || !resp.message.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_handle_ready_reports_components_and_policy_failures() {
+        let state = create_test_app_state();
+        let (status, resp) = handle_ready(State(state)).await;
+        // No repository in tests: the default policy fails on the database.
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(!resp.ready);
+        assert_eq!(resp.failed_components, vec!["database"]);
+        let names: Vec<&str> = resp.components.iter().map(|c| c.name.as_str()).collect();
+        assert!(names.starts_with(&["database", "cache", "algorithms"]));
+        assert!(names.contains(&"degradation"));
+        assert!(names.contains(&"circuit_breakers"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_ready_returns_ok_when_policy_passes() {
+        let handlers = Arc::try_unwrap(create_test_api_handlers())
+            .unwrap_or_else(|_| unreachable!("handlers are not shared yet"))
+            .with_health_config(crate::core::config::HealthConfig {
+                readiness_required: Vec::new(),
+                readiness_strict: Vec::new(),
+            });
+        let state = AppState {
+            handlers: Arc::new(handlers),
+            ..create_test_app_state()
+        };
+        let (status, resp) = handle_ready(State(state)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(resp.ready);
+        assert!(resp.failed_components.is_empty());
+    }
+
+    // ========== handle_live tests ==========
+
+    #[tokio::test]
+    async fn test_create_router_live_endpoint_responds() {
+        let handlers = create_test_api_handlers();
+        let auth = create_test_auth();
+        let rate_limiter = create_test_rate_limiter();
+        let audit_logger = create_test_audit_logger();
+
+        let router = create_router(handlers, auth, rate_limiter, audit_logger).await;
+        let resp = router
+            .oneshot(
+                sdforge::axum::http::Request::builder()
+                    .uri("/live")
+                    .body(sdforge::axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
     // ========== handle_metrics tests ==========
 
     #[tokio::test]
@@ -1593,6 +2079,184 @@ mod tests {
         assert!(!resp.message.is_empty() || resp.message.is_empty());
     }
 
+    // ========== handle_get_usage tests ==========
+
+    #[tokio::test]
+    async fn test_handle_get_usage_reports_recorded_generation() {
+        let state = create_test_app_state();
+        state
+            .handlers
+            .usage_tracker()
+            .record_generation("ws-usage", "orders", 5, true);
+        let params = UsageQueryParams {
+            window_secs: Some(60),
+            workspace: None,
+            limit: 10,
+        };
+        let resp = handle_get_usage(State(state), Extension(Locale::En), Query(params))
+            .await
+            .unwrap();
+        assert_eq!(resp.window_secs, 60);
+        assert_eq!(resp.top_workspaces[0].workspace, "ws-usage");
+        assert_eq!(resp.biz_tags[0].biz_tag, "orders");
+        assert_eq!(resp.biz_tags[0].window_generated, 5);
+    }
+
+    #[tokio::test]
+    async fn test_handle_get_usage_rejects_zero_limit() {
+        let state = create_test_app_state();
+        let params = UsageQueryParams {
+            window_secs: None,
+            workspace: None,
+            limit: 0,
+        };
+        let result = handle_get_usage(State(state), Extension(Locale::En), Query(params)).await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    // ========== ban handler tests ==========
+
+    fn create_test_app_state_with_bans(bans: Arc<crate::core::auth::BanManager>) -> AppState {
+        let handlers = Arc::try_unwrap(create_test_api_handlers())
+            .unwrap_or_else(|_| unreachable!("handlers are not shared yet"))
+            .with_ban_manager(bans);
+        AppState {
+            handlers: Arc::new(handlers),
+            ..create_test_app_state()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_list_bans_without_manager_returns_500() {
+        let state = create_test_app_state();
+        let params = BanListParams {
+            active: true,
+            limit: 10,
+        };
+        let result = handle_list_bans(State(state), Extension(Locale::En), Query(params)).await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[tokio::test]
+    async fn test_handle_lift_ban_records_caller_and_clears_active_list() {
+        use crate::core::config::BanConfig;
+        use crate::core::database::BanTargetKind;
+
+        let bans = Arc::new(crate::core::auth::BanManager::new(BanConfig {
+            failure_threshold: 1,
+            ..BanConfig::default()
+        }));
+        let ban = bans
+            .record_failure(BanTargetKind::Ip, "10.0.0.9")
+            .await
+            .unwrap();
+        let state = create_test_app_state_with_bans(bans);
+        let active = || BanListParams {
+            active: true,
+            limit: 10,
+        };
+
+        let listed = handle_list_bans(State(state.clone()), Extension(Locale::En), Query(active()))
+            .await
+            .unwrap();
+        assert_eq!(listed.bans.len(), 1);
+        assert_eq!(listed.bans[0].target_type, "ip");
+        assert!(listed.bans[0].active);
+
+        let caller = AuthenticatedKey {
+            key_id: "admin-key".to_string(),
+            role: crate::server::middleware::ApiKeyRole::Admin,
+            workspace_id: None,
+        };
+        let lifted = handle_lift_ban(
+            State(state.clone()),
+            Some(Extension(caller)),
+            Extension(Locale::En),
+            Path(ban.id.to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(lifted.lifted_by.as_deref(), Some("admin-key"));
+        assert!(!lifted.active);
+
+        let listed = handle_list_bans(State(state), Extension(Locale::En), Query(active()))
+            .await
+            .unwrap();
+        assert!(listed.bans.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_handle_lift_ban_rejects_invalid_id() {
+        let bans = Arc::new(crate::core::auth::BanManager::new(
+            crate::core::config::BanConfig::default(),
+        ));
+        let state = create_test_app_state_with_bans(bans);
+        let result = handle_lift_ban(
+            State(state),
+            None,
+            Extension(Locale::En),
+            Path("not-a-uuid".to_string()),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    // ========== handle_get_capacity tests ==========
+
+    #[tokio::test]
+    async fn test_handle_get_capacity_reports_segment_forecast() {
+        let state = create_test_app_state();
+        state
+            .handlers
+            .usage_tracker()
+            .record_segment_allocation("ws-cap", "orders", 2_000, 1_000);
+        let params = CapacityQueryParams {
+            workspace: Some("ws-cap".to_string()),
+        };
+        let resp = handle_get_capacity(State(state), Query(params)).await;
+        assert!(resp.enabled);
+        assert_eq!(resp.id_ceiling, i64::MAX as u64);
+        assert_eq!(resp.forecasts.len(), 1);
+        assert_eq!(resp.forecasts[0].biz_tag, "orders");
+        assert_eq!(resp.forecasts[0].current_max_id, 2_000);
+        assert_eq!(resp.forecasts[0].status, "ok");
+    }
+
+    // ========== degradation control tests ==========
+
+    #[tokio::test]
+    async fn test_handle_degrade_algorithm_unregistered_returns_not_found() {
+        let state = create_test_app_state();
+        let result = handle_degrade_algorithm(
+            State(state),
+            Extension(Locale::En),
+            Path("snowflake".to_string()),
+            Json(DegradationActionRequest::default()),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_handle_update_fallback_chain_unknown_algorithm_returns_bad_request() {
+        let state = create_test_app_state();
+        let result = handle_update_fallback_chain(
+            State(state),
+            Extension(Locale::En),
+            Json(UpdateFallbackChainRequest {
+                chain: vec!["not-an-algorithm".to_string()],
+                reason: None,
+            }),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
     // ========== handle_update_rate_limit tests ==========
 
     #[tokio::test]
@@ -1726,6 +2390,7 @@ mod tests {
             State(state),
             Extension(None),
             Extension(crate::server::middleware::ApiKeyRole::Admin),
+            Extension(ApiKeyScopes::unrestricted()),
             Extension(Locale::En),
             Json(make_generate_request()),
         )
@@ -1742,6 +2407,7 @@ mod tests {
             State(state),
             Extension(None),
             Extension(crate::server::middleware::ApiKeyRole::Anonymous),
+            Extension(ApiKeyScopes::unrestricted()),
             Extension(Locale::En),
             Json(make_generate_request()),
         )
@@ -1764,6 +2430,7 @@ mod tests {
             State(state),
             Extension(None),
             Extension(crate::server::middleware::ApiKeyRole::User),
+            Extension(ApiKeyScopes::unrestricted()),
             Extension(Locale::En),
             Json(req),
         )
@@ -1782,6 +2449,7 @@ mod tests {
             State(state),
             Extension(None),
             Extension(crate::server::middleware::ApiKeyRole::User),
+            Extension(ApiKeyScopes::unrestricted()),
             Extension(Locale::En),
             Json(make_generate_request()),
         )
@@ -1791,6 +2459,43 @@ mod tests {
         assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    #[tokio::test]
+    async fn test_handle_generate_scope_mismatch_returns_forbidden() {
+        // Scope check runs before the workspace lookup, so the missing
+        // repository is never reached.
+        let state = create_test_app_state();
+        let scopes = ApiKeyScopes::parse(&["generate:orders/*"]).unwrap();
+        let result = handle_generate(
+            State(state),
+            Extension(None),
+            Extension(crate::server::middleware::ApiKeyRole::User),
+            Extension(scopes),
+            Extension(Locale::En),
+            Json(make_generate_request()),
+        )
+        .await;
+        let (status, body) = result.unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert!(body.0.message.contains("generate:test-group/test-tag"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_generate_matching_scope_passes_scope_check() {
+        let state = create_test_app_state();
+        let scopes = ApiKeyScopes::parse(&["generate:test-group/*"]).unwrap();
+        let result = handle_generate(
+            State(state),
+            Extension(None),
+            Extension(crate::server::middleware::ApiKeyRole::User),
+            Extension(scopes),
+            Extension(Locale::En),
+            Json(make_generate_request()),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
     // ========== handle_batch_generate tests ==========
 
     fn make_batch_request() -> BatchGenerateRequest {
@@ -1810,6 +2515,7 @@ mod tests {
             State(state),
             Extension(None),
             Extension(crate::server::middleware::ApiKeyRole::Admin),
+            Extension(ApiKeyScopes::unrestricted()),
             Extension(Locale::En),
             Json(make_batch_request()),
         )
@@ -1826,6 +2532,7 @@ mod tests {
             State(state),
             Extension(None),
             Extension(crate::server::middleware::ApiKeyRole::Anonymous),
+            Extension(ApiKeyScopes::unrestricted()),
             Extension(Locale::En),
             Json(make_batch_request()),
         )
@@ -1849,6 +2556,7 @@ mod tests {
             State(state),
             Extension(None),
             Extension(crate::server::middleware::ApiKeyRole::User),
+            Extension(ApiKeyScopes::unrestricted()),
             Extension(Locale::En),
             Json(req),
         )
@@ -1865,6 +2573,7 @@ mod tests {
             State(state),
             Extension(None),
             Extension(crate::server::middleware::ApiKeyRole::User),
+            Extension(ApiKeyScopes::unrestricted()),
             Extension(Locale::En),
             Json(make_batch_request()),
         )
@@ -2258,6 +2967,44 @@ mod tests {
         assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    // ========== handle_update_workspace tests ==========
+
+    #[tokio::test]
+    async fn test_handle_update_workspace_invalid_max_groups_returns_bad_request() {
+        let state = create_test_app_state();
+        let req = UpdateWorkspaceRequest {
+            max_groups: Some(0), // below min=1
+            ..Default::default()
+        };
+        let result = handle_update_workspace(
+            State(state),
+            Extension(Locale::En),
+            Path("ws".to_string()),
+            Json(req),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_handle_update_workspace_unknown_status_returns_bad_request() {
+        let state = create_test_app_state();
+        let req = UpdateWorkspaceRequest {
+            status: Some("archived".to_string()),
+            ..Default::default()
+        };
+        let result = handle_update_workspace(
+            State(state),
+            Extension(Locale::En),
+            Path("ws".to_string()),
+            Json(req),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
     // ========== handle_list_workspaces tests ==========
 
     #[tokio::test]
@@ -2383,6 +3130,8 @@ mod tests {
         let state = create_test_app_state();
         let result = handle_regenerate_user_key(
             State(state),
+            Extension(None),
+            admin_role(),
             Extension(Locale::En),
             Path("any-name".to_string()),
         )
@@ -2438,6 +3187,45 @@ mod tests {
         assert!(status == StatusCode::INTERNAL_SERVER_ERROR || status == StatusCode::NOT_FOUND);
     }
 
+    fn admin_role() -> Extension<crate::server::middleware::ApiKeyRole> {
+        Extension(crate::server::middleware::ApiKeyRole::Admin)
+    }
+
+    fn workspace_admin_role() -> Extension<crate::server::middleware::ApiKeyRole> {
+        Extension(crate::server::middleware::ApiKeyRole::WorkspaceAdmin)
+    }
+
+    // ========== key_manager_workspace tests ==========
+
+    #[test]
+    fn test_key_manager_workspace_admin_is_unrestricted() {
+        let scope = key_manager_workspace(
+            crate::server::middleware::ApiKeyRole::Admin,
+            None,
+            Locale::En,
+        );
+        assert_eq!(scope.unwrap(), None);
+    }
+
+    #[test]
+    fn test_key_manager_workspace_workspace_admin_is_limited_to_own_workspace() {
+        let ws = uuid::Uuid::new_v4();
+        let scope = key_manager_workspace(
+            crate::server::middleware::ApiKeyRole::WorkspaceAdmin,
+            Some(ws),
+            Locale::En,
+        );
+        assert_eq!(scope.unwrap(), Some(ws));
+
+        let (status, _) = key_manager_workspace(
+            crate::server::middleware::ApiKeyRole::User,
+            Some(ws),
+            Locale::En,
+        )
+        .unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
     // ========== handle_create_api_key tests ==========
 
     #[tokio::test]
@@ -2450,8 +3238,18 @@ mod tests {
             role: Some("user".to_string()),
             rate_limit: None,
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
-        let result = handle_create_api_key(State(state), Extension(Locale::En), Json(req)).await;
+        let result = handle_create_api_key(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(ApiKeyScopes::unrestricted()),
+            Extension(Locale::En),
+            Json(req),
+        )
+        .await;
         assert!(result.is_err());
         let (status, _) = result.unwrap_err();
         assert_eq!(status, StatusCode::BAD_REQUEST);
@@ -2467,8 +3265,18 @@ mod tests {
             role: Some("user".to_string()),
             rate_limit: None,
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
-        let result = handle_create_api_key(State(state), Extension(Locale::En), Json(req)).await;
+        let result = handle_create_api_key(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(ApiKeyScopes::unrestricted()),
+            Extension(Locale::En),
+            Json(req),
+        )
+        .await;
         assert!(result.is_err());
         let (status, _) = result.unwrap_err();
         // workspace_id_required_response returns 400.
@@ -2485,8 +3293,18 @@ mod tests {
             role: Some("user".to_string()),
             rate_limit: None,
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
-        let result = handle_create_api_key(State(state), Extension(Locale::En), Json(req)).await;
+        let result = handle_create_api_key(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(ApiKeyScopes::unrestricted()),
+            Extension(Locale::En),
+            Json(req),
+        )
+        .await;
         assert!(result.is_err());
         let (status, _) = result.unwrap_err();
         // invalid_uuid_response returns 400.
@@ -2503,8 +3321,18 @@ mod tests {
             role: None, // defaults to user
             rate_limit: None,
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
-        let result = handle_create_api_key(State(state), Extension(Locale::En), Json(req)).await;
+        let result = handle_create_api_key(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(ApiKeyScopes::unrestricted()),
+            Extension(Locale::En),
+            Json(req),
+        )
+        .await;
         assert!(result.is_err());
         let (status, _) = result.unwrap_err();
         assert_eq!(status, StatusCode::BAD_REQUEST);
@@ -2520,8 +3348,18 @@ mod tests {
             role: Some("admin".to_string()),
             rate_limit: Some(1000),
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
-        let result = handle_create_api_key(State(state), Extension(Locale::En), Json(req)).await;
+        let result = handle_create_api_key(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(ApiKeyScopes::unrestricted()),
+            Extension(Locale::En),
+            Json(req),
+        )
+        .await;
         // Validation passes, workspace_id is None for admin, then
         // handlers.create_api_key runs. Without repository it returns Err.
         assert!(result.is_err());
@@ -2545,8 +3383,18 @@ mod tests {
             role: Some("user".to_string()),
             rate_limit: Some(1000),
             expires_at: None,
+            scopes: None,
+            request_signing: false,
         };
-        let result = handle_create_api_key(State(state), Extension(Locale::En), Json(req)).await;
+        let result = handle_create_api_key(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(ApiKeyScopes::unrestricted()),
+            Extension(Locale::En),
+            Json(req),
+        )
+        .await;
         // Validation passes, workspace_id parses, then handlers.create_api_key
         // runs. Without repository it returns Err.
         assert!(result.is_err());
@@ -2570,7 +3418,14 @@ mod tests {
             page: 1,
             page_size: 0, // below min=1
         };
-        let result = handle_list_api_keys(State(state), Extension(Locale::En), Query(params)).await;
+        let result = handle_list_api_keys(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(Locale::En),
+            Query(params),
+        )
+        .await;
         assert!(result.is_err());
         let (status, _) = result.unwrap_err();
         assert_eq!(status, StatusCode::BAD_REQUEST);
@@ -2584,7 +3439,14 @@ mod tests {
             page: 1,
             page_size: 20,
         };
-        let result = handle_list_api_keys(State(state), Extension(Locale::En), Query(params)).await;
+        let result = handle_list_api_keys(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(Locale::En),
+            Query(params),
+        )
+        .await;
         assert!(result.is_err());
         let (status, _) = result.unwrap_err();
         // Without repository, list_api_keys returns Err.
@@ -2599,7 +3461,14 @@ mod tests {
             page: 1,
             page_size: 20,
         };
-        let result = handle_list_api_keys(State(state), Extension(Locale::En), Query(params)).await;
+        let result = handle_list_api_keys(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(Locale::En),
+            Query(params),
+        )
+        .await;
         // Invalid workspace_id is logged and falls back to Uuid::nil().
         // The handler still calls list_api_keys which fails without a repo.
         assert!(result.is_err());
@@ -2615,7 +3484,14 @@ mod tests {
             page: 2,
             page_size: 50,
         };
-        let result = handle_list_api_keys(State(state), Extension(Locale::En), Query(params)).await;
+        let result = handle_list_api_keys(
+            State(state),
+            Extension(None),
+            admin_role(),
+            Extension(Locale::En),
+            Query(params),
+        )
+        .await;
         assert!(result.is_err());
         let (status, _) = result.unwrap_err();
         assert!(status == StatusCode::INTERNAL_SERVER_ERROR || status == StatusCode::NOT_FOUND);
@@ -2628,6 +3504,8 @@ mod tests {
         let state = create_test_app_state();
         let result = handle_revoke_api_key(
             State(state),
+            Extension(None),
+            admin_role(),
             Extension(Locale::En),
             Path("not-a-uuid".to_string()),
         )
@@ -2642,6 +3520,8 @@ mod tests {
         let state = create_test_app_state();
         let result = handle_revoke_api_key(
             State(state),
+            Extension(None),
+            admin_role(),
             Extension(Locale::En),
             Path(uuid::Uuid::new_v4().to_string()),
         )
@@ -2651,4 +3531,157 @@ mod tests {
         // Without repository, revoke_api_key returns Err.
         assert!(status == StatusCode::INTERNAL_SERVER_ERROR || status == StatusCode::NOT_FOUND);
     }
+
+    // ========== workspace admin self-service tests ==========
+
+    #[tokio::test]
+    async fn test_handle_create_api_key_workspace_admin_other_workspace_returns_forbidden() {
+        let state = create_test_app_state();
+        let req = CreateApiKeyRequest {
+            workspace_id: Some(uuid::Uuid::new_v4().to_string()),
+            name: "key".to_string(),
+            description: None,
+            role: Some("user".to_string()),
+            rate_limit: None,
+            expires_at: None,
+            scopes: None,
+            request_signing: false,
+        };
+        let result = handle_create_api_key(
+            State(state),
+            Extension(Some(uuid::Uuid::new_v4())),
+            workspace_admin_role(),
+            Extension(ApiKeyScopes::unrestricted()),
+            Extension(Locale::En),
+            Json(req),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_handle_create_api_key_workspace_admin_cannot_create_admin_key() {
+        for role in ["admin", "workspace_admin"] {
+            let state = create_test_app_state();
+            let req = CreateApiKeyRequest {
+                workspace_id: None,
+                name: "escalate".to_string(),
+                description: None,
+                role: Some(role.to_string()),
+                rate_limit: None,
+                expires_at: None,
+                scopes: None,
+                request_signing: false,
+            };
+            let result = handle_create_api_key(
+                State(state),
+                Extension(Some(uuid::Uuid::new_v4())),
+                workspace_admin_role(),
+                Extension(ApiKeyScopes::unrestricted()),
+                Extension(Locale::En),
+                Json(req),
+            )
+            .await;
+            let (status, _) = result.unwrap_err();
+            assert_eq!(status, StatusCode::FORBIDDEN, "role {role}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_create_api_key_workspace_admin_cannot_grant_unheld_scopes() {
+        let caller = ApiKeyScopes::parse(&["api_keys:write", "generate:orders/*"]).unwrap();
+        // 未指定作用域（不受限）、`*` 以及调用方未持有的作用域都被拒绝
+        for scopes in [
+            None,
+            Some(vec!["*".to_string()]),
+            Some(vec!["generate:payments/*".to_string()]),
+            Some(vec![
+                "generate:orders/*".to_string(),
+                "config:write".to_string(),
+            ]),
+        ] {
+            let state = create_test_app_state();
+            let req = CreateApiKeyRequest {
+                workspace_id: None,
+                name: "escalate".to_string(),
+                description: None,
+                role: Some("user".to_string()),
+                rate_limit: None,
+                expires_at: None,
+                scopes: scopes.clone(),
+                request_signing: false,
+            };
+            let result = handle_create_api_key(
+                State(state),
+                Extension(Some(uuid::Uuid::new_v4())),
+                workspace_admin_role(),
+                Extension(caller.clone()),
+                Extension(Locale::En),
+                Json(req),
+            )
+            .await;
+            let (status, _) = result.unwrap_err();
+            assert_eq!(status, StatusCode::FORBIDDEN, "scopes {scopes:?}");
+        }
+
+        // 调用方作用域的子集可以签发（测试状态没有仓储，止于仓储缺失）
+        let req = CreateApiKeyRequest {
+            workspace_id: None,
+            name: "svc".to_string(),
+            description: None,
+            role: Some("user".to_string()),
+            rate_limit: None,
+            expires_at: None,
+            scopes: Some(vec!["generate:orders/invoices".to_string()]),
+            request_signing: false,
+        };
+        let result = handle_create_api_key(
+            State(create_test_app_state()),
+            Extension(Some(uuid::Uuid::new_v4())),
+            workspace_admin_role(),
+            Extension(caller),
+            Extension(Locale::En),
+            Json(req),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_ne!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_handle_list_api_keys_workspace_admin_other_workspace_returns_forbidden() {
+        let state = create_test_app_state();
+        let params = PaginationParams {
+            workspace_id: Some(uuid::Uuid::new_v4().to_string()),
+            page: 1,
+            page_size: 20,
+        };
+        let result = handle_list_api_keys(
+            State(state),
+            Extension(Some(uuid::Uuid::new_v4())),
+            workspace_admin_role(),
+            Extension(Locale::En),
+            Query(params),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_handle_rotate_api_key_workspace_admin_without_repository_returns_error() {
+        let state = create_test_app_state();
+        let result = handle_rotate_api_key(
+            State(state),
+            Extension(Some(uuid::Uuid::new_v4())),
+            workspace_admin_role(),
+            Extension(Locale::En),
+            Path("nino_missing".to_string()),
+            Query(RotateApiKeyParams::default()),
+        )
+        .await;
+        let (status, _) = result.unwrap_err();
+        assert!(status == StatusCode::INTERNAL_SERVER_ERROR || status == StatusCode::NOT_FOUND);
+    }
 }